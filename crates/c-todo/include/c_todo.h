@@ -0,0 +1,95 @@
+#ifndef C_TODO_H
+#define C_TODO_H
+
+/* Generated by cbindgen from crates/c-todo. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Result of a C API call
+typedef enum TodoStatus {
+  TODO_STATUS_OK = 0,
+  TODO_STATUS_EMPTY_DESCRIPTION = 1,
+  TODO_STATUS_INVALID_STATE_TRANSITION = 2,
+  TODO_STATUS_TODO_NOT_FOUND = 3,
+  TODO_STATUS_REPOSITORY_ERROR = 4,
+  // An argument was null, not valid UTF-8, or not a known state name
+  TODO_STATUS_INVALID_ARGUMENT = 5,
+  // The call panicked; the service should not be used anymore
+  TODO_STATUS_PANIC = 6,
+  TODO_STATUS_QUOTA_EXCEEDED = 7,
+  TODO_STATUS_VALIDATION_ERROR = 8,
+} TodoStatus;
+
+// Opaque handle to the application handlers, sharing one in-memory repository
+typedef struct TodoService TodoService;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Creates a new service backed by an in-memory repository
+//
+// The returned handle must be released with `todo_service_free`.
+struct TodoService *todo_service_new(void);
+
+// Releases a service created by `todo_service_new`
+//
+// # Safety
+// `service` must be null or a pointer returned by `todo_service_new` that has not
+// been freed yet.
+void todo_service_free(struct TodoService *service);
+
+// Creates a todo; on success `out_json` receives the created todo and its events as
+// `{"todo": ..., "events": [...]}`
+//
+// # Safety
+// `service` must come from `todo_service_new`, `description` must be a NUL-terminated
+// string, and `out_json` must be null or valid for writes. A string written to
+// `out_json` must be released with `todo_string_free`.
+enum TodoStatus todo_service_add(const struct TodoService *service,
+                                 const char *description,
+                                 char **out_json);
+
+// Lists all todos; on success `out_json` receives the array of todos
+//
+// # Safety
+// `service` must come from `todo_service_new` and `out_json` must be null or valid for
+// writes. A string written to `out_json` must be released with `todo_string_free`.
+enum TodoStatus todo_service_list(const struct TodoService *service, char **out_json);
+
+// Changes the state of a todo (`"TODO"`, `"IN_PROGRESS"` or `"DONE"`); on success
+// `out_json` receives the changed todo and its events as `{"todo": ..., "events": [...]}`
+//
+// # Safety
+// `service` must come from `todo_service_new`, `id` and `state` must be NUL-terminated
+// strings, and `out_json` must be null or valid for writes. A string written to
+// `out_json` must be released with `todo_string_free`.
+enum TodoStatus todo_service_change_state(const struct TodoService *service,
+                                          const char *id,
+                                          const char *state,
+                                          char **out_json);
+
+// Deletes a todo; on success `out_json` receives `null`
+//
+// # Safety
+// `service` must come from `todo_service_new`, `id` must be a NUL-terminated string,
+// and `out_json` must be null or valid for writes. A string written to `out_json`
+// must be released with `todo_string_free`.
+enum TodoStatus todo_service_delete(const struct TodoService *service,
+                                    const char *id,
+                                    char **out_json);
+
+// Releases a string returned through an `out_json` parameter
+//
+// # Safety
+// `value` must be null or a string written by this library that has not been freed yet.
+void todo_string_free(char *value);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* C_TODO_H */
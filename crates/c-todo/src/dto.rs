@@ -0,0 +1,405 @@
+use serde::Serialize;
+use todo::{Attachment, Subtask, Todo, TodoError, TodoEvent, TodoState};
+
+/// JSON representation of a Todo
+#[derive(Serialize)]
+pub struct TodoDto {
+    pub id: String,
+    pub description: String,
+    pub state: &'static str,
+    pub created_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_at: Option<String>,
+    pub priority: &'static str,
+    pub tags: Vec<String>,
+    pub subtasks: Vec<SubtaskDto>,
+    pub blocked_by: Vec<String>,
+    pub attachments: Vec<AttachmentDto>,
+}
+
+impl From<&Todo> for TodoDto {
+    fn from(todo: &Todo) -> Self {
+        TodoDto {
+            id: todo.id.to_string(),
+            description: todo.description.to_string(),
+            state: state_name(todo.state),
+            created_at: todo.created_at.to_rfc3339(),
+            due_at: todo.due_at.map(|due_at| due_at.to_rfc3339()),
+            priority: todo.priority.name(),
+            tags: todo.tags.iter().map(ToString::to_string).collect(),
+            subtasks: todo.subtasks.iter().map(SubtaskDto::from).collect(),
+            blocked_by: todo.blocked_by.iter().map(ToString::to_string).collect(),
+            attachments: todo.attachments.iter().map(AttachmentDto::from).collect(),
+        }
+    }
+}
+
+/// JSON representation of one item of a todo's checklist
+#[derive(Serialize)]
+pub struct SubtaskDto {
+    pub id: String,
+    pub description: String,
+    pub done: bool,
+}
+
+impl From<&Subtask> for SubtaskDto {
+    fn from(subtask: &Subtask) -> Self {
+        SubtaskDto {
+            id: subtask.id.to_string(),
+            description: subtask.description.to_string(),
+            done: subtask.done,
+        }
+    }
+}
+
+/// JSON representation of the metadata of a file attached to a todo
+#[derive(Serialize)]
+pub struct AttachmentDto {
+    pub content_hash: String,
+    pub name: String,
+    pub media_type: String,
+    pub size: u64,
+}
+
+impl From<&Attachment> for AttachmentDto {
+    fn from(attachment: &Attachment) -> Self {
+        AttachmentDto {
+            content_hash: attachment.content_hash.to_string(),
+            name: attachment.name.to_string(),
+            media_type: attachment.media_type.to_string(),
+            size: attachment.size,
+        }
+    }
+}
+
+/// JSON representation of a TodoEvent, tagged with its `type`
+///
+/// Variants are named after their `type`, such as `TODO_CREATED`.
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+#[allow(clippy::enum_variant_names)]
+pub enum TodoEventDto {
+    TodoCreated {
+        id: String,
+        description: String,
+        created_at: String,
+    },
+    TodoStateChanged {
+        id: String,
+        from_state: &'static str,
+        to_state: &'static str,
+        changed_at: String,
+    },
+    TodoArchived {
+        id: String,
+        archived_at: String,
+    },
+    TodoStale {
+        id: String,
+        rule: String,
+        state: &'static str,
+        stale_at: String,
+    },
+    TodoDueDateChanged {
+        id: String,
+        due_at: Option<String>,
+        changed_at: String,
+    },
+    TodoPriorityChanged {
+        id: String,
+        from_priority: &'static str,
+        to_priority: &'static str,
+        changed_at: String,
+    },
+    TodoTagAdded {
+        id: String,
+        tag: String,
+        added_at: String,
+    },
+    TodoTagRemoved {
+        id: String,
+        tag: String,
+        removed_at: String,
+    },
+    TodoSubtaskAdded {
+        id: String,
+        subtask_id: String,
+        description: String,
+        added_at: String,
+    },
+    TodoSubtaskToggled {
+        id: String,
+        subtask_id: String,
+        done: bool,
+        toggled_at: String,
+    },
+    TodoSubtaskRemoved {
+        id: String,
+        subtask_id: String,
+        removed_at: String,
+    },
+    TodoDescriptionUpdated {
+        id: String,
+        old: String,
+        new: String,
+        updated_at: String,
+    },
+    TodoBlocked {
+        id: String,
+        blocker_id: String,
+        blocked_at: String,
+    },
+    TodoUnblocked {
+        id: String,
+        blocker_id: String,
+        unblocked_at: String,
+    },
+    TodoDeleted {
+        id: String,
+        deleted_at: String,
+    },
+    TodoRestored {
+        id: String,
+        restored_at: String,
+    },
+    TodoAttachmentAdded {
+        id: String,
+        content_hash: String,
+        name: String,
+        media_type: String,
+        size: u64,
+        added_at: String,
+    },
+    TodoAttachmentRemoved {
+        id: String,
+        content_hash: String,
+        removed_at: String,
+    },
+}
+
+impl From<TodoEvent> for TodoEventDto {
+    fn from(event: TodoEvent) -> Self {
+        match event {
+            TodoEvent::TodoCreated {
+                id,
+                description,
+                created_at,
+            } => TodoEventDto::TodoCreated {
+                id: id.to_string(),
+                description: description.to_string(),
+                created_at: created_at.to_rfc3339(),
+            },
+            TodoEvent::TodoStateChanged {
+                id,
+                from_state,
+                to_state,
+                changed_at,
+            } => TodoEventDto::TodoStateChanged {
+                id: id.to_string(),
+                from_state: state_name(from_state),
+                to_state: state_name(to_state),
+                changed_at: changed_at.to_rfc3339(),
+            },
+            TodoEvent::TodoArchived { id, archived_at } => TodoEventDto::TodoArchived {
+                id: id.to_string(),
+                archived_at: archived_at.to_rfc3339(),
+            },
+            TodoEvent::TodoStale {
+                id,
+                rule,
+                state,
+                stale_at,
+            } => TodoEventDto::TodoStale {
+                id: id.to_string(),
+                rule: rule.to_string(),
+                state: state_name(state),
+                stale_at: stale_at.to_rfc3339(),
+            },
+            TodoEvent::TodoDueDateChanged {
+                id,
+                due_at,
+                changed_at,
+            } => TodoEventDto::TodoDueDateChanged {
+                id: id.to_string(),
+                due_at: due_at.map(|due_at| due_at.to_rfc3339()),
+                changed_at: changed_at.to_rfc3339(),
+            },
+            TodoEvent::TodoPriorityChanged {
+                id,
+                from_priority,
+                to_priority,
+                changed_at,
+            } => TodoEventDto::TodoPriorityChanged {
+                id: id.to_string(),
+                from_priority: from_priority.name(),
+                to_priority: to_priority.name(),
+                changed_at: changed_at.to_rfc3339(),
+            },
+            TodoEvent::TodoTagAdded { id, tag, added_at } => TodoEventDto::TodoTagAdded {
+                id: id.to_string(),
+                tag: tag.to_string(),
+                added_at: added_at.to_rfc3339(),
+            },
+            TodoEvent::TodoTagRemoved {
+                id,
+                tag,
+                removed_at,
+            } => TodoEventDto::TodoTagRemoved {
+                id: id.to_string(),
+                tag: tag.to_string(),
+                removed_at: removed_at.to_rfc3339(),
+            },
+            TodoEvent::TodoSubtaskAdded {
+                id,
+                subtask_id,
+                description,
+                added_at,
+            } => TodoEventDto::TodoSubtaskAdded {
+                id: id.to_string(),
+                subtask_id: subtask_id.to_string(),
+                description: description.to_string(),
+                added_at: added_at.to_rfc3339(),
+            },
+            TodoEvent::TodoSubtaskToggled {
+                id,
+                subtask_id,
+                done,
+                toggled_at,
+            } => TodoEventDto::TodoSubtaskToggled {
+                id: id.to_string(),
+                subtask_id: subtask_id.to_string(),
+                done,
+                toggled_at: toggled_at.to_rfc3339(),
+            },
+            TodoEvent::TodoSubtaskRemoved {
+                id,
+                subtask_id,
+                removed_at,
+            } => TodoEventDto::TodoSubtaskRemoved {
+                id: id.to_string(),
+                subtask_id: subtask_id.to_string(),
+                removed_at: removed_at.to_rfc3339(),
+            },
+            TodoEvent::TodoDescriptionUpdated {
+                id,
+                old,
+                new,
+                updated_at,
+            } => TodoEventDto::TodoDescriptionUpdated {
+                id: id.to_string(),
+                old: old.to_string(),
+                new: new.to_string(),
+                updated_at: updated_at.to_rfc3339(),
+            },
+            TodoEvent::TodoBlocked {
+                id,
+                blocker_id,
+                blocked_at,
+            } => TodoEventDto::TodoBlocked {
+                id: id.to_string(),
+                blocker_id: blocker_id.to_string(),
+                blocked_at: blocked_at.to_rfc3339(),
+            },
+            TodoEvent::TodoUnblocked {
+                id,
+                blocker_id,
+                unblocked_at,
+            } => TodoEventDto::TodoUnblocked {
+                id: id.to_string(),
+                blocker_id: blocker_id.to_string(),
+                unblocked_at: unblocked_at.to_rfc3339(),
+            },
+            TodoEvent::TodoDeleted { id, deleted_at } => TodoEventDto::TodoDeleted {
+                id: id.to_string(),
+                deleted_at: deleted_at.to_rfc3339(),
+            },
+            TodoEvent::TodoRestored { id, restored_at } => TodoEventDto::TodoRestored {
+                id: id.to_string(),
+                restored_at: restored_at.to_rfc3339(),
+            },
+            TodoEvent::TodoAttachmentAdded {
+                id,
+                content_hash,
+                name,
+                media_type,
+                size,
+                added_at,
+            } => TodoEventDto::TodoAttachmentAdded {
+                id: id.to_string(),
+                content_hash: content_hash.to_string(),
+                name: name.to_string(),
+                media_type: media_type.to_string(),
+                size,
+                added_at: added_at.to_rfc3339(),
+            },
+            TodoEvent::TodoAttachmentRemoved {
+                id,
+                content_hash,
+                removed_at,
+            } => TodoEventDto::TodoAttachmentRemoved {
+                id: id.to_string(),
+                content_hash: content_hash.to_string(),
+                removed_at: removed_at.to_rfc3339(),
+            },
+        }
+    }
+}
+
+/// JSON representation of a saved change: the todo as stored and the events it emitted
+#[derive(Serialize)]
+pub struct TodoChangeDto {
+    pub todo: TodoDto,
+    pub events: Vec<TodoEventDto>,
+}
+
+impl From<(Todo, Vec<TodoEvent>)> for TodoChangeDto {
+    fn from((todo, events): (Todo, Vec<TodoEvent>)) -> Self {
+        TodoChangeDto {
+            todo: TodoDto::from(&todo),
+            events: events.into_iter().map(TodoEventDto::from).collect(),
+        }
+    }
+}
+
+/// JSON representation of a failed call
+#[derive(Serialize)]
+pub struct ErrorDto {
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl From<&TodoError> for ErrorDto {
+    fn from(err: &TodoError) -> Self {
+        let code = match err {
+            TodoError::EmptyDescription => "EMPTY_DESCRIPTION",
+            TodoError::InvalidStateTransition => "INVALID_STATE_TRANSITION",
+            TodoError::TodoNotFound => "TODO_NOT_FOUND",
+            TodoError::RepositoryError(_) => "REPOSITORY_ERROR",
+            TodoError::QuotaExceeded => "QUOTA_EXCEEDED",
+            TodoError::ValidationError(_) => "VALIDATION_ERROR",
+        };
+        ErrorDto {
+            code,
+            message: err.to_string(),
+        }
+    }
+}
+
+/// Name of a state in JSON payloads
+pub fn state_name(state: TodoState) -> &'static str {
+    match state {
+        TodoState::Todo => "TODO",
+        TodoState::InProgress => "IN_PROGRESS",
+        TodoState::Done => "DONE",
+    }
+}
+
+/// Parses a state name used in JSON payloads
+pub fn parse_state(name: &str) -> Option<TodoState> {
+    match name {
+        "TODO" => Some(TodoState::Todo),
+        "IN_PROGRESS" => Some(TodoState::InProgress),
+        "DONE" => Some(TodoState::Done),
+        _ => None,
+    }
+}
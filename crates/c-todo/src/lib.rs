@@ -0,0 +1,271 @@
+use futures::executor::block_on;
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::ptr;
+use std::sync::Arc;
+use todo::application::add_todo_handler::AddTodoHandler;
+use todo::application::change_todo_state_handler::ChangeTodoStateHandler;
+use todo::application::delete_todo_handler::DeleteTodoHandler;
+use todo::application::get_todos_handler::GetTodosHandler;
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::{TodoError, TodoRepository};
+
+mod dto;
+
+use dto::{ErrorDto, TodoChangeDto, TodoDto, parse_state};
+
+/// Result of a C API call
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TodoStatus {
+    Ok = 0,
+    EmptyDescription = 1,
+    InvalidStateTransition = 2,
+    TodoNotFound = 3,
+    RepositoryError = 4,
+    /// An argument was null, not valid UTF-8, or not a known state name
+    InvalidArgument = 5,
+    /// The call panicked; the service should not be used anymore
+    Panic = 6,
+    QuotaExceeded = 7,
+    ValidationError = 8,
+}
+
+/// Opaque handle to the application handlers, sharing one in-memory repository
+pub struct TodoService {
+    add_todo_handler: AddTodoHandler<Arc<dyn TodoRepository>>,
+    get_todos_handler: GetTodosHandler<Arc<dyn TodoRepository>>,
+    change_todo_state_handler: ChangeTodoStateHandler<Arc<dyn TodoRepository>>,
+    delete_todo_handler: DeleteTodoHandler<Arc<dyn TodoRepository>>,
+}
+
+/// Failure of a C API call before it is reported to the caller
+enum CallError {
+    Todo(TodoError),
+    InvalidArgument(String),
+}
+
+impl From<TodoError> for CallError {
+    fn from(err: TodoError) -> Self {
+        CallError::Todo(err)
+    }
+}
+
+impl CallError {
+    fn status(&self) -> TodoStatus {
+        match self {
+            CallError::Todo(TodoError::EmptyDescription) => TodoStatus::EmptyDescription,
+            CallError::Todo(TodoError::InvalidStateTransition) => {
+                TodoStatus::InvalidStateTransition
+            }
+            CallError::Todo(TodoError::TodoNotFound) => TodoStatus::TodoNotFound,
+            CallError::Todo(TodoError::RepositoryError(_)) => TodoStatus::RepositoryError,
+            CallError::Todo(TodoError::QuotaExceeded) => TodoStatus::QuotaExceeded,
+            CallError::Todo(TodoError::ValidationError(_)) => TodoStatus::ValidationError,
+            CallError::InvalidArgument(_) => TodoStatus::InvalidArgument,
+        }
+    }
+
+    fn to_dto(&self) -> ErrorDto {
+        match self {
+            CallError::Todo(err) => ErrorDto::from(err),
+            CallError::InvalidArgument(message) => ErrorDto {
+                code: "INVALID_ARGUMENT",
+                message: message.clone(),
+            },
+        }
+    }
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> Result<String, CallError> {
+    serde_json::to_string(value)
+        .map_err(|e| CallError::Todo(TodoError::RepositoryError(e.to_string())))
+}
+
+/// Runs `call` and reports its outcome
+///
+/// The JSON result, or an `{"code", "message"}` error object, is written to `out_json`
+/// when it is non-null. Panics are caught so they never unwind into C.
+fn run(out_json: *mut *mut c_char, call: impl FnOnce() -> Result<String, CallError>) -> TodoStatus {
+    let (status, json) = match catch_unwind(AssertUnwindSafe(call)) {
+        Ok(Ok(json)) => (TodoStatus::Ok, json),
+        Ok(Err(err)) => (err.status(), to_json(&err.to_dto()).unwrap_or_default()),
+        Err(_) => {
+            let err = ErrorDto {
+                code: "PANIC",
+                message: "todo call panicked".to_string(),
+            };
+            (TodoStatus::Panic, to_json(&err).unwrap_or_default())
+        }
+    };
+
+    if !out_json.is_null() {
+        let json = CString::new(json)
+            .map(CString::into_raw)
+            .unwrap_or(ptr::null_mut());
+        // SAFETY: the caller guarantees a non-null `out_json` is valid for writes
+        unsafe { *out_json = json };
+    }
+    status
+}
+
+/// Borrows the service behind a handle passed in by C
+///
+/// # Safety
+/// `service` must be null or a live pointer returned by `todo_service_new`.
+unsafe fn service_ref<'a>(service: *const TodoService) -> Result<&'a TodoService, CallError> {
+    // SAFETY: upheld by the caller
+    unsafe { service.as_ref() }
+        .ok_or_else(|| CallError::InvalidArgument("service must not be null".to_string()))
+}
+
+/// Borrows a C string argument as UTF-8
+///
+/// # Safety
+/// `value` must be null or point to a NUL-terminated string valid for the call.
+unsafe fn str_arg<'a>(value: *const c_char, name: &str) -> Result<&'a str, CallError> {
+    if value.is_null() {
+        return Err(CallError::InvalidArgument(format!(
+            "{} must not be null",
+            name
+        )));
+    }
+    // SAFETY: upheld by the caller
+    unsafe { CStr::from_ptr(value) }
+        .to_str()
+        .map_err(|_| CallError::InvalidArgument(format!("{} must be valid UTF-8", name)))
+}
+
+/// Creates a new service backed by an in-memory repository
+///
+/// The returned handle must be released with `todo_service_free`.
+#[unsafe(no_mangle)]
+pub extern "C" fn todo_service_new() -> *mut TodoService {
+    let repository: Arc<dyn TodoRepository> = Arc::new(InMemoryTodoRepository::new());
+    let service = TodoService {
+        add_todo_handler: AddTodoHandler::new(repository.clone()),
+        get_todos_handler: GetTodosHandler::new(repository.clone()),
+        change_todo_state_handler: ChangeTodoStateHandler::new(repository.clone()),
+        delete_todo_handler: DeleteTodoHandler::new(repository),
+    };
+    Box::into_raw(Box::new(service))
+}
+
+/// Releases a service created by `todo_service_new`
+///
+/// # Safety
+/// `service` must be null or a pointer returned by `todo_service_new` that has not
+/// been freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn todo_service_free(service: *mut TodoService) {
+    if !service.is_null() {
+        // SAFETY: upheld by the caller
+        drop(unsafe { Box::from_raw(service) });
+    }
+}
+
+/// Creates a todo; on success `out_json` receives the created todo and its events as
+/// `{"todo": ..., "events": [...]}`
+///
+/// # Safety
+/// `service` must come from `todo_service_new`, `description` must be a NUL-terminated
+/// string, and `out_json` must be null or valid for writes. A string written to
+/// `out_json` must be released with `todo_string_free`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn todo_service_add(
+    service: *const TodoService,
+    description: *const c_char,
+    out_json: *mut *mut c_char,
+) -> TodoStatus {
+    run(out_json, || {
+        // SAFETY: upheld by the caller
+        let (service, description) =
+            unsafe { (service_ref(service)?, str_arg(description, "description")?) };
+        let change = block_on(service.add_todo_handler.new_todo(description.to_string()))?;
+        to_json(&TodoChangeDto::from(change))
+    })
+}
+
+/// Lists all todos; on success `out_json` receives the array of todos
+///
+/// # Safety
+/// `service` must come from `todo_service_new` and `out_json` must be null or valid for
+/// writes. A string written to `out_json` must be released with `todo_string_free`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn todo_service_list(
+    service: *const TodoService,
+    out_json: *mut *mut c_char,
+) -> TodoStatus {
+    run(out_json, || {
+        // SAFETY: upheld by the caller
+        let service = unsafe { service_ref(service)? };
+        let todos = block_on(service.get_todos_handler.get_todos())?;
+        to_json(&todos.iter().map(TodoDto::from).collect::<Vec<_>>())
+    })
+}
+
+/// Changes the state of a todo (`"TODO"`, `"IN_PROGRESS"` or `"DONE"`); on success
+/// `out_json` receives the changed todo and its events as `{"todo": ..., "events": [...]}`
+///
+/// # Safety
+/// `service` must come from `todo_service_new`, `id` and `state` must be NUL-terminated
+/// strings, and `out_json` must be null or valid for writes. A string written to
+/// `out_json` must be released with `todo_string_free`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn todo_service_change_state(
+    service: *const TodoService,
+    id: *const c_char,
+    state: *const c_char,
+    out_json: *mut *mut c_char,
+) -> TodoStatus {
+    run(out_json, || {
+        // SAFETY: upheld by the caller
+        let (service, id, state) = unsafe {
+            (
+                service_ref(service)?,
+                str_arg(id, "id")?,
+                str_arg(state, "state")?,
+            )
+        };
+        let new_state = parse_state(state)
+            .ok_or_else(|| CallError::InvalidArgument(format!("unknown todo state: {}", state)))?;
+        let change = block_on(
+            service
+                .change_todo_state_handler
+                .change_state(id.to_string(), new_state),
+        )?;
+        to_json(&TodoChangeDto::from(change))
+    })
+}
+
+/// Deletes a todo; on success `out_json` receives `null`
+///
+/// # Safety
+/// `service` must come from `todo_service_new`, `id` must be a NUL-terminated string,
+/// and `out_json` must be null or valid for writes. A string written to `out_json`
+/// must be released with `todo_string_free`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn todo_service_delete(
+    service: *const TodoService,
+    id: *const c_char,
+    out_json: *mut *mut c_char,
+) -> TodoStatus {
+    run(out_json, || {
+        // SAFETY: upheld by the caller
+        let (service, id) = unsafe { (service_ref(service)?, str_arg(id, "id")?) };
+        block_on(service.delete_todo_handler.delete_todo(id.to_string()))?;
+        Ok("null".to_string())
+    })
+}
+
+/// Releases a string returned through an `out_json` parameter
+///
+/// # Safety
+/// `value` must be null or a string written by this library that has not been freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn todo_string_free(value: *mut c_char) {
+    if !value.is_null() {
+        // SAFETY: upheld by the caller
+        drop(unsafe { CString::from_raw(value) });
+    }
+}
@@ -0,0 +1,93 @@
+use c_todo::{
+    TodoStatus, todo_service_add, todo_service_change_state, todo_service_delete,
+    todo_service_free, todo_service_list, todo_service_new, todo_string_free,
+};
+use serde_json::Value;
+use std::ffi::{CStr, CString, c_char};
+use std::ptr;
+
+fn take_json(json: *mut c_char) -> Value {
+    let value = serde_json::from_str(unsafe { CStr::from_ptr(json) }.to_str().unwrap()).unwrap();
+    unsafe { todo_string_free(json) };
+    value
+}
+
+#[test]
+fn test_add_and_list_todos() {
+    // Arrange
+    let service = todo_service_new();
+    let description = CString::new("Test todo").unwrap();
+    let mut json = ptr::null_mut();
+
+    // Act
+    let status = unsafe { todo_service_add(service, description.as_ptr(), &mut json) };
+    let created = take_json(json);
+    let list_status = unsafe { todo_service_list(service, &mut json) };
+    let todos = take_json(json);
+
+    // Assert
+    assert_eq!(status, TodoStatus::Ok);
+    assert_eq!(created["events"][0]["type"], "TODO_CREATED");
+    assert_eq!(list_status, TodoStatus::Ok);
+    assert_eq!(todos[0], created["todo"]);
+    assert_eq!(todos[0]["id"], created["events"][0]["id"]);
+    assert_eq!(todos[0]["description"], "Test todo");
+    assert_eq!(todos[0]["state"], "TODO");
+
+    unsafe { todo_service_free(service) };
+}
+
+#[test]
+fn test_change_state_and_delete_todo() {
+    // Arrange
+    let service = todo_service_new();
+    let description = CString::new("Test todo").unwrap();
+    let mut json = ptr::null_mut();
+    unsafe { todo_service_add(service, description.as_ptr(), &mut json) };
+    let id = CString::new(take_json(json)["todo"]["id"].as_str().unwrap()).unwrap();
+    let state = CString::new("IN_PROGRESS").unwrap();
+
+    // Act
+    let status =
+        unsafe { todo_service_change_state(service, id.as_ptr(), state.as_ptr(), &mut json) };
+    let changed = take_json(json);
+    let delete_status = unsafe { todo_service_delete(service, id.as_ptr(), ptr::null_mut()) };
+    unsafe { todo_service_list(service, &mut json) };
+
+    // Assert
+    assert_eq!(status, TodoStatus::Ok);
+    assert_eq!(changed["todo"]["state"], "IN_PROGRESS");
+    assert_eq!(changed["events"][0]["type"], "TODO_STATE_CHANGED");
+    assert_eq!(changed["events"][0]["from_state"], "TODO");
+    assert_eq!(changed["events"][0]["to_state"], "IN_PROGRESS");
+    assert_eq!(delete_status, TodoStatus::Ok);
+    assert_eq!(take_json(json), serde_json::json!([]));
+
+    unsafe { todo_service_free(service) };
+}
+
+#[test]
+fn test_errors_are_reported_as_status_and_json() {
+    // Arrange
+    let service = todo_service_new();
+    let empty = CString::new("").unwrap();
+    let id = CString::new("non-existent-id").unwrap();
+    let state = CString::new("DONE").unwrap();
+    let mut json = ptr::null_mut();
+
+    // Act & Assert
+    let status = unsafe { todo_service_add(service, empty.as_ptr(), &mut json) };
+    assert_eq!(status, TodoStatus::EmptyDescription);
+    assert_eq!(take_json(json)["code"], "EMPTY_DESCRIPTION");
+
+    let status =
+        unsafe { todo_service_change_state(service, id.as_ptr(), state.as_ptr(), &mut json) };
+    assert_eq!(status, TodoStatus::TodoNotFound);
+    assert_eq!(take_json(json)["code"], "TODO_NOT_FOUND");
+
+    let status = unsafe { todo_service_add(service, ptr::null(), &mut json) };
+    assert_eq!(status, TodoStatus::InvalidArgument);
+    assert_eq!(take_json(json)["code"], "INVALID_ARGUMENT");
+
+    unsafe { todo_service_free(service) };
+}
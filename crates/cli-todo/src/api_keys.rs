@@ -0,0 +1,126 @@
+use clap::{Subcommand, ValueEnum};
+use futures::executor::block_on;
+use serde::Serialize;
+use std::sync::Arc;
+use todo::IdGenerator;
+use todo::application::issue_api_key_handler::IssueApiKeyHandler;
+use todo::application::revoke_api_key_handler::RevokeApiKeyHandler;
+use todo::domain::api_key::{ApiKey, ApiKeyRepository, ApiKeyScope};
+
+use crate::output::print_json;
+
+#[derive(Subcommand)]
+pub enum ApiKeyCommand {
+    /// Issue a key and print its token, which is shown only this once
+    Issue {
+        /// What the key is for, such as the client it is issued to
+        #[arg(required = true)]
+        name: Vec<String>,
+        #[arg(long, value_enum, default_value_t = ScopeArg::ReadOnly)]
+        scope: ScopeArg,
+    },
+    /// Stop accepting a key
+    Revoke { id: String },
+    /// List keys, oldest first
+    List,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ScopeArg {
+    ReadOnly,
+    ReadWrite,
+}
+
+impl From<ScopeArg> for ApiKeyScope {
+    fn from(scope: ScopeArg) -> Self {
+        match scope {
+            ScopeArg::ReadOnly => ApiKeyScope::ReadOnly,
+            ScopeArg::ReadWrite => ApiKeyScope::ReadWrite,
+        }
+    }
+}
+
+/// JSON representation of an ApiKey printed by `--json`; never includes the secret hash
+#[derive(Serialize)]
+struct ApiKeyView {
+    id: String,
+    name: String,
+    scope: &'static str,
+    created_at: String,
+    revoked_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    token: Option<String>,
+}
+
+impl From<&ApiKey> for ApiKeyView {
+    fn from(key: &ApiKey) -> Self {
+        ApiKeyView {
+            id: key.id.to_string(),
+            name: key.name.to_string(),
+            scope: key.scope.as_str(),
+            created_at: key.created_at.to_rfc3339(),
+            revoked_at: key.revoked_at.map(|at| at.to_rfc3339()),
+            token: None,
+        }
+    }
+}
+
+/// Runs an `api-key` command against the keys in `repository`
+pub fn run(
+    command: ApiKeyCommand,
+    repository: Arc<dyn ApiKeyRepository>,
+    ids: Arc<dyn IdGenerator>,
+    json: bool,
+) -> Result<(), String> {
+    match command {
+        ApiKeyCommand::Issue { name, scope } => {
+            let handler = IssueApiKeyHandler::new(repository).with_id_generator(ids);
+            let issued =
+                block_on(handler.issue(name.join(" "), scope.into())).map_err(|e| e.to_string())?;
+            if json {
+                let view = ApiKeyView {
+                    token: Some(issued.token),
+                    ..ApiKeyView::from(&issued.key)
+                };
+                return print_json(&view);
+            }
+            println!(
+                "Issued {} ({}): {}",
+                issued.key.id,
+                issued.key.scope.as_str(),
+                issued.key.name
+            );
+            println!("{}", issued.token);
+            eprintln!("Store the token now; it cannot be shown again.");
+            Ok(())
+        }
+        ApiKeyCommand::Revoke { id } => {
+            let handler = RevokeApiKeyHandler::new(repository);
+            let key = block_on(handler.revoke(id)).map_err(|e| e.to_string())?;
+            if json {
+                return print_json(&ApiKeyView::from(&key));
+            }
+            println!("Revoked {}: {}", key.id, key.name);
+            Ok(())
+        }
+        ApiKeyCommand::List => {
+            let mut keys = block_on(repository.find_all()).map_err(|e| e.to_string())?;
+            keys.sort_by_key(|key| key.created_at);
+            if json {
+                let views: Vec<ApiKeyView> = keys.iter().map(ApiKeyView::from).collect();
+                return print_json(&views);
+            }
+            for key in &keys {
+                let revoked = if key.is_revoked() { "  (revoked)" } else { "" };
+                println!(
+                    "{}  [{}]  {}{}",
+                    key.id,
+                    key.scope.as_str(),
+                    key.name,
+                    revoked
+                );
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use clap::Subcommand;
+use futures::executor::block_on;
+use serde::Serialize;
+use std::sync::Arc;
+use todo::domain::escalation::{EscalationRule, EscalationRuleRepository};
+
+use crate::StateArg;
+use crate::output::{print_json, state_name};
+
+#[derive(Subcommand)]
+pub enum EscalationCommand {
+    /// Flag the todos left in a state for more than some days as stale, replacing the rule
+    /// with the same id
+    Add {
+        /// Name of the rule, carried by the TODO_STALE events
+        id: String,
+        #[arg(value_enum)]
+        state: StateArg,
+        /// Days a todo may stay in the state
+        #[arg(long)]
+        after_days: u32,
+    },
+    /// Remove a rule
+    Remove { id: String },
+    /// List the rules
+    List,
+}
+
+/// JSON representation of an EscalationRule printed by `--json`
+#[derive(Serialize)]
+struct RuleView {
+    id: String,
+    state: &'static str,
+    after_days: u32,
+}
+
+impl From<&EscalationRule> for RuleView {
+    fn from(rule: &EscalationRule) -> Self {
+        RuleView {
+            id: rule.id.to_string(),
+            state: state_name(rule.state),
+            after_days: rule.after_days,
+        }
+    }
+}
+
+/// Runs an `escalation` command against the rules in `repository`
+pub fn run(
+    command: EscalationCommand,
+    repository: Arc<dyn EscalationRuleRepository>,
+    json: bool,
+) -> Result<(), String> {
+    match command {
+        EscalationCommand::Add {
+            id,
+            state,
+            after_days,
+        } => {
+            let rule = EscalationRule::new(id, state.into(), after_days)?;
+            block_on(repository.save(&rule)).map_err(|e| e.to_string())?;
+            if json {
+                return print_json(&RuleView::from(&rule));
+            }
+            println!(
+                "Added {}: todos {} for more than {} days are stale",
+                rule.id,
+                state_name(rule.state),
+                rule.after_days
+            );
+            Ok(())
+        }
+        EscalationCommand::Remove { id } => {
+            if !block_on(repository.remove(&id)).map_err(|e| e.to_string())? {
+                return Err(format!("no escalation rule {}", id));
+            }
+            if json {
+                return print_json(&serde_json::json!({ "id": id }));
+            }
+            println!("Removed {}", id);
+            Ok(())
+        }
+        EscalationCommand::List => {
+            let rules = block_on(repository.find_all()).map_err(|e| e.to_string())?;
+            if json {
+                let views: Vec<RuleView> = rules.iter().map(RuleView::from).collect();
+                return print_json(&views);
+            }
+            for rule in &rules {
+                println!(
+                    "{}  [{} > {} days]",
+                    rule.id,
+                    state_name(rule.state),
+                    rule.after_days
+                );
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,737 @@
+mod api_keys;
+mod escalations;
+mod output;
+mod roles;
+
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use futures::executor::block_on;
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Arc;
+use todo::application::access_control::DEFAULT_LIST;
+use todo::application::add_todo_handler::AddTodoHandler;
+use todo::application::change_todo_state_handler::ChangeTodoStateHandler;
+use todo::application::delete_todo_handler::DeleteTodoHandler;
+use todo::application::export_todos_handler::ExportTodosHandler;
+use todo::application::get_todos_handler::GetTodosHandler;
+use todo::application::import_todos_handler::ImportTodosHandler;
+use todo::application::migration::MigrateDataHandler;
+use todo::application::quota::Quota;
+use todo::application::replay::{ReplayEventsHandler, TodoProjection};
+use todo::application::restore_todo_handler::RestoreTodoHandler;
+use todo::domain::import::ImportPolicy;
+use todo::domain::trash::TrashRepository;
+use todo::infrastructure::config::TodoConfig;
+use todo::infrastructure::event_sinks::EventLog;
+use todo::infrastructure::event_stores::FileEventStore;
+use todo::infrastructure::repositories::api_key::FileApiKeyRepository;
+use todo::infrastructure::repositories::escalation::FileEscalationRuleRepository;
+use todo::infrastructure::repositories::import_mapping::FileImportMappingRepository;
+use todo::infrastructure::repositories::membership::FileMembershipRepository;
+use todo::infrastructure::repositories::todo::{FileTodoRepository, TodoBackend};
+use todo::infrastructure::repositories::trash::FileTrashRepository;
+use todo::infrastructure::serialization::SerializationError;
+use todo::infrastructure::serialization::csv::{CsvColumn, CsvOptions};
+use todo::infrastructure::serialization::encrypted;
+use todo::{
+    EventStore, IdGenerator, TenantId, Todo, TodoEvent, TodoFilter, TodoRepository, TodoState,
+};
+
+use api_keys::ApiKeyCommand;
+use escalations::EscalationCommand;
+use output::{TodoView, TrashedView, print_json, print_todo, print_trashed, short_id};
+use roles::RoleCommand;
+
+/// Manage todos from the command line
+#[derive(Parser)]
+#[command(name = "hk-todo", version)]
+struct Cli {
+    /// JSON file the todos are stored in
+    #[arg(long, global = true, env = "HK_TODO_FILE")]
+    file: Option<PathBuf>,
+
+    /// Print machine-readable JSON instead of text
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Add a new todo
+    Add {
+        #[arg(required = true)]
+        description: Vec<String>,
+    },
+    /// List todos, oldest first
+    List {
+        #[arg(long, value_enum)]
+        state: Option<StateArg>,
+    },
+    /// Move a todo to IN_PROGRESS
+    Start { id: String },
+    /// Move a todo to DONE
+    Done { id: String },
+    /// Delete a todo, into the trash when TODO_TRASH_FILE is set
+    Rm { id: String },
+    /// List the todos in the trash, most recently deleted first
+    Trash,
+    /// Move a todo from the trash back to the list
+    Restore { id: String },
+    /// List todos matching a search query, such as `state:in_progress "quarterly report"`
+    ///
+    /// Bare words and quoted phrases must appear in the description, ignoring case;
+    /// `state:<state>` and `created<YYYY-MM-DD` (also `<=`, `>`, `>=`, `:`) compare fields,
+    /// and a leading `-` negates a term.
+    Search {
+        #[arg(required = true, allow_hyphen_values = true)]
+        query: Vec<String>,
+    },
+    /// Write every todo as a versioned JSON document or as CSV, to stdout or to a file
+    Export {
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+        #[command(flatten)]
+        format: FormatArgs,
+        /// Encrypt the document with the passphrase in HK_TODO_PASSPHRASE
+        #[arg(long)]
+        encrypt: bool,
+    },
+    /// Add the todos of an exported document, skipping ids that already exist
+    ///
+    /// Encrypted documents are decrypted with the passphrase in HK_TODO_PASSPHRASE, and
+    /// refused if they were altered.
+    Import {
+        /// Document to read, or `-` for stdin
+        path: PathBuf,
+        #[command(flatten)]
+        format: FormatArgs,
+        /// What to do with items imported before: skip, update or merge
+        #[arg(long, default_value = "skip", value_parser = ImportPolicy::parse)]
+        on_existing: ImportPolicy,
+        /// JSON file linking imported items to their todos, so later imports recognize them
+        #[arg(long, env = "HK_TODO_IMPORT_MAPPINGS")]
+        mappings: Option<PathBuf>,
+    },
+    /// Rebuild the todo file from the events of the TODO_EVENT_LOG file
+    ///
+    /// Adds the todos missing from the file and applies the state changes it does not show,
+    /// so running it again over the same events changes nothing.
+    Replay {
+        /// First time replayed, as YYYY-MM-DD or RFC 3339; the first event by default
+        #[arg(long, value_parser = parse_time)]
+        from: Option<DateTime<Utc>>,
+        /// Time replaying stops before, as YYYY-MM-DD or RFC 3339; after the last event by
+        /// default
+        #[arg(long, value_parser = parse_time)]
+        to: Option<DateTime<Utc>>,
+    },
+    /// Copy every todo to another backend, and the events of TODO_EVENT_LOG with --event-log
+    ///
+    /// Todos and events the target already holds are skipped, so running it again finishes
+    /// an interrupted migration. The target is checked to hold everything afterwards.
+    Migrate {
+        /// Backend to copy to, such as `file:<path>`
+        #[arg(value_parser = TodoBackend::parse)]
+        target: TodoBackend,
+        /// JSON lines file to copy the events of TODO_EVENT_LOG to
+        #[arg(long)]
+        event_log: Option<PathBuf>,
+        /// Todos written to the target at once
+        #[arg(long, default_value_t = 500)]
+        batch_size: usize,
+    },
+    /// Manage the API keys of the servers, stored in the file set by TODO_API_KEY_FILE
+    ApiKey {
+        #[command(subcommand)]
+        command: ApiKeyCommand,
+    },
+    /// Manage the roles callers have on todo lists, stored in the file set by TODO_ROLES_FILE
+    Role {
+        #[command(subcommand)]
+        command: RoleCommand,
+    },
+    /// Manage the rules flagging todos left in a state for too long, stored in the file set
+    /// by TODO_ESCALATION_RULES_FILE
+    Escalation {
+        #[command(subcommand)]
+        command: EscalationCommand,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum StateArg {
+    Todo,
+    InProgress,
+    Done,
+}
+
+/// Document format of `export` and `import`
+#[derive(Args)]
+struct FormatArgs {
+    #[arg(long, value_enum, default_value_t = FormatArg::Json)]
+    format: FormatArg,
+
+    /// CSV columns to export, in order [default: id,description,state,created_at]
+    #[arg(long, value_delimiter = ',', value_parser = parse_column)]
+    columns: Vec<CsvColumn>,
+
+    /// CSV header of a column, such as description=Task; repeat for several columns
+    #[arg(long = "header", value_name = "COLUMN=NAME", value_parser = parse_header)]
+    headers: Vec<(CsvColumn, String)>,
+
+    /// strftime format of created_at in CSV, such as %Y-%m-%d [default: RFC3339]
+    #[arg(long)]
+    date_format: Option<String>,
+}
+
+impl FormatArgs {
+    fn csv_options(&self) -> CsvOptions {
+        let mut options = CsvOptions {
+            date_format: self.date_format.clone(),
+            ..CsvOptions::default()
+        };
+        if !self.columns.is_empty() {
+            options.columns = self
+                .columns
+                .iter()
+                .map(|column| (*column, column.default_header().to_string()))
+                .collect();
+        }
+        for (column, name) in &self.headers {
+            for (configured, header) in options.columns.iter_mut() {
+                if configured == column {
+                    *header = name.clone();
+                }
+            }
+        }
+        options
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum FormatArg {
+    Json,
+    Csv,
+    /// Taskwarrior's `task export` and `task import` JSON
+    Taskwarrior,
+    /// Org headlines; export only
+    Org,
+    /// Markdown task list
+    Markdown,
+    /// One JSON todo per line, unsorted; export only
+    Ndjson,
+}
+
+fn parse_column(name: &str) -> Result<CsvColumn, String> {
+    CsvColumn::parse(name).ok_or_else(|| {
+        format!(
+            "unknown column {}, expected id, description, state or created_at",
+            name
+        )
+    })
+}
+
+fn parse_header(mapping: &str) -> Result<(CsvColumn, String), String> {
+    let (column, name) = mapping
+        .split_once('=')
+        .ok_or_else(|| format!("expected COLUMN=NAME, found {}", mapping))?;
+    Ok((parse_column(column)?, name.to_string()))
+}
+
+/// Parses `YYYY-MM-DD`, as midnight UTC, or an RFC 3339 time
+fn parse_time(value: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(day) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(day.and_time(NaiveTime::MIN).and_utc());
+    }
+    DateTime::parse_from_rfc3339(value)
+        .map(|time| time.with_timezone(&Utc))
+        .map_err(|_| format!("expected YYYY-MM-DD or an RFC 3339 time, found {}", value))
+}
+
+impl From<StateArg> for TodoState {
+    fn from(state: StateArg) -> Self {
+        match state {
+            StateArg::Todo => TodoState::Todo,
+            StateArg::InProgress => TodoState::InProgress,
+            StateArg::Done => TodoState::Done,
+        }
+    }
+}
+
+/// Application handlers over the todo file
+struct App {
+    add_todo_handler: AddTodoHandler<Arc<dyn TodoRepository>>,
+    get_todos_handler: GetTodosHandler<Arc<dyn TodoRepository>>,
+    change_todo_state_handler: ChangeTodoStateHandler<Arc<dyn TodoRepository>>,
+    delete_todo_handler: DeleteTodoHandler<Arc<dyn TodoRepository>>,
+    export_todos_handler: ExportTodosHandler<Arc<dyn TodoRepository>>,
+    restore_todo_handler: Option<RestoreTodoHandler<Arc<dyn TodoRepository>>>,
+    replay_events_handler: Option<ReplayEventsHandler>,
+    event_store: Option<Arc<dyn EventStore>>,
+    repository: Arc<dyn TodoRepository>,
+    json: bool,
+}
+
+impl App {
+    fn new(repository: Arc<dyn TodoRepository>, ids: Arc<dyn IdGenerator>, json: bool) -> Self {
+        App {
+            add_todo_handler: AddTodoHandler::new(repository.clone()).with_id_generator(ids),
+            get_todos_handler: GetTodosHandler::new(repository.clone()),
+            change_todo_state_handler: ChangeTodoStateHandler::new(repository.clone()),
+            delete_todo_handler: DeleteTodoHandler::new(repository.clone()),
+            export_todos_handler: ExportTodosHandler::new(repository.clone()),
+            restore_todo_handler: None,
+            replay_events_handler: None,
+            event_store: None,
+            repository,
+            json,
+        }
+    }
+
+    /// Moves removed todos to `trash`, from which `restore` brings them back
+    fn with_trash(self, trash: Arc<dyn TrashRepository>) -> Self {
+        App {
+            delete_todo_handler: self.delete_todo_handler.with_trash(trash.clone()),
+            restore_todo_handler: Some(RestoreTodoHandler::new(self.repository.clone(), trash)),
+            ..self
+        }
+    }
+
+    /// Refuses to add todos past `quota`
+    fn with_quota(self, quota: Quota) -> Self {
+        App {
+            add_todo_handler: self.add_todo_handler.with_quota(quota),
+            ..self
+        }
+    }
+
+    /// Replays the events of `event_store` into the todo file with `replay`, reporting
+    /// progress on stderr every 1000 events, and copies them with `migrate --event-log`
+    fn with_event_store(self, event_store: Arc<dyn EventStore>) -> Self {
+        let handler =
+            ReplayEventsHandler::new(event_store.clone()).with_progress(Arc::new(|progress| {
+                if progress.replayed.is_multiple_of(1000) {
+                    eprintln!(
+                        "Replayed {} of {} events",
+                        progress.replayed, progress.total
+                    );
+                }
+            }));
+        App {
+            replay_events_handler: Some(handler),
+            event_store: Some(event_store),
+            ..self
+        }
+    }
+
+    fn run(&self, command: Command) -> Result<(), String> {
+        match command {
+            Command::Add { description } => {
+                let (todo, _) = block_on(self.add_todo_handler.new_todo(description.join(" ")))
+                    .map_err(|e| e.to_string())?;
+                self.print_one("Added", &todo)
+            }
+            Command::List { state } => {
+                let todos = self.todos()?;
+                let todos: Vec<&Todo> = match state {
+                    Some(state) => todos
+                        .iter()
+                        .filter(|todo| todo.state == state.into())
+                        .collect(),
+                    None => todos.iter().collect(),
+                };
+                self.print_many(&todos)
+            }
+            Command::Start { id } => self.change_state(&id, TodoState::InProgress, "Started"),
+            Command::Done { id } => self.change_state(&id, TodoState::Done, "Done"),
+            Command::Rm { id } => {
+                let todo = self.find(&id)?;
+                block_on(self.delete_todo_handler.delete_todo(todo.id.to_string()))
+                    .map_err(|e| e.to_string())?;
+                self.print_one("Removed", &todo)
+            }
+            Command::Trash => {
+                let trashed =
+                    block_on(self.restore_handler()?.trashed()).map_err(|e| e.to_string())?;
+                if self.json {
+                    let views: Vec<TrashedView> = trashed.iter().map(TrashedView::from).collect();
+                    return print_json(&views);
+                }
+                trashed.iter().for_each(print_trashed);
+                Ok(())
+            }
+            Command::Restore { id } => {
+                let handler = self.restore_handler()?;
+                let id = find_trashed(handler, &id)?;
+                let (todo, _) = block_on(handler.restore(id)).map_err(|e| e.to_string())?;
+                self.print_one("Restored", &todo)
+            }
+            Command::Search { query } => {
+                let filter = TodoFilter::parse(&query.join(" "))?;
+                let mut todos = block_on(self.get_todos_handler.search_todos(&filter))
+                    .map_err(|e| e.to_string())?;
+                todos.sort_by_key(|todo| todo.created_at);
+                self.print_many(&todos.iter().collect::<Vec<_>>())
+            }
+            Command::Export {
+                output,
+                format,
+                encrypt: true,
+            } => {
+                let mut document = Vec::new();
+                let count = self.export(&mut document, &format)?;
+                let archive =
+                    encrypted::encrypt(&document, &passphrase()?).map_err(|e| e.to_string())?;
+                match output {
+                    Some(path) => {
+                        std::fs::write(&path, archive)
+                            .map_err(|e| format!("{}: {}", path.display(), e))?;
+                        if !self.json {
+                            println!("Exported {} encrypted todos to {}", count, path.display());
+                        }
+                    }
+                    None => std::io::stdout()
+                        .lock()
+                        .write_all(&archive)
+                        .map_err(|e| e.to_string())?,
+                }
+                Ok(())
+            }
+            Command::Export {
+                output,
+                format,
+                encrypt: false,
+            } => match output {
+                Some(path) => {
+                    let file =
+                        File::create(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+                    let mut writer = BufWriter::new(file);
+                    let count = self.export(&mut writer, &format)?;
+                    writer
+                        .flush()
+                        .map_err(|e| format!("{}: {}", path.display(), e))?;
+                    if !self.json {
+                        println!("Exported {} todos to {}", count, path.display());
+                    }
+                    Ok(())
+                }
+                None => {
+                    let mut stdout = std::io::stdout().lock();
+                    self.export(&mut stdout, &format)?;
+                    if format.format == FormatArg::Json {
+                        writeln!(stdout).map_err(|e| e.to_string())?;
+                    }
+                    Ok(())
+                }
+            },
+            Command::Import {
+                path,
+                format,
+                on_existing,
+                mappings,
+            } => {
+                let mut document = Vec::new();
+                if path.as_os_str() == "-" {
+                    std::io::stdin().lock().read_to_end(&mut document)
+                } else {
+                    File::open(&path).and_then(|mut file| file.read_to_end(&mut document))
+                }
+                .map_err(|e| format!("{}: {}", path.display(), e))?;
+                if encrypted::is_encrypted(&document) {
+                    document = encrypted::decrypt(&document, &passphrase()?)
+                        .map_err(|e| format!("{}: {}", path.display(), e))?;
+                }
+                let mut handler =
+                    ImportTodosHandler::new(self.repository.clone()).with_policy(on_existing);
+                if let Some(mappings) = mappings {
+                    handler = handler
+                        .with_import_mappings(Arc::new(FileImportMappingRepository::new(mappings)));
+                }
+                let (events, errors) = import(&handler, document.as_slice(), &format)?;
+                if self.json {
+                    let ids: Vec<&str> = events
+                        .iter()
+                        .filter_map(|event| match event {
+                            TodoEvent::TodoCreated { id, .. } => Some(&**id),
+                            _ => None,
+                        })
+                        .collect();
+                    let errors: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+                    return print_json(&serde_json::json!({ "imported": ids, "errors": errors }));
+                }
+                for error in &errors {
+                    eprintln!("warning: skipped {}", error);
+                }
+                println!("Imported {} todos", events.len());
+                Ok(())
+            }
+            Command::Replay { from, to } => {
+                let handler = self.replay_events_handler.as_ref().ok_or_else(|| {
+                    "set TODO_EVENT_LOG or event_log in the config file to file:<path>".to_string()
+                })?;
+                let projection = TodoProjection::new(self.repository.clone());
+                let count = block_on(handler.replay_events(from, to, &projection))
+                    .map_err(|e| e.to_string())?;
+                if self.json {
+                    return print_json(&serde_json::json!({ "replayed": count }));
+                }
+                println!("Replayed {} events", count);
+                Ok(())
+            }
+            Command::Migrate {
+                target,
+                event_log,
+                batch_size,
+            } => {
+                let mut handler =
+                    MigrateDataHandler::new(self.repository.clone(), target.repository())
+                        .with_batch_size(batch_size);
+                if let Some(path) = event_log {
+                    let source = self.event_store.clone().ok_or_else(|| {
+                        "set TODO_EVENT_LOG or event_log in the config file to file:<path>"
+                            .to_string()
+                    })?;
+                    handler = handler.with_events(source, Arc::new(FileEventStore::new(path)));
+                }
+                let report = block_on(handler.migrate_data()).map_err(|e| e.to_string())?;
+                if self.json {
+                    return print_json(&serde_json::json!({
+                        "todos": report.todos,
+                        "todos_copied": report.todos_copied,
+                        "todos_present": report.todos_present,
+                        "events_copied": report.events_copied,
+                        "events_present": report.events_present,
+                    }));
+                }
+                println!(
+                    "Migrated {} todos, {} already there, and {} events, {} already there",
+                    report.todos,
+                    report.todos_present,
+                    report.events_copied + report.events_present,
+                    report.events_present
+                );
+                Ok(())
+            }
+            Command::ApiKey { .. } | Command::Role { .. } | Command::Escalation { .. } => {
+                unreachable!("api-key, role and escalation commands do not use the todo file")
+            }
+        }
+    }
+
+    fn export(&self, writer: &mut dyn Write, format: &FormatArgs) -> Result<usize, String> {
+        match format.format {
+            FormatArg::Json => block_on(self.export_todos_handler.export_todos(writer)),
+            FormatArg::Csv => block_on(
+                self.export_todos_handler
+                    .export_csv(writer, &format.csv_options()),
+            ),
+            FormatArg::Org => block_on(self.export_todos_handler.export_org(writer)),
+            FormatArg::Markdown => block_on(self.export_todos_handler.export_markdown(writer)),
+            FormatArg::Ndjson => block_on(self.export_todos_handler.export_ndjson(writer)),
+            FormatArg::Taskwarrior => {
+                block_on(self.export_todos_handler.export_taskwarrior(writer))
+            }
+        }
+        .map_err(|e| e.to_string())
+    }
+
+    fn restore_handler(&self) -> Result<&RestoreTodoHandler<Arc<dyn TodoRepository>>, String> {
+        self.restore_todo_handler
+            .as_ref()
+            .ok_or_else(|| "set TODO_TRASH_FILE or trash_file in the config file".to_string())
+    }
+
+    fn change_state(&self, id: &str, new_state: TodoState, verb: &str) -> Result<(), String> {
+        let todo = self.find(id)?;
+        let (todo, _) = block_on(
+            self.change_todo_state_handler
+                .change_state(todo.id.to_string(), new_state),
+        )
+        .map_err(|e| e.to_string())?;
+        self.print_one(verb, &todo)
+    }
+
+    fn todos(&self) -> Result<Vec<Todo>, String> {
+        let mut todos = block_on(self.get_todos_handler.get_todos()).map_err(|e| e.to_string())?;
+        todos.sort_by_key(|todo| todo.created_at);
+        Ok(todos)
+    }
+
+    /// Finds the todo whose id is `id` or starts with it
+    fn find(&self, id: &str) -> Result<Todo, String> {
+        let mut matches: Vec<Todo> = self
+            .todos()?
+            .into_iter()
+            .filter(|todo| todo.id.starts_with(id))
+            .collect();
+        if let Some(index) = matches.iter().position(|todo| &*todo.id == id) {
+            return Ok(matches.swap_remove(index));
+        }
+        match matches.len() {
+            0 => Err(format!("no todo matches id {}", id)),
+            1 => Ok(matches.remove(0)),
+            _ => Err(format!(
+                "id {} matches {} todos, use a longer prefix",
+                id,
+                matches.len()
+            )),
+        }
+    }
+
+    fn print_one(&self, verb: &str, todo: &Todo) -> Result<(), String> {
+        if self.json {
+            return print_json(&TodoView::from(todo));
+        }
+        println!("{} {}: {}", verb, short_id(&todo.id), todo.description);
+        Ok(())
+    }
+
+    fn print_many(&self, todos: &[&Todo]) -> Result<(), String> {
+        if self.json {
+            let views: Vec<TodoView> = todos.iter().map(|todo| TodoView::from(*todo)).collect();
+            return print_json(&views);
+        }
+        for todo in todos {
+            print_todo(todo);
+        }
+        Ok(())
+    }
+}
+
+/// Finds the id of the todo in the trash whose id is `id` or starts with it
+fn find_trashed(
+    handler: &RestoreTodoHandler<Arc<dyn TodoRepository>>,
+    id: &str,
+) -> Result<String, String> {
+    let trashed = block_on(handler.trashed()).map_err(|e| e.to_string())?;
+    let matches: Vec<&str> = trashed
+        .iter()
+        .map(|trashed| trashed.id())
+        .filter(|trashed| trashed.starts_with(id))
+        .collect();
+    if matches.contains(&id) {
+        return Ok(id.to_string());
+    }
+    match matches[..] {
+        [] => Err(format!("no todo in the trash matches id {}", id)),
+        [only] => Ok(only.to_string()),
+        _ => Err(format!(
+            "id {} matches {} todos in the trash, use a longer prefix",
+            id,
+            matches.len()
+        )),
+    }
+}
+
+fn import(
+    handler: &ImportTodosHandler<Arc<dyn TodoRepository>>,
+    reader: impl Read,
+    format: &FormatArgs,
+) -> Result<(Vec<TodoEvent>, Vec<SerializationError>), String> {
+    match format.format {
+        FormatArg::Json => {
+            block_on(handler.import_todos(reader)).map(|events| (events, Vec::new()))
+        }
+        FormatArg::Csv => block_on(handler.import_csv(reader, &format.csv_options())),
+        FormatArg::Taskwarrior => {
+            block_on(handler.import_taskwarrior(reader)).map(|events| (events, Vec::new()))
+        }
+        FormatArg::Markdown => {
+            block_on(handler.import_markdown(reader)).map(|events| (events, Vec::new()))
+        }
+        FormatArg::Org => return Err("the org format can only be exported".to_string()),
+        FormatArg::Ndjson => {
+            return Err("the ndjson format can only be exported".to_string());
+        }
+    }
+    .map_err(|e| e.to_string())
+}
+
+/// The passphrase of encrypted exports, from `HK_TODO_PASSPHRASE`
+fn passphrase() -> Result<String, String> {
+    match std::env::var("HK_TODO_PASSPHRASE") {
+        Ok(passphrase) if !passphrase.is_empty() => Ok(passphrase),
+        _ => Err("set HK_TODO_PASSPHRASE to the passphrase of the encrypted export".to_string()),
+    }
+}
+
+/// `$HOME/.hk-todo.json`, or `.hk-todo.json` in the working directory without a home
+fn default_file() -> PathBuf {
+    std::env::var_os("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_default()
+        .join(".hk-todo.json")
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let config = match TodoConfig::load() {
+        Ok(config) => config,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            return ExitCode::FAILURE;
+        }
+    };
+    let result = match cli.command {
+        Command::ApiKey { command } => match config.api_key_file {
+            Some(path) => api_keys::run(
+                command,
+                Arc::new(FileApiKeyRepository::new(path)),
+                config.id_format.generator(),
+                cli.json,
+            ),
+            None => Err("set TODO_API_KEY_FILE or api_key_file in the config file".to_string()),
+        },
+        Command::Role { command } => match config.roles_file {
+            Some(path) => roles::run(
+                command,
+                Arc::new(FileMembershipRepository::new(path)),
+                cli.json,
+            ),
+            None => Err("set TODO_ROLES_FILE or roles_file in the config file".to_string()),
+        },
+        Command::Escalation { command } => match config.escalation_rules_file {
+            Some(path) => escalations::run(
+                command,
+                Arc::new(FileEscalationRuleRepository::new(path)),
+                cli.json,
+            ),
+            None => Err(
+                "set TODO_ESCALATION_RULES_FILE or escalation_rules_file in the config file"
+                    .to_string(),
+            ),
+        },
+        command => {
+            let repository: Arc<dyn TodoRepository> = match (cli.file, config.backend) {
+                (Some(file), _) => Arc::new(FileTodoRepository::new(file)),
+                (None, Some(backend)) => backend.repository(),
+                (None, None) => Arc::new(FileTodoRepository::new(default_file())),
+            };
+            // The todo file is a single list, so it gets the quota of the `default` list
+            let list = TenantId::new(DEFAULT_LIST).expect("the default list id is valid");
+            let mut app = App::new(repository, config.id_format.generator(), cli.json)
+                .with_quota(config.quota.for_list(&list));
+            if let Some(path) = config.trash_file {
+                app = app.with_trash(Arc::new(FileTrashRepository::new(path)));
+            }
+            if let Some(EventLog::File(path)) = config.event_log {
+                app = app.with_event_store(Arc::new(FileEventStore::new(path)));
+            }
+            app.run(command)
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
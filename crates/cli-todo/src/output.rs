@@ -0,0 +1,80 @@
+use serde::Serialize;
+use todo::domain::trash::TrashedTodo;
+use todo::{Todo, TodoState};
+
+/// JSON representation of a Todo printed by `--json`
+#[derive(Serialize)]
+pub struct TodoView {
+    pub id: String,
+    pub description: String,
+    pub state: &'static str,
+    pub created_at: String,
+}
+
+impl From<&Todo> for TodoView {
+    fn from(todo: &Todo) -> Self {
+        TodoView {
+            id: todo.id.to_string(),
+            description: todo.description.to_string(),
+            state: state_name(todo.state),
+            created_at: todo.created_at.to_rfc3339(),
+        }
+    }
+}
+
+/// JSON representation of a todo in the trash printed by `trash --json`
+#[derive(Serialize)]
+pub struct TrashedView {
+    #[serde(flatten)]
+    pub todo: TodoView,
+    pub deleted_at: String,
+}
+
+impl From<&TrashedTodo> for TrashedView {
+    fn from(trashed: &TrashedTodo) -> Self {
+        TrashedView {
+            todo: TodoView::from(&trashed.todo),
+            deleted_at: trashed.deleted_at.to_rfc3339(),
+        }
+    }
+}
+
+pub fn state_name(state: TodoState) -> &'static str {
+    match state {
+        TodoState::Todo => "TODO",
+        TodoState::InProgress => "IN_PROGRESS",
+        TodoState::Done => "DONE",
+    }
+}
+
+/// Prints a todo as `<short id>  [STATE]  description`
+pub fn print_todo(todo: &Todo) {
+    println!(
+        "{}  [{}]  {}",
+        short_id(&todo.id),
+        state_name(todo.state),
+        todo.description
+    );
+}
+
+/// Prints a todo in the trash as `<short id>  [STATE]  description  (deleted <time>)`
+pub fn print_trashed(trashed: &TrashedTodo) {
+    println!(
+        "{}  [{}]  {}  (deleted {})",
+        short_id(trashed.id()),
+        state_name(trashed.todo.state),
+        trashed.todo.description,
+        trashed.deleted_at.format("%Y-%m-%d %H:%M")
+    );
+}
+
+pub fn print_json<T: Serialize + ?Sized>(value: &T) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(value).map_err(|err| err.to_string())?;
+    println!("{}", json);
+    Ok(())
+}
+
+/// First 8 characters of an id, enough to address a todo on the command line
+pub fn short_id(id: &str) -> &str {
+    id.get(..8).unwrap_or(id)
+}
@@ -0,0 +1,122 @@
+use clap::{Subcommand, ValueEnum};
+use futures::executor::block_on;
+use serde::Serialize;
+use std::sync::Arc;
+use todo::TenantId;
+use todo::application::access_control::DEFAULT_LIST;
+use todo::domain::access::{Membership, MembershipRepository, Role};
+
+use crate::output::print_json;
+
+#[derive(Subcommand)]
+pub enum RoleCommand {
+    /// Give a subject a role on a list, replacing the one it had
+    Grant {
+        /// Caller as the servers see it, such as a JWT's sub or api-key:<id>
+        subject: String,
+        #[arg(value_enum)]
+        role: RoleArg,
+        #[arg(long, default_value = DEFAULT_LIST)]
+        list: String,
+    },
+    /// Remove a subject's role on a list
+    Revoke {
+        subject: String,
+        #[arg(long, default_value = DEFAULT_LIST)]
+        list: String,
+    },
+    /// List the members of a list and their roles
+    List {
+        #[arg(long, default_value = DEFAULT_LIST)]
+        list: String,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum RoleArg {
+    Owner,
+    Editor,
+    Viewer,
+}
+
+impl From<RoleArg> for Role {
+    fn from(role: RoleArg) -> Self {
+        match role {
+            RoleArg::Owner => Role::Owner,
+            RoleArg::Editor => Role::Editor,
+            RoleArg::Viewer => Role::Viewer,
+        }
+    }
+}
+
+/// JSON representation of a Membership printed by `--json`
+#[derive(Serialize)]
+struct MembershipView {
+    list: String,
+    subject: String,
+    role: &'static str,
+}
+
+impl From<&Membership> for MembershipView {
+    fn from(membership: &Membership) -> Self {
+        MembershipView {
+            list: membership.list.to_string(),
+            subject: membership.subject.to_string(),
+            role: membership.role.as_str(),
+        }
+    }
+}
+
+/// Runs a `role` command against the memberships in `repository`
+pub fn run(
+    command: RoleCommand,
+    repository: Arc<dyn MembershipRepository>,
+    json: bool,
+) -> Result<(), String> {
+    match command {
+        RoleCommand::Grant {
+            subject,
+            role,
+            list,
+        } => {
+            let membership = Membership::new(TenantId::new(list)?, subject, role.into());
+            block_on(repository.save(&membership)).map_err(|e| e.to_string())?;
+            if json {
+                return print_json(&MembershipView::from(&membership));
+            }
+            println!(
+                "Granted {} to {} on {}",
+                membership.role.as_str(),
+                membership.subject,
+                membership.list
+            );
+            Ok(())
+        }
+        RoleCommand::Revoke { subject, list } => {
+            let list = TenantId::new(list)?;
+            block_on(repository.remove(&list, &subject)).map_err(|e| e.to_string())?;
+            if json {
+                return print_json(
+                    &serde_json::json!({ "list": list.as_str(), "subject": subject }),
+                );
+            }
+            println!("Revoked the role of {} on {}", subject, list);
+            Ok(())
+        }
+        RoleCommand::List { list } => {
+            let list = TenantId::new(list)?;
+            let mut memberships =
+                block_on(repository.find_by_list(&list)).map_err(|e| e.to_string())?;
+            memberships.sort_by(|a, b| a.subject.cmp(&b.subject));
+            if json {
+                let views: Vec<MembershipView> =
+                    memberships.iter().map(MembershipView::from).collect();
+                return print_json(&views);
+            }
+            for membership in &memberships {
+                println!("{}  [{}]", membership.subject, membership.role.as_str());
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,528 @@
+use serde_json::Value;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+fn hk_todo(file: &PathBuf, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_hk-todo"))
+        .arg("--file")
+        .arg(file)
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn json(output: &Output) -> Value {
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn test_cli_manages_todos_in_the_file() {
+    // Arrange
+    let file = std::env::temp_dir().join(format!("hk-todo-cli-{}.json", std::process::id()));
+    let added = json(&hk_todo(&file, &["--json", "add", "Write", "docs"]));
+    let id = added["id"].as_str().unwrap().to_string();
+
+    // Act
+    let started = json(&hk_todo(&file, &["--json", "start", &id[..8]]));
+    json(&hk_todo(&file, &["--json", "add", "Buy milk"]));
+    let found = json(&hk_todo(&file, &["--json", "search", "DOCS"]));
+    let not_started = json(&hk_todo(&file, &["--json", "search", "-state:in_progress"]));
+    let removed = hk_todo(&file, &["rm", &id]);
+    let remaining = json(&hk_todo(&file, &["--json", "list"]));
+
+    // Assert
+    assert_eq!(added["description"], "Write docs");
+    assert_eq!(added["state"], "TODO");
+    assert_eq!(started["state"], "IN_PROGRESS");
+    assert_eq!(found.as_array().unwrap().len(), 1);
+    assert_eq!(found[0]["id"], id.as_str());
+    assert_eq!(not_started.as_array().unwrap().len(), 1);
+    assert_eq!(not_started[0]["description"], "Buy milk");
+    assert!(removed.status.success());
+    assert_eq!(remaining.as_array().unwrap().len(), 1);
+    assert_eq!(remaining[0]["description"], "Buy milk");
+
+    std::fs::remove_file(file).unwrap();
+}
+
+#[test]
+fn test_cli_reports_errors_with_failure_status() {
+    // Arrange
+    let file = std::env::temp_dir().join(format!("hk-todo-cli-errors-{}.json", std::process::id()));
+
+    // Act
+    let output = hk_todo(&file, &["done", "non-existent-id"]);
+
+    // Assert
+    assert!(!output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr).trim(),
+        "error: no todo matches id non-existent-id"
+    );
+}
+
+#[test]
+fn test_cli_exports_and_imports_todos() {
+    // Arrange
+    let source =
+        std::env::temp_dir().join(format!("hk-todo-cli-source-{}.json", std::process::id()));
+    let target =
+        std::env::temp_dir().join(format!("hk-todo-cli-target-{}.json", std::process::id()));
+    let document =
+        std::env::temp_dir().join(format!("hk-todo-cli-export-{}.json", std::process::id()));
+    let added = json(&hk_todo(&source, &["--json", "add", "Write docs"]));
+    let exported = hk_todo(&source, &["export", "-o", document.to_str().unwrap()]);
+
+    // Act
+    let imported = json(&hk_todo(
+        &target,
+        &["--json", "import", document.to_str().unwrap()],
+    ));
+    let reimported = json(&hk_todo(
+        &target,
+        &["--json", "import", document.to_str().unwrap()],
+    ));
+    let todos = json(&hk_todo(&target, &["--json", "list"]));
+
+    // Assert
+    assert!(exported.status.success());
+    assert_eq!(imported["imported"], serde_json::json!([added["id"]]));
+    assert_eq!(reimported["imported"], serde_json::json!([]));
+    assert_eq!(todos[0]["id"], added["id"]);
+
+    for file in [source, target, document] {
+        std::fs::remove_file(file).unwrap();
+    }
+}
+
+#[test]
+fn test_cli_reads_the_shared_config_file() {
+    // Arrange
+    let dir = std::env::temp_dir();
+    let file = dir.join(format!("hk-todo-cli-config-{}.json", std::process::id()));
+    let config = dir.join(format!("hk-todo-cli-config-{}.toml", std::process::id()));
+    std::fs::write(
+        &config,
+        format!(
+            "backend = \"file:{}\"\nid_format = \"ulid\"\n",
+            file.display()
+        ),
+    )
+    .unwrap();
+
+    // Act
+    let output = Command::new(env!("CARGO_BIN_EXE_hk-todo"))
+        .args(["--json", "add", "Configured"])
+        .env("TODO_CONFIG", &config)
+        .env_remove("HK_TODO_FILE")
+        .env_remove("TODO_BACKEND")
+        .env_remove("TODO_ID_FORMAT")
+        .output()
+        .unwrap();
+
+    // Assert
+    let added = json(&output);
+    assert_eq!(added["id"].as_str().unwrap().len(), 26);
+    let stored = std::fs::read_to_string(&file).unwrap();
+    assert!(stored.contains("Configured"));
+    std::fs::remove_file(file).unwrap();
+    std::fs::remove_file(config).unwrap();
+}
+
+#[test]
+fn test_cli_refuses_todos_past_the_quota() {
+    // Arrange
+    let file = std::env::temp_dir().join(format!("hk-todo-cli-quota-{}.json", std::process::id()));
+    let add = |description: &str| {
+        Command::new(env!("CARGO_BIN_EXE_hk-todo"))
+            .arg("--file")
+            .arg(&file)
+            .args(["add", description])
+            .env("TODO_QUOTA_MAX_TODOS", "1")
+            .env_remove("TODO_CONFIG")
+            .output()
+            .unwrap()
+    };
+    assert!(add("Write docs").status.success());
+
+    // Act
+    let refused = add("Ship it");
+
+    // Assert
+    assert!(!refused.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&refused.stderr).trim(),
+        "error: todo quota exceeded"
+    );
+    assert_eq!(
+        json(&hk_todo(&file, &["--json", "list"]))
+            .as_array()
+            .unwrap()
+            .len(),
+        1
+    );
+    std::fs::remove_file(file).unwrap();
+}
+
+#[test]
+fn test_cli_issues_and_revokes_api_keys() {
+    // Arrange
+    let keys = std::env::temp_dir().join(format!("hk-todo-cli-keys-{}.json", std::process::id()));
+    let api_key = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_hk-todo"))
+            .args(["--json", "api-key"])
+            .args(args)
+            .env("TODO_API_KEY_FILE", &keys)
+            .env_remove("TODO_CONFIG")
+            .output()
+            .unwrap()
+    };
+
+    // Act
+    let issued = json(&api_key(&[
+        "issue",
+        "backup",
+        "job",
+        "--scope",
+        "read-write",
+    ]));
+    let id = issued["id"].as_str().unwrap().to_string();
+    let revoked = json(&api_key(&["revoke", &id]));
+    let listed = json(&api_key(&["list"]));
+
+    // Assert
+    assert_eq!(issued["name"], "backup job");
+    assert_eq!(issued["scope"], "read-write");
+    let token = issued["token"].as_str().unwrap();
+    assert!(token.starts_with(&format!("hkt_{}_", id)));
+    assert!(!revoked["revoked_at"].is_null());
+    assert_eq!(listed.as_array().unwrap().len(), 1);
+    assert!(listed[0].get("token").is_none());
+    let stored = std::fs::read_to_string(&keys).unwrap();
+    assert!(!stored.contains(token.rsplit('_').next().unwrap()));
+    std::fs::remove_file(keys).unwrap();
+}
+
+#[test]
+fn test_cli_grants_and_revokes_roles() {
+    // Arrange
+    let roles = std::env::temp_dir().join(format!("hk-todo-cli-roles-{}.json", std::process::id()));
+    let role = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_hk-todo"))
+            .args(["--json", "role"])
+            .args(args)
+            .env("TODO_ROLES_FILE", &roles)
+            .env_remove("TODO_CONFIG")
+            .output()
+            .unwrap()
+    };
+
+    // Act
+    json(&role(&["grant", "alice", "viewer", "--list", "team"]));
+    json(&role(&["grant", "alice", "editor", "--list", "team"]));
+    json(&role(&["grant", "bob", "owner", "--list", "team"]));
+    json(&role(&["revoke", "bob", "--list", "team"]));
+    let revoked_again = role(&["revoke", "bob", "--list", "team"]);
+    let members = json(&role(&["list", "--list", "team"]));
+
+    // Assert
+    assert!(!revoked_again.status.success());
+    assert_eq!(
+        members,
+        serde_json::json!([{ "list": "team", "subject": "alice", "role": "editor" }])
+    );
+    std::fs::remove_file(roles).unwrap();
+}
+
+#[test]
+fn test_cli_manages_escalation_rules() {
+    // Arrange
+    let rules = std::env::temp_dir().join(format!(
+        "hk-todo-cli-escalation-{}.json",
+        std::process::id()
+    ));
+    let escalation = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_hk-todo"))
+            .args(["--json", "escalation"])
+            .args(args)
+            .env("TODO_ESCALATION_RULES_FILE", &rules)
+            .env_remove("TODO_CONFIG")
+            .output()
+            .unwrap()
+    };
+    let add =
+        |id: &str, state: &str, days: &str| escalation(&["add", id, state, "--after-days", days]);
+
+    // Act
+    json(&add("stuck", "in-progress", "3"));
+    json(&add("stuck", "in-progress", "7"));
+    json(&add("forgotten", "todo", "30"));
+    let invalid = add("never", "todo", "0");
+    json(&escalation(&["remove", "forgotten"]));
+    let removed_again = escalation(&["remove", "forgotten"]);
+    let listed = json(&escalation(&["list"]));
+
+    // Assert
+    assert!(!invalid.status.success());
+    assert!(!removed_again.status.success());
+    assert_eq!(
+        listed,
+        serde_json::json!([{ "id": "stuck", "state": "IN_PROGRESS", "after_days": 7 }])
+    );
+    std::fs::remove_file(rules).unwrap();
+}
+
+#[test]
+fn test_cli_replays_the_event_log_into_the_todo_file() {
+    // Arrange
+    let dir = std::env::temp_dir();
+    let file = dir.join(format!("hk-todo-cli-replay-{}.json", std::process::id()));
+    let log = dir.join(format!("hk-todo-cli-replay-{}.jsonl", std::process::id()));
+    std::fs::write(
+        &log,
+        [
+            r#"{"type":"TODO_CREATED","id":"a1","description":"Write docs","created_at":"2024-01-01T00:00:00Z"}"#,
+            r#"{"type":"TODO_CREATED","id":"b2","description":"Ship it","created_at":"2024-01-02T00:00:00Z"}"#,
+            r#"{"type":"TODO_STATE_CHANGED","id":"a1","from_state":"TODO","to_state":"IN_PROGRESS","changed_at":"2024-01-03T00:00:00Z"}"#,
+        ]
+        .join("\n"),
+    )
+    .unwrap();
+    let replay = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_hk-todo"))
+            .arg("--file")
+            .arg(&file)
+            .args(["--json", "replay"])
+            .args(args)
+            .env("TODO_EVENT_LOG", format!("file:{}", log.display()))
+            .env_remove("TODO_CONFIG")
+            .output()
+            .unwrap()
+    };
+
+    // Act
+    let before = json(&replay(&["--to", "2024-01-02"]));
+    let all = json(&replay(&[]));
+    let listed = json(&hk_todo(&file, &["--json", "list"]));
+    let without_log = hk_todo(&file, &["replay"]);
+
+    // Assert
+    assert_eq!(before["replayed"], 1);
+    assert_eq!(all["replayed"], 3);
+    assert_eq!(listed.as_array().unwrap().len(), 2);
+    assert_eq!(listed[0]["id"], "a1");
+    assert_eq!(listed[0]["state"], "IN_PROGRESS");
+    assert_eq!(listed[1]["state"], "TODO");
+    assert!(!without_log.status.success());
+    std::fs::remove_file(file).unwrap();
+    std::fs::remove_file(log).unwrap();
+}
+
+#[test]
+fn test_cli_migrates_todos_and_events_to_another_file() {
+    // Arrange
+    let dir = std::env::temp_dir();
+    let source = dir.join(format!("hk-todo-cli-migrate-{}.json", std::process::id()));
+    let target = dir.join(format!("hk-todo-cli-migrated-{}.json", std::process::id()));
+    let log = dir.join(format!("hk-todo-cli-migrate-{}.jsonl", std::process::id()));
+    let copied_log = dir.join(format!("hk-todo-cli-migrated-{}.jsonl", std::process::id()));
+    std::fs::write(
+        &log,
+        r#"{"type":"TODO_CREATED","id":"a1","description":"Write docs","created_at":"2024-01-01T00:00:00Z"}"#,
+    )
+    .unwrap();
+    for description in ["Write docs", "Ship it", "Celebrate"] {
+        json(&hk_todo(&source, &["--json", "add", description]));
+    }
+    let migrate = || {
+        let backend = format!("file:{}", target.display());
+        Command::new(env!("CARGO_BIN_EXE_hk-todo"))
+            .arg("--file")
+            .arg(&source)
+            .args(["--json", "migrate", &backend, "--batch-size", "2"])
+            .arg("--event-log")
+            .arg(&copied_log)
+            .env("TODO_EVENT_LOG", format!("file:{}", log.display()))
+            .env_remove("TODO_CONFIG")
+            .output()
+            .unwrap()
+    };
+
+    // Act
+    let first = json(&migrate());
+    let second = json(&migrate());
+    let migrated = json(&hk_todo(&target, &["--json", "list"]));
+
+    // Assert
+    assert_eq!(first["todos_copied"], 3);
+    assert_eq!(first["events_copied"], 1);
+    assert_eq!(second["todos_present"], 3);
+    assert_eq!(second["events_present"], 1);
+    assert_eq!(
+        migrated,
+        json(&hk_todo(&source, &["--json", "list"])),
+        "the target lists the same todos"
+    );
+    assert_eq!(
+        std::fs::read_to_string(&copied_log)
+            .unwrap()
+            .lines()
+            .count(),
+        1
+    );
+    for file in [source, target, log, copied_log] {
+        std::fs::remove_file(file).unwrap();
+    }
+}
+
+#[test]
+fn test_cli_imports_encrypted_exports_only_with_the_passphrase() {
+    // Arrange
+    let dir = std::env::temp_dir();
+    let source = dir.join(format!(
+        "hk-todo-cli-enc-source-{}.json",
+        std::process::id()
+    ));
+    let target = dir.join(format!(
+        "hk-todo-cli-enc-target-{}.json",
+        std::process::id()
+    ));
+    let archive = dir.join(format!("hk-todo-cli-enc-{}.bin", std::process::id()));
+    let added = json(&hk_todo(&source, &["--json", "add", "Write docs"]));
+    let run = |file: &PathBuf, passphrase: &str, args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_hk-todo"))
+            .arg("--file")
+            .arg(file)
+            .args(args)
+            .env("HK_TODO_PASSPHRASE", passphrase)
+            .output()
+            .unwrap()
+    };
+    let exported = run(
+        &source,
+        "correct horse",
+        &["export", "--encrypt", "-o", archive.to_str().unwrap()],
+    );
+
+    // Act
+    let without = Command::new(env!("CARGO_BIN_EXE_hk-todo"))
+        .arg("--file")
+        .arg(&target)
+        .args(["import", archive.to_str().unwrap()])
+        .env_remove("HK_TODO_PASSPHRASE")
+        .output()
+        .unwrap();
+    let wrong = run(&target, "guess", &["import", archive.to_str().unwrap()]);
+    let imported = json(&run(
+        &target,
+        "correct horse",
+        &["--json", "import", archive.to_str().unwrap()],
+    ));
+
+    // Assert
+    assert!(exported.status.success());
+    let contents = std::fs::read(&archive).unwrap();
+    assert!(!String::from_utf8_lossy(&contents).contains("Write docs"));
+    assert!(!without.status.success());
+    assert!(String::from_utf8_lossy(&without.stderr).contains("HK_TODO_PASSPHRASE"));
+    assert!(!wrong.status.success());
+    assert!(String::from_utf8_lossy(&wrong.stderr).contains("integrity check failed"));
+    assert_eq!(imported["imported"], serde_json::json!([added["id"]]));
+
+    for file in [source, target, archive] {
+        std::fs::remove_file(file).unwrap();
+    }
+}
+
+#[test]
+fn test_cli_reimport_updates_todos_through_the_mapping_file() {
+    // Arrange
+    let dir = std::env::temp_dir();
+    let file = dir.join(format!("hk-todo-cli-remap-{}.json", std::process::id()));
+    let mappings = dir.join(format!("hk-todo-cli-remap-{}.map.json", std::process::id()));
+    let tasks = dir.join(format!(
+        "hk-todo-cli-remap-{}.tasks.json",
+        std::process::id()
+    ));
+    let import = |description: &str| {
+        std::fs::write(
+            &tasks,
+            format!(
+                r#"[{{"description":"{}","status":"pending","uuid":"a1"}}]"#,
+                description
+            ),
+        )
+        .unwrap();
+        hk_todo(
+            &file,
+            &[
+                "--json",
+                "import",
+                tasks.to_str().unwrap(),
+                "--format",
+                "taskwarrior",
+                "--on-existing",
+                "update",
+                "--mappings",
+                mappings.to_str().unwrap(),
+            ],
+        )
+    };
+    json(&import("Write docs"));
+
+    // Act
+    let reimported = json(&import("Write the docs"));
+    let todos = json(&hk_todo(&file, &["--json", "list"]));
+
+    // Assert
+    assert_eq!(reimported["imported"], serde_json::json!([]));
+    assert_eq!(todos.as_array().unwrap().len(), 1);
+    assert_eq!(todos[0]["description"], "Write the docs");
+    assert!(mappings.exists());
+
+    for path in [file, mappings, tasks] {
+        std::fs::remove_file(path).unwrap();
+    }
+}
+
+#[test]
+fn test_cli_restores_removed_todos_from_the_trash() {
+    // Arrange
+    let file = std::env::temp_dir().join(format!("hk-todo-cli-trash-{}.json", std::process::id()));
+    let trash =
+        std::env::temp_dir().join(format!("hk-todo-cli-trash-bin-{}.json", std::process::id()));
+    let hk_todo = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_hk-todo"))
+            .arg("--file")
+            .arg(&file)
+            .arg("--json")
+            .args(args)
+            .env("TODO_TRASH_FILE", &trash)
+            .env_remove("TODO_CONFIG")
+            .output()
+            .unwrap()
+    };
+    let added = json(&hk_todo(&["add", "Write", "docs"]));
+    let id = added["id"].as_str().unwrap().to_string();
+
+    // Act
+    json(&hk_todo(&["rm", &id]));
+    let trashed = json(&hk_todo(&["trash"]));
+    let restored = json(&hk_todo(&["restore", &id[..8]]));
+    let restored_again = hk_todo(&["restore", &id]);
+    let listed = json(&hk_todo(&["list"]));
+
+    // Assert
+    assert_eq!(trashed[0]["id"], id.as_str());
+    assert!(trashed[0]["deleted_at"].is_string());
+    assert_eq!(restored, added);
+    assert!(!restored_again.status.success());
+    assert_eq!(listed, serde_json::json!([added]));
+    std::fs::remove_file(file).unwrap();
+    std::fs::remove_file(trash).unwrap();
+}
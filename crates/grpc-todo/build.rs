@@ -0,0 +1,47 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = prost_build::Config::new();
+    config.protoc_executable(protoc_bin_vendored::protoc_bin_path()?);
+    // The entity messages are generated once, in the todo crate
+    for message in [
+        "TodoState",
+        "Priority",
+        "Subtask",
+        "Attachment",
+        "Todo",
+        "TodoCreated",
+        "TodoStateChanged",
+        "TodoArchived",
+        "TodoStale",
+        "TodoDueDateChanged",
+        "TodoPriorityChanged",
+        "TodoTagAdded",
+        "TodoTagRemoved",
+        "TodoSubtaskAdded",
+        "TodoSubtaskToggled",
+        "TodoSubtaskRemoved",
+        "TodoDescriptionUpdated",
+        "TodoBlocked",
+        "TodoUnblocked",
+        "TodoDeleted",
+        "TodoRestored",
+        "TodoAttachmentAdded",
+        "TodoAttachmentRemoved",
+        "TodoEvent",
+    ] {
+        config.extern_path(
+            format!(".todo.v1.{}", message),
+            format!(
+                "::todo::infrastructure::serialization::protobuf::proto::{}",
+                message
+            ),
+        );
+    }
+    let well_known_types = protoc_bin_vendored::include_path()?;
+    tonic_prost_build::configure().compile_with_config(
+        config,
+        &["proto/todo.proto".into()],
+        &["proto".into(), "../todo/proto".into(), well_known_types],
+    )?;
+    println!("cargo:rerun-if-changed=../todo/proto");
+    Ok(())
+}
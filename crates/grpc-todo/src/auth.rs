@@ -0,0 +1,131 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use todo::application::access_control::AccessControl;
+use todo::application::auth::{AuthError, Authenticator, READ_SCOPE, WRITE_SCOPE, bearer_token};
+use todo::domain::access::TodoAction;
+use tonic::codegen::{Service, http};
+use tonic::server::NamedService;
+
+use crate::convert::auth_status;
+
+/// Wraps a gRPC service, requiring every call to carry a bearer token, in the
+/// `authorization` metadata, that its authenticator accepts
+///
+/// `GetTodos` and `Watch` need the `todos:read` scope and the other RPCs `todos:write`.
+/// With an access control, the caller's role on their todo list must also permit the
+/// RPC's `TodoAction`. The caller's `AuthContext` is added to the request extensions, for
+/// the service to take with `request.extensions().get::<AuthContext>()` and scope the call
+/// to the caller's list. A missing or rejected token fails the call with `UNAUTHENTICATED`,
+/// and a token without the scope or role with `PERMISSION_DENIED`.
+#[derive(Clone)]
+pub struct AuthService<S> {
+    inner: S,
+    authenticator: Arc<dyn Authenticator>,
+    access_control: Option<Arc<AccessControl>>,
+}
+
+impl<S> AuthService<S> {
+    pub fn new(inner: S, authenticator: Arc<dyn Authenticator>) -> Self {
+        AuthService {
+            inner,
+            authenticator,
+            access_control: None,
+        }
+    }
+
+    /// Requires the caller's role on their todo list to permit each call, as decided by
+    /// `access_control`
+    pub fn with_access_control(mut self, access_control: Arc<AccessControl>) -> Self {
+        self.access_control = Some(access_control);
+        self
+    }
+}
+
+impl<S: NamedService> NamedService for AuthService<S> {
+    const NAME: &'static str = S::NAME;
+}
+
+impl<S, B> Service<http::Request<B>> for AuthService<S>
+where
+    S: Service<http::Request<B>, Response = http::Response<tonic::body::Body>>
+        + Clone
+        + Send
+        + 'static,
+    S::Future: Send + 'static,
+    B: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: http::Request<B>) -> Self::Future {
+        // The service polled ready is the one to call; the clone takes its place
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let authenticator = self.authenticator.clone();
+        let access_control = self.access_control.clone();
+        let token = bearer(request.headers());
+        let scope = required_scope(request.uri().path());
+        let action = todo_action(request.uri().path());
+        Box::pin(async move {
+            let context = match token {
+                Ok(token) => authenticator
+                    .authenticate(&token)
+                    .await
+                    .and_then(|context| context.require(scope).map(|()| context)),
+                Err(err) => Err(err),
+            };
+            let context = match (context, access_control) {
+                (Ok(context), Some(access_control)) => access_control
+                    .authorize(&context, action)
+                    .await
+                    .map(|_| context),
+                (context, _) => context,
+            };
+            match context {
+                Ok(context) => {
+                    request.extensions_mut().insert(context);
+                    inner.call(request).await
+                }
+                Err(err) => Ok(auth_status(err).into_http()),
+            }
+        })
+    }
+}
+
+/// The bearer token of the `authorization` metadata
+fn bearer(headers: &http::HeaderMap) -> Result<String, AuthError> {
+    match headers.get(http::header::AUTHORIZATION) {
+        Some(value) => value
+            .to_str()
+            .map_err(|_| AuthError::InvalidToken("authorization is not ASCII".to_string()))
+            .and_then(bearer_token)
+            .map(str::to_string),
+        None => Err(AuthError::MissingCredentials),
+    }
+}
+
+/// Scope needed to call the RPC at `path`, such as `/todo.v1.TodoService/GetTodos`
+fn required_scope(path: &str) -> &'static str {
+    match path.rsplit('/').next() {
+        Some("GetTodos" | "Watch") => READ_SCOPE,
+        _ => WRITE_SCOPE,
+    }
+}
+
+/// What the RPC at `path` does to the todos of the caller's list; unknown RPCs count as
+/// the most privileged action
+fn todo_action(path: &str) -> TodoAction {
+    match path.rsplit('/').next() {
+        Some("GetTodos" | "Watch") => TodoAction::View,
+        Some("AddTodo") => TodoAction::Add,
+        Some("ChangeState") => TodoAction::ChangeState,
+        _ => TodoAction::Delete,
+    }
+}
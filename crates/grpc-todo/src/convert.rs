@@ -0,0 +1,19 @@
+use todo::application::auth::AuthError;
+use todo::infrastructure::serialization::protobuf;
+use todo::{TodoError, TodoState};
+use tonic::{Code, Status};
+
+/// Reads a state field; `None` when it is unspecified
+pub fn state_from_proto(value: i32) -> Result<Option<TodoState>, Status> {
+    protobuf::state_from_proto(value).map_err(|err| Status::invalid_argument(err.to_string()))
+}
+
+/// Maps a domain error to its gRPC status, through `TodoError::grpc_code`
+pub fn to_status(err: TodoError) -> Status {
+    Status::new(Code::from_i32(err.grpc_code()), err.to_string())
+}
+
+/// Maps an authentication failure to its gRPC status, through `AuthError::grpc_code`
+pub fn auth_status(err: AuthError) -> Status {
+    Status::new(Code::from_i32(err.grpc_code()), err.to_string())
+}
@@ -0,0 +1,15 @@
+pub mod auth;
+mod convert;
+mod service;
+
+/// Types and client/server stubs generated from `proto/todo.proto`
+pub mod proto {
+    tonic::include_proto!("todo.v1");
+
+    pub use todo::infrastructure::serialization::protobuf::proto::{
+        Todo, TodoArchived, TodoCreated, TodoEvent, TodoStale, TodoState, TodoStateChanged,
+        todo_event,
+    };
+}
+
+pub use service::TodoGrpcService;
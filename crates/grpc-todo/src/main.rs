@@ -0,0 +1,141 @@
+use grpc_todo::TodoGrpcService;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+use todo::application::access_control::AccessControl;
+use todo::application::api_key_authenticator::ApiKeyAuthenticator;
+use todo::application::auth::Authenticator;
+use todo::application::quota::QuotaPolicy;
+use todo::domain::access::MembershipRepository;
+use todo::infrastructure::config::TodoConfig;
+use todo::infrastructure::jwt_authenticator::JwtAuthenticator;
+use todo::infrastructure::repositories::api_key::FileApiKeyRepository;
+use todo::infrastructure::repositories::membership::FileMembershipRepository;
+use todo::infrastructure::repositories::todo::TodoBackend;
+use tonic::transport::Server;
+
+/// How often the issuer's keys are fetched again, to pick up rotated keys
+const KEY_REFRESH_INTERVAL: Duration = Duration::from_secs(3600);
+
+struct Config {
+    addr: SocketAddr,
+    backend: TodoBackend,
+    /// OpenID Provider issuer and audience of the bearer tokens every call must carry
+    auth: Option<(String, String)>,
+    /// JSON file of the API keys calls may carry instead
+    api_key_file: Option<PathBuf>,
+    /// JSON file of the roles callers need on their todo list
+    roles_file: Option<PathBuf>,
+    /// How many todos each list may hold
+    quota: QuotaPolicy,
+}
+
+/// Reads `TODO_GRPC_ADDR` (default `127.0.0.1:50051`), and the shared settings through
+/// `TodoConfig::load`, with the `memory` backend by default
+fn config_from_env() -> Result<Config, String> {
+    let addr = match std::env::var("TODO_GRPC_ADDR") {
+        Ok(addr) => addr
+            .parse()
+            .map_err(|e| format!("invalid TODO_GRPC_ADDR {:?}: {}", addr, e))?,
+        Err(_) => SocketAddr::from(([127, 0, 0, 1], 50051)),
+    };
+    let todo = TodoConfig::load()?;
+    let auth = match (todo.auth_issuer, todo.auth_audience) {
+        (Some(issuer), Some(audience)) => Some((issuer, audience)),
+        (None, None) => None,
+        _ => {
+            return Err("TODO_AUTH_ISSUER and TODO_AUTH_AUDIENCE must be set together".to_string());
+        }
+    };
+    Ok(Config {
+        addr,
+        backend: todo.backend.unwrap_or(TodoBackend::Memory),
+        auth,
+        api_key_file: todo.api_key_file,
+        roles_file: todo.roles_file,
+        quota: todo.quota,
+    })
+}
+
+/// Fetches `authenticator`'s keys every hour, keeping the previous keys on failure
+fn refresh_keys(authenticator: Arc<JwtAuthenticator>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(KEY_REFRESH_INTERVAL);
+        // The first tick completes at once, and the keys were just fetched
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            if let Err(err) = authenticator.refresh().await {
+                eprintln!("warning: {}", err);
+            }
+        }
+    });
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let config = match config_from_env() {
+        Ok(config) => config,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    println!(
+        "Listening on {} ({:?} backend)",
+        config.addr, config.backend
+    );
+    let service = TodoGrpcService::new(config.backend.repository()).with_quota(config.quota);
+    let mut authenticator: Option<Arc<dyn Authenticator>> = None;
+    if let Some((issuer, audience)) = config.auth {
+        match JwtAuthenticator::discover(&issuer, &audience).await {
+            Ok(jwt) => {
+                let jwt = Arc::new(jwt);
+                refresh_keys(jwt.clone());
+                authenticator = Some(jwt);
+            }
+            Err(err) => {
+                eprintln!("error: {}", err);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    if let Some(path) = config.api_key_file {
+        let api_keys = ApiKeyAuthenticator::new(Arc::new(FileApiKeyRepository::new(path)));
+        authenticator = Some(match authenticator {
+            Some(jwt) => Arc::new(api_keys.with_fallback(jwt)),
+            None => Arc::new(api_keys),
+        });
+    }
+    let access_control = config.roles_file.map(|path| {
+        let memberships: Arc<dyn MembershipRepository> =
+            Arc::new(FileMembershipRepository::new(path));
+        Arc::new(AccessControl::new(memberships))
+    });
+    let mut server = Server::builder();
+    let router = match (authenticator, access_control) {
+        (Some(authenticator), Some(access_control)) => server.add_service(
+            service
+                .into_authenticated_server(authenticator)
+                .with_access_control(access_control),
+        ),
+        (Some(authenticator), None) => {
+            server.add_service(service.into_authenticated_server(authenticator))
+        }
+        (None, Some(_)) => {
+            eprintln!("error: TODO_ROLES_FILE needs TODO_AUTH_ISSUER or TODO_API_KEY_FILE");
+            return ExitCode::FAILURE;
+        }
+        (None, None) => server.add_service(service.into_server()),
+    };
+    match router.serve(config.addr).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
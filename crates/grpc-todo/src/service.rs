@@ -0,0 +1,241 @@
+use std::pin::Pin;
+use std::sync::Arc;
+use todo::application::access_control::DEFAULT_LIST;
+use todo::application::add_todo_handler::AddTodoHandler;
+use todo::application::auth::{AuthContext, Authenticator};
+use todo::application::change_todo_state_handler::ChangeTodoStateHandler;
+use todo::application::get_todos_handler::GetTodosHandler;
+use todo::application::quota::{Quota, QuotaPolicy};
+use todo::infrastructure::repositories::todo::TenantTodoRepository;
+use todo::{TenantId, Todo, TodoError, TodoEvent, TodoRepository};
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tokio_stream::{Stream, StreamExt};
+use tonic::{Request, Response, Status};
+
+use crate::auth::AuthService;
+use crate::convert::{state_from_proto, to_status};
+use crate::proto;
+use crate::proto::todo_service_server::{TodoService, TodoServiceServer};
+
+const DEFAULT_PAGE_SIZE: usize = 50;
+const MAX_PAGE_SIZE: usize = 1000;
+/// Events buffered per watcher before it is reported as lagging
+const WATCH_CAPACITY: usize = 256;
+
+/// gRPC TodoService delegating to the application handlers
+///
+/// `Watch` streams the events emitted by this service's own calls. Changes made by other
+/// processes sharing the repository are not observed.
+///
+/// Once wrapped with `into_authenticated_server`, each call's handlers only see the todos
+/// of the caller's tenant list, or of the `default` list when their credentials name no
+/// tenant, through a `TenantTodoRepository`, and `Watch` only streams the events of that
+/// list.
+pub struct TodoGrpcService {
+    repository: Arc<dyn TodoRepository>,
+    tenant_scoped: bool,
+    quota: QuotaPolicy,
+    /// Events of every call, with the ids of their todos as the repository stores them
+    events: broadcast::Sender<TodoEvent>,
+}
+
+impl TodoGrpcService {
+    pub fn new(repository: Arc<dyn TodoRepository>) -> Self {
+        TodoGrpcService {
+            repository,
+            tenant_scoped: false,
+            quota: QuotaPolicy::default(),
+            events: broadcast::channel(WATCH_CAPACITY).0,
+        }
+    }
+
+    /// Refuses todos added past the quota `quota` gives the caller's list
+    ///
+    /// Without an authenticator, every todo counts against the quota of the `default` list.
+    pub fn with_quota(mut self, quota: QuotaPolicy) -> Self {
+        self.quota = quota;
+        self
+    }
+
+    /// Wraps the service for `tonic::transport::Server::add_service`
+    pub fn into_server(self) -> TodoServiceServer<Self> {
+        TodoServiceServer::new(self)
+    }
+
+    /// Wraps the service for `tonic::transport::Server::add_service`, requiring every call
+    /// to carry a bearer token `authenticator` accepts, with the scope the RPC needs, and
+    /// scoping each call to the todos of the caller's list
+    pub fn into_authenticated_server(
+        self,
+        authenticator: Arc<dyn Authenticator>,
+    ) -> AuthService<TodoServiceServer<Self>> {
+        let service = TodoGrpcService {
+            tenant_scoped: true,
+            ..self
+        };
+        AuthService::new(service.into_server(), authenticator)
+    }
+
+    /// The list the caller of `request` works on, or `None` when calls are not scoped
+    fn list<T>(&self, request: &Request<T>) -> Option<TenantId> {
+        if !self.tenant_scoped {
+            return None;
+        }
+        let tenant = request
+            .extensions()
+            .get::<AuthContext>()
+            .and_then(|context| context.tenant.clone());
+        Some(match tenant {
+            Some(tenant) => tenant,
+            None => TenantId::new(DEFAULT_LIST).expect("the default list id is valid"),
+        })
+    }
+
+    /// The todos the caller of `request` may see
+    fn todos<T>(&self, request: &Request<T>) -> Arc<dyn TodoRepository> {
+        match self.list(request) {
+            Some(list) => Arc::new(TenantTodoRepository::new(self.repository.clone(), list)),
+            None => self.repository.clone(),
+        }
+    }
+
+    /// The quota of the list the caller of `request` works on
+    fn quota<T>(&self, request: &Request<T>) -> Quota {
+        let list = self
+            .list(request)
+            .unwrap_or_else(|| TenantId::new(DEFAULT_LIST).expect("the default list id is valid"));
+        self.quota.for_list(&list)
+    }
+
+    async fn sorted_todos(&self, todos: Arc<dyn TodoRepository>) -> Result<Vec<Todo>, TodoError> {
+        let mut todos = GetTodosHandler::new(todos).get_todos().await?;
+        todos.sort_by(|a, b| {
+            a.created_at
+                .cmp(&b.created_at)
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        Ok(todos)
+    }
+
+    /// Publishes the `events` of a call on `list` to watchers and converts them for the
+    /// response
+    fn publish(&self, list: Option<&TenantId>, events: Vec<TodoEvent>) -> Vec<proto::TodoEvent> {
+        events
+            .into_iter()
+            .map(|event| {
+                let published = match list {
+                    Some(list) => event.clone().map_todo_ids(|id| list.scope(id).into()),
+                    None => event.clone(),
+                };
+                // Sending only fails when nobody is watching
+                let _ = self.events.send(published);
+                proto::TodoEvent::from(event)
+            })
+            .collect()
+    }
+}
+
+#[tonic::async_trait]
+impl TodoService for TodoGrpcService {
+    async fn add_todo(
+        &self,
+        request: Request<proto::AddTodoRequest>,
+    ) -> Result<Response<proto::AddTodoResponse>, Status> {
+        let list = self.list(&request);
+        let todos = self.todos(&request);
+        let quota = self.quota(&request);
+        let (todo, events) = AddTodoHandler::new(todos)
+            .with_quota(quota)
+            .new_todo(request.into_inner().description)
+            .await
+            .map_err(to_status)?;
+        Ok(Response::new(proto::AddTodoResponse {
+            todo: Some(proto::Todo::from(&todo)),
+            events: self.publish(list.as_ref(), events),
+        }))
+    }
+
+    async fn get_todos(
+        &self,
+        request: Request<proto::GetTodosRequest>,
+    ) -> Result<Response<proto::GetTodosResponse>, Status> {
+        let todos = self.todos(&request);
+        let request = request.into_inner();
+        let state = state_from_proto(request.state)?;
+        let page_size = match request.page_size as usize {
+            0 => DEFAULT_PAGE_SIZE,
+            size => size.min(MAX_PAGE_SIZE),
+        };
+        let offset = match request.page_token.as_str() {
+            "" => 0,
+            token => token
+                .parse::<usize>()
+                .map_err(|_| Status::invalid_argument("invalid page token"))?,
+        };
+
+        let todos: Vec<Todo> = self
+            .sorted_todos(todos)
+            .await
+            .map_err(to_status)?
+            .into_iter()
+            .filter(|todo| state.is_none_or(|state| todo.state == state))
+            .collect();
+        let end = offset.saturating_add(page_size).min(todos.len());
+        let page = todos.get(offset..end).unwrap_or_default();
+        let next_page_token = if end < todos.len() {
+            end.to_string()
+        } else {
+            String::new()
+        };
+
+        Ok(Response::new(proto::GetTodosResponse {
+            todos: page.iter().map(proto::Todo::from).collect(),
+            next_page_token,
+        }))
+    }
+
+    async fn change_state(
+        &self,
+        request: Request<proto::ChangeStateRequest>,
+    ) -> Result<Response<proto::ChangeStateResponse>, Status> {
+        let list = self.list(&request);
+        let todos = self.todos(&request);
+        let request = request.into_inner();
+        let new_state = state_from_proto(request.state)?
+            .ok_or_else(|| Status::invalid_argument("state must be specified"))?;
+        let (todo, events) = ChangeTodoStateHandler::new(todos)
+            .change_state(request.id, new_state)
+            .await
+            .map_err(to_status)?;
+        Ok(Response::new(proto::ChangeStateResponse {
+            todo: Some(proto::Todo::from(&todo)),
+            events: self.publish(list.as_ref(), events),
+        }))
+    }
+
+    type WatchStream = Pin<Box<dyn Stream<Item = Result<proto::TodoEvent, Status>> + Send>>;
+
+    async fn watch(
+        &self,
+        request: Request<proto::WatchRequest>,
+    ) -> Result<Response<Self::WatchStream>, Status> {
+        let list = self.list(&request);
+        let stream =
+            BroadcastStream::new(self.events.subscribe()).filter_map(move |event| match event {
+                Ok(event) => match &list {
+                    Some(list) => {
+                        list.unscope(event.todo_id())?;
+                        let event = event.map_todo_ids(|id| list.unscope(id).unwrap_or(id).into());
+                        Some(Ok(proto::TodoEvent::from(event)))
+                    }
+                    None => Some(Ok(proto::TodoEvent::from(event))),
+                },
+                Err(BroadcastStreamRecvError::Lagged(missed)) => Some(Err(Status::data_loss(
+                    format!("watcher fell behind and missed {} events", missed),
+                ))),
+            });
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
@@ -0,0 +1,249 @@
+use grpc_todo::TodoGrpcService;
+use grpc_todo::proto::todo_event::Event;
+use grpc_todo::proto::todo_service_client::TodoServiceClient;
+use grpc_todo::proto::{
+    AddTodoRequest, ChangeStateRequest, GetTodosRequest, TodoState, WatchRequest,
+};
+use jsonwebtoken::{EncodingKey, Header, encode};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use todo::TenantId;
+use todo::application::access_control::AccessControl;
+use todo::application::quota::{Quota, QuotaPolicy};
+use todo::domain::access::{Membership, MembershipRepository, Role};
+use todo::infrastructure::jwt_authenticator::JwtAuthenticator;
+use todo::infrastructure::repositories::membership::InMemoryMembershipRepository;
+use todo::infrastructure::repositories::todo::TodoBackend;
+use tokio::net::TcpListener;
+use tokio_stream::StreamExt;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::{Channel, Server};
+use tonic::{Code, Request};
+
+const SECRET: &[u8] = b"test-secret";
+
+async fn start_server(access_control: Option<AccessControl>) -> TodoServiceClient<Channel> {
+    let service = TodoGrpcService::new(TodoBackend::Memory.repository());
+    serve(service, access_control).await
+}
+
+async fn serve(
+    service: TodoGrpcService,
+    access_control: Option<AccessControl>,
+) -> TodoServiceClient<Channel> {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let authenticator = Arc::new(JwtAuthenticator::with_secret(SECRET).with_audience("todo-api"));
+    let mut server = service.into_authenticated_server(authenticator);
+    if let Some(access_control) = access_control {
+        server = server.with_access_control(Arc::new(access_control));
+    }
+    tokio::spawn(
+        Server::builder()
+            .add_service(server)
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+    TodoServiceClient::connect(format!("http://{}", addr))
+        .await
+        .unwrap()
+}
+
+fn add_request(token: Option<&str>) -> Request<AddTodoRequest> {
+    let request = Request::new(AddTodoRequest {
+        description: "Write docs".to_string(),
+    });
+    match token {
+        Some(token) => authorized(request, token),
+        None => request,
+    }
+}
+
+fn authorized<T>(mut request: Request<T>, token: &str) -> Request<T> {
+    let value = format!("Bearer {}", token).parse().unwrap();
+    request.metadata_mut().insert("authorization", value);
+    request
+}
+
+fn token(scope: &str) -> String {
+    token_for("alice", "team", scope)
+}
+
+fn token_for(subject: &str, tenant: &str, scope: &str) -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let claims = json!({
+        "sub": subject,
+        "aud": "todo-api",
+        "exp": now + 300,
+        "scope": scope,
+        "tenant": tenant,
+    });
+    encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(SECRET),
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_calls_require_a_bearer_token_with_their_scope() {
+    // Arrange
+    let mut client = start_server(None).await;
+
+    // Act
+    let missing = client.add_todo(add_request(None)).await.unwrap_err();
+    let invalid = client
+        .add_todo(add_request(Some("not-a-jwt")))
+        .await
+        .unwrap_err();
+    let read_only = client
+        .add_todo(add_request(Some(&token("todos:read"))))
+        .await
+        .unwrap_err();
+    let added = client
+        .add_todo(add_request(Some(&token("todos:read todos:write"))))
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(missing.code(), Code::Unauthenticated);
+    assert_eq!(missing.message(), "missing bearer token");
+    assert_eq!(invalid.code(), Code::Unauthenticated);
+    assert_eq!(read_only.code(), Code::PermissionDenied);
+    assert_eq!(read_only.message(), "missing scope todos:write");
+    assert_eq!(added.into_inner().todo.unwrap().description, "Write docs");
+}
+
+#[tokio::test]
+async fn test_calls_require_a_role_that_permits_them() {
+    // Arrange
+    let memberships = Arc::new(InMemoryMembershipRepository::new());
+    let team = TenantId::new("team").unwrap();
+    memberships
+        .save(&Membership::new(team, "alice", Role::Viewer))
+        .await
+        .unwrap();
+    let mut client = start_server(Some(AccessControl::new(memberships))).await;
+    let token = token("todos:read todos:write");
+    let list = authorized(Request::new(GetTodosRequest::default()), &token);
+
+    // Act
+    let listed = client.get_todos(list).await;
+    let added = client
+        .add_todo(add_request(Some(&token)))
+        .await
+        .unwrap_err();
+
+    // Assert
+    assert!(listed.is_ok());
+    assert_eq!(added.code(), Code::PermissionDenied);
+    assert_eq!(
+        added.message(),
+        "forbidden: role viewer does not permit add on list team"
+    );
+}
+
+#[tokio::test]
+async fn test_calls_only_reach_the_todos_of_the_callers_list() {
+    // Arrange
+    let memberships = Arc::new(InMemoryMembershipRepository::new());
+    for (list, subject) in [("team", "alice"), ("other", "bob")] {
+        let list = TenantId::new(list).unwrap();
+        memberships
+            .save(&Membership::new(list, subject, Role::Editor))
+            .await
+            .unwrap();
+    }
+    let mut client = start_server(Some(AccessControl::new(memberships))).await;
+    let alice = token("todos:read todos:write");
+    let bob = token_for("bob", "other", "todos:read todos:write");
+    let added = client
+        .add_todo(add_request(Some(&bob)))
+        .await
+        .unwrap()
+        .into_inner()
+        .todo
+        .unwrap();
+    let change = ChangeStateRequest {
+        id: added.id.clone(),
+        state: TodoState::InProgress.into(),
+    };
+
+    // Act
+    let changed = client
+        .change_state(authorized(Request::new(change), &alice))
+        .await
+        .unwrap_err();
+    let listed = client
+        .get_todos(authorized(Request::new(GetTodosRequest::default()), &alice))
+        .await
+        .unwrap()
+        .into_inner();
+    let owned = client
+        .get_todos(authorized(Request::new(GetTodosRequest::default()), &bob))
+        .await
+        .unwrap()
+        .into_inner();
+
+    // Assert
+    assert_eq!(changed.code(), Code::NotFound);
+    assert!(listed.todos.is_empty());
+    assert_eq!(owned.todos, vec![added]);
+}
+
+#[tokio::test]
+async fn test_add_todo_is_refused_once_the_callers_list_reaches_its_quota() {
+    // Arrange
+    let one_todo = Quota {
+        max_open_todos: None,
+        max_todos: Some(1),
+    };
+    let quota = QuotaPolicy::default().with_list(TenantId::new("team").unwrap(), one_todo);
+    let service = TodoGrpcService::new(TodoBackend::Memory.repository()).with_quota(quota);
+    let mut client = serve(service, None).await;
+    let alice = token("todos:write");
+    let bob = token_for("bob", "other", "todos:write");
+    client.add_todo(add_request(Some(&alice))).await.unwrap();
+
+    // Act
+    let refused = client
+        .add_todo(add_request(Some(&alice)))
+        .await
+        .unwrap_err();
+    let other_list = client.add_todo(add_request(Some(&bob))).await;
+
+    // Assert
+    assert_eq!(refused.code(), Code::ResourceExhausted);
+    assert!(other_list.is_ok());
+}
+
+#[tokio::test]
+async fn test_watch_only_streams_the_events_of_the_callers_list() {
+    // Arrange
+    let mut client = start_server(None).await;
+    let alice = token("todos:read todos:write");
+    let bob = token_for("bob", "other", "todos:read todos:write");
+    let mut watch = client
+        .watch(authorized(Request::new(WatchRequest {}), &alice))
+        .await
+        .unwrap()
+        .into_inner();
+
+    // Act
+    client.add_todo(add_request(Some(&bob))).await.unwrap();
+    let added = client
+        .add_todo(add_request(Some(&alice)))
+        .await
+        .unwrap()
+        .into_inner()
+        .todo
+        .unwrap();
+    let event = watch.next().await.unwrap().unwrap().event;
+
+    // Assert
+    assert!(matches!(event, Some(Event::Created(created)) if created.id == added.id));
+}
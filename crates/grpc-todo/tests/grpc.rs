@@ -0,0 +1,152 @@
+use grpc_todo::TodoGrpcService;
+use grpc_todo::proto::todo_event::Event;
+use grpc_todo::proto::todo_service_client::TodoServiceClient;
+use grpc_todo::proto::{
+    AddTodoRequest, ChangeStateRequest, GetTodosRequest, TodoState, WatchRequest,
+};
+use todo::infrastructure::repositories::todo::TodoBackend;
+use tokio::net::TcpListener;
+use tokio_stream::StreamExt;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::Code;
+use tonic::transport::{Channel, Server};
+
+async fn start_server() -> TodoServiceClient<Channel> {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let service = TodoGrpcService::new(TodoBackend::Memory.repository());
+    tokio::spawn(
+        Server::builder()
+            .add_service(service.into_server())
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+    TodoServiceClient::connect(format!("http://{}", addr))
+        .await
+        .unwrap()
+}
+
+fn add_request(description: &str) -> AddTodoRequest {
+    AddTodoRequest {
+        description: description.to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_add_change_state_and_watch() {
+    // Arrange
+    let mut client = start_server().await;
+    let mut watch = client.watch(WatchRequest {}).await.unwrap().into_inner();
+
+    // Act
+    let added = client
+        .add_todo(add_request("Write docs"))
+        .await
+        .unwrap()
+        .into_inner();
+    let id = added.todo.unwrap().id;
+    let changed = client
+        .change_state(ChangeStateRequest {
+            id: id.clone(),
+            state: TodoState::InProgress.into(),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+
+    // Assert
+    assert_eq!(changed.todo.unwrap().state(), TodoState::InProgress);
+    assert!(
+        matches!(watch.next().await.unwrap().unwrap().event, Some(Event::Created(created)) if created.id == id)
+    );
+    let Some(Event::StateChanged(state_changed)) = watch.next().await.unwrap().unwrap().event
+    else {
+        panic!("Expected a state changed event");
+    };
+    assert_eq!(state_changed.from_state(), TodoState::Todo);
+    assert_eq!(state_changed.to_state(), TodoState::InProgress);
+}
+
+#[tokio::test]
+async fn test_get_todos_pages_through_todos() {
+    // Arrange
+    let mut client = start_server().await;
+    for description in ["First", "Second", "Third"] {
+        client.add_todo(add_request(description)).await.unwrap();
+    }
+
+    // Act
+    let first_page = client
+        .get_todos(GetTodosRequest {
+            page_size: 2,
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    let second_page = client
+        .get_todos(GetTodosRequest {
+            page_size: 2,
+            page_token: first_page.next_page_token.clone(),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .into_inner();
+
+    // Assert
+    let descriptions: Vec<String> = first_page
+        .todos
+        .iter()
+        .chain(&second_page.todos)
+        .map(|todo| todo.description.clone())
+        .collect();
+    assert_eq!(descriptions, ["First", "Second", "Third"]);
+    assert!(!first_page.next_page_token.is_empty());
+    assert!(second_page.next_page_token.is_empty());
+}
+
+#[tokio::test]
+async fn test_errors_map_to_status_codes() {
+    // Arrange
+    let mut client = start_server().await;
+    let id = client
+        .add_todo(add_request("Write docs"))
+        .await
+        .unwrap()
+        .into_inner()
+        .todo
+        .unwrap()
+        .id;
+
+    // Act & Assert
+    let status = client.add_todo(add_request("")).await.unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+    assert_eq!(status.message(), "todo description must not be empty");
+
+    let status = client
+        .change_state(ChangeStateRequest {
+            id,
+            state: TodoState::Todo.into(),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::FailedPrecondition);
+
+    let status = client
+        .change_state(ChangeStateRequest {
+            id: "non-existent-id".to_string(),
+            state: TodoState::Done.into(),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::NotFound);
+
+    let status = client
+        .get_todos(GetTodosRequest {
+            page_token: "not-a-token".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+}
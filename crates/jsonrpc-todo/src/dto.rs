@@ -0,0 +1,61 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use todo::infrastructure::serialization::json_schema;
+use todo::{Todo, TodoEvent, TodoState};
+
+/// Params of `add_todo`
+#[derive(Deserialize, JsonSchema)]
+pub struct AddTodoParams {
+    pub description: String,
+}
+
+/// Params of `get_todos`
+#[derive(Deserialize, Default, JsonSchema)]
+pub struct GetTodosParams {
+    pub state: Option<TodoState>,
+}
+
+/// Params of `change_state`
+#[derive(Deserialize, JsonSchema)]
+pub struct ChangeStateParams {
+    pub id: String,
+    pub state: TodoState,
+}
+
+/// Params of `delete_todo`
+#[derive(Deserialize, JsonSchema)]
+pub struct DeleteTodoParams {
+    pub id: String,
+}
+
+/// Result of `add_todo` and `change_state`: the todo as saved and the events it emitted
+#[derive(Serialize, JsonSchema)]
+pub struct TodoChange {
+    pub todo: Todo,
+    pub events: Vec<TodoEvent>,
+}
+
+impl From<(Todo, Vec<TodoEvent>)> for TodoChange {
+    fn from((todo, events): (Todo, Vec<TodoEvent>)) -> Self {
+        TodoChange { todo, events }
+    }
+}
+
+/// JSON Schemas of every method's params and of the domain types in results, keyed by type name
+pub fn schemas() -> BTreeMap<&'static str, Value> {
+    let mut schemas = json_schema::schemas();
+    schemas.insert("AddTodoParams", json_schema::schema::<AddTodoParams>());
+    schemas.insert("GetTodosParams", json_schema::schema::<GetTodosParams>());
+    schemas.insert(
+        "ChangeStateParams",
+        json_schema::schema::<ChangeStateParams>(),
+    );
+    schemas.insert(
+        "DeleteTodoParams",
+        json_schema::schema::<DeleteTodoParams>(),
+    );
+    schemas.insert("TodoChange", json_schema::schema::<TodoChange>());
+    schemas
+}
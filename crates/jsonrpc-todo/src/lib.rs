@@ -0,0 +1,6 @@
+pub mod dto;
+pub mod protocol;
+pub mod server;
+pub mod transport;
+
+pub use server::RpcServer;
@@ -0,0 +1,49 @@
+use jsonrpc_todo::RpcServer;
+use jsonrpc_todo::transport::{bind_tcp, serve_stdio};
+use std::process::ExitCode;
+use std::sync::Arc;
+use todo::TenantId;
+use todo::application::access_control::DEFAULT_LIST;
+use todo::infrastructure::config::TodoConfig;
+use todo::infrastructure::repositories::todo::TodoBackend;
+
+const USAGE: &str = "usage: jsonrpc-todo [--tcp <ADDR>]";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let tcp_addr = match args.as_slice() {
+        [] => None,
+        [flag, addr] if flag == "--tcp" => Some(addr.clone()),
+        _ => {
+            eprintln!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    let config = match TodoConfig::load() {
+        Ok(config) => config,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            return ExitCode::FAILURE;
+        }
+    };
+    let backend = config.backend.unwrap_or(TodoBackend::Memory);
+
+    // The server serves a single list, so it gets the quota of the `default` list
+    let list = TenantId::new(DEFAULT_LIST).expect("the default list id is valid");
+    let server =
+        Arc::new(RpcServer::new(backend.repository()).with_quota(config.quota.for_list(&list)));
+    let result = match tcp_addr {
+        Some(addr) => {
+            eprintln!("Listening on {} ({:?} backend)", addr, backend);
+            bind_tcp(server, addr)
+        }
+        None => serve_stdio(&*server),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
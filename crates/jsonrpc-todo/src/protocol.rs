@@ -0,0 +1,153 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use todo::TodoError;
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+pub const EMPTY_DESCRIPTION: i64 = TodoError::EmptyDescription.jsonrpc_code();
+pub const INVALID_STATE_TRANSITION: i64 = TodoError::InvalidStateTransition.jsonrpc_code();
+pub const TODO_NOT_FOUND: i64 = TodoError::TodoNotFound.jsonrpc_code();
+pub const REPOSITORY_ERROR: i64 = REPOSITORY_FAILURE.jsonrpc_code();
+pub const QUOTA_EXCEEDED: i64 = TodoError::QuotaExceeded.jsonrpc_code();
+
+const REPOSITORY_FAILURE: &TodoError = &TodoError::RepositoryError(String::new());
+
+/// A JSON-RPC 2.0 request; a request without `id` is a notification
+#[derive(Debug, Deserialize)]
+pub struct RpcRequest {
+    pub jsonrpc: String,
+    pub method: String,
+    #[serde(default)]
+    pub params: Option<Value>,
+    #[serde(default, deserialize_with = "present")]
+    pub id: Option<Value>,
+}
+
+/// Keeps `"id": null` distinct from a missing id, which marks a notification
+fn present<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<Value>, D::Error> {
+    Value::deserialize(deserializer).map(Some)
+}
+
+/// A JSON-RPC 2.0 response carrying either `result` or `error`
+#[derive(Debug, Serialize)]
+pub struct RpcResponse {
+    pub jsonrpc: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+    pub id: Value,
+}
+
+impl RpcResponse {
+    pub fn success(id: Value, result: Value) -> Self {
+        RpcResponse {
+            jsonrpc: "2.0",
+            result: Some(result),
+            error: None,
+            id,
+        }
+    }
+
+    pub fn failure(id: Value, error: RpcError) -> Self {
+        RpcResponse {
+            jsonrpc: "2.0",
+            result: None,
+            error: Some(error),
+            id,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl RpcError {
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        RpcError {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+}
+
+impl From<TodoError> for RpcError {
+    fn from(err: TodoError) -> Self {
+        RpcError {
+            code: err.jsonrpc_code(),
+            message: err.to_string(),
+            data: Some(serde_json::json!({ "error": err.code() })),
+        }
+    }
+}
+
+/// Method table behind a JSON-RPC endpoint
+pub trait RpcDispatcher: Send + Sync {
+    /// Runs `method`; the result is discarded when the call was a notification
+    fn dispatch(&self, method: &str, params: Option<Value>) -> Result<Value, RpcError>;
+}
+
+/// Handles one message, a single request or a batch, and returns the serialized reply
+///
+/// Returns `None` when nothing must be sent back: for a notification, or a batch made
+/// only of notifications.
+pub fn handle_message(dispatcher: &impl RpcDispatcher, message: &str) -> Option<String> {
+    let reply = match serde_json::from_str::<Value>(message) {
+        Err(err) => Some(to_json(RpcResponse::failure(
+            Value::Null,
+            RpcError::new(PARSE_ERROR, format!("parse error: {}", err)),
+        ))),
+        Ok(Value::Array(batch)) if batch.is_empty() => Some(to_json(RpcResponse::failure(
+            Value::Null,
+            RpcError::new(INVALID_REQUEST, "empty batch"),
+        ))),
+        Ok(Value::Array(batch)) => {
+            let responses: Vec<Value> = batch
+                .into_iter()
+                .filter_map(|call| handle_call(dispatcher, call))
+                .map(to_json)
+                .collect();
+            (!responses.is_empty()).then_some(Value::Array(responses))
+        }
+        Ok(call) => handle_call(dispatcher, call).map(to_json),
+    };
+    reply.map(|reply| reply.to_string())
+}
+
+fn handle_call(dispatcher: &impl RpcDispatcher, call: Value) -> Option<RpcResponse> {
+    let request: RpcRequest = match serde_json::from_value(call) {
+        Ok(request) => request,
+        Err(err) => {
+            return Some(RpcResponse::failure(
+                Value::Null,
+                RpcError::new(INVALID_REQUEST, format!("invalid request: {}", err)),
+            ));
+        }
+    };
+    if request.jsonrpc != "2.0" {
+        let id = request.id.unwrap_or(Value::Null);
+        return Some(RpcResponse::failure(
+            id,
+            RpcError::new(INVALID_REQUEST, "jsonrpc must be \"2.0\""),
+        ));
+    }
+
+    let result = dispatcher.dispatch(&request.method, request.params);
+    let id = request.id?;
+    Some(match result {
+        Ok(result) => RpcResponse::success(id, result),
+        Err(error) => RpcResponse::failure(id, error),
+    })
+}
+
+fn to_json(response: RpcResponse) -> Value {
+    serde_json::to_value(response).expect("responses only hold JSON values and strings")
+}
@@ -0,0 +1,97 @@
+use futures::executor::block_on;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::sync::Arc;
+use todo::application::add_todo_handler::AddTodoHandler;
+use todo::application::change_todo_state_handler::ChangeTodoStateHandler;
+use todo::application::delete_todo_handler::DeleteTodoHandler;
+use todo::application::get_todos_handler::GetTodosHandler;
+use todo::application::quota::Quota;
+use todo::{Todo, TodoError, TodoRepository};
+
+use crate::dto::{AddTodoParams, ChangeStateParams, DeleteTodoParams, GetTodosParams, TodoChange};
+use crate::protocol::{INVALID_PARAMS, METHOD_NOT_FOUND, RpcDispatcher, RpcError, handle_message};
+
+/// Dispatches JSON-RPC 2.0 messages to the application handlers
+pub struct RpcServer {
+    add_todo_handler: AddTodoHandler<Arc<dyn TodoRepository>>,
+    get_todos_handler: GetTodosHandler<Arc<dyn TodoRepository>>,
+    change_todo_state_handler: ChangeTodoStateHandler<Arc<dyn TodoRepository>>,
+    delete_todo_handler: DeleteTodoHandler<Arc<dyn TodoRepository>>,
+}
+
+impl RpcServer {
+    pub fn new(repository: Arc<dyn TodoRepository>) -> Self {
+        RpcServer {
+            add_todo_handler: AddTodoHandler::new(repository.clone()),
+            get_todos_handler: GetTodosHandler::new(repository.clone()),
+            change_todo_state_handler: ChangeTodoStateHandler::new(repository.clone()),
+            delete_todo_handler: DeleteTodoHandler::new(repository),
+        }
+    }
+
+    /// Refuses `add_todo` past `quota`
+    pub fn with_quota(self, quota: Quota) -> Self {
+        RpcServer {
+            add_todo_handler: self.add_todo_handler.with_quota(quota),
+            ..self
+        }
+    }
+
+    /// Handles one message, a single request or a batch; see [`handle_message`]
+    pub fn handle_message(&self, message: &str) -> Option<String> {
+        handle_message(self, message)
+    }
+}
+
+impl RpcDispatcher for RpcServer {
+    fn dispatch(&self, method: &str, params: Option<Value>) -> Result<Value, RpcError> {
+        match method {
+            "add_todo" => {
+                let params: AddTodoParams = parse_params(params)?;
+                let change = block_on(self.add_todo_handler.new_todo(params.description))?;
+                to_result(TodoChange::from(change))
+            }
+            "get_todos" => {
+                let params: GetTodosParams = match params {
+                    None => GetTodosParams::default(),
+                    params => parse_params(params)?,
+                };
+                let mut todos = block_on(self.get_todos_handler.get_todos())?;
+                todos.sort_by_key(|todo| todo.created_at);
+                let todos: Vec<&Todo> = todos
+                    .iter()
+                    .filter(|todo| params.state.is_none_or(|state| todo.state == state))
+                    .collect();
+                to_result(todos)
+            }
+            "change_state" => {
+                let params: ChangeStateParams = parse_params(params)?;
+                let change = block_on(
+                    self.change_todo_state_handler
+                        .change_state(params.id, params.state),
+                )?;
+                to_result(TodoChange::from(change))
+            }
+            "delete_todo" => {
+                let params: DeleteTodoParams = parse_params(params)?;
+                block_on(self.delete_todo_handler.delete_todo(params.id))?;
+                Ok(Value::Null)
+            }
+            _ => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("method not found: {}", method),
+            )),
+        }
+    }
+}
+
+pub fn parse_params<T: DeserializeOwned>(params: Option<Value>) -> Result<T, RpcError> {
+    serde_json::from_value(params.unwrap_or(Value::Null))
+        .map_err(|err| RpcError::new(INVALID_PARAMS, format!("invalid params: {}", err)))
+}
+
+pub fn to_result<T: Serialize>(value: T) -> Result<Value, RpcError> {
+    serde_json::to_value(value).map_err(|err| TodoError::RepositoryError(err.to_string()).into())
+}
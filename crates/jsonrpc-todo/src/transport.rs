@@ -0,0 +1,61 @@
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, ToSocketAddrs};
+use std::sync::Arc;
+use std::thread;
+
+use crate::protocol::{RpcDispatcher, handle_message};
+
+/// Serves newline-delimited JSON-RPC messages from `input`, writing replies to `output`
+///
+/// Each line holds one request or batch; each reply is written as one line. Returns
+/// when `input` reaches end of file.
+pub fn serve_lines(
+    dispatcher: &impl RpcDispatcher,
+    input: impl BufRead,
+    mut output: impl Write,
+) -> io::Result<()> {
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(reply) = handle_message(dispatcher, &line) {
+            writeln!(output, "{}", reply)?;
+            output.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// Serves JSON-RPC over stdin and stdout
+pub fn serve_stdio(dispatcher: &impl RpcDispatcher) -> io::Result<()> {
+    serve_lines(dispatcher, io::stdin().lock(), io::stdout().lock())
+}
+
+/// Accepts TCP connections on `listener`, serving each on its own thread
+pub fn serve_tcp<D: RpcDispatcher + 'static>(
+    dispatcher: Arc<D>,
+    listener: TcpListener,
+) -> io::Result<()> {
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let dispatcher = dispatcher.clone();
+        thread::spawn(move || {
+            let reader = match stream.try_clone() {
+                Ok(reader) => BufReader::new(reader),
+                Err(_) => return,
+            };
+            // The connection is closed on any I/O error
+            let _ = serve_lines(&*dispatcher, reader, stream);
+        });
+    }
+    Ok(())
+}
+
+/// Binds `addr` and serves JSON-RPC over TCP
+pub fn bind_tcp<D: RpcDispatcher + 'static>(
+    dispatcher: Arc<D>,
+    addr: impl ToSocketAddrs,
+) -> io::Result<()> {
+    serve_tcp(dispatcher, TcpListener::bind(addr)?)
+}
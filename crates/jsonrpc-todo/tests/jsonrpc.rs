@@ -0,0 +1,178 @@
+use jsonrpc_todo::RpcServer;
+use jsonrpc_todo::transport::{serve_lines, serve_tcp};
+use serde_json::{Value, json};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+use std::thread;
+use todo::application::quota::Quota;
+use todo::infrastructure::repositories::todo::TodoBackend;
+
+fn server() -> RpcServer {
+    RpcServer::new(TodoBackend::Memory.repository())
+}
+
+fn call(server: &RpcServer, request: Value) -> Value {
+    serde_json::from_str(&server.handle_message(&request.to_string()).unwrap()).unwrap()
+}
+
+#[test]
+fn test_add_change_state_and_list_todos() {
+    // Arrange
+    let server = server();
+
+    // Act
+    let added = call(
+        &server,
+        json!({ "jsonrpc": "2.0", "id": 1, "method": "add_todo", "params": { "description": "Write docs" } }),
+    );
+    let id = added["result"]["todo"]["id"].clone();
+    let changed = call(
+        &server,
+        json!({ "jsonrpc": "2.0", "id": 2, "method": "change_state", "params": { "id": id, "state": "IN_PROGRESS" } }),
+    );
+    let listed = call(
+        &server,
+        json!({ "jsonrpc": "2.0", "id": "list", "method": "get_todos", "params": { "state": "IN_PROGRESS" } }),
+    );
+
+    // Assert
+    assert_eq!(added["id"], 1);
+    assert_eq!(added["result"]["events"][0]["type"], "TODO_CREATED");
+    assert_eq!(changed["result"]["todo"]["state"], "IN_PROGRESS");
+    assert_eq!(changed["result"]["events"][0]["to_state"], "IN_PROGRESS");
+    assert_eq!(listed["id"], "list");
+    assert_eq!(listed["result"][0]["id"], id);
+    assert_eq!(listed["result"][0]["state"], "IN_PROGRESS");
+}
+
+#[test]
+fn test_batch_skips_notifications() {
+    // Arrange
+    let server = server();
+    let batch = json!([
+        { "jsonrpc": "2.0", "method": "add_todo", "params": { "description": "Notified" } },
+        { "jsonrpc": "2.0", "id": 1, "method": "get_todos" },
+        { "jsonrpc": "2.0", "id": 2, "method": "unknown" },
+        42
+    ]);
+
+    // Act
+    let responses = call(&server, batch);
+
+    // Assert
+    let responses = responses.as_array().unwrap();
+    assert_eq!(responses.len(), 3);
+    assert_eq!(responses[0]["result"][0]["description"], "Notified");
+    assert_eq!(responses[1]["error"]["code"], -32601);
+    assert_eq!(responses[2]["error"]["code"], -32600);
+    assert_eq!(responses[2]["id"], Value::Null);
+    assert!(
+        server
+            .handle_message(r#"[{"jsonrpc":"2.0","method":"get_todos"}]"#)
+            .is_none()
+    );
+}
+
+#[test]
+fn test_add_todo_past_the_quota_fails() {
+    // Arrange
+    let server = server().with_quota(Quota {
+        max_open_todos: Some(1),
+        max_todos: None,
+    });
+    let add = |id: u64| json!({ "jsonrpc": "2.0", "id": id, "method": "add_todo", "params": { "description": "Write docs" } });
+    call(&server, add(1));
+
+    // Act
+    let response = call(&server, add(2));
+
+    // Assert
+    assert_eq!(response["error"]["code"], -32005);
+    assert_eq!(response["error"]["data"]["error"], "QUOTA_EXCEEDED");
+}
+
+#[test]
+fn test_errors_use_json_rpc_codes() {
+    // Arrange
+    let server = server();
+
+    // Act & Assert
+    let parse_error: Value =
+        serde_json::from_str(&server.handle_message("{not json").unwrap()).unwrap();
+    assert_eq!(parse_error["error"]["code"], -32700);
+
+    let response = call(
+        &server,
+        json!({ "jsonrpc": "2.0", "id": 1, "method": "add_todo", "params": {} }),
+    );
+    assert_eq!(response["error"]["code"], -32602);
+
+    let response = call(
+        &server,
+        json!({ "jsonrpc": "2.0", "id": 2, "method": "add_todo", "params": { "description": "" } }),
+    );
+    assert_eq!(response["error"]["code"], -32001);
+    assert_eq!(response["error"]["data"]["error"], "EMPTY_DESCRIPTION");
+
+    let response = call(
+        &server,
+        json!({ "jsonrpc": "2.0", "id": 3, "method": "delete_todo", "params": { "id": "non-existent-id" } }),
+    );
+    assert_eq!(response["error"]["code"], -32003);
+    assert_eq!(response["error"]["message"], "todo not found");
+}
+
+#[test]
+fn test_line_and_tcp_transports() {
+    // Arrange
+    let input = "{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"get_todos\"}\n\n";
+    let mut output = Vec::new();
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    thread::spawn(move || serve_tcp(Arc::new(server()), listener));
+
+    // Act
+    serve_lines(&server(), input.as_bytes(), &mut output).unwrap();
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream.write_all(input.as_bytes()).unwrap();
+    let mut tcp_reply = String::new();
+    BufReader::new(stream).read_line(&mut tcp_reply).unwrap();
+
+    // Assert
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "{\"id\":1,\"jsonrpc\":\"2.0\",\"result\":[]}\n"
+    );
+    assert_eq!(tcp_reply, "{\"id\":1,\"jsonrpc\":\"2.0\",\"result\":[]}\n");
+}
+
+#[test]
+fn test_schemas_cover_params_and_results() {
+    // Act
+    let schemas = jsonrpc_todo::dto::schemas();
+
+    // Assert
+    let change_state = &schemas["ChangeStateParams"];
+    assert_eq!(change_state["required"], json!(["id", "state"]));
+    assert_eq!(
+        change_state["properties"]["state"]["$ref"],
+        "#/$defs/TodoState"
+    );
+    assert_eq!(
+        change_state["$defs"]["TodoState"]["oneOf"][1]["const"],
+        "IN_PROGRESS"
+    );
+    assert_eq!(schemas["TodoChange"]["required"], json!(["todo", "events"]));
+    for name in [
+        "AddTodoParams",
+        "GetTodosParams",
+        "DeleteTodoParams",
+        "TodoChange",
+        "Todo",
+        "TodoEvent",
+        "TodoError",
+    ] {
+        assert!(schemas.contains_key(name), "missing {}", name);
+    }
+}
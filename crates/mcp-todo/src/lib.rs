@@ -0,0 +1,4 @@
+pub mod server;
+pub mod tools;
+
+pub use server::McpServer;
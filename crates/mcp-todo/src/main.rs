@@ -0,0 +1,39 @@
+use jsonrpc_todo::transport::serve_stdio;
+use mcp_todo::McpServer;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use todo::TenantId;
+use todo::application::access_control::DEFAULT_LIST;
+use todo::infrastructure::config::TodoConfig;
+use todo::infrastructure::repositories::todo::TodoBackend;
+
+fn main() -> ExitCode {
+    let config = match TodoConfig::load() {
+        Ok(config) => config,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            return ExitCode::FAILURE;
+        }
+    };
+    // Share the hk-todo CLI's list by default
+    let backend = config.backend.unwrap_or_else(|| {
+        TodoBackend::File(
+            std::env::var_os("HOME")
+                .map(PathBuf::from)
+                .unwrap_or_default()
+                .join(".hk-todo.json"),
+        )
+    });
+
+    // stdout carries the protocol, so diagnostics go to stderr. Like the CLI, the server
+    // serves a single list and gets the quota of the `default` list.
+    let list = TenantId::new(DEFAULT_LIST).expect("the default list id is valid");
+    let server = McpServer::new(backend.repository()).with_quota(config.quota.for_list(&list));
+    match serve_stdio(&server) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
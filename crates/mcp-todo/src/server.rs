@@ -0,0 +1,183 @@
+use futures::executor::block_on;
+use jsonrpc_todo::protocol::{INVALID_PARAMS, METHOD_NOT_FOUND, RpcDispatcher, RpcError};
+use jsonrpc_todo::server::parse_params;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use std::sync::Arc;
+use todo::application::add_todo_handler::AddTodoHandler;
+use todo::application::change_todo_state_handler::ChangeTodoStateHandler;
+use todo::application::get_todos_handler::GetTodosHandler;
+use todo::application::quota::Quota;
+use todo::{Todo, TodoError, TodoRepository, TodoState};
+
+use crate::tools::{self, AddTodoArgs, CompleteTodoArgs, ListTodosArgs, SearchTodosArgs};
+
+/// MCP protocol revisions this server speaks, newest first
+pub const PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
+
+/// Params of `initialize`
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InitializeParams {
+    protocol_version: String,
+}
+
+/// Params of `tools/call`
+#[derive(Deserialize)]
+struct CallToolParams {
+    name: String,
+    #[serde(default)]
+    arguments: Option<Value>,
+}
+
+/// Model Context Protocol server exposing the todo list as tools
+///
+/// Only additive tools are offered: an assistant can add and complete todos, but not
+/// delete them or move them backwards.
+pub struct McpServer {
+    add_todo_handler: AddTodoHandler<Arc<dyn TodoRepository>>,
+    get_todos_handler: GetTodosHandler<Arc<dyn TodoRepository>>,
+    change_todo_state_handler: ChangeTodoStateHandler<Arc<dyn TodoRepository>>,
+}
+
+impl McpServer {
+    pub fn new(repository: Arc<dyn TodoRepository>) -> Self {
+        McpServer {
+            add_todo_handler: AddTodoHandler::new(repository.clone()),
+            get_todos_handler: GetTodosHandler::new(repository.clone()),
+            change_todo_state_handler: ChangeTodoStateHandler::new(repository),
+        }
+    }
+
+    /// Refuses `add_todo` past `quota`, reporting a tool error
+    pub fn with_quota(self, quota: Quota) -> Self {
+        McpServer {
+            add_todo_handler: self.add_todo_handler.with_quota(quota),
+            ..self
+        }
+    }
+
+    fn initialize(&self, params: Option<Value>) -> Result<Value, RpcError> {
+        let params: InitializeParams = parse_params(params)?;
+        // Answer with the client's revision when supported, otherwise with our newest
+        let version = PROTOCOL_VERSIONS
+            .iter()
+            .find(|version| **version == params.protocol_version)
+            .unwrap_or(&PROTOCOL_VERSIONS[0]);
+        Ok(json!({
+            "protocolVersion": version,
+            "capabilities": { "tools": {} },
+            "serverInfo": { "name": "hk-todo", "version": env!("CARGO_PKG_VERSION") }
+        }))
+    }
+
+    fn call_tool(&self, params: Option<Value>) -> Result<Value, RpcError> {
+        let params: CallToolParams = parse_params(params)?;
+        let arguments = params.arguments;
+        let result = match params.name.as_str() {
+            "list_todos" => {
+                let args: ListTodosArgs = tool_args(arguments)?;
+                self.todos().map(|todos| {
+                    let todos = todos
+                        .iter()
+                        .filter(|todo| args.state.is_none_or(|state| todo.state == state));
+                    json!({ "todos": todos.collect::<Vec<_>>() })
+                })
+            }
+            "add_todo" => {
+                let args: AddTodoArgs = tool_args(arguments)?;
+                self.add_todo(args.description)
+                    .map(|todo| json!({ "todo": todo }))
+            }
+            "complete_todo" => {
+                let args: CompleteTodoArgs = tool_args(arguments)?;
+                self.complete_todo(&args.id)
+                    .map(|todo| json!({ "todo": todo }))
+            }
+            "search_todos" => {
+                let args: SearchTodosArgs = tool_args(arguments)?;
+                let query = args.query.to_lowercase();
+                self.todos().map(|todos| {
+                    let todos = todos
+                        .iter()
+                        .filter(|todo| todo.description.to_lowercase().contains(&query));
+                    json!({ "todos": todos.collect::<Vec<_>>() })
+                })
+            }
+            name => {
+                return Err(RpcError::new(
+                    INVALID_PARAMS,
+                    format!("unknown tool: {}", name),
+                ));
+            }
+        };
+
+        // Domain failures are reported to the model as tool errors, not protocol errors
+        Ok(match result {
+            Ok(structured) => json!({
+                "content": [{ "type": "text", "text": structured.to_string() }],
+                "structuredContent": structured,
+                "isError": false
+            }),
+            Err(err) => json!({
+                "content": [{ "type": "text", "text": err.to_string() }],
+                "isError": true
+            }),
+        })
+    }
+
+    fn todos(&self) -> Result<Vec<Todo>, TodoError> {
+        let mut todos = block_on(self.get_todos_handler.get_todos())?;
+        todos.sort_by_key(|todo| todo.created_at);
+        Ok(todos)
+    }
+
+    fn find_todo(&self, id: &str) -> Result<Todo, TodoError> {
+        self.todos()?
+            .into_iter()
+            .find(|todo| &*todo.id == id)
+            .ok_or(TodoError::TodoNotFound)
+    }
+
+    fn add_todo(&self, description: String) -> Result<Todo, TodoError> {
+        let (todo, _) = block_on(self.add_todo_handler.new_todo(description))?;
+        Ok(todo)
+    }
+
+    fn complete_todo(&self, id: &str) -> Result<Todo, TodoError> {
+        let todo = self.find_todo(id)?;
+        if todo.state == TodoState::Todo {
+            block_on(
+                self.change_todo_state_handler
+                    .change_state(todo.id.to_string(), TodoState::InProgress),
+            )?;
+        }
+        let (todo, _) = block_on(
+            self.change_todo_state_handler
+                .change_state(todo.id.to_string(), TodoState::Done),
+        )?;
+        Ok(todo)
+    }
+}
+
+impl RpcDispatcher for McpServer {
+    fn dispatch(&self, method: &str, params: Option<Value>) -> Result<Value, RpcError> {
+        match method {
+            "initialize" => self.initialize(params),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": tools::definitions() })),
+            "tools/call" => self.call_tool(params),
+            method if method.starts_with("notifications/") => Ok(Value::Null),
+            _ => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("method not found: {}", method),
+            )),
+        }
+    }
+}
+
+fn tool_args<T: DeserializeOwned>(arguments: Option<Value>) -> Result<T, RpcError> {
+    serde_json::from_value(arguments.unwrap_or_else(|| json!({})))
+        .map_err(|err| RpcError::new(INVALID_PARAMS, format!("invalid arguments: {}", err)))
+}
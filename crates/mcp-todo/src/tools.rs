@@ -0,0 +1,128 @@
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+/// Arguments of `list_todos`
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ListTodosArgs {
+    pub state: Option<todo::TodoState>,
+}
+
+/// Arguments of `add_todo`
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AddTodoArgs {
+    pub description: String,
+}
+
+/// Arguments of `complete_todo`
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CompleteTodoArgs {
+    pub id: String,
+}
+
+/// Arguments of `search_todos`
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SearchTodosArgs {
+    pub query: String,
+}
+
+fn todo_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "id": { "type": "string" },
+            "description": { "type": "string" },
+            "state": { "type": "string", "enum": ["TODO", "IN_PROGRESS", "DONE"] },
+            "created_at": { "type": "string", "format": "date-time" }
+        },
+        "required": ["id", "description", "state", "created_at"]
+    })
+}
+
+fn todos_output_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": { "todos": { "type": "array", "items": todo_schema() } },
+        "required": ["todos"]
+    })
+}
+
+fn todo_output_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": { "todo": todo_schema() },
+        "required": ["todo"]
+    })
+}
+
+/// Tool definitions returned by `tools/list`
+pub fn definitions() -> Value {
+    json!([
+        {
+            "name": "list_todos",
+            "title": "List todos",
+            "description": "List the user's todos, oldest first, optionally only those in one state.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "state": {
+                        "type": "string",
+                        "enum": ["TODO", "IN_PROGRESS", "DONE"],
+                        "description": "Only return todos in this state"
+                    }
+                },
+                "additionalProperties": false
+            },
+            "outputSchema": todos_output_schema(),
+            "annotations": { "readOnlyHint": true }
+        },
+        {
+            "name": "add_todo",
+            "title": "Add todo",
+            "description": "Add a todo to the user's list. It starts in the TODO state.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "description": { "type": "string", "minLength": 1, "description": "What needs to be done" }
+                },
+                "required": ["description"],
+                "additionalProperties": false
+            },
+            "outputSchema": todo_output_schema(),
+            "annotations": { "readOnlyHint": false, "destructiveHint": false, "idempotentHint": false }
+        },
+        {
+            "name": "complete_todo",
+            "title": "Complete todo",
+            "description": "Mark a todo as DONE, starting it first if it is still TODO. Fails if it is already DONE.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "id": { "type": "string", "description": "Id of the todo, as returned by list_todos" }
+                },
+                "required": ["id"],
+                "additionalProperties": false
+            },
+            "outputSchema": todo_output_schema(),
+            "annotations": { "readOnlyHint": false, "destructiveHint": false, "idempotentHint": false }
+        },
+        {
+            "name": "search_todos",
+            "title": "Search todos",
+            "description": "Find todos whose description contains the query, ignoring case.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string", "description": "Text to look for" }
+                },
+                "required": ["query"],
+                "additionalProperties": false
+            },
+            "outputSchema": todos_output_schema(),
+            "annotations": { "readOnlyHint": true }
+        }
+    ])
+}
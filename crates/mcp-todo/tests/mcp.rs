@@ -0,0 +1,135 @@
+use jsonrpc_todo::protocol::handle_message;
+use mcp_todo::McpServer;
+use serde_json::{Value, json};
+use todo::application::quota::Quota;
+use todo::infrastructure::repositories::todo::TodoBackend;
+
+fn request(server: &McpServer, id: u64, method: &str, params: Value) -> Value {
+    let message = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+    serde_json::from_str(&handle_message(server, &message.to_string()).unwrap()).unwrap()
+}
+
+fn call_tool(server: &McpServer, name: &str, arguments: Value) -> Value {
+    request(
+        server,
+        1,
+        "tools/call",
+        json!({ "name": name, "arguments": arguments }),
+    )["result"]
+        .clone()
+}
+
+#[test]
+fn test_initialize_and_list_tools() {
+    // Arrange
+    let server = McpServer::new(TodoBackend::Memory.repository());
+
+    // Act
+    let initialized = request(
+        &server,
+        1,
+        "initialize",
+        json!({
+            "protocolVersion": "2025-03-26",
+            "capabilities": {},
+            "clientInfo": { "name": "test", "version": "1.0" }
+        }),
+    );
+    let notification = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+    let notification_reply = handle_message(&server, &notification.to_string());
+    let tools = request(&server, 2, "tools/list", json!({}));
+
+    // Assert
+    assert_eq!(initialized["result"]["protocolVersion"], "2025-03-26");
+    assert_eq!(initialized["result"]["serverInfo"]["name"], "hk-todo");
+    assert!(notification_reply.is_none());
+    let names: Vec<&str> = tools["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|tool| tool["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        names,
+        ["list_todos", "add_todo", "complete_todo", "search_todos"]
+    );
+}
+
+#[test]
+fn test_add_search_and_complete_todos() {
+    // Arrange
+    let server = McpServer::new(TodoBackend::Memory.repository());
+
+    // Act
+    let added = call_tool(&server, "add_todo", json!({ "description": "Write docs" }));
+    call_tool(&server, "add_todo", json!({ "description": "Buy milk" }));
+    let id = added["structuredContent"]["todo"]["id"].clone();
+    let found = call_tool(&server, "search_todos", json!({ "query": "DOCS" }));
+    let completed = call_tool(&server, "complete_todo", json!({ "id": id }));
+    let done = call_tool(&server, "list_todos", json!({ "state": "DONE" }));
+
+    // Assert
+    assert_eq!(added["isError"], false);
+    assert_eq!(
+        found["structuredContent"]["todos"]
+            .as_array()
+            .unwrap()
+            .len(),
+        1
+    );
+    assert_eq!(found["structuredContent"]["todos"][0]["id"], id);
+    assert_eq!(completed["structuredContent"]["todo"]["state"], "DONE");
+    assert_eq!(done["structuredContent"]["todos"][0]["id"], id);
+    let text: Value = serde_json::from_str(done["content"][0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(text, done["structuredContent"]);
+}
+
+#[test]
+fn test_add_todo_past_the_quota_is_a_tool_error() {
+    // Arrange
+    let server = McpServer::new(TodoBackend::Memory.repository()).with_quota(Quota {
+        max_open_todos: None,
+        max_todos: Some(1),
+    });
+    call_tool(&server, "add_todo", json!({ "description": "Write docs" }));
+
+    // Act
+    let refused = call_tool(&server, "add_todo", json!({ "description": "Ship it" }));
+
+    // Assert
+    assert_eq!(refused["isError"], true);
+    assert_eq!(refused["content"][0]["text"], "todo quota exceeded");
+}
+
+#[test]
+fn test_tool_errors() {
+    // Arrange
+    let server = McpServer::new(TodoBackend::Memory.repository());
+
+    // Act
+    let empty = call_tool(&server, "add_todo", json!({ "description": "" }));
+    let missing = call_tool(&server, "complete_todo", json!({ "id": "non-existent-id" }));
+    let unknown_tool = request(
+        &server,
+        2,
+        "tools/call",
+        json!({ "name": "delete_todo", "arguments": {} }),
+    );
+    let bad_arguments = request(
+        &server,
+        3,
+        "tools/call",
+        json!({ "name": "add_todo", "arguments": { "text": "x" } }),
+    );
+
+    // Assert
+    assert_eq!(empty["isError"], true);
+    assert_eq!(
+        empty["content"][0]["text"],
+        "todo description must not be empty"
+    );
+    assert_eq!(missing["isError"], true);
+    assert_eq!(missing["content"][0]["text"], "todo not found");
+    assert_eq!(unknown_tool["error"]["code"], -32602);
+    assert_eq!(bad_arguments["error"]["code"], -32602);
+}
@@ -0,0 +1,700 @@
+# This file is automatically generated by pyo3_stub_gen
+# ruff: noqa: E501, F401, F403, F405
+
+import builtins
+import datetime
+import enum
+import os
+import pathlib
+import typing
+__all__ = [
+    "EmptyDescriptionError",
+    "InvalidStateTransitionError",
+    "PyAttachment",
+    "PyEventPage",
+    "PyPriority",
+    "PySubtask",
+    "PyTodo",
+    "PyTodoCollection",
+    "PyTodoCollectionIterator",
+    "PyTodoError",
+    "PyTodoEvent",
+    "PyTodoService",
+    "PyTodoState",
+    "PyTodoTransaction",
+    "QuotaExceededError",
+    "TodoException",
+    "TodoNotFoundError",
+    "TodoRepositoryError",
+    "TodoValidationError",
+]
+
+class EmptyDescriptionError(TodoException):
+    r"""
+    Raised when a todo description is empty
+    """
+    ...
+
+class InvalidStateTransitionError(TodoException):
+    r"""
+    Raised when a state transition is not allowed
+    """
+    ...
+
+@typing.final
+class PyAttachment:
+    r"""
+    Python bindings for Attachment struct, a read-only copy of one attached file's metadata
+    """
+    @property
+    def content_hash(self) -> builtins.str:
+        r"""
+        Get the SHA-256 of the content in lowercase hex, unique within its todo
+        """
+    @property
+    def name(self) -> builtins.str:
+        r"""
+        Get the file name
+        """
+    @property
+    def media_type(self) -> builtins.str:
+        r"""
+        Get the media type of the content
+        """
+    @property
+    def size(self) -> builtins.int:
+        r"""
+        Get the size of the content in bytes
+        """
+    def __repr__(self) -> builtins.str: ...
+
+@typing.final
+class PyEventPage:
+    r"""
+    Python bindings for the events returned by one `poll_events` call
+    """
+    @property
+    def events(self) -> builtins.list[PyTodoEvent]:
+        r"""
+        The events, oldest first
+        """
+    @property
+    def cursor(self) -> typing.Optional[builtins.str]:
+        r"""
+        The cursor to pass to the next `poll_events` call, or `None` while no event was
+        returned from the start
+        """
+    def __len__(self) -> builtins.int: ...
+
+@typing.final
+class PySubtask:
+    r"""
+    Python bindings for Subtask struct, a read-only copy of one checklist item
+    """
+    @property
+    def id(self) -> builtins.str:
+        r"""
+        Get the subtask ID, unique within its todo
+        """
+    @property
+    def description(self) -> builtins.str:
+        r"""
+        Get the subtask description
+        """
+    @property
+    def done(self) -> builtins.bool:
+        r"""
+        Whether the subtask is checked off
+        """
+    def __repr__(self) -> builtins.str: ...
+
+@typing.final
+class PyTodo:
+    r"""
+    Python bindings for Todo struct
+    """
+    @property
+    def id(self) -> builtins.str:
+        r"""
+        Get the todo ID
+        """
+    @property
+    def description(self) -> builtins.str:
+        r"""
+        Get the todo description
+        """
+    @property
+    def state(self) -> PyTodoState:
+        r"""
+        Get the todo state
+        """
+    @property
+    def created_at(self) -> builtins.str:
+        r"""
+        Get the creation timestamp
+        """
+    @property
+    def due_at(self) -> typing.Optional[builtins.str]:
+        r"""
+        Get the due date, or `None` when the todo has none
+        """
+    @property
+    def priority(self) -> PyPriority:
+        r"""
+        Get the priority
+        """
+    @property
+    def tags(self) -> builtins.list[builtins.str]:
+        r"""
+        Get the tags, in the order they were added
+        """
+    @property
+    def subtasks(self) -> builtins.list[PySubtask]:
+        r"""
+        Get the checklist, in the order the subtasks were added
+        """
+    @property
+    def blocked_by(self) -> builtins.list[builtins.str]:
+        r"""
+        Get the ids of the todos that must be done first, in the order they were added
+        """
+    @property
+    def attachments(self) -> builtins.list[PyAttachment]:
+        r"""
+        Get the attached files, in the order they were attached; their content is kept in
+        a blob store, not on the todo
+        """
+    def __new__(cls, description: builtins.str, due_date: typing.Optional[datetime.datetime] = None, priority: typing.Optional[PyPriority] = None, tags: typing.Optional[typing.Sequence[builtins.str]] = None) -> PyTodo:
+        r"""
+        Creates a new Todo instance, due at the timezone-aware `datetime` `due_date` when
+        given, of `MEDIUM` priority unless `priority` is given, and with `tags`
+        """
+    @staticmethod
+    def create(description: builtins.str, due_date: typing.Optional[datetime.datetime] = None, priority: typing.Optional[PyPriority] = None, tags: typing.Optional[typing.Sequence[builtins.str]] = None) -> tuple[PyTodo, builtins.list[PyTodoEvent]]:
+        r"""
+        Creates a new Todo instance, taking the same arguments as the constructor, and
+        returns the created events
+        """
+    def update_description(self, description: builtins.str) -> builtins.list[PyTodoEvent]:
+        r"""
+        Replaces the description; an empty one raises EmptyDescriptionError, and the
+        current one returns no event
+        """
+    def set_priority(self, priority: PyPriority) -> builtins.list[PyTodoEvent]:
+        r"""
+        Sets the priority
+        """
+    def add_tag(self, tag: builtins.str) -> builtins.list[PyTodoEvent]:
+        r"""
+        Adds a tag; an empty tag, one that is not a single word, or one the todo already has
+        raises TodoValidationError
+        """
+    def remove_tag(self, tag: builtins.str) -> builtins.list[PyTodoEvent]:
+        r"""
+        Removes a tag; a tag the todo does not have returns no event
+        """
+    def add_subtask(self, description: builtins.str) -> builtins.list[PyTodoEvent]:
+        r"""
+        Adds a subtask, not yet done; its id is in the returned event
+        """
+    def toggle_subtask(self, subtask_id: builtins.str) -> builtins.list[PyTodoEvent]:
+        r"""
+        Checks off a subtask, or unchecks it if it was done; an unknown id raises
+        TodoValidationError
+        """
+    def remove_subtask(self, subtask_id: builtins.str) -> builtins.list[PyTodoEvent]:
+        r"""
+        Removes a subtask; an unknown id returns no event
+        """
+    def block_by(self, blocker_id: builtins.str) -> builtins.list[PyTodoEvent]:
+        r"""
+        Makes the todo wait on the todo `blocker_id`, which then has to be done before this
+        one can be started or finished; its own id raises TodoValidationError
+        """
+    def unblock(self, blocker_id: builtins.str) -> builtins.list[PyTodoEvent]:
+        r"""
+        Stops the todo from waiting on the todo `blocker_id`; an unknown id returns no event
+        """
+    def completion_percentage(self) -> typing.Optional[builtins.int]:
+        r"""
+        Share of the subtasks that are done, from 0 to 100, or None without subtasks
+        """
+    def set_due_date(self, due_at: builtins.str) -> builtins.list[PyTodoEvent]:
+        r"""
+        Sets the due date from an RFC3339 timestamp, which must not be before the creation
+        """
+    def clear_due_date(self) -> builtins.list[PyTodoEvent]:
+        r"""
+        Removes the due date
+        """
+    def update_state(self, new_state: PyTodoState) -> builtins.list[PyTodoEvent]:
+        r"""
+        Updates the Todo state with validation; starting or finishing a blocked todo raises
+        TodoValidationError
+        
+        Only this copy changes, so finishing it does not unblock the todos waiting on it.
+        Use `PyTodoService.change_state` for stored todos, or `PyTodoService.release` after
+        saving a todo finished here.
+        """
+    def change_to_next_state(self) -> builtins.list[PyTodoEvent]:
+        r"""
+        Transitions to the next state in the workflow
+        
+        Like `update_state`, finishing the todo does not unblock the todos waiting on it.
+        """
+    def change_to_previous_state(self) -> builtins.list[PyTodoEvent]:
+        r"""
+        Transitions to the previous state in the workflow
+        """
+    def to_dict(self) -> dict:
+        r"""
+        Returns the todo as a JSON-serializable dict
+        """
+    @staticmethod
+    def from_dict(data: dict) -> PyTodo:
+        r"""
+        Rebuilds a todo from a dict produced by `to_dict()`
+        """
+    def __getnewargs__(self) -> tuple[builtins.str]:
+        r"""
+        Arguments passed to `__new__` when unpickling, the state is restored afterwards
+        """
+    def __getstate__(self) -> dict:
+        r"""
+        Pickle state, identical to `to_dict()`
+        """
+    def __setstate__(self, state: dict) -> None:
+        r"""
+        Restores the pickled state, keeping the original id and timestamps
+        """
+    def __repr__(self) -> builtins.str: ...
+    def __str__(self) -> builtins.str: ...
+    def __eq__(self, other: builtins.object) -> builtins.bool:
+        r"""
+        Todos are equal when they share the same id
+        """
+    def __hash__(self) -> builtins.int: ...
+
+@typing.final
+class PyTodoCollection:
+    r"""
+    Python bindings for a read-only sequence of todos
+    
+    Todos are only converted into `PyTodo` objects when accessed, and filtering shares
+    the underlying todos instead of copying them. Each access returns a fresh copy, so
+    mutating an item does not change the collection.
+    """
+    def filter(self, *, state: typing.Optional[PyTodoState] = None) -> PyTodoCollection:
+        r"""
+        Returns a new collection with the todos matching all given criteria
+        """
+    def __len__(self) -> builtins.int: ...
+    def __getitem__(self, index: builtins.int) -> PyTodo: ...
+    def __iter__(self) -> PyTodoCollectionIterator: ...
+    def __repr__(self) -> builtins.str: ...
+
+@typing.final
+class PyTodoCollectionIterator:
+    r"""
+    Iterator over a PyTodoCollection
+    """
+    def __iter__(self) -> PyTodoCollectionIterator: ...
+    def __next__(self) -> PyTodo: ...
+
+class PyTodoEvent:
+    r"""
+    Python bindings for TodoEvent enum
+    """
+    def to_dict(self) -> dict:
+        r"""
+        Returns the event as a JSON-serializable dict, tagged with its `type`
+        """
+    @staticmethod
+    def from_dict(data: dict) -> PyTodoEvent:
+        r"""
+        Rebuilds an event from a dict produced by `to_dict()`
+        """
+    def __repr__(self) -> builtins.str: ...
+    def __str__(self) -> builtins.str: ...
+    def __eq__(self, other: builtins.object) -> builtins.bool:
+        r"""
+        Events are equal when they are the same variant with the same fields
+        """
+    def __hash__(self) -> builtins.int: ...
+    @typing.final
+    class TODO_CREATED(PyTodoEvent):
+        __match_args__ = ("id", "description", "created_at",)
+        @property
+        def id(self) -> builtins.str: ...
+        @property
+        def description(self) -> builtins.str: ...
+        @property
+        def created_at(self) -> builtins.str: ...
+        def __new__(cls, id: builtins.str, description: builtins.str, created_at: builtins.str) -> PyTodoEvent.TODO_CREATED: ...
+    
+    @typing.final
+    class TODO_STATE_CHANGED(PyTodoEvent):
+        __match_args__ = ("id", "from_state", "to_state", "changed_at",)
+        @property
+        def id(self) -> builtins.str: ...
+        @property
+        def from_state(self) -> PyTodoState: ...
+        @property
+        def to_state(self) -> PyTodoState: ...
+        @property
+        def changed_at(self) -> builtins.str: ...
+        def __new__(cls, id: builtins.str, from_state: PyTodoState, to_state: PyTodoState, changed_at: builtins.str) -> PyTodoEvent.TODO_STATE_CHANGED: ...
+    
+    @typing.final
+    class TODO_ARCHIVED(PyTodoEvent):
+        __match_args__ = ("id", "archived_at",)
+        @property
+        def id(self) -> builtins.str: ...
+        @property
+        def archived_at(self) -> builtins.str: ...
+        def __new__(cls, id: builtins.str, archived_at: builtins.str) -> PyTodoEvent.TODO_ARCHIVED: ...
+    
+    @typing.final
+    class TODO_STALE(PyTodoEvent):
+        __match_args__ = ("id", "rule", "state", "stale_at",)
+        @property
+        def id(self) -> builtins.str: ...
+        @property
+        def rule(self) -> builtins.str: ...
+        @property
+        def state(self) -> PyTodoState: ...
+        @property
+        def stale_at(self) -> builtins.str: ...
+        def __new__(cls, id: builtins.str, rule: builtins.str, state: PyTodoState, stale_at: builtins.str) -> PyTodoEvent.TODO_STALE: ...
+    
+    @typing.final
+    class TODO_DUE_DATE_CHANGED(PyTodoEvent):
+        __match_args__ = ("id", "due_at", "changed_at",)
+        @property
+        def id(self) -> builtins.str: ...
+        @property
+        def due_at(self) -> typing.Optional[builtins.str]: ...
+        @property
+        def changed_at(self) -> builtins.str: ...
+        def __new__(cls, id: builtins.str, due_at: typing.Optional[builtins.str], changed_at: builtins.str) -> PyTodoEvent.TODO_DUE_DATE_CHANGED: ...
+    
+    @typing.final
+    class TODO_PRIORITY_CHANGED(PyTodoEvent):
+        __match_args__ = ("id", "from_priority", "to_priority", "changed_at",)
+        @property
+        def id(self) -> builtins.str: ...
+        @property
+        def from_priority(self) -> PyPriority: ...
+        @property
+        def to_priority(self) -> PyPriority: ...
+        @property
+        def changed_at(self) -> builtins.str: ...
+        def __new__(cls, id: builtins.str, from_priority: PyPriority, to_priority: PyPriority, changed_at: builtins.str) -> PyTodoEvent.TODO_PRIORITY_CHANGED: ...
+    
+    @typing.final
+    class TODO_TAG_ADDED(PyTodoEvent):
+        __match_args__ = ("id", "tag", "added_at",)
+        @property
+        def id(self) -> builtins.str: ...
+        @property
+        def tag(self) -> builtins.str: ...
+        @property
+        def added_at(self) -> builtins.str: ...
+        def __new__(cls, id: builtins.str, tag: builtins.str, added_at: builtins.str) -> PyTodoEvent.TODO_TAG_ADDED: ...
+    
+    @typing.final
+    class TODO_TAG_REMOVED(PyTodoEvent):
+        __match_args__ = ("id", "tag", "removed_at",)
+        @property
+        def id(self) -> builtins.str: ...
+        @property
+        def tag(self) -> builtins.str: ...
+        @property
+        def removed_at(self) -> builtins.str: ...
+        def __new__(cls, id: builtins.str, tag: builtins.str, removed_at: builtins.str) -> PyTodoEvent.TODO_TAG_REMOVED: ...
+    
+    @typing.final
+    class TODO_SUBTASK_ADDED(PyTodoEvent):
+        __match_args__ = ("id", "subtask_id", "description", "added_at",)
+        @property
+        def id(self) -> builtins.str: ...
+        @property
+        def subtask_id(self) -> builtins.str: ...
+        @property
+        def description(self) -> builtins.str: ...
+        @property
+        def added_at(self) -> builtins.str: ...
+        def __new__(cls, id: builtins.str, subtask_id: builtins.str, description: builtins.str, added_at: builtins.str) -> PyTodoEvent.TODO_SUBTASK_ADDED: ...
+    
+    @typing.final
+    class TODO_SUBTASK_TOGGLED(PyTodoEvent):
+        __match_args__ = ("id", "subtask_id", "done", "toggled_at",)
+        @property
+        def id(self) -> builtins.str: ...
+        @property
+        def subtask_id(self) -> builtins.str: ...
+        @property
+        def done(self) -> builtins.bool: ...
+        @property
+        def toggled_at(self) -> builtins.str: ...
+        def __new__(cls, id: builtins.str, subtask_id: builtins.str, done: builtins.bool, toggled_at: builtins.str) -> PyTodoEvent.TODO_SUBTASK_TOGGLED: ...
+    
+    @typing.final
+    class TODO_SUBTASK_REMOVED(PyTodoEvent):
+        __match_args__ = ("id", "subtask_id", "removed_at",)
+        @property
+        def id(self) -> builtins.str: ...
+        @property
+        def subtask_id(self) -> builtins.str: ...
+        @property
+        def removed_at(self) -> builtins.str: ...
+        def __new__(cls, id: builtins.str, subtask_id: builtins.str, removed_at: builtins.str) -> PyTodoEvent.TODO_SUBTASK_REMOVED: ...
+    
+    @typing.final
+    class TODO_DESCRIPTION_UPDATED(PyTodoEvent):
+        __match_args__ = ("id", "old", "new", "updated_at",)
+        @property
+        def id(self) -> builtins.str: ...
+        @property
+        def old(self) -> builtins.str: ...
+        @property
+        def new(self) -> builtins.str: ...
+        @property
+        def updated_at(self) -> builtins.str: ...
+        def __new__(cls, id: builtins.str, old: builtins.str, new: builtins.str, updated_at: builtins.str) -> PyTodoEvent.TODO_DESCRIPTION_UPDATED: ...
+    
+    @typing.final
+    class TODO_BLOCKED(PyTodoEvent):
+        __match_args__ = ("id", "blocker_id", "blocked_at",)
+        @property
+        def id(self) -> builtins.str: ...
+        @property
+        def blocker_id(self) -> builtins.str: ...
+        @property
+        def blocked_at(self) -> builtins.str: ...
+        def __new__(cls, id: builtins.str, blocker_id: builtins.str, blocked_at: builtins.str) -> PyTodoEvent.TODO_BLOCKED: ...
+    
+    @typing.final
+    class TODO_UNBLOCKED(PyTodoEvent):
+        __match_args__ = ("id", "blocker_id", "unblocked_at",)
+        @property
+        def id(self) -> builtins.str: ...
+        @property
+        def blocker_id(self) -> builtins.str: ...
+        @property
+        def unblocked_at(self) -> builtins.str: ...
+        def __new__(cls, id: builtins.str, blocker_id: builtins.str, unblocked_at: builtins.str) -> PyTodoEvent.TODO_UNBLOCKED: ...
+    
+    @typing.final
+    class TODO_DELETED(PyTodoEvent):
+        __match_args__ = ("id", "deleted_at",)
+        @property
+        def id(self) -> builtins.str: ...
+        @property
+        def deleted_at(self) -> builtins.str: ...
+        def __new__(cls, id: builtins.str, deleted_at: builtins.str) -> PyTodoEvent.TODO_DELETED: ...
+    
+    @typing.final
+    class TODO_RESTORED(PyTodoEvent):
+        __match_args__ = ("id", "restored_at",)
+        @property
+        def id(self) -> builtins.str: ...
+        @property
+        def restored_at(self) -> builtins.str: ...
+        def __new__(cls, id: builtins.str, restored_at: builtins.str) -> PyTodoEvent.TODO_RESTORED: ...
+    
+    @typing.final
+    class TODO_ATTACHMENT_ADDED(PyTodoEvent):
+        __match_args__ = ("id", "content_hash", "name", "media_type", "size", "added_at",)
+        @property
+        def id(self) -> builtins.str: ...
+        @property
+        def content_hash(self) -> builtins.str: ...
+        @property
+        def name(self) -> builtins.str: ...
+        @property
+        def media_type(self) -> builtins.str: ...
+        @property
+        def size(self) -> builtins.int: ...
+        @property
+        def added_at(self) -> builtins.str: ...
+        def __new__(cls, id: builtins.str, content_hash: builtins.str, name: builtins.str, media_type: builtins.str, size: builtins.int, added_at: builtins.str) -> PyTodoEvent.TODO_ATTACHMENT_ADDED: ...
+    
+    @typing.final
+    class TODO_ATTACHMENT_REMOVED(PyTodoEvent):
+        __match_args__ = ("id", "content_hash", "removed_at",)
+        @property
+        def id(self) -> builtins.str: ...
+        @property
+        def content_hash(self) -> builtins.str: ...
+        @property
+        def removed_at(self) -> builtins.str: ...
+        def __new__(cls, id: builtins.str, content_hash: builtins.str, removed_at: builtins.str) -> PyTodoEvent.TODO_ATTACHMENT_REMOVED: ...
+    
+
+@typing.final
+class PyTodoService:
+    r"""
+    Python bindings for the application handlers
+    
+    All handlers share one repository: the given Python repository object, or an
+    in-memory repository when none is provided. The GIL is released while a handler
+    runs, so other Python threads keep going during storage I/O; a Python repository
+    re-acquires it only for the duration of each of its calls.
+    
+    The events of every change are recorded in an event store, in memory or in the JSON
+    lines file at `events_path`, from which `poll_events` reads them.
+    """
+    def __new__(cls, repository: typing.Optional[typing.Any] = None, events_path: typing.Optional[builtins.str | os.PathLike | pathlib.Path] = None) -> PyTodoService:
+        r"""
+        Creates a new service backed by `repository`, or in memory when omitted, recording
+        events to the file at `events_path`, or in memory when omitted
+        """
+    def transaction(self) -> PyTodoTransaction:
+        r"""
+        Starts a transaction, used as `with service.transaction() as tx:`
+        
+        Changes made through `tx` are staged and committed together when the block
+        exits normally, or rolled back when it raises. Their events are recorded when
+        they are committed.
+        """
+    def add_todo(self, description: builtins.str) -> builtins.list[PyTodoEvent]:
+        r"""
+        Creates and stores a new todo, returning the created events
+        """
+    def get_todos(self) -> PyTodoCollection:
+        r"""
+        Returns all stored todos as a lazily converted collection
+        """
+    def search(self, query: builtins.str) -> PyTodoCollection:
+        r"""
+        Returns the stored todos matching a search query, such as
+        `state:in_progress -created<2025-01-01 "quarterly report"`
+        
+        Raises `ValueError` if the query is invalid.
+        """
+    def change_state(self, id: builtins.str, new_state: PyTodoState) -> builtins.list[PyTodoEvent]:
+        r"""
+        Changes the state of a stored todo, returning the state changed events, followed by
+        the unblocked events of the todos waiting on it when it is finished
+        """
+    def block(self, id: builtins.str, blocker_id: builtins.str) -> builtins.list[PyTodoEvent]:
+        r"""
+        Makes the stored todo `id` wait on the stored todo `blocker_id`, returning the blocked
+        events; none when it already waits on it
+        
+        Raises `TodoNotFoundError` if either todo is missing, and `TodoValidationError` if
+        `blocker_id` is `id`, is done, or already waits on `id`.
+        """
+    def unblock(self, id: builtins.str, blocker_id: builtins.str) -> builtins.list[PyTodoEvent]:
+        r"""
+        Stops the stored todo `id` from waiting on `blocker_id`, returning the unblocked
+        events; none when it did not wait on it
+        
+        Raises `TodoNotFoundError` if there is no todo `id`.
+        """
+    def release(self, id: builtins.str) -> builtins.list[PyTodoEvent]:
+        r"""
+        Unblocks the stored todos waiting on the done todo `id`, such as one finished with
+        `PyTodo.update_state` and saved, returning their unblocked events
+        
+        Raises `TodoValidationError` if the todo is not done.
+        """
+    def poll_events(self, cursor: typing.Optional[builtins.str] = None, limit: typing.Optional[builtins.int] = None) -> PyEventPage:
+        r"""
+        Returns up to `limit` recorded events, 100 when omitted, after `cursor`, or from the
+        oldest when omitted
+        
+        Pass the returned page's `cursor` to the next call to resume where it left off,
+        also after a restart when events are recorded to `events_path`. Raises
+        `ValueError` if the cursor is invalid.
+        """
+
+@typing.final
+class PyTodoTransaction:
+    r"""
+    Python context manager for a Unit of Work
+    
+    Entering returns a `PyTodoService` whose changes are staged; leaving the block
+    commits them, or rolls them back if the block raised.
+    """
+    def __enter__(self) -> PyTodoService:
+        r"""
+        Returns the service operating inside this transaction
+        """
+    def __exit__(self, exc_type: typing.Optional[typing.Any], _exc_value: typing.Optional[typing.Any], _traceback: typing.Optional[typing.Any]) -> builtins.bool:
+        r"""
+        Commits when the block succeeded, rolls back when it raised
+        
+        Never suppresses the exception raised by the block.
+        """
+    def commit(self) -> None:
+        r"""
+        Applies the staged changes to the service's repository and records their events
+        """
+    def rollback(self) -> None:
+        r"""
+        Discards the staged changes and their events
+        """
+
+class QuotaExceededError(TodoException):
+    r"""
+    Raised when a todo list is over its quota
+    """
+    ...
+
+class TodoException(builtins.ValueError):
+    r"""
+    Base class for all todo errors. Carries the `error` code and a human readable `message`.
+    """
+    ...
+
+class TodoNotFoundError(TodoException):
+    r"""
+    Raised when a todo does not exist
+    """
+    ...
+
+class TodoRepositoryError(TodoException):
+    r"""
+    Raised when the underlying repository fails
+    """
+    ...
+
+class TodoValidationError(TodoException):
+    r"""
+    Raised when a value is rejected, such as a due date before the todo was created
+    """
+    ...
+
+@typing.final
+class PyPriority(enum.Enum):
+    r"""
+    Python bindings for the Priority value object, ordered from `LOW` to `URGENT`
+    """
+    LOW = ...
+    MEDIUM = ...
+    HIGH = ...
+    URGENT = ...
+
+@typing.final
+class PyTodoError(enum.Enum):
+    r"""
+    Python bindings for TodoError enum
+    """
+    EMPTY_DESCRIPTION = ...
+    INVALID_STATE_TRANSITION = ...
+    TODO_NOT_FOUND = ...
+    REPOSITORY_ERROR = ...
+    QUOTA_EXCEEDED = ...
+    VALIDATION_ERROR = ...
+
+@typing.final
+class PyTodoState(enum.Enum):
+    r"""
+    Python bindings for TodoState enum
+    """
+    TODO = ...
+    IN_PROGRESS = ...
+    DONE = ...
+
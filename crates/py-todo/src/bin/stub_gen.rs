@@ -0,0 +1,8 @@
+use pyo3_stub_gen::Result;
+
+// Generates py_todo.pyi next to pyproject.toml, picked up by maturin when building wheels
+fn main() -> Result<()> {
+    let stub = py_todo::stub_info()?;
+    stub.generate()?;
+    Ok(())
+}
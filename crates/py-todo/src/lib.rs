@@ -1,4 +1,5 @@
 use pyo3::prelude::*;
+use pyo3_stub_gen::define_stub_info_gatherer;
 
 // Re-export the Python module from todo crate
 // The module is already initialized by todo::python::todo
@@ -9,3 +10,6 @@ fn py_todo(py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     todo::python::todo(py, m)
 }
 
+// Gathers the stub information registered by the todo::python bindings,
+// used by `cargo run --bin stub_gen` to write py_todo.pyi
+define_stub_info_gatherer!(stub_info);
@@ -0,0 +1,32 @@
+import pytest
+
+import py_todo
+
+
+def test_service_blocks_and_unblocks_stored_todos():
+    # Arrange
+    service = py_todo.PyTodoService()
+    review = service.add_todo("Review docs")[0].id
+    publish = service.add_todo("Publish docs")[0].id
+
+    # Act
+    blocked = service.block(publish, review)
+    unblocked = service.unblock(publish, review)
+    unblocked_again = service.unblock(publish, review)
+
+    # Assert
+    assert isinstance(blocked[0], py_todo.PyTodoEvent.TODO_BLOCKED)
+    assert isinstance(unblocked[0], py_todo.PyTodoEvent.TODO_UNBLOCKED)
+    assert unblocked_again == []
+    stored = next(todo for todo in service.get_todos() if todo.id == publish)
+    assert stored.blocked_by == []
+
+
+def test_service_refuses_to_block_a_todo_by_itself():
+    # Arrange
+    service = py_todo.PyTodoService()
+    review = service.add_todo("Review docs")[0].id
+
+    # Act / Assert
+    with pytest.raises(py_todo.TodoValidationError):
+        service.block(review, review)
@@ -0,0 +1,37 @@
+from datetime import datetime, timedelta, timezone
+
+import pytest
+
+import py_todo
+
+
+def test_create_takes_a_datetime_as_due_date():
+    # Arrange
+    due_date = datetime.now(timezone(timedelta(hours=7))) + timedelta(days=2)
+
+    # Act
+    todo, events = py_todo.PyTodo.create("Write docs", due_date=due_date)
+
+    # Assert
+    assert datetime.fromisoformat(todo.due_at) == due_date
+    assert isinstance(events[1], py_todo.PyTodoEvent.TODO_DUE_DATE_CHANGED)
+
+
+def test_constructor_takes_a_datetime_as_due_date():
+    # Arrange
+    due_date = datetime.now(timezone.utc) + timedelta(days=2)
+
+    # Act
+    todo = py_todo.PyTodo("Write docs", due_date=due_date)
+
+    # Assert
+    assert datetime.fromisoformat(todo.due_at) == due_date
+
+
+def test_due_date_must_be_timezone_aware():
+    # Arrange
+    due_date = datetime.now() + timedelta(days=2)
+
+    # Act / Assert
+    with pytest.raises(TypeError):
+        py_todo.PyTodo("Write docs", due_date=due_date)
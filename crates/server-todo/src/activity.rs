@@ -0,0 +1,87 @@
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, HeaderValue, header};
+use axum::response::{IntoResponse, Response};
+use axum::{Extension, Json};
+use chrono::Utc;
+use serde::Deserialize;
+use std::sync::Arc;
+use todo::application::activity_feed::{Activity, ActivityFeed, ActivityFilter};
+use todo::application::auth::AuthContext;
+use utoipa::IntoParams;
+
+use crate::dto::{ActivityDto, ActivityPageDto};
+use crate::routes::AppState;
+
+/// Activities returned when the request does not set `limit`
+const DEFAULT_LIMIT: usize = 20;
+
+/// Most activities returned by one request
+const MAX_LIMIT: usize = 100;
+
+/// Query of `GET /activity`
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ActivityQuery {
+    /// `next_cursor` of the previous page; omitted for the newest activities
+    pub cursor: Option<u64>,
+    /// Activities per page, 20 by default and at most 100
+    pub limit: Option<usize>,
+    /// Only the changes made by this subject
+    pub actor: Option<String>,
+}
+
+/// `GET /activity`: the activity feed of the caller's list, newest first, one page at a time
+#[utoipa::path(
+    get,
+    path = "/activity",
+    tag = "events",
+    params(ActivityQuery),
+    responses((status = 200, description = "Page of the activity feed", body = ActivityPageDto)),
+)]
+pub(crate) async fn activity_handler(
+    State(state): State<Arc<AppState>>,
+    context: Option<Extension<AuthContext>>,
+    Query(query): Query<ActivityQuery>,
+    headers: HeaderMap,
+) -> Response {
+    let Some(feed) = &state.activity else {
+        return axum::http::StatusCode::NOT_FOUND.into_response();
+    };
+    let accept_language = headers
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    let locale = state.messages.negotiate(accept_language);
+    let filter = ActivityFilter {
+        list: state.list(context.as_deref()),
+        actor: query.actor.as_deref().map(Arc::from),
+    };
+    let body = page(feed, &filter, &query, |activity| {
+        activity.describe(&state.messages, locale, Utc::now())
+    });
+    let mut response = Json(body).into_response();
+    if let Ok(language) = HeaderValue::from_str(locale) {
+        response
+            .headers_mut()
+            .insert(header::CONTENT_LANGUAGE, language);
+    }
+    response
+}
+
+fn page(
+    feed: &ActivityFeed,
+    filter: &ActivityFilter,
+    query: &ActivityQuery,
+    describe: impl Fn(&Activity) -> String,
+) -> ActivityPageDto {
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let page = feed.page_where(filter, query.cursor, limit);
+    ActivityPageDto {
+        items: page
+            .activities
+            .iter()
+            .map(|activity| ActivityDto::new(activity, describe(activity)))
+            .collect(),
+        next_cursor: page.next_cursor,
+    }
+}
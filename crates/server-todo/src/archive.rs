@@ -0,0 +1,35 @@
+use async_trait::async_trait;
+use todo::TodoRepository;
+use todo::application::archive::ArchiveTodosHandler;
+
+use crate::scheduler::ScheduledJob;
+
+/// ScheduledJob moving the todos done for long enough to the archive
+///
+/// Every run logs the ids of the todos it archived.
+pub struct ArchiveJob<R = Box<dyn TodoRepository>> {
+    handler: ArchiveTodosHandler<R>,
+}
+
+impl<R: TodoRepository> ArchiveJob<R> {
+    /// Creates a new ArchiveJob running `handler`
+    pub fn new(handler: ArchiveTodosHandler<R>) -> Self {
+        ArchiveJob { handler }
+    }
+}
+
+#[async_trait]
+impl<R: TodoRepository> ScheduledJob for ArchiveJob<R> {
+    async fn run(&self) -> Result<(), String> {
+        let events = self
+            .handler
+            .archive()
+            .await
+            .map_err(|err| err.to_string())?;
+        for event in &events {
+            tracing::info!(todo.id = %event.todo_id(), "archived a done todo");
+        }
+        tracing::info!(archived_todos = events.len(), "archive run finished");
+        Ok(())
+    }
+}
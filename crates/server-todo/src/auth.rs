@@ -0,0 +1,98 @@
+use axum::extract::{Request, State};
+use axum::http::{Method, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use std::sync::Arc;
+use std::time::Duration;
+use todo::application::auth::{AuthError, READ_SCOPE, WRITE_SCOPE, bearer_token};
+use todo::domain::access::TodoAction;
+use todo::infrastructure::jwt_authenticator::JwtAuthenticator;
+
+use crate::error::AuthRejection;
+use crate::routes::AppState;
+
+/// How often the issuer's keys are fetched again, to pick up rotated keys
+const KEY_REFRESH_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Middleware requiring a bearer token the state's authenticator accepts, when it has one
+///
+/// `GET` requests need the `todos:read` scope and other methods `todos:write`. With an
+/// access control in the state, the caller's role on their todo list must also permit the
+/// request's `TodoAction`. The caller's `AuthContext` is added to the request extensions,
+/// for handlers to take with `Extension<AuthContext>`. A missing or rejected token gets a
+/// `401` problem response, and a token without the scope or role a `403`.
+pub async fn require_auth(
+    State(state): State<Arc<AppState>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let Some(authenticator) = &state.authenticator else {
+        return next.run(request).await;
+    };
+    let scope = if request.method().is_safe() {
+        READ_SCOPE
+    } else {
+        WRITE_SCOPE
+    };
+    let action = todo_action(request.method(), request.uri().path());
+    let token = match request.headers().get(header::AUTHORIZATION) {
+        Some(value) => value
+            .to_str()
+            .map_err(|_| AuthError::InvalidToken("Authorization is not ASCII".to_string()))
+            .and_then(bearer_token),
+        None => Err(AuthError::MissingCredentials),
+    };
+    let context = match token {
+        Ok(token) => authenticator
+            .authenticate(token)
+            .await
+            .and_then(|context| context.require(scope).map(|()| context)),
+        Err(err) => Err(err),
+    };
+    let context = match (context, &state.access_control) {
+        (Ok(context), Some(access_control)) => access_control
+            .authorize(&context, action)
+            .await
+            .map(|_| context),
+        (context, _) => context,
+    };
+    match context {
+        Ok(context) => {
+            request.extensions_mut().insert(context);
+            next.run(request).await
+        }
+        Err(err) => AuthRejection(err).into_response(),
+    }
+}
+
+/// What a request to the protected routes does: any request to `/webhooks` manages
+/// webhooks; otherwise `GET` views todos, `POST` adds one, `PUT` changes a state, and other
+/// methods, `DELETE` included, count as deleting
+fn todo_action(method: &Method, path: &str) -> TodoAction {
+    if path == "/webhooks" || path.starts_with("/webhooks/") {
+        return TodoAction::ManageWebhooks;
+    }
+    match *method {
+        Method::GET | Method::HEAD => TodoAction::View,
+        Method::POST => TodoAction::Add,
+        Method::PUT | Method::PATCH => TodoAction::ChangeState,
+        _ => TodoAction::Delete,
+    }
+}
+
+/// Fetches `authenticator`'s keys every hour, in a task that runs until the process exits
+///
+/// A failed refresh is logged and the keys already fetched stay trusted.
+pub fn refresh_keys(authenticator: Arc<JwtAuthenticator>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(KEY_REFRESH_INTERVAL);
+        // The first tick completes at once, and the keys were just fetched
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            if let Err(err) = authenticator.refresh().await {
+                tracing::warn!(error = %err, "cannot refresh the token signing keys");
+            }
+        }
+    });
+}
@@ -0,0 +1,174 @@
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use todo::application::archive::ArchivePolicy;
+use todo::application::quota::QuotaPolicy;
+use todo::application::retention::RetentionPolicy;
+use todo::infrastructure::config::TodoConfig;
+use todo::infrastructure::event_sinks::EventLog;
+use todo::infrastructure::id_format::IdFormat;
+use todo::infrastructure::repositories::todo::TodoBackend;
+
+use crate::scheduler::CronSchedule;
+
+/// Server settings, read from the environment and the `TODO_CONFIG` file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerConfig {
+    pub addr: SocketAddr,
+    pub backend: TodoBackend,
+    pub event_log: EventLog,
+    pub id_format: IdFormat,
+    /// Events kept for `/events` and `/ws` subscribers resuming from a cursor
+    pub event_retention: usize,
+    /// Whether `GET /metrics` is served
+    pub metrics: bool,
+    pub otlp_endpoint: Option<String>,
+    /// OpenID Provider whose bearer tokens the todo routes require, if any
+    pub auth_issuer: Option<String>,
+    /// Audience those tokens must be issued to
+    pub auth_audience: Option<String>,
+    /// JSON file of the API keys the todo routes accept, if any
+    pub api_key_file: Option<PathBuf>,
+    /// JSON file of the roles callers need on their todo list, if any
+    pub roles_file: Option<PathBuf>,
+    /// JSON file deleted todos are moved to, served on `/trash`, if any
+    pub trash_file: Option<PathBuf>,
+    /// Whether the `/webhooks` routes are served and events delivered to webhooks
+    pub webhooks: bool,
+    /// Whether changes are recorded in an activity feed served on `/activity`
+    pub activity_feed: bool,
+    /// When the retention policy is applied
+    pub retention_schedule: CronSchedule,
+    /// What retention runs remove; nothing by default
+    pub retention: RetentionPolicy,
+    /// JSON file done todos are archived to, if any
+    pub archive_file: Option<PathBuf>,
+    /// When done todos are archived
+    pub archive_schedule: CronSchedule,
+    /// How long todos stay done before they are archived; never by default
+    pub archive: ArchivePolicy,
+    /// JSON file of the escalation rules, if any
+    pub escalation_rules_file: Option<PathBuf>,
+    /// When the escalation rules are checked
+    pub escalation_schedule: CronSchedule,
+    /// How many todos each list holds; no limit by default
+    pub quota: QuotaPolicy,
+    /// When the weekly digest is built and sent
+    pub digest_schedule: CronSchedule,
+    /// Where the digest is emailed; `None` sends no digest
+    pub digest_email: Option<DigestEmail>,
+}
+
+/// Email delivery of the digest, through a plain-text SMTP relay
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigestEmail {
+    pub from: String,
+    pub to: Vec<String>,
+    pub smtp_host: String,
+    pub smtp_port: u16,
+}
+
+impl ServerConfig {
+    /// Reads `TODO_SERVER_ADDR` (default `127.0.0.1:3000`), `TODO_WEBHOOKS` and
+    /// `TODO_ACTIVITY_FEED` (default `false`), the digest settings and
+    /// `OTEL_EXPORTER_OTLP_ENDPOINT` (default unset, which disables OTLP export), and the
+    /// shared settings through `TodoConfig::load`, with the `memory` backend, the `stdout`
+    /// event log, daily retention and archive runs and hourly escalation runs by default
+    pub fn from_env() -> Result<Self, String> {
+        let addr = match std::env::var("TODO_SERVER_ADDR") {
+            Ok(addr) => addr
+                .parse()
+                .map_err(|e| format!("invalid TODO_SERVER_ADDR {:?}: {}", addr, e))?,
+            Err(_) => SocketAddr::from(([127, 0, 0, 1], 3000)),
+        };
+        let webhooks = flag("TODO_WEBHOOKS")?;
+        let activity_feed = flag("TODO_ACTIVITY_FEED")?;
+        let todo = TodoConfig::load()?;
+        let retention_schedule =
+            CronSchedule::parse(todo.retention_schedule.as_deref().unwrap_or("@daily"))?;
+        let archive_schedule =
+            CronSchedule::parse(todo.archive_schedule.as_deref().unwrap_or("@daily"))?;
+        let escalation_schedule =
+            CronSchedule::parse(todo.escalation_schedule.as_deref().unwrap_or("@hourly"))?;
+        let digest_schedule = CronSchedule::parse(
+            std::env::var("TODO_DIGEST_SCHEDULE")
+                .as_deref()
+                .unwrap_or("@weekly"),
+        )?;
+        let digest_email = digest_email()?;
+        let otlp_endpoint = std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT")
+            .ok()
+            .filter(|endpoint| !endpoint.is_empty());
+        Ok(ServerConfig {
+            addr,
+            backend: todo.backend.unwrap_or(TodoBackend::Memory),
+            event_log: todo.event_log.unwrap_or(EventLog::Stdout),
+            id_format: todo.id_format,
+            event_retention: todo.event_retention,
+            metrics: todo.metrics,
+            otlp_endpoint,
+            auth_issuer: todo.auth_issuer,
+            auth_audience: todo.auth_audience,
+            api_key_file: todo.api_key_file,
+            roles_file: todo.roles_file,
+            trash_file: todo.trash_file,
+            webhooks,
+            activity_feed,
+            retention_schedule,
+            retention: todo.retention,
+            archive_file: todo.archive_file,
+            archive_schedule,
+            archive: todo.archive,
+            escalation_rules_file: todo.escalation_rules_file,
+            escalation_schedule,
+            quota: todo.quota,
+            digest_schedule,
+            digest_email,
+        })
+    }
+}
+
+/// Reads `TODO_DIGEST_TO`, a comma-separated list of addresses, with the `TODO_DIGEST_FROM`
+/// address and the `TODO_SMTP_HOST` relay, as `host` or `host:port`, that it requires
+fn digest_email() -> Result<Option<DigestEmail>, String> {
+    let Ok(to) = std::env::var("TODO_DIGEST_TO") else {
+        return Ok(None);
+    };
+    let to: Vec<String> = to
+        .split(',')
+        .map(str::trim)
+        .filter(|address| !address.is_empty())
+        .map(str::to_string)
+        .collect();
+    let (Ok(from), Ok(relay)) = (
+        std::env::var("TODO_DIGEST_FROM"),
+        std::env::var("TODO_SMTP_HOST"),
+    ) else {
+        return Err("TODO_DIGEST_TO needs TODO_DIGEST_FROM and TODO_SMTP_HOST".to_string());
+    };
+    let (smtp_host, smtp_port) = match relay.rsplit_once(':') {
+        Some((host, port)) => (
+            host.to_string(),
+            port.parse()
+                .map_err(|e| format!("invalid TODO_SMTP_HOST {:?}: {}", relay, e))?,
+        ),
+        None => (relay, 25),
+    };
+    Ok(Some(DigestEmail {
+        from,
+        to,
+        smtp_host,
+        smtp_port,
+    }))
+}
+
+/// Reads a `true`/`false` (or `1`/`0`) variable, `false` when unset
+fn flag(name: &str) -> Result<bool, String> {
+    match std::env::var(name).as_deref() {
+        Ok("true" | "1") => Ok(true),
+        Ok("false" | "0") | Err(_) => Ok(false),
+        Ok(value) => Err(format!(
+            "invalid {} {:?}, expected true or false",
+            name, value
+        )),
+    }
+}
@@ -0,0 +1,51 @@
+use async_trait::async_trait;
+use std::sync::{Mutex, PoisonError};
+use todo::TodoRepository;
+use todo::application::digest::{Digest, DigestHandler};
+
+use crate::scheduler::ScheduledJob;
+
+/// ScheduledJob summarizing the last seven days and delivering the digest
+///
+/// Every run logs the digest's summary and keeps the digest for `last_digest`, even when
+/// its delivery failed.
+pub struct DigestJob<R = Box<dyn TodoRepository>> {
+    handler: DigestHandler<R>,
+    last_digest: Mutex<Option<Digest>>,
+}
+
+impl<R: TodoRepository> DigestJob<R> {
+    /// Creates a new DigestJob running `handler`
+    pub fn new(handler: DigestHandler<R>) -> Self {
+        DigestJob {
+            handler,
+            last_digest: Mutex::new(None),
+        }
+    }
+
+    /// The digest of the last run that could build one, if any
+    pub fn last_digest(&self) -> Option<Digest> {
+        self.last_digest
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+#[async_trait]
+impl<R: TodoRepository> ScheduledJob for DigestJob<R> {
+    async fn run(&self) -> Result<(), String> {
+        let digest = self
+            .handler
+            .last_week()
+            .await
+            .map_err(|err| err.to_string())?;
+        tracing::info!(summary = %digest.summary(), "digest built");
+        let delivered = self.handler.deliver(&digest).await;
+        *self
+            .last_digest
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(digest);
+        delivered.map_err(|err| err.to_string())
+    }
+}
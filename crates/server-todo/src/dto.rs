@@ -0,0 +1,700 @@
+use serde::{Deserialize, Serialize};
+use todo::application::activity_feed::Activity;
+use todo::application::error_mapping::ProblemDetails;
+use todo::application::escalation::Escalation;
+use todo::application::quota::QuotaUsage;
+use todo::domain::trash::TrashedTodo;
+use todo::infrastructure::serialization::cloudevents::CloudEvent;
+use todo::{Attachment, Priority, Subtask, Todo, TodoEvent, TodoState};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::webhooks::{DeadLetter, Webhook, WebhookEvent};
+
+/// JSON representation of a Todo
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct TodoDto {
+    pub id: String,
+    pub description: String,
+    pub state: StateDto,
+    #[schema(format = DateTime)]
+    pub created_at: String,
+    /// Omitted when the todo has no due date
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(format = DateTime)]
+    pub due_at: Option<String>,
+    /// `"MEDIUM"` when missing
+    #[serde(default)]
+    pub priority: PriorityDto,
+    /// In the order they were added; `[]` when missing
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// The checklist, in the order it was added to; `[]` when missing
+    #[serde(default)]
+    pub subtasks: Vec<SubtaskDto>,
+    /// Ids of the todos that must be done before this one can be started or finished; `[]`
+    /// when missing
+    #[serde(default)]
+    pub blocked_by: Vec<String>,
+    /// The attached files, in the order they were attached; `[]` when missing
+    #[serde(default)]
+    pub attachments: Vec<AttachmentDto>,
+}
+
+impl From<&Todo> for TodoDto {
+    fn from(todo: &Todo) -> Self {
+        TodoDto {
+            id: todo.id.to_string(),
+            description: todo.description.to_string(),
+            state: todo.state.into(),
+            created_at: todo.created_at.to_rfc3339(),
+            due_at: todo.due_at.map(|due_at| due_at.to_rfc3339()),
+            priority: todo.priority.into(),
+            tags: todo.tags.iter().map(ToString::to_string).collect(),
+            subtasks: todo.subtasks.iter().map(SubtaskDto::from).collect(),
+            blocked_by: todo.blocked_by.iter().map(ToString::to_string).collect(),
+            attachments: todo.attachments.iter().map(AttachmentDto::from).collect(),
+        }
+    }
+}
+
+/// JSON representation of one item of a todo's checklist
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct SubtaskDto {
+    pub id: String,
+    pub description: String,
+    pub done: bool,
+}
+
+impl From<&Subtask> for SubtaskDto {
+    fn from(subtask: &Subtask) -> Self {
+        SubtaskDto {
+            id: subtask.id.to_string(),
+            description: subtask.description.to_string(),
+            done: subtask.done,
+        }
+    }
+}
+
+/// JSON representation of the metadata of a file attached to a todo
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct AttachmentDto {
+    /// SHA-256 of the content in lowercase hex
+    pub content_hash: String,
+    pub name: String,
+    pub media_type: String,
+    /// Size of the content in bytes
+    pub size: u64,
+}
+
+impl From<&Attachment> for AttachmentDto {
+    fn from(attachment: &Attachment) -> Self {
+        AttachmentDto {
+            content_hash: attachment.content_hash.to_string(),
+            name: attachment.name.to_string(),
+            media_type: attachment.media_type.to_string(),
+            size: attachment.size,
+        }
+    }
+}
+
+/// JSON representation of a TrashedTodo: the todo as it was deleted, and when
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct TrashedTodoDto {
+    #[serde(flatten)]
+    pub todo: TodoDto,
+    #[schema(format = DateTime)]
+    pub deleted_at: String,
+}
+
+impl From<&TrashedTodo> for TrashedTodoDto {
+    fn from(trashed: &TrashedTodo) -> Self {
+        TrashedTodoDto {
+            todo: TodoDto::from(&trashed.todo),
+            deleted_at: trashed.deleted_at.to_rfc3339(),
+        }
+    }
+}
+
+/// A todo left in a state for longer than an escalation rule allows
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct EscalationDto {
+    pub todo: TodoDto,
+    /// Id of the rule the todo breaks
+    pub rule: String,
+    /// Days the rule allows in the todo's state
+    pub after_days: u32,
+    /// When the todo entered its state
+    #[schema(format = DateTime)]
+    pub since: String,
+}
+
+impl From<&Escalation> for EscalationDto {
+    fn from(escalation: &Escalation) -> Self {
+        EscalationDto {
+            todo: TodoDto::from(&escalation.todo),
+            rule: escalation.rule.id.to_string(),
+            after_days: escalation.rule.after_days,
+            since: escalation.since.to_rfc3339(),
+        }
+    }
+}
+
+/// Body of the `GET /quota` response
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct QuotaUsageDto {
+    /// Todos not `DONE`
+    pub open_todos: u32,
+    pub todos: u32,
+    /// Most todos not `DONE` allowed; `null` for no limit
+    pub max_open_todos: Option<u32>,
+    /// Most todos allowed; `null` for no limit
+    pub max_todos: Option<u32>,
+    /// How many todos can still be created; `null` for no limit
+    pub remaining: Option<u32>,
+}
+
+impl From<&QuotaUsage> for QuotaUsageDto {
+    fn from(usage: &QuotaUsage) -> Self {
+        QuotaUsageDto {
+            open_todos: usage.open_todos,
+            todos: usage.todos,
+            max_open_todos: usage.quota.max_open_todos,
+            max_todos: usage.quota.max_todos,
+            remaining: usage.remaining(),
+        }
+    }
+}
+
+/// JSON representation of a TodoEvent, tagged with its `type`
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TodoEventDto {
+    TodoCreated {
+        id: String,
+        description: String,
+        #[schema(format = DateTime)]
+        created_at: String,
+    },
+    TodoStateChanged {
+        id: String,
+        from_state: StateDto,
+        to_state: StateDto,
+        #[schema(format = DateTime)]
+        changed_at: String,
+    },
+    TodoArchived {
+        id: String,
+        #[schema(format = DateTime)]
+        archived_at: String,
+    },
+    TodoStale {
+        id: String,
+        /// Id of the escalation rule the todo broke
+        rule: String,
+        state: StateDto,
+        #[schema(format = DateTime)]
+        stale_at: String,
+    },
+    TodoDueDateChanged {
+        id: String,
+        /// `null` when the due date was cleared
+        #[schema(format = DateTime)]
+        due_at: Option<String>,
+        #[schema(format = DateTime)]
+        changed_at: String,
+    },
+    TodoPriorityChanged {
+        id: String,
+        from_priority: PriorityDto,
+        to_priority: PriorityDto,
+        #[schema(format = DateTime)]
+        changed_at: String,
+    },
+    TodoTagAdded {
+        id: String,
+        tag: String,
+        #[schema(format = DateTime)]
+        added_at: String,
+    },
+    TodoTagRemoved {
+        id: String,
+        tag: String,
+        #[schema(format = DateTime)]
+        removed_at: String,
+    },
+    TodoSubtaskAdded {
+        id: String,
+        subtask_id: String,
+        description: String,
+        #[schema(format = DateTime)]
+        added_at: String,
+    },
+    TodoSubtaskToggled {
+        id: String,
+        subtask_id: String,
+        done: bool,
+        #[schema(format = DateTime)]
+        toggled_at: String,
+    },
+    TodoSubtaskRemoved {
+        id: String,
+        subtask_id: String,
+        #[schema(format = DateTime)]
+        removed_at: String,
+    },
+    TodoDescriptionUpdated {
+        id: String,
+        old: String,
+        new: String,
+        #[schema(format = DateTime)]
+        updated_at: String,
+    },
+    TodoBlocked {
+        id: String,
+        blocker_id: String,
+        #[schema(format = DateTime)]
+        blocked_at: String,
+    },
+    TodoUnblocked {
+        id: String,
+        blocker_id: String,
+        #[schema(format = DateTime)]
+        unblocked_at: String,
+    },
+    TodoDeleted {
+        id: String,
+        #[schema(format = DateTime)]
+        deleted_at: String,
+    },
+    TodoRestored {
+        id: String,
+        #[schema(format = DateTime)]
+        restored_at: String,
+    },
+    TodoAttachmentAdded {
+        id: String,
+        content_hash: String,
+        name: String,
+        media_type: String,
+        size: u64,
+        #[schema(format = DateTime)]
+        added_at: String,
+    },
+    TodoAttachmentRemoved {
+        id: String,
+        content_hash: String,
+        #[schema(format = DateTime)]
+        removed_at: String,
+    },
+}
+
+impl From<TodoEvent> for TodoEventDto {
+    fn from(event: TodoEvent) -> Self {
+        match event {
+            TodoEvent::TodoCreated {
+                id,
+                description,
+                created_at,
+            } => TodoEventDto::TodoCreated {
+                id: id.to_string(),
+                description: description.to_string(),
+                created_at: created_at.to_rfc3339(),
+            },
+            TodoEvent::TodoStateChanged {
+                id,
+                from_state,
+                to_state,
+                changed_at,
+            } => TodoEventDto::TodoStateChanged {
+                id: id.to_string(),
+                from_state: from_state.into(),
+                to_state: to_state.into(),
+                changed_at: changed_at.to_rfc3339(),
+            },
+            TodoEvent::TodoArchived { id, archived_at } => TodoEventDto::TodoArchived {
+                id: id.to_string(),
+                archived_at: archived_at.to_rfc3339(),
+            },
+            TodoEvent::TodoStale {
+                id,
+                rule,
+                state,
+                stale_at,
+            } => TodoEventDto::TodoStale {
+                id: id.to_string(),
+                rule: rule.to_string(),
+                state: state.into(),
+                stale_at: stale_at.to_rfc3339(),
+            },
+            TodoEvent::TodoDueDateChanged {
+                id,
+                due_at,
+                changed_at,
+            } => TodoEventDto::TodoDueDateChanged {
+                id: id.to_string(),
+                due_at: due_at.map(|due_at| due_at.to_rfc3339()),
+                changed_at: changed_at.to_rfc3339(),
+            },
+            TodoEvent::TodoPriorityChanged {
+                id,
+                from_priority,
+                to_priority,
+                changed_at,
+            } => TodoEventDto::TodoPriorityChanged {
+                id: id.to_string(),
+                from_priority: from_priority.into(),
+                to_priority: to_priority.into(),
+                changed_at: changed_at.to_rfc3339(),
+            },
+            TodoEvent::TodoTagAdded { id, tag, added_at } => TodoEventDto::TodoTagAdded {
+                id: id.to_string(),
+                tag: tag.to_string(),
+                added_at: added_at.to_rfc3339(),
+            },
+            TodoEvent::TodoTagRemoved {
+                id,
+                tag,
+                removed_at,
+            } => TodoEventDto::TodoTagRemoved {
+                id: id.to_string(),
+                tag: tag.to_string(),
+                removed_at: removed_at.to_rfc3339(),
+            },
+            TodoEvent::TodoSubtaskAdded {
+                id,
+                subtask_id,
+                description,
+                added_at,
+            } => TodoEventDto::TodoSubtaskAdded {
+                id: id.to_string(),
+                subtask_id: subtask_id.to_string(),
+                description: description.to_string(),
+                added_at: added_at.to_rfc3339(),
+            },
+            TodoEvent::TodoSubtaskToggled {
+                id,
+                subtask_id,
+                done,
+                toggled_at,
+            } => TodoEventDto::TodoSubtaskToggled {
+                id: id.to_string(),
+                subtask_id: subtask_id.to_string(),
+                done,
+                toggled_at: toggled_at.to_rfc3339(),
+            },
+            TodoEvent::TodoSubtaskRemoved {
+                id,
+                subtask_id,
+                removed_at,
+            } => TodoEventDto::TodoSubtaskRemoved {
+                id: id.to_string(),
+                subtask_id: subtask_id.to_string(),
+                removed_at: removed_at.to_rfc3339(),
+            },
+            TodoEvent::TodoDescriptionUpdated {
+                id,
+                old,
+                new,
+                updated_at,
+            } => TodoEventDto::TodoDescriptionUpdated {
+                id: id.to_string(),
+                old: old.to_string(),
+                new: new.to_string(),
+                updated_at: updated_at.to_rfc3339(),
+            },
+            TodoEvent::TodoBlocked {
+                id,
+                blocker_id,
+                blocked_at,
+            } => TodoEventDto::TodoBlocked {
+                id: id.to_string(),
+                blocker_id: blocker_id.to_string(),
+                blocked_at: blocked_at.to_rfc3339(),
+            },
+            TodoEvent::TodoUnblocked {
+                id,
+                blocker_id,
+                unblocked_at,
+            } => TodoEventDto::TodoUnblocked {
+                id: id.to_string(),
+                blocker_id: blocker_id.to_string(),
+                unblocked_at: unblocked_at.to_rfc3339(),
+            },
+            TodoEvent::TodoDeleted { id, deleted_at } => TodoEventDto::TodoDeleted {
+                id: id.to_string(),
+                deleted_at: deleted_at.to_rfc3339(),
+            },
+            TodoEvent::TodoRestored { id, restored_at } => TodoEventDto::TodoRestored {
+                id: id.to_string(),
+                restored_at: restored_at.to_rfc3339(),
+            },
+            TodoEvent::TodoAttachmentAdded {
+                id,
+                content_hash,
+                name,
+                media_type,
+                size,
+                added_at,
+            } => TodoEventDto::TodoAttachmentAdded {
+                id: id.to_string(),
+                content_hash: content_hash.to_string(),
+                name: name.to_string(),
+                media_type: media_type.to_string(),
+                size,
+                added_at: added_at.to_rfc3339(),
+            },
+            TodoEvent::TodoAttachmentRemoved {
+                id,
+                content_hash,
+                removed_at,
+            } => TodoEventDto::TodoAttachmentRemoved {
+                id: id.to_string(),
+                content_hash: content_hash.to_string(),
+                removed_at: removed_at.to_rfc3339(),
+            },
+        }
+    }
+}
+
+/// TodoState as `"TODO"`, `"IN_PROGRESS"` or `"DONE"`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum StateDto {
+    Todo,
+    InProgress,
+    Done,
+}
+
+impl From<TodoState> for StateDto {
+    fn from(state: TodoState) -> Self {
+        match state {
+            TodoState::Todo => StateDto::Todo,
+            TodoState::InProgress => StateDto::InProgress,
+            TodoState::Done => StateDto::Done,
+        }
+    }
+}
+
+impl From<StateDto> for TodoState {
+    fn from(state: StateDto) -> Self {
+        match state {
+            StateDto::Todo => TodoState::Todo,
+            StateDto::InProgress => TodoState::InProgress,
+            StateDto::Done => TodoState::Done,
+        }
+    }
+}
+
+/// Priority as `"LOW"`, `"MEDIUM"`, `"HIGH"` or `"URGENT"`
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PriorityDto {
+    Low,
+    #[default]
+    Medium,
+    High,
+    Urgent,
+}
+
+impl From<Priority> for PriorityDto {
+    fn from(priority: Priority) -> Self {
+        match priority {
+            Priority::Low => PriorityDto::Low,
+            Priority::Medium => PriorityDto::Medium,
+            Priority::High => PriorityDto::High,
+            Priority::Urgent => PriorityDto::Urgent,
+        }
+    }
+}
+
+impl From<PriorityDto> for Priority {
+    fn from(priority: PriorityDto) -> Self {
+        match priority {
+            PriorityDto::Low => Priority::Low,
+            PriorityDto::Medium => Priority::Medium,
+            PriorityDto::High => Priority::High,
+            PriorityDto::Urgent => Priority::Urgent,
+        }
+    }
+}
+
+/// Body of `POST /todos`
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateTodoRequest {
+    pub description: String,
+}
+
+/// Body of `PUT /todos/{id}/state`
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ChangeStateRequest {
+    pub state: StateDto,
+}
+
+/// Query of `GET /todos`
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListTodosQuery {
+    /// Only return todos in this state
+    pub state: Option<StateDto>,
+    /// Only return todos matching this search query, such as
+    /// `state:in_progress -created<2025-01-01 "quarterly report"`
+    pub q: Option<String>,
+}
+
+/// Body of every error response, an RFC 9457 problem details object served as
+/// `application/problem+json`
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ErrorDto {
+    /// `urn:hk-todo:error:<code>`
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    /// The error's message
+    pub detail: String,
+    /// The `TodoError` or `AuthError` name, such as `TODO_NOT_FOUND`
+    pub code: String,
+}
+
+impl From<ProblemDetails> for ErrorDto {
+    fn from(problem: ProblemDetails) -> Self {
+        ErrorDto {
+            problem_type: problem.problem_type,
+            title: problem.title,
+            status: problem.status,
+            detail: problem.detail,
+            code: problem.code,
+        }
+    }
+}
+
+/// One entry of the activity feed
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ActivityDto {
+    pub sequence: u64,
+    pub todo_id: String,
+    /// Subject who made the change, or `null` when the server has no authenticator
+    pub actor: Option<String>,
+    /// `TODO_CREATED`, `TODO_STATE_CHANGED`, `TODO_ARCHIVED`, `TODO_STALE`,
+    /// `TODO_DUE_DATE_CHANGED`, `TODO_PRIORITY_CHANGED`, `TODO_TAG_ADDED`, `TODO_TAG_REMOVED`,
+    /// `TODO_SUBTASK_ADDED`, `TODO_SUBTASK_TOGGLED`, `TODO_SUBTASK_REMOVED`,
+    /// `TODO_DESCRIPTION_UPDATED`, `TODO_BLOCKED`, `TODO_UNBLOCKED`, `TODO_DELETED`,
+    /// `TODO_RESTORED`, `TODO_ATTACHMENT_ADDED` or `TODO_ATTACHMENT_REMOVED`
+    #[serde(rename = "type")]
+    pub event_type: String,
+    /// What happened and how long ago, in the language negotiated from `Accept-Language`
+    pub message: String,
+    #[schema(format = DateTime)]
+    pub occurred_at: String,
+}
+
+impl ActivityDto {
+    pub(crate) fn new(activity: &Activity, message: String) -> Self {
+        let event_type = match activity.event {
+            TodoEvent::TodoCreated { .. } => "TODO_CREATED",
+            TodoEvent::TodoStateChanged { .. } => "TODO_STATE_CHANGED",
+            TodoEvent::TodoArchived { .. } => "TODO_ARCHIVED",
+            TodoEvent::TodoStale { .. } => "TODO_STALE",
+            TodoEvent::TodoDueDateChanged { .. } => "TODO_DUE_DATE_CHANGED",
+            TodoEvent::TodoPriorityChanged { .. } => "TODO_PRIORITY_CHANGED",
+            TodoEvent::TodoTagAdded { .. } => "TODO_TAG_ADDED",
+            TodoEvent::TodoTagRemoved { .. } => "TODO_TAG_REMOVED",
+            TodoEvent::TodoSubtaskAdded { .. } => "TODO_SUBTASK_ADDED",
+            TodoEvent::TodoSubtaskToggled { .. } => "TODO_SUBTASK_TOGGLED",
+            TodoEvent::TodoSubtaskRemoved { .. } => "TODO_SUBTASK_REMOVED",
+            TodoEvent::TodoDescriptionUpdated { .. } => "TODO_DESCRIPTION_UPDATED",
+            TodoEvent::TodoBlocked { .. } => "TODO_BLOCKED",
+            TodoEvent::TodoUnblocked { .. } => "TODO_UNBLOCKED",
+            TodoEvent::TodoDeleted { .. } => "TODO_DELETED",
+            TodoEvent::TodoRestored { .. } => "TODO_RESTORED",
+            TodoEvent::TodoAttachmentAdded { .. } => "TODO_ATTACHMENT_ADDED",
+            TodoEvent::TodoAttachmentRemoved { .. } => "TODO_ATTACHMENT_REMOVED",
+        };
+        ActivityDto {
+            sequence: activity.sequence,
+            todo_id: activity.todo_id().to_string(),
+            actor: activity.actor.as_deref().map(str::to_string),
+            event_type: event_type.to_string(),
+            message,
+            occurred_at: activity.occurred_at().to_rfc3339(),
+        }
+    }
+}
+
+/// Body of `GET /activity`
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ActivityPageDto {
+    /// Newest first
+    pub items: Vec<ActivityDto>,
+    /// Cursor of the next, older page, or `null` on the last page
+    pub next_cursor: Option<u64>,
+}
+
+/// JSON representation of a Webhook; its secret is never returned
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WebhookDto {
+    pub id: String,
+    pub url: String,
+    /// Events delivered to the URL; empty for every event
+    pub events: Vec<WebhookEvent>,
+    #[schema(format = DateTime)]
+    pub created_at: String,
+}
+
+impl From<&Webhook> for WebhookDto {
+    fn from(webhook: &Webhook) -> Self {
+        WebhookDto {
+            id: webhook.id.clone(),
+            url: webhook.url.clone(),
+            events: webhook.events.clone(),
+            created_at: webhook.created_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Body of `POST /webhooks`
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RegisterWebhookRequest {
+    /// `http` or `https` URL the events are POSTed to
+    pub url: String,
+    /// Key of the `x-hk-todo-signature` HMAC
+    pub secret: String,
+    /// Events to deliver; omitted or empty for every event
+    #[serde(default)]
+    pub events: Vec<WebhookEvent>,
+}
+
+/// JSON representation of a DeadLetter
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DeadLetterDto {
+    pub webhook_id: String,
+    pub url: String,
+    /// The CloudEvent that could not be delivered
+    #[schema(value_type = Object)]
+    pub event: CloudEvent,
+    pub attempts: u32,
+    /// Why the last attempt failed
+    pub error: String,
+    #[schema(format = DateTime)]
+    pub failed_at: String,
+}
+
+impl From<&DeadLetter> for DeadLetterDto {
+    fn from(dead_letter: &DeadLetter) -> Self {
+        DeadLetterDto {
+            webhook_id: dead_letter.webhook_id.clone(),
+            url: dead_letter.url.clone(),
+            event: dead_letter.event.clone(),
+            attempts: dead_letter.attempts,
+            error: dead_letter.error.clone(),
+            failed_at: dead_letter.failed_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Body of the `POST /webhooks/dead-letters/retry` response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RetriedDto {
+    /// Dead letters scheduled for delivery again
+    pub retried: usize,
+}
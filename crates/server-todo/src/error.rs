@@ -0,0 +1,167 @@
+use axum::Json;
+use axum::body::Body;
+use axum::extract::{Request, State};
+use axum::http::{HeaderValue, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use std::sync::Arc;
+use todo::TodoError;
+use todo::application::auth::AuthError;
+use todo::application::error_mapping::{PROBLEM_JSON, ProblemDetails};
+
+use crate::dto::ErrorDto;
+use crate::routes::AppState;
+use crate::webhooks::WebhookError;
+
+/// The error an `application/problem+json` response was made from, kept in its extensions
+/// so `localize_problems` can describe it in the caller's language
+#[derive(Clone)]
+enum ProblemSource {
+    Todo(TodoError),
+    Auth(AuthError),
+}
+
+/// TodoError as an `application/problem+json` response, with the status of
+/// `TodoError::http_status`
+pub struct ApiError(pub TodoError);
+
+impl From<TodoError> for ApiError {
+    fn from(err: TodoError) -> Self {
+        ApiError(err)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status =
+            StatusCode::from_u16(self.0.http_status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let body = ErrorDto::from(self.0.problem());
+        let mut response =
+            (status, [(header::CONTENT_TYPE, PROBLEM_JSON)], Json(body)).into_response();
+        response
+            .extensions_mut()
+            .insert(ProblemSource::Todo(self.0));
+        response
+    }
+}
+
+/// AuthError as an `application/problem+json` response, with the status of
+/// `AuthError::http_status` and, for a `401` or `403`, the RFC 6750 `WWW-Authenticate`
+/// challenge
+pub struct AuthRejection(pub AuthError);
+
+impl IntoResponse for AuthRejection {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.0.http_status()).unwrap_or(StatusCode::UNAUTHORIZED);
+        let body = ErrorDto::from(self.0.problem());
+        let mut response =
+            (status, [(header::CONTENT_TYPE, PROBLEM_JSON)], Json(body)).into_response();
+        let challenge = match &self.0 {
+            AuthError::MissingCredentials => Some("Bearer".to_string()),
+            AuthError::InvalidToken(_) => Some("Bearer error=\"invalid_token\"".to_string()),
+            AuthError::InsufficientScope(scope) => Some(format!(
+                "Bearer error=\"insufficient_scope\", scope=\"{}\"",
+                scope
+            )),
+            AuthError::Forbidden(_) | AuthError::Unavailable(_) => None,
+        };
+        if let Some(challenge) = challenge.and_then(|value| HeaderValue::from_str(&value).ok()) {
+            response
+                .headers_mut()
+                .insert(header::WWW_AUTHENTICATE, challenge);
+        }
+        response
+            .extensions_mut()
+            .insert(ProblemSource::Auth(self.0));
+        response
+    }
+}
+
+/// A search query `TodoFilter::parse` rejects, as a `400` `application/problem+json`
+/// response with the code `INVALID_QUERY`
+pub struct InvalidQuery(pub String);
+
+impl IntoResponse for InvalidQuery {
+    fn into_response(self) -> Response {
+        let body = ErrorDto {
+            problem_type: "urn:hk-todo:error:INVALID_QUERY".to_string(),
+            title: "Invalid query".to_string(),
+            status: StatusCode::BAD_REQUEST.as_u16(),
+            detail: self.0,
+            code: "INVALID_QUERY".to_string(),
+        };
+        (
+            StatusCode::BAD_REQUEST,
+            [(header::CONTENT_TYPE, PROBLEM_JSON)],
+            Json(body),
+        )
+            .into_response()
+    }
+}
+
+/// `422` for a webhook that cannot be registered and `404` for an unknown one, as an
+/// `application/problem+json` response
+impl IntoResponse for WebhookError {
+    fn into_response(self) -> Response {
+        let (status, code, title) = match self {
+            WebhookError::InvalidUrl(_) | WebhookError::EmptySecret => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "INVALID_WEBHOOK",
+                "Invalid webhook",
+            ),
+            WebhookError::WebhookNotFound => (
+                StatusCode::NOT_FOUND,
+                "WEBHOOK_NOT_FOUND",
+                "Webhook not found",
+            ),
+        };
+        let body = ErrorDto {
+            problem_type: format!("urn:hk-todo:error:{}", code),
+            title: title.to_string(),
+            status: status.as_u16(),
+            detail: self.to_string(),
+            code: code.to_string(),
+        };
+        (status, [(header::CONTENT_TYPE, PROBLEM_JSON)], Json(body)).into_response()
+    }
+}
+
+/// Rewrites the title and detail of `ApiError` and `AuthRejection` responses in the language
+/// the request's `Accept-Language` prefers among those of `AppState::with_messages`
+///
+/// The `code` stays the same whatever the language. The response names its language in
+/// `Content-Language`.
+pub(crate) async fn localize_problems(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let accept_language = request
+        .headers()
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    let response = next.run(request).await;
+    let Some(source) = response.extensions().get::<ProblemSource>().cloned() else {
+        return response;
+    };
+    let messages = &state.messages;
+    let locale = messages.negotiate(&accept_language);
+    let problem: ProblemDetails = match &source {
+        ProblemSource::Todo(err) => messages.localize_problem(locale, err.problem(), err),
+        ProblemSource::Auth(err) => messages.localize_problem(locale, err.problem(), err),
+    };
+    let Ok(body) = serde_json::to_vec(&ErrorDto::from(problem)) else {
+        return response;
+    };
+    let (mut parts, _) = response.into_parts();
+    parts.headers.remove(header::CONTENT_LENGTH);
+    if let Ok(language) = HeaderValue::from_str(locale) {
+        parts.headers.insert(header::CONTENT_LANGUAGE, language);
+    }
+    parts
+        .headers
+        .append(header::VARY, HeaderValue::from_static("accept-language"));
+    Response::from_parts(parts, Body::from(body))
+}
@@ -0,0 +1,40 @@
+use async_trait::async_trait;
+use todo::application::escalation::EscalateTodosHandler;
+use todo::{TodoEvent, TodoRepository};
+
+use crate::scheduler::ScheduledJob;
+
+/// ScheduledJob emitting `TodoStale` events for the todos that broke an escalation rule, and
+/// raising their priority
+///
+/// Every run logs the ids of the todos found stale and the rules they broke.
+pub struct EscalationJob<R = Box<dyn TodoRepository>> {
+    handler: EscalateTodosHandler<R>,
+}
+
+impl<R: TodoRepository> EscalationJob<R> {
+    /// Creates a new EscalationJob running `handler`
+    pub fn new(handler: EscalateTodosHandler<R>) -> Self {
+        EscalationJob { handler }
+    }
+}
+
+#[async_trait]
+impl<R: TodoRepository> ScheduledJob for EscalationJob<R> {
+    async fn run(&self) -> Result<(), String> {
+        let events = self
+            .handler
+            .escalate()
+            .await
+            .map_err(|err| err.to_string())?;
+        let mut stale = 0;
+        for event in &events {
+            if let TodoEvent::TodoStale { id, rule, .. } = event {
+                tracing::info!(todo.id = %id, rule = %rule, "todo is stale");
+                stale += 1;
+            }
+        }
+        tracing::info!(stale_todos = stale, "escalation run finished");
+        Ok(())
+    }
+}
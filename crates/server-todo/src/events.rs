@@ -0,0 +1,141 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use todo::infrastructure::config::DEFAULT_EVENT_RETENTION;
+use todo::{TenantId, TodoEvent};
+use tokio::sync::broadcast;
+
+/// Events buffered per subscriber before it is reported as lagging
+pub(crate) const CAPACITY: usize = 256;
+
+/// A published event and its position in the bus, starting at 1
+#[derive(Debug, Clone)]
+pub struct SequencedEvent {
+    pub sequence: u64,
+    pub event: TodoEvent,
+}
+
+/// Events published from a cursor on: the retained backlog, then the live receiver
+pub struct Replay {
+    pub backlog: Vec<SequencedEvent>,
+    /// `true` when events after the cursor were already dropped from the log
+    pub missed: bool,
+    pub receiver: broadcast::Receiver<SequencedEvent>,
+}
+
+struct Log {
+    retention: usize,
+    next_sequence: u64,
+    events: VecDeque<SequencedEvent>,
+}
+
+/// In-process bus carrying the events emitted through this server's handlers
+///
+/// Every event gets a sequence number, and the last 1024 are kept so subscribers can
+/// resume from a cursor; `with_retention` keeps another number. Only changes made through
+/// this server are published; other processes writing to the same repository are not
+/// observed, and the log does not survive a restart.
+///
+/// When callers are scoped to their list, events carry the ids of their todos as the
+/// repository stores them, `<list>/<id>`, and `for_list` hands out those of one list.
+#[derive(Clone)]
+pub struct EventBus {
+    log: Arc<Mutex<Log>>,
+    sender: broadcast::Sender<SequencedEvent>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self::with_retention(DEFAULT_EVENT_RETENTION)
+    }
+
+    /// Creates a bus keeping the last `retention` events, at least 1, for replay
+    pub fn with_retention(retention: usize) -> Self {
+        EventBus {
+            log: Arc::new(Mutex::new(Log {
+                retention: retention.max(1),
+                next_sequence: 1,
+                events: VecDeque::new(),
+            })),
+            sender: broadcast::channel(CAPACITY).0,
+        }
+    }
+
+    /// Appends `events` to the log and sends them to every current subscriber
+    pub fn publish(&self, events: &[TodoEvent]) {
+        let mut log = self.lock_log();
+        for event in events {
+            let event = SequencedEvent {
+                sequence: log.next_sequence,
+                event: event.clone(),
+            };
+            log.next_sequence += 1;
+            if log.events.len() == log.retention {
+                log.events.pop_front();
+            }
+            log.events.push_back(event.clone());
+            // Sending only fails when nobody is subscribed
+            let _ = self.sender.send(event);
+        }
+    }
+
+    /// Receives every event published from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<SequencedEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Receives the retained events after `cursor`, then every event published from now on
+    ///
+    /// The backlog and the receiver never overlap or leave a gap.
+    pub fn subscribe_from(&self, cursor: u64) -> Replay {
+        let log = self.lock_log();
+        let receiver = self.sender.subscribe();
+        let oldest = log
+            .events
+            .front()
+            .map_or(log.next_sequence, |event| event.sequence);
+        Replay {
+            backlog: log
+                .events
+                .iter()
+                .filter(|event| event.sequence > cursor)
+                .cloned()
+                .collect(),
+            missed: cursor + 1 < oldest,
+            receiver,
+        }
+    }
+
+    /// Events sent but not yet received by every subscriber
+    ///
+    /// Reaching `CAPACITY` means the slowest subscriber is about to lag and miss events.
+    pub fn backlog(&self) -> usize {
+        self.sender.len()
+    }
+
+    fn lock_log(&self) -> std::sync::MutexGuard<'_, Log> {
+        // The log stays consistent even if a publisher panicked
+        self.log
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// `event` as callers working on `list` see it, or `None` if it is about a todo of another
+/// list; every event when callers are not scoped to a list
+pub fn for_list(list: Option<&TenantId>, event: &TodoEvent) -> Option<TodoEvent> {
+    let Some(list) = list else {
+        return Some(event.clone());
+    };
+    list.unscope(event.todo_id())?;
+    Some(
+        event
+            .clone()
+            .map_todo_ids(|id| list.unscope(id).unwrap_or(id).into()),
+    )
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
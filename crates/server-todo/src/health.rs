@@ -0,0 +1,116 @@
+use axum::Json;
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::events::CAPACITY;
+use crate::routes::AppState;
+
+/// How long the repository may take to answer a readiness probe
+const REPOSITORY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Outcome of a health check, from best to worst
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Pass,
+    /// Serving, but degraded
+    Warn,
+    /// Not able to serve
+    Fail,
+}
+
+/// JSON body of `GET /healthz` and `GET /readyz`
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HealthDto {
+    /// The worst status of the checks
+    pub status: HealthStatus,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub checks: BTreeMap<String, CheckDto>,
+}
+
+/// One dependency check of `GET /readyz`
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CheckDto {
+    pub status: HealthStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Events not yet received by every subscriber, for the `event_bus` check
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backlog: Option<usize>,
+    /// Backlog at which subscribers start missing events, for the `event_bus` check
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capacity: Option<usize>,
+}
+
+/// `GET /healthz`: liveness, `200` whenever the server answers
+///
+/// Dependencies are left to `/readyz`, so a slow repository does not get the process
+/// restarted.
+pub async fn healthz() -> Json<HealthDto> {
+    Json(HealthDto {
+        status: HealthStatus::Pass,
+        checks: BTreeMap::new(),
+    })
+}
+
+/// `GET /readyz`: readiness, `503` when a check fails
+///
+/// - `repository`: fails if the repository's health check errors or takes over 2 seconds
+/// - `event_bus`: warns once the backlog reaches the per-subscriber capacity
+pub async fn readyz(State(state): State<Arc<AppState>>) -> (StatusCode, Json<HealthDto>) {
+    let repository =
+        match tokio::time::timeout(REPOSITORY_TIMEOUT, state.repository.health_check()).await {
+            Ok(Ok(())) => check(HealthStatus::Pass, None),
+            Ok(Err(err)) => check(HealthStatus::Fail, Some(err.to_string())),
+            Err(_) => check(
+                HealthStatus::Fail,
+                Some(format!(
+                    "no answer within {} seconds",
+                    REPOSITORY_TIMEOUT.as_secs()
+                )),
+            ),
+        };
+
+    let backlog = state.events.backlog();
+    let event_bus = CheckDto {
+        backlog: Some(backlog),
+        capacity: Some(CAPACITY),
+        ..if backlog < CAPACITY {
+            check(HealthStatus::Pass, None)
+        } else {
+            check(
+                HealthStatus::Warn,
+                Some("a subscriber is lagging".to_string()),
+            )
+        }
+    };
+
+    let checks = BTreeMap::from([
+        ("repository".to_string(), repository),
+        ("event_bus".to_string(), event_bus),
+    ]);
+    let status = checks
+        .values()
+        .map(|check| check.status)
+        .max()
+        .unwrap_or(HealthStatus::Pass);
+    let code = if status == HealthStatus::Fail {
+        StatusCode::SERVICE_UNAVAILABLE
+    } else {
+        StatusCode::OK
+    };
+    (code, Json(HealthDto { status, checks }))
+}
+
+fn check(status: HealthStatus, message: Option<String>) -> CheckDto {
+    CheckDto {
+        status,
+        message,
+        backlog: None,
+        capacity: None,
+    }
+}
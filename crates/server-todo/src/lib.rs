@@ -0,0 +1,30 @@
+pub mod activity;
+pub mod archive;
+pub mod auth;
+pub mod config;
+pub mod digest;
+pub mod dto;
+pub mod error;
+pub mod escalation;
+pub mod events;
+pub mod health;
+pub mod metrics;
+pub mod openapi;
+pub mod retention;
+pub mod routes;
+pub mod scheduler;
+pub mod sse;
+pub mod telemetry;
+pub mod webhooks;
+pub mod ws;
+
+pub use archive::ArchiveJob;
+pub use config::ServerConfig;
+pub use digest::DigestJob;
+pub use escalation::EscalationJob;
+pub use events::EventBus;
+pub use openapi::ApiDoc;
+pub use retention::RetentionJob;
+pub use routes::{AppState, router};
+pub use scheduler::{CronSchedule, Scheduler};
+pub use webhooks::WebhookManager;
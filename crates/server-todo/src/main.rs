@@ -0,0 +1,212 @@
+use server_todo::auth::refresh_keys;
+use server_todo::telemetry::Telemetry;
+use server_todo::{
+    AppState, ArchiveJob, DigestJob, EscalationJob, RetentionJob, Scheduler, ServerConfig,
+    WebhookManager, router,
+};
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+use todo::application::access_control::AccessControl;
+use todo::application::activity_feed::ActivityFeed;
+use todo::application::api_key_authenticator::ApiKeyAuthenticator;
+use todo::application::archive::ArchiveTodosHandler;
+use todo::application::auth::Authenticator;
+use todo::application::digest::DigestHandler;
+use todo::application::escalation::EscalateTodosHandler;
+use todo::application::retention::ApplyRetentionHandler;
+use todo::domain::escalation::EscalationRuleRepository;
+use todo::domain::trash::TrashRepository;
+use todo::infrastructure::event_sinks::EventLog;
+use todo::infrastructure::event_stores::FileEventStore;
+use todo::infrastructure::jwt_authenticator::JwtAuthenticator;
+use todo::infrastructure::mailers::SmtpMailer;
+use todo::infrastructure::repositories::api_key::FileApiKeyRepository;
+use todo::infrastructure::repositories::escalation::FileEscalationRuleRepository;
+use todo::infrastructure::repositories::membership::FileMembershipRepository;
+use todo::infrastructure::repositories::todo::FileTodoRepository;
+use todo::infrastructure::repositories::trash::FileTrashRepository;
+use todo::{EventSink, EventStore};
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let config = match ServerConfig::from_env() {
+        Ok(config) => config,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let telemetry = match Telemetry::install(config.otlp_endpoint.as_deref()) {
+        Ok(telemetry) => telemetry,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            return ExitCode::FAILURE;
+        }
+    };
+    let repository = config.backend.repository();
+    let mut state = AppState::new(repository.clone())
+        .with_id_generator(config.id_format.generator())
+        .with_event_retention(config.event_retention)
+        .with_quota(config.quota);
+    if config.metrics {
+        state = state.with_metrics(telemetry.prometheus.clone());
+    }
+    if config.webhooks {
+        state = state.with_webhooks(Arc::new(WebhookManager::new()));
+    }
+    if config.activity_feed {
+        state = state.with_activity_feed(Arc::new(ActivityFeed::new()));
+    }
+    let mut authenticator: Option<Arc<dyn Authenticator>> = None;
+    if let (Some(issuer), Some(audience)) = (&config.auth_issuer, &config.auth_audience) {
+        match JwtAuthenticator::discover(issuer, audience).await {
+            Ok(jwt) => {
+                let jwt = Arc::new(jwt);
+                refresh_keys(jwt.clone());
+                authenticator = Some(jwt);
+            }
+            Err(err) => {
+                eprintln!("error: {}", err);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    if let Some(path) = &config.api_key_file {
+        let api_keys = ApiKeyAuthenticator::new(Arc::new(FileApiKeyRepository::new(path)));
+        authenticator = Some(match authenticator {
+            Some(jwt) => Arc::new(api_keys.with_fallback(jwt)),
+            None => Arc::new(api_keys),
+        });
+    }
+    if let Some(path) = &config.roles_file {
+        if authenticator.is_none() {
+            eprintln!("error: TODO_ROLES_FILE needs TODO_AUTH_ISSUER or TODO_API_KEY_FILE");
+            return ExitCode::FAILURE;
+        }
+        let memberships = Arc::new(FileMembershipRepository::new(path));
+        state = state.with_access_control(Arc::new(AccessControl::new(memberships)));
+    }
+    if let Some(authenticator) = authenticator {
+        state = state.with_authenticator(authenticator);
+    }
+    let mut trash: Option<Arc<dyn TrashRepository>> = None;
+    if let Some(path) = &config.trash_file {
+        let file: Arc<dyn TrashRepository> = Arc::new(FileTrashRepository::new(path));
+        state = state.with_trash(file.clone());
+        trash = Some(file);
+    }
+    let mut event_store: Option<Arc<dyn EventStore>> = None;
+    let mut event_sink: Option<Arc<dyn EventSink>> = None;
+    match &config.event_log {
+        // A file log is read back for the history of each todo
+        EventLog::File(path) => {
+            let store: Arc<dyn EventStore> = Arc::new(FileEventStore::new(path));
+            state = state.with_event_store(store.clone());
+            event_store = Some(store);
+        }
+        event_log => match event_log.sink() {
+            Ok(Some(sink)) => {
+                state = state.with_event_sink(sink.clone());
+                event_sink = Some(sink);
+            }
+            Ok(None) => {}
+            Err(message) => {
+                eprintln!("error: {}", message);
+                return ExitCode::FAILURE;
+            }
+        },
+    }
+    let mut scheduler = Scheduler::new().with_jitter(Duration::from_secs(30));
+    if let Some(email) = &config.digest_email {
+        // Completion times are only known from a file log
+        let Some(event_store) = event_store.clone() else {
+            eprintln!("error: TODO_DIGEST_TO needs a file: TODO_EVENT_LOG");
+            return ExitCode::FAILURE;
+        };
+        let mailer = SmtpMailer::new(&email.smtp_host).with_port(email.smtp_port);
+        let digest = DigestHandler::new(repository.clone(), event_store).with_mailer(
+            Arc::new(mailer),
+            email.from.clone(),
+            email.to.clone(),
+        );
+        let job = Arc::new(DigestJob::new(digest));
+        scheduler = scheduler.with_job("digest", config.digest_schedule.clone(), job);
+    }
+    if let Some(path) = &config.escalation_rules_file {
+        let rules: Arc<dyn EscalationRuleRepository> =
+            Arc::new(FileEscalationRuleRepository::new(path));
+        let escalation = {
+            let event_store = event_store.clone();
+            move |repository| {
+                let handler = EscalateTodosHandler::new(repository, rules.clone());
+                match event_store.clone() {
+                    Some(event_store) => handler.with_event_store(event_store),
+                    None => handler,
+                }
+            }
+        };
+        let mut job = escalation(repository.clone());
+        state = state.with_escalations(escalation);
+        if let Some(event_sink) = event_sink.clone() {
+            job = job.with_event_sink(event_sink);
+        }
+        let job = Arc::new(EscalationJob::new(job));
+        scheduler = scheduler.with_job("escalation", config.escalation_schedule.clone(), job);
+    }
+    if let (true, Some(path)) = (config.archive.is_enabled(), &config.archive_file) {
+        let mut archive = ArchiveTodosHandler::new(
+            repository.clone(),
+            Arc::new(FileTodoRepository::new(path)),
+            config.archive.clone(),
+        );
+        if let Some(event_store) = event_store.clone() {
+            archive = archive.with_event_store(event_store);
+        }
+        if let Some(event_sink) = event_sink {
+            archive = archive.with_event_sink(event_sink);
+        }
+        let job = Arc::new(ArchiveJob::new(archive));
+        scheduler = scheduler.with_job("archive", config.archive_schedule.clone(), job);
+    }
+    if config.retention.is_enabled() {
+        let mut retention = ApplyRetentionHandler::new(repository, config.retention.clone());
+        if let Some(event_store) = event_store {
+            retention = retention.with_event_store(event_store);
+        }
+        if let Some(trash) = trash {
+            retention = retention.with_trash(trash);
+        }
+        let job = Arc::new(RetentionJob::new(retention));
+        scheduler = scheduler.with_job("retention", config.retention_schedule.clone(), job);
+    }
+    let _jobs = Arc::new(scheduler).start();
+    let app = router(Arc::new(state));
+    let listener = match tokio::net::TcpListener::bind(config.addr).await {
+        Ok(listener) => listener,
+        Err(err) => {
+            eprintln!("error: cannot bind {}: {}", config.addr, err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    println!(
+        "Listening on http://{} ({:?} backend)",
+        config.addr, config.backend
+    );
+    // Stopping on Ctrl-C lets the OTLP exporters flush what they have buffered
+    let served = axum::serve(listener, app)
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await;
+    telemetry.shutdown();
+    match served {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
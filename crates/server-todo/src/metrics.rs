@@ -0,0 +1,64 @@
+use axum::extract::State;
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+use metrics_util::layers::FanoutBuilder;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::routes::AppState;
+
+/// How often histogram samples are drained into the exposition
+const UPKEEP_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Installs a Prometheus recorder as the process-wide `metrics` recorder
+///
+/// Call once at startup, within a Tokio runtime that runs the recorder's upkeep, and pass
+/// the handle to `AppState::with_metrics`; the todo handler metrics are described so the
+/// exposition carries their help texts.
+pub fn install() -> Result<PrometheusHandle, String> {
+    install_with(None)
+}
+
+/// Installs the Prometheus recorder, fanning out to `otlp` as well when given
+pub(crate) fn install_with(
+    otlp: Option<metrics_exporter_opentelemetry::Recorder>,
+) -> Result<PrometheusHandle, String> {
+    let prometheus = PrometheusBuilder::new().build_recorder();
+    let handle = prometheus.handle();
+    let install_error =
+        |err: &dyn std::fmt::Display| format!("cannot install metrics recorder: {}", err);
+    match otlp {
+        Some(otlp) => metrics::set_global_recorder(
+            FanoutBuilder::default()
+                .add_recorder(prometheus)
+                .add_recorder(otlp)
+                .build(),
+        )
+        .map_err(|err| install_error(&err))?,
+        None => metrics::set_global_recorder(prometheus).map_err(|err| install_error(&err))?,
+    }
+    todo::application::metrics::describe();
+
+    let upkeep = handle.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(UPKEEP_INTERVAL);
+        loop {
+            interval.tick().await;
+            upkeep.run_upkeep();
+        }
+    });
+    Ok(handle)
+}
+
+/// `GET /metrics`: the Prometheus text exposition, or `404` when metrics are not enabled
+pub async fn metrics_handler(State(state): State<Arc<AppState>>) -> Response {
+    match &state.metrics {
+        Some(handle) => (
+            [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+            handle.render(),
+        )
+            .into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
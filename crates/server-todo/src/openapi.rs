@@ -0,0 +1,93 @@
+use axum::Json;
+use axum::response::Html;
+use utoipa::OpenApi;
+
+use crate::dto::{
+    ActivityDto, ActivityPageDto, AttachmentDto, ChangeStateRequest, CreateTodoRequest,
+    DeadLetterDto, ErrorDto, EscalationDto, PriorityDto, QuotaUsageDto, RegisterWebhookRequest,
+    RetriedDto, StateDto, SubtaskDto, TodoDto, TodoEventDto, TrashedTodoDto, WebhookDto,
+};
+use crate::webhooks::WebhookEvent;
+
+/// OpenAPI 3 document generated from the route handlers and DTOs
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "hk-todo REST API", description = "Todo management over HTTP"),
+    paths(
+        crate::routes::list_todos,
+        crate::routes::create_todo,
+        crate::routes::get_todo,
+        crate::routes::get_todo_history,
+        crate::routes::undo_last_change,
+        crate::routes::change_state,
+        crate::routes::delete_todo,
+        crate::routes::list_trash,
+        crate::routes::restore_todo,
+        crate::routes::purge_todo,
+        crate::routes::get_quota_usage,
+        crate::routes::list_escalations,
+        crate::sse::sse_handler,
+        crate::activity::activity_handler,
+        crate::webhooks::list_webhooks,
+        crate::webhooks::register_webhook,
+        crate::webhooks::unregister_webhook,
+        crate::webhooks::list_dead_letters,
+        crate::webhooks::retry_dead_letters,
+    ),
+    components(schemas(
+        TodoDto,
+        SubtaskDto,
+        AttachmentDto,
+        TodoEventDto,
+        TrashedTodoDto,
+        QuotaUsageDto,
+        EscalationDto,
+        ActivityDto,
+        ActivityPageDto,
+        StateDto,
+        PriorityDto,
+        CreateTodoRequest,
+        ChangeStateRequest,
+        ErrorDto,
+        WebhookDto,
+        WebhookEvent,
+        RegisterWebhookRequest,
+        DeadLetterDto,
+        RetriedDto,
+    )),
+    tags(
+        (name = "todos", description = "Create, list, update and delete todos"),
+        (name = "events", description = "Live todo events and the activity feed"),
+        (name = "webhooks", description = "Todo events delivered to registered URLs"),
+    ),
+)]
+pub struct ApiDoc;
+
+/// `GET /openapi.json`
+pub async fn openapi_json() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+/// `GET /docs`: Swagger UI for `/openapi.json`, with its assets loaded from unpkg
+pub async fn swagger_ui() -> Html<&'static str> {
+    Html(SWAGGER_UI)
+}
+
+const SWAGGER_UI: &str = r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8">
+  <title>hk-todo REST API</title>
+  <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@5/swagger-ui.css">
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js"></script>
+  <script>
+    window.onload = () => {
+      window.ui = SwaggerUIBundle({ url: "/openapi.json", dom_id: "#swagger-ui" });
+    };
+  </script>
+</body>
+</html>
+"##;
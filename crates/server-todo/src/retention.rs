@@ -0,0 +1,57 @@
+use async_trait::async_trait;
+use std::sync::{Mutex, PoisonError};
+use todo::TodoRepository;
+use todo::application::retention::{ApplyRetentionHandler, RetentionReport};
+
+use crate::scheduler::ScheduledJob;
+
+/// ScheduledJob applying the retention policy
+///
+/// Every run logs the ids of the todos it deleted or emptied from the trash and the number
+/// of events it compacted, and keeps its report for `last_report`.
+pub struct RetentionJob<R = Box<dyn TodoRepository>> {
+    handler: ApplyRetentionHandler<R>,
+    last_report: Mutex<Option<RetentionReport>>,
+}
+
+impl<R: TodoRepository> RetentionJob<R> {
+    /// Creates a new RetentionJob running `handler`
+    pub fn new(handler: ApplyRetentionHandler<R>) -> Self {
+        RetentionJob {
+            handler,
+            last_report: Mutex::new(None),
+        }
+    }
+
+    /// The report of the last successful run, if any
+    pub fn last_report(&self) -> Option<RetentionReport> {
+        self.last_report
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+#[async_trait]
+impl<R: TodoRepository> ScheduledJob for RetentionJob<R> {
+    async fn run(&self) -> Result<(), String> {
+        let report = self.handler.apply().await.map_err(|err| err.to_string())?;
+        for id in &report.deleted_todos {
+            tracing::info!(todo.id = %id, "retention deleted a done todo");
+        }
+        for id in &report.emptied_trash {
+            tracing::info!(todo.id = %id, "retention emptied a todo from the trash");
+        }
+        tracing::info!(
+            deleted_todos = report.deleted_todos.len(),
+            compacted_events = report.compacted_events,
+            emptied_trash = report.emptied_trash.len(),
+            "retention run finished"
+        );
+        *self
+            .last_report
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(report);
+        Ok(())
+    }
+}
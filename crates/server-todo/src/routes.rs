@@ -0,0 +1,684 @@
+use axum::extract::{Path, Query, State};
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{delete, get, post, put};
+use axum::{Extension, Json, Router};
+use metrics_exporter_prometheus::PrometheusHandle;
+use std::sync::Arc;
+use todo::application::access_control::{AccessControl, DEFAULT_LIST};
+use todo::application::activity_feed::ActivityFeed;
+use todo::application::add_todo_handler::AddTodoHandler;
+use todo::application::auth::{AuthContext, Authenticator};
+use todo::application::change_todo_state_handler::ChangeTodoStateHandler;
+use todo::application::delete_todo_handler::DeleteTodoHandler;
+use todo::application::escalation::EscalateTodosHandler;
+use todo::application::get_todo_history_handler::GetTodoHistoryHandler;
+use todo::application::get_todos_handler::GetTodosHandler;
+use todo::application::messages::MessageCatalog;
+use todo::application::quota::{GetQuotaUsageHandler, Quota, QuotaPolicy};
+use todo::application::restore_todo_handler::RestoreTodoHandler;
+use todo::application::undo_last_change_handler::UndoLastChangeHandler;
+use todo::domain::trash::TrashRepository;
+use todo::infrastructure::event_stores::TenantEventStore;
+use todo::infrastructure::repositories::todo::TenantTodoRepository;
+use todo::infrastructure::repositories::trash::TenantTrashRepository;
+use todo::{
+    EventSink, EventStore, IdGenerator, TenantId, TodoError, TodoEvent, TodoFilter, TodoRepository,
+    UuidV4Generator,
+};
+
+use crate::activity::activity_handler;
+use crate::auth::require_auth;
+use crate::dto::{
+    ChangeStateRequest, CreateTodoRequest, ErrorDto, EscalationDto, ListTodosQuery, QuotaUsageDto,
+    TodoDto, TodoEventDto, TrashedTodoDto,
+};
+use crate::error::{ApiError, InvalidQuery, localize_problems};
+use crate::events::EventBus;
+use crate::health::{healthz, readyz};
+use crate::metrics::metrics_handler;
+use crate::openapi::{openapi_json, swagger_ui};
+use crate::sse::sse_handler;
+use crate::telemetry::trace_context;
+use crate::webhooks::{WebhookManager, webhook_routes};
+use crate::ws::ws_handler;
+
+/// Type of the closures building the escalation handler of a request over its todos
+type EscalationFactory =
+    dyn Fn(Arc<dyn TodoRepository>) -> EscalateTodosHandler<Arc<dyn TodoRepository>> + Send + Sync;
+
+/// What the application handlers of every request are built from
+///
+/// The handlers of a request are built over the todos its caller may see: with an
+/// authenticator, those of the caller's tenant list, or of the `default` list when their
+/// credentials name no tenant, through a `TenantTodoRepository`; without one, every todo of
+/// the repository. The trash and the event sink or store are scoped to the same list,
+/// through a `TenantTrashRepository` and a `TenantEventStore`.
+pub struct AppState {
+    event_sink: Option<Arc<dyn EventSink>>,
+    event_store: Option<Arc<dyn EventStore>>,
+    trash: Option<Arc<dyn TrashRepository>>,
+    ids: Arc<dyn IdGenerator>,
+    quota: QuotaPolicy,
+    escalations: Option<Arc<EscalationFactory>>,
+    pub(crate) repository: Arc<dyn TodoRepository>,
+    pub(crate) events: EventBus,
+    pub(crate) metrics: Option<PrometheusHandle>,
+    pub(crate) authenticator: Option<Arc<dyn Authenticator>>,
+    pub(crate) access_control: Option<Arc<AccessControl>>,
+    pub(crate) webhooks: Option<Arc<WebhookManager>>,
+    pub(crate) messages: Arc<MessageCatalog>,
+    pub(crate) activity: Option<Arc<ActivityFeed>>,
+}
+
+impl AppState {
+    pub fn new(repository: Arc<dyn TodoRepository>) -> Self {
+        AppState {
+            event_sink: None,
+            event_store: None,
+            trash: None,
+            ids: Arc::new(UuidV4Generator),
+            quota: QuotaPolicy::default(),
+            escalations: None,
+            repository,
+            events: EventBus::new(),
+            metrics: None,
+            authenticator: None,
+            access_control: None,
+            webhooks: None,
+            messages: Arc::new(MessageCatalog::default()),
+            activity: None,
+        }
+    }
+
+    /// Writes the events of every change made through the API to `event_sink`
+    pub fn with_event_sink(mut self, event_sink: Arc<dyn EventSink>) -> Self {
+        self.event_sink = Some(event_sink);
+        self
+    }
+
+    /// Writes the events of every change made through the API to `event_store`, serves the
+    /// history of each todo from it on `GET /todos/{id}/history` and undoes the last change
+    /// of a todo on `POST /todos/{id}/undo`
+    pub fn with_event_store(self, event_store: Arc<dyn EventStore>) -> Self {
+        AppState {
+            event_store: Some(event_store.clone()),
+            ..self.with_event_sink(event_store)
+        }
+    }
+
+    /// Moves todos deleted through the API to `trash`, serves it on `GET /trash`, and
+    /// restores or purges them on `POST /trash/{id}/restore` and `DELETE /trash/{id}`
+    pub fn with_trash(mut self, trash: Arc<dyn TrashRepository>) -> Self {
+        self.trash = Some(trash);
+        self
+    }
+
+    /// Gives todos created through the API ids from `ids`
+    pub fn with_id_generator(mut self, ids: Arc<dyn IdGenerator>) -> Self {
+        self.ids = ids;
+        self
+    }
+
+    /// Refuses todos created through the API past the quota `quota` gives the caller's list,
+    /// and reports its usage on `GET /quota`
+    ///
+    /// Without an authenticator, every todo counts against the quota of the `default` list.
+    pub fn with_quota(mut self, quota: QuotaPolicy) -> Self {
+        self.quota = quota;
+        self
+    }
+
+    /// Serves on `GET /escalations` the todos that break the escalation rules of the handler
+    /// `escalations` builds over the todos of a request
+    pub fn with_escalations(
+        mut self,
+        escalations: impl Fn(Arc<dyn TodoRepository>) -> EscalateTodosHandler<Arc<dyn TodoRepository>>
+        + Send
+        + Sync
+        + 'static,
+    ) -> Self {
+        self.escalations = Some(Arc::new(escalations));
+        self
+    }
+
+    /// Keeps the last `retention` events for subscribers resuming from a cursor, instead
+    /// of 1024
+    pub fn with_event_retention(mut self, retention: usize) -> Self {
+        self.events = EventBus::with_retention(retention);
+        self
+    }
+
+    /// Serves `handle`'s metrics on `GET /metrics`
+    pub fn with_metrics(mut self, handle: PrometheusHandle) -> Self {
+        self.metrics = Some(handle);
+        self
+    }
+
+    /// Requires a bearer token `authenticator` accepts on every route but the health, metrics
+    /// and API description ones
+    pub fn with_authenticator(mut self, authenticator: Arc<dyn Authenticator>) -> Self {
+        self.authenticator = Some(authenticator);
+        self
+    }
+
+    /// Requires authenticated callers to have a role on their todo list that permits the
+    /// request, as decided by `access_control`
+    pub fn with_access_control(mut self, access_control: Arc<AccessControl>) -> Self {
+        self.access_control = Some(access_control);
+        self
+    }
+
+    /// Serves the `/webhooks` routes and delivers the events of every change made through
+    /// the API to the webhooks registered with `webhooks`
+    ///
+    /// `router` starts the delivery task, so it must then be called within a Tokio runtime.
+    pub fn with_webhooks(mut self, webhooks: Arc<WebhookManager>) -> Self {
+        self.webhooks = Some(webhooks);
+        self
+    }
+
+    /// Describes errors in the languages of `messages` instead of the built-in English and
+    /// Vietnamese
+    pub fn with_messages(mut self, messages: Arc<MessageCatalog>) -> Self {
+        self.messages = messages;
+        self
+    }
+
+    /// Records the events of every change made through the API in `feed`, with the list and
+    /// the subject of the caller, and serves the activities of the caller's list on
+    /// `GET /activity`
+    pub fn with_activity_feed(mut self, feed: Arc<ActivityFeed>) -> Self {
+        self.activity = Some(feed);
+        self
+    }
+
+    /// Sends the events of a change made by the caller of a request with `context` to live
+    /// subscribers and the activity feed
+    ///
+    /// Events go on the bus with the ids of their todos under the caller's list, so
+    /// subscribers only receive those of their own list.
+    fn publish(&self, context: Option<&AuthContext>, events: &[TodoEvent]) {
+        let list = self.list(context);
+        match &list {
+            Some(list) => {
+                let scoped: Vec<TodoEvent> = events
+                    .iter()
+                    .map(|event| event.clone().map_todo_ids(|id| list.scope(id).into()))
+                    .collect();
+                self.events.publish(&scoped);
+            }
+            None => self.events.publish(events),
+        }
+        let actor = context.map(|context| context.subject.as_str());
+        if let Some(feed) = &self.activity
+            && let Err(err) = feed.record_change(list.as_ref(), actor, events)
+        {
+            tracing::warn!(error = %err, "cannot record activity");
+        }
+    }
+
+    /// The list the caller of a request with `context` works on, or `None` without an
+    /// authenticator, when every todo is reachable
+    pub(crate) fn list(&self, context: Option<&AuthContext>) -> Option<TenantId> {
+        self.authenticator.as_ref()?;
+        Some(match context.and_then(|context| context.tenant.clone()) {
+            Some(tenant) => tenant,
+            None => TenantId::new(DEFAULT_LIST).expect("the default list id is valid"),
+        })
+    }
+
+    /// The todos the caller of a request with `context` may see
+    fn todos(&self, context: Option<&AuthContext>) -> Arc<dyn TodoRepository> {
+        match self.list(context) {
+            Some(list) => Arc::new(TenantTodoRepository::new(self.repository.clone(), list)),
+            None => self.repository.clone(),
+        }
+    }
+
+    /// The quota of the list the caller of a request with `context` works on
+    fn quota(&self, context: Option<&AuthContext>) -> Quota {
+        let list = self
+            .list(context)
+            .unwrap_or_else(|| TenantId::new(DEFAULT_LIST).expect("the default list id is valid"));
+        self.quota.for_list(&list)
+    }
+
+    /// Where the events of the changes made by the caller of a request with `context` go
+    fn event_sink(&self, context: Option<&AuthContext>) -> Option<Arc<dyn EventSink>> {
+        let event_sink = self.event_sink.clone()?;
+        Some(match self.list(context) {
+            Some(list) => Arc::new(TenantEventStore::new(event_sink, list)),
+            None => event_sink,
+        })
+    }
+
+    /// The events of the todos the caller of a request with `context` may see
+    fn event_store(&self, context: Option<&AuthContext>) -> Option<Arc<dyn EventStore>> {
+        let event_store = self.event_store.clone()?;
+        Some(match self.list(context) {
+            Some(list) => Arc::new(TenantEventStore::new(event_store, list)),
+            None => event_store,
+        })
+    }
+
+    /// The deleted todos the caller of a request with `context` may see
+    fn trash(&self, context: Option<&AuthContext>) -> Option<Arc<dyn TrashRepository>> {
+        let trash = self.trash.clone()?;
+        Some(match self.list(context) {
+            Some(list) => Arc::new(TenantTrashRepository::new(trash, list)),
+            None => trash,
+        })
+    }
+
+    fn add_todo_handler(
+        &self,
+        context: Option<&AuthContext>,
+    ) -> AddTodoHandler<Arc<dyn TodoRepository>> {
+        let handler = AddTodoHandler::new(self.todos(context))
+            .with_id_generator(self.ids.clone())
+            .with_quota(self.quota(context));
+        match self.event_sink(context) {
+            Some(event_sink) => handler.with_event_sink(event_sink),
+            None => handler,
+        }
+    }
+
+    fn change_todo_state_handler(
+        &self,
+        context: Option<&AuthContext>,
+    ) -> ChangeTodoStateHandler<Arc<dyn TodoRepository>> {
+        let handler = ChangeTodoStateHandler::new(self.todos(context));
+        match self.event_sink(context) {
+            Some(event_sink) => handler.with_event_sink(event_sink),
+            None => handler,
+        }
+    }
+
+    fn delete_todo_handler(
+        &self,
+        context: Option<&AuthContext>,
+    ) -> DeleteTodoHandler<Arc<dyn TodoRepository>> {
+        let mut handler = DeleteTodoHandler::new(self.todos(context));
+        if let Some(trash) = self.trash(context) {
+            handler = handler.with_trash(trash);
+        }
+        match self.event_sink(context) {
+            Some(event_sink) => handler.with_event_sink(event_sink),
+            None => handler,
+        }
+    }
+
+    fn restore_todo_handler(
+        &self,
+        context: Option<&AuthContext>,
+    ) -> Option<RestoreTodoHandler<Arc<dyn TodoRepository>>> {
+        let handler = RestoreTodoHandler::new(self.todos(context), self.trash(context)?);
+        Some(match self.event_sink(context) {
+            Some(event_sink) => handler.with_event_sink(event_sink),
+            None => handler,
+        })
+    }
+
+    fn undo_last_change_handler(
+        &self,
+        context: Option<&AuthContext>,
+    ) -> Option<UndoLastChangeHandler<Arc<dyn TodoRepository>>> {
+        Some(UndoLastChangeHandler::new(
+            self.todos(context),
+            self.event_store(context)?,
+        ))
+    }
+
+    fn get_todo_history_handler(
+        &self,
+        context: Option<&AuthContext>,
+    ) -> Option<GetTodoHistoryHandler> {
+        Some(GetTodoHistoryHandler::new(self.event_store(context)?))
+    }
+}
+
+/// Routes of the REST API
+pub fn router(state: Arc<AppState>) -> Router {
+    let mut protected = Router::new()
+        .route("/todos", get(list_todos).post(create_todo))
+        .route("/todos/{id}", get(get_todo).delete(delete_todo))
+        .route("/todos/{id}/state", put(change_state))
+        .route("/quota", get(get_quota_usage))
+        .route("/ws", get(ws_handler))
+        .route("/events", get(sse_handler));
+    if state.event_store.is_some() {
+        protected = protected
+            .route("/todos/{id}/history", get(get_todo_history))
+            .route("/todos/{id}/undo", post(undo_last_change));
+    }
+    if state.trash.is_some() {
+        protected = protected
+            .route("/trash", get(list_trash))
+            .route("/trash/{id}", delete(purge_todo))
+            .route("/trash/{id}/restore", post(restore_todo));
+    }
+    if state.escalations.is_some() {
+        protected = protected.route("/escalations", get(list_escalations));
+    }
+    if state.activity.is_some() {
+        protected = protected.route("/activity", get(activity_handler));
+    }
+    if let Some(webhooks) = &state.webhooks {
+        webhooks.start(&state.events);
+        protected = protected.merge(webhook_routes());
+    }
+    let protected = protected.route_layer(axum::middleware::from_fn_with_state(
+        state.clone(),
+        require_auth,
+    ));
+    Router::new()
+        .merge(protected)
+        .route("/metrics", get(metrics_handler))
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz))
+        .route("/openapi.json", get(openapi_json))
+        .route("/docs", get(swagger_ui))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            localize_problems,
+        ))
+        .layer(axum::middleware::from_fn(trace_context))
+        .with_state(state)
+}
+
+#[utoipa::path(
+    get,
+    path = "/todos",
+    tag = "todos",
+    params(ListTodosQuery),
+    responses(
+        (status = 200, description = "Todos, oldest first", body = Vec<TodoDto>),
+        (status = 400, description = "Invalid search query", body = ErrorDto, content_type = "application/problem+json"),
+        (status = 500, description = "Repository failure", body = ErrorDto, content_type = "application/problem+json"),
+    ),
+)]
+pub(crate) async fn list_todos(
+    State(state): State<Arc<AppState>>,
+    context: Option<Extension<AuthContext>>,
+    Query(query): Query<ListTodosQuery>,
+) -> Result<Json<Vec<TodoDto>>, Response> {
+    let filter = TodoFilter::parse(query.q.as_deref().unwrap_or_default())
+        .map_err(|err| InvalidQuery(err).into_response())?;
+    let mut todos = GetTodosHandler::new(state.todos(context.as_deref()))
+        .search_todos(&filter)
+        .await
+        .map_err(|err| ApiError(err).into_response())?;
+    todos.sort_by_key(|todo| todo.created_at);
+    let todos = todos
+        .iter()
+        .filter(|todo| query.state.is_none_or(|state| todo.state == state.into()))
+        .map(TodoDto::from)
+        .collect();
+    Ok(Json(todos))
+}
+
+#[utoipa::path(
+    post,
+    path = "/todos",
+    tag = "todos",
+    request_body = CreateTodoRequest,
+    responses(
+        (status = 201, description = "Created todo", body = TodoDto,
+            headers(("Location" = String, description = "URL of the created todo"))),
+        (status = 409, description = "Quota exceeded", body = ErrorDto, content_type = "application/problem+json"),
+        (status = 422, description = "Empty description", body = ErrorDto, content_type = "application/problem+json"),
+        (status = 500, description = "Repository failure", body = ErrorDto, content_type = "application/problem+json"),
+    ),
+)]
+pub(crate) async fn create_todo(
+    State(state): State<Arc<AppState>>,
+    context: Option<Extension<AuthContext>>,
+    Json(request): Json<CreateTodoRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let (todo, events) = state
+        .add_todo_handler(context.as_deref())
+        .new_todo(request.description)
+        .await?;
+    state.publish(context.as_deref(), &events);
+    let location = format!("/todos/{}", todo.id);
+    Ok((
+        StatusCode::CREATED,
+        [(header::LOCATION, location)],
+        Json(TodoDto::from(&todo)),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/todos/{id}",
+    tag = "todos",
+    params(("id" = String, Path, description = "Todo id")),
+    responses(
+        (status = 200, description = "The todo", body = TodoDto),
+        (status = 404, description = "Todo not found", body = ErrorDto, content_type = "application/problem+json"),
+        (status = 500, description = "Repository failure", body = ErrorDto, content_type = "application/problem+json"),
+    ),
+)]
+pub(crate) async fn get_todo(
+    State(state): State<Arc<AppState>>,
+    context: Option<Extension<AuthContext>>,
+    Path(id): Path<String>,
+) -> Result<Json<TodoDto>, ApiError> {
+    let todo = state
+        .todos(context.as_deref())
+        .find_by_id(&id)
+        .await?
+        .ok_or(TodoError::TodoNotFound)?;
+    Ok(Json(TodoDto::from(&todo)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/todos/{id}/history",
+    tag = "todos",
+    params(("id" = String, Path, description = "Todo id")),
+    responses(
+        (status = 200, description = "Events of the todo, oldest first", body = Vec<TodoEventDto>),
+        (status = 404, description = "No event recorded for the todo", body = ErrorDto, content_type = "application/problem+json"),
+        (status = 500, description = "Event store failure", body = ErrorDto, content_type = "application/problem+json"),
+    ),
+)]
+pub(crate) async fn get_todo_history(
+    State(state): State<Arc<AppState>>,
+    context: Option<Extension<AuthContext>>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<TodoEventDto>>, ApiError> {
+    let Some(handler) = state.get_todo_history_handler(context.as_deref()) else {
+        return Err(TodoError::TodoNotFound.into());
+    };
+    let events = handler.get_history(&id).await?;
+    Ok(Json(events.into_iter().map(TodoEventDto::from).collect()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/todos/{id}/undo",
+    tag = "todos",
+    params(("id" = String, Path, description = "Todo id")),
+    responses(
+        (status = 200, description = "Todo with its last change reverted", body = TodoDto),
+        (status = 404, description = "Todo not found", body = ErrorDto, content_type = "application/problem+json"),
+        (status = 409, description = "No change to undo", body = ErrorDto, content_type = "application/problem+json"),
+        (status = 500, description = "Repository or event store failure", body = ErrorDto, content_type = "application/problem+json"),
+    ),
+)]
+pub(crate) async fn undo_last_change(
+    State(state): State<Arc<AppState>>,
+    context: Option<Extension<AuthContext>>,
+    Path(id): Path<String>,
+) -> Result<Json<TodoDto>, ApiError> {
+    let Some(handler) = state.undo_last_change_handler(context.as_deref()) else {
+        return Err(TodoError::TodoNotFound.into());
+    };
+    let (todo, events) = handler.undo_last_change(id).await?;
+    state.publish(context.as_deref(), &events);
+    Ok(Json(TodoDto::from(&todo)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/todos/{id}/state",
+    tag = "todos",
+    params(("id" = String, Path, description = "Todo id")),
+    request_body = ChangeStateRequest,
+    responses(
+        (status = 200, description = "Updated todo", body = TodoDto),
+        (status = 404, description = "Todo not found", body = ErrorDto, content_type = "application/problem+json"),
+        (status = 409, description = "Invalid state transition", body = ErrorDto, content_type = "application/problem+json"),
+        (status = 500, description = "Repository failure", body = ErrorDto, content_type = "application/problem+json"),
+    ),
+)]
+pub(crate) async fn change_state(
+    State(state): State<Arc<AppState>>,
+    context: Option<Extension<AuthContext>>,
+    Path(id): Path<String>,
+    Json(request): Json<ChangeStateRequest>,
+) -> Result<Json<TodoDto>, ApiError> {
+    let (todo, events) = state
+        .change_todo_state_handler(context.as_deref())
+        .change_state(id, request.state.into())
+        .await?;
+    state.publish(context.as_deref(), &events);
+    Ok(Json(TodoDto::from(&todo)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/todos/{id}",
+    tag = "todos",
+    params(("id" = String, Path, description = "Todo id")),
+    responses(
+        (status = 204, description = "Todo deleted"),
+        (status = 404, description = "Todo not found", body = ErrorDto, content_type = "application/problem+json"),
+        (status = 500, description = "Repository failure", body = ErrorDto, content_type = "application/problem+json"),
+    ),
+)]
+pub(crate) async fn delete_todo(
+    State(state): State<Arc<AppState>>,
+    context: Option<Extension<AuthContext>>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let events = state
+        .delete_todo_handler(context.as_deref())
+        .delete_todo(id)
+        .await?;
+    state.publish(context.as_deref(), &events);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/quota",
+    tag = "todos",
+    responses(
+        (status = 200, description = "Todos counted against the quota, and the remaining capacity", body = QuotaUsageDto),
+        (status = 500, description = "Repository failure", body = ErrorDto, content_type = "application/problem+json"),
+    ),
+)]
+pub(crate) async fn get_quota_usage(
+    State(state): State<Arc<AppState>>,
+    context: Option<Extension<AuthContext>>,
+) -> Result<Json<QuotaUsageDto>, ApiError> {
+    let usage = GetQuotaUsageHandler::new(
+        state.todos(context.as_deref()),
+        state.quota(context.as_deref()),
+    )
+    .usage()
+    .await?;
+    Ok(Json(QuotaUsageDto::from(&usage)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/escalations",
+    tag = "todos",
+    responses(
+        (status = 200, description = "Todos left in a state for longer than an escalation rule allows, once per rule they break", body = Vec<EscalationDto>),
+        (status = 500, description = "Repository failure", body = ErrorDto, content_type = "application/problem+json"),
+    ),
+)]
+pub(crate) async fn list_escalations(
+    State(state): State<Arc<AppState>>,
+    context: Option<Extension<AuthContext>>,
+) -> Result<Json<Vec<EscalationDto>>, ApiError> {
+    let Some(escalations) = &state.escalations else {
+        return Err(TodoError::TodoNotFound.into());
+    };
+    let escalations = escalations(state.todos(context.as_deref()))
+        .escalated()
+        .await?;
+    Ok(Json(escalations.iter().map(EscalationDto::from).collect()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/trash",
+    tag = "todos",
+    responses(
+        (status = 200, description = "Deleted todos, most recently deleted first", body = Vec<TrashedTodoDto>),
+        (status = 500, description = "Trash failure", body = ErrorDto, content_type = "application/problem+json"),
+    ),
+)]
+pub(crate) async fn list_trash(
+    State(state): State<Arc<AppState>>,
+    context: Option<Extension<AuthContext>>,
+) -> Result<Json<Vec<TrashedTodoDto>>, ApiError> {
+    let Some(handler) = state.restore_todo_handler(context.as_deref()) else {
+        return Err(TodoError::TodoNotFound.into());
+    };
+    let trashed = handler.trashed().await?;
+    Ok(Json(trashed.iter().map(TrashedTodoDto::from).collect()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/trash/{id}/restore",
+    tag = "todos",
+    params(("id" = String, Path, description = "Todo id")),
+    responses(
+        (status = 200, description = "Restored todo", body = TodoDto),
+        (status = 404, description = "Todo not in the trash", body = ErrorDto, content_type = "application/problem+json"),
+        (status = 500, description = "Repository failure, or a todo with the same id exists", body = ErrorDto, content_type = "application/problem+json"),
+    ),
+)]
+pub(crate) async fn restore_todo(
+    State(state): State<Arc<AppState>>,
+    context: Option<Extension<AuthContext>>,
+    Path(id): Path<String>,
+) -> Result<Json<TodoDto>, ApiError> {
+    let Some(handler) = state.restore_todo_handler(context.as_deref()) else {
+        return Err(TodoError::TodoNotFound.into());
+    };
+    let (todo, events) = handler.restore(id).await?;
+    state.publish(context.as_deref(), &events);
+    Ok(Json(TodoDto::from(&todo)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/trash/{id}",
+    tag = "todos",
+    params(("id" = String, Path, description = "Todo id")),
+    responses(
+        (status = 204, description = "Todo deleted for good"),
+        (status = 404, description = "Todo not in the trash", body = ErrorDto, content_type = "application/problem+json"),
+        (status = 500, description = "Trash failure", body = ErrorDto, content_type = "application/problem+json"),
+    ),
+)]
+pub(crate) async fn purge_todo(
+    State(state): State<Arc<AppState>>,
+    context: Option<Extension<AuthContext>>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let Some(handler) = state.restore_todo_handler(context.as_deref()) else {
+        return Err(TodoError::TodoNotFound.into());
+    };
+    handler.purge(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,364 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, Duration as TimeDelta, NaiveDate, TimeZone, Timelike, Utc};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::hash::{BuildHasher, RandomState};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// Runs kept by `Scheduler::history`; older ones are dropped
+pub const HISTORY_CAPACITY: usize = 256;
+
+/// How many years ahead `CronSchedule::next_after` looks before giving up on a schedule
+/// that never matches, such as `0 0 30 2 *`
+const SEARCH_YEARS: i32 = 5;
+
+/// When a job runs, as a cron expression in UTC
+///
+/// Takes five fields, `minute hour day-of-month month day-of-week`, or six with a leading
+/// `second`. A field is `*`, a value, a range `a-b`, a step `*/n`, `a-b/n` or `a/n`, or a
+/// comma-separated list of those. Months may be named `JAN`-`DEC` and days `SUN`-`SAT`;
+/// Sunday is `0` or `7`. As in cron, when both day fields are restricted a day matching
+/// either one runs. `@hourly`, `@daily`, `@weekly`, `@monthly` and `@yearly` are accepted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    expression: String,
+    seconds: u64,
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    /// Whether the day-of-month field is not `*`
+    days_of_month_restricted: bool,
+    /// Whether the day-of-week field is not `*`
+    days_of_week_restricted: bool,
+}
+
+impl CronSchedule {
+    /// Parses a cron expression
+    ///
+    /// # Returns
+    /// - `Err(String)`: Naming the field that is not valid
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let expanded = match expression.trim() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            other => other,
+        };
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let (second, rest) = match fields.len() {
+            5 => ("0", &fields[..]),
+            6 => (fields[0], &fields[1..]),
+            count => {
+                return Err(format!(
+                    "invalid cron expression {:?}: expected 5 or 6 fields, found {}",
+                    expression, count
+                ));
+            }
+        };
+        let invalid = |err: String| format!("invalid cron expression {:?}: {}", expression, err);
+        let days_of_week = parse_field(rest[4], "day of week", 0, 7, DAY_NAMES).map_err(invalid)?;
+        Ok(CronSchedule {
+            expression: expression.trim().to_string(),
+            seconds: parse_field(second, "second", 0, 59, &[]).map_err(invalid)?,
+            minutes: parse_field(rest[0], "minute", 0, 59, &[]).map_err(invalid)?,
+            hours: parse_field(rest[1], "hour", 0, 23, &[]).map_err(invalid)?,
+            days_of_month: parse_field(rest[2], "day of month", 1, 31, &[]).map_err(invalid)?,
+            months: parse_field(rest[3], "month", 1, 12, MONTH_NAMES).map_err(invalid)?,
+            // 7 is another name for Sunday
+            days_of_week: (days_of_week | days_of_week >> 7) & 0x7f,
+            days_of_month_restricted: rest[2] != "*",
+            days_of_week_restricted: rest[4] != "*",
+        })
+    }
+
+    /// The expression the schedule was parsed from
+    pub fn as_str(&self) -> &str {
+        &self.expression
+    }
+
+    /// The first time strictly after `after` the schedule matches, to the second, or
+    /// `None` if it matches no time in the next five years
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut time = after.with_nanosecond(0)? + TimeDelta::seconds(1);
+        let limit = after.year() + SEARCH_YEARS;
+        while time.year() <= limit {
+            if !has(self.months, time.month()) {
+                let (year, month) = match time.month() {
+                    12 => (time.year() + 1, 1),
+                    month => (time.year(), month + 1),
+                };
+                time = Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0).single()?;
+            } else if !self.matches_day(time.date_naive()) {
+                time = Utc.from_utc_datetime(&time.date_naive().succ_opt()?.and_hms_opt(0, 0, 0)?);
+            } else if !has(self.hours, time.hour()) {
+                time = time.with_minute(0)?.with_second(0)? + TimeDelta::hours(1);
+            } else if !has(self.minutes, time.minute()) {
+                time = time.with_second(0)? + TimeDelta::minutes(1);
+            } else if !has(self.seconds, time.second()) {
+                time += TimeDelta::seconds(1);
+            } else {
+                return Some(time);
+            }
+        }
+        None
+    }
+
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let day_of_month = has(self.days_of_month, date.day());
+        let day_of_week = has(self.days_of_week, date.weekday().num_days_from_sunday());
+        match (self.days_of_month_restricted, self.days_of_week_restricted) {
+            (true, true) => day_of_month || day_of_week,
+            _ => day_of_month && day_of_week,
+        }
+    }
+}
+
+impl FromStr for CronSchedule {
+    type Err = String;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        Self::parse(expression)
+    }
+}
+
+const MONTH_NAMES: &[&str] = &[
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+
+const DAY_NAMES: &[&str] = &["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+fn has(set: u64, value: u32) -> bool {
+    set & (1 << value) != 0
+}
+
+/// Parses one field into a bit set of the values it matches; `names` are the values from
+/// `min` on
+fn parse_field(field: &str, what: &str, min: u32, max: u32, names: &[&str]) -> Result<u64, String> {
+    let value = |text: &str| -> Result<u32, String> {
+        let position = names
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(text));
+        let value = match position {
+            Some(position) => position as u32 + min,
+            None => text
+                .parse()
+                .map_err(|_| format!("invalid {} {:?}", what, text))?,
+        };
+        if value < min || value > max {
+            return Err(format!("{} {} is not within {}-{}", what, value, min, max));
+        }
+        Ok(value)
+    };
+    let mut set = 0;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .ok_or_else(|| format!("invalid {} step {:?}", what, step))?;
+                (range, Some(step))
+            }
+            None => (part, None),
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            range => match range.split_once('-') {
+                Some((start, end)) => (value(start)?, value(end)?),
+                // `a/n` runs from `a` to the end of the field
+                None if step.is_some() => (value(range)?, max),
+                None => {
+                    let value = value(range)?;
+                    (value, value)
+                }
+            },
+        };
+        if start > end {
+            return Err(format!("invalid {} range {:?}", what, range));
+        }
+        for value in (start..=end).step_by(step.unwrap_or(1) as usize) {
+            set |= 1 << value;
+        }
+    }
+    Ok(set)
+}
+
+/// A maintenance task the Scheduler runs
+#[async_trait]
+pub trait ScheduledJob: Send + Sync {
+    /// Runs the task once
+    ///
+    /// # Returns
+    /// - `Err(String)`: Why the run failed, recorded in the run history
+    async fn run(&self) -> Result<(), String>;
+}
+
+/// How a scheduled run ended
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", content = "error", rename_all = "snake_case")]
+pub enum RunOutcome {
+    Succeeded,
+    Failed(String),
+    /// Not started, because the previous run of the job was still going
+    Skipped,
+}
+
+/// A run of a job, as recorded in the Scheduler's history
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JobRun {
+    pub job: String,
+    /// The time the schedule fired, before jitter
+    pub scheduled_at: DateTime<Utc>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub outcome: RunOutcome,
+}
+
+struct Job {
+    name: String,
+    schedule: CronSchedule,
+    task: Arc<dyn ScheduledJob>,
+    /// Set while a run is going, so the next one is skipped rather than overlapping it
+    running: AtomicBool,
+}
+
+/// Runs maintenance jobs on cron schedules
+///
+/// Each job waits for its next scheduled time, plus a random delay of up to the jitter so
+/// jobs due at the same time, or the same job on several servers, do not all start at
+/// once. A run due while the previous one is still going is skipped. Every run, skipped
+/// ones included, is recorded in a bounded history.
+pub struct Scheduler {
+    jobs: Vec<Arc<Job>>,
+    jitter: Duration,
+    history: Mutex<VecDeque<JobRun>>,
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scheduler {
+    /// Creates a new Scheduler without jobs or jitter
+    pub fn new() -> Self {
+        Scheduler {
+            jobs: Vec::new(),
+            jitter: Duration::ZERO,
+            history: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Runs `task` as `name` whenever `schedule` fires
+    pub fn with_job(
+        mut self,
+        name: impl Into<String>,
+        schedule: CronSchedule,
+        task: Arc<dyn ScheduledJob>,
+    ) -> Self {
+        self.jobs.push(Arc::new(Job {
+            name: name.into(),
+            schedule,
+            task,
+            running: AtomicBool::new(false),
+        }));
+        self
+    }
+
+    /// Delays every run by a random duration of up to `jitter`
+    pub fn with_jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// The recorded runs, oldest first
+    pub fn history(&self) -> Vec<JobRun> {
+        self.lock_history().iter().cloned().collect()
+    }
+
+    /// Spawns a task per job that runs it on its schedule, until the handles are aborted
+    pub fn start(self: &Arc<Self>) -> Vec<JoinHandle<()>> {
+        self.jobs
+            .iter()
+            .map(|job| {
+                let scheduler = self.clone();
+                let job = job.clone();
+                tokio::spawn(async move { scheduler.run_job(job).await })
+            })
+            .collect()
+    }
+
+    async fn run_job(self: Arc<Self>, job: Arc<Job>) {
+        let mut after = Utc::now();
+        while let Some(scheduled_at) = job.schedule.next_after(after) {
+            let wait = (scheduled_at - Utc::now()).to_std().unwrap_or_default();
+            tokio::time::sleep(wait + self.random_jitter()).await;
+            after = scheduled_at;
+            if job.running.swap(true, Ordering::AcqRel) {
+                let now = Utc::now();
+                tracing::warn!(job = %job.name, "scheduled job still running, skipping a run");
+                self.record(JobRun {
+                    job: job.name.clone(),
+                    scheduled_at,
+                    started_at: now,
+                    finished_at: now,
+                    outcome: RunOutcome::Skipped,
+                });
+                continue;
+            }
+            let scheduler = self.clone();
+            let job = job.clone();
+            tokio::spawn(async move {
+                let started_at = Utc::now();
+                let outcome = match job.task.run().await {
+                    Ok(()) => RunOutcome::Succeeded,
+                    Err(error) => {
+                        tracing::warn!(job = %job.name, %error, "scheduled job failed");
+                        RunOutcome::Failed(error)
+                    }
+                };
+                job.running.store(false, Ordering::Release);
+                scheduler.record(JobRun {
+                    job: job.name.clone(),
+                    scheduled_at,
+                    started_at,
+                    finished_at: Utc::now(),
+                    outcome,
+                });
+            });
+        }
+        tracing::warn!(job = %job.name, schedule = job.schedule.as_str(), "schedule never fires again");
+    }
+
+    fn random_jitter(&self) -> Duration {
+        if self.jitter.is_zero() {
+            return Duration::ZERO;
+        }
+        let random = RandomState::new().hash_one(Utc::now());
+        Duration::from_nanos(random % self.jitter.as_nanos().min(u64::MAX as u128) as u64)
+    }
+
+    fn record(&self, run: JobRun) {
+        let mut history = self.lock_history();
+        if history.len() == HISTORY_CAPACITY {
+            history.pop_front();
+        }
+        history.push_back(run);
+    }
+
+    fn lock_history(&self) -> std::sync::MutexGuard<'_, VecDeque<JobRun>> {
+        self.history
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
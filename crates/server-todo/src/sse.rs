@@ -0,0 +1,119 @@
+use axum::Extension;
+use axum::extract::{Query, State};
+use axum::http::HeaderMap;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use futures_util::stream::{self, Stream, StreamExt};
+use serde::Deserialize;
+use std::convert::Infallible;
+use std::sync::Arc;
+use todo::TenantId;
+use todo::application::auth::AuthContext;
+use tokio::sync::broadcast::error::RecvError;
+use utoipa::IntoParams;
+
+use crate::dto::TodoEventDto;
+use crate::events::{SequencedEvent, for_list};
+use crate::routes::AppState;
+
+/// Query of `GET /events`
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct EventsQuery {
+    /// Sequence number of the last event the client has seen
+    pub cursor: Option<u64>,
+}
+
+/// `GET /events`: replays the events of the caller's list after the cursor, then follows live
+///
+/// The cursor comes from the `cursor` query parameter or the `Last-Event-ID` header sent
+/// by reconnecting browsers. Without either, only new events are streamed. The events of
+/// other lists are skipped, so the sequence numbers a client sees can have gaps.
+#[utoipa::path(
+    get,
+    path = "/events",
+    tag = "events",
+    params(
+        EventsQuery,
+        ("Last-Event-ID" = Option<u64>, Header, description = "Used as the cursor when `cursor` is absent"),
+    ),
+    responses((
+        status = 200,
+        description = "`todo` events of the caller's list carrying a TodoEventDto with the sequence number as id, and `reset` events when events were missed",
+        content_type = "text/event-stream",
+        body = TodoEventDto,
+    )),
+)]
+pub async fn sse_handler(
+    State(state): State<Arc<AppState>>,
+    context: Option<Extension<AuthContext>>,
+    Query(query): Query<EventsQuery>,
+    headers: HeaderMap,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let list = state.list(context.as_deref());
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok());
+
+    let (backlog, missed, receiver) = match query.cursor.or(last_event_id) {
+        Some(cursor) => {
+            let replay = state.events.subscribe_from(cursor);
+            (replay.backlog, replay.missed, replay.receiver)
+        }
+        None => (Vec::new(), false, state.events.subscribe()),
+    };
+
+    let reset = missed.then(reset_event);
+    let backlog: Vec<Event> = backlog
+        .iter()
+        .filter_map(|event| todo_event(list.as_ref(), event))
+        .collect();
+    let live = stream::unfold(
+        (receiver, list, false),
+        |(mut receiver, list, lagged)| async move {
+            if lagged {
+                return None;
+            }
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => {
+                        if let Some(event) = todo_event(list.as_ref(), &event) {
+                            return Some((event, (receiver, list, false)));
+                        }
+                    }
+                    // The client reconnects with its cursor and gets a reset if it fell out of
+                    // the log
+                    Err(RecvError::Lagged(_)) => {
+                        return Some((reset_event(), (receiver, list, true)));
+                    }
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        },
+    );
+
+    let events = stream::iter(reset.into_iter().chain(backlog))
+        .chain(live)
+        .map(Ok);
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+/// A `todo` event whose id is the sequence number, so browsers resume after it, or `None`
+/// if `event` is about a todo outside `list`
+fn todo_event(list: Option<&TenantId>, event: &SequencedEvent) -> Option<Event> {
+    let todo_event = for_list(list, &event.event)?;
+    let data = serde_json::to_string(&TodoEventDto::from(todo_event)).unwrap_or_default();
+    Some(
+        Event::default()
+            .event("todo")
+            .id(event.sequence.to_string())
+            .data(data),
+    )
+}
+
+/// Tells the client that events were lost and it should reload the todos
+fn reset_event() -> Event {
+    Event::default()
+        .event("reset")
+        .data("events were missed, reload the todos")
+}
@@ -0,0 +1,123 @@
+use axum::extract::Request;
+use axum::middleware::Next;
+use axum::response::Response;
+use metrics_exporter_prometheus::PrometheusHandle;
+use opentelemetry::global;
+use opentelemetry::metrics::MeterProvider;
+use opentelemetry::trace::TracerProvider;
+use opentelemetry_http::HeaderExtractor;
+use opentelemetry_otlp::{MetricExporter, SpanExporter, WithExportConfig};
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::metrics::SdkMeterProvider;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use tracing::Instrument;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+use crate::metrics;
+
+/// Service name reported when `OTEL_SERVICE_NAME` is not set
+const SERVICE_NAME: &str = "server-todo";
+
+/// Metric and span exporters installed at startup
+pub struct Telemetry {
+    /// Handle serving the Prometheus exposition, for `AppState::with_metrics`
+    pub prometheus: PrometheusHandle,
+    tracer_provider: Option<SdkTracerProvider>,
+    meter_provider: Option<SdkMeterProvider>,
+}
+
+impl Telemetry {
+    /// Installs the metrics recorder, and OTLP export when `otlp_endpoint` is set
+    ///
+    /// With an endpoint, spans from the handlers' `tracing` instrumentation and the handler
+    /// metrics are sent over OTLP/HTTP to `<endpoint>/v1/traces` and `<endpoint>/v1/metrics`,
+    /// and W3C trace context is read from incoming requests by `trace_context`. The resource
+    /// carries `OTEL_SERVICE_NAME` (default `server-todo`) and `OTEL_RESOURCE_ATTRIBUTES`.
+    /// Metrics stay available on `GET /metrics` either way.
+    ///
+    /// Must be called once, within a Tokio runtime.
+    pub fn install(otlp_endpoint: Option<&str>) -> Result<Telemetry, String> {
+        let Some(endpoint) = otlp_endpoint else {
+            return Ok(Telemetry {
+                prometheus: metrics::install()?,
+                tracer_provider: None,
+                meter_provider: None,
+            });
+        };
+        let endpoint = endpoint.trim_end_matches('/');
+        let otlp_error =
+            |err: &dyn std::fmt::Display| format!("cannot set up OTLP export: {}", err);
+
+        let mut resource = Resource::builder();
+        if std::env::var_os("OTEL_SERVICE_NAME").is_none() {
+            resource = resource.with_service_name(SERVICE_NAME);
+        }
+        let resource = resource.build();
+
+        let span_exporter = SpanExporter::builder()
+            .with_http()
+            .with_endpoint(format!("{}/v1/traces", endpoint))
+            .build()
+            .map_err(|err| otlp_error(&err))?;
+        let tracer_provider = SdkTracerProvider::builder()
+            .with_batch_exporter(span_exporter)
+            .with_resource(resource.clone())
+            .build();
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(tracer_provider.tracer(SERVICE_NAME)))
+            .try_init()
+            .map_err(|err| otlp_error(&err))?;
+
+        let metric_exporter = MetricExporter::builder()
+            .with_http()
+            .with_endpoint(format!("{}/v1/metrics", endpoint))
+            .build()
+            .map_err(|err| otlp_error(&err))?;
+        let meter_provider = SdkMeterProvider::builder()
+            .with_periodic_exporter(metric_exporter)
+            .with_resource(resource)
+            .build();
+        let recorder = metrics_exporter_opentelemetry::Recorder::with_meter(
+            meter_provider.meter(SERVICE_NAME),
+        );
+
+        Ok(Telemetry {
+            prometheus: metrics::install_with(Some(recorder))?,
+            tracer_provider: Some(tracer_provider),
+            meter_provider: Some(meter_provider),
+        })
+    }
+
+    /// Flushes and stops the OTLP exporters
+    pub fn shutdown(self) {
+        if let Some(tracer_provider) = self.tracer_provider {
+            let _ = tracer_provider.shutdown();
+        }
+        if let Some(meter_provider) = self.meter_provider {
+            let _ = meter_provider.shutdown();
+        }
+    }
+}
+
+/// Middleware running each request in an `http_request` span whose parent is the trace
+/// context in the request's `traceparent` header, if any
+///
+/// The handlers' spans, and everything they call, nest under it.
+pub async fn trace_context(request: Request, next: Next) -> Response {
+    let parent = global::get_text_map_propagator(|propagator| {
+        propagator.extract(&HeaderExtractor(request.headers()))
+    });
+    let span = tracing::info_span!(
+        "http_request",
+        otel.kind = "server",
+        http.request.method = %request.method(),
+        url.path = %request.uri().path(),
+    );
+    // Fails only when no OpenTelemetry layer is installed, and then there is nothing to link
+    let _ = span.set_parent(parent);
+    next.run(request).instrument(span).await
+}
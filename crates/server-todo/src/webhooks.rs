@@ -0,0 +1,520 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{delete, get, post};
+use axum::{Extension, Json, Router};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use todo::application::auth::AuthContext;
+use todo::infrastructure::serialization::cloudevents::CloudEvent;
+use todo::{TenantId, TodoEvent};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::task::JoinHandle;
+use utoipa::ToSchema;
+
+use crate::dto::{DeadLetterDto, ErrorDto, RegisterWebhookRequest, RetriedDto, WebhookDto};
+use crate::events::{EventBus, for_list};
+use crate::routes::AppState;
+
+/// Header carrying `sha256=<hex HMAC-SHA256 of the body>`, keyed with the webhook's secret
+pub const SIGNATURE_HEADER: &str = "x-hk-todo-signature";
+/// Deliveries attempted per event before it goes to the dead-letter queue, by default
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 5;
+/// Wait after the first failed attempt, doubled after each further one, by default
+pub const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+/// Dead letters kept; the oldest is dropped to make room for a new one
+pub const DEAD_LETTER_CAPACITY: usize = 1024;
+
+/// `source` of the CloudEvents delivered
+const SOURCE: &str = "/hk-todo/server";
+/// How long a target has to answer one delivery
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Todo events a webhook can subscribe to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEvent {
+    TodoCreated,
+    TodoStateChanged,
+    TodoArchived,
+    TodoStale,
+    TodoDueDateChanged,
+    TodoPriorityChanged,
+    TodoTagAdded,
+    TodoTagRemoved,
+    TodoSubtaskAdded,
+    TodoSubtaskToggled,
+    TodoSubtaskRemoved,
+    TodoDescriptionUpdated,
+    TodoBlocked,
+    TodoUnblocked,
+    TodoDeleted,
+    TodoRestored,
+    TodoAttachmentAdded,
+    TodoAttachmentRemoved,
+}
+
+impl WebhookEvent {
+    fn of(event: &TodoEvent) -> Self {
+        match event {
+            TodoEvent::TodoCreated { .. } => WebhookEvent::TodoCreated,
+            TodoEvent::TodoStateChanged { .. } => WebhookEvent::TodoStateChanged,
+            TodoEvent::TodoArchived { .. } => WebhookEvent::TodoArchived,
+            TodoEvent::TodoStale { .. } => WebhookEvent::TodoStale,
+            TodoEvent::TodoDueDateChanged { .. } => WebhookEvent::TodoDueDateChanged,
+            TodoEvent::TodoPriorityChanged { .. } => WebhookEvent::TodoPriorityChanged,
+            TodoEvent::TodoTagAdded { .. } => WebhookEvent::TodoTagAdded,
+            TodoEvent::TodoTagRemoved { .. } => WebhookEvent::TodoTagRemoved,
+            TodoEvent::TodoSubtaskAdded { .. } => WebhookEvent::TodoSubtaskAdded,
+            TodoEvent::TodoSubtaskToggled { .. } => WebhookEvent::TodoSubtaskToggled,
+            TodoEvent::TodoSubtaskRemoved { .. } => WebhookEvent::TodoSubtaskRemoved,
+            TodoEvent::TodoDescriptionUpdated { .. } => WebhookEvent::TodoDescriptionUpdated,
+            TodoEvent::TodoBlocked { .. } => WebhookEvent::TodoBlocked,
+            TodoEvent::TodoUnblocked { .. } => WebhookEvent::TodoUnblocked,
+            TodoEvent::TodoDeleted { .. } => WebhookEvent::TodoDeleted,
+            TodoEvent::TodoRestored { .. } => WebhookEvent::TodoRestored,
+            TodoEvent::TodoAttachmentAdded { .. } => WebhookEvent::TodoAttachmentAdded,
+            TodoEvent::TodoAttachmentRemoved { .. } => WebhookEvent::TodoAttachmentRemoved,
+        }
+    }
+}
+
+/// A target URL notified of todo events
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Webhook {
+    pub id: String,
+    /// The list whose events it receives and whose callers manage it; `None` when callers
+    /// are not scoped to a list
+    pub list: Option<TenantId>,
+    pub url: String,
+    /// Key of the `x-hk-todo-signature` HMAC; never returned by the API
+    pub secret: String,
+    /// Events delivered to the target; empty for every event
+    pub events: Vec<WebhookEvent>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Webhook {
+    fn accepts(&self, event: WebhookEvent) -> bool {
+        self.events.is_empty() || self.events.contains(&event)
+    }
+}
+
+/// An event that could not be delivered to a webhook after every attempt
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeadLetter {
+    pub webhook_id: String,
+    /// The list of the webhook
+    pub list: Option<TenantId>,
+    pub url: String,
+    pub event: CloudEvent,
+    pub attempts: u32,
+    /// Why the last attempt failed
+    pub error: String,
+    pub failed_at: DateTime<Utc>,
+}
+
+/// Why a webhook cannot be registered or found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WebhookError {
+    /// The URL is not an absolute `http` or `https` URL
+    InvalidUrl(String),
+    /// The secret is empty
+    EmptySecret,
+    /// No webhook has the given id
+    WebhookNotFound,
+}
+
+impl std::fmt::Display for WebhookError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WebhookError::InvalidUrl(url) => {
+                write!(f, "invalid webhook url {:?}, expected http or https", url)
+            }
+            WebhookError::EmptySecret => write!(f, "webhook secret must not be empty"),
+            WebhookError::WebhookNotFound => write!(f, "webhook not found"),
+        }
+    }
+}
+
+impl std::error::Error for WebhookError {}
+
+/// Delivers the events of an `EventBus` to registered webhooks
+///
+/// Each event is POSTed to every webhook of its todo's list subscribed to its type as a
+/// CloudEvent, with `Content-Type: application/cloudevents+json`, the bus sequence number as
+/// `id`, and the body signed in `x-hk-todo-signature`. A delivery is retried with
+/// exponential backoff until the target answers `2xx`; after the last attempt the event goes
+/// to a bounded dead-letter queue, from which `retry_dead_letters` delivers it again.
+///
+/// Webhooks and dead letters are kept in memory and lost on restart.
+pub struct WebhookManager {
+    client: reqwest::Client,
+    webhooks: RwLock<Vec<Webhook>>,
+    dead_letters: Mutex<VecDeque<DeadLetter>>,
+    max_attempts: u32,
+    initial_backoff: Duration,
+}
+
+impl WebhookManager {
+    pub fn new() -> Self {
+        WebhookManager {
+            client: reqwest::Client::builder()
+                .timeout(DELIVERY_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+            webhooks: RwLock::new(Vec::new()),
+            dead_letters: Mutex::new(VecDeque::new()),
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+        }
+    }
+
+    /// Attempts each delivery `max_attempts` times, at least once, waiting `initial_backoff`
+    /// after the first failure and twice as long after each further one
+    pub fn with_retries(mut self, max_attempts: u32, initial_backoff: Duration) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Registers `url` to receive `events` of the todos of `list`, or every event when
+    /// `events` is empty
+    ///
+    /// # Returns
+    /// - `Ok(Webhook)`: The registered webhook, with a new id
+    /// - `Err(WebhookError)`: If the URL is not `http` or `https`, or the secret is empty
+    pub fn register(
+        &self,
+        list: Option<TenantId>,
+        url: &str,
+        secret: String,
+        events: Vec<WebhookEvent>,
+    ) -> Result<Webhook, WebhookError> {
+        match reqwest::Url::parse(url) {
+            Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => {}
+            _ => return Err(WebhookError::InvalidUrl(url.to_string())),
+        }
+        if secret.is_empty() {
+            return Err(WebhookError::EmptySecret);
+        }
+        let webhook = Webhook {
+            id: uuid::Uuid::new_v4().to_string(),
+            list,
+            url: url.to_string(),
+            secret,
+            events,
+            created_at: Utc::now(),
+        };
+        self.write_webhooks().push(webhook.clone());
+        Ok(webhook)
+    }
+
+    /// Stops notifying a webhook of `list`; deliveries already under way still finish
+    ///
+    /// # Returns
+    /// - `Err(WebhookError::WebhookNotFound)`: If no webhook of `list` has `id`
+    pub fn unregister(&self, list: Option<&TenantId>, id: &str) -> Result<(), WebhookError> {
+        let mut webhooks = self.write_webhooks();
+        let count = webhooks.len();
+        webhooks.retain(|webhook| webhook.id != id || webhook.list.as_ref() != list);
+        if webhooks.len() == count {
+            return Err(WebhookError::WebhookNotFound);
+        }
+        Ok(())
+    }
+
+    /// Registered webhooks of `list`, oldest first
+    pub fn webhooks(&self, list: Option<&TenantId>) -> Vec<Webhook> {
+        self.read_webhooks()
+            .iter()
+            .filter(|webhook| webhook.list.as_ref() == list)
+            .cloned()
+            .collect()
+    }
+
+    /// Events that could not be delivered to the webhooks of `list`, oldest first
+    pub fn dead_letters(&self, list: Option<&TenantId>) -> Vec<DeadLetter> {
+        self.lock_dead_letters()
+            .iter()
+            .filter(|dead_letter| dead_letter.list.as_ref() == list)
+            .cloned()
+            .collect()
+    }
+
+    /// Takes the dead letters of `list` out of the queue, delivering each event again to its
+    /// webhook if that is still registered
+    ///
+    /// # Returns
+    /// The number of events scheduled for delivery
+    pub fn retry_dead_letters(self: &Arc<Self>, list: Option<&TenantId>) -> usize {
+        let dead_letters: VecDeque<DeadLetter> = {
+            let mut queue = self.lock_dead_letters();
+            let (retried, kept) = queue
+                .drain(..)
+                .partition(|dead_letter| dead_letter.list.as_ref() == list);
+            *queue = kept;
+            retried
+        };
+        let webhooks = self.webhooks(list);
+        let mut retried = 0;
+        for dead_letter in dead_letters {
+            let Some(webhook) = webhooks.iter().find(|w| w.id == dead_letter.webhook_id) else {
+                continue;
+            };
+            self.spawn_delivery(webhook.clone(), dead_letter.event);
+            retried += 1;
+        }
+        retried
+    }
+
+    /// Delivers every event published on `bus` from now on, in a task that runs until the
+    /// bus is dropped
+    ///
+    /// Events on the bus carry the ids of their todos under the list they were made in, and
+    /// a webhook receives those of its list with the ids unscoped. Each delivery runs in its
+    /// own task, so a slow target does not hold up the others.
+    pub fn start(self: &Arc<Self>, bus: &EventBus) -> JoinHandle<()> {
+        let manager = self.clone();
+        let mut receiver = bus.subscribe();
+        tokio::spawn(async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(published) => {
+                        let kind = WebhookEvent::of(&published.event);
+                        let webhooks = manager.read_webhooks().clone();
+                        for webhook in webhooks {
+                            if !webhook.accepts(kind) {
+                                continue;
+                            }
+                            let Some(event) = for_list(webhook.list.as_ref(), &published.event)
+                            else {
+                                continue;
+                            };
+                            let event = CloudEvent::from_todo_event(&event, SOURCE)
+                                .with_id(published.sequence.to_string());
+                            manager.spawn_delivery(webhook, event);
+                        }
+                    }
+                    Err(RecvError::Lagged(missed)) => {
+                        tracing::warn!(missed, "webhook delivery fell behind and skipped events");
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    fn spawn_delivery(self: &Arc<Self>, webhook: Webhook, event: CloudEvent) {
+        let manager = self.clone();
+        tokio::spawn(async move { manager.deliver(webhook, event).await });
+    }
+
+    async fn deliver(&self, webhook: Webhook, event: CloudEvent) {
+        let body = match serde_json::to_vec(&event) {
+            Ok(body) => body,
+            Err(err) => return self.dead_letter(&webhook, event, 0, err.to_string()),
+        };
+        let signature = sign(webhook.secret.as_bytes(), &body);
+        let mut backoff = self.initial_backoff;
+        let mut error = String::new();
+        for attempt in 1..=self.max_attempts {
+            let sent = self
+                .client
+                .post(&webhook.url)
+                .header(
+                    reqwest::header::CONTENT_TYPE,
+                    "application/cloudevents+json",
+                )
+                .header(SIGNATURE_HEADER, &signature)
+                .body(body.clone())
+                .send()
+                .await;
+            match sent {
+                Ok(response) if response.status().is_success() => return,
+                Ok(response) => error = format!("target answered {}", response.status()),
+                Err(err) => error = err.to_string(),
+            }
+            if attempt < self.max_attempts {
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+            }
+        }
+        tracing::warn!(webhook.id = %webhook.id, %error, "webhook delivery failed");
+        self.dead_letter(&webhook, event, self.max_attempts, error);
+    }
+
+    fn dead_letter(&self, webhook: &Webhook, event: CloudEvent, attempts: u32, error: String) {
+        let mut dead_letters = self.lock_dead_letters();
+        if dead_letters.len() == DEAD_LETTER_CAPACITY {
+            dead_letters.pop_front();
+        }
+        dead_letters.push_back(DeadLetter {
+            webhook_id: webhook.id.clone(),
+            list: webhook.list.clone(),
+            url: webhook.url.clone(),
+            event,
+            attempts,
+            error,
+            failed_at: Utc::now(),
+        });
+    }
+
+    fn read_webhooks(&self) -> std::sync::RwLockReadGuard<'_, Vec<Webhook>> {
+        self.webhooks
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn write_webhooks(&self) -> std::sync::RwLockWriteGuard<'_, Vec<Webhook>> {
+        self.webhooks
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn lock_dead_letters(&self) -> std::sync::MutexGuard<'_, VecDeque<DeadLetter>> {
+        self.dead_letters
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for WebhookManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `sha256=<hex>` HMAC-SHA256 of `body` keyed with `secret`, as sent in
+/// `x-hk-todo-signature`
+///
+/// Targets recompute it over the raw body they received and compare in constant time.
+pub fn sign(secret: &[u8], body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC takes keys of any length");
+    mac.update(body);
+    let digest: String = mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    format!("sha256={}", digest)
+}
+
+/// Routes managing the webhooks of the caller's list
+pub(crate) fn webhook_routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/webhooks", get(list_webhooks).post(register_webhook))
+        .route("/webhooks/{id}", delete(unregister_webhook))
+        .route("/webhooks/dead-letters", get(list_dead_letters))
+        .route("/webhooks/dead-letters/retry", post(retry_dead_letters))
+}
+
+#[utoipa::path(
+    get,
+    path = "/webhooks",
+    tag = "webhooks",
+    responses((status = 200, description = "Registered webhooks of the caller's list, oldest first", body = Vec<WebhookDto>)),
+)]
+pub(crate) async fn list_webhooks(
+    State(state): State<Arc<AppState>>,
+    context: Option<Extension<AuthContext>>,
+) -> Response {
+    let Some(manager) = &state.webhooks else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let list = state.list(context.as_deref());
+    let webhooks = manager.webhooks(list.as_ref());
+    Json(webhooks.iter().map(WebhookDto::from).collect::<Vec<_>>()).into_response()
+}
+
+#[utoipa::path(
+    post,
+    path = "/webhooks",
+    tag = "webhooks",
+    request_body = RegisterWebhookRequest,
+    responses(
+        (status = 201, description = "Registered webhook, receiving the events of the caller's list", body = WebhookDto),
+        (status = 422, description = "Invalid URL or empty secret", body = ErrorDto, content_type = "application/problem+json"),
+    ),
+)]
+pub(crate) async fn register_webhook(
+    State(state): State<Arc<AppState>>,
+    context: Option<Extension<AuthContext>>,
+    Json(request): Json<RegisterWebhookRequest>,
+) -> Result<Response, WebhookError> {
+    let Some(manager) = &state.webhooks else {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+    };
+    let list = state.list(context.as_deref());
+    let webhook = manager.register(list, &request.url, request.secret, request.events)?;
+    Ok((StatusCode::CREATED, Json(WebhookDto::from(&webhook))).into_response())
+}
+
+#[utoipa::path(
+    delete,
+    path = "/webhooks/{id}",
+    tag = "webhooks",
+    params(("id" = String, Path, description = "Webhook id")),
+    responses(
+        (status = 204, description = "Webhook removed"),
+        (status = 404, description = "No webhook of the caller's list has the id", body = ErrorDto, content_type = "application/problem+json"),
+    ),
+)]
+pub(crate) async fn unregister_webhook(
+    State(state): State<Arc<AppState>>,
+    context: Option<Extension<AuthContext>>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, WebhookError> {
+    let manager = state
+        .webhooks
+        .as_ref()
+        .ok_or(WebhookError::WebhookNotFound)?;
+    manager.unregister(state.list(context.as_deref()).as_ref(), &id)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/webhooks/dead-letters",
+    tag = "webhooks",
+    responses((status = 200, description = "Events that could not be delivered to the webhooks of the caller's list, oldest first", body = Vec<DeadLetterDto>)),
+)]
+pub(crate) async fn list_dead_letters(
+    State(state): State<Arc<AppState>>,
+    context: Option<Extension<AuthContext>>,
+) -> Response {
+    let Some(manager) = &state.webhooks else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let dead_letters = manager.dead_letters(state.list(context.as_deref()).as_ref());
+    Json(
+        dead_letters
+            .iter()
+            .map(DeadLetterDto::from)
+            .collect::<Vec<_>>(),
+    )
+    .into_response()
+}
+
+#[utoipa::path(
+    post,
+    path = "/webhooks/dead-letters/retry",
+    tag = "webhooks",
+    responses((status = 202, description = "Dead letters of the caller's list scheduled for delivery again", body = RetriedDto)),
+)]
+pub(crate) async fn retry_dead_letters(
+    State(state): State<Arc<AppState>>,
+    context: Option<Extension<AuthContext>>,
+) -> Response {
+    let Some(manager) = &state.webhooks else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let retried = manager.retry_dead_letters(state.list(context.as_deref()).as_ref());
+    (StatusCode::ACCEPTED, Json(RetriedDto { retried })).into_response()
+}
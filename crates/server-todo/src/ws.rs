@@ -0,0 +1,67 @@
+use axum::Extension;
+use axum::extract::State;
+use axum::extract::ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade};
+use axum::response::Response;
+use std::sync::Arc;
+use todo::TenantId;
+use todo::application::auth::AuthContext;
+use tokio::sync::broadcast::Receiver;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::dto::TodoEventDto;
+use crate::events::{SequencedEvent, for_list};
+use crate::routes::AppState;
+
+/// Close code sent to a client that fell too far behind to be caught up
+pub const CLOSE_LAGGED: u16 = 4000;
+
+/// `GET /ws`: upgrades to a WebSocket receiving every event of the caller's list as a JSON
+/// text message
+pub async fn ws_handler(
+    State(state): State<Arc<AppState>>,
+    context: Option<Extension<AuthContext>>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    let list = state.list(context.as_deref());
+    // Subscribe before the upgrade so no event is lost during the handshake
+    let events = state.events.subscribe();
+    ws.on_upgrade(move |socket| push_events(socket, list, events))
+}
+
+async fn push_events(
+    mut socket: WebSocket,
+    list: Option<TenantId>,
+    mut events: Receiver<SequencedEvent>,
+) {
+    loop {
+        tokio::select! {
+            event = events.recv() => match event {
+                Ok(event) => {
+                    let Some(event) = for_list(list.as_ref(), &event.event) else {
+                        continue;
+                    };
+                    let Ok(json) = serde_json::to_string(&TodoEventDto::from(event)) else {
+                        continue;
+                    };
+                    if socket.send(Message::Text(json.into())).await.is_err() {
+                        return;
+                    }
+                }
+                Err(RecvError::Lagged(_)) => {
+                    let close = CloseFrame {
+                        code: CLOSE_LAGGED,
+                        reason: "missed events, reload the todos and reconnect".into(),
+                    };
+                    let _ = socket.send(Message::Close(Some(close))).await;
+                    return;
+                }
+                Err(RecvError::Closed) => return,
+            },
+            // Client messages are ignored; the channel only pushes
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
@@ -0,0 +1,174 @@
+mod common;
+
+use axum::Router;
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use common::{SECRET, tenant_token};
+use http_body_util::BodyExt;
+use serde_json::{Value, json};
+use server_todo::{AppState, router};
+use std::sync::Arc;
+use todo::application::activity_feed::ActivityFeed;
+use todo::infrastructure::jwt_authenticator::JwtAuthenticator;
+use todo::infrastructure::repositories::todo::TodoBackend;
+use todo::infrastructure::repositories::trash::InMemoryTrashRepository;
+use tower::ServiceExt;
+
+async fn send(app: &Router, request: Request<Body>) -> (StatusCode, Value) {
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let value = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    (status, value)
+}
+
+async fn create_todo(app: &Router, description: &str) -> String {
+    create_todo_as(app, description, None).await
+}
+
+async fn create_todo_as(app: &Router, description: &str, token: Option<&str>) -> String {
+    let mut request = Request::post("/todos").header(header::CONTENT_TYPE, "application/json");
+    if let Some(token) = token {
+        request = request.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let request = request
+        .body(Body::from(
+            json!({ "description": description }).to_string(),
+        ))
+        .unwrap();
+    let (_, todo) = send(app, request).await;
+    todo["id"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn test_activity_feed_lists_changes_in_the_accepted_language() {
+    // Arrange
+    let state = AppState::new(TodoBackend::Memory.repository())
+        .with_activity_feed(Arc::new(ActivityFeed::new()));
+    let app = router(Arc::new(state));
+    let id = create_todo(&app, "Write docs").await;
+    create_todo(&app, "Buy milk").await;
+    let request = Request::put(format!("/todos/{}/state", id))
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(r#"{"state":"IN_PROGRESS"}"#))
+        .unwrap();
+    send(&app, request).await;
+
+    // Act
+    let request = Request::get("/activity?limit=2")
+        .header(header::ACCEPT_LANGUAGE, "vi")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let language = response.headers()[header::CONTENT_LANGUAGE].clone();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let first: Value = serde_json::from_slice(&bytes).unwrap();
+    let uri = format!("/activity?cursor={}", first["next_cursor"]);
+    let (_, second) = send(&app, Request::get(uri).body(Body::empty()).unwrap()).await;
+
+    // Assert
+    assert_eq!(language, "vi");
+    assert_eq!(first["items"][0]["sequence"], 3);
+    assert_eq!(first["items"][0]["todo_id"], id.as_str());
+    assert_eq!(first["items"][0]["type"], "TODO_STATE_CHANGED");
+    assert_eq!(
+        first["items"][0]["message"],
+        "Đã bắt đầu \"Write docs\" vừa xong"
+    );
+    assert_eq!(first["items"][1]["message"], "Đã tạo \"Buy milk\" vừa xong");
+    assert_eq!(first["next_cursor"], 2);
+    assert_eq!(
+        second["items"][0]["message"],
+        "Created \"Write docs\" just now"
+    );
+    assert_eq!(second["next_cursor"], Value::Null);
+}
+
+#[tokio::test]
+async fn test_activity_feed_lists_deletes_and_restores() {
+    // Arrange
+    let state = AppState::new(TodoBackend::Memory.repository())
+        .with_trash(Arc::new(InMemoryTrashRepository::new()))
+        .with_activity_feed(Arc::new(ActivityFeed::new()));
+    let app = router(Arc::new(state));
+    let id = create_todo(&app, "Oops").await;
+    let request = Request::delete(format!("/todos/{}", id))
+        .body(Body::empty())
+        .unwrap();
+    send(&app, request).await;
+    let request = Request::post(format!("/trash/{}/restore", id))
+        .body(Body::empty())
+        .unwrap();
+    send(&app, request).await;
+
+    // Act
+    let request = Request::get("/activity").body(Body::empty()).unwrap();
+    let (status, activity) = send(&app, request).await;
+
+    // Assert
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(activity["items"][0]["type"], "TODO_RESTORED");
+    assert_eq!(
+        activity["items"][0]["message"],
+        "Restored \"Oops\" just now"
+    );
+    assert_eq!(activity["items"][1]["type"], "TODO_DELETED");
+    assert_eq!(activity["items"][1]["todo_id"], id.as_str());
+    assert_eq!(activity["items"][1]["message"], "Deleted \"Oops\" just now");
+}
+
+#[tokio::test]
+async fn test_activity_feed_is_not_served_unless_enabled() {
+    let app = router(Arc::new(AppState::new(TodoBackend::Memory.repository())));
+
+    let (status, _) = send(&app, Request::get("/activity").body(Body::empty()).unwrap()).await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_activity_feed_only_lists_the_callers_list() {
+    // Arrange
+    let authenticator = JwtAuthenticator::with_secret(SECRET).with_audience("todo-api");
+    let state = AppState::new(TodoBackend::Memory.repository())
+        .with_authenticator(Arc::new(authenticator))
+        .with_activity_feed(Arc::new(ActivityFeed::new()));
+    let app = router(Arc::new(state));
+    let alice = tenant_token("alice", "team");
+    let carol = tenant_token("carol", "team");
+    let bob = tenant_token("bob", "other");
+    create_todo_as(&app, "Write docs", Some(&alice)).await;
+    create_todo_as(&app, "Buy milk", Some(&bob)).await;
+    create_todo_as(&app, "Review docs", Some(&carol)).await;
+    let get = |uri: &str, token: &str| {
+        Request::get(uri)
+            .header(header::AUTHORIZATION, format!("Bearer {}", token))
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    // Act
+    let (_, team) = send(&app, get("/activity", &alice)).await;
+    let (_, other) = send(&app, get("/activity", &bob)).await;
+    let (_, by_alice) = send(&app, get("/activity?actor=alice", &carol)).await;
+
+    // Assert
+    let messages = |page: &Value| -> Vec<String> {
+        page["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|item| item["message"].as_str().unwrap().to_string())
+            .collect()
+    };
+    assert_eq!(
+        messages(&team),
+        vec![
+            "Created \"Review docs\" just now",
+            "Created \"Write docs\" just now"
+        ]
+    );
+    assert_eq!(team["items"][0]["actor"], "carol");
+    assert_eq!(messages(&other), vec!["Created \"Buy milk\" just now"]);
+    assert_eq!(messages(&by_alice), vec!["Created \"Write docs\" just now"]);
+}
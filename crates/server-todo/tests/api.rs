@@ -0,0 +1,413 @@
+use axum::Router;
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use chrono::{TimeDelta, Utc};
+use http_body_util::BodyExt;
+use serde_json::{Value, json};
+use server_todo::{AppState, router};
+use std::sync::Arc;
+use todo::application::escalation::EscalateTodosHandler;
+use todo::application::messages::MessageCatalog;
+use todo::application::quota::{Quota, QuotaPolicy};
+use todo::domain::escalation::{EscalationRule, EscalationRuleRepository};
+use todo::infrastructure::event_stores::InMemoryEventStore;
+use todo::infrastructure::repositories::escalation::InMemoryEscalationRuleRepository;
+use todo::infrastructure::repositories::todo::TodoBackend;
+use todo::infrastructure::repositories::trash::InMemoryTrashRepository;
+use todo::{FixedClock, TodoState};
+use tower::ServiceExt;
+
+fn app() -> Router {
+    router(Arc::new(AppState::new(TodoBackend::Memory.repository())))
+}
+
+async fn send(app: &Router, method: &str, uri: &str, body: Option<Value>) -> (StatusCode, Value) {
+    let request = Request::builder().method(method).uri(uri);
+    let request = match body {
+        Some(body) => request
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string())),
+        None => request.body(Body::empty()),
+    };
+    let response = app.clone().oneshot(request.unwrap()).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let value = if bytes.is_empty() {
+        Value::Null
+    } else {
+        serde_json::from_slice(&bytes).unwrap()
+    };
+    (status, value)
+}
+
+#[tokio::test]
+async fn test_create_change_state_and_delete_todo() {
+    // Arrange
+    let app = app();
+
+    // Act & Assert
+    let (status, created) = send(
+        &app,
+        "POST",
+        "/todos",
+        Some(json!({ "description": "Write docs" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(created["state"], "TODO");
+    let uri = format!("/todos/{}", created["id"].as_str().unwrap());
+
+    let (status, changed) = send(
+        &app,
+        "PUT",
+        &format!("{}/state", uri),
+        Some(json!({ "state": "IN_PROGRESS" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(changed["state"], "IN_PROGRESS");
+
+    let (status, todos) = send(&app, "GET", "/todos?state=IN_PROGRESS", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(todos, json!([changed]));
+
+    let (status, _) = send(&app, "DELETE", &uri, None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, _) = send(&app, "GET", &uri, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_todo_errors_map_to_status_codes() {
+    // Arrange
+    let app = app();
+    let (_, created) = send(
+        &app,
+        "POST",
+        "/todos",
+        Some(json!({ "description": "Write docs" })),
+    )
+    .await;
+    let state_uri = format!("/todos/{}/state", created["id"].as_str().unwrap());
+
+    // Act & Assert
+    let (status, error) = send(&app, "POST", "/todos", Some(json!({ "description": "" }))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(error["code"], "EMPTY_DESCRIPTION");
+
+    let (status, error) = send(&app, "PUT", &state_uri, Some(json!({ "state": "TODO" }))).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(error["code"], "INVALID_STATE_TRANSITION");
+
+    let (status, error) = send(
+        &app,
+        "PUT",
+        "/todos/non-existent-id/state",
+        Some(json!({ "state": "DONE" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(error["code"], "TODO_NOT_FOUND");
+
+    let (status, error) = send(&app, "DELETE", "/todos/non-existent-id", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(error["status"], 404);
+    assert_eq!(error["type"], "urn:hk-todo:error:TODO_NOT_FOUND");
+    assert_eq!(error["title"], "Todo not found");
+    assert_eq!(error["detail"], "todo not found");
+}
+
+#[tokio::test]
+async fn test_list_todos_filters_by_search_query() {
+    // Arrange
+    let app = app();
+    for description in ["Write quarterly report", "Report the bug", "Buy milk"] {
+        send(
+            &app,
+            "POST",
+            "/todos",
+            Some(json!({ "description": description })),
+        )
+        .await;
+    }
+
+    // Act
+    let (status, found) = send(&app, "GET", "/todos?q=report%20-%22the%20bug%22", None).await;
+    let (invalid_status, error) = send(&app, "GET", "/todos?q=start%3C2025-01-01", None).await;
+
+    // Assert
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(found.as_array().unwrap().len(), 1);
+    assert_eq!(found[0]["description"], "Write quarterly report");
+    assert_eq!(invalid_status, StatusCode::BAD_REQUEST);
+    assert_eq!(error["code"], "INVALID_QUERY");
+    assert_eq!(error["detail"], "unknown field \"start\"");
+}
+
+#[tokio::test]
+async fn test_todo_history_lists_events_of_deleted_todos() {
+    // Arrange
+    let without_store = app();
+    let state = AppState::new(TodoBackend::Memory.repository())
+        .with_event_store(Arc::new(InMemoryEventStore::new()));
+    let app = router(Arc::new(state));
+    let (_, todo) = send(
+        &app,
+        "POST",
+        "/todos",
+        Some(json!({"description": "Tracked"})),
+    )
+    .await;
+    let id = todo["id"].as_str().unwrap();
+    let uri = format!("/todos/{}", id);
+    let body = json!({"state": "IN_PROGRESS"});
+    send(&app, "PUT", &format!("{}/state", uri), Some(body)).await;
+    send(&app, "DELETE", &uri, None).await;
+
+    // Act
+    let (status, history) = send(&app, "GET", &format!("{}/history", uri), None).await;
+    let (missing, _) = send(&app, "GET", "/todos/missing/history", None).await;
+    let (unserved, _) = send(&without_store, "GET", &format!("{}/history", uri), None).await;
+
+    // Assert
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(history[0]["type"], "TODO_CREATED");
+    assert_eq!(history[1]["type"], "TODO_STATE_CHANGED");
+    assert_eq!(history[1]["to_state"], "IN_PROGRESS");
+    assert_eq!(history[2]["type"], "TODO_DELETED");
+    assert_eq!(history.as_array().unwrap().len(), 3);
+    assert_eq!(missing, StatusCode::NOT_FOUND);
+    assert_eq!(unserved, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_deleted_todo_is_restored_from_the_trash() {
+    // Arrange
+    let without_trash = app();
+    let state = AppState::new(TodoBackend::Memory.repository())
+        .with_trash(Arc::new(InMemoryTrashRepository::new()));
+    let app = router(Arc::new(state));
+    let (_, todo) = send(&app, "POST", "/todos", Some(json!({"description": "Oops"}))).await;
+    let id = todo["id"].as_str().unwrap();
+    send(&app, "DELETE", &format!("/todos/{}", id), None).await;
+
+    // Act
+    let (listed, trash) = send(&app, "GET", "/trash", None).await;
+    let (restored, restored_todo) =
+        send(&app, "POST", &format!("/trash/{}/restore", id), None).await;
+    let (again, _) = send(&app, "POST", &format!("/trash/{}/restore", id), None).await;
+    let (found, _) = send(&app, "GET", &format!("/todos/{}", id), None).await;
+    let (unserved, _) = send(&without_trash, "GET", "/trash", None).await;
+
+    // Assert
+    assert_eq!(listed, StatusCode::OK);
+    assert_eq!(trash[0]["id"], id);
+    assert_eq!(trash[0]["description"], "Oops");
+    assert!(trash[0]["deleted_at"].is_string());
+    assert_eq!(restored, StatusCode::OK);
+    assert_eq!(restored_todo, todo);
+    assert_eq!(again, StatusCode::NOT_FOUND);
+    assert_eq!(found, StatusCode::OK);
+    assert_eq!(unserved, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_quota_refuses_new_todos_once_used_up() {
+    // Arrange
+    let (_, unlimited) = send(&app(), "GET", "/quota", None).await;
+    let state =
+        AppState::new(TodoBackend::Memory.repository()).with_quota(QuotaPolicy::new(Quota {
+            max_open_todos: None,
+            max_todos: Some(1),
+        }));
+    let app = router(Arc::new(state));
+    send(
+        &app,
+        "POST",
+        "/todos",
+        Some(json!({"description": "First"})),
+    )
+    .await;
+
+    // Act
+    let (refused, problem) = send(
+        &app,
+        "POST",
+        "/todos",
+        Some(json!({"description": "Second"})),
+    )
+    .await;
+    let (status, usage) = send(&app, "GET", "/quota", None).await;
+
+    // Assert
+    assert_eq!(refused, StatusCode::CONFLICT);
+    assert_eq!(problem["code"], "QUOTA_EXCEEDED");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        usage,
+        json!({
+            "open_todos": 1,
+            "todos": 1,
+            "max_open_todos": null,
+            "max_todos": 1,
+            "remaining": 0
+        })
+    );
+    assert_eq!(unlimited["remaining"], Value::Null);
+}
+
+#[tokio::test]
+async fn test_undo_reverts_the_last_state_change() {
+    // Arrange
+    let state = AppState::new(TodoBackend::Memory.repository())
+        .with_event_store(Arc::new(InMemoryEventStore::new()));
+    let app = router(Arc::new(state));
+    let (_, todo) = send(&app, "POST", "/todos", Some(json!({"description": "Oops"}))).await;
+    let uri = format!("/todos/{}", todo["id"].as_str().unwrap());
+    let body = json!({"state": "IN_PROGRESS"});
+    send(&app, "PUT", &format!("{}/state", uri), Some(body)).await;
+
+    // Act
+    let (status, undone) = send(&app, "POST", &format!("{}/undo", uri), None).await;
+    let (_, history) = send(&app, "GET", &format!("{}/history", uri), None).await;
+    let (redo_status, redone) = send(&app, "POST", &format!("{}/undo", uri), None).await;
+
+    // Assert
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(undone["state"], "TODO");
+    assert_eq!(history.as_array().unwrap().len(), 3);
+    assert_eq!(history[2]["from_state"], "IN_PROGRESS");
+    // Undoing the undo moves the todo forward again
+    assert_eq!(redo_status, StatusCode::OK);
+    assert_eq!(redone["state"], "IN_PROGRESS");
+}
+
+#[tokio::test]
+async fn test_problems_are_described_in_the_accepted_language() {
+    // Arrange
+    let messages =
+        MessageCatalog::default().with_messages("fr", [("TODO_NOT_FOUND", "tâche introuvable")]);
+    let app = router(Arc::new(
+        AppState::new(TodoBackend::Memory.repository()).with_messages(Arc::new(messages)),
+    ));
+    let request = |language: &str| {
+        Request::builder()
+            .uri("/todos/non-existent-id")
+            .header(header::ACCEPT_LANGUAGE, language)
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    // Act
+    let vietnamese = app
+        .clone()
+        .oneshot(request("vi-VN, en;q=0.5"))
+        .await
+        .unwrap();
+    let french = app.clone().oneshot(request("fr-CA")).await.unwrap();
+
+    // Assert
+    assert_eq!(vietnamese.status(), StatusCode::NOT_FOUND);
+    assert_eq!(vietnamese.headers()[header::CONTENT_LANGUAGE], "vi");
+    assert_eq!(vietnamese.headers()[header::VARY], "accept-language");
+    let bytes = vietnamese.into_body().collect().await.unwrap().to_bytes();
+    let error: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(error["code"], "TODO_NOT_FOUND");
+    assert_eq!(error["title"], "Không tìm thấy công việc");
+    assert_eq!(error["detail"], "không tìm thấy công việc");
+
+    assert_eq!(french.headers()[header::CONTENT_LANGUAGE], "fr");
+    let bytes = french.into_body().collect().await.unwrap().to_bytes();
+    let error: Value = serde_json::from_slice(&bytes).unwrap();
+    // fr has no title, so it stays in the default locale
+    assert_eq!(error["title"], "Todo not found");
+    assert_eq!(error["detail"], "tâche introuvable");
+}
+
+#[test]
+fn test_backend_parsing() {
+    assert_eq!(TodoBackend::parse("memory"), Ok(TodoBackend::Memory));
+    assert_eq!(
+        TodoBackend::parse("file:/tmp/todos.json"),
+        Ok(TodoBackend::File("/tmp/todos.json".into()))
+    );
+    assert!(TodoBackend::parse("sqlite:todos.db").is_err());
+    assert!(TodoBackend::parse("file:").is_err());
+}
+
+#[tokio::test]
+async fn test_escalations_list_the_todos_breaking_a_rule() {
+    // Arrange
+    let (unserved, _) = send(&app(), "GET", "/escalations", None).await;
+    let repository = TodoBackend::Memory.repository();
+    let rules = Arc::new(InMemoryEscalationRuleRepository::new());
+    let rule = EscalationRule::new("forgotten", TodoState::Todo, 1).unwrap();
+    rules.save(&rule).await.unwrap();
+    let later = Utc::now() + TimeDelta::days(2);
+    let app = router(Arc::new(AppState::new(repository).with_escalations(
+        move |repository| {
+            EscalateTodosHandler::new(repository, rules.clone())
+                .with_clock(Arc::new(FixedClock::new(later)))
+        },
+    )));
+    let (_, created) = send(
+        &app,
+        "POST",
+        "/todos",
+        Some(json!({"description": "Forgotten"})),
+    )
+    .await;
+
+    // Act
+    let (status, escalations) = send(&app, "GET", "/escalations", None).await;
+
+    // Assert
+    assert_eq!(unserved, StatusCode::NOT_FOUND);
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(escalations[0]["todo"], created);
+    assert_eq!(escalations[0]["rule"], "forgotten");
+    assert_eq!(escalations[0]["after_days"], 1);
+    assert_eq!(escalations[0]["since"], created["created_at"]);
+}
+
+#[tokio::test]
+async fn test_openapi_document_covers_routes() {
+    // Arrange
+    let app = app();
+
+    // Act
+    let (status, document) = send(&app, "GET", "/openapi.json", None).await;
+
+    // Assert
+    assert_eq!(status, StatusCode::OK);
+    assert!(document["openapi"].as_str().unwrap().starts_with("3."));
+    let paths = &document["paths"];
+    for (path, method) in [
+        ("/todos", "get"),
+        ("/todos", "post"),
+        ("/todos/{id}", "get"),
+        ("/todos/{id}", "delete"),
+        ("/todos/{id}/state", "put"),
+        ("/todos/{id}/history", "get"),
+        ("/todos/{id}/undo", "post"),
+        ("/trash", "get"),
+        ("/trash/{id}/restore", "post"),
+        ("/trash/{id}", "delete"),
+        ("/quota", "get"),
+        ("/escalations", "get"),
+        ("/events", "get"),
+    ] {
+        assert!(
+            paths[path][method].is_object(),
+            "{} {} is not documented",
+            method,
+            path
+        );
+    }
+    let schemas = &document["components"]["schemas"];
+    assert_eq!(
+        schemas["StateDto"]["enum"],
+        json!(["TODO", "IN_PROGRESS", "DONE"])
+    );
+}
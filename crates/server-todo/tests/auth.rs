@@ -0,0 +1,362 @@
+mod common;
+
+use axum::body::Body;
+use axum::http::request::Builder;
+use axum::http::{Request, StatusCode, header};
+use axum::routing::get;
+use axum::{Json, Router};
+use common::{SECRET, tenant_token};
+use http_body_util::BodyExt;
+use jsonwebtoken::{EncodingKey, Header, encode};
+use serde_json::{Value, json};
+use server_todo::{AppState, router};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use todo::TenantId;
+use todo::application::access_control::AccessControl;
+use todo::application::api_key_authenticator::ApiKeyAuthenticator;
+use todo::application::auth::AuthError;
+use todo::application::issue_api_key_handler::IssueApiKeyHandler;
+use todo::application::quota::{Quota, QuotaPolicy};
+use todo::domain::access::{Membership, MembershipRepository, Role};
+use todo::domain::api_key::{ApiKeyRepository, ApiKeyScope};
+use todo::infrastructure::event_stores::InMemoryEventStore;
+use todo::infrastructure::jwt_authenticator::JwtAuthenticator;
+use todo::infrastructure::repositories::api_key::InMemoryApiKeyRepository;
+use todo::infrastructure::repositories::membership::InMemoryMembershipRepository;
+use todo::infrastructure::repositories::todo::TodoBackend;
+use todo::infrastructure::repositories::trash::InMemoryTrashRepository;
+use tokio::net::TcpListener;
+use tower::ServiceExt;
+
+/// Serves a discovery document and a key set holding `SECRET`, returning the issuer URL
+async fn start_provider() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let issuer = format!("http://{}", listener.local_addr().unwrap());
+    let discovery = json!({ "issuer": issuer, "jwks_uri": format!("{}/jwks", issuer) });
+    let jwks = json!({ "keys": [
+        { "kty": "oct", "kid": "key-1", "alg": "HS256", "k": "cHJvdmlkZXItc2VjcmV0" },
+    ]});
+    let provider = Router::new()
+        .route(
+            "/.well-known/openid-configuration",
+            get(move || async move { Json(discovery) }),
+        )
+        .route("/jwks", get(move || async move { Json(jwks) }));
+    tokio::spawn(async move { axum::serve(listener, provider).await });
+    issuer
+}
+
+fn token(issuer: &str, audience: &str) -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let header = Header {
+        kid: Some("key-1".to_string()),
+        ..Header::default()
+    };
+    let claims = json!({
+        "sub": "alice",
+        "iss": issuer,
+        "aud": audience,
+        "exp": now + 300,
+        "scope": "todos:read",
+    });
+    encode(&header, &claims, &EncodingKey::from_secret(SECRET)).unwrap()
+}
+
+async fn get_with(app: &Router, uri: &str, token: Option<&str>) -> (StatusCode, Value) {
+    send(app, Request::get(uri), Body::empty(), token).await
+}
+
+async fn send(
+    app: &Router,
+    mut request: Builder,
+    body: Body,
+    token: Option<&str>,
+) -> (StatusCode, Value) {
+    if let Some(token) = token {
+        request = request.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let response = app
+        .clone()
+        .oneshot(request.body(body).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let challenged = response.headers().contains_key(header::WWW_AUTHENTICATE);
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    // Only credential problems are challenged; a caller denied by its role is not
+    let challenge = status == StatusCode::UNAUTHORIZED || body["code"] == "INSUFFICIENT_SCOPE";
+    assert_eq!(challenged, challenge);
+    (status, body)
+}
+
+#[tokio::test]
+async fn test_todo_routes_require_a_token_from_the_issuer() {
+    // Arrange
+    let issuer = start_provider().await;
+    let authenticator = JwtAuthenticator::discover(&issuer, "todo-api")
+        .await
+        .unwrap();
+    let state =
+        AppState::new(TodoBackend::Memory.repository()).with_authenticator(Arc::new(authenticator));
+    let app = router(Arc::new(state));
+
+    // Act
+    let (missing, missing_body) = get_with(&app, "/todos", None).await;
+    let (wrong_audience, wrong_audience_body) =
+        get_with(&app, "/todos", Some(&token(&issuer, "billing-api"))).await;
+    let (valid, valid_body) = get_with(&app, "/todos", Some(&token(&issuer, "todo-api"))).await;
+    let (health, _) = get_with(&app, "/healthz", None).await;
+
+    // Assert
+    assert_eq!(missing, StatusCode::UNAUTHORIZED);
+    assert_eq!(missing_body["code"], "MISSING_CREDENTIALS");
+    assert_eq!(wrong_audience, StatusCode::UNAUTHORIZED);
+    assert_eq!(wrong_audience_body["code"], "INVALID_TOKEN");
+    assert_eq!(valid, StatusCode::OK);
+    assert_eq!(valid_body, json!([]));
+    assert_eq!(health, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_discovery_fails_without_a_discovery_document() {
+    let issuer = start_provider().await;
+
+    let discovered = JwtAuthenticator::discover(&format!("{}/tenant", issuer), "todo-api").await;
+
+    assert!(matches!(discovered, Err(AuthError::Unavailable(_))));
+}
+
+#[tokio::test]
+async fn test_read_only_api_keys_cannot_change_todos() {
+    // Arrange
+    let keys: Arc<dyn ApiKeyRepository> = Arc::new(InMemoryApiKeyRepository::new());
+    let issued = IssueApiKeyHandler::new(keys.clone())
+        .issue("dashboard".to_string(), ApiKeyScope::ReadOnly)
+        .await
+        .unwrap();
+    let state = AppState::new(TodoBackend::Memory.repository())
+        .with_authenticator(Arc::new(ApiKeyAuthenticator::new(keys)));
+    let app = router(Arc::new(state));
+    let create = Request::post("/todos").header(header::CONTENT_TYPE, "application/json");
+    let body = Body::from(r#"{"description": "Write docs"}"#);
+
+    // Act
+    let (read, _) = get_with(&app, "/todos", Some(&issued.token)).await;
+    let (create_status, create_body) = send(&app, create, body, Some(&issued.token)).await;
+
+    // Assert
+    assert_eq!(read, StatusCode::OK);
+    assert_eq!(create_status, StatusCode::FORBIDDEN);
+    assert_eq!(create_body["code"], "INSUFFICIENT_SCOPE");
+}
+
+#[tokio::test]
+async fn test_roles_restrict_what_callers_may_do() {
+    // Arrange
+    let keys: Arc<dyn ApiKeyRepository> = Arc::new(InMemoryApiKeyRepository::new());
+    let issue = IssueApiKeyHandler::new(keys.clone());
+    let editor = issue
+        .issue("sync".to_string(), ApiKeyScope::ReadWrite)
+        .await
+        .unwrap();
+    let stranger = issue
+        .issue("unknown".to_string(), ApiKeyScope::ReadWrite)
+        .await
+        .unwrap();
+    let memberships = Arc::new(InMemoryMembershipRepository::new());
+    let subject = format!("api-key:{}", editor.key.id);
+    memberships
+        .save(&Membership::new(
+            TenantId::new("default").unwrap(),
+            subject,
+            Role::Editor,
+        ))
+        .await
+        .unwrap();
+    let state = AppState::new(TodoBackend::Memory.repository())
+        .with_authenticator(Arc::new(ApiKeyAuthenticator::new(keys)))
+        .with_access_control(Arc::new(AccessControl::new(memberships)));
+    let app = router(Arc::new(state));
+    let create = Request::post("/todos").header(header::CONTENT_TYPE, "application/json");
+    let body = Body::from(r#"{"description": "Write docs"}"#);
+
+    // Act
+    let (created, todo) = send(&app, create, body, Some(&editor.token)).await;
+    let delete = Request::delete(format!("/todos/{}", todo["id"].as_str().unwrap()));
+    let (deleted, deleted_body) = send(&app, delete, Body::empty(), Some(&editor.token)).await;
+    let (listed, listed_body) = get_with(&app, "/todos", Some(&stranger.token)).await;
+
+    // Assert
+    assert_eq!(created, StatusCode::CREATED);
+    assert_eq!(deleted, StatusCode::FORBIDDEN);
+    assert_eq!(deleted_body["code"], "FORBIDDEN");
+    assert_eq!(listed, StatusCode::FORBIDDEN);
+    assert_eq!(listed_body["code"], "FORBIDDEN");
+}
+
+#[tokio::test]
+async fn test_callers_only_reach_the_todos_of_their_list() {
+    // Arrange
+    let memberships = Arc::new(InMemoryMembershipRepository::new());
+    for (list, subject) in [("team", "alice"), ("other", "bob")] {
+        let list = TenantId::new(list).unwrap();
+        memberships
+            .save(&Membership::new(list, subject, Role::Editor))
+            .await
+            .unwrap();
+    }
+    let authenticator = JwtAuthenticator::with_secret(SECRET).with_audience("todo-api");
+    let state = AppState::new(TodoBackend::Memory.repository())
+        .with_authenticator(Arc::new(authenticator))
+        .with_access_control(Arc::new(AccessControl::new(memberships)));
+    let app = router(Arc::new(state));
+    let alice = tenant_token("alice", "team");
+    let bob = tenant_token("bob", "other");
+    let create = Request::post("/todos").header(header::CONTENT_TYPE, "application/json");
+    let body = Body::from(r#"{"description": "Write docs"}"#);
+    let (_, todo) = send(&app, create, body, Some(&bob)).await;
+    let uri = format!("/todos/{}", todo["id"].as_str().unwrap());
+
+    // Act
+    let (read, _) = get_with(&app, &uri, Some(&alice)).await;
+    let change =
+        Request::put(format!("{}/state", uri)).header(header::CONTENT_TYPE, "application/json");
+    let body = Body::from(r#"{"state": "IN_PROGRESS"}"#);
+    let (changed, _) = send(&app, change, body, Some(&alice)).await;
+    let (listed, listed_body) = get_with(&app, "/todos", Some(&alice)).await;
+    let (owned, owned_body) = get_with(&app, &uri, Some(&bob)).await;
+
+    // Assert
+    assert_eq!(read, StatusCode::NOT_FOUND);
+    assert_eq!(changed, StatusCode::NOT_FOUND);
+    assert_eq!(listed, StatusCode::OK);
+    assert_eq!(listed_body, json!([]));
+    assert_eq!(owned, StatusCode::OK);
+    assert_eq!(owned_body, todo);
+}
+
+/// A router over an event store and a trash, whose callers are scoped to their list, with
+/// a todo `bob` created and deleted on the `other` list
+async fn deleted_todo_of_another_list() -> (Router, String) {
+    let authenticator = JwtAuthenticator::with_secret(SECRET).with_audience("todo-api");
+    let state = AppState::new(TodoBackend::Memory.repository())
+        .with_authenticator(Arc::new(authenticator))
+        .with_event_store(Arc::new(InMemoryEventStore::new()))
+        .with_trash(Arc::new(InMemoryTrashRepository::new()));
+    let app = router(Arc::new(state));
+    let bob = tenant_token("bob", "other");
+    let create = Request::post("/todos").header(header::CONTENT_TYPE, "application/json");
+    let body = Body::from(r#"{"description": "Write docs"}"#);
+    let (_, todo) = send(&app, create, body, Some(&bob)).await;
+    let id = todo["id"].as_str().unwrap().to_string();
+    let delete = Request::delete(format!("/todos/{}", id))
+        .header(header::AUTHORIZATION, format!("Bearer {}", bob))
+        .body(Body::empty())
+        .unwrap();
+    let deleted = app.clone().oneshot(delete).await.unwrap();
+    assert_eq!(deleted.status(), StatusCode::NO_CONTENT);
+    (app, id)
+}
+
+#[tokio::test]
+async fn test_callers_only_read_the_history_of_their_list() {
+    // Arrange
+    let (app, id) = deleted_todo_of_another_list().await;
+    let uri = format!("/todos/{}/history", id);
+
+    // Act
+    let (foreign, foreign_body) = get_with(&app, &uri, Some(&tenant_token("alice", "team"))).await;
+    let (owned, owned_body) = get_with(&app, &uri, Some(&tenant_token("bob", "other"))).await;
+
+    // Assert
+    assert_eq!(foreign, StatusCode::NOT_FOUND);
+    assert_eq!(foreign_body["code"], "TODO_NOT_FOUND");
+    assert_eq!(owned, StatusCode::OK);
+    assert_eq!(owned_body[0]["type"], "TODO_CREATED");
+    assert_eq!(owned_body[0]["id"], id.as_str());
+    assert_eq!(owned_body[1]["type"], "TODO_DELETED");
+}
+
+#[tokio::test]
+async fn test_callers_only_reach_the_trash_of_their_list() {
+    // Arrange
+    let (app, id) = deleted_todo_of_another_list().await;
+    let alice = tenant_token("alice", "team");
+    let bob = tenant_token("bob", "other");
+
+    // Act
+    let (listed, listed_body) = get_with(&app, "/trash", Some(&alice)).await;
+    let restore = Request::post(format!("/trash/{}/restore", id));
+    let (restored, _) = send(&app, restore, Body::empty(), Some(&alice)).await;
+    let purge = Request::delete(format!("/trash/{}", id));
+    let (purged, _) = send(&app, purge, Body::empty(), Some(&alice)).await;
+    let (alice_todos, alice_todos_body) = get_with(&app, "/todos", Some(&alice)).await;
+    let (owned, owned_body) = get_with(&app, "/trash", Some(&bob)).await;
+    let restore = Request::post(format!("/trash/{}/restore", id));
+    let (owner_restored, owner_restored_body) =
+        send(&app, restore, Body::empty(), Some(&bob)).await;
+
+    // Assert
+    assert_eq!(listed, StatusCode::OK);
+    assert_eq!(listed_body, json!([]));
+    assert_eq!(restored, StatusCode::NOT_FOUND);
+    assert_eq!(purged, StatusCode::NOT_FOUND);
+    assert_eq!(alice_todos, StatusCode::OK);
+    assert_eq!(alice_todos_body, json!([]));
+    assert_eq!(owned, StatusCode::OK);
+    assert_eq!(owned_body.as_array().unwrap().len(), 1);
+    assert_eq!(owned_body[0]["id"], id.as_str());
+    assert_eq!(owner_restored, StatusCode::OK);
+    assert_eq!(owner_restored_body["id"], id.as_str());
+}
+
+#[tokio::test]
+async fn test_each_list_gets_its_own_quota() {
+    // Arrange
+    let limit = |max_todos| Quota {
+        max_open_todos: None,
+        max_todos: Some(max_todos),
+    };
+    let policy = QuotaPolicy::new(limit(1)).with_list(TenantId::new("team").unwrap(), limit(2));
+    let authenticator = JwtAuthenticator::with_secret(SECRET).with_audience("todo-api");
+    let state = AppState::new(TodoBackend::Memory.repository())
+        .with_authenticator(Arc::new(authenticator))
+        .with_quota(policy);
+    let app = router(Arc::new(state));
+    let alice = tenant_token("alice", "team");
+    let bob = tenant_token("bob", "other");
+    let create = |token: &str| {
+        let request = Request::post("/todos")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(header::AUTHORIZATION, format!("Bearer {}", token));
+        (request, Body::from(r#"{"description": "Write docs"}"#))
+    };
+
+    // Act
+    let mut alice_created = Vec::new();
+    let mut bob_created = Vec::new();
+    for _ in 0..2 {
+        let (request, body) = create(&alice);
+        alice_created.push(send(&app, request, body, None).await.0);
+        let (request, body) = create(&bob);
+        bob_created.push(send(&app, request, body, None).await.0);
+    }
+    let (_, alice_usage) = get_with(&app, "/quota", Some(&alice)).await;
+    let (_, bob_usage) = get_with(&app, "/quota", Some(&bob)).await;
+
+    // Assert
+    assert_eq!(
+        alice_created,
+        vec![StatusCode::CREATED, StatusCode::CREATED]
+    );
+    assert_eq!(bob_created, vec![StatusCode::CREATED, StatusCode::CONFLICT]);
+    assert_eq!(alice_usage["max_todos"], 2);
+    assert_eq!(alice_usage["remaining"], 0);
+    assert_eq!(bob_usage["max_todos"], 1);
+    assert_eq!(bob_usage["todos"], 1);
+}
@@ -0,0 +1,27 @@
+use jsonwebtoken::{EncodingKey, Header, encode};
+use serde_json::json;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Secret the test tokens are signed with
+pub const SECRET: &[u8] = b"provider-secret";
+
+/// A read and write token of `subject`, acting for `tenant`, signed with `SECRET`
+pub fn tenant_token(subject: &str, tenant: &str) -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let claims = json!({
+        "sub": subject,
+        "aud": "todo-api",
+        "exp": now + 300,
+        "scope": "todos:read todos:write",
+        "tenant": tenant,
+    });
+    encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(SECRET),
+    )
+    .unwrap()
+}
@@ -0,0 +1,49 @@
+use chrono::{TimeZone, Utc};
+use server_todo::DigestJob;
+use server_todo::scheduler::ScheduledJob;
+use std::sync::Arc;
+use todo::application::add_todo_handler::AddTodoHandler;
+use todo::application::digest::DigestHandler;
+use todo::infrastructure::event_stores::InMemoryEventStore;
+use todo::infrastructure::mailers::InMemoryMailer;
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::{Clock, FixedClock};
+
+#[tokio::test]
+async fn test_digest_job_mails_the_last_week_and_keeps_the_digest() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let store = Arc::new(InMemoryEventStore::new());
+    let clock = Arc::new(FixedClock::new(
+        Utc.with_ymd_and_hms(2024, 1, 10, 0, 0, 0).unwrap(),
+    ));
+    AddTodoHandler::new(repository.clone())
+        .with_clock(clock.clone())
+        .with_event_sink(store.clone())
+        .new_todo("Write docs".to_string())
+        .await
+        .unwrap();
+    clock.set(Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap());
+    let mailer = Arc::new(InMemoryMailer::new());
+    let handler = DigestHandler::new(repository, store)
+        .with_clock(clock.clone())
+        .with_mailer(
+            mailer.clone(),
+            "todo@example.com".to_string(),
+            vec!["team@example.com".to_string()],
+        );
+    let job = DigestJob::new(handler);
+    assert_eq!(job.last_digest(), None);
+
+    // Act
+    job.run().await.unwrap();
+
+    // Assert
+    let digest = job.last_digest().unwrap();
+    assert_eq!(digest.end, clock.now());
+    assert_eq!(digest.created.len(), 1);
+    let sent = mailer.sent();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].to, vec!["team@example.com".to_string()]);
+    assert_eq!(sent[0].text, digest.to_markdown());
+}
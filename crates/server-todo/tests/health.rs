@@ -0,0 +1,60 @@
+use axum::Router;
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use http_body_util::BodyExt;
+use serde_json::{Value, json};
+use server_todo::{AppState, router};
+use std::sync::Arc;
+use todo::infrastructure::repositories::todo::TodoBackend;
+use tower::ServiceExt;
+
+async fn get(app: Router, uri: &str) -> (StatusCode, Value) {
+    let response = app
+        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+async fn test_healthz_and_readyz_pass() {
+    let app = router(Arc::new(AppState::new(TodoBackend::Memory.repository())));
+
+    let (status, body) = get(app.clone(), "/healthz").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "status": "pass" }));
+
+    let (status, body) = get(app, "/readyz").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body,
+        json!({
+            "status": "pass",
+            "checks": {
+                "repository": { "status": "pass" },
+                "event_bus": { "status": "pass", "backlog": 0, "capacity": 256 },
+            },
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_readyz_fails_when_the_repository_cannot_be_read() {
+    // Arrange: a directory cannot be read as a todo file
+    let backend = TodoBackend::File(std::env::temp_dir());
+    let app = router(Arc::new(AppState::new(backend.repository())));
+
+    // Act
+    let (ready_status, ready) = get(app.clone(), "/readyz").await;
+    let (live_status, _) = get(app, "/healthz").await;
+
+    // Assert
+    assert_eq!(ready_status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(ready["status"], "fail");
+    assert_eq!(ready["checks"]["repository"]["status"], "fail");
+    assert!(ready["checks"]["repository"]["message"].is_string());
+    assert_eq!(ready["checks"]["event_bus"]["status"], "pass");
+    assert_eq!(live_status, StatusCode::OK);
+}
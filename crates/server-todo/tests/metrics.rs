@@ -0,0 +1,50 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use http_body_util::BodyExt;
+use serde_json::json;
+use server_todo::{AppState, metrics, router};
+use std::sync::Arc;
+use todo::infrastructure::repositories::todo::TodoBackend;
+use tower::ServiceExt;
+
+#[tokio::test]
+async fn test_metrics_expose_handler_counters() {
+    // Arrange
+    let handle = metrics::install().unwrap();
+    let app = router(Arc::new(
+        AppState::new(TodoBackend::Memory.repository()).with_metrics(handle),
+    ));
+    let create = Request::post("/todos")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            json!({ "description": "Measured todo" }).to_string(),
+        ))
+        .unwrap();
+    app.clone().oneshot(create).await.unwrap();
+
+    // Act
+    let response = app
+        .oneshot(Request::get("/metrics").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let text = String::from_utf8(bytes.to_vec()).unwrap();
+    assert!(text.contains("todo_todos_created_total 1"));
+    assert!(text.contains("todo_handler_duration_seconds"));
+    assert!(text.contains("command=\"add_todo\""));
+}
+
+#[tokio::test]
+async fn test_metrics_are_not_found_without_a_recorder() {
+    let app = router(Arc::new(AppState::new(TodoBackend::Memory.repository())));
+
+    let response = app
+        .oneshot(Request::get("/metrics").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,37 @@
+use chrono::{TimeDelta, TimeZone, Utc};
+use server_todo::RetentionJob;
+use server_todo::scheduler::ScheduledJob;
+use std::sync::Arc;
+use todo::application::retention::{ApplyRetentionHandler, RetentionPolicy, RetentionRules};
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::{FixedClock, Todo, TodoRepository, TodoState};
+
+#[tokio::test]
+async fn test_retention_job_keeps_the_report_of_its_last_run() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let created_at = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let clock = FixedClock::new(created_at);
+    let (mut todo, _) = Todo::new_with_clock("Old".to_string(), &clock).unwrap();
+    todo.update_state(TodoState::InProgress).unwrap();
+    todo.update_state(TodoState::Done).unwrap();
+    repository.save(&todo).await.unwrap();
+    clock.set(created_at + TimeDelta::days(8));
+    let policy = RetentionPolicy::new(RetentionRules {
+        done_todo_days: Some(7),
+        event_months: None,
+        trash_days: None,
+    });
+    let handler =
+        ApplyRetentionHandler::new(repository.clone(), policy).with_clock(Arc::new(clock));
+    let job = RetentionJob::new(handler);
+    assert_eq!(job.last_report(), None);
+
+    // Act
+    job.run().await.unwrap();
+
+    // Assert
+    let report = job.last_report().unwrap();
+    assert_eq!(report.deleted_todos, vec![todo.id.clone()]);
+    assert!(repository.find_all().await.unwrap().is_empty());
+}
@@ -0,0 +1,145 @@
+use async_trait::async_trait;
+use chrono::{DateTime, TimeZone, Utc};
+use server_todo::scheduler::{RunOutcome, ScheduledJob};
+use server_todo::{CronSchedule, Scheduler};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+fn at(year: i32, month: u32, day: u32, hour: u32, minute: u32, second: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(year, month, day, hour, minute, second)
+        .unwrap()
+}
+
+fn next(expression: &str, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    CronSchedule::parse(expression).unwrap().next_after(after)
+}
+
+#[test]
+fn test_cron_schedule_finds_the_next_matching_time() {
+    let after = at(2024, 1, 31, 23, 59, 30);
+
+    // Every minute, on the minute
+    assert_eq!(next("* * * * *", after), Some(at(2024, 2, 1, 0, 0, 0)));
+    // Every 15 seconds
+    assert_eq!(
+        next("*/15 * * * * *", after),
+        Some(at(2024, 1, 31, 23, 59, 45))
+    );
+    // 03:30 on weekdays; 2024-02-01 is a Thursday
+    assert_eq!(
+        next("30 3 * * MON-FRI", after),
+        Some(at(2024, 2, 1, 3, 30, 0))
+    );
+    // Sundays, as 7; 2024-02-04 is a Sunday
+    assert_eq!(next("0 0 * * 7", after), Some(at(2024, 2, 4, 0, 0, 0)));
+    // Leap day
+    assert_eq!(
+        next("0 12 29 FEB *", after),
+        Some(at(2024, 2, 29, 12, 0, 0))
+    );
+    // Either day field matches when both are restricted: the 15th or a Monday
+    assert_eq!(next("0 0 15 * 1", after), Some(at(2024, 2, 5, 0, 0, 0)));
+    assert_eq!(next("@monthly", after), Some(at(2024, 2, 1, 0, 0, 0)));
+    assert_eq!(next("0 0 1,15 */3 *", after), Some(at(2024, 4, 1, 0, 0, 0)));
+    // Strictly after
+    assert_eq!(
+        next("0 0 1 2 *", at(2024, 2, 1, 0, 0, 0)),
+        Some(at(2025, 2, 1, 0, 0, 0))
+    );
+    // Never
+    assert_eq!(next("0 0 30 2 *", after), None);
+}
+
+#[test]
+fn test_cron_schedule_rejects_invalid_expressions() {
+    for (expression, error) in [
+        ("* * * *", "expected 5 or 6 fields, found 4"),
+        ("60 * * * *", "minute 60 is not within 0-59"),
+        ("* * 0 * *", "day of month 0 is not within 1-31"),
+        ("* * * FOO *", "invalid month \"FOO\""),
+        ("*/0 * * * *", "invalid minute step \"0\""),
+        ("5-1 * * * *", "invalid minute range \"5-1\""),
+    ] {
+        assert_eq!(
+            CronSchedule::parse(expression),
+            Err(format!(
+                "invalid cron expression {:?}: {}",
+                expression, error
+            ))
+        );
+    }
+}
+
+/// Counts its runs, taking `duration` over each and failing when `fail` is set
+struct CountingJob {
+    runs: AtomicUsize,
+    duration: Duration,
+    fail: bool,
+}
+
+impl CountingJob {
+    fn new(duration: Duration, fail: bool) -> Arc<Self> {
+        Arc::new(CountingJob {
+            runs: AtomicUsize::new(0),
+            duration,
+            fail,
+        })
+    }
+}
+
+#[async_trait]
+impl ScheduledJob for CountingJob {
+    async fn run(&self) -> Result<(), String> {
+        self.runs.fetch_add(1, Ordering::SeqCst);
+        tokio::time::sleep(self.duration).await;
+        if self.fail {
+            return Err("purge failed".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_scheduler_runs_jobs_and_records_their_runs() {
+    // Arrange
+    let every_second = CronSchedule::parse("* * * * * *").unwrap();
+    let quick = CountingJob::new(Duration::ZERO, false);
+    let failing = CountingJob::new(Duration::ZERO, true);
+    // Takes longer than the interval, so every other run finds it still going
+    let slow = CountingJob::new(Duration::from_millis(1500), false);
+    let scheduler = Arc::new(
+        Scheduler::new()
+            .with_job("quick", every_second.clone(), quick.clone())
+            .with_job("failing", every_second.clone(), failing.clone())
+            .with_job("slow", every_second, slow.clone())
+            .with_jitter(Duration::from_millis(50)),
+    );
+
+    // Act
+    let handles = scheduler.start();
+    tokio::time::sleep(Duration::from_millis(3500)).await;
+    handles.iter().for_each(|handle| handle.abort());
+    let history = scheduler.history();
+
+    // Assert
+    let outcomes = |job: &str| -> Vec<RunOutcome> {
+        history
+            .iter()
+            .filter(|run| run.job == job)
+            .map(|run| run.outcome.clone())
+            .collect()
+    };
+    assert!(quick.runs.load(Ordering::SeqCst) >= 3);
+    assert!(
+        outcomes("quick")
+            .iter()
+            .all(|outcome| *outcome == RunOutcome::Succeeded)
+    );
+    assert!(outcomes("failing").contains(&RunOutcome::Failed("purge failed".to_string())));
+    assert!(outcomes("slow").contains(&RunOutcome::Skipped));
+    assert!(slow.runs.load(Ordering::SeqCst) < quick.runs.load(Ordering::SeqCst));
+    for run in &history {
+        assert!(run.finished_at >= run.started_at);
+    }
+}
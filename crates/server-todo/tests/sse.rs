@@ -0,0 +1,124 @@
+mod common;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use common::{SECRET, tenant_token};
+use http_body_util::BodyExt;
+use server_todo::{AppState, router};
+use std::sync::Arc;
+use std::time::Duration;
+use todo::infrastructure::jwt_authenticator::JwtAuthenticator;
+use todo::infrastructure::repositories::todo::TodoBackend;
+use tower::ServiceExt;
+
+async fn create_todo(app: &axum::Router, description: &str) {
+    create_todo_as(app, description, None).await;
+}
+
+async fn create_todo_as(app: &axum::Router, description: &str, token: Option<&str>) {
+    let mut request = Request::post("/todos").header(header::CONTENT_TYPE, "application/json");
+    if let Some(token) = token {
+        request = request.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let request = request
+        .body(Body::from(format!(
+            r#"{{"description":"{}"}}"#,
+            description
+        )))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+}
+
+/// Reads the stream until `count` events arrived, returning their `id` lines
+async fn read_event_ids(body: &mut Body, count: usize) -> Vec<String> {
+    let mut text = String::new();
+    let mut ids = Vec::new();
+    while ids.len() < count {
+        let frame = tokio::time::timeout(Duration::from_secs(5), body.frame())
+            .await
+            .expect("event not received")
+            .unwrap()
+            .unwrap();
+        if let Ok(data) = frame.into_data() {
+            text.push_str(std::str::from_utf8(&data).unwrap());
+        }
+        ids = text
+            .lines()
+            .filter_map(|line| line.strip_prefix("id: "))
+            .map(str::to_string)
+            .collect();
+    }
+    assert!(text.contains("event: todo"));
+    ids
+}
+
+#[tokio::test]
+async fn test_events_replay_from_cursor_then_follow_live() {
+    // Arrange
+    let app = router(Arc::new(AppState::new(TodoBackend::Memory.repository())));
+    create_todo(&app, "First").await;
+    create_todo(&app, "Second").await;
+
+    // Act
+    let request = Request::get("/events?cursor=1")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let mut body = response.into_body();
+    let replayed = read_event_ids(&mut body, 1).await;
+    create_todo(&app, "Third").await;
+    let followed = read_event_ids(&mut body, 1).await;
+
+    // Assert
+    assert_eq!(replayed, vec!["2"]);
+    assert_eq!(followed, vec!["3"]);
+}
+
+#[tokio::test]
+async fn test_events_resume_from_last_event_id() {
+    // Arrange
+    let app = router(Arc::new(AppState::new(TodoBackend::Memory.repository())));
+    create_todo(&app, "First").await;
+    create_todo(&app, "Second").await;
+    create_todo(&app, "Third").await;
+
+    // Act
+    let request = Request::get("/events")
+        .header("Last-Event-ID", "1")
+        .body(Body::empty())
+        .unwrap();
+    let mut body = app.oneshot(request).await.unwrap().into_body();
+
+    // Assert
+    assert_eq!(read_event_ids(&mut body, 2).await, vec!["2", "3"]);
+}
+
+#[tokio::test]
+async fn test_events_only_stream_the_callers_list() {
+    // Arrange
+    let authenticator = JwtAuthenticator::with_secret(SECRET).with_audience("todo-api");
+    let app = router(Arc::new(
+        AppState::new(TodoBackend::Memory.repository()).with_authenticator(Arc::new(authenticator)),
+    ));
+    let alice = tenant_token("alice", "team");
+    let bob = tenant_token("bob", "other");
+    create_todo_as(&app, "Bob's plan", Some(&bob)).await;
+    create_todo_as(&app, "Alice's plan", Some(&alice)).await;
+
+    // Act
+    let request = Request::get("/events?cursor=0")
+        .header(header::AUTHORIZATION, format!("Bearer {}", alice))
+        .body(Body::empty())
+        .unwrap();
+    let mut body = app.clone().oneshot(request).await.unwrap().into_body();
+    let replayed = read_event_ids(&mut body, 1).await;
+    create_todo_as(&app, "Bob's review", Some(&bob)).await;
+    create_todo_as(&app, "Alice's review", Some(&alice)).await;
+    let followed = read_event_ids(&mut body, 1).await;
+
+    // Assert
+    assert_eq!(replayed, vec!["2"]);
+    assert_eq!(followed, vec!["4"]);
+}
@@ -0,0 +1,57 @@
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use opentelemetry::global;
+use opentelemetry::trace::TracerProvider;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider};
+use serde_json::json;
+use server_todo::{AppState, router};
+use std::sync::Arc;
+use todo::infrastructure::repositories::todo::TodoBackend;
+use tower::ServiceExt;
+use tracing_subscriber::layer::SubscriberExt;
+
+#[tokio::test]
+async fn test_request_spans_continue_the_incoming_trace() {
+    // Arrange
+    let exporter = InMemorySpanExporter::default();
+    let provider = SdkTracerProvider::builder()
+        .with_simple_exporter(exporter.clone())
+        .build();
+    let subscriber = tracing_subscriber::registry()
+        .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+    let _guard = tracing::subscriber::set_default(subscriber);
+    global::set_text_map_propagator(TraceContextPropagator::new());
+    let app = router(Arc::new(AppState::new(TodoBackend::Memory.repository())));
+    let request = Request::post("/todos")
+        .header("content-type", "application/json")
+        .header(
+            "traceparent",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+        )
+        .body(Body::from(
+            json!({ "description": "Traced todo" }).to_string(),
+        ))
+        .unwrap();
+
+    // Act
+    let response = app.oneshot(request).await.unwrap();
+
+    // Assert
+    assert_eq!(response.status(), StatusCode::CREATED);
+    provider.force_flush().unwrap();
+    let spans = exporter.get_finished_spans().unwrap();
+    let trace_id = "4bf92f3577b34da6a3ce929d0e0e4736";
+    let request_span = spans
+        .iter()
+        .find(|span| span.name == "http_request")
+        .unwrap();
+    assert_eq!(request_span.span_context.trace_id().to_string(), trace_id);
+    assert_eq!(request_span.parent_span_id.to_string(), "00f067aa0ba902b7");
+    let handler_span = spans.iter().find(|span| span.name == "new_todo").unwrap();
+    assert_eq!(handler_span.span_context.trace_id().to_string(), trace_id);
+    assert_eq!(
+        handler_span.parent_span_id,
+        request_span.span_context.span_id()
+    );
+}
@@ -0,0 +1,244 @@
+mod common;
+
+use axum::body::{Body, Bytes};
+use axum::http::{HeaderMap, Request, StatusCode, header};
+use axum::routing::post;
+use common::{SECRET, tenant_token};
+use http_body_util::BodyExt;
+use serde_json::{Value, json};
+use server_todo::webhooks::{SIGNATURE_HEADER, sign};
+use server_todo::{AppState, WebhookManager, router};
+use std::sync::Arc;
+use std::time::Duration;
+use todo::infrastructure::jwt_authenticator::JwtAuthenticator;
+use todo::infrastructure::repositories::todo::TodoBackend;
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tower::ServiceExt;
+
+/// Serves `POST /hook`, answering `status` and forwarding each request it receives
+async fn start_target(status: StatusCode) -> (String, mpsc::UnboundedReceiver<(HeaderMap, Bytes)>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    let (sender, receiver) = mpsc::unbounded_channel();
+    let target = axum::Router::new().route(
+        "/hook",
+        post(move |headers: HeaderMap, body: Bytes| {
+            let sender = sender.clone();
+            async move {
+                let _ = sender.send((headers, body));
+                status
+            }
+        }),
+    );
+    tokio::spawn(async move { axum::serve(listener, target).await });
+    (url, receiver)
+}
+
+fn app(manager: WebhookManager) -> axum::Router {
+    let state = AppState::new(TodoBackend::Memory.repository()).with_webhooks(Arc::new(manager));
+    router(Arc::new(state))
+}
+
+async fn send(app: &axum::Router, request: Request<Body>) -> (StatusCode, Value) {
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (
+        status,
+        serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+    )
+}
+
+fn post_json(uri: &str, body: Value) -> Request<Body> {
+    Request::post(uri)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+/// `request` with `token` as its bearer token
+fn as_caller(mut request: Request<Body>, token: &str) -> Request<Body> {
+    let bearer = format!("Bearer {}", token).parse().unwrap();
+    request.headers_mut().insert(header::AUTHORIZATION, bearer);
+    request
+}
+
+#[tokio::test]
+async fn test_webhooks_receive_signed_events_of_their_types() {
+    // Arrange
+    let (url, mut received) = start_target(StatusCode::NO_CONTENT).await;
+    let app = app(WebhookManager::new());
+    let registration = json!({ "url": url, "secret": "s3cret", "events": ["todo_created"] });
+    let (registered, webhook) = send(&app, post_json("/webhooks", registration)).await;
+
+    // Act
+    let (_, todo) = send(
+        &app,
+        post_json("/todos", json!({ "description": "Write docs" })),
+    )
+    .await;
+    let state_uri = format!("/todos/{}/state", todo["id"].as_str().unwrap());
+    let change = Request::put(state_uri)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(r#"{"state": "IN_PROGRESS"}"#))
+        .unwrap();
+    send(&app, change).await;
+    let (headers, body) = tokio::time::timeout(Duration::from_secs(5), received.recv())
+        .await
+        .expect("event not delivered")
+        .unwrap();
+    let (_, listed) = send(&app, Request::get("/webhooks").body(Body::empty()).unwrap()).await;
+
+    // Assert
+    assert_eq!(registered, StatusCode::CREATED);
+    assert!(webhook.get("secret").is_none());
+    assert_eq!(listed, json!([webhook]));
+    assert_eq!(
+        headers[header::CONTENT_TYPE],
+        "application/cloudevents+json"
+    );
+    assert_eq!(headers[SIGNATURE_HEADER], sign(b"s3cret", &body).as_str());
+    let event: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(event["type"], "com.hungknow.todo.created");
+    assert_eq!(event["subject"], todo["id"]);
+    // The state change is not one of the webhook's events
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(received.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn test_failed_deliveries_go_to_the_dead_letter_queue() {
+    // Arrange
+    let (url, mut received) = start_target(StatusCode::INTERNAL_SERVER_ERROR).await;
+    let app = app(WebhookManager::new().with_retries(2, Duration::from_millis(10)));
+    send(
+        &app,
+        post_json("/webhooks", json!({ "url": url, "secret": "s3cret" })),
+    )
+    .await;
+
+    // Act
+    send(
+        &app,
+        post_json("/todos", json!({ "description": "Write docs" })),
+    )
+    .await;
+    let mut dead_letters = Value::Null;
+    for _ in 0..50 {
+        let request = Request::get("/webhooks/dead-letters")
+            .body(Body::empty())
+            .unwrap();
+        (_, dead_letters) = send(&app, request).await;
+        if dead_letters
+            .as_array()
+            .is_some_and(|letters| !letters.is_empty())
+        {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    let retry = Request::post("/webhooks/dead-letters/retry")
+        .body(Body::empty())
+        .unwrap();
+    let (retried_status, retried) = send(&app, retry).await;
+
+    // Assert
+    assert_eq!(dead_letters[0]["url"], url.as_str());
+    assert_eq!(dead_letters[0]["attempts"], 2);
+    assert_eq!(
+        dead_letters[0]["error"],
+        "target answered 500 Internal Server Error"
+    );
+    assert_eq!(dead_letters[0]["event"]["id"], "1");
+    assert_eq!(retried_status, StatusCode::ACCEPTED);
+    assert_eq!(retried, json!({ "retried": 1 }));
+    for _ in 0..3 {
+        tokio::time::timeout(Duration::from_secs(5), received.recv())
+            .await
+            .expect("delivery not attempted");
+    }
+}
+
+#[tokio::test]
+async fn test_webhooks_need_an_http_url_and_a_secret() {
+    let app = app(WebhookManager::new());
+
+    let (bad_url, bad_url_body) = send(
+        &app,
+        post_json(
+            "/webhooks",
+            json!({ "url": "ftp://example.com", "secret": "s3cret" }),
+        ),
+    )
+    .await;
+    let (no_secret, _) = send(
+        &app,
+        post_json(
+            "/webhooks",
+            json!({ "url": "https://example.com", "secret": "" }),
+        ),
+    )
+    .await;
+    let (unknown, unknown_body) = send(
+        &app,
+        Request::delete("/webhooks/missing")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+
+    assert_eq!(bad_url, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(bad_url_body["code"], "INVALID_WEBHOOK");
+    assert_eq!(no_secret, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(unknown, StatusCode::NOT_FOUND);
+    assert_eq!(unknown_body["code"], "WEBHOOK_NOT_FOUND");
+}
+
+#[tokio::test]
+async fn test_webhooks_only_see_and_receive_their_own_list() {
+    // Arrange
+    let authenticator = JwtAuthenticator::with_secret(SECRET).with_audience("todo-api");
+    let state = AppState::new(TodoBackend::Memory.repository())
+        .with_authenticator(Arc::new(authenticator))
+        .with_webhooks(Arc::new(WebhookManager::new()));
+    let app = router(Arc::new(state));
+    let alice = tenant_token("alice", "team");
+    let bob = tenant_token("bob", "other");
+    let (alice_url, mut alice_received) = start_target(StatusCode::NO_CONTENT).await;
+    let (bob_url, mut bob_received) = start_target(StatusCode::NO_CONTENT).await;
+    let registration =
+        |url: &str| post_json("/webhooks", json!({ "url": url, "secret": "s3cret" }));
+    let (_, alice_webhook) = send(&app, as_caller(registration(&alice_url), &alice)).await;
+    send(&app, as_caller(registration(&bob_url), &bob)).await;
+    let list = || Request::get("/webhooks").body(Body::empty()).unwrap();
+
+    // Act
+    let (_, todo) = send(
+        &app,
+        as_caller(
+            post_json("/todos", json!({ "description": "Alice's plan" })),
+            &alice,
+        ),
+    )
+    .await;
+    let (_, delivered) = tokio::time::timeout(Duration::from_secs(5), alice_received.recv())
+        .await
+        .expect("event not delivered")
+        .unwrap();
+    let (_, bob_listed) = send(&app, as_caller(list(), &bob)).await;
+    let alice_uri = format!("/webhooks/{}", alice_webhook["id"].as_str().unwrap());
+    let delete = Request::delete(alice_uri).body(Body::empty()).unwrap();
+    let (deleted, _) = send(&app, as_caller(delete, &bob)).await;
+    let (_, alice_listed) = send(&app, as_caller(list(), &alice)).await;
+
+    // Assert
+    let event: Value = serde_json::from_slice(&delivered).unwrap();
+    assert_eq!(event["subject"], todo["id"]);
+    assert_eq!(bob_listed.as_array().unwrap().len(), 1);
+    assert_eq!(bob_listed[0]["url"], bob_url.as_str());
+    assert_eq!(deleted, StatusCode::NOT_FOUND);
+    assert_eq!(alice_listed, json!([alice_webhook]));
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(bob_received.try_recv().is_err());
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use axum::body::Body;
+use axum::http::{Request, header};
+use common::{SECRET, tenant_token};
+use futures_util::StreamExt;
+use serde_json::{Value, json};
+use server_todo::{AppState, router};
+use std::sync::Arc;
+use todo::infrastructure::jwt_authenticator::JwtAuthenticator;
+use todo::infrastructure::repositories::todo::TodoBackend;
+use tokio::net::TcpListener;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tower::ServiceExt;
+
+fn create_request(description: &str, token: Option<&str>) -> Request<Body> {
+    let mut request = Request::post("/todos").header(header::CONTENT_TYPE, "application/json");
+    if let Some(token) = token {
+        request = request.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    request
+        .body(Body::from(
+            json!({ "description": description }).to_string(),
+        ))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_websocket_receives_events() {
+    // Arrange
+    let state = Arc::new(AppState::new(TodoBackend::Memory.repository()));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(axum::serve(listener, router(state.clone())).into_future());
+    let (mut socket, _) = connect_async(format!("ws://{}/ws", addr)).await.unwrap();
+
+    // Act
+    router(state)
+        .oneshot(create_request("Write docs", None))
+        .await
+        .unwrap();
+    let message = socket.next().await.unwrap().unwrap();
+
+    // Assert
+    let event: Value = serde_json::from_str(message.to_text().unwrap()).unwrap();
+    assert_eq!(event["type"], "TODO_CREATED");
+    assert_eq!(event["description"], "Write docs");
+}
+
+#[tokio::test]
+async fn test_websocket_only_receives_the_events_of_the_callers_list() {
+    // Arrange
+    let authenticator = JwtAuthenticator::with_secret(SECRET).with_audience("todo-api");
+    let state = Arc::new(
+        AppState::new(TodoBackend::Memory.repository()).with_authenticator(Arc::new(authenticator)),
+    );
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(axum::serve(listener, router(state.clone())).into_future());
+    let alice = tenant_token("alice", "team");
+    let bob = tenant_token("bob", "other");
+    let mut upgrade = format!("ws://{}/ws", addr).into_client_request().unwrap();
+    upgrade.headers_mut().insert(
+        header::AUTHORIZATION,
+        format!("Bearer {}", alice).parse().unwrap(),
+    );
+    let (mut socket, _) = connect_async(upgrade).await.unwrap();
+
+    // Act
+    router(state.clone())
+        .oneshot(create_request("Bob's plan", Some(&bob)))
+        .await
+        .unwrap();
+    let created = router(state)
+        .oneshot(create_request("Alice's plan", Some(&alice)))
+        .await
+        .unwrap();
+    let message = socket.next().await.unwrap().unwrap();
+
+    // Assert
+    let location = created.headers()[header::LOCATION].to_str().unwrap();
+    let event: Value = serde_json::from_str(message.to_text().unwrap()).unwrap();
+    assert_eq!(event["type"], "TODO_CREATED");
+    assert_eq!(event["description"], "Alice's plan");
+    assert_eq!(
+        location,
+        format!("/todos/{}", event["id"].as_str().unwrap())
+    );
+}
@@ -0,0 +1,68 @@
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use futures::executor::block_on;
+use std::hint::black_box;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::{Todo, TodoRepository, TodoState};
+
+const TODOS: usize = 10_000;
+
+fn repository() -> InMemoryTodoRepository {
+    let repository = InMemoryTodoRepository::new();
+    for index in 0..TODOS {
+        let (todo, _) = Todo::new(format!(
+            "Todo number {} with a realistic description",
+            index
+        ))
+        .unwrap();
+        block_on(repository.save(&todo)).unwrap();
+    }
+    repository
+}
+
+fn find_all(c: &mut Criterion) {
+    let repository = repository();
+    c.bench_function("find_all 10k", |b| {
+        b.iter(|| black_box(block_on(repository.find_all()).unwrap()))
+    });
+}
+
+fn emit_events(c: &mut Criterion) {
+    let todos = block_on(repository().find_all()).unwrap();
+    c.bench_function("start 10k todos", |b| {
+        b.iter_batched(
+            || todos.clone(),
+            |mut todos| {
+                for todo in &mut todos {
+                    black_box(todo.update_state(TodoState::InProgress).unwrap());
+                }
+                todos
+            },
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+fn find_all_while_saving(c: &mut Criterion) {
+    let repository = Arc::new(repository());
+    let saving = Arc::new(AtomicBool::new(true));
+    let writer = {
+        let repository = repository.clone();
+        let saving = saving.clone();
+        std::thread::spawn(move || {
+            let (todo, _) = Todo::new("Todo saved while reading".to_string()).unwrap();
+            while saving.load(Ordering::Relaxed) {
+                block_on(repository.save(&todo)).unwrap();
+            }
+        })
+    };
+    c.bench_function("find_all 10k while saving", |b| {
+        b.iter(|| black_box(block_on(repository.find_all()).unwrap()))
+    });
+    saving.store(false, Ordering::Relaxed);
+    writer.join().unwrap();
+}
+
+criterion_group!(benches, find_all, emit_events, find_all_while_saving);
+criterion_main!(benches);
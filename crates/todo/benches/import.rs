@@ -0,0 +1,38 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use futures::executor::block_on;
+use std::hint::black_box;
+use todo::application::import_todos_handler::ImportTodosHandler;
+use todo::infrastructure::repositories::todo::FileTodoRepository;
+
+const TASKS: usize = 100_000;
+
+fn taskwarrior_export() -> String {
+    let tasks: Vec<String> = (0..TASKS)
+        .map(|index| {
+            format!(
+                r#"{{"uuid":"{}","description":"Task number {} with a realistic description","status":"pending","entry":"20240102T030405Z"}}"#,
+                uuid::Uuid::new_v4(),
+                index
+            )
+        })
+        .collect();
+    format!("[{}]", tasks.join(","))
+}
+
+fn import_taskwarrior(c: &mut Criterion) {
+    let export = taskwarrior_export();
+    let mut group = c.benchmark_group("import");
+    group.sample_size(10);
+    group.bench_function("taskwarrior 100k into file", |b| {
+        b.iter(|| {
+            let path = std::env::temp_dir().join(format!("todo-{}.json", uuid::Uuid::new_v4()));
+            let handler = ImportTodosHandler::new(FileTodoRepository::new(&path));
+            black_box(block_on(handler.import_taskwarrior(export.as_bytes())).unwrap());
+            std::fs::remove_file(path).unwrap();
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, import_taskwarrior);
+criterion_main!(benches);
@@ -0,0 +1,13 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(feature = "protobuf")]
+    {
+        let mut config = prost_build::Config::new();
+        config.protoc_executable(protoc_bin_vendored::protoc_bin_path()?);
+        let well_known_types = protoc_bin_vendored::include_path()?;
+        config.compile_protos(
+            &["proto/todo/v1/entities.proto"],
+            &["proto".into(), well_known_types],
+        )?;
+    }
+    Ok(())
+}
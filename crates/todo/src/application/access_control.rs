@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use crate::TenantId;
+use crate::application::auth::{AuthContext, AuthError};
+use crate::domain::access::{AccessPolicy, MembershipRepository, Role, TodoAction};
+
+/// List of the callers whose credentials name no tenant
+pub const DEFAULT_LIST: &str = "default";
+
+/// Decides whether an authenticated caller may take an action on a todo list
+///
+/// The list is the caller's tenant, or `default` for credentials without one. The caller's
+/// role on it is read from a MembershipRepository and checked against an `AccessPolicy`,
+/// the default one unless `with_policy` sets another. The REST middleware and the gRPC
+/// layer evaluate it after authentication, before the request reaches the handlers.
+pub struct AccessControl<R = Arc<dyn MembershipRepository>> {
+    membership_repository: R,
+    policy: AccessPolicy,
+}
+
+impl<R: MembershipRepository> AccessControl<R> {
+    pub fn new(membership_repository: R) -> Self {
+        Self {
+            membership_repository,
+            policy: AccessPolicy::default(),
+        }
+    }
+
+    /// Checks roles against `policy` instead of `AccessPolicy::default()`
+    pub fn with_policy(mut self, policy: AccessPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Checks that the caller of `context` may take `action` on their list
+    ///
+    /// # Returns
+    /// - `Ok(Role)`: The caller's role on the list
+    /// - `Err(AuthError::Forbidden)`: If the caller has no role on the list, or a role the
+    ///   policy does not let take `action`
+    /// - `Err(AuthError::Unavailable)`: If the roles cannot be read
+    pub async fn authorize(
+        &self,
+        context: &AuthContext,
+        action: TodoAction,
+    ) -> Result<Role, AuthError> {
+        let list = match &context.tenant {
+            Some(tenant) => tenant.clone(),
+            None => TenantId::new(DEFAULT_LIST).expect("the default list id is valid"),
+        };
+        let membership = self
+            .membership_repository
+            .find(&list, &context.subject)
+            .await
+            .map_err(|err| AuthError::Unavailable(err.to_string()))?
+            .ok_or_else(|| AuthError::Forbidden(format!("no role on list {}", list)))?;
+        if !self.policy.permits(membership.role, action) {
+            return Err(AuthError::Forbidden(format!(
+                "role {} does not permit {} on list {}",
+                membership.role.as_str(),
+                action.as_str(),
+                list
+            )));
+        }
+        Ok(membership.role)
+    }
+}
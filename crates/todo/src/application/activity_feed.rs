@@ -0,0 +1,341 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::application::messages::{Localizable, MessageArg, MessageCatalog};
+use crate::{EventSink, TenantId, TodoError, TodoEvent, TodoState};
+
+/// Activities an ActivityFeed keeps unless told otherwise
+const DEFAULT_RETENTION: usize = 1000;
+
+/// One entry of an ActivityFeed: a recorded event, the todo it is about, and who made the
+/// change on which list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Activity {
+    /// Position in the feed, starting at 1
+    pub sequence: u64,
+    /// The list of the todo, or `None` if the change was recorded without one
+    pub list: Option<TenantId>,
+    /// The subject who made the change, or `None` if the change was recorded without one
+    pub actor: Option<Arc<str>>,
+    /// The todo's description when the event was recorded, or its id if the feed never saw
+    /// it created
+    pub description: Arc<str>,
+    pub event: TodoEvent,
+}
+
+impl Activity {
+    /// Id of the todo the activity is about
+    pub fn todo_id(&self) -> &str {
+        self.event.todo_id()
+    }
+
+    /// When the event happened
+    pub fn occurred_at(&self) -> DateTime<Utc> {
+        self.event.occurred_at()
+    }
+
+    /// The activity in `locale`, relative to `now`, such as `Completed "Write docs" 2 hours
+    /// ago`
+    pub fn describe(&self, catalog: &MessageCatalog, locale: &str, now: DateTime<Utc>) -> String {
+        let time = catalog.message(locale, &RelativeTime(now - self.occurred_at()));
+        catalog.message(
+            locale,
+            &ActivityMessage {
+                activity: self,
+                time,
+            },
+        )
+    }
+}
+
+/// Which activities of an ActivityFeed a page covers; the default covers every activity
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ActivityFilter {
+    /// Only the activities of this list
+    pub list: Option<TenantId>,
+    /// Only the activities of changes this subject made
+    pub actor: Option<Arc<str>>,
+}
+
+impl ActivityFilter {
+    /// Whether `activity` is covered
+    pub fn matches(&self, activity: &Activity) -> bool {
+        self.list
+            .as_ref()
+            .is_none_or(|list| activity.list.as_ref() == Some(list))
+            && self
+                .actor
+                .as_ref()
+                .is_none_or(|actor| activity.actor.as_ref() == Some(actor))
+    }
+}
+
+/// A page of an ActivityFeed, newest first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActivityPage {
+    pub activities: Vec<Activity>,
+    /// Cursor of the next, older page, or `None` on the last page
+    pub next_cursor: Option<u64>,
+}
+
+/// Projection of the event stream into a human-readable activity feed
+///
+/// The feed is an `EventSink`: handlers given it as their sink, or a front end passing it
+/// the events it publishes, keep it up to date one change at a time. Events carry neither
+/// the caller nor the todo list, so a front end serving many lists passes them along with
+/// `record_change`, and pages through one list or one caller's changes with `page_where`.
+/// The feed remembers each todo's description from its `TodoCreated` event, so later state
+/// changes can name the todo. Only the last 1000 activities are kept unless
+/// `with_retention` says otherwise.
+pub struct ActivityFeed {
+    retention: usize,
+    state: Mutex<FeedState>,
+}
+
+#[derive(Default)]
+struct FeedState {
+    next_sequence: u64,
+    activities: VecDeque<Activity>,
+    /// Description of each todo, by list and todo id
+    descriptions: HashMap<(Option<TenantId>, Arc<str>), Arc<str>>,
+}
+
+impl ActivityFeed {
+    /// Creates a new, empty ActivityFeed
+    pub fn new() -> Self {
+        Self::with_retention(DEFAULT_RETENTION)
+    }
+
+    /// Creates a new, empty ActivityFeed keeping the last `retention` activities
+    pub fn with_retention(retention: usize) -> Self {
+        ActivityFeed {
+            retention,
+            state: Mutex::new(FeedState {
+                next_sequence: 1,
+                ..FeedState::default()
+            }),
+        }
+    }
+
+    /// Returns up to `limit` activities older than `cursor`, newest first
+    ///
+    /// # Parameters
+    /// - `cursor`: The `next_cursor` of the previous page, or `None` for the newest page
+    pub fn page(&self, cursor: Option<u64>, limit: usize) -> ActivityPage {
+        self.page_where(&ActivityFilter::default(), cursor, limit)
+    }
+
+    /// Returns up to `limit` activities `filter` covers older than `cursor`, newest first
+    ///
+    /// # Parameters
+    /// - `cursor`: The `next_cursor` of the previous page, or `None` for the newest page
+    pub fn page_where(
+        &self,
+        filter: &ActivityFilter,
+        cursor: Option<u64>,
+        limit: usize,
+    ) -> ActivityPage {
+        let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut older = state.activities.iter().rev().filter(|activity| {
+            cursor.is_none_or(|cursor| activity.sequence < cursor) && filter.matches(activity)
+        });
+        let activities: Vec<Activity> = older.by_ref().take(limit).cloned().collect();
+        let next_cursor = match older.next() {
+            Some(_) => activities.last().map(|activity| activity.sequence),
+            None => None,
+        };
+        ActivityPage {
+            activities,
+            next_cursor,
+        }
+    }
+
+    /// Whether the feed still holds an activity of `event`
+    pub fn contains(&self, event: &TodoEvent) -> bool {
+        let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state
+            .activities
+            .iter()
+            .any(|activity| activity.event == *event)
+    }
+
+    /// Removes the activities about the todo with `id` on `list`, or recorded without a list
+    /// when `None`, and the description the feed keeps for it
+    ///
+    /// # Returns
+    /// - The number of activities removed
+    pub fn forget(&self, list: Option<&TenantId>, id: &str) -> usize {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.descriptions.remove(&(list.cloned(), id.into()));
+        let count = state.activities.len();
+        state
+            .activities
+            .retain(|activity| activity.list.as_ref() != list || activity.todo_id() != id);
+        count - state.activities.len()
+    }
+
+    /// Records the events of one change `actor` made on `list`, each `None` if unknown
+    pub fn record_change(
+        &self,
+        list: Option<&TenantId>,
+        actor: Option<&str>,
+        events: &[TodoEvent],
+    ) -> Result<(), TodoError> {
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|_| TodoError::RepositoryError("activity feed lock poisoned".to_string()))?;
+        let actor: Option<Arc<str>> = actor.map(Arc::from);
+        for event in events {
+            let key = (list.cloned(), Arc::<str>::from(event.todo_id()));
+            let description = match event {
+                TodoEvent::TodoCreated { description, .. } => {
+                    state.descriptions.insert(key, description.clone());
+                    description.clone()
+                }
+                TodoEvent::TodoDescriptionUpdated { new, .. } => {
+                    state.descriptions.insert(key, new.clone());
+                    new.clone()
+                }
+                _ => state.descriptions.get(&key).unwrap_or(&key.1).clone(),
+            };
+            let sequence = state.next_sequence;
+            state.next_sequence += 1;
+            state.activities.push_back(Activity {
+                sequence,
+                list: list.cloned(),
+                actor: actor.clone(),
+                description,
+                event: event.clone(),
+            });
+            if state.activities.len() > self.retention {
+                state.activities.pop_front();
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for ActivityFeed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventSink for ActivityFeed {
+    fn record(&self, events: &[TodoEvent]) -> Result<(), TodoError> {
+        self.record_change(None, None, events)
+    }
+}
+
+/// `ACTIVITY_CREATED`, `ACTIVITY_STARTED`, `ACTIVITY_COMPLETED`, `ACTIVITY_REOPENED`,
+/// `ACTIVITY_ARCHIVED`, `ACTIVITY_STALE`, `ACTIVITY_DUE_DATE_SET`, `ACTIVITY_DUE_DATE_CLEARED`,
+/// `ACTIVITY_PRIORITY_CHANGED`, `ACTIVITY_TAG_ADDED`, `ACTIVITY_TAG_REMOVED`,
+/// `ACTIVITY_SUBTASK_ADDED`, `ACTIVITY_SUBTASK_COMPLETED`, `ACTIVITY_SUBTASK_REOPENED`,
+/// `ACTIVITY_SUBTASK_REMOVED`, `ACTIVITY_DESCRIPTION_UPDATED`, `ACTIVITY_BLOCKED`,
+/// `ACTIVITY_UNBLOCKED`, `ACTIVITY_DELETED`, `ACTIVITY_RESTORED`, `ACTIVITY_ATTACHMENT_ADDED` or
+/// `ACTIVITY_ATTACHMENT_REMOVED`, with the todo's `description` and the relative `time`, the
+/// new `priority` for `ACTIVITY_PRIORITY_CHANGED`, the `tag` for `ACTIVITY_TAG_ADDED` and
+/// `ACTIVITY_TAG_REMOVED`, the `subtask`'s description for `ACTIVITY_SUBTASK_ADDED`, the `old`
+/// description for `ACTIVITY_DESCRIPTION_UPDATED`, and the file `name` for
+/// `ACTIVITY_ATTACHMENT_ADDED`
+struct ActivityMessage<'a> {
+    activity: &'a Activity,
+    time: String,
+}
+
+impl Localizable for ActivityMessage<'_> {
+    fn message_code(&self) -> &'static str {
+        match &self.activity.event {
+            TodoEvent::TodoCreated { .. } => "ACTIVITY_CREATED",
+            TodoEvent::TodoStateChanged { to_state, .. } => match to_state {
+                TodoState::Todo => "ACTIVITY_REOPENED",
+                TodoState::InProgress => "ACTIVITY_STARTED",
+                TodoState::Done => "ACTIVITY_COMPLETED",
+            },
+            TodoEvent::TodoArchived { .. } => "ACTIVITY_ARCHIVED",
+            TodoEvent::TodoStale { .. } => "ACTIVITY_STALE",
+            TodoEvent::TodoDueDateChanged {
+                due_at: Some(_), ..
+            } => "ACTIVITY_DUE_DATE_SET",
+            TodoEvent::TodoDueDateChanged { due_at: None, .. } => "ACTIVITY_DUE_DATE_CLEARED",
+            TodoEvent::TodoPriorityChanged { .. } => "ACTIVITY_PRIORITY_CHANGED",
+            TodoEvent::TodoTagAdded { .. } => "ACTIVITY_TAG_ADDED",
+            TodoEvent::TodoTagRemoved { .. } => "ACTIVITY_TAG_REMOVED",
+            TodoEvent::TodoSubtaskAdded { .. } => "ACTIVITY_SUBTASK_ADDED",
+            TodoEvent::TodoSubtaskToggled { done: true, .. } => "ACTIVITY_SUBTASK_COMPLETED",
+            TodoEvent::TodoSubtaskToggled { done: false, .. } => "ACTIVITY_SUBTASK_REOPENED",
+            TodoEvent::TodoSubtaskRemoved { .. } => "ACTIVITY_SUBTASK_REMOVED",
+            TodoEvent::TodoDescriptionUpdated { .. } => "ACTIVITY_DESCRIPTION_UPDATED",
+            TodoEvent::TodoBlocked { .. } => "ACTIVITY_BLOCKED",
+            TodoEvent::TodoUnblocked { .. } => "ACTIVITY_UNBLOCKED",
+            TodoEvent::TodoDeleted { .. } => "ACTIVITY_DELETED",
+            TodoEvent::TodoRestored { .. } => "ACTIVITY_RESTORED",
+            TodoEvent::TodoAttachmentAdded { .. } => "ACTIVITY_ATTACHMENT_ADDED",
+            TodoEvent::TodoAttachmentRemoved { .. } => "ACTIVITY_ATTACHMENT_REMOVED",
+        }
+    }
+
+    fn message_args(&self) -> Vec<(&'static str, MessageArg)> {
+        let mut args = vec![
+            (
+                "description",
+                MessageArg::Text(self.activity.description.to_string()),
+            ),
+            ("time", MessageArg::Text(self.time.clone())),
+        ];
+        match &self.activity.event {
+            TodoEvent::TodoPriorityChanged { to_priority, .. } => {
+                args.push(("priority", MessageArg::Code(to_priority.message_code())));
+            }
+            TodoEvent::TodoTagAdded { tag, .. } | TodoEvent::TodoTagRemoved { tag, .. } => {
+                args.push(("tag", MessageArg::Text(tag.to_string())));
+            }
+            TodoEvent::TodoSubtaskAdded { description, .. } => {
+                args.push(("subtask", MessageArg::Text(description.to_string())));
+            }
+            TodoEvent::TodoDescriptionUpdated { old, .. } => {
+                args.push(("old", MessageArg::Text(old.to_string())));
+            }
+            TodoEvent::TodoAttachmentAdded { name, .. } => {
+                args.push(("name", MessageArg::Text(name.to_string())));
+            }
+            _ => {}
+        }
+        args
+    }
+}
+
+/// How long ago something happened, such as `2 hours ago`
+struct RelativeTime(TimeDelta);
+
+impl RelativeTime {
+    /// The message code and, for plurals, the count
+    fn unit(&self) -> (&'static str, i64) {
+        let elapsed = self.0;
+        match (
+            elapsed.num_minutes(),
+            elapsed.num_hours(),
+            elapsed.num_days(),
+        ) {
+            (minutes, _, _) if minutes < 1 => ("TIME_JUST_NOW", 0),
+            (1, _, _) => ("TIME_MINUTE_AGO", 1),
+            (minutes, 0, _) => ("TIME_MINUTES_AGO", minutes),
+            (_, 1, _) => ("TIME_HOUR_AGO", 1),
+            (_, hours, 0) => ("TIME_HOURS_AGO", hours),
+            (_, _, 1) => ("TIME_DAY_AGO", 1),
+            (_, _, days) => ("TIME_DAYS_AGO", days),
+        }
+    }
+}
+
+impl Localizable for RelativeTime {
+    fn message_code(&self) -> &'static str {
+        self.unit().0
+    }
+
+    fn message_args(&self) -> Vec<(&'static str, MessageArg)> {
+        vec![("count", MessageArg::Text(self.unit().1.to_string()))]
+    }
+}
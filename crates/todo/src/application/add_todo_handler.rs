@@ -1,17 +1,88 @@
-use crate::{Todo, TodoError, TodoEvent, TodoRepository};
+use std::sync::Arc;
 
-pub struct AddTodoHandler {
-    todo_repository: Box<dyn TodoRepository>,
+use crate::application::metrics::{observe, record_events};
+use crate::application::quota::{Quota, QuotaUsage};
+use crate::{
+    Clock, EventSink, IdGenerator, SystemClock, Todo, TodoError, TodoEvent, TodoRepository,
+    UuidV4Generator,
+};
+
+pub struct AddTodoHandler<R = Box<dyn TodoRepository>> {
+    todo_repository: R,
+    event_sink: Option<Arc<dyn EventSink>>,
+    clock: Arc<dyn Clock>,
+    ids: Arc<dyn IdGenerator>,
+    quota: Quota,
 }
 
-impl AddTodoHandler {
-    pub fn new(todo_repository: Box<dyn TodoRepository>) -> Self {
-        Self { todo_repository }
+impl<R: TodoRepository> AddTodoHandler<R> {
+    pub fn new(todo_repository: R) -> Self {
+        Self {
+            todo_repository,
+            event_sink: None,
+            clock: Arc::new(SystemClock),
+            ids: Arc::new(UuidV4Generator),
+            quota: Quota::default(),
+        }
+    }
+
+    /// Passes the events of every saved change to `event_sink`
+    pub fn with_event_sink(mut self, event_sink: Arc<dyn EventSink>) -> Self {
+        self.event_sink = Some(event_sink);
+        self
+    }
+
+    /// Stamps created todos with `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Gives created todos ids from `ids` instead of random UUIDv4s
+    pub fn with_id_generator(mut self, ids: Arc<dyn IdGenerator>) -> Self {
+        self.ids = ids;
+        self
+    }
+
+    /// Refuses new todos past `quota`, counting every todo of the repository, which for a
+    /// `TenantTodoRepository` are those of its tenant's list
+    pub fn with_quota(mut self, quota: Quota) -> Self {
+        self.quota = quota;
+        self
+    }
+
+    fn record(&self, events: &[TodoEvent]) -> Result<(), TodoError> {
+        match &self.event_sink {
+            Some(event_sink) => event_sink.record(events),
+            None => Ok(()),
+        }
     }
 
-    pub async fn new_todo(&self, description: String) -> Result<Vec<TodoEvent>, TodoError> {
-        let (todo, events) = Todo::new(description)?;
-        self.todo_repository.save(&todo).await?;
-        Ok(events)
+    /// Creates a todo with `description` and saves it
+    ///
+    /// # Returns
+    /// - `Ok((Todo, Vec<TodoEvent>))`: The todo as saved, and its `TodoCreated` event
+    /// - `Err(TodoError::EmptyDescription)`: If `description` is blank
+    /// - `Err(TodoError::QuotaExceeded)`: If the repository is at its quota
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "add_todo"), err)
+    )]
+    pub async fn new_todo(&self, description: String) -> Result<(Todo, Vec<TodoEvent>), TodoError> {
+        observe("add_todo", async move {
+            let (mut todo, events) = Todo::new_with(description, &*self.clock, &*self.ids)?;
+            if self.quota.is_limited() {
+                let todos = self.todo_repository.find_all().await?;
+                if QuotaUsage::new(&todos, self.quota).remaining() == Some(0) {
+                    return Err(TodoError::QuotaExceeded);
+                }
+            }
+            self.todo_repository.save(&todo).await?;
+            todo.dirty = Some(false);
+            record_events(&events);
+            self.record(&events)?;
+            Ok((todo, events))
+        })
+        .await
     }
 }
@@ -0,0 +1,62 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::application::auth::{AuthContext, AuthError, Authenticator, READ_SCOPE, WRITE_SCOPE};
+use crate::domain::api_key::{API_KEY_PREFIX, ApiKey, ApiKeyRepository, ApiKeyScope};
+
+/// Accepts the tokens of the unrevoked keys of an ApiKeyRepository
+///
+/// The `AuthContext` has the subject `api-key:<id>`, no tenant, and `todos:read`, plus
+/// `todos:write` for `ApiKeyScope::ReadWrite` keys. Tokens that do not start with `hkt_`
+/// are passed to the fallback authenticator, if any, so API keys and JWTs can be accepted
+/// side by side.
+pub struct ApiKeyAuthenticator<R = Arc<dyn ApiKeyRepository>> {
+    api_key_repository: R,
+    fallback: Option<Arc<dyn Authenticator>>,
+}
+
+impl<R: ApiKeyRepository> ApiKeyAuthenticator<R> {
+    pub fn new(api_key_repository: R) -> Self {
+        Self {
+            api_key_repository,
+            fallback: None,
+        }
+    }
+
+    /// Passes tokens that are not API keys to `fallback` instead of rejecting them
+    pub fn with_fallback(mut self, fallback: Arc<dyn Authenticator>) -> Self {
+        self.fallback = Some(fallback);
+        self
+    }
+}
+
+#[async_trait]
+impl<R: ApiKeyRepository> Authenticator for ApiKeyAuthenticator<R> {
+    async fn authenticate(&self, token: &str) -> Result<AuthContext, AuthError> {
+        if !token.starts_with(API_KEY_PREFIX) {
+            return match &self.fallback {
+                Some(fallback) => fallback.authenticate(token).await,
+                None => Err(AuthError::InvalidToken("not an api key".to_string())),
+            };
+        }
+        let rejected = || AuthError::InvalidToken("unknown or revoked api key".to_string());
+        let (id, secret) = ApiKey::parse_token(token).ok_or_else(rejected)?;
+        let key = match self.api_key_repository.find_by_id(id).await {
+            Ok(Some(key)) => key,
+            Ok(None) => return Err(rejected()),
+            Err(err) => return Err(AuthError::Unavailable(err.to_string())),
+        };
+        if key.is_revoked() || !key.verify(secret) {
+            return Err(rejected());
+        }
+        let scopes = match key.scope {
+            ApiKeyScope::ReadOnly => vec![READ_SCOPE.to_string()],
+            ApiKeyScope::ReadWrite => vec![READ_SCOPE.to_string(), WRITE_SCOPE.to_string()],
+        };
+        Ok(AuthContext {
+            subject: format!("api-key:{}", key.id),
+            tenant: None,
+            scopes,
+        })
+    }
+}
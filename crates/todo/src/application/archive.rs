@@ -0,0 +1,149 @@
+use chrono::{DateTime, Days, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::application::metrics::{observe, record_events};
+use crate::application::retention::state_since;
+use crate::{
+    Clock, EventSink, EventStore, SystemClock, TenantId, TodoError, TodoEvent, TodoRepository,
+    TodoState,
+};
+
+/// How long todos stay `DONE` in their list before they are archived
+///
+/// A list is the set of todos of a tenant, stored under `<tenant>/<id>` as a
+/// `TenantTodoRepository` stores them. Lists follow their own setting when they have one,
+/// and the default otherwise. The default policy archives nothing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArchivePolicy {
+    /// Archives todos `DONE` for at least this many days; `None` never archives them
+    pub done_days: Option<u32>,
+    /// Overrides of `done_days` per list; `None` turns archiving off for the list
+    pub lists: HashMap<TenantId, Option<u32>>,
+}
+
+impl ArchivePolicy {
+    /// Creates a new ArchivePolicy archiving todos `DONE` for `done_days` in every list
+    pub fn new(done_days: Option<u32>) -> Self {
+        ArchivePolicy {
+            done_days,
+            lists: HashMap::new(),
+        }
+    }
+
+    /// Applies `done_days` instead of the default to the todos of `list`
+    pub fn with_list(mut self, list: TenantId, done_days: Option<u32>) -> Self {
+        self.lists.insert(list, done_days);
+        self
+    }
+
+    /// Whether any list has its todos archived
+    pub fn is_enabled(&self) -> bool {
+        self.done_days.is_some() || self.lists.values().any(Option::is_some)
+    }
+
+    /// The setting of the todo stored under `id`
+    fn done_days_for(&self, id: &str) -> Option<u32> {
+        id.split_once('/')
+            .and_then(|(list, _)| TenantId::new(list).ok())
+            .and_then(|list| self.lists.get(&list).copied())
+            .unwrap_or(self.done_days)
+    }
+}
+
+/// Moves the todos that an ArchivePolicy no longer keeps in their list to an archive
+///
+/// The archive is another `TodoRepository`, such as a `FileTodoRepository` of its own, where
+/// archived todos keep their id, description, state and creation time. A todo's time in
+/// `DONE` counts from its last change to `DONE` in the event store given with
+/// `with_event_store`, or from its creation when the store has no such change or there is no
+/// store.
+pub struct ArchiveTodosHandler<R = Box<dyn TodoRepository>> {
+    todo_repository: R,
+    archive: Arc<dyn TodoRepository>,
+    policy: ArchivePolicy,
+    event_store: Option<Arc<dyn EventStore>>,
+    event_sink: Option<Arc<dyn EventSink>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl<R: TodoRepository> ArchiveTodosHandler<R> {
+    pub fn new(
+        todo_repository: R,
+        archive: Arc<dyn TodoRepository>,
+        policy: ArchivePolicy,
+    ) -> Self {
+        Self {
+            todo_repository,
+            archive,
+            policy,
+            event_store: None,
+            event_sink: None,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Reads completion times from `event_store` and records the `TodoArchived` events in it
+    pub fn with_event_store(mut self, event_store: Arc<dyn EventStore>) -> Self {
+        self.event_sink = Some(event_store.clone());
+        self.event_store = Some(event_store);
+        self
+    }
+
+    /// Passes the `TodoArchived` event of every archived todo to `event_sink`
+    pub fn with_event_sink(mut self, event_sink: Arc<dyn EventSink>) -> Self {
+        self.event_sink = Some(event_sink);
+        self
+    }
+
+    /// Measures ages and stamps events with `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Moves every todo `DONE` for longer than its list's `done_days` to the archive
+    ///
+    /// # Returns
+    /// - `Ok(Vec<TodoEvent>)`: A `TodoArchived` per archived todo, with its id as stored
+    /// - `Err(TodoError::RepositoryError)`: If a repository failed; the todos archived until
+    ///   then stay archived, and a todo saved to the archive but not yet deleted from its
+    ///   list is in both until the next run
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "archive_todos"), err)
+    )]
+    pub async fn archive(&self) -> Result<Vec<TodoEvent>, TodoError> {
+        observe("archive_todos", async {
+            let now = self.clock.now();
+            let mut archived = Vec::new();
+            for todo in self.todo_repository.find_all().await? {
+                let Some(days) = self.policy.done_days_for(&todo.id) else {
+                    continue;
+                };
+                if todo.state != TodoState::Done {
+                    continue;
+                }
+                let cutoff = now
+                    .checked_sub_days(Days::new(days.into()))
+                    .unwrap_or(DateTime::<Utc>::MIN_UTC);
+                if state_since(self.event_store.as_deref(), &todo)? > cutoff {
+                    continue;
+                }
+                self.archive.save(&todo).await?;
+                self.todo_repository.delete(&todo.id).await?;
+                let events = [TodoEvent::TodoArchived {
+                    id: todo.id,
+                    archived_at: now,
+                }];
+                record_events(&events);
+                if let Some(event_sink) = &self.event_sink {
+                    event_sink.record(&events)?;
+                }
+                archived.extend(events);
+            }
+            Ok(archived)
+        })
+        .await
+    }
+}
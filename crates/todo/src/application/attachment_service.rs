@@ -0,0 +1,182 @@
+use std::sync::Arc;
+
+use crate::application::metrics::{observe, record_events};
+use crate::domain::blob::{BlobStore, content_hash};
+use crate::{
+    Attachment, Clock, EventSink, SystemClock, Todo, TodoError, TodoEvent, TodoRepository,
+};
+
+/// Attaches files to the todos of a repository, keeping their content in a BlobStore
+///
+/// The todo holds an `Attachment` with the file's metadata and the `content_hash` its
+/// content is stored under, so saving and loading the todo never moves the content. Equal
+/// contents share one blob, across todos too, so `detach` only deletes a blob once no todo
+/// of the repository refers to it any more. Todos in the trash are not looked at, so
+/// restoring a deleted todo can leave it with attachments whose content is gone.
+pub struct AttachmentService<R = Box<dyn TodoRepository>, B = Box<dyn BlobStore>> {
+    todo_repository: R,
+    blob_store: B,
+    event_sink: Option<Arc<dyn EventSink>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl<R: TodoRepository, B: BlobStore> AttachmentService<R, B> {
+    pub fn new(todo_repository: R, blob_store: B) -> Self {
+        Self {
+            todo_repository,
+            blob_store,
+            event_sink: None,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Passes the events of every saved change to `event_sink`
+    pub fn with_event_sink(mut self, event_sink: Arc<dyn EventSink>) -> Self {
+        self.event_sink = Some(event_sink);
+        self
+    }
+
+    /// Stamps added and removed attachments with `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn record(&self, events: &[TodoEvent]) -> Result<(), TodoError> {
+        match &self.event_sink {
+            Some(event_sink) => event_sink.record(events),
+            None => Ok(()),
+        }
+    }
+
+    /// Stores `content` and attaches it to the todo with `id` as the file `name`, and saves
+    /// the todo
+    ///
+    /// # Returns
+    /// - `Ok((Todo, Vec<TodoEvent>))`: The todo as saved, and its `TodoAttachmentAdded`
+    ///   event; no event, and nothing stored or saved, when the todo already has an
+    ///   attachment with the same content
+    /// - `Err(TodoError::TodoNotFound)`: If there is no todo with `id`
+    /// - `Err(TodoError::ValidationError)`: If `name` is empty
+    /// - `Err(TodoError::RepositoryError)`: If the content cannot be stored
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "attach_file", todo.id = %id), err)
+    )]
+    pub async fn attach(
+        &self,
+        id: String,
+        name: String,
+        media_type: String,
+        content: Vec<u8>,
+    ) -> Result<(Todo, Vec<TodoEvent>), TodoError> {
+        observe("attach_file", async move {
+            let mut todo = self
+                .todo_repository
+                .find_by_id(&id)
+                .await?
+                .ok_or(TodoError::TodoNotFound)?;
+            let attachment = Attachment {
+                content_hash: content_hash(&content).into(),
+                name: name.into(),
+                media_type: media_type.into(),
+                size: content.len() as u64,
+            };
+            let events = todo.attach_with_clock(attachment, &*self.clock)?;
+            if events.is_empty() {
+                return Ok((todo, events));
+            }
+            // The content is stored before the todo refers to it
+            self.blob_store.put(&content).await?;
+            self.todo_repository.save(&todo).await?;
+            todo.dirty = Some(false);
+            record_events(&events);
+            self.record(&events)?;
+            Ok((todo, events))
+        })
+        .await
+    }
+
+    /// Reads the attachment with `content_hash` of the todo with `id`
+    ///
+    /// # Returns
+    /// - `Ok((Attachment, Vec<u8>))`: The attachment's metadata and its content
+    /// - `Err(TodoError::TodoNotFound)`: If there is no todo with `id`
+    /// - `Err(TodoError::ValidationError)`: If the todo has no attachment with `content_hash`
+    /// - `Err(TodoError::RepositoryError)`: If its content is missing from the blob store
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "open_attachment", todo.id = %id), err)
+    )]
+    pub async fn open(
+        &self,
+        id: String,
+        content_hash: String,
+    ) -> Result<(Attachment, Vec<u8>), TodoError> {
+        observe("open_attachment", async move {
+            let todo = self
+                .todo_repository
+                .find_by_id(&id)
+                .await?
+                .ok_or(TodoError::TodoNotFound)?;
+            let attachment = todo.attachment(&content_hash).cloned().ok_or_else(|| {
+                TodoError::ValidationError(format!(
+                    "todo {} has no attachment {}",
+                    todo.id, content_hash
+                ))
+            })?;
+            let content = self.blob_store.get(&content_hash).await?.ok_or_else(|| {
+                TodoError::RepositoryError(format!(
+                    "content of attachment {} is missing",
+                    content_hash
+                ))
+            })?;
+            Ok((attachment, content))
+        })
+        .await
+    }
+
+    /// Removes the attachment with `content_hash` from the todo with `id`, saves the todo,
+    /// and deletes the content once no other todo refers to it
+    ///
+    /// # Returns
+    /// - `Ok((Todo, Vec<TodoEvent>))`: The todo as saved, and its `TodoAttachmentRemoved`
+    ///   event; no event, and nothing saved or deleted, when it had no such attachment
+    /// - `Err(TodoError::TodoNotFound)`: If there is no todo with `id`
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "detach_file", todo.id = %id), err)
+    )]
+    pub async fn detach(
+        &self,
+        id: String,
+        content_hash: String,
+    ) -> Result<(Todo, Vec<TodoEvent>), TodoError> {
+        observe("detach_file", async move {
+            let mut todo = self
+                .todo_repository
+                .find_by_id(&id)
+                .await?
+                .ok_or(TodoError::TodoNotFound)?;
+            let events = todo.detach_with_clock(&content_hash, &*self.clock);
+            if events.is_empty() {
+                return Ok((todo, events));
+            }
+            self.todo_repository.save(&todo).await?;
+            todo.dirty = Some(false);
+            let shared = self
+                .todo_repository
+                .find_all()
+                .await?
+                .iter()
+                .any(|other| other.attachment(&content_hash).is_some());
+            if !shared {
+                self.blob_store.delete(&content_hash).await?;
+            }
+            record_events(&events);
+            self.record(&events)?;
+            Ok((todo, events))
+        })
+        .await
+    }
+}
@@ -0,0 +1,106 @@
+use async_trait::async_trait;
+
+use crate::TenantId;
+
+/// Scope needed to list and read todos
+pub const READ_SCOPE: &str = "todos:read";
+/// Scope needed to create, change and delete todos
+pub const WRITE_SCOPE: &str = "todos:write";
+
+/// Who made a request, as established by an `Authenticator`
+///
+/// The front ends attach it to each authenticated request, for the handlers that decide
+/// what the caller may do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthContext {
+    /// The authenticated principal, such as a JWT's `sub`
+    pub subject: String,
+    /// The tenant the principal acts for, if the credentials name one
+    pub tenant: Option<TenantId>,
+    /// The scopes granted to the credentials
+    pub scopes: Vec<String>,
+}
+
+impl AuthContext {
+    /// Whether the credentials were granted `scope`
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|granted| granted == scope)
+    }
+
+    /// Checks that the credentials were granted `scope`
+    ///
+    /// # Returns
+    /// - `Err(AuthError::InsufficientScope)`: If they were not
+    pub fn require(&self, scope: &str) -> Result<(), AuthError> {
+        if self.has_scope(scope) {
+            Ok(())
+        } else {
+            Err(AuthError::InsufficientScope(scope.to_string()))
+        }
+    }
+}
+
+/// Why a request could not be authenticated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthError {
+    /// The request carries no credentials
+    MissingCredentials,
+    /// The credentials are malformed, expired, or not signed by a trusted key
+    InvalidToken(String),
+    /// The credentials are valid but were not granted the scope, carried here, that the
+    /// request needs
+    InsufficientScope(String),
+    /// The caller's role on the todo list does not permit the request, for the reason
+    /// carried here
+    Forbidden(String),
+    /// The credentials could not be checked, such as when the keys cannot be fetched
+    Unavailable(String),
+}
+
+impl std::fmt::Display for AuthError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuthError::MissingCredentials => write!(f, "missing bearer token"),
+            AuthError::InvalidToken(message) => write!(f, "invalid token: {}", message),
+            AuthError::InsufficientScope(scope) => write!(f, "missing scope {}", scope),
+            AuthError::Forbidden(reason) => write!(f, "forbidden: {}", reason),
+            AuthError::Unavailable(message) => {
+                write!(f, "authentication unavailable: {}", message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AuthError {}
+
+/// Turns the credentials of a request into an `AuthContext`
+///
+/// Implemented by `ApiKeyAuthenticator` and `JwtAuthenticator` (`jwt` feature); the REST
+/// middleware and the gRPC layer take any implementation.
+#[async_trait]
+pub trait Authenticator: Send + Sync {
+    /// Checks a bearer token
+    ///
+    /// # Returns
+    /// - `Ok(AuthContext)`: The caller the token identifies
+    /// - `Err(AuthError)`: If the token is not accepted
+    async fn authenticate(&self, token: &str) -> Result<AuthContext, AuthError>;
+}
+
+/// The token of an `Authorization: Bearer <token>` header value
+///
+/// # Returns
+/// - `Ok(&str)`: The token
+/// - `Err(AuthError::InvalidToken)`: If the value uses another scheme or has no token
+pub fn bearer_token(authorization: &str) -> Result<&str, AuthError> {
+    match authorization.split_once(' ') {
+        Some((scheme, token))
+            if scheme.eq_ignore_ascii_case("bearer") && !token.trim().is_empty() =>
+        {
+            Ok(token.trim())
+        }
+        _ => Err(AuthError::InvalidToken(
+            "expected an Authorization: Bearer header".to_string(),
+        )),
+    }
+}
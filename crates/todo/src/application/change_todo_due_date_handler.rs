@@ -0,0 +1,79 @@
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+use crate::application::metrics::{observe, record_events};
+use crate::{Clock, EventSink, SystemClock, Todo, TodoError, TodoEvent, TodoRepository};
+
+pub struct ChangeTodoDueDateHandler<R = Box<dyn TodoRepository>> {
+    todo_repository: R,
+    event_sink: Option<Arc<dyn EventSink>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl<R: TodoRepository> ChangeTodoDueDateHandler<R> {
+    pub fn new(todo_repository: R) -> Self {
+        Self {
+            todo_repository,
+            event_sink: None,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Passes the events of every saved change to `event_sink`
+    pub fn with_event_sink(mut self, event_sink: Arc<dyn EventSink>) -> Self {
+        self.event_sink = Some(event_sink);
+        self
+    }
+
+    /// Stamps due date changes with `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn record(&self, events: &[TodoEvent]) -> Result<(), TodoError> {
+        match &self.event_sink {
+            Some(event_sink) => event_sink.record(events),
+            None => Ok(()),
+        }
+    }
+
+    /// Sets the due date of the todo with `id` to `due_at`, or removes it when `None`, and
+    /// saves the todo
+    ///
+    /// # Returns
+    /// - `Ok((Todo, Vec<TodoEvent>))`: The todo as saved, and its `TodoDueDateChanged` event;
+    ///   no event, and nothing saved, when the due date was already `due_at`
+    /// - `Err(TodoError::TodoNotFound)`: If there is no todo with `id`
+    /// - `Err(TodoError::ValidationError)`: If `due_at` is before the todo was created
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "change_todo_due_date", todo.id = %id), err)
+    )]
+    pub async fn change_due_date(
+        &self,
+        id: String,
+        due_at: Option<DateTime<Utc>>,
+    ) -> Result<(Todo, Vec<TodoEvent>), TodoError> {
+        observe("change_todo_due_date", async move {
+            let mut todo = self
+                .todo_repository
+                .find_by_id(&id)
+                .await?
+                .ok_or(TodoError::TodoNotFound)?;
+            let events = match due_at {
+                Some(due_at) => todo.set_due_date_with_clock(due_at, &*self.clock)?,
+                None => todo.clear_due_date_with_clock(&*self.clock)?,
+            };
+            if events.is_empty() {
+                return Ok((todo, events));
+            }
+            self.todo_repository.save(&todo).await?;
+            todo.dirty = Some(false);
+            record_events(&events);
+            self.record(&events)?;
+            Ok((todo, events))
+        })
+        .await
+    }
+}
@@ -1,18 +1,85 @@
-use crate::{TodoError, TodoEvent, TodoRepository, TodoState};
+use std::sync::Arc;
 
-pub struct ChangeTodoStateHandler {
-    todo_repository: Box<dyn TodoRepository>,
+use crate::application::dependency_service::released_dependents;
+use crate::application::metrics::{observe, record_events};
+use crate::{Clock, EventSink, SystemClock, Todo, TodoError, TodoEvent, TodoRepository, TodoState};
+
+pub struct ChangeTodoStateHandler<R = Box<dyn TodoRepository>> {
+    todo_repository: R,
+    event_sink: Option<Arc<dyn EventSink>>,
+    clock: Arc<dyn Clock>,
 }
 
-impl ChangeTodoStateHandler {
-    pub fn new(todo_repository: Box<dyn TodoRepository>) -> Self {
-        Self { todo_repository }
+impl<R: TodoRepository> ChangeTodoStateHandler<R> {
+    pub fn new(todo_repository: R) -> Self {
+        Self {
+            todo_repository,
+            event_sink: None,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Passes the events of every saved change to `event_sink`
+    pub fn with_event_sink(mut self, event_sink: Arc<dyn EventSink>) -> Self {
+        self.event_sink = Some(event_sink);
+        self
+    }
+
+    /// Stamps state changes with `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn record(&self, events: &[TodoEvent]) -> Result<(), TodoError> {
+        match &self.event_sink {
+            Some(event_sink) => event_sink.record(events),
+            None => Ok(()),
+        }
     }
 
-    pub async fn change_state(&self, id: String, new_state: TodoState) -> Result<Vec<TodoEvent>, TodoError> {
-        let mut todo = self.todo_repository.find_by_id(&id).await?.unwrap();
-        let events = todo.update_state(new_state)?;
-        self.todo_repository.save(&todo).await?;
-        Ok(events)
+    /// Moves the todo with `id` to `new_state` and saves it
+    ///
+    /// A todo moved to `DONE` no longer blocks the todos waiting on it, which are saved with
+    /// it in one `save_all`.
+    ///
+    /// # Returns
+    /// - `Ok((Todo, Vec<TodoEvent>))`: The todo as saved, its `TodoStateChanged` event, and
+    ///   a `TodoUnblocked` event for each todo that waited on it when it is now done
+    /// - `Err(TodoError::TodoNotFound)`: If there is no todo with `id`
+    /// - `Err(TodoError::InvalidStateTransition)`: If the todo cannot move to `new_state`
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "change_todo_state", todo.id = %id, to_state = ?new_state), err)
+    )]
+    pub async fn change_state(
+        &self,
+        id: String,
+        new_state: TodoState,
+    ) -> Result<(Todo, Vec<TodoEvent>), TodoError> {
+        observe("change_todo_state", async move {
+            let mut todo = self
+                .todo_repository
+                .find_by_id(&id)
+                .await?
+                .ok_or(TodoError::TodoNotFound)?;
+            let mut events = todo.update_state_with_clock(new_state, &*self.clock)?;
+            let mut changed = Vec::new();
+            if new_state == TodoState::Done {
+                let (dependents, unblocked) =
+                    released_dependents(&self.todo_repository, &todo.id, &*self.clock).await?;
+                changed = dependents;
+                events.extend(unblocked);
+            }
+            // The dependents go first, so a save failing part way leaves the todo not done
+            // and changing its state again releases the rest
+            changed.push(todo.clone());
+            self.todo_repository.save_all(&changed).await?;
+            todo.dirty = Some(false);
+            record_events(&events);
+            self.record(&events)?;
+            Ok((todo, events))
+        })
+        .await
     }
 }
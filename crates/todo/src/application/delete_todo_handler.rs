@@ -0,0 +1,89 @@
+use std::sync::Arc;
+
+use crate::application::dependency_service::unblock_dependents;
+use crate::application::metrics::{observe, record_events};
+use crate::domain::trash::{TrashRepository, TrashedTodo};
+use crate::{Clock, EventSink, SystemClock, TodoError, TodoEvent, TodoRepository};
+
+/// Deletes todos, for good or into a trash they can be restored from
+///
+/// Without `with_trash`, deletion is permanent. With it, the todo is saved in the trash
+/// before it is removed from the repository, so a failed deletion never loses it.
+///
+/// The todos waiting on a deleted todo are unblocked and saved before it is removed, so
+/// they can still be started and finished. Restoring it from the trash does not block them
+/// again.
+pub struct DeleteTodoHandler<R = Box<dyn TodoRepository>> {
+    todo_repository: R,
+    trash: Option<Arc<dyn TrashRepository>>,
+    event_sink: Option<Arc<dyn EventSink>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl<R: TodoRepository> DeleteTodoHandler<R> {
+    pub fn new(todo_repository: R) -> Self {
+        Self {
+            todo_repository,
+            trash: None,
+            event_sink: None,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Moves deleted todos to `trash` instead of deleting them for good
+    pub fn with_trash(mut self, trash: Arc<dyn TrashRepository>) -> Self {
+        self.trash = Some(trash);
+        self
+    }
+
+    /// Passes the `TodoDeleted` event of every deletion to `event_sink`
+    pub fn with_event_sink(mut self, event_sink: Arc<dyn EventSink>) -> Self {
+        self.event_sink = Some(event_sink);
+        self
+    }
+
+    /// Records deletion times from `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn record(&self, events: &[TodoEvent]) -> Result<(), TodoError> {
+        match &self.event_sink {
+            Some(event_sink) => event_sink.record(events),
+            None => Ok(()),
+        }
+    }
+
+    /// Deletes the todo with `id`, into the trash when there is one
+    ///
+    /// # Returns
+    /// - `Ok(Vec<TodoEvent>)`: The `TodoDeleted` event, and a `TodoUnblocked` event for each
+    ///   todo that waited on it
+    /// - `Err(TodoError::TodoNotFound)`: If there is no todo with `id`
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "delete_todo", todo.id = %id), err)
+    )]
+    pub async fn delete_todo(&self, id: String) -> Result<Vec<TodoEvent>, TodoError> {
+        observe("delete_todo", async move {
+            let Some(todo) = self.todo_repository.find_by_id(&id).await? else {
+                return Err(TodoError::TodoNotFound);
+            };
+            let deleted_at = self.clock.now();
+            let mut events = vec![TodoEvent::TodoDeleted {
+                id: todo.id.clone(),
+                deleted_at,
+            }];
+            if let Some(trash) = &self.trash {
+                trash.save(&TrashedTodo::new(todo, deleted_at)).await?;
+            }
+            events.extend(unblock_dependents(&self.todo_repository, &id, &*self.clock).await?);
+            self.todo_repository.delete(&id).await?;
+            record_events(&events);
+            self.record(&events)?;
+            Ok(events)
+        })
+        .await
+    }
+}
@@ -0,0 +1,221 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::application::metrics::{observe, record_events};
+use crate::{Clock, EventSink, SystemClock, Todo, TodoError, TodoEvent, TodoRepository, TodoState};
+
+/// Keeps the blocked-by relationships between the todos of a repository consistent
+///
+/// `Todo::update_state` refuses to start or finish a todo while its `blocked_by` is not
+/// empty, so a todo's blockers are the todos it still waits on. `block` only adds blockers
+/// that exist, are not done and do not close a cycle. `ChangeTodoStateHandler` removes a
+/// todo it finishes from every todo waiting on it, `DeleteTodoHandler` one it deletes, and
+/// `release` does the same for a todo finished otherwise.
+///
+/// Released links are gone: moving a done todo back, or undoing its completion, leaves its
+/// former dependents unblocked until they are blocked again with `block`.
+pub struct DependencyService<R = Box<dyn TodoRepository>> {
+    todo_repository: R,
+    event_sink: Option<Arc<dyn EventSink>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl<R: TodoRepository> DependencyService<R> {
+    pub fn new(todo_repository: R) -> Self {
+        Self {
+            todo_repository,
+            event_sink: None,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Passes the events of every saved change to `event_sink`
+    pub fn with_event_sink(mut self, event_sink: Arc<dyn EventSink>) -> Self {
+        self.event_sink = Some(event_sink);
+        self
+    }
+
+    /// Stamps blocks and unblocks with `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn record(&self, events: &[TodoEvent]) -> Result<(), TodoError> {
+        match &self.event_sink {
+            Some(event_sink) => event_sink.record(events),
+            None => Ok(()),
+        }
+    }
+
+    /// Makes the todo with `id` wait on the todo with `blocker_id`, and saves it
+    ///
+    /// # Returns
+    /// - `Ok((Todo, Vec<TodoEvent>))`: The todo as saved, and its `TodoBlocked` event; no
+    ///   event, and nothing saved, when it was already blocked by `blocker_id`
+    /// - `Err(TodoError::TodoNotFound)`: If there is no todo with `id` or `blocker_id`
+    /// - `Err(TodoError::ValidationError)`: If `blocker_id` is `id`, the blocker is done, or
+    ///   the blocker waits on the todo, directly or through other todos
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "block_todo", todo.id = %id), err)
+    )]
+    pub async fn block(
+        &self,
+        id: String,
+        blocker_id: String,
+    ) -> Result<(Todo, Vec<TodoEvent>), TodoError> {
+        observe("block_todo", async move {
+            let mut todo = self
+                .todo_repository
+                .find_by_id(&id)
+                .await?
+                .ok_or(TodoError::TodoNotFound)?;
+            let blocker = self
+                .todo_repository
+                .find_by_id(&blocker_id)
+                .await?
+                .ok_or(TodoError::TodoNotFound)?;
+            if blocker.state == TodoState::Done {
+                return Err(TodoError::ValidationError(format!(
+                    "todo {} is already done",
+                    blocker.id
+                )));
+            }
+            if self.waits_on(&blocker, &todo.id).await? {
+                return Err(TodoError::ValidationError(format!(
+                    "todo {} already waits on todo {}",
+                    blocker.id, todo.id
+                )));
+            }
+            let events = todo.block_by_with_clock(&blocker_id, &*self.clock)?;
+            if events.is_empty() {
+                return Ok((todo, events));
+            }
+            self.todo_repository.save(&todo).await?;
+            todo.dirty = Some(false);
+            record_events(&events);
+            self.record(&events)?;
+            Ok((todo, events))
+        })
+        .await
+    }
+
+    /// Stops the todo with `id` from waiting on the todo with `blocker_id`, and saves it
+    ///
+    /// # Returns
+    /// - `Ok((Todo, Vec<TodoEvent>))`: The todo as saved, and its `TodoUnblocked` event; no
+    ///   event, and nothing saved, when it was not blocked by `blocker_id`
+    /// - `Err(TodoError::TodoNotFound)`: If there is no todo with `id`
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "unblock_todo", todo.id = %id), err)
+    )]
+    pub async fn unblock(
+        &self,
+        id: String,
+        blocker_id: String,
+    ) -> Result<(Todo, Vec<TodoEvent>), TodoError> {
+        observe("unblock_todo", async move {
+            let mut todo = self
+                .todo_repository
+                .find_by_id(&id)
+                .await?
+                .ok_or(TodoError::TodoNotFound)?;
+            let events = todo.unblock_with_clock(&blocker_id, &*self.clock);
+            if events.is_empty() {
+                return Ok((todo, events));
+            }
+            self.todo_repository.save(&todo).await?;
+            todo.dirty = Some(false);
+            record_events(&events);
+            self.record(&events)?;
+            Ok((todo, events))
+        })
+        .await
+    }
+
+    /// Unblocks every todo waiting on the done todo with `blocker_id`, and saves them
+    ///
+    /// # Returns
+    /// - `Ok(Vec<TodoEvent>)`: A `TodoUnblocked` event for each todo that waited on it
+    /// - `Err(TodoError::TodoNotFound)`: If there is no todo with `blocker_id`
+    /// - `Err(TodoError::ValidationError)`: If the todo with `blocker_id` is not done
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "release_dependents", todo.id = %blocker_id), err)
+    )]
+    pub async fn release(&self, blocker_id: String) -> Result<Vec<TodoEvent>, TodoError> {
+        observe("release_dependents", async move {
+            let blocker = self
+                .todo_repository
+                .find_by_id(&blocker_id)
+                .await?
+                .ok_or(TodoError::TodoNotFound)?;
+            if blocker.state != TodoState::Done {
+                return Err(TodoError::ValidationError(format!(
+                    "todo {} is not done",
+                    blocker.id
+                )));
+            }
+            let events =
+                unblock_dependents(&self.todo_repository, &blocker_id, &*self.clock).await?;
+            if events.is_empty() {
+                return Ok(events);
+            }
+            record_events(&events);
+            self.record(&events)?;
+            Ok(events)
+        })
+        .await
+    }
+
+    /// Whether `todo` waits on the todo with `id`, directly or through the todos it waits on;
+    /// blockers missing from the repository end their chain
+    async fn waits_on(&self, todo: &Todo, id: &str) -> Result<bool, TodoError> {
+        let mut pending: Vec<Arc<str>> = todo.blocked_by.clone();
+        let mut seen = HashSet::new();
+        while let Some(blocker_id) = pending.pop() {
+            if &*blocker_id == id {
+                return Ok(true);
+            }
+            if !seen.insert(blocker_id.clone()) {
+                continue;
+            }
+            if let Some(blocker) = self.todo_repository.find_by_id(&blocker_id).await? {
+                pending.extend(blocker.blocked_by);
+            }
+        }
+        Ok(false)
+    }
+}
+
+/// The todos of `todo_repository` waiting on the todo with `blocker_id`, unblocked but not
+/// saved, and their `TodoUnblocked` events stamped with `clock`
+pub(crate) async fn released_dependents<R: TodoRepository>(
+    todo_repository: &R,
+    blocker_id: &str,
+    clock: &dyn Clock,
+) -> Result<(Vec<Todo>, Vec<TodoEvent>), TodoError> {
+    let mut dependents = Vec::new();
+    let mut events = Vec::new();
+    for mut todo in todo_repository.find_blocked_by(blocker_id).await? {
+        events.extend(todo.unblock_with_clock(blocker_id, clock));
+        dependents.push(todo);
+    }
+    Ok((dependents, events))
+}
+
+/// Unblocks and saves every todo of `todo_repository` waiting on the todo with `blocker_id`,
+/// stamping the `TodoUnblocked` events with `clock`
+pub(crate) async fn unblock_dependents<R: TodoRepository>(
+    todo_repository: &R,
+    blocker_id: &str,
+    clock: &dyn Clock,
+) -> Result<Vec<TodoEvent>, TodoError> {
+    let (dependents, events) = released_dependents(todo_repository, blocker_id, clock).await?;
+    if !dependents.is_empty() {
+        todo_repository.save_all(&dependents).await?;
+    }
+    Ok(events)
+}
@@ -0,0 +1,298 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use std::fmt::Write;
+use std::sync::Arc;
+
+use crate::application::metrics::observe;
+use crate::application::push_notifier::PushNotifier;
+use crate::domain::notification::{EmailMessage, Mailer, NotificationError, PushNotification};
+use crate::{
+    Clock, EventStore, SystemClock, Todo, TodoError, TodoEvent, TodoRepository, TodoState,
+};
+
+/// Length of the period of `DigestHandler::last_week`
+const WEEK: TimeDelta = TimeDelta::days(7);
+
+/// A state a todo moved to, and when
+type StateChange = (TodoState, DateTime<Utc>);
+
+/// A todo as it was at the end of a Digest's period
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigestEntry {
+    pub id: Arc<str>,
+    pub description: Arc<str>,
+    pub created_at: DateTime<Utc>,
+    /// The todo's state at the end of the period
+    pub state: TodoState,
+}
+
+/// What happened to the todos over a period, from `start` included to `end` excluded
+///
+/// Todos created at the same time are listed by id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Digest {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    /// Todos that became `DONE` during the period and still were at its end, oldest first
+    pub completed: Vec<DigestEntry>,
+    /// Todos created during the period, oldest first
+    pub created: Vec<DigestEntry>,
+    /// Todos created before the period and still open at its end, oldest first
+    pub carried_over: Vec<DigestEntry>,
+}
+
+impl Digest {
+    /// `Todo digest <start> to <end>`, with the last day of the period
+    pub fn title(&self) -> String {
+        format!(
+            "Todo digest {} to {}",
+            self.start.format("%Y-%m-%d"),
+            (self.end - TimeDelta::nanoseconds(1)).format("%Y-%m-%d")
+        )
+    }
+
+    /// One line of counts, such as `3 completed, 2 created, 1 carried over`
+    pub fn summary(&self) -> String {
+        format!(
+            "{} completed, {} created, {} carried over",
+            self.completed.len(),
+            self.created.len(),
+            self.carried_over.len()
+        )
+    }
+
+    /// Renders the digest as a Markdown document: a title, the summary, then a section
+    /// listing the todos of each non-empty group
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!("# {}\n\n{}\n", self.title(), self.summary());
+        for (heading, entries) in self.sections() {
+            let _ = write!(markdown, "\n## {}\n\n", heading);
+            for entry in entries {
+                let _ = writeln!(markdown, "- {}", entry.description.replace('\n', " "));
+            }
+        }
+        markdown
+    }
+
+    /// Renders the digest as an HTML fragment with the same content as `to_markdown`,
+    /// escaping descriptions
+    pub fn to_html(&self) -> String {
+        let mut html = format!(
+            "<h1>{}</h1>\n<p>{}</p>\n",
+            escape_html(&self.title()),
+            escape_html(&self.summary())
+        );
+        for (heading, entries) in self.sections() {
+            let _ = writeln!(html, "<h2>{}</h2>\n<ul>", heading);
+            for entry in entries {
+                let _ = writeln!(html, "<li>{}</li>", escape_html(&entry.description));
+            }
+            html.push_str("</ul>\n");
+        }
+        html
+    }
+
+    fn sections(&self) -> impl Iterator<Item = (&'static str, &Vec<DigestEntry>)> {
+        [
+            ("Completed", &self.completed),
+            ("Created", &self.created),
+            ("Carried over", &self.carried_over),
+        ]
+        .into_iter()
+        .filter(|(_, entries)| !entries.is_empty())
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Builds Digests from the todos of a repository and the changes of an event store, and
+/// delivers them through the notification channels
+///
+/// A todo's state at the end of a period is the last change the store recorded before it,
+/// or its current state when the store has none of its events. Deleted todos are not in
+/// the repository, so they are left out. Tenant todos are looked up in the store by their
+/// id as stored and as their tenant's handlers see it.
+pub struct DigestHandler<R = Box<dyn TodoRepository>> {
+    todo_repository: R,
+    event_store: Arc<dyn EventStore>,
+    clock: Arc<dyn Clock>,
+    email: Option<(Arc<dyn Mailer>, String, Vec<String>)>,
+    push: Option<(Arc<PushNotifier>, String)>,
+}
+
+impl<R: TodoRepository> DigestHandler<R> {
+    pub fn new(todo_repository: R, event_store: Arc<dyn EventStore>) -> Self {
+        Self {
+            todo_repository,
+            event_store,
+            clock: Arc::new(SystemClock),
+            email: None,
+            push: None,
+        }
+    }
+
+    /// Ends `last_week`'s period at `clock`'s time instead of the system clock's
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Delivers digests by email through `mailer`, from `from` to every address of `to`
+    pub fn with_mailer(mut self, mailer: Arc<dyn Mailer>, from: String, to: Vec<String>) -> Self {
+        self.email = Some((mailer, from, to));
+        self
+    }
+
+    /// Delivers digests as push notifications to the devices of `subject`
+    pub fn with_push(mut self, notifier: Arc<PushNotifier>, subject: String) -> Self {
+        self.push = Some((notifier, subject));
+        self
+    }
+
+    /// Summarizes the seven days up to now
+    pub async fn last_week(&self) -> Result<Digest, TodoError> {
+        let end = self.clock.now();
+        self.digest(end - WEEK, end).await
+    }
+
+    /// Summarizes the period from `start` included to `end` excluded
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "generate_digest"), err)
+    )]
+    pub async fn digest(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Digest, TodoError> {
+        observe("generate_digest", async {
+            let mut todos = self.todo_repository.find_all().await?;
+            todos.sort_by(|a, b| {
+                a.created_at
+                    .cmp(&b.created_at)
+                    .then_with(|| a.id.cmp(&b.id))
+            });
+            let mut digest = Digest {
+                start,
+                end,
+                completed: Vec::new(),
+                created: Vec::new(),
+                carried_over: Vec::new(),
+            };
+            for todo in todos {
+                if todo.created_at >= end {
+                    continue;
+                }
+                let (state, changes) = match self.changes_before(&todo, end)? {
+                    Some(changes) => {
+                        let state = changes.last().map_or(TodoState::Todo, |(state, _)| *state);
+                        (state, changes)
+                    }
+                    None => (todo.state, Vec::new()),
+                };
+                let completed = state == TodoState::Done
+                    && changes.iter().any(|(to_state, changed_at)| {
+                        *to_state == TodoState::Done && *changed_at >= start
+                    });
+                let entry = DigestEntry {
+                    id: todo.id,
+                    description: todo.description,
+                    created_at: todo.created_at,
+                    state,
+                };
+                if completed {
+                    digest.completed.push(entry.clone());
+                }
+                if entry.created_at >= start {
+                    digest.created.push(entry);
+                } else if state != TodoState::Done {
+                    digest.carried_over.push(entry);
+                }
+            }
+            Ok(digest)
+        })
+        .await
+    }
+
+    /// Sends `digest` through every channel given with `with_mailer` and `with_push`
+    ///
+    /// The email's text is `to_markdown`; the push notification shows the title and
+    /// summary. Every channel is tried, even after one fails.
+    ///
+    /// # Returns
+    /// - `Err(NotificationError)`: The last failure, if a channel failed
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "deliver_digest"), err)
+    )]
+    pub async fn deliver(&self, digest: &Digest) -> Result<(), NotificationError> {
+        let mut failure = None;
+        if let Some((mailer, from, to)) = &self.email {
+            let sent = match EmailMessage::new(
+                from.clone(),
+                to.clone(),
+                digest.title(),
+                digest.to_markdown(),
+            ) {
+                Ok(message) => mailer.send(&message).await,
+                Err(err) => Err(err),
+            };
+            if let Err(err) = sent {
+                failure = Some(err);
+            }
+        }
+        if let Some((notifier, subject)) = &self.push {
+            let notification = PushNotification::new(digest.title(), digest.summary());
+            if let Err(err) = notifier.notify(subject, &notification).await {
+                failure = Some(err);
+            }
+        }
+        match failure {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    /// The state changes of `todo` recorded before `end`, oldest first, or `None` when the
+    /// store has no event of the todo
+    fn changes_before(
+        &self,
+        todo: &Todo,
+        end: DateTime<Utc>,
+    ) -> Result<Option<Vec<StateChange>>, TodoError> {
+        let mut ids = vec![&*todo.id];
+        if let Some((_, id)) = todo.id.split_once('/') {
+            ids.push(id);
+        }
+        let mut recorded = false;
+        let mut changes = Vec::new();
+        for id in ids {
+            for event in self.event_store.find_by_todo(id)? {
+                recorded = true;
+                if let TodoEvent::TodoStateChanged {
+                    to_state,
+                    changed_at,
+                    ..
+                } = event
+                    && changed_at < end
+                {
+                    changes.push((to_state, changed_at));
+                }
+            }
+        }
+        changes.sort_by_key(|(_, changed_at)| *changed_at);
+        Ok(recorded.then_some(changes))
+    }
+}
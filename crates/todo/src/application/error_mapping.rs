@@ -0,0 +1,175 @@
+use crate::TodoError;
+use crate::application::auth::AuthError;
+
+/// Media type of a problem details body
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// How each front end reports a `TodoError`, kept in one place so REST, gRPC and JSON-RPC
+/// agree on what a failure means
+///
+/// | Error | `code` | HTTP | gRPC | JSON-RPC |
+/// |---|---|---|---|---|
+/// | `EmptyDescription` | `EMPTY_DESCRIPTION` | 422 | `INVALID_ARGUMENT` | -32001 |
+/// | `InvalidStateTransition` | `INVALID_STATE_TRANSITION` | 409 | `FAILED_PRECONDITION` | -32002 |
+/// | `TodoNotFound` | `TODO_NOT_FOUND` | 404 | `NOT_FOUND` | -32003 |
+/// | `RepositoryError` | `REPOSITORY_ERROR` | 500 | `INTERNAL` | -32004 |
+/// | `QuotaExceeded` | `QUOTA_EXCEEDED` | 409 | `RESOURCE_EXHAUSTED` | -32005 |
+/// | `ValidationError` | `VALIDATION_ERROR` | 422 | `INVALID_ARGUMENT` | -32006 |
+impl TodoError {
+    /// Stable name of the error, as serialized in the `code` field
+    pub const fn code(&self) -> &'static str {
+        match self {
+            TodoError::EmptyDescription => "EMPTY_DESCRIPTION",
+            TodoError::InvalidStateTransition => "INVALID_STATE_TRANSITION",
+            TodoError::TodoNotFound => "TODO_NOT_FOUND",
+            TodoError::RepositoryError(_) => "REPOSITORY_ERROR",
+            TodoError::QuotaExceeded => "QUOTA_EXCEEDED",
+            TodoError::ValidationError(_) => "VALIDATION_ERROR",
+        }
+    }
+
+    /// Short summary of the kind of error, the same for every occurrence
+    pub const fn title(&self) -> &'static str {
+        match self {
+            TodoError::EmptyDescription => "Empty description",
+            TodoError::InvalidStateTransition => "Invalid state transition",
+            TodoError::TodoNotFound => "Todo not found",
+            TodoError::RepositoryError(_) => "Repository failure",
+            TodoError::QuotaExceeded => "Quota exceeded",
+            TodoError::ValidationError(_) => "Validation failed",
+        }
+    }
+
+    /// HTTP status code
+    pub const fn http_status(&self) -> u16 {
+        match self {
+            TodoError::EmptyDescription => 422,
+            TodoError::InvalidStateTransition => 409,
+            TodoError::TodoNotFound => 404,
+            TodoError::RepositoryError(_) => 500,
+            TodoError::QuotaExceeded => 409,
+            TodoError::ValidationError(_) => 422,
+        }
+    }
+
+    /// gRPC status code, as its number in `google.rpc.Code`
+    pub const fn grpc_code(&self) -> i32 {
+        match self {
+            // INVALID_ARGUMENT
+            TodoError::EmptyDescription | TodoError::ValidationError(_) => 3,
+            // FAILED_PRECONDITION
+            TodoError::InvalidStateTransition => 9,
+            // NOT_FOUND
+            TodoError::TodoNotFound => 5,
+            // INTERNAL
+            TodoError::RepositoryError(_) => 13,
+            // RESOURCE_EXHAUSTED
+            TodoError::QuotaExceeded => 8,
+        }
+    }
+
+    /// JSON-RPC error code, in the range reserved for server errors
+    pub const fn jsonrpc_code(&self) -> i64 {
+        match self {
+            TodoError::EmptyDescription => -32001,
+            TodoError::InvalidStateTransition => -32002,
+            TodoError::TodoNotFound => -32003,
+            TodoError::RepositoryError(_) => -32004,
+            TodoError::QuotaExceeded => -32005,
+            TodoError::ValidationError(_) => -32006,
+        }
+    }
+
+    /// RFC 9457 problem details describing this error
+    pub fn problem(&self) -> ProblemDetails {
+        ProblemDetails {
+            problem_type: format!("urn:hk-todo:error:{}", self.code()),
+            title: self.title().to_string(),
+            status: self.http_status(),
+            detail: self.to_string(),
+            code: self.code().to_string(),
+        }
+    }
+}
+
+/// How each front end reports an `AuthError`
+///
+/// | Error | `code` | HTTP | gRPC |
+/// |---|---|---|---|
+/// | `MissingCredentials` | `MISSING_CREDENTIALS` | 401 | `UNAUTHENTICATED` |
+/// | `InvalidToken` | `INVALID_TOKEN` | 401 | `UNAUTHENTICATED` |
+/// | `InsufficientScope` | `INSUFFICIENT_SCOPE` | 403 | `PERMISSION_DENIED` |
+/// | `Forbidden` | `FORBIDDEN` | 403 | `PERMISSION_DENIED` |
+/// | `Unavailable` | `AUTHENTICATION_UNAVAILABLE` | 503 | `UNAVAILABLE` |
+impl AuthError {
+    /// Stable name of the error, as serialized in the `code` field
+    pub const fn code(&self) -> &'static str {
+        match self {
+            AuthError::MissingCredentials => "MISSING_CREDENTIALS",
+            AuthError::InvalidToken(_) => "INVALID_TOKEN",
+            AuthError::InsufficientScope(_) => "INSUFFICIENT_SCOPE",
+            AuthError::Forbidden(_) => "FORBIDDEN",
+            AuthError::Unavailable(_) => "AUTHENTICATION_UNAVAILABLE",
+        }
+    }
+
+    /// Short summary of the kind of error, the same for every occurrence
+    pub const fn title(&self) -> &'static str {
+        match self {
+            AuthError::MissingCredentials => "Missing credentials",
+            AuthError::InvalidToken(_) => "Invalid token",
+            AuthError::InsufficientScope(_) => "Insufficient scope",
+            AuthError::Forbidden(_) => "Forbidden",
+            AuthError::Unavailable(_) => "Authentication unavailable",
+        }
+    }
+
+    /// HTTP status code
+    pub const fn http_status(&self) -> u16 {
+        match self {
+            AuthError::MissingCredentials | AuthError::InvalidToken(_) => 401,
+            AuthError::InsufficientScope(_) | AuthError::Forbidden(_) => 403,
+            AuthError::Unavailable(_) => 503,
+        }
+    }
+
+    /// gRPC status code, as its number in `google.rpc.Code`
+    pub const fn grpc_code(&self) -> i32 {
+        match self {
+            // UNAUTHENTICATED
+            AuthError::MissingCredentials | AuthError::InvalidToken(_) => 16,
+            // PERMISSION_DENIED
+            AuthError::InsufficientScope(_) | AuthError::Forbidden(_) => 7,
+            // UNAVAILABLE
+            AuthError::Unavailable(_) => 14,
+        }
+    }
+
+    /// RFC 9457 problem details describing this error
+    pub fn problem(&self) -> ProblemDetails {
+        ProblemDetails {
+            problem_type: format!("urn:hk-todo:error:{}", self.code()),
+            title: self.title().to_string(),
+            status: self.http_status(),
+            detail: self.to_string(),
+            code: self.code().to_string(),
+        }
+    }
+}
+
+/// An RFC 9457 problem details body, served as `application/problem+json`
+///
+/// `code` is an extension member carrying `TodoError::code` or `AuthError::code`, so
+/// clients can match on it without parsing `type`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProblemDetails {
+    /// `urn:hk-todo:error:<code>`
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    /// The error's message
+    pub detail: String,
+    pub code: String,
+}
@@ -0,0 +1,174 @@
+use chrono::{DateTime, Days, Utc};
+use std::sync::Arc;
+
+use crate::application::metrics::{observe, record_events};
+use crate::application::retention::{events_of, state_since};
+use crate::domain::escalation::{EscalationRule, EscalationRuleRepository};
+use crate::{
+    Clock, EventSink, EventStore, SystemClock, Todo, TodoError, TodoEvent, TodoRepository,
+};
+
+/// A todo left in a state for longer than an EscalationRule allows
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Escalation {
+    pub todo: Todo,
+    pub rule: EscalationRule,
+    /// When the todo entered the rule's state
+    pub since: DateTime<Utc>,
+}
+
+/// Finds the todos left in a state for too long by the rules of an EscalationRuleRepository
+///
+/// A todo's time in its state counts from its last change to that state in the event store
+/// given with `with_event_store`, or from its creation when the store has no such change or
+/// there is no store. Every rule applies to every todo of the repository, which for a
+/// `TenantTodoRepository` are those of its tenant's list.
+pub struct EscalateTodosHandler<R = Box<dyn TodoRepository>> {
+    todo_repository: R,
+    rules: Arc<dyn EscalationRuleRepository>,
+    event_store: Option<Arc<dyn EventStore>>,
+    event_sink: Option<Arc<dyn EventSink>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl<R: TodoRepository> EscalateTodosHandler<R> {
+    pub fn new(todo_repository: R, rules: Arc<dyn EscalationRuleRepository>) -> Self {
+        Self {
+            todo_repository,
+            rules,
+            event_store: None,
+            event_sink: None,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Reads state change times and earlier `TodoStale` events from `event_store`, and
+    /// records the new `TodoStale` events in it
+    pub fn with_event_store(mut self, event_store: Arc<dyn EventStore>) -> Self {
+        self.event_sink = Some(event_store.clone());
+        self.event_store = Some(event_store);
+        self
+    }
+
+    /// Passes the `TodoStale` events to `event_sink`
+    pub fn with_event_sink(mut self, event_sink: Arc<dyn EventSink>) -> Self {
+        self.event_sink = Some(event_sink);
+        self
+    }
+
+    /// Measures ages and stamps events with `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Lists the todos currently left in a state for too long, once per rule they break
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "get_escalated_todos"), err)
+    )]
+    pub async fn escalated(&self) -> Result<Vec<Escalation>, TodoError> {
+        observe("get_escalated_todos", self.find_escalations()).await
+    }
+
+    /// Emits a `TodoStale` event for every todo that broke a rule since it entered its state,
+    /// and raises its priority by one level
+    ///
+    /// A todo breaking a rule is reported once per stay in the rule's state: the runs after
+    /// the first skip it while the event store holds the `TodoStale` of that rule. Without an
+    /// event store every run reports, and raises, it again. A todo breaking several rules in
+    /// one run is raised once, and `Urgent` todos stay `Urgent`.
+    ///
+    /// # Returns
+    /// - `Ok(Vec<TodoEvent>)`: For each reported todo, its `TodoPriorityChanged` event unless
+    ///   it was already `Urgent`, then its new `TodoStale` events, with the todos' ids as
+    ///   stored
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "escalate_todos"), err)
+    )]
+    pub async fn escalate(&self) -> Result<Vec<TodoEvent>, TodoError> {
+        observe("escalate_todos", async {
+            let now = self.clock.now();
+            let mut events = Vec::new();
+            let mut seen: Vec<Arc<str>> = Vec::new();
+            let mut raised = Vec::new();
+            for escalation in self.find_escalations().await? {
+                if self.was_reported(&escalation)? {
+                    continue;
+                }
+                if !seen.contains(&escalation.todo.id) {
+                    seen.push(escalation.todo.id.clone());
+                    let mut todo = escalation.todo.clone();
+                    let changed =
+                        todo.set_priority_with_clock(todo.priority.raised(), &*self.clock);
+                    if !changed.is_empty() {
+                        raised.push(todo);
+                    }
+                    events.extend(changed);
+                }
+                events.push(TodoEvent::TodoStale {
+                    id: escalation.todo.id,
+                    rule: escalation.rule.id,
+                    state: escalation.rule.state,
+                    stale_at: now,
+                });
+            }
+            if !raised.is_empty() {
+                self.todo_repository.save_all(&raised).await?;
+            }
+            record_events(&events);
+            if let Some(event_sink) = &self.event_sink
+                && !events.is_empty()
+            {
+                event_sink.record(&events)?;
+            }
+            Ok(events)
+        })
+        .await
+    }
+
+    async fn find_escalations(&self) -> Result<Vec<Escalation>, TodoError> {
+        let rules = self.rules.find_all().await?;
+        let now = self.clock.now();
+        let mut escalations = Vec::new();
+        for todo in self.todo_repository.find_all().await? {
+            let matching: Vec<_> = rules
+                .iter()
+                .filter(|rule| rule.state == todo.state)
+                .collect();
+            if matching.is_empty() {
+                continue;
+            }
+            let since = state_since(self.event_store.as_deref(), &todo)?;
+            for rule in matching {
+                let cutoff = now
+                    .checked_sub_days(Days::new(rule.after_days.into()))
+                    .unwrap_or(DateTime::<Utc>::MIN_UTC);
+                if since <= cutoff {
+                    escalations.push(Escalation {
+                        todo: todo.clone(),
+                        rule: rule.clone(),
+                        since,
+                    });
+                }
+            }
+        }
+        Ok(escalations)
+    }
+
+    /// Whether the event store has a `TodoStale` of the escalation's rule since the todo
+    /// entered its state
+    fn was_reported(&self, escalation: &Escalation) -> Result<bool, TodoError> {
+        let Some(store) = self.event_store.as_deref() else {
+            return Ok(false);
+        };
+        Ok(events_of(store, &escalation.todo)?.iter().any(|event| {
+            matches!(
+                event,
+                TodoEvent::TodoStale { rule, stale_at, .. }
+                    if *rule == escalation.rule.id && *stale_at >= escalation.since
+            )
+        }))
+    }
+}
@@ -0,0 +1,130 @@
+use futures::StreamExt;
+use std::io::Write;
+
+use crate::application::metrics::observe;
+#[cfg(feature = "csv")]
+use crate::infrastructure::serialization::csv::{self, CsvOptions};
+use crate::infrastructure::serialization::{
+    SerializationError, json, markdown, ndjson, org, taskwarrior,
+};
+use crate::{Todo, TodoError, TodoRepository};
+
+pub struct ExportTodosHandler<R = Box<dyn TodoRepository>> {
+    todo_repository: R,
+}
+
+impl<R: TodoRepository> ExportTodosHandler<R> {
+    pub fn new(todo_repository: R) -> Self {
+        Self { todo_repository }
+    }
+
+    /// Writes every todo, oldest first, as a versioned JSON document and returns how many
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "export_todos", format = "json"), err)
+    )]
+    pub async fn export_todos(&self, writer: impl Write) -> Result<usize, TodoError> {
+        observe("export_todos", async move {
+            let todos = self.todos().await?;
+            json::export_todos(writer, &todos)?;
+            Ok(todos.len())
+        })
+        .await
+    }
+
+    /// Writes every todo, oldest first, as CSV laid out by `options` and returns how many
+    #[cfg(feature = "csv")]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "export_todos", format = "csv"), err)
+    )]
+    pub async fn export_csv(
+        &self,
+        writer: impl Write,
+        options: &CsvOptions,
+    ) -> Result<usize, TodoError> {
+        observe("export_todos", async move {
+            let todos = self.todos().await?;
+            csv::export_todos(writer, &todos, options)?;
+            Ok(todos.len())
+        })
+        .await
+    }
+
+    /// Writes every todo, oldest first, as Org headlines and returns how many
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "export_todos", format = "org"), err)
+    )]
+    pub async fn export_org(&self, writer: impl Write) -> Result<usize, TodoError> {
+        observe("export_todos", async move {
+            let todos = self.todos().await?;
+            org::export_todos(writer, &todos)?;
+            Ok(todos.len())
+        })
+        .await
+    }
+
+    /// Writes every todo, oldest first, as a Markdown task list and returns how many
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "export_todos", format = "markdown"), err)
+    )]
+    pub async fn export_markdown(&self, writer: impl Write) -> Result<usize, TodoError> {
+        observe("export_todos", async move {
+            let todos = self.todos().await?;
+            markdown::export_todos(writer, &todos)?;
+            Ok(todos.len())
+        })
+        .await
+    }
+
+    /// Writes every todo, oldest first, as Taskwarrior tasks for `task import` and returns
+    /// how many
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(command = "export_todos", format = "taskwarrior"),
+            err
+        )
+    )]
+    pub async fn export_taskwarrior(&self, writer: impl Write) -> Result<usize, TodoError> {
+        observe("export_todos", async move {
+            let todos = self.todos().await?;
+            taskwarrior::export_todos(writer, &todos)?;
+            Ok(todos.len())
+        })
+        .await
+    }
+
+    /// Writes every todo as one JSON object per line, in repository order, and returns how many
+    ///
+    /// Todos are written as the repository streams them, so memory stays bounded for
+    /// backends that override `TodoRepository::stream_all`.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "export_todos", format = "ndjson"), err)
+    )]
+    pub async fn export_ndjson(&self, mut writer: impl Write) -> Result<usize, TodoError> {
+        observe("export_todos", async move {
+            let mut todos = self.todo_repository.stream_all();
+            let mut count = 0;
+            while let Some(todo) = todos.next().await {
+                ndjson::write_todo(&mut writer, &todo?)?;
+                count += 1;
+            }
+            writer
+                .flush()
+                .map_err(|err| SerializationError::Io(err.to_string()))?;
+            Ok(count)
+        })
+        .await
+    }
+
+    async fn todos(&self) -> Result<Vec<Todo>, TodoError> {
+        let mut todos = self.todo_repository.find_all().await?;
+        todos.sort_by_key(|todo| todo.created_at);
+        Ok(todos)
+    }
+}
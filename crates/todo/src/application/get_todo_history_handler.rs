@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use crate::application::metrics::observe;
+use crate::{EventStore, TodoError, TodoEvent};
+
+/// Reads the change log of a single todo from an EventStore
+///
+/// The history covers the events the store recorded, so it outlives the todo: a deleted
+/// todo keeps its history. Events carry no caller, so the history tells what changed and
+/// when, not who changed it.
+pub struct GetTodoHistoryHandler {
+    event_store: Arc<dyn EventStore>,
+}
+
+impl GetTodoHistoryHandler {
+    pub fn new(event_store: Arc<dyn EventStore>) -> Self {
+        Self { event_store }
+    }
+
+    /// Returns the events of the todo with `id`, oldest first
+    ///
+    /// # Returns
+    /// - `Err(TodoError::TodoNotFound)`: If the store recorded no event for the todo
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "get_todo_history", todo.id = %id), err)
+    )]
+    pub async fn get_history(&self, id: &str) -> Result<Vec<TodoEvent>, TodoError> {
+        observe("get_todo_history", async {
+            let events = self.event_store.find_by_todo(id)?;
+            if events.is_empty() {
+                return Err(TodoError::TodoNotFound);
+            }
+            Ok(events)
+        })
+        .await
+    }
+}
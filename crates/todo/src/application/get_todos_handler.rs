@@ -1,16 +1,126 @@
-use crate::{Todo, TodoError, TodoRepository};
+use crate::application::metrics::observe;
+use crate::domain::todo::FilterTerm;
+use crate::{Todo, TodoError, TodoFilter, TodoRepository, TodoState};
+use chrono::{DateTime, Days, Offset, TimeZone, Utc};
 
-pub struct GetTodosHandler {
-    todo_repository: Box<dyn TodoRepository>,
+/// Todos that are not done, sorted by their due date relative to a moment and a timezone
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DueBuckets {
+    /// Due before the moment
+    pub overdue: Vec<Todo>,
+    /// Due from the moment until the end of its day in the timezone
+    pub today: Vec<Todo>,
+    /// Due on a later day in the timezone
+    pub upcoming: Vec<Todo>,
 }
 
-impl GetTodosHandler {
-    pub fn new(todo_repository: Box<dyn TodoRepository>) -> Self {
+pub struct GetTodosHandler<R = Box<dyn TodoRepository>> {
+    todo_repository: R,
+}
+
+impl<R: TodoRepository> GetTodosHandler<R> {
+    pub fn new(todo_repository: R) -> Self {
         Self { todo_repository }
     }
 
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "get_todos"), err)
+    )]
     pub async fn get_todos(&self) -> Result<Vec<Todo>, TodoError> {
-        let todos = self.todo_repository.find_all().await?;
+        observe("get_todos", async {
+            let todos = self.todo_repository.find_all().await?;
+            Ok(todos)
+        })
+        .await
+    }
+
+    /// Returns the todos `filter` matches, such as those of a search query parsed with
+    /// `TodoFilter::parse`
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "search_todos"), err)
+    )]
+    pub async fn search_todos(&self, filter: &TodoFilter) -> Result<Vec<Todo>, TodoError> {
+        observe("search_todos", async {
+            let todos = self.todo_repository.find_matching(filter).await?;
+            Ok(todos)
+        })
+        .await
+    }
+
+    /// Returns every todo, most urgent first, then oldest first within a priority
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "get_todos_by_priority"), err)
+    )]
+    pub async fn get_todos_by_priority(&self) -> Result<Vec<Todo>, TodoError> {
+        observe("get_todos_by_priority", async {
+            let mut todos = self.todo_repository.find_all().await?;
+            todos.sort_by(|a, b| {
+                b.priority
+                    .cmp(&a.priority)
+                    .then(a.created_at.cmp(&b.created_at))
+                    .then_with(|| a.id.cmp(&b.id))
+            });
+            Ok(todos)
+        })
+        .await
+    }
+
+    /// Returns the todos that are not done and have a due date, bucketed relative to `now`
+    /// and the days of `timezone`, each bucket soonest due first
+    ///
+    /// Each bucket is read from the repository with its own filter, so backends that
+    /// implement `find_matching` natively only return the todos of that bucket.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "get_todos_by_due_date"), err)
+    )]
+    pub async fn get_todos_by_due_date<Tz: TimeZone>(
+        &self,
+        timezone: &Tz,
+        now: DateTime<Utc>,
+    ) -> Result<DueBuckets, TodoError> {
+        observe("get_todos_by_due_date", async {
+            let tomorrow = start_of_next_day(timezone, now);
+            Ok(DueBuckets {
+                overdue: self.due_between(None, Some(now)).await?,
+                today: self.due_between(Some(now), Some(tomorrow)).await?,
+                upcoming: self.due_between(Some(tomorrow), None).await?,
+            })
+        })
+        .await
+    }
+
+    async fn due_between(
+        &self,
+        from: Option<DateTime<Utc>>,
+        before: Option<DateTime<Utc>>,
+    ) -> Result<Vec<Todo>, TodoError> {
+        let filter = TodoFilter::new()
+            .with(FilterTerm::Due { from, before })
+            .with(FilterTerm::Not(Box::new(FilterTerm::State(
+                TodoState::Done,
+            ))));
+        let mut todos = self.todo_repository.find_matching(&filter).await?;
+        todos.sort_by(|a, b| a.due_at.cmp(&b.due_at).then_with(|| a.id.cmp(&b.id)));
         Ok(todos)
     }
 }
+
+/// The first moment of the day after `now`'s in `timezone`, falling back to `now`'s UTC
+/// offset when that midnight does not exist there
+fn start_of_next_day<Tz: TimeZone>(timezone: &Tz, now: DateTime<Utc>) -> DateTime<Utc> {
+    let local = now.with_timezone(timezone);
+    let midnight = local
+        .date_naive()
+        .checked_add_days(Days::new(1))
+        .unwrap_or(local.date_naive())
+        .and_hms_opt(0, 0, 0)
+        .unwrap_or_default();
+    match timezone.from_local_datetime(&midnight).earliest() {
+        Some(start) => start.with_timezone(&Utc),
+        None => midnight.and_utc() - local.offset().fix(),
+    }
+}
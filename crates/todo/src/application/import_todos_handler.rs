@@ -0,0 +1,293 @@
+use std::collections::HashMap;
+use std::io::Read;
+use std::sync::Arc;
+
+use crate::application::metrics::{observe, record_events};
+use crate::domain::import::{ImportMapping, ImportMappingRepository, ImportPolicy};
+#[cfg(feature = "csv")]
+use crate::infrastructure::serialization::SerializationError;
+#[cfg(feature = "csv")]
+use crate::infrastructure::serialization::csv::{self, CsvOptions};
+use crate::infrastructure::serialization::{json, markdown, taskwarrior};
+use crate::{Clock, EventSink, SystemClock, Todo, TodoError, TodoEvent, TodoRepository};
+
+/// Number of todos written per `save_all` call, so a large import does not hold every write
+/// in one repository operation
+const IMPORT_BATCH: usize = 10_000;
+
+/// Imports todos from exchange formats
+///
+/// An item is recognized as imported before when a todo has its id, or when the mappings
+/// given with `with_import_mappings` link its id in that format to a todo. The latter keeps
+/// working once the todo has another id, and remembers the values of the last import,
+/// which `ImportPolicy::Merge` needs. What happens to such items is set by `with_policy`;
+/// they are skipped by default. Items whose todo was deleted since are not imported again.
+pub struct ImportTodosHandler<R = Box<dyn TodoRepository>> {
+    todo_repository: R,
+    event_sink: Option<Arc<dyn EventSink>>,
+    mappings: Option<Arc<dyn ImportMappingRepository>>,
+    policy: ImportPolicy,
+    clock: Arc<dyn Clock>,
+}
+
+impl<R: TodoRepository> ImportTodosHandler<R> {
+    pub fn new(todo_repository: R) -> Self {
+        Self {
+            todo_repository,
+            event_sink: None,
+            mappings: None,
+            policy: ImportPolicy::default(),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Passes the events of every saved change to `event_sink`
+    pub fn with_event_sink(mut self, event_sink: Arc<dyn EventSink>) -> Self {
+        self.event_sink = Some(event_sink);
+        self
+    }
+
+    /// Links imported items to their todos in `mappings`, by format and external id
+    pub fn with_import_mappings(mut self, mappings: Arc<dyn ImportMappingRepository>) -> Self {
+        self.mappings = Some(mappings);
+        self
+    }
+
+    /// Handles items imported before according to `policy` instead of skipping them
+    pub fn with_policy(mut self, policy: ImportPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Stamps state changes and mappings with `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn record(&self, events: &[TodoEvent]) -> Result<(), TodoError> {
+        match &self.event_sink {
+            Some(event_sink) => event_sink.record(events),
+            None => Ok(()),
+        }
+    }
+
+    /// Saves the todos of a document written by `ExportTodosHandler`
+    ///
+    /// # Returns
+    /// - `Ok(Vec<TodoEvent>)`: `TodoCreated` for each imported todo, and `TodoStateChanged`
+    ///   for each todo whose state the policy updated
+    /// - `Err(TodoError::RepositoryError)`: If the document is invalid; nothing is saved
+    ///
+    /// # Special Requirements
+    /// - Keeps the ids, states and creation times from the document
+    /// - Skips todos imported before unless the policy says otherwise, so importing the same
+    ///   document twice is a no-op
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "import_todos", format = "json"), err)
+    )]
+    pub async fn import_todos(&self, reader: impl Read) -> Result<Vec<TodoEvent>, TodoError> {
+        observe("import_todos", async move {
+            let todos = json::import_todos(reader)?;
+            self.save_imported("json", todos).await
+        })
+        .await
+    }
+
+    /// Saves the valid rows of a CSV file laid out by `options`
+    ///
+    /// # Returns
+    /// - `Ok((Vec<TodoEvent>, Vec<SerializationError>))`: The events, like `import_todos`,
+    ///   and an error for each row that was not a valid todo
+    /// - `Err(TodoError::RepositoryError)`: If the file cannot be read at all
+    ///
+    /// # Special Requirements
+    /// - Handles todos imported before like `import_todos`
+    #[cfg(feature = "csv")]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "import_todos", format = "csv"), err)
+    )]
+    pub async fn import_csv(
+        &self,
+        reader: impl Read,
+        options: &CsvOptions,
+    ) -> Result<(Vec<TodoEvent>, Vec<SerializationError>), TodoError> {
+        observe("import_todos", async move {
+            let import = csv::import_todos(reader, options)?;
+            let events = self.save_imported("csv", import.todos).await?;
+            Ok((events, import.errors))
+        })
+        .await
+    }
+
+    /// Saves the tasks of a Taskwarrior `task export`
+    ///
+    /// # Returns
+    /// - `Ok(Vec<TodoEvent>)`: The events, like `import_todos`
+    /// - `Err(TodoError::RepositoryError)`: If the export is invalid; nothing is saved
+    ///
+    /// # Special Requirements
+    /// - Todos keep the task's uuid as id, so tasks imported before are recognized
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(command = "import_todos", format = "taskwarrior"),
+            err
+        )
+    )]
+    pub async fn import_taskwarrior(&self, reader: impl Read) -> Result<Vec<TodoEvent>, TodoError> {
+        observe("import_todos", async move {
+            let todos = taskwarrior::import_todos(reader)?;
+            self.save_imported("taskwarrior", todos).await
+        })
+        .await
+    }
+
+    /// Saves the task list items of a Markdown document
+    ///
+    /// # Returns
+    /// - `Ok(Vec<TodoEvent>)`: The events, like `import_todos`
+    /// - `Err(TodoError::RepositoryError)`: If two items share an id; nothing is saved
+    ///
+    /// # Special Requirements
+    /// - Items exported with an id comment keep it, so they are recognized when imported
+    ///   again
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "import_todos", format = "markdown"), err)
+    )]
+    pub async fn import_markdown(&self, reader: impl Read) -> Result<Vec<TodoEvent>, TodoError> {
+        observe("import_todos", async move {
+            let todos = markdown::import_todos(reader)?;
+            self.save_imported("markdown", todos).await
+        })
+        .await
+    }
+
+    /// Saves the new todos of `imported`, read from `source`, and applies the policy to
+    /// those imported before
+    pub(crate) async fn save_imported(
+        &self,
+        source: &str,
+        imported: Vec<Todo>,
+    ) -> Result<Vec<TodoEvent>, TodoError> {
+        let mut existing: HashMap<Arc<str>, Option<Todo>> = self
+            .todo_repository
+            .find_all()
+            .await?
+            .into_iter()
+            .map(|todo| (todo.id.clone(), Some(todo)))
+            .collect();
+        let known: HashMap<Arc<str>, ImportMapping> = match &self.mappings {
+            Some(mappings) => mappings
+                .find_by_source(source)
+                .await?
+                .into_iter()
+                .map(|mapping| (mapping.external_id.clone(), mapping))
+                .collect(),
+            None => HashMap::new(),
+        };
+        let now = self.clock.now();
+        let mut new = Vec::new();
+        let mut updated = Vec::new();
+        let mut changed = Vec::new();
+        let mut mappings = Vec::new();
+        for item in imported {
+            let mapping = known.get(&item.id);
+            let todo_id = mapping.map_or(&item.id, |mapping| &mapping.todo_id).clone();
+            match existing.get_mut(&todo_id) {
+                // Deleted since the last import, or the same item twice in one import
+                None if mapping.is_some() => continue,
+                Some(None) => continue,
+                None => {
+                    existing.insert(todo_id.clone(), None);
+                    new.push(item.clone());
+                }
+                Some(slot) => {
+                    let Some(mut todo) = slot.take() else {
+                        continue;
+                    };
+                    if let Some(events) = self.apply_policy(&mut todo, &item, mapping, now) {
+                        changed.extend(events);
+                        updated.push(todo);
+                    }
+                }
+            }
+            mappings.push(ImportMapping::new(source, &item, todo_id, now));
+        }
+
+        let mut events = Vec::new();
+        for batch in new.chunks(IMPORT_BATCH) {
+            self.todo_repository.save_all(batch).await?;
+            let created: Vec<TodoEvent> = batch
+                .iter()
+                .map(|todo| TodoEvent::TodoCreated {
+                    id: todo.id.clone(),
+                    description: todo.description.clone(),
+                    created_at: todo.created_at,
+                })
+                .collect();
+            self.record(&created)?;
+            events.extend(created);
+        }
+        for batch in updated.chunks(IMPORT_BATCH) {
+            self.todo_repository.save_all(batch).await?;
+        }
+        self.record(&changed)?;
+        events.extend(changed);
+        if let Some(repository) = &self.mappings {
+            repository.save_all(&mappings).await?;
+        }
+        record_events(&events);
+        Ok(events)
+    }
+
+    /// Updates `todo` from `item` as the policy says
+    ///
+    /// # Returns
+    /// - `Some(Vec<TodoEvent>)`: If the todo changed, with `TodoStateChanged` if its state did
+    /// - `None`: If the todo is left as it is
+    fn apply_policy(
+        &self,
+        todo: &mut Todo,
+        item: &Todo,
+        mapping: Option<&ImportMapping>,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Option<Vec<TodoEvent>> {
+        let (description, state) = match (self.policy, mapping) {
+            (ImportPolicy::Skip, _) | (ImportPolicy::Merge, None) => return None,
+            (ImportPolicy::Update, _) => (item.description.clone(), item.state),
+            (ImportPolicy::Merge, Some(last)) => (
+                if todo.description == last.description {
+                    item.description.clone()
+                } else {
+                    todo.description.clone()
+                },
+                if todo.state == last.state {
+                    item.state
+                } else {
+                    todo.state
+                },
+            ),
+        };
+        if description == todo.description && state == todo.state {
+            return None;
+        }
+        let mut events = Vec::new();
+        if state != todo.state {
+            events.push(TodoEvent::TodoStateChanged {
+                id: todo.id.clone(),
+                from_state: todo.state,
+                to_state: state,
+                changed_at: now,
+            });
+        }
+        todo.description = description;
+        todo.state = state;
+        todo.dirty = Some(true);
+        Some(events)
+    }
+}
@@ -0,0 +1,183 @@
+use std::sync::Arc;
+
+use crate::TenantId;
+use crate::domain::access::{
+    AccessError, Invitation, InvitationEvent, InvitationRepository, InvitationStatus, Membership,
+    MembershipRepository, Role,
+};
+use crate::{Clock, IdGenerator, SystemClock, UuidV4Generator};
+
+/// Invites members to todo lists, and answers invitations
+///
+/// Only owners of a list may invite to it. The invitee is a subject or an email address;
+/// whoever authenticates as that subject, or with a subject equal to that address, may
+/// accept or decline. Accepting saves the invitee's Membership of the list, after which the
+/// access control lets them act on it and `GetSharedListsHandler` shows it to them.
+pub struct InvitationHandler {
+    invitations: Arc<dyn InvitationRepository>,
+    memberships: Arc<dyn MembershipRepository>,
+    clock: Arc<dyn Clock>,
+    ids: Arc<dyn IdGenerator>,
+}
+
+impl InvitationHandler {
+    pub fn new(
+        invitations: Arc<dyn InvitationRepository>,
+        memberships: Arc<dyn MembershipRepository>,
+    ) -> Self {
+        Self {
+            invitations,
+            memberships,
+            clock: Arc::new(SystemClock),
+            ids: Arc::new(UuidV4Generator),
+        }
+    }
+
+    /// Stamps invitations and their answers with `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Gives invitations ids from `ids` instead of random UUIDv4s
+    pub fn with_id_generator(mut self, ids: Arc<dyn IdGenerator>) -> Self {
+        self.ids = ids;
+        self
+    }
+
+    /// Invites `invitee` to `list` with `role`, on behalf of `inviter`
+    ///
+    /// # Returns
+    /// - `Ok((Invitation, InvitationEvent::InvitationSent))`: The stored invitation
+    /// - `Err(AccessError::NotPermitted)`: If `inviter` is not an owner of the list
+    /// - `Err(AccessError::InvalidInvitation)`: If `invitee` is blank, already a member of
+    ///   the list, or has a pending invitation to it
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "invite_member", list = %list, role = role.as_str()), err)
+    )]
+    pub async fn invite(
+        &self,
+        inviter: &str,
+        list: &TenantId,
+        invitee: &str,
+        role: Role,
+    ) -> Result<(Invitation, InvitationEvent), AccessError> {
+        self.require_owner(list, inviter, "invite to").await?;
+        let (invitation, event) = Invitation::new(
+            self.ids.next_id(),
+            list.clone(),
+            invitee,
+            role,
+            inviter,
+            self.clock.now(),
+        )?;
+        if self.memberships.find(list, invitee).await?.is_some() {
+            return Err(AccessError::InvalidInvitation(format!(
+                "{} is already a member of list {}",
+                invitee, list
+            )));
+        }
+        let pending = self.invitations.find_by_list(list).await?;
+        if pending.iter().any(|existing| {
+            existing.status == InvitationStatus::Pending && existing.is_for(invitee)
+        }) {
+            return Err(AccessError::InvalidInvitation(format!(
+                "{} already has a pending invitation to list {}",
+                invitee, list
+            )));
+        }
+        self.invitations.save(&invitation).await?;
+        Ok((invitation, event))
+    }
+
+    /// Accepts the invitation `id` addressed to `subject`, making them a member of its list
+    /// with the offered role, in place of any role they had there
+    ///
+    /// # Returns
+    /// - `Ok((Membership, InvitationEvent::InvitationAccepted))`: The saved membership
+    /// - `Err(AccessError::InvitationNotFound)`: If there is no such invitation to `subject`
+    /// - `Err(AccessError::InvalidInvitation)`: If it was answered already
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "accept_invitation"), err)
+    )]
+    pub async fn accept(
+        &self,
+        id: &str,
+        subject: &str,
+    ) -> Result<(Membership, InvitationEvent), AccessError> {
+        let mut invitation = self.find(id, subject).await?;
+        let (membership, event) = invitation.accept(subject, self.clock.now())?;
+        self.memberships.save(&membership).await?;
+        self.invitations.save(&invitation).await?;
+        Ok((membership, event))
+    }
+
+    /// Declines the invitation `id` addressed to `subject`
+    ///
+    /// # Returns
+    /// - `Ok(InvitationEvent::InvitationDeclined)`
+    /// - `Err(AccessError::InvitationNotFound)`: If there is no such invitation to `subject`
+    /// - `Err(AccessError::InvalidInvitation)`: If it was answered already
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "decline_invitation"), err)
+    )]
+    pub async fn decline(&self, id: &str, subject: &str) -> Result<InvitationEvent, AccessError> {
+        let mut invitation = self.find(id, subject).await?;
+        let event = invitation.decline(self.clock.now())?;
+        self.invitations.save(&invitation).await?;
+        Ok(event)
+    }
+
+    /// Lists the invitations addressed to `subject` that are not answered yet, oldest first
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "get_pending_invitations"), err)
+    )]
+    pub async fn pending(&self, subject: &str) -> Result<Vec<Invitation>, AccessError> {
+        let mut invitations = self.invitations.find_by_invitee(subject).await?;
+        invitations.retain(|invitation| invitation.status == InvitationStatus::Pending);
+        Ok(invitations)
+    }
+
+    /// Lists every invitation to `list`, answered or not, for one of its owners
+    ///
+    /// # Returns
+    /// - `Err(AccessError::NotPermitted)`: If `owner` is not an owner of the list
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "get_list_invitations", list = %list), err)
+    )]
+    pub async fn sent(&self, owner: &str, list: &TenantId) -> Result<Vec<Invitation>, AccessError> {
+        self.require_owner(list, owner, "see the invitations to")
+            .await?;
+        self.invitations.find_by_list(list).await
+    }
+
+    /// Checks that `subject` is an owner of `list`, who alone may `what` it
+    async fn require_owner(
+        &self,
+        list: &TenantId,
+        subject: &str,
+        what: &str,
+    ) -> Result<(), AccessError> {
+        match self.memberships.find(list, subject).await? {
+            Some(membership) if membership.role == Role::Owner => Ok(()),
+            _ => Err(AccessError::NotPermitted(format!(
+                "only owners may {} list {}",
+                what, list
+            ))),
+        }
+    }
+
+    /// The invitation `id`, if it is addressed to `subject`
+    async fn find(&self, id: &str, subject: &str) -> Result<Invitation, AccessError> {
+        self.invitations
+            .find_by_id(id)
+            .await?
+            .filter(|invitation| invitation.is_for(subject))
+            .ok_or(AccessError::InvitationNotFound)
+    }
+}
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use crate::domain::api_key::{ApiKey, ApiKeyError, ApiKeyRepository, ApiKeyScope};
+use crate::{Clock, IdGenerator, SystemClock, UuidV4Generator};
+
+/// A newly issued key with its token, which is not stored and cannot be shown again
+#[derive(Debug, Clone)]
+pub struct IssuedApiKey {
+    pub key: ApiKey,
+    /// `hkt_<id>_<secret>`, for the client's `Authorization: Bearer` header
+    pub token: String,
+}
+
+pub struct IssueApiKeyHandler<R = Box<dyn ApiKeyRepository>> {
+    api_key_repository: R,
+    clock: Arc<dyn Clock>,
+    ids: Arc<dyn IdGenerator>,
+}
+
+impl<R: ApiKeyRepository> IssueApiKeyHandler<R> {
+    pub fn new(api_key_repository: R) -> Self {
+        Self {
+            api_key_repository,
+            clock: Arc::new(SystemClock),
+            ids: Arc::new(UuidV4Generator),
+        }
+    }
+
+    /// Stamps issued keys with `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Gives issued keys ids from `ids` instead of random UUIDv4s
+    pub fn with_id_generator(mut self, ids: Arc<dyn IdGenerator>) -> Self {
+        self.ids = ids;
+        self
+    }
+
+    /// Issues a key named `name` and stores it
+    ///
+    /// # Returns
+    /// - `Ok(IssuedApiKey)`: The stored key and its token
+    /// - `Err(ApiKeyError::EmptyName)`: If `name` is blank
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "issue_api_key", scope = scope.as_str()), err)
+    )]
+    pub async fn issue(
+        &self,
+        name: String,
+        scope: ApiKeyScope,
+    ) -> Result<IssuedApiKey, ApiKeyError> {
+        let (key, token) = ApiKey::issue(name, scope, &*self.clock, &*self.ids)?;
+        self.api_key_repository.save(&key).await?;
+        Ok(IssuedApiKey { key, token })
+    }
+}
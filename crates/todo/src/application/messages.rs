@@ -0,0 +1,408 @@
+use chrono::SecondsFormat;
+use std::collections::HashMap;
+
+use crate::application::auth::AuthError;
+use crate::application::error_mapping::ProblemDetails;
+use crate::{Priority, TodoError, TodoEvent, TodoState};
+
+/// A value substituted for a `{name}` placeholder of a message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MessageArg {
+    /// Substituted as is, such as a todo's description
+    Text(String),
+    /// A code, substituted with its own message in the same locale, such as a state name
+    Code(&'static str),
+}
+
+/// Errors and events that can be described in the user's language
+///
+/// `message_code` is the key of the message in a `MessageCatalog`; it is the same stable
+/// code front ends already expose, so clients keep matching on codes while showing the
+/// localized message.
+pub trait Localizable {
+    /// Stable code of the message, such as `TODO_NOT_FOUND`
+    fn message_code(&self) -> &'static str;
+
+    /// Values of the message's `{name}` placeholders
+    fn message_args(&self) -> Vec<(&'static str, MessageArg)> {
+        Vec::new()
+    }
+}
+
+/// Messages of every locale, keyed by code
+///
+/// A message is looked up in the requested locale, then in its primary language (`fr` for
+/// `fr-CA`), then in the default locale; when no locale has it, the code itself is returned.
+/// The title of an error is the message keyed `<code>.title`.
+///
+/// With the `serde` feature, `MessageCatalog::default()` holds the built-in `en` and `vi`
+/// messages, with `en` as the default locale.
+#[derive(Debug, Clone)]
+pub struct MessageCatalog {
+    default_locale: String,
+    locales: HashMap<String, HashMap<String, String>>,
+}
+
+impl MessageCatalog {
+    /// Creates a new MessageCatalog without messages, falling back to `default_locale`
+    pub fn new(default_locale: impl Into<String>) -> Self {
+        MessageCatalog {
+            default_locale: normalize(&default_locale.into()),
+            locales: HashMap::new(),
+        }
+    }
+
+    /// Adds `messages` to `locale`, replacing those with the same code
+    pub fn with_messages<K, V>(
+        mut self,
+        locale: &str,
+        messages: impl IntoIterator<Item = (K, V)>,
+    ) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.locales.entry(normalize(locale)).or_default().extend(
+            messages
+                .into_iter()
+                .map(|(code, message)| (code.into(), message.into())),
+        );
+        self
+    }
+
+    /// Adds the messages of `json`, an object of messages keyed by code, to `locale`
+    ///
+    /// # Returns
+    /// - `Err(String)`: If `json` is not an object of strings
+    #[cfg(feature = "serde")]
+    pub fn with_json(self, locale: &str, json: &str) -> Result<Self, String> {
+        let messages: HashMap<String, String> = serde_json::from_str(json)
+            .map_err(|err| format!("invalid messages for locale {}: {}", locale, err))?;
+        Ok(self.with_messages(locale, messages))
+    }
+
+    /// The locale used when no requested one has a message
+    pub fn default_locale(&self) -> &str {
+        &self.default_locale
+    }
+
+    /// The locales with messages, sorted
+    pub fn locales(&self) -> Vec<&str> {
+        let mut locales: Vec<&str> = self.locales.keys().map(String::as_str).collect();
+        locales.sort_unstable();
+        locales
+    }
+
+    /// The locale to answer in for an `Accept-Language` header value
+    ///
+    /// Languages are tried by decreasing `q` weight, each as is and then as its primary
+    /// language; `*`, an empty header or no known language gives the default locale.
+    pub fn negotiate(&self, accept_language: &str) -> &str {
+        let mut ranges: Vec<(&str, f32)> = accept_language
+            .split(',')
+            .filter_map(|range| {
+                let mut parts = range.split(';');
+                let tag = parts.next()?.trim();
+                let weight = parts
+                    .find_map(|param| param.trim().strip_prefix("q="))
+                    .map_or(Some(1.0), |q| q.trim().parse::<f32>().ok())?;
+                (!tag.is_empty() && weight > 0.0).then_some((tag, weight))
+            })
+            .collect();
+        // Stable, so equally weighted languages keep the client's order
+        ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+        ranges
+            .into_iter()
+            .find_map(|(tag, _)| self.supported(tag))
+            .unwrap_or(&self.default_locale)
+    }
+
+    /// The message of `item` in `locale`, with its placeholders substituted
+    pub fn message(&self, locale: &str, item: &dyn Localizable) -> String {
+        let code = item.message_code();
+        let Some(template) = self.lookup(locale, code) else {
+            return code.to_string();
+        };
+        let args: Vec<(&str, String)> = item
+            .message_args()
+            .into_iter()
+            .map(|(name, arg)| {
+                let value = match arg {
+                    MessageArg::Text(text) => text,
+                    MessageArg::Code(code) => self.lookup(locale, code).unwrap_or(code).to_string(),
+                };
+                (name, value)
+            })
+            .collect();
+        substitute(template, &args)
+    }
+
+    /// `problem` with its title and detail in `locale`, when the catalog has them
+    pub fn localize_problem(
+        &self,
+        locale: &str,
+        problem: ProblemDetails,
+        error: &dyn Localizable,
+    ) -> ProblemDetails {
+        let title_key = format!("{}.title", error.message_code());
+        let title = self.lookup(locale, &title_key).map(str::to_string);
+        let detail = match self.lookup(locale, error.message_code()) {
+            Some(_) => self.message(locale, error),
+            None => problem.detail,
+        };
+        ProblemDetails {
+            title: title.unwrap_or(problem.title),
+            detail,
+            ..problem
+        }
+    }
+
+    /// The locale of the catalog matching `tag` or its primary language
+    fn supported(&self, tag: &str) -> Option<&str> {
+        if tag == "*" {
+            return Some(self.default_locale.as_str());
+        }
+        let tag = normalize(tag);
+        let primary = tag.split('-').next().unwrap_or_default();
+        [tag.as_str(), primary]
+            .into_iter()
+            .find_map(|locale| self.locales.get_key_value(locale))
+            .map(|(locale, _)| locale.as_str())
+    }
+
+    /// The message of `code` in `locale`, its primary language or the default locale
+    fn lookup(&self, locale: &str, code: &str) -> Option<&str> {
+        let locale = normalize(locale);
+        let primary = locale.split('-').next().unwrap_or_default();
+        [locale.as_str(), primary, self.default_locale.as_str()]
+            .into_iter()
+            .find_map(|locale| self.locales.get(locale)?.get(code))
+            .map(String::as_str)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Default for MessageCatalog {
+    fn default() -> Self {
+        MessageCatalog::new("en")
+            .with_json("en", include_str!("messages/en.json"))
+            .and_then(|catalog| catalog.with_json("vi", include_str!("messages/vi.json")))
+            .expect("built-in messages are valid JSON")
+    }
+}
+
+/// Language tags compare case-insensitively; `en_US` is accepted for `en-US`
+fn normalize(locale: &str) -> String {
+    locale.trim().replace('_', "-").to_ascii_lowercase()
+}
+
+/// Replaces the `{name}` placeholders of `template`, leaving unknown ones as they are
+fn substitute(template: &str, args: &[(&str, String)]) -> String {
+    let mut message = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        message.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let value = after.find('}').and_then(|end| {
+            let name = &after[..end];
+            let (_, value) = args.iter().find(|(arg, _)| *arg == name)?;
+            Some((value, end))
+        });
+        match value {
+            Some((value, end)) => {
+                message.push_str(value);
+                rest = &after[end + 1..];
+            }
+            None => {
+                message.push('{');
+                rest = after;
+            }
+        }
+    }
+    message.push_str(rest);
+    message
+}
+
+impl Localizable for TodoError {
+    fn message_code(&self) -> &'static str {
+        self.code()
+    }
+
+    fn message_args(&self) -> Vec<(&'static str, MessageArg)> {
+        match self {
+            TodoError::RepositoryError(message) | TodoError::ValidationError(message) => {
+                vec![("message", MessageArg::Text(message.clone()))]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl Localizable for AuthError {
+    fn message_code(&self) -> &'static str {
+        self.code()
+    }
+
+    fn message_args(&self) -> Vec<(&'static str, MessageArg)> {
+        match self {
+            AuthError::MissingCredentials => Vec::new(),
+            AuthError::InvalidToken(message) | AuthError::Unavailable(message) => {
+                vec![("message", MessageArg::Text(message.clone()))]
+            }
+            AuthError::InsufficientScope(scope) => {
+                vec![("scope", MessageArg::Text(scope.clone()))]
+            }
+            AuthError::Forbidden(reason) => vec![("reason", MessageArg::Text(reason.clone()))],
+        }
+    }
+}
+
+/// `TODO`, `IN_PROGRESS` or `DONE`
+impl Localizable for TodoState {
+    fn message_code(&self) -> &'static str {
+        match self {
+            TodoState::Todo => "TODO",
+            TodoState::InProgress => "IN_PROGRESS",
+            TodoState::Done => "DONE",
+        }
+    }
+}
+
+/// `PRIORITY_LOW`, `PRIORITY_MEDIUM`, `PRIORITY_HIGH` or `PRIORITY_URGENT`
+impl Localizable for Priority {
+    fn message_code(&self) -> &'static str {
+        match self {
+            Priority::Low => "PRIORITY_LOW",
+            Priority::Medium => "PRIORITY_MEDIUM",
+            Priority::High => "PRIORITY_HIGH",
+            Priority::Urgent => "PRIORITY_URGENT",
+        }
+    }
+}
+
+/// `TODO_CREATED` with `id` and `description`, `TODO_STATE_CHANGED` with `id`,
+/// `from_state` and `to_state`, `TODO_ARCHIVED` with `id`, `TODO_STALE` with `id` and
+/// `state`, `TODO_DUE_DATE_SET` with `id` and `due_at`, `TODO_DUE_DATE_CLEARED` with `id`,
+/// `TODO_PRIORITY_CHANGED` with `id`, `from_priority` and `to_priority`, `TODO_TAG_ADDED`
+/// and `TODO_TAG_REMOVED` with `id` and `tag`, `TODO_SUBTASK_ADDED` with `id` and `subtask`,
+/// the subtask's description, or `TODO_SUBTASK_COMPLETED`, `TODO_SUBTASK_REOPENED` and
+/// `TODO_SUBTASK_REMOVED` with `id` and `subtask_id`, `TODO_DESCRIPTION_UPDATED` with `id`,
+/// `old` and `new`, `TODO_BLOCKED` and `TODO_UNBLOCKED` with `id` and `blocker_id`,
+/// `TODO_DELETED` and `TODO_RESTORED` with `id`, `TODO_ATTACHMENT_ADDED` with `id` and the
+/// file `name`, or `TODO_ATTACHMENT_REMOVED` with `id` and `content_hash`
+impl Localizable for TodoEvent {
+    fn message_code(&self) -> &'static str {
+        match self {
+            TodoEvent::TodoCreated { .. } => "TODO_CREATED",
+            TodoEvent::TodoStateChanged { .. } => "TODO_STATE_CHANGED",
+            TodoEvent::TodoArchived { .. } => "TODO_ARCHIVED",
+            TodoEvent::TodoStale { .. } => "TODO_STALE",
+            TodoEvent::TodoDueDateChanged {
+                due_at: Some(_), ..
+            } => "TODO_DUE_DATE_SET",
+            TodoEvent::TodoDueDateChanged { due_at: None, .. } => "TODO_DUE_DATE_CLEARED",
+            TodoEvent::TodoPriorityChanged { .. } => "TODO_PRIORITY_CHANGED",
+            TodoEvent::TodoTagAdded { .. } => "TODO_TAG_ADDED",
+            TodoEvent::TodoTagRemoved { .. } => "TODO_TAG_REMOVED",
+            TodoEvent::TodoSubtaskAdded { .. } => "TODO_SUBTASK_ADDED",
+            TodoEvent::TodoSubtaskToggled { done: true, .. } => "TODO_SUBTASK_COMPLETED",
+            TodoEvent::TodoSubtaskToggled { done: false, .. } => "TODO_SUBTASK_REOPENED",
+            TodoEvent::TodoSubtaskRemoved { .. } => "TODO_SUBTASK_REMOVED",
+            TodoEvent::TodoDescriptionUpdated { .. } => "TODO_DESCRIPTION_UPDATED",
+            TodoEvent::TodoBlocked { .. } => "TODO_BLOCKED",
+            TodoEvent::TodoUnblocked { .. } => "TODO_UNBLOCKED",
+            TodoEvent::TodoDeleted { .. } => "TODO_DELETED",
+            TodoEvent::TodoRestored { .. } => "TODO_RESTORED",
+            TodoEvent::TodoAttachmentAdded { .. } => "TODO_ATTACHMENT_ADDED",
+            TodoEvent::TodoAttachmentRemoved { .. } => "TODO_ATTACHMENT_REMOVED",
+        }
+    }
+
+    fn message_args(&self) -> Vec<(&'static str, MessageArg)> {
+        match self {
+            TodoEvent::TodoCreated {
+                id, description, ..
+            } => vec![
+                ("id", MessageArg::Text(id.to_string())),
+                ("description", MessageArg::Text(description.to_string())),
+            ],
+            TodoEvent::TodoStateChanged {
+                id,
+                from_state,
+                to_state,
+                ..
+            } => vec![
+                ("id", MessageArg::Text(id.to_string())),
+                ("from_state", MessageArg::Code(from_state.message_code())),
+                ("to_state", MessageArg::Code(to_state.message_code())),
+            ],
+            TodoEvent::TodoArchived { id, .. }
+            | TodoEvent::TodoDeleted { id, .. }
+            | TodoEvent::TodoRestored { id, .. } => vec![("id", MessageArg::Text(id.to_string()))],
+            TodoEvent::TodoStale { id, state, .. } => vec![
+                ("id", MessageArg::Text(id.to_string())),
+                ("state", MessageArg::Code(state.message_code())),
+            ],
+            TodoEvent::TodoDueDateChanged { id, due_at, .. } => {
+                let mut args = vec![("id", MessageArg::Text(id.to_string()))];
+                if let Some(due_at) = due_at {
+                    args.push((
+                        "due_at",
+                        MessageArg::Text(due_at.to_rfc3339_opts(SecondsFormat::Secs, true)),
+                    ));
+                }
+                args
+            }
+            TodoEvent::TodoPriorityChanged {
+                id,
+                from_priority,
+                to_priority,
+                ..
+            } => vec![
+                ("id", MessageArg::Text(id.to_string())),
+                (
+                    "from_priority",
+                    MessageArg::Code(from_priority.message_code()),
+                ),
+                ("to_priority", MessageArg::Code(to_priority.message_code())),
+            ],
+            TodoEvent::TodoTagAdded { id, tag, .. } | TodoEvent::TodoTagRemoved { id, tag, .. } => {
+                vec![
+                    ("id", MessageArg::Text(id.to_string())),
+                    ("tag", MessageArg::Text(tag.to_string())),
+                ]
+            }
+            TodoEvent::TodoSubtaskAdded {
+                id, description, ..
+            } => vec![
+                ("id", MessageArg::Text(id.to_string())),
+                ("subtask", MessageArg::Text(description.to_string())),
+            ],
+            TodoEvent::TodoSubtaskToggled { id, subtask_id, .. }
+            | TodoEvent::TodoSubtaskRemoved { id, subtask_id, .. } => vec![
+                ("id", MessageArg::Text(id.to_string())),
+                ("subtask_id", MessageArg::Text(subtask_id.to_string())),
+            ],
+            TodoEvent::TodoDescriptionUpdated { id, old, new, .. } => vec![
+                ("id", MessageArg::Text(id.to_string())),
+                ("old", MessageArg::Text(old.to_string())),
+                ("new", MessageArg::Text(new.to_string())),
+            ],
+            TodoEvent::TodoBlocked { id, blocker_id, .. }
+            | TodoEvent::TodoUnblocked { id, blocker_id, .. } => vec![
+                ("id", MessageArg::Text(id.to_string())),
+                ("blocker_id", MessageArg::Text(blocker_id.to_string())),
+            ],
+            TodoEvent::TodoAttachmentAdded { id, name, .. } => vec![
+                ("id", MessageArg::Text(id.to_string())),
+                ("name", MessageArg::Text(name.to_string())),
+            ],
+            TodoEvent::TodoAttachmentRemoved {
+                id, content_hash, ..
+            } => vec![
+                ("id", MessageArg::Text(id.to_string())),
+                ("content_hash", MessageArg::Text(content_hash.to_string())),
+            ],
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use std::future::Future;
+
+use crate::{TodoError, TodoEvent, TodoState};
+
+/// Counter of todos created through the handlers, including imported ones
+pub const TODOS_CREATED: &str = "todo_todos_created_total";
+/// Counter of state transitions, labelled `from` and `to` with the serialized state names
+pub const STATE_TRANSITIONS: &str = "todo_state_transitions_total";
+/// Counter of todos moved to an archive
+pub const TODOS_ARCHIVED: &str = "todo_todos_archived_total";
+/// Counter of todos found stale by an escalation rule, labelled `rule` with its id
+pub const TODOS_STALE: &str = "todo_todos_stale_total";
+/// Histogram of handler call durations in seconds, labelled `command` and `outcome`
+/// (`ok` or `error`)
+pub const HANDLER_DURATION: &str = "todo_handler_duration_seconds";
+/// Counter of `TodoError::RepositoryError` results, labelled `command`
+pub const REPOSITORY_ERRORS: &str = "todo_repository_errors_total";
+
+/// Registers units and help texts for the handler metrics with the installed recorder
+///
+/// Metrics are recorded through the `metrics` facade whether or not this is called; it only
+/// adds descriptions for exporters that show them.
+#[cfg(feature = "metrics")]
+pub fn describe() {
+    use metrics::{Unit, describe_counter, describe_histogram};
+
+    describe_counter!(TODOS_CREATED, "Todos created through the handlers");
+    describe_counter!(
+        STATE_TRANSITIONS,
+        "Todo state transitions by from and to state"
+    );
+    describe_counter!(TODOS_ARCHIVED, "Todos moved to an archive");
+    describe_counter!(TODOS_STALE, "Todos found stale by escalation rules");
+    describe_histogram!(HANDLER_DURATION, Unit::Seconds, "Handler call duration");
+    describe_counter!(
+        REPOSITORY_ERRORS,
+        "Repository failures seen by the handlers"
+    );
+}
+
+/// Runs a handler call, recording its duration and repository errors as `command`
+pub(crate) async fn observe<T>(
+    command: &'static str,
+    call: impl Future<Output = Result<T, TodoError>>,
+) -> Result<T, TodoError> {
+    #[cfg(feature = "metrics")]
+    let started = std::time::Instant::now();
+    let result = call.await;
+    #[cfg(feature = "metrics")]
+    {
+        let outcome = if result.is_ok() { "ok" } else { "error" };
+        metrics::histogram!(HANDLER_DURATION, "command" => command, "outcome" => outcome)
+            .record(started.elapsed());
+        if let Err(TodoError::RepositoryError(_)) = &result {
+            metrics::counter!(REPOSITORY_ERRORS, "command" => command).increment(1);
+        }
+    }
+    #[cfg(not(feature = "metrics"))]
+    let _ = command;
+    result
+}
+
+/// Counts the todos created, archived and found stale and the state transitions in `events`
+pub(crate) fn record_events(events: &[TodoEvent]) {
+    #[cfg(feature = "metrics")]
+    for event in events {
+        match event {
+            TodoEvent::TodoCreated { .. } => metrics::counter!(TODOS_CREATED).increment(1),
+            TodoEvent::TodoStateChanged {
+                from_state,
+                to_state,
+                ..
+            } => metrics::counter!(
+                STATE_TRANSITIONS,
+                "from" => state_label(*from_state),
+                "to" => state_label(*to_state)
+            )
+            .increment(1),
+            TodoEvent::TodoArchived { .. } => metrics::counter!(TODOS_ARCHIVED).increment(1),
+            TodoEvent::TodoStale { rule, .. } => {
+                metrics::counter!(TODOS_STALE, "rule" => rule.to_string()).increment(1)
+            }
+            TodoEvent::TodoDueDateChanged { .. }
+            | TodoEvent::TodoPriorityChanged { .. }
+            | TodoEvent::TodoTagAdded { .. }
+            | TodoEvent::TodoTagRemoved { .. }
+            | TodoEvent::TodoSubtaskAdded { .. }
+            | TodoEvent::TodoSubtaskToggled { .. }
+            | TodoEvent::TodoSubtaskRemoved { .. }
+            | TodoEvent::TodoDescriptionUpdated { .. }
+            | TodoEvent::TodoBlocked { .. }
+            | TodoEvent::TodoUnblocked { .. }
+            | TodoEvent::TodoDeleted { .. }
+            | TodoEvent::TodoRestored { .. }
+            | TodoEvent::TodoAttachmentAdded { .. }
+            | TodoEvent::TodoAttachmentRemoved { .. } => {}
+        }
+    }
+    #[cfg(not(feature = "metrics"))]
+    let _ = events;
+}
+
+#[cfg_attr(not(feature = "metrics"), allow(dead_code))]
+fn state_label(state: TodoState) -> &'static str {
+    match state {
+        TodoState::Todo => "TODO",
+        TodoState::InProgress => "IN_PROGRESS",
+        TodoState::Done => "DONE",
+    }
+}
@@ -0,0 +1,171 @@
+use futures::StreamExt;
+use std::sync::Arc;
+
+use crate::application::metrics::observe;
+use crate::{EventStore, Todo, TodoError, TodoRepository};
+
+/// Todos written to the target at once unless told otherwise
+const DEFAULT_BATCH_SIZE: usize = 500;
+
+/// What a migration copied, and what the target held already
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MigrationReport {
+    /// Todos of the source
+    pub todos: usize,
+    /// Todos written to the target
+    pub todos_copied: usize,
+    /// Todos the target already held unchanged, such as from an interrupted run
+    pub todos_present: usize,
+    /// Events appended to the target store
+    pub events_copied: usize,
+    /// Events the target store already held
+    pub events_present: usize,
+}
+
+/// Copies every todo, and optionally every event, from one backend to another, such as
+/// from a `FileTodoRepository` to a database
+///
+/// Todos are streamed from the source and saved to the target in batches, skipping those
+/// the target already holds unchanged, so a migration that failed part way can be run
+/// again to finish it. The target is checked afterwards to hold every todo of the source.
+/// Todos of the target that the source does not have are left alone.
+pub struct MigrateDataHandler<S = Box<dyn TodoRepository>, T = Box<dyn TodoRepository>> {
+    source: S,
+    target: T,
+    events: Option<(Arc<dyn EventStore>, Arc<dyn EventStore>)>,
+    batch_size: usize,
+}
+
+impl<S: TodoRepository, T: TodoRepository> MigrateDataHandler<S, T> {
+    pub fn new(source: S, target: T) -> Self {
+        Self {
+            source,
+            target,
+            events: None,
+            batch_size: DEFAULT_BATCH_SIZE,
+        }
+    }
+
+    /// Writes `batch_size` todos to the target at once instead of 500
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Also copies the events of `source` to `target`
+    ///
+    /// The events of the target must be the first events of the source, as an earlier,
+    /// interrupted migration leaves them; the rest are appended in order.
+    pub fn with_events(mut self, source: Arc<dyn EventStore>, target: Arc<dyn EventStore>) -> Self {
+        self.events = Some((source, target));
+        self
+    }
+
+    /// Copies the todos, then the events, to the target
+    ///
+    /// # Returns
+    /// - `Ok(MigrationReport)`: What was copied, once the target holds every todo and event
+    /// - `Err(TodoError::RepositoryError)`: If either side fails, the target store holds
+    ///   events the source does not, or the target lacks todos after the copy
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "migrate_data"), err)
+    )]
+    pub async fn migrate_data(&self) -> Result<MigrationReport, TodoError> {
+        observe("migrate_data", async {
+            let mut report = MigrationReport::default();
+            let mut ids = Vec::new();
+            let mut batch = Vec::with_capacity(self.batch_size);
+            let mut todos = self.source.stream_all();
+            while let Some(todo) = todos.next().await {
+                let todo = todo?;
+                ids.push(todo.id.clone());
+                batch.push(todo);
+                if batch.len() == self.batch_size {
+                    self.copy(&mut batch, &mut report).await?;
+                }
+            }
+            self.copy(&mut batch, &mut report).await?;
+            report.todos = ids.len();
+            self.verify(&ids).await?;
+            if let Some((source, target)) = &self.events {
+                copy_events(source.as_ref(), target.as_ref(), &mut report)?;
+            }
+            Ok(report)
+        })
+        .await
+    }
+
+    /// Saves the todos of `batch` the target does not hold unchanged, emptying the batch
+    async fn copy(
+        &self,
+        batch: &mut Vec<Todo>,
+        report: &mut MigrationReport,
+    ) -> Result<(), TodoError> {
+        let mut changed = Vec::with_capacity(batch.len());
+        for todo in batch.drain(..) {
+            match self.target.find_by_id(&todo.id).await? {
+                Some(existing) if same_content(&existing, &todo) => report.todos_present += 1,
+                _ => changed.push(todo),
+            }
+        }
+        if !changed.is_empty() {
+            self.target.save_all(&changed).await?;
+        }
+        report.todos_copied += changed.len();
+        Ok(())
+    }
+
+    /// Checks that the target holds a todo for each of `ids`
+    async fn verify(&self, ids: &[Arc<str>]) -> Result<(), TodoError> {
+        let mut missing = 0;
+        for id in ids {
+            if self.target.find_by_id(id).await?.is_none() {
+                missing += 1;
+            }
+        }
+        if missing > 0 {
+            return Err(TodoError::RepositoryError(format!(
+                "migration incomplete: {} of {} todos missing from the target",
+                missing,
+                ids.len()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Whether two todos hold the same data, ignoring whether they were changed since loaded
+fn same_content(a: &Todo, b: &Todo) -> bool {
+    a.id == b.id
+        && a.created_at == b.created_at
+        && a.description == b.description
+        && a.state == b.state
+}
+
+/// Appends the events of `source` that `target` does not hold yet
+fn copy_events(
+    source: &dyn EventStore,
+    target: &dyn EventStore,
+    report: &mut MigrationReport,
+) -> Result<(), TodoError> {
+    let events = source.find_all()?;
+    let present = target.find_all()?;
+    if !events.starts_with(&present) {
+        return Err(TodoError::RepositoryError(
+            "target event store holds events the source does not".to_string(),
+        ));
+    }
+    let missing = &events[present.len()..];
+    if !missing.is_empty() {
+        target.record(missing)?;
+    }
+    report.events_present = present.len();
+    report.events_copied = missing.len();
+    if target.find_all()?.len() != events.len() {
+        return Err(TodoError::RepositoryError(
+            "migration incomplete: the target event store lacks events".to_string(),
+        ));
+    }
+    Ok(())
+}
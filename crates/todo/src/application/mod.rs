@@ -1,3 +1,42 @@
+pub mod access_control;
+pub mod activity_feed;
 pub mod add_todo_handler;
+pub mod api_key_authenticator;
+pub mod archive;
+pub mod attachment_service;
+pub mod auth;
+pub mod change_todo_due_date_handler;
+pub mod change_todo_state_handler;
+pub mod delete_todo_handler;
+pub mod dependency_service;
+pub mod digest;
+pub mod escalation;
+pub mod error_mapping;
+#[cfg(feature = "serde")]
+pub mod export_todos_handler;
+pub mod get_todo_history_handler;
 pub mod get_todos_handler;
-pub mod change_todo_state_handler;
\ No newline at end of file
+#[cfg(feature = "serde")]
+pub mod import_todos_handler;
+pub mod invitations;
+pub mod issue_api_key_handler;
+pub mod messages;
+pub mod metrics;
+pub mod migration;
+pub mod poll_events_handler;
+pub mod push_notifier;
+pub mod quota;
+pub mod replay;
+pub mod register_device_handler;
+pub mod rename_tag_handler;
+pub mod resolve_conflict_handler;
+pub mod restore_todo_handler;
+pub mod retention;
+pub mod revoke_api_key_handler;
+pub mod shared_lists;
+#[cfg(feature = "serde")]
+pub mod sync_todos_handler;
+pub mod todo_app;
+pub mod undo_last_change_handler;
+pub mod update_todo_description_handler;
+pub mod user_data_handler;
@@ -0,0 +1,134 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::fmt;
+use std::sync::Arc;
+
+use crate::application::metrics::observe;
+use crate::{EventStore, TodoError, TodoEvent};
+
+/// Position of a consumer in the events of an EventStore
+///
+/// A cursor marks as consumed the events that happened before its time and the first
+/// `seen` events that happened at that time. It does not count from the start of the
+/// store, so compacting older events leaves it valid. `to_string` writes it as text, such
+/// as `2@2025-01-01T09:30:00.000000000Z`, which a consumer can persist and give back to
+/// `parse` to resume after a restart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventCursor {
+    at: DateTime<Utc>,
+    seen: usize,
+}
+
+impl EventCursor {
+    /// Parses a cursor written by `to_string`
+    ///
+    /// # Returns
+    /// - `Ok(EventCursor)`: The cursor
+    /// - `Err(String)`: If `cursor` is not `<seen>@<RFC3339 time>`
+    pub fn parse(cursor: &str) -> Result<Self, String> {
+        let invalid = || format!("invalid event cursor {:?}", cursor);
+        let (seen, at) = cursor.split_once('@').ok_or_else(invalid)?;
+        Ok(EventCursor {
+            at: DateTime::parse_from_rfc3339(at)
+                .map_err(|_| invalid())?
+                .with_timezone(&Utc),
+            seen: seen.parse().map_err(|_| invalid())?,
+        })
+    }
+
+    /// When the last consumed event happened
+    pub fn at(&self) -> DateTime<Utc> {
+        self.at
+    }
+
+    /// Moves the cursor past `event`, the next event in the order of `poll_events`
+    fn advance(cursor: Option<Self>, event: &TodoEvent) -> Self {
+        let at = event.occurred_at();
+        match cursor {
+            Some(cursor) if cursor.at == at => EventCursor {
+                at,
+                seen: cursor.seen + 1,
+            },
+            _ => EventCursor { at, seen: 1 },
+        }
+    }
+}
+
+impl fmt::Display for EventCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}@{}",
+            self.seen,
+            self.at.to_rfc3339_opts(SecondsFormat::Nanos, true)
+        )
+    }
+}
+
+/// Events returned by one poll, and the cursor to poll the following ones from
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventPage {
+    pub events: Vec<TodoEvent>,
+    /// Past the returned events; the polled cursor when there were none
+    pub cursor: Option<EventCursor>,
+}
+
+/// Pulls the events of an EventStore in pages, from a cursor the consumer keeps
+///
+/// This is the pull counterpart of an event sink: a consumer such as a script or a UI
+/// isolate polls at its own pace and resumes from its last cursor, even across restarts
+/// when the store is a file. Events are returned in the order they happened, and events
+/// that happened at the same time in the order they were recorded.
+///
+/// An event recorded with a time before a consumer's cursor, such as from another process
+/// whose clock lags, is not returned to that consumer. Events compacted away before a
+/// consumer polled them are not returned either.
+pub struct PollEventsHandler {
+    event_store: Arc<dyn EventStore>,
+}
+
+impl PollEventsHandler {
+    pub fn new(event_store: Arc<dyn EventStore>) -> Self {
+        Self { event_store }
+    }
+
+    /// Returns up to `limit` events after `cursor`, or from the oldest when `None`
+    ///
+    /// # Returns
+    /// - `Ok(EventPage)`: The events, fewer than `limit` once the consumer caught up
+    /// - `Err(TodoError)`: If the store cannot be read
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "poll_events"), err)
+    )]
+    pub async fn poll_events(
+        &self,
+        cursor: Option<&EventCursor>,
+        limit: usize,
+    ) -> Result<EventPage, TodoError> {
+        observe("poll_events", async {
+            let mut events = self.event_store.find_all()?;
+            events.sort_by_key(TodoEvent::occurred_at);
+            let mut skipped = 0;
+            let mut page = EventPage {
+                events: Vec::new(),
+                cursor: cursor.copied(),
+            };
+            for event in events {
+                if let Some(cursor) = cursor {
+                    let at = event.occurred_at();
+                    if at < cursor.at || (at == cursor.at && skipped < cursor.seen) {
+                        skipped += usize::from(at == cursor.at);
+                        continue;
+                    }
+                }
+                if page.events.len() == limit {
+                    break;
+                }
+                page.cursor = Some(EventCursor::advance(page.cursor, &event));
+                page.events.push(event);
+            }
+            Ok(page)
+        })
+        .await
+    }
+}
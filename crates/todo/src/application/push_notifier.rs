@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::domain::notification::{
+    DeviceRepository, NotificationError, PushNotification, PushPlatform, PushSender,
+};
+
+/// Pushes notifications to every device registered for a subject
+///
+/// Each device is sent to through the `PushSender` of its platform; devices of a platform
+/// without one are skipped. Devices whose token the push service no longer knows are
+/// removed, so they are not tried again.
+pub struct PushNotifier<R = Arc<dyn DeviceRepository>> {
+    device_repository: R,
+    senders: HashMap<PushPlatform, Arc<dyn PushSender>>,
+}
+
+impl<R: DeviceRepository> PushNotifier<R> {
+    /// Creates a new PushNotifier without senders
+    pub fn new(device_repository: R) -> Self {
+        Self {
+            device_repository,
+            senders: HashMap::new(),
+        }
+    }
+
+    /// Sends to the devices of `platform` through `sender`
+    pub fn with_sender(mut self, platform: PushPlatform, sender: Arc<dyn PushSender>) -> Self {
+        self.senders.insert(platform, sender);
+        self
+    }
+
+    /// Sends `notification` to the devices of `subject`
+    ///
+    /// Every device is tried, even after one fails.
+    ///
+    /// # Returns
+    /// - `Ok(usize)`: How many devices the notification was delivered to
+    /// - `Err(NotificationError)`: The last failure, if a delivery or the repository failed
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "push_notification"), err)
+    )]
+    pub async fn notify(
+        &self,
+        subject: &str,
+        notification: &PushNotification,
+    ) -> Result<usize, NotificationError> {
+        let mut delivered = 0;
+        let mut failure = None;
+        for device in self.device_repository.find_by_subject(subject).await? {
+            let Some(sender) = self.senders.get(&device.platform) else {
+                continue;
+            };
+            match sender.send(&device, notification).await {
+                Ok(()) => delivered += 1,
+                Err(NotificationError::DeviceUnregistered) => {
+                    match self.device_repository.remove(&device.token).await {
+                        Ok(()) | Err(NotificationError::DeviceNotFound) => {}
+                        Err(err) => failure = Some(err),
+                    }
+                }
+                Err(err) => failure = Some(err),
+            }
+        }
+        match failure {
+            Some(err) => Err(err),
+            None => Ok(delivered),
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+use crate::application::metrics::observe;
+use crate::{TenantId, Todo, TodoError, TodoRepository, TodoState};
+
+/// Limits on the number of todos in a list
+///
+/// A list is the set of todos of a tenant, so the limits of a list are those of its tenant.
+/// The default quota limits nothing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Quota {
+    /// Most todos not `DONE` the list may hold; `None` for no limit
+    pub max_open_todos: Option<u32>,
+    /// Most todos the list may hold, `DONE` or not; `None` for no limit
+    pub max_todos: Option<u32>,
+}
+
+impl Quota {
+    /// Whether the quota limits anything
+    pub fn is_limited(&self) -> bool {
+        self.max_open_todos.is_some() || self.max_todos.is_some()
+    }
+}
+
+/// The Quota of every list, with its own for some of them
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QuotaPolicy {
+    /// The quota of lists without one of their own
+    pub default: Quota,
+    /// Quotas replacing `default` for some lists
+    pub lists: HashMap<TenantId, Quota>,
+}
+
+impl QuotaPolicy {
+    /// Creates a new QuotaPolicy giving `default` to every list
+    pub fn new(default: Quota) -> Self {
+        QuotaPolicy {
+            default,
+            lists: HashMap::new(),
+        }
+    }
+
+    /// Gives `quota` instead of the default to `list`
+    pub fn with_list(mut self, list: TenantId, quota: Quota) -> Self {
+        self.lists.insert(list, quota);
+        self
+    }
+
+    /// The quota of `list`
+    pub fn for_list(&self, list: &TenantId) -> Quota {
+        self.lists.get(list).copied().unwrap_or(self.default)
+    }
+}
+
+/// How much of its Quota a list uses
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuotaUsage {
+    /// Todos of the list not `DONE`
+    pub open_todos: u32,
+    /// Todos of the list
+    pub todos: u32,
+    pub quota: Quota,
+}
+
+impl QuotaUsage {
+    /// Counts `todos` against `quota`
+    pub fn new(todos: &[Todo], quota: Quota) -> Self {
+        let count = |todos: usize| u32::try_from(todos).unwrap_or(u32::MAX);
+        QuotaUsage {
+            open_todos: count(
+                todos
+                    .iter()
+                    .filter(|todo| todo.state != TodoState::Done)
+                    .count(),
+            ),
+            todos: count(todos.len()),
+            quota,
+        }
+    }
+
+    /// How many more todos not `DONE` the list may hold, or `None` without a limit
+    pub fn remaining_open_todos(&self) -> Option<u32> {
+        self.quota
+            .max_open_todos
+            .map(|max| max.saturating_sub(self.open_todos))
+    }
+
+    /// How many more todos the list may hold, or `None` without a limit
+    pub fn remaining_todos(&self) -> Option<u32> {
+        self.quota
+            .max_todos
+            .map(|max| max.saturating_sub(self.todos))
+    }
+
+    /// How many todos can still be added, as both limits allow, or `None` without a limit
+    pub fn remaining(&self) -> Option<u32> {
+        match (self.remaining_open_todos(), self.remaining_todos()) {
+            (Some(open), Some(todos)) => Some(open.min(todos)),
+            (open, todos) => open.or(todos),
+        }
+    }
+}
+
+/// Reports how much of a Quota the todos of a repository use, so front ends can show the
+/// remaining capacity
+///
+/// Like `AddTodoHandler::with_quota`, it counts every todo of the repository, which for a
+/// `TenantTodoRepository` are those of its tenant's list.
+pub struct GetQuotaUsageHandler<R = Box<dyn TodoRepository>> {
+    todo_repository: R,
+    quota: Quota,
+}
+
+impl<R: TodoRepository> GetQuotaUsageHandler<R> {
+    pub fn new(todo_repository: R, quota: Quota) -> Self {
+        Self {
+            todo_repository,
+            quota,
+        }
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "get_quota_usage"), err)
+    )]
+    pub async fn usage(&self) -> Result<QuotaUsage, TodoError> {
+        observe("get_quota_usage", async {
+            let todos = self.todo_repository.find_all().await?;
+            Ok(QuotaUsage::new(&todos, self.quota))
+        })
+        .await
+    }
+}
@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use crate::domain::notification::{Device, DeviceRepository, NotificationError, PushPlatform};
+use crate::{Clock, SystemClock};
+
+pub struct RegisterDeviceHandler<R = Box<dyn DeviceRepository>> {
+    device_repository: R,
+    clock: Arc<dyn Clock>,
+}
+
+impl<R: DeviceRepository> RegisterDeviceHandler<R> {
+    pub fn new(device_repository: R) -> Self {
+        Self {
+            device_repository,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Stamps registrations with `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Registers the device with `token` to receive the push notifications of `subject`,
+    /// moving it from any subject it was registered to before
+    ///
+    /// # Returns
+    /// - `Ok(Device)`: The stored device
+    /// - `Err(NotificationError::EmptyDeviceToken)`: If `token` is blank
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "register_device", platform = platform.as_str()), err)
+    )]
+    pub async fn register(
+        &self,
+        subject: &str,
+        platform: PushPlatform,
+        token: String,
+    ) -> Result<Device, NotificationError> {
+        let device = Device::new(token, platform, subject, self.clock.now())?;
+        self.device_repository.save(&device).await?;
+        Ok(device)
+    }
+}
@@ -0,0 +1,89 @@
+use std::sync::Arc;
+
+use crate::application::metrics::{observe, record_events};
+use crate::{Clock, EventSink, SystemClock, Tag, Todo, TodoError, TodoEvent, TodoRepository};
+
+pub struct RenameTagHandler<R = Box<dyn TodoRepository>> {
+    todo_repository: R,
+    event_sink: Option<Arc<dyn EventSink>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl<R: TodoRepository> RenameTagHandler<R> {
+    pub fn new(todo_repository: R) -> Self {
+        Self {
+            todo_repository,
+            event_sink: None,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Passes the events of every saved change to `event_sink`
+    pub fn with_event_sink(mut self, event_sink: Arc<dyn EventSink>) -> Self {
+        self.event_sink = Some(event_sink);
+        self
+    }
+
+    /// Stamps tag changes with `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn record(&self, events: &[TodoEvent]) -> Result<(), TodoError> {
+        match &self.event_sink {
+            Some(event_sink) => event_sink.record(events),
+            None => Ok(()),
+        }
+    }
+
+    /// Renames the tag `from` to `to` on every todo that has it, merging the two tags on the
+    /// todos that already have `to`, and saves the changed todos with one `save_all`
+    ///
+    /// # Returns
+    /// - `Ok((Vec<Todo>, Vec<TodoEvent>))`: The todos as saved, and for each of them a
+    ///   `TodoTagRemoved` event for `from` followed by a `TodoTagAdded` event for `to` unless
+    ///   it already had `to`; nothing saved and no event when no todo has `from`, or `from`
+    ///   and `to` are the same tag
+    /// - `Err(TodoError::ValidationError)`: If `from` or `to` is empty or not a single word
+    ///
+    /// # Special Requirements
+    /// - Either every todo is changed or none is, on backends whose `save_all` writes all
+    ///   the todos in one operation, such as `FileTodoRepository`
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "rename_tag"), err)
+    )]
+    pub async fn rename_tag(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<(Vec<Todo>, Vec<TodoEvent>), TodoError> {
+        observe("rename_tag", async move {
+            let from = Tag::new(from).map_err(TodoError::ValidationError)?;
+            let to = Tag::new(to).map_err(TodoError::ValidationError)?;
+            if from == to {
+                return Ok((Vec::new(), Vec::new()));
+            }
+            let mut todos = self.todo_repository.find_by_tag(&from).await?;
+            let mut events = Vec::new();
+            for todo in &mut todos {
+                events.extend(todo.remove_tag_with_clock(from.as_str(), &*self.clock)?);
+                if !todo.has_tag(&to) {
+                    events.extend(todo.add_tag_with_clock(to.as_str(), &*self.clock)?);
+                }
+            }
+            if todos.is_empty() {
+                return Ok((todos, events));
+            }
+            self.todo_repository.save_all(&todos).await?;
+            for todo in &mut todos {
+                todo.dirty = Some(false);
+            }
+            record_events(&events);
+            self.record(&events)?;
+            Ok((todos, events))
+        })
+        .await
+    }
+}
@@ -0,0 +1,300 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+use crate::application::activity_feed::ActivityFeed;
+use crate::application::metrics::observe;
+use crate::{
+    Attachment, EventSink, EventStore, Priority, Subtask, Todo, TodoError, TodoEvent,
+    TodoRepository, TodoState,
+};
+
+/// A view built from the events of an EventStore, which ReplayEventsHandler can rebuild
+///
+/// Applying an event already applied must leave the projection as it was, so replaying a
+/// range of events more than once, or a range overlapping events the projection has seen,
+/// is safe.
+#[async_trait]
+pub trait Projection: Send + Sync {
+    /// Applies one event to the projection
+    ///
+    /// # Returns
+    /// - `Ok(())`: The event was applied, or ignored as already applied or not relevant
+    /// - `Err(TodoError)`: If the projection cannot be updated
+    async fn apply(&self, event: &TodoEvent) -> Result<(), TodoError>;
+}
+
+/// Projection of the events into the todos of a TodoRepository
+///
+/// `TodoCreated` adds a todo missing from the repository, `TodoStateChanged` moves a todo
+/// still in the change's `from_state`, `TodoDescriptionUpdated` replaces the description,
+/// `TodoDueDateChanged` sets or clears the due date, `TodoPriorityChanged` sets the priority,
+/// `TodoTagAdded` and `TodoTagRemoved` add and remove a tag, the subtask events add, check
+/// off or uncheck, and remove a subtask, `TodoBlocked` and `TodoUnblocked` add and remove a
+/// blocker, `TodoAttachmentAdded` and `TodoAttachmentRemoved` add and remove an attachment,
+/// and `TodoArchived` and `TodoDeleted` remove the todo from the list.
+/// `TodoRestored` is ignored, as the event does not carry the todo it brings back.
+/// Events of todos the repository no longer holds, or of changes it already shows, are
+/// ignored.
+pub struct TodoProjection<R = Box<dyn TodoRepository>> {
+    todo_repository: R,
+}
+
+impl<R: TodoRepository> TodoProjection<R> {
+    pub fn new(todo_repository: R) -> Self {
+        Self { todo_repository }
+    }
+}
+
+#[async_trait]
+impl<R: TodoRepository> Projection for TodoProjection<R> {
+    async fn apply(&self, event: &TodoEvent) -> Result<(), TodoError> {
+        let existing = self.todo_repository.find_by_id(event.todo_id()).await?;
+        match (event, existing) {
+            (
+                TodoEvent::TodoCreated {
+                    id,
+                    description,
+                    created_at,
+                },
+                None,
+            ) => {
+                let todo = Todo {
+                    id: id.clone(),
+                    created_at: *created_at,
+                    description: description.clone(),
+                    state: TodoState::Todo,
+                    due_at: None,
+                    priority: Priority::default(),
+                    tags: Vec::new(),
+                    subtasks: Vec::new(),
+                    blocked_by: Vec::new(),
+                    attachments: Vec::new(),
+                    dirty: Some(true),
+                };
+                self.todo_repository.save(&todo).await
+            }
+            (
+                TodoEvent::TodoStateChanged {
+                    from_state,
+                    to_state,
+                    ..
+                },
+                Some(mut todo),
+            ) if todo.state == *from_state && from_state.can_transition_to(*to_state) => {
+                todo.state = *to_state;
+                todo.dirty = Some(true);
+                self.todo_repository.save(&todo).await
+            }
+            (TodoEvent::TodoDescriptionUpdated { new, .. }, Some(mut todo))
+                if todo.description != *new =>
+            {
+                todo.description = new.clone();
+                todo.dirty = Some(true);
+                self.todo_repository.save(&todo).await
+            }
+            (TodoEvent::TodoDueDateChanged { due_at, .. }, Some(mut todo))
+                if todo.due_at != *due_at =>
+            {
+                todo.due_at = *due_at;
+                todo.dirty = Some(true);
+                self.todo_repository.save(&todo).await
+            }
+            (TodoEvent::TodoPriorityChanged { to_priority, .. }, Some(mut todo))
+                if todo.priority != *to_priority =>
+            {
+                todo.priority = *to_priority;
+                todo.dirty = Some(true);
+                self.todo_repository.save(&todo).await
+            }
+            (TodoEvent::TodoTagAdded { tag, .. }, Some(mut todo)) if !todo.has_tag(tag) => {
+                todo.tags.push(tag.clone());
+                todo.dirty = Some(true);
+                self.todo_repository.save(&todo).await
+            }
+            (TodoEvent::TodoTagRemoved { tag, .. }, Some(mut todo)) if todo.has_tag(tag) => {
+                todo.tags.retain(|existing| existing != tag);
+                todo.dirty = Some(true);
+                self.todo_repository.save(&todo).await
+            }
+            (
+                TodoEvent::TodoSubtaskAdded {
+                    subtask_id,
+                    description,
+                    ..
+                },
+                Some(mut todo),
+            ) if !todo
+                .subtasks
+                .iter()
+                .any(|subtask| subtask.id == *subtask_id) =>
+            {
+                todo.subtasks.push(Subtask {
+                    id: subtask_id.clone(),
+                    description: description.clone(),
+                    done: false,
+                });
+                todo.dirty = Some(true);
+                self.todo_repository.save(&todo).await
+            }
+            (
+                TodoEvent::TodoSubtaskToggled {
+                    subtask_id, done, ..
+                },
+                Some(mut todo),
+            ) if todo
+                .subtasks
+                .iter()
+                .any(|subtask| subtask.id == *subtask_id && subtask.done != *done) =>
+            {
+                for subtask in todo
+                    .subtasks
+                    .iter_mut()
+                    .filter(|subtask| subtask.id == *subtask_id)
+                {
+                    subtask.done = *done;
+                }
+                todo.dirty = Some(true);
+                self.todo_repository.save(&todo).await
+            }
+            (TodoEvent::TodoSubtaskRemoved { subtask_id, .. }, Some(mut todo))
+                if todo
+                    .subtasks
+                    .iter()
+                    .any(|subtask| subtask.id == *subtask_id) =>
+            {
+                todo.subtasks.retain(|subtask| subtask.id != *subtask_id);
+                todo.dirty = Some(true);
+                self.todo_repository.save(&todo).await
+            }
+            (TodoEvent::TodoBlocked { blocker_id, .. }, Some(mut todo))
+                if !todo.is_blocked_by(blocker_id) =>
+            {
+                todo.blocked_by.push(blocker_id.clone());
+                todo.dirty = Some(true);
+                self.todo_repository.save(&todo).await
+            }
+            (TodoEvent::TodoUnblocked { blocker_id, .. }, Some(mut todo))
+                if todo.is_blocked_by(blocker_id) =>
+            {
+                todo.blocked_by.retain(|id| id != blocker_id);
+                todo.dirty = Some(true);
+                self.todo_repository.save(&todo).await
+            }
+            (
+                TodoEvent::TodoAttachmentAdded {
+                    content_hash,
+                    name,
+                    media_type,
+                    size,
+                    ..
+                },
+                Some(mut todo),
+            ) if todo.attachment(content_hash).is_none() => {
+                todo.attachments.push(Attachment {
+                    content_hash: content_hash.clone(),
+                    name: name.clone(),
+                    media_type: media_type.clone(),
+                    size: *size,
+                });
+                todo.dirty = Some(true);
+                self.todo_repository.save(&todo).await
+            }
+            (TodoEvent::TodoAttachmentRemoved { content_hash, .. }, Some(mut todo))
+                if todo.attachment(content_hash).is_some() =>
+            {
+                todo.attachments
+                    .retain(|attachment| attachment.content_hash != *content_hash);
+                todo.dirty = Some(true);
+                self.todo_repository.save(&todo).await
+            }
+            (TodoEvent::TodoArchived { id, .. }, Some(_))
+            | (TodoEvent::TodoDeleted { id, .. }, Some(_)) => self.todo_repository.delete(id).await,
+            _ => Ok(()),
+        }
+    }
+}
+
+#[async_trait]
+impl Projection for ActivityFeed {
+    /// Records the event unless the feed still holds it
+    async fn apply(&self, event: &TodoEvent) -> Result<(), TodoError> {
+        if self.contains(event) {
+            return Ok(());
+        }
+        self.record(std::slice::from_ref(event))
+    }
+}
+
+/// How far a replay has gone
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplayProgress {
+    /// Events applied so far
+    pub replayed: usize,
+    /// Events the replay applies in all
+    pub total: usize,
+}
+
+/// Rebuilds projections from the events of an EventStore, such as after a bug left a
+/// repository or an activity feed out of step with the events
+pub struct ReplayEventsHandler {
+    event_store: Arc<dyn EventStore>,
+    progress: Option<Arc<dyn Fn(ReplayProgress) + Send + Sync>>,
+}
+
+impl ReplayEventsHandler {
+    pub fn new(event_store: Arc<dyn EventStore>) -> Self {
+        Self {
+            event_store,
+            progress: None,
+        }
+    }
+
+    /// Calls `progress` after each applied event
+    pub fn with_progress(mut self, progress: Arc<dyn Fn(ReplayProgress) + Send + Sync>) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Applies the events that happened from `from` and before `to` to `projection`, in
+    /// the order they were recorded
+    ///
+    /// # Parameters
+    /// - `from`: The first time replayed, or `None` to start at the first event
+    /// - `to`: The time replaying stops before, or `None` to go to the last event
+    ///
+    /// # Returns
+    /// - `Ok(usize)`: The number of events replayed
+    /// - `Err(TodoError)`: If the store cannot be read or the projection fails; the events
+    ///   before the failing one stay applied, and replaying again resumes safely
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "replay_events"), err)
+    )]
+    pub async fn replay_events(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        projection: &dyn Projection,
+    ) -> Result<usize, TodoError> {
+        observe("replay_events", async {
+            let mut events = self.event_store.find_all()?;
+            events.retain(|event| {
+                let occurred_at = event.occurred_at();
+                from.is_none_or(|from| occurred_at >= from) && to.is_none_or(|to| occurred_at < to)
+            });
+            let total = events.len();
+            for (index, event) in events.iter().enumerate() {
+                projection.apply(event).await?;
+                if let Some(progress) = &self.progress {
+                    progress(ReplayProgress {
+                        replayed: index + 1,
+                        total,
+                    });
+                }
+            }
+            Ok(total)
+        })
+        .await
+    }
+}
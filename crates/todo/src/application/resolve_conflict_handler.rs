@@ -0,0 +1,95 @@
+use crate::application::metrics::observe;
+use crate::domain::sync::{
+    ConflictResolver, PendingConflict, PendingConflictRepository, Resolution, SyncConflict,
+};
+use crate::{Todo, TodoError, TodoRepository};
+
+/// Applies the resolutions of sync conflicts to the local repository
+///
+/// The sync engine passes each conflict it detects to `settle` with its configured
+/// `ConflictResolver`; conflicts a `ManualQueue` defers are listed by `pending_conflicts`
+/// for a UI to present, and settled by `decide` once the user has chosen a side.
+pub struct ResolveConflictHandler<
+    R = Box<dyn TodoRepository>,
+    P = Box<dyn PendingConflictRepository>,
+> {
+    todo_repository: R,
+    pending: P,
+}
+
+impl<R: TodoRepository, P: PendingConflictRepository> ResolveConflictHandler<R, P> {
+    pub fn new(todo_repository: R, pending: P) -> Self {
+        Self {
+            todo_repository,
+            pending,
+        }
+    }
+
+    /// Resolves `conflict` with `resolver`, saving the remote todo locally when it wins
+    ///
+    /// # Returns
+    /// - `Ok(Resolution)`: The side kept; the sync engine pushes the local todo when it is
+    ///   `Local`, and leaves both sides alone when it is `Deferred`
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "settle_conflict", todo.id = %conflict.todo_id()), err)
+    )]
+    pub async fn settle(
+        &self,
+        conflict: &SyncConflict,
+        resolver: &dyn ConflictResolver,
+    ) -> Result<Resolution, TodoError> {
+        observe("settle_conflict", async {
+            let resolution = resolver.resolve(conflict).await?;
+            if resolution == Resolution::Remote {
+                self.todo_repository.save(&conflict.remote).await?;
+            }
+            Ok(resolution)
+        })
+        .await
+    }
+
+    /// Returns the conflicts waiting for a decision, oldest first
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "pending_conflicts"), err)
+    )]
+    pub async fn pending_conflicts(&self) -> Result<Vec<PendingConflict>, TodoError> {
+        observe("pending_conflicts", self.pending.find_all()).await
+    }
+
+    /// Settles the pending conflict of the todo with `todo_id` as the user decided
+    ///
+    /// As with `settle`, a `Remote` decision saves the remote todo locally and a `Local`
+    /// one leaves the local todo for the sync engine to push; `Deferred` keeps the conflict
+    /// pending.
+    ///
+    /// # Returns
+    /// - `Ok(Todo)`: The todo kept
+    /// - `Err(TodoError::TodoNotFound)`: If the todo has no pending conflict
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "decide_conflict", todo.id = %todo_id), err)
+    )]
+    pub async fn decide(&self, todo_id: &str, resolution: Resolution) -> Result<Todo, TodoError> {
+        observe("decide_conflict", async {
+            let pending = self
+                .pending
+                .find(todo_id)
+                .await?
+                .ok_or(TodoError::TodoNotFound)?;
+            let conflict = pending.conflict;
+            let kept = match resolution {
+                Resolution::Deferred => return Ok(conflict.local),
+                Resolution::Local => conflict.local,
+                Resolution::Remote => {
+                    self.todo_repository.save(&conflict.remote).await?;
+                    conflict.remote
+                }
+            };
+            self.pending.remove(todo_id).await?;
+            Ok(kept)
+        })
+        .await
+    }
+}
@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use crate::application::metrics::{observe, record_events};
+use crate::domain::trash::{TrashRepository, TrashedTodo};
+use crate::{Clock, EventSink, SystemClock, Todo, TodoError, TodoEvent, TodoRepository};
+
+/// Lists the todos in the trash, and restores or purges them
+///
+/// Todos get into the trash through a `DeleteTodoHandler` given the same TrashRepository
+/// with `with_trash`, and are emptied from it by retention runs.
+pub struct RestoreTodoHandler<R = Box<dyn TodoRepository>> {
+    todo_repository: R,
+    trash: Arc<dyn TrashRepository>,
+    event_sink: Option<Arc<dyn EventSink>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl<R: TodoRepository> RestoreTodoHandler<R> {
+    pub fn new(todo_repository: R, trash: Arc<dyn TrashRepository>) -> Self {
+        Self {
+            todo_repository,
+            trash,
+            event_sink: None,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Passes the `TodoRestored` event of every restore to `event_sink`
+    pub fn with_event_sink(mut self, event_sink: Arc<dyn EventSink>) -> Self {
+        self.event_sink = Some(event_sink);
+        self
+    }
+
+    /// Records restore times from `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn record(&self, events: &[TodoEvent]) -> Result<(), TodoError> {
+        match &self.event_sink {
+            Some(event_sink) => event_sink.record(events),
+            None => Ok(()),
+        }
+    }
+
+    /// Moves the todo with `id` from the trash back to the repository, as it was deleted
+    ///
+    /// # Returns
+    /// - `Ok((Todo, Vec<TodoEvent>))`: The restored todo, and its `TodoRestored` event
+    /// - `Err(TodoError::TodoNotFound)`: If the todo is not in the trash
+    /// - `Err(TodoError::RepositoryError)`: If a todo with the same id exists again
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "restore_todo", todo.id = %id), err)
+    )]
+    pub async fn restore(&self, id: String) -> Result<(Todo, Vec<TodoEvent>), TodoError> {
+        observe("restore_todo", async move {
+            let trashed = self.trash.find(&id).await?.ok_or(TodoError::TodoNotFound)?;
+            if self.todo_repository.find_by_id(&id).await?.is_some() {
+                return Err(TodoError::RepositoryError(format!(
+                    "todo {} already exists",
+                    id
+                )));
+            }
+            self.todo_repository.save(&trashed.todo).await?;
+            self.trash.remove(&id).await?;
+            let events = vec![TodoEvent::TodoRestored {
+                id: trashed.todo.id.clone(),
+                restored_at: self.clock.now(),
+            }];
+            record_events(&events);
+            self.record(&events)?;
+            Ok((trashed.todo, events))
+        })
+        .await
+    }
+
+    /// Returns the todos in the trash, most recently deleted first
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "list_trash"), err)
+    )]
+    pub async fn trashed(&self) -> Result<Vec<TrashedTodo>, TodoError> {
+        observe("list_trash", async {
+            let mut trashed = self.trash.find_all().await?;
+            trashed.sort_by_key(|trashed| std::cmp::Reverse(trashed.deleted_at));
+            Ok(trashed)
+        })
+        .await
+    }
+
+    /// Removes the todo with `id` from the trash for good
+    ///
+    /// # Returns
+    /// - `Err(TodoError::TodoNotFound)`: If the todo is not in the trash
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "purge_todo", todo.id = %id), err)
+    )]
+    pub async fn purge(&self, id: String) -> Result<(), TodoError> {
+        observe("purge_todo", async move { self.trash.remove(&id).await }).await
+    }
+}
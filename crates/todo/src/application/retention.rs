@@ -0,0 +1,215 @@
+use chrono::{DateTime, Days, Months, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::application::metrics::observe;
+use crate::domain::trash::TrashRepository;
+use crate::{
+    Clock, EventStore, SystemClock, TenantId, Todo, TodoError, TodoEvent, TodoRepository, TodoState,
+};
+
+/// What a retention run removes; unset rules keep everything
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RetentionRules {
+    /// Deletes todos `DONE` for at least this many days
+    pub done_todo_days: Option<u32>,
+    /// Compacts events older than this many months
+    pub event_months: Option<u32>,
+    /// Empties todos from the trash once deleted for at least this many days
+    pub trash_days: Option<u32>,
+}
+
+/// Retention rules, with overrides for some tenants
+///
+/// Todos stored under `<tenant>/<id>`, as a `TenantTodoRepository` stores them, follow their
+/// tenant's override when it has one, and the default rules otherwise. An override replaces
+/// the default rules as a whole. Events carry no tenant, so they are always compacted by the
+/// default `event_months`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    pub default: RetentionRules,
+    pub tenants: HashMap<TenantId, RetentionRules>,
+}
+
+impl RetentionPolicy {
+    /// Creates a new RetentionPolicy applying `default` to every tenant
+    pub fn new(default: RetentionRules) -> Self {
+        RetentionPolicy {
+            default,
+            tenants: HashMap::new(),
+        }
+    }
+
+    /// Applies `rules` instead of the default ones to `tenant`'s todos
+    pub fn with_tenant(mut self, tenant: TenantId, rules: RetentionRules) -> Self {
+        self.tenants.insert(tenant, rules);
+        self
+    }
+
+    /// Whether any rule removes anything
+    pub fn is_enabled(&self) -> bool {
+        [&self.default]
+            .into_iter()
+            .chain(self.tenants.values())
+            .any(|rules| {
+                rules.done_todo_days.is_some()
+                    || rules.event_months.is_some()
+                    || rules.trash_days.is_some()
+            })
+    }
+
+    /// The rules of the todo stored under `id`
+    fn rules_for(&self, id: &str) -> &RetentionRules {
+        id.split_once('/')
+            .and_then(|(tenant, _)| TenantId::new(tenant).ok())
+            .and_then(|tenant| self.tenants.get(&tenant))
+            .unwrap_or(&self.default)
+    }
+}
+
+/// What a retention run removed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RetentionReport {
+    /// Ids of the deleted todos, as stored in the repository
+    pub deleted_todos: Vec<Arc<str>>,
+    /// Number of events removed from the event store
+    pub compacted_events: usize,
+    /// Ids of the todos emptied from the trash
+    pub emptied_trash: Vec<Arc<str>>,
+}
+
+/// Removes what a RetentionPolicy no longer keeps
+///
+/// A todo's time in `DONE` counts from its last change to `DONE` in the event store, given
+/// with `with_event_store`, or from its creation when the store has no such change or there
+/// is no store. Tenant todos are looked up in the store by their id as stored and as their
+/// tenant's handlers see it. Todos in the trash given with `with_trash` are emptied once
+/// their tenant's `trash_days` have passed since their deletion.
+pub struct ApplyRetentionHandler<R = Box<dyn TodoRepository>> {
+    todo_repository: R,
+    policy: RetentionPolicy,
+    event_store: Option<Arc<dyn EventStore>>,
+    trash: Option<Arc<dyn TrashRepository>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl<R: TodoRepository> ApplyRetentionHandler<R> {
+    pub fn new(todo_repository: R, policy: RetentionPolicy) -> Self {
+        Self {
+            todo_repository,
+            policy,
+            event_store: None,
+            trash: None,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Reads completion times from `event_store` and compacts its events
+    pub fn with_event_store(mut self, event_store: Arc<dyn EventStore>) -> Self {
+        self.event_store = Some(event_store);
+        self
+    }
+
+    /// Empties expired todos from `trash`
+    pub fn with_trash(mut self, trash: Arc<dyn TrashRepository>) -> Self {
+        self.trash = Some(trash);
+        self
+    }
+
+    /// Measures ages against `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Deletes the expired done todos, empties the expired trash and compacts the expired
+    /// events
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "apply_retention"), err)
+    )]
+    pub async fn apply(&self) -> Result<RetentionReport, TodoError> {
+        observe("apply_retention", async {
+            let now = self.clock.now();
+            let mut report = RetentionReport::default();
+            for todo in self.todo_repository.find_all().await? {
+                let Some(days) = self.policy.rules_for(&todo.id).done_todo_days else {
+                    continue;
+                };
+                if todo.state != TodoState::Done {
+                    continue;
+                }
+                let cutoff = now
+                    .checked_sub_days(Days::new(days.into()))
+                    .unwrap_or(DateTime::<Utc>::MIN_UTC);
+                if state_since(self.event_store.as_deref(), &todo)? <= cutoff {
+                    self.todo_repository.delete(&todo.id).await?;
+                    report.deleted_todos.push(todo.id);
+                }
+            }
+            if let Some(trash) = &self.trash {
+                for trashed in trash.find_all().await? {
+                    let Some(days) = self.policy.rules_for(trashed.id()).trash_days else {
+                        continue;
+                    };
+                    let cutoff = now
+                        .checked_sub_days(Days::new(days.into()))
+                        .unwrap_or(DateTime::<Utc>::MIN_UTC);
+                    if trashed.deleted_at <= cutoff {
+                        trash.remove(trashed.id()).await?;
+                        report.emptied_trash.push(trashed.todo.id);
+                    }
+                }
+            }
+            if let (Some(store), Some(months)) =
+                (&self.event_store, self.policy.default.event_months)
+            {
+                let cutoff = now
+                    .checked_sub_months(Months::new(months))
+                    .unwrap_or(DateTime::<Utc>::MIN_UTC);
+                report.compacted_events = store.compact(cutoff)?;
+            }
+            Ok(report)
+        })
+        .await
+    }
+}
+
+/// When `todo` last entered its current state according to `event_store`, or was created
+/// when that is unknown
+///
+/// Tenant todos are looked up by their id as stored and as their tenant's handlers see it.
+pub(crate) fn state_since(
+    event_store: Option<&dyn EventStore>,
+    todo: &Todo,
+) -> Result<DateTime<Utc>, TodoError> {
+    let Some(store) = event_store else {
+        return Ok(todo.created_at);
+    };
+    let mut since = None;
+    for event in events_of(store, todo)? {
+        if let TodoEvent::TodoStateChanged {
+            to_state,
+            changed_at,
+            ..
+        } = event
+            && to_state == todo.state
+        {
+            since = since.max(Some(changed_at));
+        }
+    }
+    Ok(since.unwrap_or(todo.created_at))
+}
+
+/// The events of `todo` in `event_store`, under its id as stored and, for tenant todos, as
+/// their tenant's handlers see it
+pub(crate) fn events_of(
+    event_store: &dyn EventStore,
+    todo: &Todo,
+) -> Result<Vec<TodoEvent>, TodoError> {
+    let mut events = event_store.find_by_todo(&todo.id)?;
+    if let Some((_, id)) = todo.id.split_once('/') {
+        events.extend(event_store.find_by_todo(id)?);
+    }
+    Ok(events)
+}
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use crate::domain::api_key::{ApiKey, ApiKeyError, ApiKeyRepository};
+use crate::{Clock, SystemClock};
+
+pub struct RevokeApiKeyHandler<R = Box<dyn ApiKeyRepository>> {
+    api_key_repository: R,
+    clock: Arc<dyn Clock>,
+}
+
+impl<R: ApiKeyRepository> RevokeApiKeyHandler<R> {
+    pub fn new(api_key_repository: R) -> Self {
+        Self {
+            api_key_repository,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Stamps revocations with `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Stops accepting the key with `id`; revoking a revoked key changes nothing
+    ///
+    /// # Returns
+    /// - `Ok(ApiKey)`: The revoked key
+    /// - `Err(ApiKeyError::ApiKeyNotFound)`: If there is no key with `id`
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "revoke_api_key", api_key.id = %id), err)
+    )]
+    pub async fn revoke(&self, id: String) -> Result<ApiKey, ApiKeyError> {
+        let mut key = self
+            .api_key_repository
+            .find_by_id(&id)
+            .await?
+            .ok_or(ApiKeyError::ApiKeyNotFound)?;
+        if !key.is_revoked() {
+            key.revoke(&*self.clock);
+            self.api_key_repository.save(&key).await?;
+        }
+        Ok(key)
+    }
+}
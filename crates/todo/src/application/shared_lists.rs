@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use crate::application::metrics::observe;
+use crate::domain::access::{MembershipRepository, Role};
+use crate::infrastructure::repositories::todo::TenantTodoRepository;
+use crate::{TenantId, Todo, TodoError, TodoRepository};
+
+/// A todo list a subject is a member of, with its todos
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SharedList {
+    pub list: TenantId,
+    /// The subject's role on the list
+    pub role: Role,
+    /// The todos of the list, with the ids its handlers see
+    pub todos: Vec<Todo>,
+}
+
+/// Lists the todos of every list a subject is a member of, their own and those shared with
+/// them through an accepted invitation
+///
+/// The todo repository is the one shared by every tenant, holding the todos under
+/// `<tenant>/<id>`, as for `UserDataHandler`.
+pub struct GetSharedListsHandler {
+    todo_repository: Arc<dyn TodoRepository>,
+    memberships: Arc<dyn MembershipRepository>,
+}
+
+impl GetSharedListsHandler {
+    pub fn new(
+        todo_repository: Arc<dyn TodoRepository>,
+        memberships: Arc<dyn MembershipRepository>,
+    ) -> Self {
+        Self {
+            todo_repository,
+            memberships,
+        }
+    }
+
+    /// The lists `subject` has a role on, ordered by list id
+    ///
+    /// # Returns
+    /// - `Err(TodoError::RepositoryError)`: If the roles or the todos cannot be read
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "get_shared_lists"), err)
+    )]
+    pub async fn shared_lists(&self, subject: &str) -> Result<Vec<SharedList>, TodoError> {
+        observe("get_shared_lists", async {
+            let mut memberships = self
+                .memberships
+                .find_by_subject(subject)
+                .await
+                .map_err(|err| TodoError::RepositoryError(err.to_string()))?;
+            memberships.sort_by(|a, b| a.list.cmp(&b.list));
+            let mut lists = Vec::with_capacity(memberships.len());
+            for membership in memberships {
+                let todos = TenantTodoRepository::new(
+                    self.todo_repository.clone(),
+                    membership.list.clone(),
+                )
+                .find_all()
+                .await?;
+                lists.push(SharedList {
+                    list: membership.list,
+                    role: membership.role,
+                    todos,
+                });
+            }
+            Ok(lists)
+        })
+        .await
+    }
+}
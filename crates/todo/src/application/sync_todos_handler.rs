@@ -0,0 +1,182 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::application::import_todos_handler::ImportTodosHandler;
+use crate::application::metrics::{observe, record_events};
+use crate::domain::import::{ImportMapping, ImportMappingRepository, ImportPolicy};
+use crate::domain::sync::RemoteTaskList;
+use crate::{Clock, EventSink, SystemClock, Todo, TodoError, TodoEvent, TodoRepository};
+
+/// What a sync changed on each side
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    /// Local changes: `TodoCreated` and `TodoStateChanged` for the pulled tasks, and
+    /// `TodoDeleted` for the todos whose task was removed
+    pub events: Vec<TodoEvent>,
+    /// Number of todos pushed as new remote tasks
+    pub created: usize,
+    /// Number of todos pushed as updates of their remote task
+    pub updated: usize,
+    /// Number of todos not pushed because their task changed since the pull; the next sync
+    /// merges the task and pushes them
+    pub deferred: usize,
+}
+
+/// Syncs the todos of a repository with a RemoteTaskList, both ways
+///
+/// Remote tasks are linked to todos by the import mappings of the list's source, which
+/// remember the values of each task when it was last synced. A sync pulls the changed
+/// tasks and merges them as `ImportPolicy::Merge` does, so a field changed on one side only
+/// takes that side's value and a field changed on both keeps the local one. It then pushes
+/// every todo without a task, and every todo whose description or state differs from the
+/// last synced values.
+///
+/// A removed task deletes its todo unless the todo changed since the last sync; such a todo
+/// is pushed as a new task instead. A todo whose task changed between the pull and the push,
+/// as lists that check etags report, is left for the next sync.
+pub struct SyncTodosHandler<R = Arc<dyn TodoRepository>> {
+    todo_repository: R,
+    remote: Arc<dyn RemoteTaskList>,
+    mappings: Arc<dyn ImportMappingRepository>,
+    event_sink: Option<Arc<dyn EventSink>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl<R: TodoRepository + Clone> SyncTodosHandler<R> {
+    pub fn new(
+        todo_repository: R,
+        remote: Arc<dyn RemoteTaskList>,
+        mappings: Arc<dyn ImportMappingRepository>,
+    ) -> Self {
+        Self {
+            todo_repository,
+            remote,
+            mappings,
+            event_sink: None,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Passes the events of every local change to `event_sink`
+    pub fn with_event_sink(mut self, event_sink: Arc<dyn EventSink>) -> Self {
+        self.event_sink = Some(event_sink);
+        self
+    }
+
+    /// Stamps local changes and mappings with `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn record(&self, events: &[TodoEvent]) -> Result<(), TodoError> {
+        match &self.event_sink {
+            Some(event_sink) => event_sink.record(events),
+            None => Ok(()),
+        }
+    }
+
+    /// Pulls the remote changes into the repository, then pushes the local ones
+    ///
+    /// # Returns
+    /// - `Ok(SyncReport)`: The local events and the number of tasks created and updated
+    /// - `Err(TodoError)`: If the service or a repository fails; the changes made before
+    ///   stay, and the next sync picks up the rest
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "sync_todos", source = %self.remote.source()), err)
+    )]
+    pub async fn sync(&self) -> Result<SyncReport, TodoError> {
+        observe("sync_todos", async {
+            let source = self.remote.source();
+            let changes = self.remote.pull().await?;
+            let mut import = ImportTodosHandler::new(self.todo_repository.clone())
+                .with_import_mappings(self.mappings.clone())
+                .with_policy(ImportPolicy::Merge)
+                .with_clock(self.clock.clone());
+            if let Some(event_sink) = &self.event_sink {
+                import = import.with_event_sink(event_sink.clone());
+            }
+            let mut report = SyncReport {
+                events: import.save_imported(source, changes.todos).await?,
+                ..SyncReport::default()
+            };
+
+            let mut last_synced: HashMap<Arc<str>, ImportMapping> = HashMap::new();
+            for mapping in self.mappings.find_by_source(source).await? {
+                match last_synced.get(&mapping.todo_id) {
+                    Some(newer) if newer.imported_at > mapping.imported_at => {}
+                    _ => {
+                        last_synced.insert(mapping.todo_id.clone(), mapping);
+                    }
+                }
+            }
+            let removed: HashSet<&str> = changes.removed.iter().map(String::as_str).collect();
+            let now = self.clock.now();
+            let mut deleted = Vec::new();
+            let mut synced = Vec::new();
+            for todo in self.todo_repository.find_all().await? {
+                let mapping = last_synced.get(&todo.id);
+                let changed = mapping.is_none_or(|mapping| {
+                    mapping.description != todo.description || mapping.state != todo.state
+                });
+                match mapping {
+                    Some(mapping) if removed.contains(&*mapping.external_id) => {
+                        if !changed {
+                            self.todo_repository.delete(&todo.id).await?;
+                            deleted.push(TodoEvent::TodoDeleted {
+                                id: todo.id.clone(),
+                                deleted_at: now,
+                            });
+                            continue;
+                        }
+                        if let Some(remote_id) = self.remote.create(&todo).await? {
+                            synced.push(synced_mapping(source, remote_id.into(), &todo, now));
+                            report.created += 1;
+                        }
+                    }
+                    Some(mapping) if changed => {
+                        if !self.remote.update(&mapping.external_id, &todo).await? {
+                            report.deferred += 1;
+                            continue;
+                        }
+                        synced.push(synced_mapping(
+                            source,
+                            mapping.external_id.clone(),
+                            &todo,
+                            now,
+                        ));
+                        report.updated += 1;
+                    }
+                    Some(_) => {}
+                    None => {
+                        if let Some(remote_id) = self.remote.create(&todo).await? {
+                            synced.push(synced_mapping(source, remote_id.into(), &todo, now));
+                            report.created += 1;
+                        }
+                    }
+                }
+            }
+            self.mappings.save_all(&synced).await?;
+            record_events(&deleted);
+            self.record(&deleted)?;
+            report.events.extend(deleted);
+            Ok(report)
+        })
+        .await
+    }
+}
+
+/// The mapping of `todo` to the remote task `remote_id`, with the todo's values as synced
+fn synced_mapping(
+    source: &str,
+    remote_id: Arc<str>,
+    todo: &Todo,
+    now: chrono::DateTime<chrono::Utc>,
+) -> ImportMapping {
+    let task = Todo {
+        id: remote_id,
+        ..todo.clone()
+    };
+    ImportMapping::new(source, &task, todo.id.clone(), now)
+}
@@ -0,0 +1,132 @@
+#[cfg(feature = "serde")]
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::application::add_todo_handler::AddTodoHandler;
+use crate::application::change_todo_state_handler::ChangeTodoStateHandler;
+use crate::application::delete_todo_handler::DeleteTodoHandler;
+use crate::application::get_todos_handler::GetTodosHandler;
+#[cfg(feature = "serde")]
+use crate::infrastructure::repositories::todo::FileTodoRepository;
+use crate::infrastructure::repositories::todo::InMemoryTodoRepository;
+use crate::{Todo, TodoError, TodoRepository, TodoState};
+
+/// The application handlers over one repository, behind methods that return todos
+///
+/// For programs that want a todo list without wiring handlers and repositories:
+///
+/// ```
+/// use todo::prelude::*;
+///
+/// # futures::executor::block_on(async {
+/// let app = TodoApp::in_memory();
+/// let todo = app.add("Write docs").await?;
+/// app.complete(&todo.id).await?;
+/// for todo in app.list().await? {
+///     println!("{:?} {}", todo.state, todo.description);
+/// }
+/// # Ok::<(), TodoError>(())
+/// # }).unwrap();
+/// ```
+///
+/// The handlers stay available for programs that need events, event sinks or their own
+/// repository wiring.
+pub struct TodoApp {
+    repository: Arc<dyn TodoRepository>,
+    add_todo_handler: AddTodoHandler<Arc<dyn TodoRepository>>,
+    get_todos_handler: GetTodosHandler<Arc<dyn TodoRepository>>,
+    change_todo_state_handler: ChangeTodoStateHandler<Arc<dyn TodoRepository>>,
+    delete_todo_handler: DeleteTodoHandler<Arc<dyn TodoRepository>>,
+}
+
+impl TodoApp {
+    /// Creates a new TodoApp over `repository`
+    pub fn new(repository: Arc<dyn TodoRepository>) -> Self {
+        TodoApp {
+            add_todo_handler: AddTodoHandler::new(repository.clone()),
+            get_todos_handler: GetTodosHandler::new(repository.clone()),
+            change_todo_state_handler: ChangeTodoStateHandler::new(repository.clone()),
+            delete_todo_handler: DeleteTodoHandler::new(repository.clone()),
+            repository,
+        }
+    }
+
+    /// Creates a new TodoApp whose todos are lost when it is dropped
+    pub fn in_memory() -> Self {
+        Self::new(Arc::new(InMemoryTodoRepository::new()))
+    }
+
+    /// Creates a new TodoApp storing its todos in the JSON file at `path`
+    #[cfg(feature = "serde")]
+    pub fn file(path: impl Into<PathBuf>) -> Self {
+        Self::new(Arc::new(FileTodoRepository::new(path)))
+    }
+
+    /// Adds a todo
+    ///
+    /// # Returns
+    /// - `Ok(Todo)`: The new todo, in `TodoState::Todo`
+    /// - `Err(TodoError::EmptyDescription)`: If `description` is blank
+    pub async fn add(&self, description: impl Into<String>) -> Result<Todo, TodoError> {
+        let (todo, _) = self.add_todo_handler.new_todo(description.into()).await?;
+        Ok(todo)
+    }
+
+    /// Every todo, oldest first
+    pub async fn list(&self) -> Result<Vec<Todo>, TodoError> {
+        let mut todos = self.get_todos_handler.get_todos().await?;
+        todos.sort_by_key(|todo| todo.created_at);
+        Ok(todos)
+    }
+
+    /// The todo with `id`
+    ///
+    /// # Returns
+    /// - `Err(TodoError::TodoNotFound)`: If there is none
+    pub async fn get(&self, id: &str) -> Result<Todo, TodoError> {
+        self.repository
+            .find_by_id(id)
+            .await?
+            .ok_or(TodoError::TodoNotFound)
+    }
+
+    /// Moves a todo to `TodoState::InProgress`
+    ///
+    /// # Returns
+    /// - `Ok(Todo)`: The updated todo
+    /// - `Err(TodoError::TodoNotFound)`: If there is no todo with `id`
+    /// - `Err(TodoError::InvalidStateTransition)`: If it is not in `TodoState::Todo`
+    pub async fn start(&self, id: &str) -> Result<Todo, TodoError> {
+        self.change_state(id, TodoState::InProgress).await
+    }
+
+    /// Moves a todo to `TodoState::Done`, starting it first if it is still `TodoState::Todo`
+    ///
+    /// # Returns
+    /// - `Ok(Todo)`: The updated todo
+    /// - `Err(TodoError::TodoNotFound)`: If there is no todo with `id`
+    /// - `Err(TodoError::InvalidStateTransition)`: If it is already done
+    pub async fn complete(&self, id: &str) -> Result<Todo, TodoError> {
+        if self.get(id).await?.state == TodoState::Todo {
+            self.change_state(id, TodoState::InProgress).await?;
+        }
+        self.change_state(id, TodoState::Done).await
+    }
+
+    /// Deletes a todo
+    ///
+    /// # Returns
+    /// - `Err(TodoError::TodoNotFound)`: If there is no todo with `id`
+    pub async fn delete(&self, id: &str) -> Result<(), TodoError> {
+        self.delete_todo_handler.delete_todo(id.to_string()).await?;
+        Ok(())
+    }
+
+    async fn change_state(&self, id: &str, state: TodoState) -> Result<Todo, TodoError> {
+        let (todo, _) = self
+            .change_todo_state_handler
+            .change_state(id.to_string(), state)
+            .await?;
+        Ok(todo)
+    }
+}
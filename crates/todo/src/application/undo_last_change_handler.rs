@@ -0,0 +1,111 @@
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+use crate::application::metrics::{observe, record_events};
+use crate::{Clock, EventStore, SystemClock, Todo, TodoError, TodoEvent, TodoRepository};
+
+/// Undoes the most recent change of a todo with a compensating event
+///
+/// The last event the EventStore recorded for the todo is reverted by a new change, which
+/// is saved and recorded like any other, so the history stays append-only and shows both
+/// the change and its undo. Undoing twice therefore redoes the change.
+///
+/// A state change is compensated by the change back to its `from_state`, a description or
+/// priority change by the change back to the previous value, a due date change by the change
+/// back to the due date of the todo's previous `TodoDueDateChanged`, or no due date, and an
+/// added or removed tag by removing or adding it again. Creating a todo and the other
+/// changes have no compensating change. Undoing a change to `DONE` does not block the todos
+/// it released again.
+pub struct UndoLastChangeHandler<R = Box<dyn TodoRepository>> {
+    todo_repository: R,
+    event_store: Arc<dyn EventStore>,
+    clock: Arc<dyn Clock>,
+}
+
+impl<R: TodoRepository> UndoLastChangeHandler<R> {
+    pub fn new(todo_repository: R, event_store: Arc<dyn EventStore>) -> Self {
+        Self {
+            todo_repository,
+            event_store,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Stamps compensating changes with `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Reverts the most recent change of the todo with `id`
+    ///
+    /// # Returns
+    /// - `Ok((Todo, Vec<TodoEvent>))`: The todo as saved, and the compensating events,
+    ///   already recorded in the store
+    /// - `Err(TodoError::TodoNotFound)`: If the todo does not exist or has no recorded event
+    /// - `Err(TodoError::InvalidStateTransition)`: If the last event has no compensating
+    ///   change, or the todo was changed since without the store recording it
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "undo_last_change", todo.id = %id), err)
+    )]
+    pub async fn undo_last_change(&self, id: String) -> Result<(Todo, Vec<TodoEvent>), TodoError> {
+        observe("undo_last_change", async move {
+            let history = self.event_store.find_by_todo(&id)?;
+            let Some((last, earlier)) = history.split_last() else {
+                return Err(TodoError::TodoNotFound);
+            };
+            let mut todo = self
+                .todo_repository
+                .find_by_id(&id)
+                .await?
+                .ok_or(TodoError::TodoNotFound)?;
+            let events = match last {
+                TodoEvent::TodoStateChanged {
+                    from_state,
+                    to_state,
+                    ..
+                } if todo.state == *to_state => {
+                    todo.update_state_with_clock(*from_state, &*self.clock)?
+                }
+                TodoEvent::TodoDescriptionUpdated { old, new, .. } if todo.description == *new => {
+                    todo.update_description_with_clock(old.to_string(), &*self.clock)?
+                }
+                TodoEvent::TodoDueDateChanged { due_at, .. } if todo.due_at == *due_at => {
+                    match previous_due_date(earlier) {
+                        Some(due_at) => todo.set_due_date_with_clock(due_at, &*self.clock)?,
+                        None => todo.clear_due_date_with_clock(&*self.clock)?,
+                    }
+                }
+                TodoEvent::TodoPriorityChanged {
+                    from_priority,
+                    to_priority,
+                    ..
+                } if todo.priority == *to_priority => {
+                    todo.set_priority_with_clock(*from_priority, &*self.clock)
+                }
+                TodoEvent::TodoTagAdded { tag, .. } if todo.has_tag(tag) => {
+                    todo.remove_tag_with_clock(tag.as_str(), &*self.clock)?
+                }
+                TodoEvent::TodoTagRemoved { tag, .. } if !todo.has_tag(tag) => {
+                    todo.add_tag_with_clock(tag.as_str(), &*self.clock)?
+                }
+                _ => return Err(TodoError::InvalidStateTransition),
+            };
+            self.todo_repository.save(&todo).await?;
+            todo.dirty = Some(false);
+            record_events(&events);
+            self.event_store.record(&events)?;
+            Ok((todo, events))
+        })
+        .await
+    }
+}
+
+/// The due date set by the last `TodoDueDateChanged` of `history`, or `None` if it never had one
+fn previous_due_date(history: &[TodoEvent]) -> Option<DateTime<Utc>> {
+    history.iter().rev().find_map(|event| match event {
+        TodoEvent::TodoDueDateChanged { due_at, .. } => Some(*due_at),
+        _ => None,
+    })?
+}
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use crate::application::metrics::{observe, record_events};
+use crate::{Clock, EventSink, SystemClock, Todo, TodoError, TodoEvent, TodoRepository};
+
+pub struct UpdateTodoDescriptionHandler<R = Box<dyn TodoRepository>> {
+    todo_repository: R,
+    event_sink: Option<Arc<dyn EventSink>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl<R: TodoRepository> UpdateTodoDescriptionHandler<R> {
+    pub fn new(todo_repository: R) -> Self {
+        Self {
+            todo_repository,
+            event_sink: None,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Passes the events of every saved change to `event_sink`
+    pub fn with_event_sink(mut self, event_sink: Arc<dyn EventSink>) -> Self {
+        self.event_sink = Some(event_sink);
+        self
+    }
+
+    /// Stamps description updates with `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn record(&self, events: &[TodoEvent]) -> Result<(), TodoError> {
+        match &self.event_sink {
+            Some(event_sink) => event_sink.record(events),
+            None => Ok(()),
+        }
+    }
+
+    /// Replaces the description of the todo with `id` by `description`, and saves the todo
+    ///
+    /// # Returns
+    /// - `Ok((Todo, Vec<TodoEvent>))`: The todo as saved, and its `TodoDescriptionUpdated`
+    ///   event; no event, and nothing saved, when the description was already `description`
+    /// - `Err(TodoError::TodoNotFound)`: If there is no todo with `id`
+    /// - `Err(TodoError::EmptyDescription)`: If `description` is empty
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "update_todo_description", todo.id = %id), err)
+    )]
+    pub async fn update_description(
+        &self,
+        id: String,
+        description: String,
+    ) -> Result<(Todo, Vec<TodoEvent>), TodoError> {
+        observe("update_todo_description", async move {
+            let mut todo = self
+                .todo_repository
+                .find_by_id(&id)
+                .await?
+                .ok_or(TodoError::TodoNotFound)?;
+            let events = todo.update_description_with_clock(description, &*self.clock)?;
+            if events.is_empty() {
+                return Ok((todo, events));
+            }
+            self.todo_repository.save(&todo).await?;
+            todo.dirty = Some(false);
+            record_events(&events);
+            self.record(&events)?;
+            Ok((todo, events))
+        })
+        .await
+    }
+}
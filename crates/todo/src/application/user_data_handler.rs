@@ -0,0 +1,221 @@
+use std::sync::Arc;
+
+use crate::application::activity_feed::ActivityFeed;
+use crate::application::metrics::observe;
+use crate::domain::access::{Membership, MembershipRepository};
+use crate::domain::notification::{Device, DeviceRepository};
+use crate::infrastructure::repositories::todo::TenantTodoRepository;
+use crate::{EventStore, TenantId, Todo, TodoError, TodoEvent, TodoRepository};
+
+/// Everything stored about one user
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserData {
+    pub subject: Arc<str>,
+    pub tenant: TenantId,
+    /// The todos of the user's tenant, with the ids its handlers see
+    pub todos: Vec<Todo>,
+    /// The events of those todos, in the order they were recorded
+    pub events: Vec<TodoEvent>,
+    /// The devices registered for the user's push notifications
+    pub devices: Vec<Device>,
+    /// The user's roles on todo lists
+    pub memberships: Vec<Membership>,
+}
+
+impl UserData {
+    /// Whether nothing is stored about the user
+    pub fn is_empty(&self) -> bool {
+        self.todos.is_empty()
+            && self.events.is_empty()
+            && self.devices.is_empty()
+            && self.memberships.is_empty()
+    }
+}
+
+/// What `UserDataHandler::erase` removed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ErasureReport {
+    pub todos: usize,
+    pub events: usize,
+    pub devices: usize,
+    pub memberships: usize,
+    /// Activities removed from the activity feed
+    pub activities: usize,
+}
+
+/// Exports and erases the data of a user, for data subject requests
+///
+/// A user is a subject, such as a JWT's `sub`, owning the todos of a tenant, their own
+/// list. The todo repository is the one shared by every tenant, holding the todos under
+/// `<tenant>/<id>`. Events, devices, roles and the activity feed are only covered once
+/// their stores are given with the `with_*` methods. Events are found by the id of the
+/// todo as stored and as the tenant's handlers see it.
+pub struct UserDataHandler {
+    todo_repository: Arc<dyn TodoRepository>,
+    event_store: Option<Arc<dyn EventStore>>,
+    devices: Option<Arc<dyn DeviceRepository>>,
+    memberships: Option<Arc<dyn MembershipRepository>>,
+    activity: Option<Arc<ActivityFeed>>,
+}
+
+impl UserDataHandler {
+    pub fn new(todo_repository: Arc<dyn TodoRepository>) -> Self {
+        Self {
+            todo_repository,
+            event_store: None,
+            devices: None,
+            memberships: None,
+            activity: None,
+        }
+    }
+
+    /// Covers the events of the user's todos in `event_store`
+    pub fn with_event_store(mut self, event_store: Arc<dyn EventStore>) -> Self {
+        self.event_store = Some(event_store);
+        self
+    }
+
+    /// Covers the devices the user registered in `devices`
+    pub fn with_devices(mut self, devices: Arc<dyn DeviceRepository>) -> Self {
+        self.devices = Some(devices);
+        self
+    }
+
+    /// Covers the user's roles in `memberships`
+    pub fn with_memberships(mut self, memberships: Arc<dyn MembershipRepository>) -> Self {
+        self.memberships = Some(memberships);
+        self
+    }
+
+    /// Erases the activities about the user's todos from `activity`
+    pub fn with_activity_feed(mut self, activity: Arc<ActivityFeed>) -> Self {
+        self.activity = Some(activity);
+        self
+    }
+
+    /// Collects everything stored about `subject` and the todos of `tenant`
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "export_user_data", tenant = %tenant), err)
+    )]
+    pub async fn export(&self, subject: &str, tenant: &TenantId) -> Result<UserData, TodoError> {
+        observe("export_user_data", self.collect(subject, tenant)).await
+    }
+
+    /// Removes everything stored about `subject` and the todos of `tenant`, then checks that
+    /// nothing is left
+    ///
+    /// # Returns
+    /// - `Ok(ErasureReport)`: What was removed, once nothing is left
+    /// - `Err(TodoError::RepositoryError)`: If a store fails, or still holds data of the user
+    ///   afterwards; erasing again resumes where it stopped
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(command = "erase_user_data", tenant = %tenant), err)
+    )]
+    pub async fn erase(
+        &self,
+        subject: &str,
+        tenant: &TenantId,
+    ) -> Result<ErasureReport, TodoError> {
+        observe("erase_user_data", async {
+            let data = self.collect(subject, tenant).await?;
+            let mut report = ErasureReport::default();
+            let todos = self.tenant_repository(tenant);
+            for todo in &data.todos {
+                todos.delete(&todo.id).await?;
+                report.todos += 1;
+                if let Some(activity) = &self.activity {
+                    report.activities += activity.forget(Some(tenant), &todo.id);
+                }
+                for id in event_ids(tenant, &todo.id) {
+                    if let Some(store) = &self.event_store {
+                        report.events += store.remove_todo(&id)?;
+                    }
+                    if let Some(activity) = &self.activity {
+                        report.activities += activity.forget(None, &id);
+                    }
+                }
+            }
+            if let Some(devices) = &self.devices {
+                for device in &data.devices {
+                    devices
+                        .remove(&device.token)
+                        .await
+                        .map_err(repository_error)?;
+                    report.devices += 1;
+                }
+            }
+            if let Some(memberships) = &self.memberships {
+                for membership in &data.memberships {
+                    memberships
+                        .remove(&membership.list, &membership.subject)
+                        .await
+                        .map_err(repository_error)?;
+                    report.memberships += 1;
+                }
+            }
+            let left = self.collect(subject, tenant).await?;
+            if !left.is_empty() {
+                return Err(TodoError::RepositoryError(format!(
+                    "erasure incomplete: {} todos, {} events, {} devices and {} roles left",
+                    left.todos.len(),
+                    left.events.len(),
+                    left.devices.len(),
+                    left.memberships.len()
+                )));
+            }
+            Ok(report)
+        })
+        .await
+    }
+
+    async fn collect(&self, subject: &str, tenant: &TenantId) -> Result<UserData, TodoError> {
+        let mut todos = self.tenant_repository(tenant).find_all().await?;
+        todos.sort_by_key(|todo| todo.created_at);
+        let mut events = Vec::new();
+        if let Some(store) = &self.event_store {
+            for todo in &todos {
+                for id in event_ids(tenant, &todo.id) {
+                    events.extend(store.find_by_todo(&id)?);
+                }
+            }
+        }
+        let devices = match &self.devices {
+            Some(devices) => devices
+                .find_by_subject(subject)
+                .await
+                .map_err(repository_error)?,
+            None => Vec::new(),
+        };
+        let memberships = match &self.memberships {
+            Some(memberships) => memberships
+                .find_by_subject(subject)
+                .await
+                .map_err(repository_error)?,
+            None => Vec::new(),
+        };
+        Ok(UserData {
+            subject: subject.into(),
+            tenant: tenant.clone(),
+            todos,
+            events,
+            devices,
+            memberships,
+        })
+    }
+
+    fn tenant_repository(&self, tenant: &TenantId) -> TenantTodoRepository {
+        TenantTodoRepository::new(self.todo_repository.clone(), tenant.clone())
+    }
+}
+
+/// The ids a tenant's todo may have in the event store: as its handlers see it, then as
+/// stored
+fn event_ids(tenant: &TenantId, id: &str) -> [String; 2] {
+    [id.to_string(), format!("{}/{}", tenant, id)]
+}
+
+fn repository_error(err: impl std::fmt::Display) -> TodoError {
+    TodoError::RepositoryError(err.to_string())
+}
@@ -0,0 +1,339 @@
+//! proptest strategies for the domain types
+//!
+//! `Todo`, `TodoState` and `TodoEvent` implement `Arbitrary`, so `any::<Todo>()` works in
+//! `proptest!` blocks. `TodoHistory` and `EventStream` pair todos with the valid events
+//! that built them, for invariants such as replaying events reproducing the aggregate:
+//!
+//! ```
+//! use proptest::prelude::*;
+//! use todo::Todo;
+//! use todo::arbitrary::TodoHistory;
+//!
+//! proptest!(|(history: TodoHistory)| {
+//!     prop_assert_eq!(Todo::replay(&history.events)?, history.todo);
+//! });
+//! ```
+
+use chrono::{DateTime, TimeDelta, Utc};
+use proptest::collection::vec;
+use proptest::prelude::*;
+use proptest::sample::Index;
+use std::sync::Arc;
+
+use crate::{Attachment, Clock, FixedClock, Priority, Subtask, Tag, Todo, TodoEvent, TodoState};
+
+/// Earliest generated time, 2000-01-01T00:00:00Z
+const MIN_TIMESTAMP: i64 = 946_684_800;
+/// Latest generated creation time, 2100-01-01T00:00:00Z
+const MAX_TIMESTAMP: i64 = 4_102_444_800;
+/// Most state changes in a TodoHistory
+const MAX_TRANSITIONS: usize = 16;
+/// Most todos in an EventStream
+const MAX_TODOS: usize = 8;
+/// Longest time between two changes of a todo, one week in seconds
+const MAX_GAP: i64 = 7 * 24 * 60 * 60;
+
+const STATES: [TodoState; 3] = [TodoState::Todo, TodoState::InProgress, TodoState::Done];
+
+/// Generates ids of 1 to 16 lowercase letters, digits and dashes
+pub fn todo_id() -> impl Strategy<Value = Arc<str>> {
+    "[a-z0-9][a-z0-9-]{0,15}".prop_map(Arc::from)
+}
+
+/// Generates valid descriptions: up to 64 printable characters, not all whitespace
+pub fn description() -> impl Strategy<Value = Arc<str>> {
+    "[^\\s\\p{C}][^\\p{C}]{0,63}".prop_map(Arc::from)
+}
+
+/// Generates up to 4 distinct tags of 1 to 12 lowercase letters, digits and dashes
+pub fn tags() -> impl Strategy<Value = Vec<Tag>> {
+    proptest::collection::btree_set("[a-z0-9-]{1,12}", 0..=4).prop_map(|names| {
+        names
+            .iter()
+            .map(|name| Tag::new(name).expect("generated tags are single words"))
+            .collect()
+    })
+}
+
+/// Generates up to 4 subtasks with distinct ids, each done or not
+pub fn subtasks() -> impl Strategy<Value = Vec<Subtask>> {
+    proptest::collection::btree_map(todo_id(), (description(), any::<bool>()), 0..=4).prop_map(
+        |subtasks| {
+            subtasks
+                .into_iter()
+                .map(|(id, (description, done))| Subtask {
+                    id,
+                    description,
+                    done,
+                })
+                .collect()
+        },
+    )
+}
+
+/// Generates up to 4 distinct blocker ids
+pub fn blocked_by() -> impl Strategy<Value = Vec<Arc<str>>> {
+    proptest::collection::btree_set(todo_id(), 0..=4).prop_map(|ids| ids.into_iter().collect())
+}
+
+/// Generates up to 4 attachments with distinct content hashes, of up to 1 GiB each
+pub fn attachments() -> impl Strategy<Value = Vec<Attachment>> {
+    let media_types = ["application/pdf", "image/png", "text/plain"];
+    proptest::collection::btree_map(
+        "[0-9a-f]{64}",
+        (
+            "[a-z0-9-]{1,12}\\.[a-z]{3}",
+            proptest::sample::select(media_types.to_vec()),
+            0..=1u64 << 30,
+        ),
+        0..=4,
+    )
+    .prop_map(|attachments| {
+        attachments
+            .into_iter()
+            .map(|(content_hash, (name, media_type, size))| Attachment {
+                content_hash: content_hash.into(),
+                name: name.into(),
+                media_type: media_type.into(),
+                size,
+            })
+            .collect()
+    })
+}
+
+/// Generates times between 2000 and 2100, to the second
+pub fn timestamp() -> impl Strategy<Value = DateTime<Utc>> {
+    (MIN_TIMESTAMP..MAX_TIMESTAMP)
+        .prop_map(|seconds| DateTime::from_timestamp(seconds, 0).unwrap_or_default())
+}
+
+/// Generates up to `max_len` states, each a valid transition from the one before it,
+/// starting from `from`
+///
+/// `from` itself is not part of the sequence.
+pub fn transitions(from: TodoState, max_len: usize) -> impl Strategy<Value = Vec<TodoState>> {
+    vec(any::<Index>(), 0..=max_len).prop_map(move |choices| {
+        let mut state = from;
+        let mut states = Vec::with_capacity(choices.len());
+        for choice in choices {
+            let next: Vec<TodoState> = STATES
+                .into_iter()
+                .filter(|next| state.can_transition_to(*next))
+                .collect();
+            if next.is_empty() {
+                break;
+            }
+            state = next[choice.index(next.len())];
+            states.push(state);
+        }
+        states
+    })
+}
+
+impl Arbitrary for TodoState {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        proptest::sample::select(STATES.to_vec()).boxed()
+    }
+}
+
+/// Any valid todo, not `dirty`; its state is not necessarily reachable from its events
+///
+/// A due date, when there is one, is up to a year after the creation time, and the todo has
+/// up to 4 tags, up to 4 subtasks, up to 4 blockers and up to 4 attachments.
+impl Arbitrary for Todo {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            todo_id(),
+            timestamp(),
+            description(),
+            any::<TodoState>(),
+            proptest::option::of(0..=52 * MAX_GAP),
+            proptest::sample::select(Priority::ALL.to_vec()),
+            tags(),
+            subtasks(),
+            blocked_by(),
+            attachments(),
+        )
+            .prop_map(
+                |(
+                    id,
+                    created_at,
+                    description,
+                    state,
+                    due_in,
+                    priority,
+                    tags,
+                    subtasks,
+                    blocked_by,
+                    attachments,
+                )| Todo {
+                    id,
+                    created_at,
+                    description,
+                    state,
+                    due_at: due_in.map(|seconds| created_at + TimeDelta::seconds(seconds)),
+                    priority,
+                    tags,
+                    subtasks,
+                    blocked_by,
+                    attachments,
+                    dirty: Some(false),
+                },
+            )
+            .boxed()
+    }
+}
+
+/// A `TodoCreated`, or a `TodoStateChanged` along an allowed transition
+impl Arbitrary for TodoEvent {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        let created =
+            (todo_id(), description(), timestamp()).prop_map(|(id, description, created_at)| {
+                TodoEvent::TodoCreated {
+                    id,
+                    description,
+                    created_at,
+                }
+            });
+        let changed = (todo_id(), any::<TodoState>(), any::<Index>(), timestamp()).prop_map(
+            |(id, from_state, choice, changed_at)| {
+                let next: Vec<TodoState> = STATES
+                    .into_iter()
+                    .filter(|next| from_state.can_transition_to(*next))
+                    .collect();
+                TodoEvent::TodoStateChanged {
+                    id,
+                    from_state,
+                    to_state: next[choice.index(next.len())],
+                    changed_at,
+                }
+            },
+        );
+        prop_oneof![created, changed].boxed()
+    }
+}
+
+/// A todo and the events that built it: its `TodoCreated`, then up to 16 valid state
+/// changes, each at least a second after the one before
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TodoHistory {
+    /// The todo after the last event, as a repository would return it
+    pub todo: Todo,
+    pub events: Vec<TodoEvent>,
+}
+
+impl TodoHistory {
+    /// Creates the todo and applies each state of `steps` the given number of seconds after
+    /// the previous change
+    ///
+    /// # Panics
+    /// - If a step is not a valid transition
+    fn build(
+        id: Arc<str>,
+        description: Arc<str>,
+        created_at: DateTime<Utc>,
+        steps: Vec<(TodoState, i64)>,
+    ) -> Self {
+        let mut todo = Todo {
+            id: id.clone(),
+            created_at,
+            description: description.clone(),
+            state: TodoState::Todo,
+            due_at: None,
+            priority: Priority::default(),
+            tags: Vec::new(),
+            subtasks: Vec::new(),
+            blocked_by: Vec::new(),
+            attachments: Vec::new(),
+            dirty: Some(false),
+        };
+        let mut events = vec![TodoEvent::TodoCreated {
+            id,
+            description,
+            created_at,
+        }];
+        let clock = FixedClock::new(created_at);
+        for (state, gap) in steps {
+            clock.set(clock.now() + TimeDelta::seconds(gap));
+            let changed = todo
+                .update_state_with_clock(state, &clock)
+                .expect("transitions() yields valid transitions");
+            events.extend(changed);
+        }
+        todo.dirty = Some(false);
+        TodoHistory { todo, events }
+    }
+}
+
+/// Builds histories from an id strategy, so an EventStream can give each todo its own id
+fn history(id: impl Strategy<Value = Arc<str>>) -> impl Strategy<Value = TodoHistory> {
+    (
+        id,
+        description(),
+        timestamp(),
+        transitions(TodoState::Todo, MAX_TRANSITIONS),
+        vec(1..=MAX_GAP, MAX_TRANSITIONS),
+    )
+        .prop_map(|(id, description, created_at, states, gaps)| {
+            let steps = states.into_iter().zip(gaps).collect();
+            TodoHistory::build(id, description, created_at, steps)
+        })
+}
+
+impl Arbitrary for TodoHistory {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        history(todo_id()).boxed()
+    }
+}
+
+/// The events of several todos, interleaved in the order they happened, and the todos they
+/// build
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventStream {
+    /// The todos after their last event, with distinct ids
+    pub todos: Vec<Todo>,
+    /// Every event of `todos`, ordered by time; each todo's events keep their order
+    pub events: Vec<TodoEvent>,
+}
+
+/// Generates streams of up to `max_todos` todos, named `todo-1`, `todo-2` and so on
+pub fn event_stream(max_todos: usize) -> impl Strategy<Value = EventStream> {
+    (0..=max_todos)
+        .prop_flat_map(|count| {
+            (1..=count)
+                .map(|index| history(Just(Arc::from(format!("todo-{}", index)))))
+                .collect::<Vec<_>>()
+        })
+        .prop_map(|histories| {
+            let mut events: Vec<TodoEvent> = histories
+                .iter()
+                .flat_map(|history| history.events.iter().cloned())
+                .collect();
+            events.sort_by_key(TodoEvent::occurred_at);
+            EventStream {
+                todos: histories.into_iter().map(|history| history.todo).collect(),
+                events,
+            }
+        })
+}
+
+impl Arbitrary for EventStream {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        event_stream(MAX_TODOS).boxed()
+    }
+}
@@ -0,0 +1,29 @@
+/// Errors of the memberships of todo lists and their repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccessError {
+    /// Returned when the subject has no role on the list
+    MembershipNotFound,
+    /// Returned when no invitation has the given id, or it is addressed to someone else
+    InvitationNotFound,
+    /// Returned when an invitation cannot be sent or answered, for the reason carried here
+    InvalidInvitation(String),
+    /// Returned when the caller's role on the list does not let them do this, for the
+    /// reason carried here
+    NotPermitted(String),
+    /// Returned when the underlying storage fails, carrying the backend's message
+    RepositoryError(String),
+}
+
+impl std::fmt::Display for AccessError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AccessError::MembershipNotFound => write!(f, "membership not found"),
+            AccessError::InvitationNotFound => write!(f, "invitation not found"),
+            AccessError::InvalidInvitation(reason) => write!(f, "invalid invitation: {}", reason),
+            AccessError::NotPermitted(reason) => write!(f, "not permitted: {}", reason),
+            AccessError::RepositoryError(message) => write!(f, "repository error: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for AccessError {}
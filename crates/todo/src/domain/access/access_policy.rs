@@ -0,0 +1,62 @@
+use std::collections::HashSet;
+
+use crate::domain::access::{Role, TodoAction};
+
+/// Which roles may take which actions on a todo list
+///
+/// The default policy lets viewers view, editors also add todos and change their state,
+/// and owners also delete them and manage webhooks. `allow` and `deny` adjust it, such as
+/// to keep editors from changing state on a shared list:
+///
+/// ```
+/// use todo::domain::access::{AccessPolicy, Role, TodoAction};
+///
+/// let policy = AccessPolicy::default().deny(Role::Editor, TodoAction::ChangeState);
+/// assert!(policy.permits(Role::Editor, TodoAction::Add));
+/// assert!(!policy.permits(Role::Editor, TodoAction::ChangeState));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessPolicy {
+    grants: HashSet<(Role, TodoAction)>,
+}
+
+impl AccessPolicy {
+    /// A policy that permits nothing, to build up with `allow`
+    pub fn empty() -> Self {
+        AccessPolicy {
+            grants: HashSet::new(),
+        }
+    }
+
+    /// Lets `role` take `action`
+    pub fn allow(mut self, role: Role, action: TodoAction) -> Self {
+        self.grants.insert((role, action));
+        self
+    }
+
+    /// Stops `role` from taking `action`
+    pub fn deny(mut self, role: Role, action: TodoAction) -> Self {
+        self.grants.remove(&(role, action));
+        self
+    }
+
+    /// Whether `role` may take `action`
+    pub fn permits(&self, role: Role, action: TodoAction) -> bool {
+        self.grants.contains(&(role, action))
+    }
+}
+
+impl Default for AccessPolicy {
+    fn default() -> Self {
+        AccessPolicy::empty()
+            .allow(Role::Viewer, TodoAction::View)
+            .allow(Role::Editor, TodoAction::View)
+            .allow(Role::Editor, TodoAction::Add)
+            .allow(Role::Editor, TodoAction::ChangeState)
+            .allow(Role::Owner, TodoAction::View)
+            .allow(Role::Owner, TodoAction::Add)
+            .allow(Role::Owner, TodoAction::ChangeState)
+            .allow(Role::Owner, TodoAction::Delete)
+            .allow(Role::Owner, TodoAction::ManageWebhooks)
+    }
+}
@@ -0,0 +1,159 @@
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+use crate::TenantId;
+use crate::domain::access::{AccessError, InvitationEvent, Membership, Role};
+
+/// Where an Invitation is in its lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InvitationStatus {
+    /// Sent, and not answered yet
+    Pending,
+    /// The invitee became a member of the list
+    Accepted,
+    /// The invitee turned it down
+    Declined,
+}
+
+impl InvitationStatus {
+    /// Parses `pending`, `accepted` or `declined`
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "pending" => Ok(InvitationStatus::Pending),
+            "accepted" => Ok(InvitationStatus::Accepted),
+            "declined" => Ok(InvitationStatus::Declined),
+            _ => Err(format!(
+                "unknown invitation status {:?}, expected pending, accepted or declined",
+                value
+            )),
+        }
+    }
+
+    /// `pending`, `accepted` or `declined`, as accepted by `parse`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InvitationStatus::Pending => "pending",
+            InvitationStatus::Accepted => "accepted",
+            InvitationStatus::Declined => "declined",
+        }
+    }
+}
+
+/// An offer from an owner of a todo list to share it with someone, with a role
+///
+/// The invitee is a subject, such as a JWT's `sub`, or an email address. An invitation is
+/// answered once: accepting it makes the subject answering a member of the list with the
+/// offered role, declining it leaves the list as it is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Invitation {
+    pub id: Arc<str>,
+    pub list: TenantId,
+    pub invitee: Arc<str>,
+    pub role: Role,
+    /// Subject of the owner who sent it
+    pub invited_by: Arc<str>,
+    pub status: InvitationStatus,
+    pub created_at: DateTime<Utc>,
+    /// When it was accepted or declined
+    pub answered_at: Option<DateTime<Utc>>,
+}
+
+impl Invitation {
+    /// Creates a pending Invitation of `invitee` to `list`
+    ///
+    /// # Returns
+    /// - `Ok((Invitation, InvitationEvent::InvitationSent))`
+    /// - `Err(AccessError::InvalidInvitation)`: If `invitee` is blank or has whitespace
+    pub fn new(
+        id: impl Into<Arc<str>>,
+        list: TenantId,
+        invitee: &str,
+        role: Role,
+        invited_by: impl Into<Arc<str>>,
+        created_at: DateTime<Utc>,
+    ) -> Result<(Self, InvitationEvent), AccessError> {
+        if invitee.is_empty() || invitee.chars().any(char::is_whitespace) {
+            return Err(AccessError::InvalidInvitation(format!(
+                "invalid invitee {:?}, expected a user id or an email address",
+                invitee
+            )));
+        }
+        let invitation = Invitation {
+            id: id.into(),
+            list,
+            invitee: invitee.into(),
+            role,
+            invited_by: invited_by.into(),
+            status: InvitationStatus::Pending,
+            created_at,
+            answered_at: None,
+        };
+        let event = InvitationEvent::InvitationSent {
+            id: invitation.id.clone(),
+            list: invitation.list.clone(),
+            invitee: invitation.invitee.clone(),
+            role,
+            invited_by: invitation.invited_by.clone(),
+            sent_at: created_at,
+        };
+        Ok((invitation, event))
+    }
+
+    /// Whether the invitation is addressed to `subject`: its invitee is `subject`, or an
+    /// email address equal to it but for the case of its letters
+    pub fn is_for(&self, subject: &str) -> bool {
+        *self.invitee == *subject
+            || (self.invitee.contains('@') && self.invitee.eq_ignore_ascii_case(subject))
+    }
+
+    /// Accepts the invitation on behalf of `subject`
+    ///
+    /// # Returns
+    /// - `Ok((Membership, InvitationEvent::InvitationAccepted))`: The role `subject` now has
+    ///   on the list, still to be saved
+    /// - `Err(AccessError::InvalidInvitation)`: If it was answered already
+    pub fn accept(
+        &mut self,
+        subject: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(Membership, InvitationEvent), AccessError> {
+        self.answer(InvitationStatus::Accepted, now)?;
+        let membership = Membership::new(self.list.clone(), subject, self.role);
+        let event = InvitationEvent::InvitationAccepted {
+            id: self.id.clone(),
+            list: self.list.clone(),
+            subject: membership.subject.clone(),
+            role: self.role,
+            accepted_at: now,
+        };
+        Ok((membership, event))
+    }
+
+    /// Declines the invitation
+    ///
+    /// # Returns
+    /// - `Ok(InvitationEvent::InvitationDeclined)`
+    /// - `Err(AccessError::InvalidInvitation)`: If it was answered already
+    pub fn decline(&mut self, now: DateTime<Utc>) -> Result<InvitationEvent, AccessError> {
+        self.answer(InvitationStatus::Declined, now)?;
+        Ok(InvitationEvent::InvitationDeclined {
+            id: self.id.clone(),
+            list: self.list.clone(),
+            invitee: self.invitee.clone(),
+            declined_at: now,
+        })
+    }
+
+    fn answer(&mut self, status: InvitationStatus, now: DateTime<Utc>) -> Result<(), AccessError> {
+        if self.status != InvitationStatus::Pending {
+            return Err(AccessError::InvalidInvitation(format!(
+                "invitation {} was {} already",
+                self.id,
+                self.status.as_str()
+            )));
+        }
+        self.status = status;
+        self.answered_at = Some(now);
+        Ok(())
+    }
+}
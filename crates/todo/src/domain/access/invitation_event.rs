@@ -0,0 +1,54 @@
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+use crate::TenantId;
+use crate::domain::access::Role;
+
+/// What happened to an Invitation, as returned by its lifecycle methods
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvitationEvent {
+    /// An owner of `list` invited `invitee` to it
+    InvitationSent {
+        id: Arc<str>,
+        list: TenantId,
+        invitee: Arc<str>,
+        role: Role,
+        invited_by: Arc<str>,
+        sent_at: DateTime<Utc>,
+    },
+    /// `subject` accepted, and became a member of `list` with `role`
+    InvitationAccepted {
+        id: Arc<str>,
+        list: TenantId,
+        subject: Arc<str>,
+        role: Role,
+        accepted_at: DateTime<Utc>,
+    },
+    /// `invitee` declined
+    InvitationDeclined {
+        id: Arc<str>,
+        list: TenantId,
+        invitee: Arc<str>,
+        declined_at: DateTime<Utc>,
+    },
+}
+
+impl InvitationEvent {
+    /// Id of the invitation the event is about
+    pub fn invitation_id(&self) -> &str {
+        match self {
+            InvitationEvent::InvitationSent { id, .. }
+            | InvitationEvent::InvitationAccepted { id, .. }
+            | InvitationEvent::InvitationDeclined { id, .. } => id,
+        }
+    }
+
+    /// The list the invitation is to
+    pub fn list(&self) -> &TenantId {
+        match self {
+            InvitationEvent::InvitationSent { list, .. }
+            | InvitationEvent::InvitationAccepted { list, .. }
+            | InvitationEvent::InvitationDeclined { list, .. } => list,
+        }
+    }
+}
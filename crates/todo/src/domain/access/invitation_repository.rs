@@ -0,0 +1,66 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::TenantId;
+use crate::domain::access::{AccessError, Invitation};
+
+/// Repository trait for the invitations to todo lists, answered or not
+#[async_trait]
+pub trait InvitationRepository: Send + Sync {
+    /// Saves an Invitation, replacing the one with the same id
+    async fn save(&self, invitation: &Invitation) -> Result<(), AccessError>;
+
+    /// Finds the Invitation `id`
+    ///
+    /// # Returns
+    /// - `Ok(Option<Invitation>)`: `Some(Invitation)` if found, `None` if not found
+    /// - `Err(AccessError)`: If retrieval fails
+    async fn find_by_id(&self, id: &str) -> Result<Option<Invitation>, AccessError>;
+
+    /// Finds every Invitation to the list, in the order they were first saved
+    async fn find_by_list(&self, list: &TenantId) -> Result<Vec<Invitation>, AccessError>;
+
+    /// Finds every Invitation addressed to `subject`, as decided by `Invitation::is_for`, in
+    /// the order they were first saved
+    async fn find_by_invitee(&self, subject: &str) -> Result<Vec<Invitation>, AccessError>;
+}
+
+/// Shares a single repository between the invitation commands of several front ends
+#[async_trait]
+impl<T: InvitationRepository + ?Sized> InvitationRepository for Arc<T> {
+    async fn save(&self, invitation: &Invitation) -> Result<(), AccessError> {
+        (**self).save(invitation).await
+    }
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<Invitation>, AccessError> {
+        (**self).find_by_id(id).await
+    }
+
+    async fn find_by_list(&self, list: &TenantId) -> Result<Vec<Invitation>, AccessError> {
+        (**self).find_by_list(list).await
+    }
+
+    async fn find_by_invitee(&self, subject: &str) -> Result<Vec<Invitation>, AccessError> {
+        (**self).find_by_invitee(subject).await
+    }
+}
+
+/// Lets owners hold a `Box<dyn InvitationRepository>`
+#[async_trait]
+impl<T: InvitationRepository + ?Sized> InvitationRepository for Box<T> {
+    async fn save(&self, invitation: &Invitation) -> Result<(), AccessError> {
+        (**self).save(invitation).await
+    }
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<Invitation>, AccessError> {
+        (**self).find_by_id(id).await
+    }
+
+    async fn find_by_list(&self, list: &TenantId) -> Result<Vec<Invitation>, AccessError> {
+        (**self).find_by_list(list).await
+    }
+
+    async fn find_by_invitee(&self, subject: &str) -> Result<Vec<Invitation>, AccessError> {
+        (**self).find_by_invitee(subject).await
+    }
+}
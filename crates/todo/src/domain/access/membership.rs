@@ -0,0 +1,25 @@
+use std::sync::Arc;
+
+use crate::TenantId;
+use crate::domain::access::Role;
+
+/// The role of a subject, such as a JWT's `sub` or `api-key:<id>`, on a todo list
+///
+/// A list is the set of todos of a tenant, so lists are identified by `TenantId`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Membership {
+    pub list: TenantId,
+    pub subject: Arc<str>,
+    pub role: Role,
+}
+
+impl Membership {
+    /// Creates a Membership
+    pub fn new(list: TenantId, subject: impl Into<Arc<str>>, role: Role) -> Self {
+        Membership {
+            list,
+            subject: subject.into(),
+            role,
+        }
+    }
+}
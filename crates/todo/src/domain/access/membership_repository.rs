@@ -0,0 +1,88 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::TenantId;
+use crate::domain::access::{AccessError, Membership};
+
+/// Repository trait for the roles of subjects on todo lists
+#[async_trait]
+pub trait MembershipRepository: Send + Sync {
+    /// Saves a Membership, replacing the role the subject had on the list
+    async fn save(&self, membership: &Membership) -> Result<(), AccessError>;
+
+    /// Removes the subject's role on the list
+    ///
+    /// # Returns
+    /// - `Err(AccessError::MembershipNotFound)`: If the subject has none
+    async fn remove(&self, list: &TenantId, subject: &str) -> Result<(), AccessError>;
+
+    /// Finds the subject's Membership of the list
+    ///
+    /// # Returns
+    /// - `Ok(Option<Membership>)`: `Some(Membership)` if found, `None` if not found
+    /// - `Err(AccessError)`: If retrieval fails
+    async fn find(&self, list: &TenantId, subject: &str)
+    -> Result<Option<Membership>, AccessError>;
+
+    /// Finds every Membership of the list
+    async fn find_by_list(&self, list: &TenantId) -> Result<Vec<Membership>, AccessError>;
+
+    /// Finds every Membership of the subject, on any list
+    async fn find_by_subject(&self, subject: &str) -> Result<Vec<Membership>, AccessError>;
+}
+
+/// Shares a single repository between the access control and the management commands
+#[async_trait]
+impl<T: MembershipRepository + ?Sized> MembershipRepository for Arc<T> {
+    async fn save(&self, membership: &Membership) -> Result<(), AccessError> {
+        (**self).save(membership).await
+    }
+
+    async fn remove(&self, list: &TenantId, subject: &str) -> Result<(), AccessError> {
+        (**self).remove(list, subject).await
+    }
+
+    async fn find(
+        &self,
+        list: &TenantId,
+        subject: &str,
+    ) -> Result<Option<Membership>, AccessError> {
+        (**self).find(list, subject).await
+    }
+
+    async fn find_by_list(&self, list: &TenantId) -> Result<Vec<Membership>, AccessError> {
+        (**self).find_by_list(list).await
+    }
+
+    async fn find_by_subject(&self, subject: &str) -> Result<Vec<Membership>, AccessError> {
+        (**self).find_by_subject(subject).await
+    }
+}
+
+/// Lets owners hold a `Box<dyn MembershipRepository>`
+#[async_trait]
+impl<T: MembershipRepository + ?Sized> MembershipRepository for Box<T> {
+    async fn save(&self, membership: &Membership) -> Result<(), AccessError> {
+        (**self).save(membership).await
+    }
+
+    async fn remove(&self, list: &TenantId, subject: &str) -> Result<(), AccessError> {
+        (**self).remove(list, subject).await
+    }
+
+    async fn find(
+        &self,
+        list: &TenantId,
+        subject: &str,
+    ) -> Result<Option<Membership>, AccessError> {
+        (**self).find(list, subject).await
+    }
+
+    async fn find_by_list(&self, list: &TenantId) -> Result<Vec<Membership>, AccessError> {
+        (**self).find_by_list(list).await
+    }
+
+    async fn find_by_subject(&self, subject: &str) -> Result<Vec<Membership>, AccessError> {
+        (**self).find_by_subject(subject).await
+    }
+}
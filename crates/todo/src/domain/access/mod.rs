@@ -0,0 +1,19 @@
+mod access_error;
+mod access_policy;
+mod invitation;
+mod invitation_event;
+mod invitation_repository;
+mod membership;
+mod membership_repository;
+mod role;
+mod todo_action;
+
+pub use access_error::AccessError;
+pub use access_policy::AccessPolicy;
+pub use invitation::{Invitation, InvitationStatus};
+pub use invitation_event::InvitationEvent;
+pub use invitation_repository::InvitationRepository;
+pub use membership::Membership;
+pub use membership_repository::MembershipRepository;
+pub use role::Role;
+pub use todo_action::TodoAction;
@@ -0,0 +1,34 @@
+/// What a member of a todo list may do there, as decided by an `AccessPolicy`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Role {
+    /// Everything, deleting todos and managing webhooks included
+    Owner,
+    /// Read, add and change the state of todos
+    Editor,
+    /// Read todos only
+    Viewer,
+}
+
+impl Role {
+    /// Parses `owner`, `editor` or `viewer`
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "owner" => Ok(Role::Owner),
+            "editor" => Ok(Role::Editor),
+            "viewer" => Ok(Role::Viewer),
+            _ => Err(format!(
+                "unknown role {:?}, expected owner, editor or viewer",
+                value
+            )),
+        }
+    }
+
+    /// `owner`, `editor` or `viewer`, as accepted by `parse`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Owner => "owner",
+            Role::Editor => "editor",
+            Role::Viewer => "viewer",
+        }
+    }
+}
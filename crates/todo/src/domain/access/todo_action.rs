@@ -0,0 +1,27 @@
+/// Something a caller asks to do with the todos of a list, checked by an `AccessPolicy`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TodoAction {
+    /// List, read or watch todos
+    View,
+    /// Create todos
+    Add,
+    /// Move todos to another state
+    ChangeState,
+    /// Delete todos
+    Delete,
+    /// Register, remove and retry the webhooks notified of todo events
+    ManageWebhooks,
+}
+
+impl TodoAction {
+    /// `view`, `add`, `change-state`, `delete` or `manage-webhooks`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TodoAction::View => "view",
+            TodoAction::Add => "add",
+            TodoAction::ChangeState => "change-state",
+            TodoAction::Delete => "delete",
+            TodoAction::ManageWebhooks => "manage-webhooks",
+        }
+    }
+}
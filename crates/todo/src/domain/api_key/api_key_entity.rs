@@ -0,0 +1,102 @@
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+use crate::domain::api_key::{ApiKeyError, ApiKeyScope};
+use crate::domain::todo::{Clock, IdGenerator, fill_random};
+
+/// Start of every API key token, so keys are recognisable in configuration and logs
+pub const API_KEY_PREFIX: &str = "hkt_";
+
+/// Aggregate root representing a credential issued to a machine client
+///
+/// The client holds a token `hkt_<id>_<secret>`; only the SHA-256 hash of the secret is
+/// kept here, so the token cannot be recovered from storage and is shown once, when the
+/// key is issued.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiKey {
+    pub id: Arc<str>,
+    /// What the key is for, such as the client it was issued to
+    pub name: Arc<str>,
+    pub scope: ApiKeyScope,
+    /// Lowercase hex SHA-256 of the secret
+    pub secret_hash: String,
+    pub created_at: DateTime<Utc>,
+    /// When the key stopped being accepted, if it was revoked
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl ApiKey {
+    /// Issues a new key, reading `created_at` from `clock` and its id from `ids`
+    ///
+    /// # Returns
+    /// - `Ok((ApiKey, String))`: The key and the token to hand to the client
+    /// - `Err(ApiKeyError::EmptyName)`: If `name` is blank
+    pub fn issue(
+        name: String,
+        scope: ApiKeyScope,
+        clock: &dyn Clock,
+        ids: &dyn IdGenerator,
+    ) -> Result<(Self, String), ApiKeyError> {
+        if name.trim().is_empty() {
+            return Err(ApiKeyError::EmptyName);
+        }
+        let id: Arc<str> = ids.next_id().into();
+        // 256 random bits, as 64 hex characters
+        let mut bytes = [0u8; 32];
+        fill_random(&mut bytes);
+        let secret: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+        let token = format!("{}{}_{}", API_KEY_PREFIX, id, secret);
+        let key = ApiKey {
+            id,
+            name: name.into(),
+            scope,
+            secret_hash: hash_secret(&secret),
+            created_at: clock.now(),
+            revoked_at: None,
+        };
+        Ok((key, token))
+    }
+
+    /// Splits a token into the id of its key and its secret
+    ///
+    /// # Returns
+    /// - `None`: If `token` is not shaped like an API key token
+    pub fn parse_token(token: &str) -> Option<(&str, &str)> {
+        let (id, secret) = token.strip_prefix(API_KEY_PREFIX)?.rsplit_once('_')?;
+        (!id.is_empty() && !secret.is_empty()).then_some((id, secret))
+    }
+
+    /// Whether `secret` is this key's secret, compared in constant time
+    pub fn verify(&self, secret: &str) -> bool {
+        let expected = self.secret_hash.as_bytes();
+        let actual = hash_secret(secret);
+        expected.len() == actual.len()
+            && expected
+                .iter()
+                .zip(actual.as_bytes())
+                .fold(0, |diff, (a, b)| diff | (a ^ b))
+                == 0
+    }
+
+    /// Whether the key has been revoked
+    pub fn is_revoked(&self) -> bool {
+        self.revoked_at.is_some()
+    }
+
+    /// Stops accepting the key from now on, as read from `clock`
+    ///
+    /// Revoking a revoked key keeps its first `revoked_at`.
+    pub fn revoke(&mut self, clock: &dyn Clock) {
+        if self.revoked_at.is_none() {
+            self.revoked_at = Some(clock.now());
+        }
+    }
+}
+
+fn hash_secret(secret: &str) -> String {
+    Sha256::digest(secret.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
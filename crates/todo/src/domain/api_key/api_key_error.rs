@@ -0,0 +1,22 @@
+/// Errors of the ApiKey aggregate and its repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiKeyError {
+    /// Returned when issuing a key with an empty name
+    EmptyName,
+    /// Returned when no key has the given id
+    ApiKeyNotFound,
+    /// Returned when the underlying storage fails, carrying the backend's message
+    RepositoryError(String),
+}
+
+impl std::fmt::Display for ApiKeyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApiKeyError::EmptyName => write!(f, "api key name must not be empty"),
+            ApiKeyError::ApiKeyNotFound => write!(f, "api key not found"),
+            ApiKeyError::RepositoryError(message) => write!(f, "repository error: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for ApiKeyError {}
@@ -0,0 +1,56 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::domain::api_key::{ApiKey, ApiKeyError};
+
+/// Repository trait for persisting and retrieving ApiKey aggregates
+///
+/// Keys are stored with the hash of their secret only, so a leaked store does not leak
+/// usable keys.
+#[async_trait]
+pub trait ApiKeyRepository: Send + Sync {
+    /// Saves an ApiKey, inserting it or replacing the key with the same id
+    async fn save(&self, key: &ApiKey) -> Result<(), ApiKeyError>;
+
+    /// Finds an ApiKey by its id
+    ///
+    /// # Returns
+    /// - `Ok(Option<ApiKey>)`: `Some(ApiKey)` if found, `None` if not found
+    /// - `Err(ApiKeyError)`: If retrieval fails
+    async fn find_by_id(&self, id: &str) -> Result<Option<ApiKey>, ApiKeyError>;
+
+    /// Finds every ApiKey, revoked ones included
+    async fn find_all(&self) -> Result<Vec<ApiKey>, ApiKeyError>;
+}
+
+/// Shares a single repository between the handlers and the authenticator
+#[async_trait]
+impl<T: ApiKeyRepository + ?Sized> ApiKeyRepository for Arc<T> {
+    async fn save(&self, key: &ApiKey) -> Result<(), ApiKeyError> {
+        (**self).save(key).await
+    }
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<ApiKey>, ApiKeyError> {
+        (**self).find_by_id(id).await
+    }
+
+    async fn find_all(&self) -> Result<Vec<ApiKey>, ApiKeyError> {
+        (**self).find_all().await
+    }
+}
+
+/// Lets handlers own a `Box<dyn ApiKeyRepository>`, their default repository type
+#[async_trait]
+impl<T: ApiKeyRepository + ?Sized> ApiKeyRepository for Box<T> {
+    async fn save(&self, key: &ApiKey) -> Result<(), ApiKeyError> {
+        (**self).save(key).await
+    }
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<ApiKey>, ApiKeyError> {
+        (**self).find_by_id(id).await
+    }
+
+    async fn find_all(&self) -> Result<Vec<ApiKey>, ApiKeyError> {
+        (**self).find_all().await
+    }
+}
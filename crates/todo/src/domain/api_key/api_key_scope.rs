@@ -0,0 +1,30 @@
+/// What the holder of an API key may do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApiKeyScope {
+    /// List and read todos
+    ReadOnly,
+    /// Also create, change and delete todos
+    ReadWrite,
+}
+
+impl ApiKeyScope {
+    /// Parses `read-only` or `read-write`
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "read-only" => Ok(ApiKeyScope::ReadOnly),
+            "read-write" => Ok(ApiKeyScope::ReadWrite),
+            _ => Err(format!(
+                "unknown api key scope {:?}, expected read-only or read-write",
+                value
+            )),
+        }
+    }
+
+    /// `read-only` or `read-write`, as accepted by `parse`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiKeyScope::ReadOnly => "read-only",
+            ApiKeyScope::ReadWrite => "read-write",
+        }
+    }
+}
@@ -0,0 +1,9 @@
+mod api_key_entity;
+mod api_key_error;
+mod api_key_repository;
+mod api_key_scope;
+
+pub use api_key_entity::{API_KEY_PREFIX, ApiKey};
+pub use api_key_error::ApiKeyError;
+pub use api_key_repository::ApiKeyRepository;
+pub use api_key_scope::ApiKeyScope;
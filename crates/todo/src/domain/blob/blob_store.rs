@@ -0,0 +1,78 @@
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+use crate::domain::todo::TodoError;
+
+/// The hash a BlobStore keys `content` by: its SHA-256 in lowercase hex
+pub fn content_hash(content: &[u8]) -> String {
+    Sha256::digest(content)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Whether `hash` has the shape of a `content_hash`, 64 lowercase hex digits
+pub fn is_content_hash(hash: &str) -> bool {
+    hash.len() == 64
+        && hash
+            .bytes()
+            .all(|byte| byte.is_ascii_digit() || (b'a'..=b'f').contains(&byte))
+}
+
+/// Storage for the content of attachments, addressed by the hash of their content
+///
+/// Equal contents share one blob, so putting the same bytes twice stores them once.
+/// Hashes that are not a `content_hash` are refused with `TodoError::RepositoryError`.
+#[async_trait]
+pub trait BlobStore: Send + Sync {
+    /// Stores `content`
+    ///
+    /// # Returns
+    /// - `Ok(String)`: The `content_hash` of `content`, to get it back with
+    /// - `Err(TodoError)`: If the content cannot be stored
+    async fn put(&self, content: &[u8]) -> Result<String, TodoError>;
+
+    /// Reads the content with `hash`
+    ///
+    /// # Returns
+    /// - `Ok(Some(Vec<u8>))`: The content
+    /// - `Ok(None)`: If no content has that hash
+    /// - `Err(TodoError)`: If the store cannot be read
+    async fn get(&self, hash: &str) -> Result<Option<Vec<u8>>, TodoError>;
+
+    /// Removes the content with `hash`; removing missing content succeeds
+    async fn delete(&self, hash: &str) -> Result<(), TodoError>;
+}
+
+/// Shares a single store between handlers
+#[async_trait]
+impl<T: BlobStore + ?Sized> BlobStore for Arc<T> {
+    async fn put(&self, content: &[u8]) -> Result<String, TodoError> {
+        (**self).put(content).await
+    }
+
+    async fn get(&self, hash: &str) -> Result<Option<Vec<u8>>, TodoError> {
+        (**self).get(hash).await
+    }
+
+    async fn delete(&self, hash: &str) -> Result<(), TodoError> {
+        (**self).delete(hash).await
+    }
+}
+
+/// Lets owners hold a `Box<dyn BlobStore>`
+#[async_trait]
+impl<T: BlobStore + ?Sized> BlobStore for Box<T> {
+    async fn put(&self, content: &[u8]) -> Result<String, TodoError> {
+        (**self).put(content).await
+    }
+
+    async fn get(&self, hash: &str) -> Result<Option<Vec<u8>>, TodoError> {
+        (**self).get(hash).await
+    }
+
+    async fn delete(&self, hash: &str) -> Result<(), TodoError> {
+        (**self).delete(hash).await
+    }
+}
@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use crate::domain::todo::TodoState;
+
+/// A rule flagging the todos left in a state for too long as stale
+///
+/// Such as `stuck` for todos `IN_PROGRESS` for more than 7 days. A todo's time in `state`
+/// counts from when it last entered it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EscalationRule {
+    /// Name of the rule, carried by the `TodoStale` events it emits
+    pub id: Arc<str>,
+    pub state: TodoState,
+    /// Days a todo may stay in `state` before it is stale, at least 1
+    pub after_days: u32,
+}
+
+impl EscalationRule {
+    /// Creates an EscalationRule
+    ///
+    /// # Returns
+    /// - `Err(String)`: If `id` is empty or has whitespace, or `after_days` is 0
+    pub fn new(id: impl Into<Arc<str>>, state: TodoState, after_days: u32) -> Result<Self, String> {
+        let id = id.into();
+        if id.is_empty() || id.chars().any(char::is_whitespace) {
+            return Err(format!(
+                "invalid escalation rule id {:?}, expected a name without whitespace",
+                id
+            ));
+        }
+        if after_days == 0 {
+            return Err("escalation rules need after_days of at least 1".to_string());
+        }
+        Ok(EscalationRule {
+            id,
+            state,
+            after_days,
+        })
+    }
+}
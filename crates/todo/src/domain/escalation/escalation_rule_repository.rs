@@ -0,0 +1,54 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::domain::escalation::EscalationRule;
+use crate::domain::todo::TodoError;
+
+/// Repository trait for escalation rules, one per rule id
+#[async_trait]
+pub trait EscalationRuleRepository: Send + Sync {
+    /// Saves an EscalationRule, replacing the one with the same id
+    async fn save(&self, rule: &EscalationRule) -> Result<(), TodoError>;
+
+    /// Removes the rule `id`
+    ///
+    /// # Returns
+    /// - `Ok(bool)`: Whether there was such a rule
+    /// - `Err(TodoError)`: If removal fails
+    async fn remove(&self, id: &str) -> Result<bool, TodoError>;
+
+    /// Finds every EscalationRule, in the order they were first saved
+    async fn find_all(&self) -> Result<Vec<EscalationRule>, TodoError>;
+}
+
+/// Shares a single repository between the escalation runs and the commands managing rules
+#[async_trait]
+impl<T: EscalationRuleRepository + ?Sized> EscalationRuleRepository for Arc<T> {
+    async fn save(&self, rule: &EscalationRule) -> Result<(), TodoError> {
+        (**self).save(rule).await
+    }
+
+    async fn remove(&self, id: &str) -> Result<bool, TodoError> {
+        (**self).remove(id).await
+    }
+
+    async fn find_all(&self) -> Result<Vec<EscalationRule>, TodoError> {
+        (**self).find_all().await
+    }
+}
+
+/// Lets owners hold a `Box<dyn EscalationRuleRepository>`
+#[async_trait]
+impl<T: EscalationRuleRepository + ?Sized> EscalationRuleRepository for Box<T> {
+    async fn save(&self, rule: &EscalationRule) -> Result<(), TodoError> {
+        (**self).save(rule).await
+    }
+
+    async fn remove(&self, id: &str) -> Result<bool, TodoError> {
+        (**self).remove(id).await
+    }
+
+    async fn find_all(&self) -> Result<Vec<EscalationRule>, TodoError> {
+        (**self).find_all().await
+    }
+}
@@ -0,0 +1,5 @@
+mod escalation_rule;
+mod escalation_rule_repository;
+
+pub use escalation_rule::EscalationRule;
+pub use escalation_rule_repository::EscalationRuleRepository;
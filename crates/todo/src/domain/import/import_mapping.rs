@@ -0,0 +1,77 @@
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+use crate::domain::todo::{Todo, TodoState};
+
+/// Links an item of an import source, such as a Taskwarrior task, to the todo it became
+///
+/// `description` and `state` are the item's values when it was last imported: a merge
+/// compares them with the todo to tell which side changed since.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportMapping {
+    /// The format imported from, such as `taskwarrior`
+    pub source: Arc<str>,
+    /// The item's id in the source
+    pub external_id: Arc<str>,
+    pub todo_id: Arc<str>,
+    pub description: Arc<str>,
+    pub state: TodoState,
+    pub imported_at: DateTime<Utc>,
+}
+
+impl ImportMapping {
+    /// Creates an ImportMapping from `imported`, the item as read from `source`, whose id is
+    /// the external id
+    pub fn new(
+        source: impl Into<Arc<str>>,
+        imported: &Todo,
+        todo_id: Arc<str>,
+        imported_at: DateTime<Utc>,
+    ) -> Self {
+        ImportMapping {
+            source: source.into(),
+            external_id: imported.id.clone(),
+            todo_id,
+            description: imported.description.clone(),
+            state: imported.state,
+            imported_at,
+        }
+    }
+}
+
+/// What an import does with an item it imported before
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ImportPolicy {
+    /// Leaves the todo as it is
+    #[default]
+    Skip,
+    /// Overwrites the todo's description and state with the item's
+    Update,
+    /// Takes the item's value of each field the todo has not changed since the last import,
+    /// and keeps the todo's own changes
+    Merge,
+}
+
+impl ImportPolicy {
+    /// Parses `skip`, `update` or `merge`
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "skip" => Ok(ImportPolicy::Skip),
+            "update" => Ok(ImportPolicy::Update),
+            "merge" => Ok(ImportPolicy::Merge),
+            _ => Err(format!(
+                "unknown import policy {:?}, expected skip, update or merge",
+                value
+            )),
+        }
+    }
+
+    /// `skip`, `update` or `merge`, as accepted by `parse`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportPolicy::Skip => "skip",
+            ImportPolicy::Update => "update",
+            ImportPolicy::Merge => "merge",
+        }
+    }
+}
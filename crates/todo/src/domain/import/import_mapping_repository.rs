@@ -0,0 +1,67 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::domain::import::ImportMapping;
+use crate::domain::todo::TodoError;
+
+/// Repository trait for the links between imported items and todos, one per source and
+/// external id
+#[async_trait]
+pub trait ImportMappingRepository: Send + Sync {
+    /// Saves every ImportMapping, replacing those with the same source and external id
+    async fn save_all(&self, mappings: &[ImportMapping]) -> Result<(), TodoError>;
+
+    /// Finds the ImportMapping of the item `external_id` of `source`
+    ///
+    /// # Returns
+    /// - `Ok(Option<ImportMapping>)`: `Some(ImportMapping)` if found, `None` if not found
+    /// - `Err(TodoError)`: If retrieval fails
+    async fn find(
+        &self,
+        source: &str,
+        external_id: &str,
+    ) -> Result<Option<ImportMapping>, TodoError>;
+
+    /// Finds every ImportMapping of `source`
+    async fn find_by_source(&self, source: &str) -> Result<Vec<ImportMapping>, TodoError>;
+}
+
+/// Shares a single repository between the import handlers of several front ends
+#[async_trait]
+impl<T: ImportMappingRepository + ?Sized> ImportMappingRepository for Arc<T> {
+    async fn save_all(&self, mappings: &[ImportMapping]) -> Result<(), TodoError> {
+        (**self).save_all(mappings).await
+    }
+
+    async fn find(
+        &self,
+        source: &str,
+        external_id: &str,
+    ) -> Result<Option<ImportMapping>, TodoError> {
+        (**self).find(source, external_id).await
+    }
+
+    async fn find_by_source(&self, source: &str) -> Result<Vec<ImportMapping>, TodoError> {
+        (**self).find_by_source(source).await
+    }
+}
+
+/// Lets owners hold a `Box<dyn ImportMappingRepository>`
+#[async_trait]
+impl<T: ImportMappingRepository + ?Sized> ImportMappingRepository for Box<T> {
+    async fn save_all(&self, mappings: &[ImportMapping]) -> Result<(), TodoError> {
+        (**self).save_all(mappings).await
+    }
+
+    async fn find(
+        &self,
+        source: &str,
+        external_id: &str,
+    ) -> Result<Option<ImportMapping>, TodoError> {
+        (**self).find(source, external_id).await
+    }
+
+    async fn find_by_source(&self, source: &str) -> Result<Vec<ImportMapping>, TodoError> {
+        (**self).find_by_source(source).await
+    }
+}
@@ -0,0 +1,5 @@
+mod import_mapping;
+mod import_mapping_repository;
+
+pub use import_mapping::{ImportMapping, ImportPolicy};
+pub use import_mapping_repository::ImportMappingRepository;
@@ -1,2 +1,9 @@
+pub mod access;
+pub mod api_key;
+pub mod blob;
+pub mod escalation;
+pub mod import;
+pub mod notification;
+pub mod sync;
 pub mod todo;
-
+pub mod trash;
@@ -0,0 +1,72 @@
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+use crate::domain::notification::NotificationError;
+
+/// The push service a device is reached through
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PushPlatform {
+    /// Firebase Cloud Messaging, for Android and web clients
+    Fcm,
+    /// Apple Push Notification service, for iOS and macOS clients
+    Apns,
+}
+
+impl PushPlatform {
+    /// Parses `fcm` or `apns`
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "fcm" => Ok(PushPlatform::Fcm),
+            "apns" => Ok(PushPlatform::Apns),
+            _ => Err(format!(
+                "unknown push platform {:?}, expected fcm or apns",
+                value
+            )),
+        }
+    }
+
+    /// `fcm` or `apns`, as accepted by `parse`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PushPlatform::Fcm => "fcm",
+            PushPlatform::Apns => "apns",
+        }
+    }
+}
+
+/// An app installation that receives push notifications for a subject, such as a JWT's
+/// `sub`
+///
+/// The token is the one the push service gave the app, and identifies the device: it is
+/// registered to one subject at a time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Device {
+    pub token: Arc<str>,
+    pub platform: PushPlatform,
+    pub subject: Arc<str>,
+    pub registered_at: DateTime<Utc>,
+}
+
+impl Device {
+    /// Creates a Device
+    ///
+    /// # Returns
+    /// - `Err(NotificationError::EmptyDeviceToken)`: If `token` is blank
+    pub fn new(
+        token: impl Into<Arc<str>>,
+        platform: PushPlatform,
+        subject: impl Into<Arc<str>>,
+        registered_at: DateTime<Utc>,
+    ) -> Result<Self, NotificationError> {
+        let token = token.into();
+        if token.trim().is_empty() {
+            return Err(NotificationError::EmptyDeviceToken);
+        }
+        Ok(Device {
+            token,
+            platform,
+            subject: subject.into(),
+            registered_at,
+        })
+    }
+}
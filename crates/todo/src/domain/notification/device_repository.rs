@@ -0,0 +1,52 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::domain::notification::{Device, NotificationError};
+
+/// Repository trait for the devices registered for push notifications
+#[async_trait]
+pub trait DeviceRepository: Send + Sync {
+    /// Saves a Device, replacing any device with the same token
+    async fn save(&self, device: &Device) -> Result<(), NotificationError>;
+
+    /// Removes the device with `token`
+    ///
+    /// # Returns
+    /// - `Err(NotificationError::DeviceNotFound)`: If there is none
+    async fn remove(&self, token: &str) -> Result<(), NotificationError>;
+
+    /// Finds the devices registered for `subject`, oldest first
+    async fn find_by_subject(&self, subject: &str) -> Result<Vec<Device>, NotificationError>;
+}
+
+/// Shares a single repository between registration and the notifier
+#[async_trait]
+impl<T: DeviceRepository + ?Sized> DeviceRepository for Arc<T> {
+    async fn save(&self, device: &Device) -> Result<(), NotificationError> {
+        (**self).save(device).await
+    }
+
+    async fn remove(&self, token: &str) -> Result<(), NotificationError> {
+        (**self).remove(token).await
+    }
+
+    async fn find_by_subject(&self, subject: &str) -> Result<Vec<Device>, NotificationError> {
+        (**self).find_by_subject(subject).await
+    }
+}
+
+/// Lets owners hold a `Box<dyn DeviceRepository>`
+#[async_trait]
+impl<T: DeviceRepository + ?Sized> DeviceRepository for Box<T> {
+    async fn save(&self, device: &Device) -> Result<(), NotificationError> {
+        (**self).save(device).await
+    }
+
+    async fn remove(&self, token: &str) -> Result<(), NotificationError> {
+        (**self).remove(token).await
+    }
+
+    async fn find_by_subject(&self, subject: &str) -> Result<Vec<Device>, NotificationError> {
+        (**self).find_by_subject(subject).await
+    }
+}
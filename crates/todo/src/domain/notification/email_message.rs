@@ -0,0 +1,63 @@
+use crate::domain::notification::NotificationError;
+
+/// A plain-text email, checked so every Mailer can put it on the wire as is
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmailMessage {
+    pub from: String,
+    pub to: Vec<String>,
+    pub subject: String,
+    pub text: String,
+}
+
+impl EmailMessage {
+    /// Creates a new EmailMessage from `from` to every address of `to`
+    ///
+    /// # Returns
+    /// - `Err(NotificationError::InvalidAddress)`: If an address is not `local@domain`
+    /// - `Err(NotificationError::InvalidMessage)`: If `to` is empty or `subject` has a line
+    ///   break
+    pub fn new(
+        from: impl Into<String>,
+        to: Vec<String>,
+        subject: impl Into<String>,
+        text: impl Into<String>,
+    ) -> Result<Self, NotificationError> {
+        let from = from.into();
+        let subject = subject.into();
+        if to.is_empty() {
+            return Err(NotificationError::InvalidMessage(
+                "no recipients".to_string(),
+            ));
+        }
+        if let Some(address) = std::iter::once(&from)
+            .chain(&to)
+            .find(|address| !is_address(address))
+        {
+            return Err(NotificationError::InvalidAddress(address.clone()));
+        }
+        if subject.contains(['\r', '\n']) {
+            return Err(NotificationError::InvalidMessage(
+                "subject has a line break".to_string(),
+            ));
+        }
+        Ok(EmailMessage {
+            from,
+            to,
+            subject,
+            text: text.into(),
+        })
+    }
+}
+
+/// Whether `address` is a bare `local@domain`, without a display name, spaces or the
+/// characters that end an SMTP path
+fn is_address(address: &str) -> bool {
+    let Some((local, domain)) = address.rsplit_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && !domain.is_empty()
+        && !address
+            .chars()
+            .any(|c| c.is_whitespace() || c.is_control() || matches!(c, '<' | '>' | ','))
+}
@@ -0,0 +1,31 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::domain::notification::{EmailMessage, NotificationError};
+
+/// Channel sending emails, such as through an SMTP relay or a mail API
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    /// Sends `message` to all of its recipients
+    ///
+    /// # Returns
+    /// - `Err(NotificationError::DeliveryFailed)`: If the service refuses the message or
+    ///   cannot be reached
+    async fn send(&self, message: &EmailMessage) -> Result<(), NotificationError>;
+}
+
+/// Shares a single mailer between the parts of an application that send mail
+#[async_trait]
+impl<T: Mailer + ?Sized> Mailer for Arc<T> {
+    async fn send(&self, message: &EmailMessage) -> Result<(), NotificationError> {
+        (**self).send(message).await
+    }
+}
+
+/// Lets owners hold a `Box<dyn Mailer>`
+#[async_trait]
+impl<T: Mailer + ?Sized> Mailer for Box<T> {
+    async fn send(&self, message: &EmailMessage) -> Result<(), NotificationError> {
+        (**self).send(message).await
+    }
+}
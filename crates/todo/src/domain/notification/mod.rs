@@ -0,0 +1,15 @@
+mod device;
+mod device_repository;
+mod email_message;
+mod mailer;
+mod notification_error;
+mod push_notification;
+mod push_sender;
+
+pub use device::{Device, PushPlatform};
+pub use device_repository::DeviceRepository;
+pub use email_message::EmailMessage;
+pub use mailer::Mailer;
+pub use notification_error::NotificationError;
+pub use push_notification::PushNotification;
+pub use push_sender::PushSender;
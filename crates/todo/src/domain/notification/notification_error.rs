@@ -0,0 +1,46 @@
+/// Errors of building and delivering notifications
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotificationError {
+    /// Returned when an address is not `local@domain`, carrying the address
+    InvalidAddress(String),
+    /// Returned when a message has no recipient or a header with a line break
+    InvalidMessage(String),
+    /// Returned when registering a device with an empty token
+    EmptyDeviceToken,
+    /// Returned when no device is registered with the given token
+    DeviceNotFound,
+    /// Returned when the push service says a device token is no longer valid, such as after
+    /// the app was uninstalled; the token should be removed
+    DeviceUnregistered,
+    /// Returned when the delivery service refuses the message or cannot be reached,
+    /// carrying its message
+    DeliveryFailed(String),
+    /// Returned when the underlying storage fails, carrying the backend's message
+    RepositoryError(String),
+}
+
+impl std::fmt::Display for NotificationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NotificationError::InvalidAddress(address) => {
+                write!(f, "invalid address: {:?}", address)
+            }
+            NotificationError::InvalidMessage(message) => {
+                write!(f, "invalid message: {}", message)
+            }
+            NotificationError::EmptyDeviceToken => write!(f, "device token must not be empty"),
+            NotificationError::DeviceNotFound => write!(f, "device not found"),
+            NotificationError::DeviceUnregistered => {
+                write!(f, "device token is no longer registered")
+            }
+            NotificationError::DeliveryFailed(message) => {
+                write!(f, "delivery failed: {}", message)
+            }
+            NotificationError::RepositoryError(message) => {
+                write!(f, "repository error: {}", message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for NotificationError {}
@@ -0,0 +1,27 @@
+use std::collections::BTreeMap;
+
+/// An alert shown on a device, with data handed to the app when it is opened
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PushNotification {
+    pub title: String,
+    pub body: String,
+    /// Key-value pairs for the app, such as the id of the todo the alert is about
+    pub data: BTreeMap<String, String>,
+}
+
+impl PushNotification {
+    /// Creates a PushNotification without data
+    pub fn new(title: impl Into<String>, body: impl Into<String>) -> Self {
+        PushNotification {
+            title: title.into(),
+            body: body.into(),
+            data: BTreeMap::new(),
+        }
+    }
+
+    /// Adds `key` with `value` to the data for the app
+    pub fn with_data(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.data.insert(key.into(), value.into());
+        self
+    }
+}
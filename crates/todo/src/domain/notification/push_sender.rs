@@ -0,0 +1,45 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::domain::notification::{Device, NotificationError, PushNotification};
+
+/// Channel delivering push notifications to devices of one `PushPlatform`
+#[async_trait]
+pub trait PushSender: Send + Sync {
+    /// Sends `notification` to `device`
+    ///
+    /// # Returns
+    /// - `Err(NotificationError::DeviceUnregistered)`: If the push service no longer knows
+    ///   the device's token
+    /// - `Err(NotificationError::DeliveryFailed)`: If the service refuses the notification or
+    ///   cannot be reached
+    async fn send(
+        &self,
+        device: &Device,
+        notification: &PushNotification,
+    ) -> Result<(), NotificationError>;
+}
+
+/// Shares a single sender between the parts of an application that push
+#[async_trait]
+impl<T: PushSender + ?Sized> PushSender for Arc<T> {
+    async fn send(
+        &self,
+        device: &Device,
+        notification: &PushNotification,
+    ) -> Result<(), NotificationError> {
+        (**self).send(device, notification).await
+    }
+}
+
+/// Lets owners hold a `Box<dyn PushSender>`
+#[async_trait]
+impl<T: PushSender + ?Sized> PushSender for Box<T> {
+    async fn send(
+        &self,
+        device: &Device,
+        notification: &PushNotification,
+    ) -> Result<(), NotificationError> {
+        (**self).send(device, notification).await
+    }
+}
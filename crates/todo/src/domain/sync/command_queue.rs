@@ -0,0 +1,55 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::domain::sync::QueuedCommand;
+use crate::domain::todo::TodoError;
+
+/// Queue of the commands recorded while offline, waiting to be replayed in order
+#[async_trait]
+pub trait CommandQueue: Send + Sync {
+    /// Appends `command` to the queue
+    async fn push(&self, command: &QueuedCommand) -> Result<(), TodoError>;
+
+    /// Finds every queued command, oldest first
+    async fn find_all(&self) -> Result<Vec<QueuedCommand>, TodoError>;
+
+    /// Removes the command with `key`
+    ///
+    /// # Returns
+    /// - `Ok(bool)`: Whether there was such a command
+    /// - `Err(TodoError)`: If removal fails
+    async fn remove(&self, key: &str) -> Result<bool, TodoError>;
+}
+
+/// Shares a single queue between the repository recording commands and the code replaying
+/// them
+#[async_trait]
+impl<T: CommandQueue + ?Sized> CommandQueue for Arc<T> {
+    async fn push(&self, command: &QueuedCommand) -> Result<(), TodoError> {
+        (**self).push(command).await
+    }
+
+    async fn find_all(&self) -> Result<Vec<QueuedCommand>, TodoError> {
+        (**self).find_all().await
+    }
+
+    async fn remove(&self, key: &str) -> Result<bool, TodoError> {
+        (**self).remove(key).await
+    }
+}
+
+/// Lets owners hold a `Box<dyn CommandQueue>`
+#[async_trait]
+impl<T: CommandQueue + ?Sized> CommandQueue for Box<T> {
+    async fn push(&self, command: &QueuedCommand) -> Result<(), TodoError> {
+        (**self).push(command).await
+    }
+
+    async fn find_all(&self) -> Result<Vec<QueuedCommand>, TodoError> {
+        (**self).find_all().await
+    }
+
+    async fn remove(&self, key: &str) -> Result<bool, TodoError> {
+        (**self).remove(key).await
+    }
+}
@@ -0,0 +1,88 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::domain::sync::{PendingConflict, PendingConflictRepository, Resolution, SyncConflict};
+use crate::domain::todo::{Clock, SystemClock, TodoError};
+
+/// Strategy deciding which side of a SyncConflict the sync engine keeps
+#[async_trait]
+pub trait ConflictResolver: Send + Sync {
+    /// Decides `conflict`
+    ///
+    /// # Returns
+    /// - `Ok(Resolution::Deferred)`: If the conflict is left for a user to decide
+    /// - `Err(TodoError)`: If the strategy fails, such as a queue that cannot be written
+    async fn resolve(&self, conflict: &SyncConflict) -> Result<Resolution, TodoError>;
+}
+
+/// Keeps the side changed last, the local one on a tie
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LastWriterWins;
+
+#[async_trait]
+impl ConflictResolver for LastWriterWins {
+    async fn resolve(&self, conflict: &SyncConflict) -> Result<Resolution, TodoError> {
+        if conflict.remote_modified_at > conflict.local_modified_at {
+            Ok(Resolution::Remote)
+        } else {
+            Ok(Resolution::Local)
+        }
+    }
+}
+
+/// Always keeps the local side
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PreferLocal;
+
+#[async_trait]
+impl ConflictResolver for PreferLocal {
+    async fn resolve(&self, _conflict: &SyncConflict) -> Result<Resolution, TodoError> {
+        Ok(Resolution::Local)
+    }
+}
+
+/// Always keeps the remote side
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PreferRemote;
+
+#[async_trait]
+impl ConflictResolver for PreferRemote {
+    async fn resolve(&self, _conflict: &SyncConflict) -> Result<Resolution, TodoError> {
+        Ok(Resolution::Remote)
+    }
+}
+
+/// Defers every conflict, saving it as a PendingConflict for a user to decide
+pub struct ManualQueue<R = Arc<dyn PendingConflictRepository>> {
+    pending: R,
+    clock: Arc<dyn Clock>,
+}
+
+impl<R: PendingConflictRepository> ManualQueue<R> {
+    /// Creates a new ManualQueue saving conflicts to `pending`
+    pub fn new(pending: R) -> Self {
+        ManualQueue {
+            pending,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Stamps queued conflicts with `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+}
+
+#[async_trait]
+impl<R: PendingConflictRepository> ConflictResolver for ManualQueue<R> {
+    async fn resolve(&self, conflict: &SyncConflict) -> Result<Resolution, TodoError> {
+        self.pending
+            .save(&PendingConflict {
+                conflict: conflict.clone(),
+                detected_at: self.clock.now(),
+            })
+            .await?;
+        Ok(Resolution::Deferred)
+    }
+}
@@ -0,0 +1,15 @@
+mod command_queue;
+mod conflict_resolver;
+mod pending_conflict_repository;
+mod queued_command;
+mod remote_task_list;
+mod sync_conflict;
+
+pub use command_queue::CommandQueue;
+pub use conflict_resolver::{
+    ConflictResolver, LastWriterWins, ManualQueue, PreferLocal, PreferRemote,
+};
+pub use pending_conflict_repository::PendingConflictRepository;
+pub use queued_command::{QueuedCommand, RejectedCommand, TodoCommand};
+pub use remote_task_list::{RemoteChanges, RemoteTaskList};
+pub use sync_conflict::{PendingConflict, Resolution, SyncConflict};
@@ -0,0 +1,69 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::domain::sync::PendingConflict;
+use crate::domain::todo::TodoError;
+
+/// Repository trait for the conflicts waiting for a user's decision, one per todo
+#[async_trait]
+pub trait PendingConflictRepository: Send + Sync {
+    /// Saves a PendingConflict, replacing the one of the same todo
+    async fn save(&self, pending: &PendingConflict) -> Result<(), TodoError>;
+
+    /// Finds the PendingConflict of the todo with `todo_id`
+    ///
+    /// # Returns
+    /// - `Ok(Option<PendingConflict>)`: `Some(PendingConflict)` if found, `None` if not found
+    /// - `Err(TodoError)`: If retrieval fails
+    async fn find(&self, todo_id: &str) -> Result<Option<PendingConflict>, TodoError>;
+
+    /// Finds every PendingConflict, oldest first
+    async fn find_all(&self) -> Result<Vec<PendingConflict>, TodoError>;
+
+    /// Removes the PendingConflict of the todo with `todo_id`
+    ///
+    /// # Returns
+    /// - `Err(TodoError::TodoNotFound)`: If the todo has none
+    async fn remove(&self, todo_id: &str) -> Result<(), TodoError>;
+}
+
+/// Shares a single repository between the resolver queueing conflicts and the handler
+/// deciding them
+#[async_trait]
+impl<T: PendingConflictRepository + ?Sized> PendingConflictRepository for Arc<T> {
+    async fn save(&self, pending: &PendingConflict) -> Result<(), TodoError> {
+        (**self).save(pending).await
+    }
+
+    async fn find(&self, todo_id: &str) -> Result<Option<PendingConflict>, TodoError> {
+        (**self).find(todo_id).await
+    }
+
+    async fn find_all(&self) -> Result<Vec<PendingConflict>, TodoError> {
+        (**self).find_all().await
+    }
+
+    async fn remove(&self, todo_id: &str) -> Result<(), TodoError> {
+        (**self).remove(todo_id).await
+    }
+}
+
+/// Lets owners hold a `Box<dyn PendingConflictRepository>`
+#[async_trait]
+impl<T: PendingConflictRepository + ?Sized> PendingConflictRepository for Box<T> {
+    async fn save(&self, pending: &PendingConflict) -> Result<(), TodoError> {
+        (**self).save(pending).await
+    }
+
+    async fn find(&self, todo_id: &str) -> Result<Option<PendingConflict>, TodoError> {
+        (**self).find(todo_id).await
+    }
+
+    async fn find_all(&self) -> Result<Vec<PendingConflict>, TodoError> {
+        (**self).find_all().await
+    }
+
+    async fn remove(&self, todo_id: &str) -> Result<(), TodoError> {
+        (**self).remove(todo_id).await
+    }
+}
@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+use crate::domain::todo::Todo;
+
+/// A change to the todos of a repository, as a command that can be sent again later
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TodoCommand {
+    /// Save the todo, creating or replacing it
+    Save(Todo),
+    /// Delete the todo with the id
+    Delete(Arc<str>),
+}
+
+impl TodoCommand {
+    /// Id of the todo the command changes
+    pub fn todo_id(&self) -> &str {
+        match self {
+            TodoCommand::Save(todo) => &todo.id,
+            TodoCommand::Delete(id) => id,
+        }
+    }
+}
+
+/// A TodoCommand recorded while the repository it is meant for could not be reached
+///
+/// `key` identifies the command, so it is applied once however often it is replayed.
+/// `base` is the todo as it was known when the command was recorded, `None` if it did not
+/// exist; the command only applies while the repository still holds that version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueuedCommand {
+    pub key: Arc<str>,
+    pub command: TodoCommand,
+    pub base: Option<Todo>,
+    pub queued_at: DateTime<Utc>,
+}
+
+/// A QueuedCommand that was not applied on replay, because the todo changed remotely since
+/// the command was recorded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RejectedCommand {
+    pub command: QueuedCommand,
+    /// The todo as the remote repository holds it, `None` if it was deleted there
+    pub remote: Option<Todo>,
+}
@@ -0,0 +1,45 @@
+use async_trait::async_trait;
+
+use crate::domain::todo::{Todo, TodoError};
+
+/// The remote tasks changed since the previous pull
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RemoteChanges {
+    /// Tasks added or changed, as todos whose id is the remote task's id
+    pub todos: Vec<Todo>,
+    /// Ids of the tasks removed
+    pub removed: Vec<String>,
+}
+
+/// A task list of a remote service, such as a Microsoft To Do list, that todos are synced
+/// with
+///
+/// Implementations keep whatever they need to pull only the changes since the previous
+/// pull, such as a delta link or the versions of the tasks seen; the first pull of a new
+/// instance returns every task.
+#[async_trait]
+pub trait RemoteTaskList: Send + Sync {
+    /// Name of the service, such as `microsoft-todo`: the source of the import mappings
+    /// linking its tasks to todos
+    fn source(&self) -> &str;
+
+    /// Fetches the tasks changed since the previous pull
+    async fn pull(&self) -> Result<RemoteChanges, TodoError>;
+
+    /// Creates a remote task from `todo`
+    ///
+    /// # Returns
+    /// - `Ok(Some(String))`: The id of the new task
+    /// - `Ok(None)`: If the list does not take new tasks; the todo stays local
+    /// - `Err(TodoError)`: If the service refuses the task or cannot be reached
+    async fn create(&self, todo: &Todo) -> Result<Option<String>, TodoError>;
+
+    /// Updates the remote task `remote_id` from `todo`
+    ///
+    /// # Returns
+    /// - `Ok(true)`: If the task was updated
+    /// - `Ok(false)`: If the task changed since the last pull, for lists that can tell, such
+    ///   as by an etag; it is left as is, for the next pull to merge
+    /// - `Err(TodoError)`: If the service refuses the task or cannot be reached
+    async fn update(&self, remote_id: &str, todo: &Todo) -> Result<bool, TodoError>;
+}
@@ -0,0 +1,40 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::todo::Todo;
+
+/// A todo changed both locally and remotely since the last sync
+///
+/// The sync engine supplies when each side last changed, as todos only carry their
+/// creation time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncConflict {
+    pub local: Todo,
+    pub remote: Todo,
+    pub local_modified_at: DateTime<Utc>,
+    pub remote_modified_at: DateTime<Utc>,
+}
+
+impl SyncConflict {
+    /// Id of the conflicting todo
+    pub fn todo_id(&self) -> &str {
+        &self.local.id
+    }
+}
+
+/// The side of a SyncConflict to keep
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    /// Keep the local todo; the sync engine pushes it to the remote
+    Local,
+    /// Keep the remote todo; it replaces the local one
+    Remote,
+    /// Keep both sides as they are until someone decides
+    Deferred,
+}
+
+/// A SyncConflict waiting for a user's decision
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingConflict {
+    pub conflict: SyncConflict,
+    pub detected_at: DateTime<Utc>,
+}
@@ -0,0 +1,21 @@
+use std::sync::Arc;
+
+/// Value object describing a file attached to a Todo
+///
+/// Only the metadata lives on the todo; the content is kept in a `BlobStore` under
+/// `content_hash`, so `AttachmentService` is what stores and reads it. `content_hash` is
+/// unique within a todo. With the `serde` feature it serializes as
+/// `{content_hash, name, media_type, size}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Attachment {
+    /// The `content_hash` of the content
+    pub content_hash: Arc<str>,
+    /// The file name, such as `receipt.pdf`
+    pub name: Arc<str>,
+    /// The media type of the content, such as `application/pdf`
+    pub media_type: Arc<str>,
+    /// The size of the content in bytes
+    pub size: u64,
+}
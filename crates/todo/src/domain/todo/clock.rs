@@ -0,0 +1,87 @@
+use chrono::{DateTime, Duration, Utc};
+use std::sync::Mutex;
+
+/// Source of the timestamps stamped on todos and their events
+///
+/// `Todo::new` and `update_state` read the system clock; the `_with_clock` variants and the
+/// handlers' `with_clock` take one of these instead, so tests and replays get the same
+/// timestamps on every run.
+pub trait Clock: Send + Sync {
+    /// The current time
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// The system clock, through `Utc::now()`
+///
+/// Reads the clock given to `set_platform_clock` instead once one is installed, which
+/// targets without a system clock, or builds without the `std-clock` feature, need.
+///
+/// # Panics
+/// `now` panics without the `std-clock` feature until a clock is installed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        super::platform::now()
+    }
+}
+
+/// A clock that stays at one instant until it is `set`
+#[derive(Debug)]
+pub struct FixedClock {
+    now: Mutex<DateTime<Utc>>,
+}
+
+impl FixedClock {
+    /// Creates a new FixedClock reading `now`
+    pub fn new(now: DateTime<Utc>) -> Self {
+        FixedClock {
+            now: Mutex::new(now),
+        }
+    }
+
+    /// Moves the clock to `now`
+    pub fn set(&self, now: DateTime<Utc>) {
+        *lock(&self.now) = now;
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        *lock(&self.now)
+    }
+}
+
+/// A clock that reads `start` first and moves forward by `step` after every reading
+///
+/// Each timestamp it hands out is distinct, so the order of events is visible in their times.
+#[derive(Debug)]
+pub struct SteppingClock {
+    next: Mutex<DateTime<Utc>>,
+    step: Duration,
+}
+
+impl SteppingClock {
+    /// Creates a new SteppingClock reading `start`, then `start + step`, and so on
+    pub fn new(start: DateTime<Utc>, step: Duration) -> Self {
+        SteppingClock {
+            next: Mutex::new(start),
+            step,
+        }
+    }
+}
+
+impl Clock for SteppingClock {
+    fn now(&self) -> DateTime<Utc> {
+        let mut next = lock(&self.next);
+        let now = *next;
+        *next = now + self.step;
+        now
+    }
+}
+
+fn lock(time: &Mutex<DateTime<Utc>>) -> std::sync::MutexGuard<'_, DateTime<Utc>> {
+    // A timestamp cannot be left half-written, so a poisoned lock is still usable
+    time.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
@@ -0,0 +1,16 @@
+use crate::domain::todo::{TodoError, TodoEvent};
+
+/// Destination for the domain events of committed changes
+///
+/// Handlers given a sink pass it the events of every change after the repository has saved
+/// it, in the order they were emitted. Implementations keep an append-only activity record,
+/// such as a log file or an external collector; they are separate from the repository and
+/// never read back.
+pub trait EventSink: Send + Sync {
+    /// Records the events of one committed change
+    ///
+    /// # Returns
+    /// - `Ok(())`: Every event was recorded
+    /// - `Err(TodoError)`: If the sink failed; the change itself stays saved
+    fn record(&self, events: &[TodoEvent]) -> Result<(), TodoError>;
+}
@@ -0,0 +1,40 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::todo::{EventSink, TodoError, TodoEvent};
+
+/// EventSink whose recorded events can be read back
+///
+/// A store keeps every event it records, so the change log of a todo can be rebuilt from
+/// it after the todo itself has changed or been deleted.
+pub trait EventStore: EventSink {
+    /// Returns the events recorded for the todo with `id`, in the order they were recorded
+    ///
+    /// # Returns
+    /// - `Ok(Vec<TodoEvent>)`: The todo's events, empty if none were recorded
+    /// - `Err(TodoError)`: If the store cannot be read
+    fn find_by_todo(&self, id: &str) -> Result<Vec<TodoEvent>, TodoError>;
+
+    /// Returns every event of the store, in the order they were recorded
+    ///
+    /// # Returns
+    /// - `Ok(Vec<TodoEvent>)`: The events, empty if none were recorded
+    /// - `Err(TodoError)`: If the store cannot be read
+    fn find_all(&self) -> Result<Vec<TodoEvent>, TodoError>;
+
+    /// Removes the events that happened before `before`
+    ///
+    /// The repository keeps the current state of every todo, so it stands as the snapshot
+    /// of what the removed events built up; histories then start at the first kept event.
+    ///
+    /// # Returns
+    /// - `Ok(usize)`: The number of events removed
+    /// - `Err(TodoError)`: If the store cannot be rewritten
+    fn compact(&self, before: DateTime<Utc>) -> Result<usize, TodoError>;
+
+    /// Removes every event of the todo with `id`, such as when its owner's data is erased
+    ///
+    /// # Returns
+    /// - `Ok(usize)`: The number of events removed
+    /// - `Err(TodoError)`: If the store cannot be rewritten
+    fn remove_todo(&self, id: &str) -> Result<usize, TodoError>;
+}
@@ -0,0 +1,147 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+use super::{Clock, SystemClock, platform};
+
+/// Source of the ids given to new todos
+///
+/// `Todo::new` uses `UuidV4Generator`; `Todo::new_with` and the `with_id_generator` of
+/// `AddTodoHandler` take another one, such as a time-sortable generator for storage that
+/// indexes by id, or `SequentialIdGenerator` for ids that are the same on every test run.
+pub trait IdGenerator: Send + Sync {
+    /// A new id, distinct from every id this generator returned before
+    fn next_id(&self) -> String;
+}
+
+/// Random UUIDv4 ids, such as `67e55044-10b1-426f-9247-bb680e5fe0c8`
+///
+/// # Panics
+/// `next_id` panics without the `getrandom` feature until `set_platform_random` installed a
+/// random source.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UuidV4Generator;
+
+impl IdGenerator for UuidV4Generator {
+    fn next_id(&self) -> String {
+        let mut bytes = [0; 16];
+        platform::fill_random(&mut bytes);
+        uuid::Builder::from_random_bytes(bytes)
+            .into_uuid()
+            .to_string()
+    }
+}
+
+/// Bits of a UUIDv7 after its timestamp that are not version or variant bits
+const UUID_V7_COUNTER_BITS: u32 = 74;
+
+/// The millisecond and counter of the last UUIDv7 handed out in this process
+static LAST_UUID_V7: Mutex<(u64, u128)> = Mutex::new((0, 0));
+
+/// UUIDv7 ids, which start with their creation time in milliseconds, read from `SystemClock`
+///
+/// Ids from one process sort in creation order, also within a millisecond: the random bits
+/// after the time count up from the last id while the clock reads the same millisecond, or
+/// an earlier one.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UuidV7Generator;
+
+impl IdGenerator for UuidV7Generator {
+    fn next_id(&self) -> String {
+        let mut last = lock(&LAST_UUID_V7);
+        let (last_millis, last_counter) = *last;
+        let millis = unix_millis();
+        let (millis, counter) = if millis > last_millis {
+            // The top counter bit starts clear, leaving room to count up
+            (
+                millis,
+                platform::random_u128() >> (129 - UUID_V7_COUNTER_BITS),
+            )
+        } else if last_counter + 1 < 1 << UUID_V7_COUNTER_BITS {
+            (last_millis, last_counter + 1)
+        } else {
+            (
+                last_millis + 1,
+                platform::random_u128() >> (129 - UUID_V7_COUNTER_BITS),
+            )
+        };
+        *last = (millis, counter);
+        // 12 counter bits go after the version, the other 62 after the variant
+        let mut bytes = [0; 10];
+        bytes[..2].copy_from_slice(&((counter >> 62) as u16).to_be_bytes());
+        bytes[2..].copy_from_slice(&((counter as u64) & ((1 << 62) - 1)).to_be_bytes());
+        uuid::Builder::from_unix_timestamp_millis(millis, &bytes)
+            .into_uuid()
+            .to_string()
+    }
+}
+
+/// ULIDs, 26 Crockford base32 characters that start with their creation time, read from
+/// `SystemClock`
+///
+/// Ids from one generator sort in creation order, also within a millisecond.
+#[derive(Default)]
+pub struct UlidGenerator {
+    last: Mutex<Option<ulid::Ulid>>,
+}
+
+impl UlidGenerator {
+    /// Creates a new UlidGenerator
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl IdGenerator for UlidGenerator {
+    fn next_id(&self) -> String {
+        let mut last = lock(&self.last);
+        let millis = unix_millis();
+        let next = match *last {
+            Some(previous) if previous.timestamp_ms() >= millis => {
+                // Only overflows once the random part ran out within one millisecond
+                previous.increment().unwrap_or_else(|| {
+                    ulid::Ulid::from_parts(previous.timestamp_ms() + 1, platform::random_u128())
+                })
+            }
+            _ => ulid::Ulid::from_parts(millis, platform::random_u128()),
+        };
+        *last = Some(next);
+        next.to_string()
+    }
+}
+
+/// Milliseconds since the Unix epoch on `SystemClock`, 0 before it
+fn unix_millis() -> u64 {
+    SystemClock.now().timestamp_millis().max(0) as u64
+}
+
+fn lock<T>(last: &Mutex<T>) -> MutexGuard<'_, T> {
+    // The last id is replaced whole, so a poisoned lock is still usable
+    last.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Ids made of a prefix and a counter starting at 1, such as `todo-1`, `todo-2`
+#[derive(Debug)]
+pub struct SequentialIdGenerator {
+    prefix: String,
+    next: AtomicU64,
+}
+
+impl SequentialIdGenerator {
+    /// Creates a new SequentialIdGenerator numbering ids after `prefix`
+    pub fn new(prefix: impl Into<String>) -> Self {
+        SequentialIdGenerator {
+            prefix: prefix.into(),
+            next: AtomicU64::new(1),
+        }
+    }
+}
+
+impl IdGenerator for SequentialIdGenerator {
+    fn next_id(&self) -> String {
+        format!(
+            "{}{}",
+            self.prefix,
+            self.next.fetch_add(1, Ordering::Relaxed)
+        )
+    }
+}
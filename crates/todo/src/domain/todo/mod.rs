@@ -1,12 +1,39 @@
+mod attachment;
+mod clock;
+mod event_sink;
+mod event_store;
+mod id_generator;
+mod platform;
+mod priority;
+mod subtask;
+mod tag;
+mod tenant_id;
 mod todo_state;
 mod todo_event;
 mod todo_entity;
 mod todo_error;
+mod todo_filter;
 mod todo_repository;
+#[cfg(feature = "serde")]
+mod rfc3339;
 
+pub use attachment::Attachment;
+pub use clock::{Clock, FixedClock, SteppingClock, SystemClock};
+pub use event_sink::EventSink;
+pub use event_store::EventStore;
+pub use id_generator::{
+    IdGenerator, SequentialIdGenerator, UlidGenerator, UuidV4Generator, UuidV7Generator,
+};
+pub use platform::{set_platform_clock, set_platform_random};
+pub use priority::Priority;
+pub use subtask::Subtask;
+pub use tag::Tag;
+pub(crate) use platform::fill_random;
+pub use tenant_id::TenantId;
 pub use todo_state::TodoState;
 pub use todo_event::TodoEvent;
 pub use todo_entity::Todo;
 pub use todo_error::TodoError;
+pub use todo_filter::{FilterTerm, TodoFilter};
 pub use todo_repository::TodoRepository;
 
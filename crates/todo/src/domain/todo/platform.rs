@@ -0,0 +1,84 @@
+use chrono::{DateTime, Utc};
+use std::sync::OnceLock;
+
+use super::Clock;
+
+static CLOCK: OnceLock<Box<dyn Clock>> = OnceLock::new();
+static RANDOM: OnceLock<fn(&mut [u8])> = OnceLock::new();
+
+/// Makes `SystemClock`, and through it the time-sortable id generators, read `clock`
+///
+/// For targets without a system clock `Utc::now()` can read, such as `wasm32-unknown-unknown`
+/// or embedded ones, and for builds without the `std-clock` feature. The installed clock is
+/// read instead of the system clock when there is one. It can be installed once per process,
+/// before the first todo is created. Without the feature, reading `SystemClock` before a
+/// clock is installed panics.
+///
+/// # Returns
+/// - `Err(clock)`: If a clock was installed before
+pub fn set_platform_clock(clock: Box<dyn Clock>) -> Result<(), Box<dyn Clock>> {
+    CLOCK.set(clock)
+}
+
+/// Makes the id generators and API key secrets draw their random bytes from `fill`
+///
+/// For targets without an entropy source `getrandom` supports, and for builds without the
+/// `getrandom` feature. `fill` must fill the whole slice with cryptographically secure random
+/// bytes. It is used instead of `getrandom` when installed, and can be installed once per
+/// process. Without the feature, generating a random id or API key secret before a source is
+/// installed panics.
+///
+/// # Returns
+/// - `Err(fill)`: If a source was installed before
+pub fn set_platform_random(fill: fn(&mut [u8])) -> Result<(), fn(&mut [u8])> {
+    RANDOM.set(fill)
+}
+
+/// The time of the installed clock, or of the system clock
+///
+/// # Panics
+/// Without the `std-clock` feature, if no clock was installed with `set_platform_clock`
+pub(crate) fn now() -> DateTime<Utc> {
+    if let Some(clock) = CLOCK.get() {
+        return clock.now();
+    }
+    #[cfg(feature = "std-clock")]
+    {
+        Utc::now()
+    }
+    #[cfg(not(feature = "std-clock"))]
+    {
+        panic!(
+            "SystemClock has no clock to read without the `std-clock` feature; install one \
+             with `set_platform_clock` before the first todo is created"
+        )
+    }
+}
+
+/// Fills `bytes` from the installed random source, or from the operating system
+///
+/// # Panics
+/// Without the `getrandom` feature, if no source was installed with `set_platform_random`
+pub(crate) fn fill_random(bytes: &mut [u8]) {
+    if let Some(fill) = RANDOM.get() {
+        return fill(bytes);
+    }
+    #[cfg(feature = "getrandom")]
+    {
+        getrandom::fill(bytes).expect("the operating system has no random source")
+    }
+    #[cfg(not(feature = "getrandom"))]
+    {
+        panic!(
+            "no random source without the `getrandom` feature; install one with \
+             `set_platform_random` before the first id or API key is generated"
+        )
+    }
+}
+
+/// 128 random bits
+pub(crate) fn random_u128() -> u128 {
+    let mut bytes = [0; 16];
+    fill_random(&mut bytes);
+    u128::from_be_bytes(bytes)
+}
@@ -0,0 +1,57 @@
+/// Value object representing how urgent a Todo is
+///
+/// Priorities are ordered from `Low` to `Urgent`, so sorting in descending order puts the
+/// most urgent todos first. Todos are `Medium` until their priority is set. With the `serde`
+/// feature it serializes as `"LOW"`, `"MEDIUM"`, `"HIGH"` or `"URGENT"`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "SCREAMING_SNAKE_CASE"))]
+pub enum Priority {
+    Low,
+    #[default]
+    Medium,
+    High,
+    Urgent,
+}
+
+impl Priority {
+    /// Every priority, from `Low` to `Urgent`
+    pub const ALL: [Priority; 4] = [
+        Priority::Low,
+        Priority::Medium,
+        Priority::High,
+        Priority::Urgent,
+    ];
+
+    /// Name of the priority in files and payloads: `LOW`, `MEDIUM`, `HIGH` or `URGENT`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Priority::Low => "LOW",
+            Priority::Medium => "MEDIUM",
+            Priority::High => "HIGH",
+            Priority::Urgent => "URGENT",
+        }
+    }
+
+    /// The next more urgent priority, or `Urgent` for `Urgent`
+    pub fn raised(self) -> Self {
+        match self {
+            Priority::Low => Priority::Medium,
+            Priority::Medium => Priority::High,
+            Priority::High | Priority::Urgent => Priority::Urgent,
+        }
+    }
+
+    /// Parses a priority name, ignoring case
+    ///
+    /// # Returns
+    /// - `Ok(Priority)`: The priority named `name`
+    /// - `Err(String)`: If `name` is not `low`, `medium`, `high` or `urgent`
+    pub fn parse(name: &str) -> Result<Self, String> {
+        Priority::ALL
+            .into_iter()
+            .find(|priority| priority.name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| format!("unknown priority: {}", name))
+    }
+}
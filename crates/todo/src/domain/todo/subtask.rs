@@ -0,0 +1,15 @@
+use std::sync::Arc;
+
+/// Entity representing one item of a Todo's checklist
+///
+/// Subtasks belong to the Todo that holds them: they are changed only through its
+/// `add_subtask`, `toggle_subtask` and `remove_subtask` methods, and their `id` is unique
+/// within it. With the `serde` feature it serializes as `{id, description, done}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Subtask {
+    pub id: Arc<str>,
+    pub description: Arc<str>,
+    pub done: bool,
+}
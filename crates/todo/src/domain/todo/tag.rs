@@ -0,0 +1,57 @@
+use std::fmt;
+use std::sync::Arc;
+
+/// Value object labelling a Todo, such as `work` or `errands`
+///
+/// A tag is a non-empty word: surrounding whitespace is trimmed and inner whitespace is
+/// rejected, so tags can be written space-separated, as in a `tag:` search term. Tags are
+/// compared as written, so `Work` and `work` are different tags. With the `serde` feature it
+/// serializes as its string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(try_from = "String", into = "String"))]
+pub struct Tag(Arc<str>);
+
+impl Tag {
+    /// Creates a Tag
+    ///
+    /// # Returns
+    /// - `Ok(Tag)`: For a name that is a single non-empty word once trimmed
+    /// - `Err(String)`: Otherwise
+    pub fn new(name: &str) -> Result<Self, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("tag must not be empty".to_string());
+        }
+        if name.contains(char::is_whitespace) {
+            return Err(format!("invalid tag {:?}, expected a single word", name));
+        }
+        Ok(Tag(name.into()))
+    }
+
+    /// The tag as a string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl TryFrom<String> for Tag {
+    type Error = String;
+
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        Tag::new(&name)
+    }
+}
+
+impl From<Tag> for String {
+    fn from(tag: Tag) -> Self {
+        tag.0.to_string()
+    }
+}
@@ -0,0 +1,48 @@
+use std::fmt;
+use std::sync::Arc;
+
+/// Identifies the tenant, such as an organisation or a single user, that owns a set of todos
+///
+/// A tenant id is non-empty and contains no `/`, so the repositories can namespace ids with
+/// it unambiguously.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TenantId(Arc<str>);
+
+impl TenantId {
+    /// Creates a TenantId
+    ///
+    /// # Returns
+    /// - `Ok(TenantId)`: For a non-empty id without `/`
+    /// - `Err(String)`: Otherwise
+    pub fn new(id: impl Into<String>) -> Result<Self, String> {
+        let id = id.into();
+        if id.is_empty() || id.contains('/') {
+            return Err(format!(
+                "invalid tenant id {:?}, expected a non-empty id without '/'",
+                id
+            ));
+        }
+        Ok(TenantId(id.into()))
+    }
+
+    /// The id as a string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// `id` stored under the tenant, as `<tenant>/<id>`
+    pub fn scope(&self, id: &str) -> String {
+        format!("{}/{}", self.0, id)
+    }
+
+    /// `id` as the tenant's handlers see it, or `None` if it is not stored under the tenant
+    pub fn unscope<'a>(&self, id: &'a str) -> Option<&'a str> {
+        id.strip_prefix(&*self.0)?.strip_prefix('/')
+    }
+}
+
+impl fmt::Display for TenantId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
@@ -1,12 +1,54 @@
 use chrono::{DateTime, Utc};
-use crate::domain::todo::{TodoError, TodoEvent, TodoState};
+use std::sync::Arc;
+use crate::domain::blob::is_content_hash;
+use crate::domain::todo::{
+    Attachment, Clock, IdGenerator, Priority, Subtask, SystemClock, Tag, TodoError, TodoEvent,
+    TodoState, UuidV4Generator,
+};
 
 /// Aggregate root representing a Todo task
+///
+/// With the `serde` feature it serializes as
+/// `{id, created_at, description, state, priority}`, with `created_at` in RFC3339, `due_at`
+/// when the todo has a due date, and `tags`, `subtasks`, `blocked_by` and `attachments` when
+/// it has any. A missing `priority` reads as `MEDIUM`. The dirty flag is not serialized, but
+/// equality compares it.
+///
+/// `id` and `description` are shared with clones and with the events they emit, so copying
+/// todos out of a repository does not copy their text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Todo {
-    pub id: String,
+    pub id: Arc<str>,
+    #[cfg_attr(feature = "serde", serde(with = "super::rfc3339"))]
+    #[cfg_attr(feature = "schemars", schemars(with = "DateTime<Utc>"))]
     pub created_at: DateTime<Utc>,
-    pub description: String,
+    pub description: Arc<str>,
     pub state: TodoState,
+    /// When the todo is due, never before `created_at`
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none", with = "super::rfc3339::option")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<DateTime<Utc>>"))]
+    pub due_at: Option<DateTime<Utc>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub priority: Priority,
+    /// Distinct tags, in the order they were added
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub tags: Vec<Tag>,
+    /// Checklist items, in the order they were added
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub subtasks: Vec<Subtask>,
+    /// Ids of the todos that must be done before this one can be started or finished, in the
+    /// order they were added
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub blocked_by: Vec<Arc<str>>,
+    /// Files attached to the todo, in the order they were attached
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub attachments: Vec<Attachment>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) dirty: Option<bool>,
 }
 
@@ -26,18 +68,54 @@ impl Todo {
     /// - Sets `state = TodoState::Todo`
     /// - Sets `created_at` to current timestamp
     pub fn new(description: String) -> Result<(Self, Vec<TodoEvent>), TodoError> {
+        Self::new_with_clock(description, &SystemClock)
+    }
+
+    /// Creates a new Todo instance, reading `created_at` from `clock`
+    /// 
+    /// # Returns
+    /// - Same as `new`
+    pub fn new_with_clock(
+        description: String,
+        clock: &dyn Clock,
+    ) -> Result<(Self, Vec<TodoEvent>), TodoError> {
+        Self::new_with(description, clock, &UuidV4Generator)
+    }
+
+    /// Creates a new Todo instance, reading `created_at` from `clock` and its id from `ids`
+    /// 
+    /// # Returns
+    /// - Same as `new`
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "new", level = "debug", skip_all, fields(todo.id = tracing::field::Empty), err)
+    )]
+    pub fn new_with(
+        description: String,
+        clock: &dyn Clock,
+        ids: &dyn IdGenerator,
+    ) -> Result<(Self, Vec<TodoEvent>), TodoError> {
         if description.trim().is_empty() {
             return Err(TodoError::EmptyDescription);
         }
 
-        let id = uuid::Uuid::new_v4().to_string();
-        let created_at = Utc::now();
+        let id: Arc<str> = ids.next_id().into();
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("todo.id", &*id);
+        let description: Arc<str> = description.into();
+        let created_at = clock.now();
 
         let todo = Todo {
             id: id.clone(),
             created_at,
             description: description.clone(),
             state: TodoState::Todo,
+            due_at: None,
+            priority: Priority::default(),
+            tags: Vec::new(),
+            subtasks: Vec::new(),
+            blocked_by: Vec::new(),
+            attachments: Vec::new(),
             dirty: Some(false),
         };
 
@@ -59,18 +137,48 @@ impl Todo {
     /// # Returns
     /// - `Ok(Vec<TodoEvent>)`: Returns `[TodoEvent::TodoStateChanged]`
     /// - `Err(TodoError::InvalidStateTransition)`: If transition not allowed or same state
+    /// - `Err(TodoError::ValidationError)`: If moving to `InProgress` or `Done` while
+    ///   `blocked_by` is not empty
     /// 
     /// # Special Requirements
     /// - Validates new state differs from current using TodoState::can_transition_to()
     /// - Mutates internal state directly
     /// - Marks as `dirty`
     pub fn update_state(&mut self, new_state: TodoState) -> Result<Vec<TodoEvent>, TodoError> {
+        self.update_state_with_clock(new_state, &SystemClock)
+    }
+
+    /// Updates the Todo state with validation, reading `changed_at` from `clock`
+    /// 
+    /// # Returns
+    /// - Same as `update_state`
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "update_state",
+            level = "debug",
+            skip_all,
+            fields(todo.id = %self.id, from_state = ?self.state, to_state = ?new_state),
+            err
+        )
+    )]
+    pub fn update_state_with_clock(
+        &mut self,
+        new_state: TodoState,
+        clock: &dyn Clock,
+    ) -> Result<Vec<TodoEvent>, TodoError> {
         if !self.state.can_transition_to(new_state) {
             return Err(TodoError::InvalidStateTransition);
         }
+        if new_state != TodoState::Todo && !self.blocked_by.is_empty() {
+            return Err(TodoError::ValidationError(format!(
+                "todo is blocked by {}",
+                self.blocked_by.join(", ")
+            )));
+        }
 
         let from_state = self.state;
-        let changed_at = Utc::now();
+        let changed_at = clock.now();
 
         self.state = new_state;
         self.dirty = Some(true);
@@ -130,5 +238,649 @@ impl Todo {
 
         self.update_state(previous_state)
     }
+
+    /// Replaces the Todo's description
+    /// 
+    /// # Parameters
+    /// - `new`: The new description (must be non-empty)
+    /// 
+    /// # Returns
+    /// - `Ok(Vec<TodoEvent>)`: Returns `[TodoEvent::TodoDescriptionUpdated]`, or no event if
+    ///   the description was already `new`
+    /// - `Err(TodoError::EmptyDescription)`: If `new` is empty
+    /// 
+    /// # Special Requirements
+    /// - Validates non-empty description, as `new` does
+    /// - Marks as `dirty` when the description changes
+    pub fn update_description(&mut self, new: String) -> Result<Vec<TodoEvent>, TodoError> {
+        self.update_description_with_clock(new, &SystemClock)
+    }
+
+    /// Replaces the Todo's description, reading `updated_at` from `clock`
+    /// 
+    /// # Returns
+    /// - Same as `update_description`
+    pub fn update_description_with_clock(
+        &mut self,
+        new: String,
+        clock: &dyn Clock,
+    ) -> Result<Vec<TodoEvent>, TodoError> {
+        if new.trim().is_empty() {
+            return Err(TodoError::EmptyDescription);
+        }
+        if *self.description == *new {
+            return Ok(Vec::new());
+        }
+        let new: Arc<str> = new.into();
+        let old = std::mem::replace(&mut self.description, new.clone());
+        self.dirty = Some(true);
+        Ok(vec![TodoEvent::TodoDescriptionUpdated {
+            id: self.id.clone(),
+            old,
+            new,
+            updated_at: clock.now(),
+        }])
+    }
+
+    /// Sets the date the Todo is due
+    /// 
+    /// # Parameters
+    /// - `due_at`: When the todo is due
+    /// 
+    /// # Returns
+    /// - `Ok(Vec<TodoEvent>)`: Returns `[TodoEvent::TodoDueDateChanged]`, or no event if the
+    ///   todo was already due at `due_at`
+    /// - `Err(TodoError::ValidationError)`: If `due_at` is before `created_at`
+    /// 
+    /// # Special Requirements
+    /// - Marks as `dirty` when the due date changes
+    pub fn set_due_date(&mut self, due_at: DateTime<Utc>) -> Result<Vec<TodoEvent>, TodoError> {
+        self.set_due_date_with_clock(due_at, &SystemClock)
+    }
+
+    /// Sets the date the Todo is due, reading `changed_at` from `clock`
+    /// 
+    /// # Returns
+    /// - Same as `set_due_date`
+    pub fn set_due_date_with_clock(
+        &mut self,
+        due_at: DateTime<Utc>,
+        clock: &dyn Clock,
+    ) -> Result<Vec<TodoEvent>, TodoError> {
+        if due_at < self.created_at {
+            return Err(TodoError::ValidationError(
+                "due date must not be before the todo was created".to_string(),
+            ));
+        }
+        Ok(self.change_due_date(Some(due_at), clock))
+    }
+
+    /// Removes the date the Todo is due
+    /// 
+    /// # Returns
+    /// - `Ok(Vec<TodoEvent>)`: Returns `[TodoEvent::TodoDueDateChanged]` with no `due_at`,
+    ///   or no event if the todo had no due date
+    /// 
+    /// # Special Requirements
+    /// - Marks as `dirty` when the todo had a due date
+    pub fn clear_due_date(&mut self) -> Result<Vec<TodoEvent>, TodoError> {
+        self.clear_due_date_with_clock(&SystemClock)
+    }
+
+    /// Removes the date the Todo is due, reading `changed_at` from `clock`
+    /// 
+    /// # Returns
+    /// - Same as `clear_due_date`
+    pub fn clear_due_date_with_clock(
+        &mut self,
+        clock: &dyn Clock,
+    ) -> Result<Vec<TodoEvent>, TodoError> {
+        Ok(self.change_due_date(None, clock))
+    }
+
+    fn change_due_date(&mut self, due_at: Option<DateTime<Utc>>, clock: &dyn Clock) -> Vec<TodoEvent> {
+        if self.due_at == due_at {
+            return Vec::new();
+        }
+        self.due_at = due_at;
+        self.dirty = Some(true);
+        vec![TodoEvent::TodoDueDateChanged {
+            id: self.id.clone(),
+            due_at,
+            changed_at: clock.now(),
+        }]
+    }
+
+    /// Sets how urgent the Todo is
+    /// 
+    /// # Parameters
+    /// - `priority`: The new priority
+    /// 
+    /// # Returns
+    /// - `Vec<TodoEvent>`: Returns `[TodoEvent::TodoPriorityChanged]`, or no event if the
+    ///   todo already had `priority`
+    /// 
+    /// # Special Requirements
+    /// - Marks as `dirty` when the priority changes
+    pub fn set_priority(&mut self, priority: Priority) -> Vec<TodoEvent> {
+        self.set_priority_with_clock(priority, &SystemClock)
+    }
+
+    /// Sets how urgent the Todo is, reading `changed_at` from `clock`
+    /// 
+    /// # Returns
+    /// - Same as `set_priority`
+    pub fn set_priority_with_clock(&mut self, priority: Priority, clock: &dyn Clock) -> Vec<TodoEvent> {
+        if self.priority == priority {
+            return Vec::new();
+        }
+        let from_priority = std::mem::replace(&mut self.priority, priority);
+        self.dirty = Some(true);
+        vec![TodoEvent::TodoPriorityChanged {
+            id: self.id.clone(),
+            from_priority,
+            to_priority: priority,
+            changed_at: clock.now(),
+        }]
+    }
+
+    /// Adds a tag to the Todo
+    /// 
+    /// # Parameters
+    /// - `tag`: The tag, a single word once trimmed
+    /// 
+    /// # Returns
+    /// - `Ok(Vec<TodoEvent>)`: Returns `[TodoEvent::TodoTagAdded]`
+    /// - `Err(TodoError::ValidationError)`: If `tag` is empty or not a single word, or the
+    ///   todo already has it
+    /// 
+    /// # Special Requirements
+    /// - Marks as `dirty`
+    pub fn add_tag(&mut self, tag: &str) -> Result<Vec<TodoEvent>, TodoError> {
+        self.add_tag_with_clock(tag, &SystemClock)
+    }
+
+    /// Adds a tag to the Todo, reading `added_at` from `clock`
+    /// 
+    /// # Returns
+    /// - Same as `add_tag`
+    pub fn add_tag_with_clock(
+        &mut self,
+        tag: &str,
+        clock: &dyn Clock,
+    ) -> Result<Vec<TodoEvent>, TodoError> {
+        let tag = Tag::new(tag).map_err(TodoError::ValidationError)?;
+        if self.tags.contains(&tag) {
+            return Err(TodoError::ValidationError(format!(
+                "todo already has tag {}",
+                tag
+            )));
+        }
+        self.tags.push(tag.clone());
+        self.dirty = Some(true);
+        Ok(vec![TodoEvent::TodoTagAdded {
+            id: self.id.clone(),
+            tag,
+            added_at: clock.now(),
+        }])
+    }
+
+    /// Removes a tag from the Todo
+    /// 
+    /// # Parameters
+    /// - `tag`: The tag, a single word once trimmed
+    /// 
+    /// # Returns
+    /// - `Ok(Vec<TodoEvent>)`: Returns `[TodoEvent::TodoTagRemoved]`, or no event if the
+    ///   todo does not have `tag`
+    /// - `Err(TodoError::ValidationError)`: If `tag` is empty or not a single word
+    /// 
+    /// # Special Requirements
+    /// - Marks as `dirty` when the todo had the tag
+    pub fn remove_tag(&mut self, tag: &str) -> Result<Vec<TodoEvent>, TodoError> {
+        self.remove_tag_with_clock(tag, &SystemClock)
+    }
+
+    /// Removes a tag from the Todo, reading `removed_at` from `clock`
+    /// 
+    /// # Returns
+    /// - Same as `remove_tag`
+    pub fn remove_tag_with_clock(
+        &mut self,
+        tag: &str,
+        clock: &dyn Clock,
+    ) -> Result<Vec<TodoEvent>, TodoError> {
+        let tag = Tag::new(tag).map_err(TodoError::ValidationError)?;
+        let Some(index) = self.tags.iter().position(|existing| *existing == tag) else {
+            return Ok(Vec::new());
+        };
+        self.tags.remove(index);
+        self.dirty = Some(true);
+        Ok(vec![TodoEvent::TodoTagRemoved {
+            id: self.id.clone(),
+            tag,
+            removed_at: clock.now(),
+        }])
+    }
+
+    /// Checks whether the Todo has `tag`
+    pub fn has_tag(&self, tag: &Tag) -> bool {
+        self.tags.contains(tag)
+    }
+
+    /// Adds an item to the Todo's checklist, not yet done
+    /// 
+    /// # Parameters
+    /// - `description`: Item description (must be non-empty)
+    /// 
+    /// # Returns
+    /// - `Ok(Vec<TodoEvent>)`: Returns `[TodoEvent::TodoSubtaskAdded]`, carrying the new
+    ///   subtask's id
+    /// - `Err(TodoError::EmptyDescription)`: If description is empty
+    /// 
+    /// # Special Requirements
+    /// - Marks as `dirty`
+    pub fn add_subtask(&mut self, description: String) -> Result<Vec<TodoEvent>, TodoError> {
+        self.add_subtask_with(description, &SystemClock, &UuidV4Generator)
+    }
+
+    /// Adds an item to the Todo's checklist, reading `added_at` from `clock` and its id
+    /// from `ids`
+    /// 
+    /// # Returns
+    /// - Same as `add_subtask`
+    pub fn add_subtask_with(
+        &mut self,
+        description: String,
+        clock: &dyn Clock,
+        ids: &dyn IdGenerator,
+    ) -> Result<Vec<TodoEvent>, TodoError> {
+        if description.trim().is_empty() {
+            return Err(TodoError::EmptyDescription);
+        }
+        let subtask = Subtask {
+            id: ids.next_id().into(),
+            description: description.into(),
+            done: false,
+        };
+        let event = TodoEvent::TodoSubtaskAdded {
+            id: self.id.clone(),
+            subtask_id: subtask.id.clone(),
+            description: subtask.description.clone(),
+            added_at: clock.now(),
+        };
+        self.subtasks.push(subtask);
+        self.dirty = Some(true);
+        Ok(vec![event])
+    }
+
+    /// Marks a checklist item done, or not done if it was
+    /// 
+    /// # Parameters
+    /// - `subtask_id`: The id of the subtask to toggle
+    /// 
+    /// # Returns
+    /// - `Ok(Vec<TodoEvent>)`: Returns `[TodoEvent::TodoSubtaskToggled]` with the new `done`
+    /// - `Err(TodoError::ValidationError)`: If the todo has no subtask with `subtask_id`
+    /// 
+    /// # Special Requirements
+    /// - Marks as `dirty`
+    pub fn toggle_subtask(&mut self, subtask_id: &str) -> Result<Vec<TodoEvent>, TodoError> {
+        self.toggle_subtask_with_clock(subtask_id, &SystemClock)
+    }
+
+    /// Marks a checklist item done, or not done if it was, reading `toggled_at` from `clock`
+    /// 
+    /// # Returns
+    /// - Same as `toggle_subtask`
+    pub fn toggle_subtask_with_clock(
+        &mut self,
+        subtask_id: &str,
+        clock: &dyn Clock,
+    ) -> Result<Vec<TodoEvent>, TodoError> {
+        let Some(subtask) = self.subtasks.iter_mut().find(|subtask| &*subtask.id == subtask_id) else {
+            return Err(TodoError::ValidationError(format!(
+                "todo has no subtask {}",
+                subtask_id
+            )));
+        };
+        subtask.done = !subtask.done;
+        let event = TodoEvent::TodoSubtaskToggled {
+            id: self.id.clone(),
+            subtask_id: subtask.id.clone(),
+            done: subtask.done,
+            toggled_at: clock.now(),
+        };
+        self.dirty = Some(true);
+        Ok(vec![event])
+    }
+
+    /// Removes an item from the Todo's checklist
+    /// 
+    /// # Parameters
+    /// - `subtask_id`: The id of the subtask to remove
+    /// 
+    /// # Returns
+    /// - `Vec<TodoEvent>`: Returns `[TodoEvent::TodoSubtaskRemoved]`, or no event if the
+    ///   todo has no subtask with `subtask_id`
+    /// 
+    /// # Special Requirements
+    /// - Marks as `dirty` when the todo had the subtask
+    pub fn remove_subtask(&mut self, subtask_id: &str) -> Vec<TodoEvent> {
+        self.remove_subtask_with_clock(subtask_id, &SystemClock)
+    }
+
+    /// Removes an item from the Todo's checklist, reading `removed_at` from `clock`
+    /// 
+    /// # Returns
+    /// - Same as `remove_subtask`
+    pub fn remove_subtask_with_clock(&mut self, subtask_id: &str, clock: &dyn Clock) -> Vec<TodoEvent> {
+        let Some(index) = self.subtasks.iter().position(|subtask| &*subtask.id == subtask_id) else {
+            return Vec::new();
+        };
+        let subtask = self.subtasks.remove(index);
+        self.dirty = Some(true);
+        vec![TodoEvent::TodoSubtaskRemoved {
+            id: self.id.clone(),
+            subtask_id: subtask.id,
+            removed_at: clock.now(),
+        }]
+    }
+
+    /// Makes the Todo wait on another todo
+    /// 
+    /// # Parameters
+    /// - `blocker_id`: The id of the todo that must be done first
+    /// 
+    /// # Returns
+    /// - `Ok(Vec<TodoEvent>)`: Returns `[TodoEvent::TodoBlocked]`, or no event if the todo
+    ///   is already blocked by `blocker_id`
+    /// - `Err(TodoError::ValidationError)`: If `blocker_id` is the todo's own id
+    /// 
+    /// # Special Requirements
+    /// - Does not look at the blocker; `DependencyService::block` checks that it exists, is
+    ///   not done and does not make a cycle
+    /// - Marks as `dirty` when the todo was not blocked by `blocker_id`
+    pub fn block_by(&mut self, blocker_id: &str) -> Result<Vec<TodoEvent>, TodoError> {
+        self.block_by_with_clock(blocker_id, &SystemClock)
+    }
+
+    /// Makes the Todo wait on another todo, reading `blocked_at` from `clock`
+    /// 
+    /// # Returns
+    /// - Same as `block_by`
+    pub fn block_by_with_clock(
+        &mut self,
+        blocker_id: &str,
+        clock: &dyn Clock,
+    ) -> Result<Vec<TodoEvent>, TodoError> {
+        if blocker_id == &*self.id {
+            return Err(TodoError::ValidationError("todo cannot block itself".to_string()));
+        }
+        if self.is_blocked_by(blocker_id) {
+            return Ok(Vec::new());
+        }
+        let blocker_id: Arc<str> = blocker_id.into();
+        self.blocked_by.push(blocker_id.clone());
+        self.dirty = Some(true);
+        Ok(vec![TodoEvent::TodoBlocked {
+            id: self.id.clone(),
+            blocker_id,
+            blocked_at: clock.now(),
+        }])
+    }
+
+    /// Stops the Todo from waiting on another todo
+    /// 
+    /// # Returns
+    /// - `Vec<TodoEvent>`: Returns `[TodoEvent::TodoUnblocked]`, or no event if the todo is
+    ///   not blocked by `blocker_id`
+    /// 
+    /// # Special Requirements
+    /// - Marks as `dirty` when the todo was blocked by `blocker_id`
+    pub fn unblock(&mut self, blocker_id: &str) -> Vec<TodoEvent> {
+        self.unblock_with_clock(blocker_id, &SystemClock)
+    }
+
+    /// Stops the Todo from waiting on another todo, reading `unblocked_at` from `clock`
+    /// 
+    /// # Returns
+    /// - Same as `unblock`
+    pub fn unblock_with_clock(&mut self, blocker_id: &str, clock: &dyn Clock) -> Vec<TodoEvent> {
+        let Some(index) = self.blocked_by.iter().position(|id| &**id == blocker_id) else {
+            return Vec::new();
+        };
+        let blocker_id = self.blocked_by.remove(index);
+        self.dirty = Some(true);
+        vec![TodoEvent::TodoUnblocked {
+            id: self.id.clone(),
+            blocker_id,
+            unblocked_at: clock.now(),
+        }]
+    }
+
+    /// Whether the Todo waits on the todo `blocker_id`
+    pub fn is_blocked_by(&self, blocker_id: &str) -> bool {
+        self.blocked_by.iter().any(|id| &**id == blocker_id)
+    }
+
+    /// Attaches a file to the Todo, whose content is already stored under
+    /// `attachment.content_hash`
+    ///
+    /// # Parameters
+    /// - `attachment`: The metadata of the file
+    ///
+    /// # Returns
+    /// - `Ok(Vec<TodoEvent>)`: Returns `[TodoEvent::TodoAttachmentAdded]`, or no event if the
+    ///   todo already has an attachment with the same content
+    /// - `Err(TodoError::ValidationError)`: If the name is empty or `content_hash` is not a
+    ///   `content_hash`
+    ///
+    /// # Special Requirements
+    /// - Does not look at the content; `AttachmentService::attach` stores it first
+    /// - Marks as `dirty` when the attachment was added
+    pub fn attach(&mut self, attachment: Attachment) -> Result<Vec<TodoEvent>, TodoError> {
+        self.attach_with_clock(attachment, &SystemClock)
+    }
+
+    /// Attaches a file to the Todo, reading `added_at` from `clock`
+    ///
+    /// # Returns
+    /// - Same as `attach`
+    pub fn attach_with_clock(
+        &mut self,
+        attachment: Attachment,
+        clock: &dyn Clock,
+    ) -> Result<Vec<TodoEvent>, TodoError> {
+        if attachment.name.trim().is_empty() {
+            return Err(TodoError::ValidationError(
+                "attachment name must not be empty".to_string(),
+            ));
+        }
+        if !is_content_hash(&attachment.content_hash) {
+            return Err(TodoError::ValidationError(format!(
+                "invalid content hash: {}",
+                attachment.content_hash
+            )));
+        }
+        if self.attachment(&attachment.content_hash).is_some() {
+            return Ok(Vec::new());
+        }
+        let event = TodoEvent::TodoAttachmentAdded {
+            id: self.id.clone(),
+            content_hash: attachment.content_hash.clone(),
+            name: attachment.name.clone(),
+            media_type: attachment.media_type.clone(),
+            size: attachment.size,
+            added_at: clock.now(),
+        };
+        self.attachments.push(attachment);
+        self.dirty = Some(true);
+        Ok(vec![event])
+    }
+
+    /// Removes an attachment from the Todo, leaving its content where it is stored
+    ///
+    /// # Parameters
+    /// - `content_hash`: The `content_hash` of the attachment to remove
+    ///
+    /// # Returns
+    /// - `Vec<TodoEvent>`: Returns `[TodoEvent::TodoAttachmentRemoved]`, or no event if the
+    ///   todo has no attachment with `content_hash`
+    ///
+    /// # Special Requirements
+    /// - Marks as `dirty` when the todo had the attachment
+    pub fn detach(&mut self, content_hash: &str) -> Vec<TodoEvent> {
+        self.detach_with_clock(content_hash, &SystemClock)
+    }
+
+    /// Removes an attachment from the Todo, reading `removed_at` from `clock`
+    ///
+    /// # Returns
+    /// - Same as `detach`
+    pub fn detach_with_clock(&mut self, content_hash: &str, clock: &dyn Clock) -> Vec<TodoEvent> {
+        let Some(index) = self
+            .attachments
+            .iter()
+            .position(|attachment| &*attachment.content_hash == content_hash)
+        else {
+            return Vec::new();
+        };
+        let attachment = self.attachments.remove(index);
+        self.dirty = Some(true);
+        vec![TodoEvent::TodoAttachmentRemoved {
+            id: self.id.clone(),
+            content_hash: attachment.content_hash,
+            removed_at: clock.now(),
+        }]
+    }
+
+    /// The Todo's attachment with `content_hash`, if it has one
+    pub fn attachment(&self, content_hash: &str) -> Option<&Attachment> {
+        self.attachments
+            .iter()
+            .find(|attachment| &*attachment.content_hash == content_hash)
+    }
+
+    /// Share of the Todo's checklist that is done, from 0 to 100, rounded down
+    /// 
+    /// # Returns
+    /// - `Some(percentage)`: For a todo with subtasks
+    /// - `None`: For a todo without subtasks
+    pub fn completion_percentage(&self) -> Option<u8> {
+        if self.subtasks.is_empty() {
+            return None;
+        }
+        let done = self.subtasks.iter().filter(|subtask| subtask.done).count();
+        Some((done * 100 / self.subtasks.len()) as u8)
+    }
+
+    /// Rebuilds a Todo from the events it emitted
+    /// 
+    /// # Parameters
+    /// - `events`: A `TodoCreated` followed by the todo's `TodoStateChanged`,
+    ///   `TodoDescriptionUpdated`, `TodoDueDateChanged`, `TodoPriorityChanged`,
+    ///   `TodoTagAdded`, `TodoTagRemoved` and subtask events, `TodoBlocked`,
+    ///   `TodoUnblocked`, `TodoAttachmentAdded` and `TodoAttachmentRemoved`, in the order
+    ///   they were emitted; events about other todos are skipped, and so are `TodoArchived`,
+    ///   `TodoStale`, `TodoDeleted` and `TodoRestored` events, which leave the todo as it was
+    /// 
+    /// # Returns
+    /// - `Ok(Todo)`: The todo as it was after the last event, not `dirty`
+    /// - `Err(TodoError::TodoNotFound)`: If `events` does not start with a `TodoCreated`
+    /// - `Err(TodoError::InvalidStateTransition)`: If a state change does not start from the
+    ///   todo's state at that point, or is not an allowed transition
+    /// 
+    /// # Special Requirements
+    /// - Applies the same transition rules as `update_state`
+    pub fn replay(events: &[TodoEvent]) -> Result<Self, TodoError> {
+        let Some(TodoEvent::TodoCreated {
+            id,
+            description,
+            created_at,
+        }) = events.first()
+        else {
+            return Err(TodoError::TodoNotFound);
+        };
+        let mut todo = Todo {
+            id: id.clone(),
+            created_at: *created_at,
+            description: description.clone(),
+            state: TodoState::Todo,
+            due_at: None,
+            priority: Priority::default(),
+            tags: Vec::new(),
+            subtasks: Vec::new(),
+            blocked_by: Vec::new(),
+            attachments: Vec::new(),
+            dirty: Some(false),
+        };
+        for event in events[1..].iter().filter(|event| event.todo_id() == &*todo.id) {
+            match event {
+                TodoEvent::TodoStateChanged {
+                    from_state,
+                    to_state,
+                    ..
+                } if *from_state == todo.state && from_state.can_transition_to(*to_state) => {
+                    todo.state = *to_state;
+                }
+                TodoEvent::TodoDescriptionUpdated { new, .. } => todo.description = new.clone(),
+                TodoEvent::TodoDueDateChanged { due_at, .. } => todo.due_at = *due_at,
+                TodoEvent::TodoPriorityChanged { to_priority, .. } => todo.priority = *to_priority,
+                TodoEvent::TodoTagAdded { tag, .. } => {
+                    if !todo.tags.contains(tag) {
+                        todo.tags.push(tag.clone());
+                    }
+                }
+                TodoEvent::TodoTagRemoved { tag, .. } => todo.tags.retain(|existing| existing != tag),
+                TodoEvent::TodoSubtaskAdded {
+                    subtask_id,
+                    description,
+                    ..
+                } => {
+                    if !todo.subtasks.iter().any(|subtask| subtask.id == *subtask_id) {
+                        todo.subtasks.push(Subtask {
+                            id: subtask_id.clone(),
+                            description: description.clone(),
+                            done: false,
+                        });
+                    }
+                }
+                TodoEvent::TodoSubtaskToggled { subtask_id, done, .. } => {
+                    if let Some(subtask) = todo.subtasks.iter_mut().find(|subtask| subtask.id == *subtask_id) {
+                        subtask.done = *done;
+                    }
+                }
+                TodoEvent::TodoSubtaskRemoved { subtask_id, .. } => {
+                    todo.subtasks.retain(|subtask| subtask.id != *subtask_id)
+                }
+                TodoEvent::TodoBlocked { blocker_id, .. } => {
+                    if !todo.is_blocked_by(blocker_id) {
+                        todo.blocked_by.push(blocker_id.clone());
+                    }
+                }
+                TodoEvent::TodoUnblocked { blocker_id, .. } => todo.blocked_by.retain(|id| id != blocker_id),
+                TodoEvent::TodoAttachmentAdded { content_hash, name, media_type, size, .. } => {
+                    if todo.attachment(content_hash).is_none() {
+                        todo.attachments.push(Attachment {
+                            content_hash: content_hash.clone(),
+                            name: name.clone(),
+                            media_type: media_type.clone(),
+                            size: *size,
+                        });
+                    }
+                }
+                TodoEvent::TodoAttachmentRemoved { content_hash, .. } => todo
+                    .attachments
+                    .retain(|attachment| attachment.content_hash != *content_hash),
+                TodoEvent::TodoArchived { .. }
+                | TodoEvent::TodoStale { .. }
+                | TodoEvent::TodoDeleted { .. }
+                | TodoEvent::TodoRestored { .. } => {}
+                _ => return Err(TodoError::InvalidStateTransition),
+            }
+        }
+        Ok(todo)
+    }
 }
 
@@ -1,5 +1,14 @@
 /// Error types for Todo domain operations
+///
+/// With the `serde` feature it serializes as `{"code": "TODO_NOT_FOUND"}`, with the backend's
+/// message in `message` for `REPOSITORY_ERROR` and the broken rule for `VALIDATION_ERROR`.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "serde",
+    serde(tag = "code", content = "message", rename_all = "SCREAMING_SNAKE_CASE")
+)]
 pub enum TodoError {
     /// Returned when attempting to create a Todo with an empty description
     EmptyDescription,
@@ -7,5 +16,26 @@ pub enum TodoError {
     InvalidStateTransition,
     /// Returned when a Todo is not found in the repository
     TodoNotFound,
+    /// Returned when the underlying storage fails, carrying the backend's message
+    RepositoryError(String),
+    /// Returned when adding a todo would take a list over its quota
+    QuotaExceeded,
+    /// Returned when a change breaks a rule of the todo, carrying the rule, such as a due
+    /// date before the todo was created
+    ValidationError(String),
 }
 
+impl std::fmt::Display for TodoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TodoError::EmptyDescription => write!(f, "todo description must not be empty"),
+            TodoError::InvalidStateTransition => write!(f, "invalid todo state transition"),
+            TodoError::TodoNotFound => write!(f, "todo not found"),
+            TodoError::RepositoryError(message) => write!(f, "repository error: {}", message),
+            TodoError::QuotaExceeded => write!(f, "todo quota exceeded"),
+            TodoError::ValidationError(message) => write!(f, "validation error: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for TodoError {}
@@ -1,19 +1,250 @@
 use chrono::{DateTime, Utc};
-use crate::domain::todo::TodoState;
+use std::sync::Arc;
+use crate::domain::todo::{Priority, Tag, TodoState};
 
 /// Domain events that describe significant occurrences in the Todo lifecycle
+///
+/// With the `serde` feature it serializes tagged with its `type`, `"TODO_CREATED"`,
+/// `"TODO_STATE_CHANGED"`, `"TODO_ARCHIVED"`, `"TODO_STALE"`, `"TODO_DUE_DATE_CHANGED"`,
+/// `"TODO_PRIORITY_CHANGED"`, `"TODO_TAG_ADDED"`, `"TODO_TAG_REMOVED"`, `"TODO_SUBTASK_ADDED"`,
+/// `"TODO_SUBTASK_TOGGLED"`, `"TODO_SUBTASK_REMOVED"`, `"TODO_DESCRIPTION_UPDATED"`,
+/// `"TODO_BLOCKED"`, `"TODO_UNBLOCKED"`, `"TODO_DELETED"`, `"TODO_RESTORED"`,
+/// `"TODO_ATTACHMENT_ADDED"` or `"TODO_ATTACHMENT_REMOVED"`, and timestamps in RFC3339.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE"))]
 pub enum TodoEvent {
     TodoCreated {
-        id: String,
-        description: String,
+        id: Arc<str>,
+        description: Arc<str>,
+        #[cfg_attr(feature = "serde", serde(with = "super::rfc3339"))]
+        #[cfg_attr(feature = "schemars", schemars(with = "DateTime<Utc>"))]
         created_at: DateTime<Utc>,
     },
     TodoStateChanged {
-        id: String,
+        id: Arc<str>,
         from_state: TodoState,
         to_state: TodoState,
+        #[cfg_attr(feature = "serde", serde(with = "super::rfc3339"))]
+        #[cfg_attr(feature = "schemars", schemars(with = "DateTime<Utc>"))]
         changed_at: DateTime<Utc>,
     },
+    /// The todo was moved out of the list into the archive
+    TodoArchived {
+        id: Arc<str>,
+        #[cfg_attr(feature = "serde", serde(with = "super::rfc3339"))]
+        #[cfg_attr(feature = "schemars", schemars(with = "DateTime<Utc>"))]
+        archived_at: DateTime<Utc>,
+    },
+    /// The todo stayed in `state` for longer than the escalation rule `rule` allows
+    TodoStale {
+        id: Arc<str>,
+        rule: Arc<str>,
+        state: TodoState,
+        #[cfg_attr(feature = "serde", serde(with = "super::rfc3339"))]
+        #[cfg_attr(feature = "schemars", schemars(with = "DateTime<Utc>"))]
+        stale_at: DateTime<Utc>,
+    },
+    /// The todo's due date was set to `due_at`, or cleared when `None`
+    TodoDueDateChanged {
+        id: Arc<str>,
+        #[cfg_attr(feature = "serde", serde(with = "super::rfc3339::option"))]
+        #[cfg_attr(feature = "schemars", schemars(with = "Option<DateTime<Utc>>"))]
+        due_at: Option<DateTime<Utc>>,
+        #[cfg_attr(feature = "serde", serde(with = "super::rfc3339"))]
+        #[cfg_attr(feature = "schemars", schemars(with = "DateTime<Utc>"))]
+        changed_at: DateTime<Utc>,
+    },
+    TodoPriorityChanged {
+        id: Arc<str>,
+        from_priority: Priority,
+        to_priority: Priority,
+        #[cfg_attr(feature = "serde", serde(with = "super::rfc3339"))]
+        #[cfg_attr(feature = "schemars", schemars(with = "DateTime<Utc>"))]
+        changed_at: DateTime<Utc>,
+    },
+    TodoTagAdded {
+        id: Arc<str>,
+        tag: Tag,
+        #[cfg_attr(feature = "serde", serde(with = "super::rfc3339"))]
+        #[cfg_attr(feature = "schemars", schemars(with = "DateTime<Utc>"))]
+        added_at: DateTime<Utc>,
+    },
+    TodoTagRemoved {
+        id: Arc<str>,
+        tag: Tag,
+        #[cfg_attr(feature = "serde", serde(with = "super::rfc3339"))]
+        #[cfg_attr(feature = "schemars", schemars(with = "DateTime<Utc>"))]
+        removed_at: DateTime<Utc>,
+    },
+    TodoSubtaskAdded {
+        id: Arc<str>,
+        subtask_id: Arc<str>,
+        description: Arc<str>,
+        #[cfg_attr(feature = "serde", serde(with = "super::rfc3339"))]
+        #[cfg_attr(feature = "schemars", schemars(with = "DateTime<Utc>"))]
+        added_at: DateTime<Utc>,
+    },
+    TodoSubtaskToggled {
+        id: Arc<str>,
+        subtask_id: Arc<str>,
+        done: bool,
+        #[cfg_attr(feature = "serde", serde(with = "super::rfc3339"))]
+        #[cfg_attr(feature = "schemars", schemars(with = "DateTime<Utc>"))]
+        toggled_at: DateTime<Utc>,
+    },
+    TodoSubtaskRemoved {
+        id: Arc<str>,
+        subtask_id: Arc<str>,
+        #[cfg_attr(feature = "serde", serde(with = "super::rfc3339"))]
+        #[cfg_attr(feature = "schemars", schemars(with = "DateTime<Utc>"))]
+        removed_at: DateTime<Utc>,
+    },
+    TodoDescriptionUpdated {
+        id: Arc<str>,
+        old: Arc<str>,
+        new: Arc<str>,
+        #[cfg_attr(feature = "serde", serde(with = "super::rfc3339"))]
+        #[cfg_attr(feature = "schemars", schemars(with = "DateTime<Utc>"))]
+        updated_at: DateTime<Utc>,
+    },
+    /// The todo cannot be started or finished until the todo `blocker_id` is done
+    TodoBlocked {
+        id: Arc<str>,
+        blocker_id: Arc<str>,
+        #[cfg_attr(feature = "serde", serde(with = "super::rfc3339"))]
+        #[cfg_attr(feature = "schemars", schemars(with = "DateTime<Utc>"))]
+        blocked_at: DateTime<Utc>,
+    },
+    /// The todo no longer waits on the todo `blocker_id`
+    TodoUnblocked {
+        id: Arc<str>,
+        blocker_id: Arc<str>,
+        #[cfg_attr(feature = "serde", serde(with = "super::rfc3339"))]
+        #[cfg_attr(feature = "schemars", schemars(with = "DateTime<Utc>"))]
+        unblocked_at: DateTime<Utc>,
+    },
+    /// The todo was removed from the list, for good or into the trash
+    TodoDeleted {
+        id: Arc<str>,
+        #[cfg_attr(feature = "serde", serde(with = "super::rfc3339"))]
+        #[cfg_attr(feature = "schemars", schemars(with = "DateTime<Utc>"))]
+        deleted_at: DateTime<Utc>,
+    },
+    /// The todo was moved from the trash back to the list, as it was deleted
+    TodoRestored {
+        id: Arc<str>,
+        #[cfg_attr(feature = "serde", serde(with = "super::rfc3339"))]
+        #[cfg_attr(feature = "schemars", schemars(with = "DateTime<Utc>"))]
+        restored_at: DateTime<Utc>,
+    },
+    /// A file was attached to the todo, with its content stored under `content_hash`
+    TodoAttachmentAdded {
+        id: Arc<str>,
+        content_hash: Arc<str>,
+        name: Arc<str>,
+        media_type: Arc<str>,
+        size: u64,
+        #[cfg_attr(feature = "serde", serde(with = "super::rfc3339"))]
+        #[cfg_attr(feature = "schemars", schemars(with = "DateTime<Utc>"))]
+        added_at: DateTime<Utc>,
+    },
+    /// The file with `content_hash` is no longer attached to the todo
+    TodoAttachmentRemoved {
+        id: Arc<str>,
+        content_hash: Arc<str>,
+        #[cfg_attr(feature = "serde", serde(with = "super::rfc3339"))]
+        #[cfg_attr(feature = "schemars", schemars(with = "DateTime<Utc>"))]
+        removed_at: DateTime<Utc>,
+    },
 }
 
+impl TodoEvent {
+    /// Id of the todo the event is about
+    pub fn todo_id(&self) -> &str {
+        match self {
+            TodoEvent::TodoCreated { id, .. }
+            | TodoEvent::TodoStateChanged { id, .. }
+            | TodoEvent::TodoArchived { id, .. }
+            | TodoEvent::TodoStale { id, .. }
+            | TodoEvent::TodoDueDateChanged { id, .. }
+            | TodoEvent::TodoPriorityChanged { id, .. }
+            | TodoEvent::TodoTagAdded { id, .. }
+            | TodoEvent::TodoTagRemoved { id, .. }
+            | TodoEvent::TodoSubtaskAdded { id, .. }
+            | TodoEvent::TodoSubtaskToggled { id, .. }
+            | TodoEvent::TodoSubtaskRemoved { id, .. }
+            | TodoEvent::TodoDescriptionUpdated { id, .. }
+            | TodoEvent::TodoBlocked { id, .. }
+            | TodoEvent::TodoUnblocked { id, .. }
+            | TodoEvent::TodoDeleted { id, .. }
+            | TodoEvent::TodoRestored { id, .. }
+            | TodoEvent::TodoAttachmentAdded { id, .. }
+            | TodoEvent::TodoAttachmentRemoved { id, .. } => id,
+        }
+    }
+
+    /// The event with the id of its todo, and of the todo blocking it if any, passed
+    /// through `f`
+    ///
+    /// Tenant views use it to move events between the ids their handlers see and the ids
+    /// stored under the tenant.
+    pub fn map_todo_ids(mut self, f: impl Fn(&str) -> Arc<str>) -> TodoEvent {
+        match &mut self {
+            TodoEvent::TodoBlocked { blocker_id, .. }
+            | TodoEvent::TodoUnblocked { blocker_id, .. } => *blocker_id = f(blocker_id),
+            _ => {}
+        }
+        let id = self.todo_id_mut();
+        *id = f(id);
+        self
+    }
+
+    fn todo_id_mut(&mut self) -> &mut Arc<str> {
+        match self {
+            TodoEvent::TodoCreated { id, .. }
+            | TodoEvent::TodoStateChanged { id, .. }
+            | TodoEvent::TodoArchived { id, .. }
+            | TodoEvent::TodoStale { id, .. }
+            | TodoEvent::TodoDueDateChanged { id, .. }
+            | TodoEvent::TodoPriorityChanged { id, .. }
+            | TodoEvent::TodoTagAdded { id, .. }
+            | TodoEvent::TodoTagRemoved { id, .. }
+            | TodoEvent::TodoSubtaskAdded { id, .. }
+            | TodoEvent::TodoSubtaskToggled { id, .. }
+            | TodoEvent::TodoSubtaskRemoved { id, .. }
+            | TodoEvent::TodoDescriptionUpdated { id, .. }
+            | TodoEvent::TodoBlocked { id, .. }
+            | TodoEvent::TodoUnblocked { id, .. }
+            | TodoEvent::TodoDeleted { id, .. }
+            | TodoEvent::TodoRestored { id, .. }
+            | TodoEvent::TodoAttachmentAdded { id, .. }
+            | TodoEvent::TodoAttachmentRemoved { id, .. } => id,
+        }
+    }
+
+    /// When the event happened
+    pub fn occurred_at(&self) -> DateTime<Utc> {
+        match self {
+            TodoEvent::TodoCreated { created_at, .. } => *created_at,
+            TodoEvent::TodoStateChanged { changed_at, .. } => *changed_at,
+            TodoEvent::TodoArchived { archived_at, .. } => *archived_at,
+            TodoEvent::TodoStale { stale_at, .. } => *stale_at,
+            TodoEvent::TodoDueDateChanged { changed_at, .. } => *changed_at,
+            TodoEvent::TodoPriorityChanged { changed_at, .. } => *changed_at,
+            TodoEvent::TodoTagAdded { added_at, .. } => *added_at,
+            TodoEvent::TodoTagRemoved { removed_at, .. } => *removed_at,
+            TodoEvent::TodoSubtaskAdded { added_at, .. } => *added_at,
+            TodoEvent::TodoSubtaskToggled { toggled_at, .. } => *toggled_at,
+            TodoEvent::TodoSubtaskRemoved { removed_at, .. } => *removed_at,
+            TodoEvent::TodoDescriptionUpdated { updated_at, .. } => *updated_at,
+            TodoEvent::TodoBlocked { blocked_at, .. } => *blocked_at,
+            TodoEvent::TodoUnblocked { unblocked_at, .. } => *unblocked_at,
+            TodoEvent::TodoDeleted { deleted_at, .. } => *deleted_at,
+            TodoEvent::TodoRestored { restored_at, .. } => *restored_at,
+            TodoEvent::TodoAttachmentAdded { added_at, .. } => *added_at,
+            TodoEvent::TodoAttachmentRemoved { removed_at, .. } => *removed_at,
+        }
+    }
+}
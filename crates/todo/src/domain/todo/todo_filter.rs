@@ -0,0 +1,220 @@
+use crate::domain::todo::{Tag, Todo, TodoState};
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use std::sync::Arc;
+
+/// One condition of a `TodoFilter`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterTerm {
+    /// The todo is in this state
+    State(TodoState),
+    /// The description contains this text, ignoring case
+    Text(String),
+    /// The todo has this tag
+    Tag(Tag),
+    /// The todo was created at or after `from` and before `before`, each when set
+    Created {
+        from: Option<DateTime<Utc>>,
+        before: Option<DateTime<Utc>>,
+    },
+    /// The todo has a due date, at or after `from` and before `before`, each when set
+    Due {
+        from: Option<DateTime<Utc>>,
+        before: Option<DateTime<Utc>>,
+    },
+    /// The todo waits on the todo with this id
+    BlockedBy(Arc<str>),
+    /// The inner condition does not hold
+    Not(Box<FilterTerm>),
+}
+
+impl FilterTerm {
+    /// Checks whether `todo` meets the condition
+    pub fn matches(&self, todo: &Todo) -> bool {
+        match self {
+            FilterTerm::State(state) => todo.state == *state,
+            FilterTerm::Text(text) => todo
+                .description
+                .to_lowercase()
+                .contains(&text.to_lowercase()),
+            FilterTerm::Tag(tag) => todo.has_tag(tag),
+            FilterTerm::Created { from, before } => {
+                from.is_none_or(|from| todo.created_at >= from)
+                    && before.is_none_or(|before| todo.created_at < before)
+            }
+            FilterTerm::Due { from, before } => todo.due_at.is_some_and(|due_at| {
+                from.is_none_or(|from| due_at >= from)
+                    && before.is_none_or(|before| due_at < before)
+            }),
+            FilterTerm::BlockedBy(blocker_id) => todo.is_blocked_by(blocker_id),
+            FilterTerm::Not(term) => !term.matches(todo),
+        }
+    }
+}
+
+/// Value object selecting todos whose every term holds
+///
+/// Parsed from a search query such as `state:in_progress tag:home -tag:waiting
+/// due<2025-01-01 "quarterly report"`, whose space-separated terms are:
+/// - `state:<state>`: the todo is in `todo`, `in_progress` or `done`
+/// - `tag:<tag>`: the todo has the tag
+/// - `created<date`, `created<=date`, `created>date`, `created>=date` or `created:date`:
+///   compares the creation day, a `YYYY-MM-DD` date in UTC
+/// - `due<date`, `due<=date`, `due>date`, `due>=date` or `due:date`: compares the due day
+///   the same way; todos without a due date never match
+/// - a bare word or a `"quoted phrase"`: the description contains it, ignoring case
+///
+/// A leading `-` negates a term. An empty query matches every todo.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TodoFilter {
+    terms: Vec<FilterTerm>,
+}
+
+impl TodoFilter {
+    /// Creates a new TodoFilter matching every todo
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also requires `term` to hold
+    pub fn with(mut self, term: FilterTerm) -> Self {
+        self.terms.push(term);
+        self
+    }
+
+    /// Parses a search query
+    ///
+    /// # Returns
+    /// - `Err(String)`: If a term has an unknown field, a bad value or an unclosed quote
+    pub fn parse(query: &str) -> Result<Self, String> {
+        let mut filter = TodoFilter::new();
+        for token in tokenize(query)? {
+            let term = parse_term(&token)?;
+            filter.terms.push(if token.negated {
+                FilterTerm::Not(Box::new(term))
+            } else {
+                term
+            });
+        }
+        Ok(filter)
+    }
+
+    /// The terms that must all hold
+    pub fn terms(&self) -> &[FilterTerm] {
+        &self.terms
+    }
+
+    /// Checks whether `todo` meets every term
+    pub fn matches(&self, todo: &Todo) -> bool {
+        self.terms.iter().all(|term| term.matches(todo))
+    }
+}
+
+impl std::str::FromStr for TodoFilter {
+    type Err = String;
+
+    fn from_str(query: &str) -> Result<Self, Self::Err> {
+        TodoFilter::parse(query)
+    }
+}
+
+/// A term of a query, without its quotes and leading `-`
+struct Token<'a> {
+    text: &'a str,
+    quoted: bool,
+    negated: bool,
+}
+
+/// Splits `query` on whitespace outside of double quotes
+fn tokenize(query: &str) -> Result<Vec<Token<'_>>, String> {
+    let mut tokens = Vec::new();
+    let mut rest = query.trim_start();
+    while !rest.is_empty() {
+        let negated =
+            rest.len() > 1 && rest.starts_with('-') && !rest[1..].starts_with(char::is_whitespace);
+        if negated {
+            rest = &rest[1..];
+        }
+        if let Some(quoted) = rest.strip_prefix('"') {
+            let end = quoted
+                .find('"')
+                .ok_or_else(|| format!("unclosed quote in {:?}", query))?;
+            tokens.push(Token {
+                text: &quoted[..end],
+                quoted: true,
+                negated,
+            });
+            rest = &quoted[end + 1..];
+        } else {
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            tokens.push(Token {
+                text: &rest[..end],
+                quoted: false,
+                negated,
+            });
+            rest = &rest[end..];
+        }
+        rest = rest.trim_start();
+    }
+    Ok(tokens)
+}
+
+fn parse_term(token: &Token<'_>) -> Result<FilterTerm, String> {
+    let text = token.text;
+    let split = text.find([':', '<', '>']);
+    let (Some(split), false) = (split, token.quoted) else {
+        return Ok(FilterTerm::Text(text.to_string()));
+    };
+    let (field, rest) = text.split_at(split);
+    let (operator, value) = ["<=", ">=", ":", "<", ">"]
+        .into_iter()
+        .find_map(|operator| Some((operator, rest.strip_prefix(operator)?)))
+        .unwrap_or_default();
+    match (field.to_ascii_lowercase().as_str(), operator) {
+        ("state", ":") => Ok(FilterTerm::State(parse_state(value)?)),
+        ("state", _) => Err(format!("state only supports \":\", in {:?}", text)),
+        ("tag", ":") => Ok(FilterTerm::Tag(Tag::new(value)?)),
+        ("tag", _) => Err(format!("tag only supports \":\", in {:?}", text)),
+        ("created", _) => {
+            let (from, before) = day_range(operator, value)?;
+            Ok(FilterTerm::Created { from, before })
+        }
+        ("due", _) => {
+            let (from, before) = day_range(operator, value)?;
+            Ok(FilterTerm::Due { from, before })
+        }
+        _ => Err(format!("unknown field {:?}", field)),
+    }
+}
+
+/// The `from` and `before` bounds of a date term
+type DayRange = (Option<DateTime<Utc>>, Option<DateTime<Utc>>);
+
+/// The instants `operator` selects relative to the `YYYY-MM-DD` day `value`, in UTC
+fn day_range(operator: &str, value: &str) -> Result<DayRange, String> {
+    let day = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| format!("invalid date {:?}, expected YYYY-MM-DD", value))?;
+    let start = day.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc();
+    let end = start
+        .checked_add_days(Days::new(1))
+        .ok_or_else(|| format!("invalid date {:?}", value))?;
+    Ok(match operator {
+        "<" => (None, Some(start)),
+        "<=" => (None, Some(end)),
+        ">" => (Some(end), None),
+        ">=" => (Some(start), None),
+        _ => (Some(start), Some(end)),
+    })
+}
+
+/// Accepts the serialized names and their lowercase, case-insensitively
+fn parse_state(value: &str) -> Result<TodoState, String> {
+    match value.to_ascii_uppercase().replace('-', "_").as_str() {
+        "TODO" => Ok(TodoState::Todo),
+        "IN_PROGRESS" => Ok(TodoState::InProgress),
+        "DONE" => Ok(TodoState::Done),
+        _ => Err(format!(
+            "invalid state {:?}, expected todo, in_progress or done",
+            value
+        )),
+    }
+}
@@ -1,5 +1,7 @@
 use async_trait::async_trait;
-use crate::domain::todo::{Todo, TodoError};
+use futures::stream::{self, BoxStream, StreamExt};
+use std::sync::Arc;
+use crate::domain::todo::{FilterTerm, Tag, Todo, TodoError, TodoFilter};
 
 /// Repository trait for persisting and retrieving Todo aggregates
 /// 
@@ -21,6 +23,25 @@ pub trait TodoRepository: Send + Sync {
     /// - Persists all Todo fields including state
     async fn save(&self, todo: &Todo) -> Result<(), TodoError>;
 
+    /// Saves several Todo aggregates at once
+    /// 
+    /// # Parameters
+    /// - `todos`: The Todo aggregates to save, with distinct ids
+    /// 
+    /// # Returns
+    /// - `Ok(())`: Successfully saved all of them
+    /// - `Err(TodoError)`: If the save operation fails; some todos may already be saved
+    /// 
+    /// # Special Requirements
+    /// - The default implementation calls `save` for each todo in order; backends that can
+    ///   write several records in one operation override it
+    async fn save_all(&self, todos: &[Todo]) -> Result<(), TodoError> {
+        for todo in todos {
+            self.save(todo).await?;
+        }
+        Ok(())
+    }
+
     /// Finds a Todo by its unique identifier
     /// 
     /// # Parameters
@@ -38,6 +59,81 @@ pub trait TodoRepository: Send + Sync {
     /// - `Err(TodoError)`: If retrieval operation fails
     async fn find_all(&self) -> Result<Vec<Todo>, TodoError>;
 
+    /// Finds the Todos `filter` matches
+    /// 
+    /// # Parameters
+    /// - `filter`: The conditions a Todo must meet, such as a parsed search query
+    /// 
+    /// # Returns
+    /// - `Ok(Vec<Todo>)`: Returns the matching Todos, empty vector if none match
+    /// - `Err(TodoError)`: If retrieval operation fails
+    /// 
+    /// # Special Requirements
+    /// - The default implementation filters the Todos of `stream_all`; backends that can
+    ///   evaluate the filter in their storage override it
+    async fn find_matching(&self, filter: &TodoFilter) -> Result<Vec<Todo>, TodoError> {
+        let mut todos = Vec::new();
+        let mut stream = self.stream_all();
+        while let Some(todo) = stream.next().await {
+            let todo = todo?;
+            if filter.matches(&todo) {
+                todos.push(todo);
+            }
+        }
+        Ok(todos)
+    }
+
+    /// Finds the Todos that have a tag
+    /// 
+    /// # Parameters
+    /// - `tag`: The tag to look for
+    /// 
+    /// # Returns
+    /// - `Ok(Vec<Todo>)`: Returns the Todos with `tag`, empty vector if none have it
+    /// - `Err(TodoError)`: If retrieval operation fails
+    /// 
+    /// # Special Requirements
+    /// - The default implementation calls `find_matching` with a `FilterTerm::Tag`; backends
+    ///   that index tags override it
+    async fn find_by_tag(&self, tag: &Tag) -> Result<Vec<Todo>, TodoError> {
+        self.find_matching(&TodoFilter::new().with(FilterTerm::Tag(tag.clone())))
+            .await
+    }
+
+    /// Finds the Todos waiting on a todo
+    /// 
+    /// # Parameters
+    /// - `blocker_id`: The id of the todo they wait on
+    /// 
+    /// # Returns
+    /// - `Ok(Vec<Todo>)`: Returns the Todos blocked by `blocker_id`, empty vector if none are
+    /// - `Err(TodoError)`: If retrieval operation fails
+    /// 
+    /// # Special Requirements
+    /// - The default implementation calls `find_matching` with a `FilterTerm::BlockedBy`;
+    ///   backends that index dependencies override it
+    async fn find_blocked_by(&self, blocker_id: &str) -> Result<Vec<Todo>, TodoError> {
+        self.find_matching(&TodoFilter::new().with(FilterTerm::BlockedBy(blocker_id.into())))
+            .await
+    }
+
+    /// Streams all Todos in the repository, in no particular order
+    /// 
+    /// # Returns
+    /// - A stream of `Ok(Todo)`, ending after an `Err(TodoError)` if retrieval fails
+    /// 
+    /// # Special Requirements
+    /// - The default implementation loads every Todo with `find_all`; backends that can
+    ///   page through their storage override it to keep memory bounded
+    fn stream_all(&self) -> BoxStream<'_, Result<Todo, TodoError>> {
+        stream::once(self.find_all())
+            .flat_map(|result| match result {
+                Ok(todos) => stream::iter(todos.into_iter().map(Ok)).left_stream(),
+                Err(err) => stream::iter([Err(err)]).right_stream(),
+            })
+            .boxed()
+    }
+
     /// Deletes a Todo by its unique identifier
     /// 
     /// # Parameters
@@ -47,5 +143,109 @@ pub trait TodoRepository: Send + Sync {
     /// - `Ok(())`: Successfully deleted (or Todo didn't exist)
     /// - `Err(TodoError)`: If deletion operation fails
     async fn delete(&self, id: &str) -> Result<(), TodoError>;
+
+    /// Checks that the repository can serve requests
+    /// 
+    /// # Returns
+    /// - `Ok(())`: The storage is reachable
+    /// - `Err(TodoError)`: If the storage cannot be read
+    /// 
+    /// # Special Requirements
+    /// - The default implementation looks up an id no Todo has; backends with a cheaper
+    ///   probe, such as a connection ping, override it
+    async fn health_check(&self) -> Result<(), TodoError> {
+        self.find_by_id("").await.map(|_| ())
+    }
+}
+
+
+/// Shares a single repository between several handlers
+///
+/// Each handler owns its repository; passing each an `Arc` clone lets them all operate on
+/// the same underlying storage.
+#[async_trait]
+impl<T: TodoRepository + ?Sized> TodoRepository for Arc<T> {
+    async fn save(&self, todo: &Todo) -> Result<(), TodoError> {
+        (**self).save(todo).await
+    }
+
+    async fn save_all(&self, todos: &[Todo]) -> Result<(), TodoError> {
+        (**self).save_all(todos).await
+    }
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<Todo>, TodoError> {
+        (**self).find_by_id(id).await
+    }
+
+    async fn find_all(&self) -> Result<Vec<Todo>, TodoError> {
+        (**self).find_all().await
+    }
+
+    async fn find_matching(&self, filter: &TodoFilter) -> Result<Vec<Todo>, TodoError> {
+        (**self).find_matching(filter).await
+    }
+
+    async fn find_by_tag(&self, tag: &Tag) -> Result<Vec<Todo>, TodoError> {
+        (**self).find_by_tag(tag).await
+    }
+
+    async fn find_blocked_by(&self, blocker_id: &str) -> Result<Vec<Todo>, TodoError> {
+        (**self).find_blocked_by(blocker_id).await
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Todo, TodoError>> {
+        (**self).stream_all()
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), TodoError> {
+        (**self).delete(id).await
+    }
+
+    async fn health_check(&self) -> Result<(), TodoError> {
+        (**self).health_check().await
+    }
 }
 
+/// Lets handlers own a `Box<dyn TodoRepository>`, their default repository type
+#[async_trait]
+impl<T: TodoRepository + ?Sized> TodoRepository for Box<T> {
+    async fn save(&self, todo: &Todo) -> Result<(), TodoError> {
+        (**self).save(todo).await
+    }
+
+    async fn save_all(&self, todos: &[Todo]) -> Result<(), TodoError> {
+        (**self).save_all(todos).await
+    }
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<Todo>, TodoError> {
+        (**self).find_by_id(id).await
+    }
+
+    async fn find_all(&self) -> Result<Vec<Todo>, TodoError> {
+        (**self).find_all().await
+    }
+
+    async fn find_matching(&self, filter: &TodoFilter) -> Result<Vec<Todo>, TodoError> {
+        (**self).find_matching(filter).await
+    }
+
+    async fn find_by_tag(&self, tag: &Tag) -> Result<Vec<Todo>, TodoError> {
+        (**self).find_by_tag(tag).await
+    }
+
+    async fn find_blocked_by(&self, blocker_id: &str) -> Result<Vec<Todo>, TodoError> {
+        (**self).find_blocked_by(blocker_id).await
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Todo, TodoError>> {
+        (**self).stream_all()
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), TodoError> {
+        (**self).delete(id).await
+    }
+
+    async fn health_check(&self) -> Result<(), TodoError> {
+        (**self).health_check().await
+    }
+}
@@ -1,5 +1,10 @@
 /// Value object representing the state of a Todo in its workflow
+///
+/// With the `serde` feature it serializes as `"TODO"`, `"IN_PROGRESS"` or `"DONE"`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "SCREAMING_SNAKE_CASE"))]
 pub enum TodoState {
     /// Initial state when a todo is created
     Todo,
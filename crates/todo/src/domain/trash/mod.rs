@@ -0,0 +1,5 @@
+mod trash_repository;
+mod trashed_todo;
+
+pub use trash_repository::TrashRepository;
+pub use trashed_todo::TrashedTodo;
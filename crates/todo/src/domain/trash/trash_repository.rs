@@ -0,0 +1,70 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::domain::todo::TodoError;
+use crate::domain::trash::TrashedTodo;
+
+/// Repository trait for deleted todos, one per todo id
+#[async_trait]
+pub trait TrashRepository: Send + Sync {
+    /// Saves a TrashedTodo, replacing the one with the same todo id
+    async fn save(&self, trashed: &TrashedTodo) -> Result<(), TodoError>;
+
+    /// Finds the TrashedTodo of the todo `id`
+    ///
+    /// # Returns
+    /// - `Ok(Option<TrashedTodo>)`: `Some(TrashedTodo)` if found, `None` if not found
+    /// - `Err(TodoError)`: If retrieval fails
+    async fn find(&self, id: &str) -> Result<Option<TrashedTodo>, TodoError>;
+
+    /// Finds every TrashedTodo, in the order they were deleted
+    async fn find_all(&self) -> Result<Vec<TrashedTodo>, TodoError>;
+
+    /// Removes the TrashedTodo of the todo `id` for good
+    ///
+    /// # Returns
+    /// - `Ok(())`: Successfully removed
+    /// - `Err(TodoError::TodoNotFound)`: If the todo is not in the trash
+    /// - `Err(TodoError)`: If removal fails
+    async fn remove(&self, id: &str) -> Result<(), TodoError>;
+}
+
+/// Shares a single trash between the handlers deleting, restoring and purging todos
+#[async_trait]
+impl<T: TrashRepository + ?Sized> TrashRepository for Arc<T> {
+    async fn save(&self, trashed: &TrashedTodo) -> Result<(), TodoError> {
+        (**self).save(trashed).await
+    }
+
+    async fn find(&self, id: &str) -> Result<Option<TrashedTodo>, TodoError> {
+        (**self).find(id).await
+    }
+
+    async fn find_all(&self) -> Result<Vec<TrashedTodo>, TodoError> {
+        (**self).find_all().await
+    }
+
+    async fn remove(&self, id: &str) -> Result<(), TodoError> {
+        (**self).remove(id).await
+    }
+}
+
+/// Lets owners hold a `Box<dyn TrashRepository>`
+#[async_trait]
+impl<T: TrashRepository + ?Sized> TrashRepository for Box<T> {
+    async fn save(&self, trashed: &TrashedTodo) -> Result<(), TodoError> {
+        (**self).save(trashed).await
+    }
+
+    async fn find(&self, id: &str) -> Result<Option<TrashedTodo>, TodoError> {
+        (**self).find(id).await
+    }
+
+    async fn find_all(&self) -> Result<Vec<TrashedTodo>, TodoError> {
+        (**self).find_all().await
+    }
+
+    async fn remove(&self, id: &str) -> Result<(), TodoError> {
+        (**self).remove(id).await
+    }
+}
@@ -0,0 +1,23 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::todo::Todo;
+
+/// A deleted todo, kept until it is restored or the trash is emptied
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrashedTodo {
+    /// The todo as it was when deleted
+    pub todo: Todo,
+    pub deleted_at: DateTime<Utc>,
+}
+
+impl TrashedTodo {
+    /// Creates a TrashedTodo for `todo`, deleted at `deleted_at`
+    pub fn new(todo: Todo, deleted_at: DateTime<Utc>) -> Self {
+        TrashedTodo { todo, deleted_at }
+    }
+
+    /// Id of the deleted todo
+    pub fn id(&self) -> &str {
+        &self.todo.id
+    }
+}
@@ -0,0 +1,72 @@
+use async_trait::async_trait;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use crate::TodoError;
+use crate::domain::blob::{BlobStore, content_hash, is_content_hash};
+
+/// BlobStore keeping each content in a file of a directory
+///
+/// Content with hash `ab12…` is stored at `<dir>/ab/ab12…`, written to a temporary file
+/// first and renamed into place, so readers never see part of a blob. The directory is
+/// created on the first `put`.
+pub struct FileBlobStore {
+    dir: PathBuf,
+}
+
+impl FileBlobStore {
+    /// Creates a new FileBlobStore keeping blobs under `dir`
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        FileBlobStore { dir: dir.into() }
+    }
+
+    /// Path of the blob with `hash`, once `hash` is checked to be a content hash
+    fn path(&self, hash: &str) -> Result<PathBuf, TodoError> {
+        if !is_content_hash(hash) {
+            return Err(TodoError::RepositoryError(format!(
+                "invalid content hash {:?}",
+                hash
+            )));
+        }
+        Ok(self.dir.join(&hash[..2]).join(hash))
+    }
+}
+
+fn io(err: std::io::Error) -> TodoError {
+    TodoError::RepositoryError(err.to_string())
+}
+
+#[async_trait]
+impl BlobStore for FileBlobStore {
+    async fn put(&self, content: &[u8]) -> Result<String, TodoError> {
+        let hash = content_hash(content);
+        let path = self.path(&hash)?;
+        if path.exists() {
+            return Ok(hash);
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(io)?;
+        }
+        let mut tmp_path = path.clone().into_os_string();
+        tmp_path.push(".tmp");
+        fs::write(&tmp_path, content).map_err(io)?;
+        fs::rename(&tmp_path, &path).map_err(io)?;
+        Ok(hash)
+    }
+
+    async fn get(&self, hash: &str) -> Result<Option<Vec<u8>>, TodoError> {
+        match fs::read(self.path(hash)?) {
+            Ok(content) => Ok(Some(content)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(io(err)),
+        }
+    }
+
+    async fn delete(&self, hash: &str) -> Result<(), TodoError> {
+        match fs::remove_file(self.path(hash)?) {
+            Err(err) if err.kind() != ErrorKind::NotFound => Err(io(err)),
+            _ => Ok(()),
+        }
+    }
+}
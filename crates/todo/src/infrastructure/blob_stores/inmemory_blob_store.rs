@@ -0,0 +1,57 @@
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crate::TodoError;
+use crate::domain::blob::{BlobStore, content_hash, is_content_hash};
+
+/// In-memory implementation of BlobStore, for tests and short-lived front ends
+#[derive(Default)]
+pub struct InMemoryBlobStore {
+    blobs: RwLock<HashMap<String, Vec<u8>>>,
+}
+
+impl InMemoryBlobStore {
+    /// Creates a new, empty InMemoryBlobStore
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+fn poisoned<T>(_: T) -> TodoError {
+    TodoError::RepositoryError("blob store lock poisoned".to_string())
+}
+
+#[async_trait]
+impl BlobStore for InMemoryBlobStore {
+    async fn put(&self, content: &[u8]) -> Result<String, TodoError> {
+        let hash = content_hash(content);
+        self.blobs
+            .write()
+            .map_err(poisoned)?
+            .entry(hash.clone())
+            .or_insert_with(|| content.to_vec());
+        Ok(hash)
+    }
+
+    async fn get(&self, hash: &str) -> Result<Option<Vec<u8>>, TodoError> {
+        check(hash)?;
+        Ok(self.blobs.read().map_err(poisoned)?.get(hash).cloned())
+    }
+
+    async fn delete(&self, hash: &str) -> Result<(), TodoError> {
+        check(hash)?;
+        self.blobs.write().map_err(poisoned)?.remove(hash);
+        Ok(())
+    }
+}
+
+fn check(hash: &str) -> Result<(), TodoError> {
+    if is_content_hash(hash) {
+        return Ok(());
+    }
+    Err(TodoError::RepositoryError(format!(
+        "invalid content hash {:?}",
+        hash
+    )))
+}
@@ -0,0 +1,9 @@
+mod file_blob_store;
+mod inmemory_blob_store;
+#[cfg(feature = "s3")]
+mod s3_blob_store;
+
+pub use file_blob_store::FileBlobStore;
+pub use inmemory_blob_store::InMemoryBlobStore;
+#[cfg(feature = "s3")]
+pub use s3_blob_store::S3BlobStore;
@@ -0,0 +1,237 @@
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use reqwest::{Method, StatusCode};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::TodoError;
+use crate::domain::blob::{BlobStore, content_hash, is_content_hash};
+use crate::{Clock, SystemClock};
+
+/// How long a request to S3 may take
+const S3_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// BlobStore keeping each content as an object of an Amazon S3 bucket, or of a service
+/// with the same API, signing requests with AWS Signature Version 4
+///
+/// Content with hash `ab12…` is the object `<prefix>ab12…` of the bucket, addressed in the
+/// path style `<endpoint>/<bucket>/<key>`.
+pub struct S3BlobStore {
+    client: reqwest::Client,
+    endpoint: String,
+    region: String,
+    bucket: String,
+    prefix: String,
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+    clock: Arc<dyn Clock>,
+}
+
+impl S3BlobStore {
+    /// Creates a new S3BlobStore over `bucket` of `region`, with an access key
+    pub fn new(
+        region: impl Into<String>,
+        bucket: impl Into<String>,
+        access_key_id: impl Into<String>,
+        secret_access_key: impl Into<String>,
+    ) -> Self {
+        let region = region.into();
+        S3BlobStore {
+            client: reqwest::Client::new(),
+            endpoint: format!("https://s3.{}.amazonaws.com", region),
+            region,
+            bucket: bucket.into(),
+            prefix: String::new(),
+            access_key_id: access_key_id.into(),
+            secret_access_key: secret_access_key.into(),
+            session_token: None,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Creates a new S3BlobStore over `bucket` from `AWS_REGION` (or `AWS_DEFAULT_REGION`),
+    /// `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and the optional `AWS_SESSION_TOKEN`
+    ///
+    /// # Returns
+    /// - `Err(String)`: Naming the first variable that is not set
+    pub fn from_env(bucket: impl Into<String>) -> Result<Self, String> {
+        let var = |name: &str| std::env::var(name).map_err(|_| format!("{} is not set", name));
+        let region = var("AWS_REGION").or_else(|_| var("AWS_DEFAULT_REGION"))?;
+        let store = Self::new(
+            region,
+            bucket,
+            var("AWS_ACCESS_KEY_ID")?,
+            var("AWS_SECRET_ACCESS_KEY")?,
+        );
+        Ok(match std::env::var("AWS_SESSION_TOKEN") {
+            Ok(token) => store.with_session_token(token),
+            Err(_) => store,
+        })
+    }
+
+    /// Sends the temporary credentials' session token along
+    pub fn with_session_token(mut self, token: impl Into<String>) -> Self {
+        self.session_token = Some(token.into());
+        self
+    }
+
+    /// Sends to `endpoint`, such as a VPC endpoint or another S3-compatible service,
+    /// instead of the region's public one
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = endpoint.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Keys objects `<prefix><hash>` instead of `<hash>`, such as `attachments/`
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Signs requests with the time of `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Sends a signed `method` request for the object of `hash` with `body`
+    async fn send(
+        &self,
+        method: Method,
+        hash: &str,
+        body: Vec<u8>,
+    ) -> Result<reqwest::Response, TodoError> {
+        if !is_content_hash(hash) {
+            return Err(failed(format!("invalid content hash {:?}", hash)));
+        }
+        let path = format!("/{}/{}{}", self.bucket, self.prefix, hash);
+        let url = reqwest::Url::parse(&format!("{}{}", self.endpoint, path))
+            .map_err(|err| failed(format!("invalid endpoint {}: {}", self.endpoint, err)))?;
+        let host = match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            (None, _) => return Err(failed(format!("endpoint {} has no host", self.endpoint))),
+        };
+        let amz_date = self.clock.now().format("%Y%m%dT%H%M%SZ").to_string();
+        let content_sha256 = hex(&Sha256::digest(&body));
+        let mut headers = vec![
+            ("host", host.as_str()),
+            ("x-amz-content-sha256", content_sha256.as_str()),
+            ("x-amz-date", amz_date.as_str()),
+        ];
+        if let Some(token) = &self.session_token {
+            headers.push(("x-amz-security-token", token.as_str()));
+        }
+        let authorization = self.authorization(&method, url.path(), &headers, &amz_date);
+        let mut request = self
+            .client
+            .request(method, url.clone())
+            .timeout(S3_TIMEOUT)
+            .header("authorization", authorization)
+            .body(body);
+        for (name, value) in headers.iter().filter(|(name, _)| *name != "host") {
+            request = request.header(*name, *value);
+        }
+        request.send().await.map_err(failed)
+    }
+
+    /// The `Authorization` header of a `method` request of `path` with the given signed
+    /// headers, which must be sorted by name and include `x-amz-content-sha256`
+    fn authorization(
+        &self,
+        method: &Method,
+        path: &str,
+        headers: &[(&str, &str)],
+        amz_date: &str,
+    ) -> String {
+        let signed_headers = headers
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>()
+            .join(";");
+        let canonical_headers: String = headers
+            .iter()
+            .map(|(name, value)| format!("{}:{}\n", name, value.trim()))
+            .collect();
+        let content_sha256 = headers
+            .iter()
+            .find(|(name, _)| *name == "x-amz-content-sha256")
+            .map_or("", |(_, value)| *value);
+        let canonical_request = format!(
+            "{}\n{}\n\n{}\n{}\n{}",
+            method, path, canonical_headers, signed_headers, content_sha256
+        );
+        let date = &amz_date[..8];
+        let scope = format!("{}/{}/s3/aws4_request", date, self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex(&Sha256::digest(canonical_request.as_bytes()))
+        );
+        let key = [self.region.as_str(), "s3", "aws4_request"].iter().fold(
+            hmac(format!("AWS4{}", self.secret_access_key).as_bytes(), date),
+            |key, part| hmac(&key, part),
+        );
+        format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.access_key_id,
+            scope,
+            signed_headers,
+            hex(&hmac(&key, &string_to_sign))
+        )
+    }
+}
+
+#[async_trait]
+impl BlobStore for S3BlobStore {
+    async fn put(&self, content: &[u8]) -> Result<String, TodoError> {
+        let hash = content_hash(content);
+        let response = self.send(Method::PUT, &hash, content.to_vec()).await?;
+        check(response).await?;
+        Ok(hash)
+    }
+
+    async fn get(&self, hash: &str) -> Result<Option<Vec<u8>>, TodoError> {
+        let response = self.send(Method::GET, hash, Vec::new()).await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let content = check(response).await?.bytes().await.map_err(failed)?;
+        Ok(Some(content.to_vec()))
+    }
+
+    async fn delete(&self, hash: &str) -> Result<(), TodoError> {
+        let response = self.send(Method::DELETE, hash, Vec::new()).await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(());
+        }
+        check(response).await.map(|_| ())
+    }
+}
+
+/// Passes successful responses through, and turns the others into errors with their body
+async fn check(response: reqwest::Response) -> Result<reqwest::Response, TodoError> {
+    if response.status().is_success() {
+        return Ok(response);
+    }
+    let status = response.status();
+    let detail = response.text().await.unwrap_or_default();
+    Err(failed(format!("S3 answered {}: {}", status, detail)))
+}
+
+fn hmac(key: &[u8], data: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn failed(message: impl ToString) -> TodoError {
+    TodoError::RepositoryError(message.to_string())
+}
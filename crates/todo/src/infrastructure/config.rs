@@ -0,0 +1,423 @@
+use crate::TenantId;
+use crate::application::archive::ArchivePolicy;
+use crate::application::quota::{Quota, QuotaPolicy};
+use crate::application::retention::{RetentionPolicy, RetentionRules};
+use crate::infrastructure::event_sinks::EventLog;
+use crate::infrastructure::id_format::IdFormat;
+use crate::infrastructure::repositories::todo::TodoBackend;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Events a front end keeps for replay when `event_retention` is not set
+pub const DEFAULT_EVENT_RETENTION: usize = 1024;
+
+/// Settings shared by the front ends, read from an optional TOML file and the environment
+///
+/// Each setting is looked up in order, the first one found wins:
+/// 1. its environment variable
+/// 2. its key in the file named by `TODO_CONFIG`
+/// 3. its default
+///
+/// | Key | Variable | Default |
+/// |---|---|---|
+/// | `backend` | `TODO_BACKEND` | chosen by the front end |
+/// | `id_format` | `TODO_ID_FORMAT` | `uuid4` |
+/// | `event_log` | `TODO_EVENT_LOG` | chosen by the front end |
+/// | `event_retention` | `TODO_EVENT_RETENTION` | 1024 |
+/// | `metrics` | `TODO_METRICS` | `true` |
+/// | `auth_issuer` | `TODO_AUTH_ISSUER` | unset, no authentication |
+/// | `auth_audience` | `TODO_AUTH_AUDIENCE` | unset |
+/// | `api_key_file` | `TODO_API_KEY_FILE` | unset, no API keys |
+/// | `roles_file` | `TODO_ROLES_FILE` | unset, no roles |
+/// | `trash_file` | `TODO_TRASH_FILE` | unset, deletions are permanent |
+/// | `retention.schedule` | `TODO_RETENTION_SCHEDULE` | unset, chosen by the front end |
+/// | `retention.done_todo_days` | `TODO_RETENTION_DONE_DAYS` | unset, done todos are kept |
+/// | `retention.event_months` | `TODO_RETENTION_EVENT_MONTHS` | unset, events are kept |
+/// | `retention.trash_days` | `TODO_RETENTION_TRASH_DAYS` | unset, the trash is kept |
+/// | `archive_file` | `TODO_ARCHIVE_FILE` | unset |
+/// | `archive.schedule` | `TODO_ARCHIVE_SCHEDULE` | unset, chosen by the front end |
+/// | `archive.done_days` | `TODO_ARCHIVE_DONE_DAYS` | unset, done todos stay in their list |
+/// | `escalation_rules_file` | `TODO_ESCALATION_RULES_FILE` | unset, no escalation |
+/// | `escalation.schedule` | `TODO_ESCALATION_SCHEDULE` | unset, chosen by the front end |
+/// | `quota.max_open_todos` | `TODO_QUOTA_MAX_OPEN_TODOS` | unset, no limit |
+/// | `quota.max_todos` | `TODO_QUOTA_MAX_TODOS` | unset, no limit |
+///
+/// `backend`, `id_format` and `event_log` take the values of `TodoBackend::parse`,
+/// `IdFormat::parse` and `EventLog::parse`. `auth_issuer` and `auth_audience` are set together.
+/// Tenants override the retention rules in `[retention.tenants.<tenant>]` tables of the file,
+/// with their own `done_todo_days`, `event_months` and `trash_days`. Lists override
+/// `archive.done_days` in `[archive.lists.<list>]` tables, where leaving `done_days` out turns
+/// archiving off for the list. Archiving needs `archive_file`. Lists replace the quota in
+/// `[quota.lists.<list>]` tables, where a limit left out is no limit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TodoConfig {
+    /// Where todos are stored; `None` leaves the choice to the front end
+    pub backend: Option<TodoBackend>,
+    /// Ids given to new todos
+    pub id_format: IdFormat,
+    /// Where the domain event log is written; `None` leaves the choice to the front end
+    pub event_log: Option<EventLog>,
+    /// Events kept for subscribers resuming from a cursor, at least 1
+    pub event_retention: usize,
+    /// Whether metrics are exposed
+    pub metrics: bool,
+    /// OpenID Provider whose bearer tokens the servers require; `None` leaves them open
+    pub auth_issuer: Option<String>,
+    /// Audience the tokens must be issued to, set whenever `auth_issuer` is
+    pub auth_audience: Option<String>,
+    /// JSON file of the API keys the servers accept, managed with `hk-todo api-key`
+    pub api_key_file: Option<PathBuf>,
+    /// JSON file of the roles callers have on todo lists, managed with `hk-todo role`
+    pub roles_file: Option<PathBuf>,
+    /// JSON file deleted todos are moved to, to be restored or emptied by retention runs
+    pub trash_file: Option<PathBuf>,
+    /// Cron expression of the retention runs; `None` leaves the choice to the front end
+    pub retention_schedule: Option<String>,
+    /// What retention runs remove
+    pub retention: RetentionPolicy,
+    /// JSON file done todos are archived to
+    pub archive_file: Option<PathBuf>,
+    /// Cron expression of the archive runs; `None` leaves the choice to the front end
+    pub archive_schedule: Option<String>,
+    /// When archive runs archive done todos
+    pub archive: ArchivePolicy,
+    /// JSON file of the escalation rules, managed with `hk-todo escalation`
+    pub escalation_rules_file: Option<PathBuf>,
+    /// Cron expression of the escalation runs; `None` leaves the choice to the front end
+    pub escalation_schedule: Option<String>,
+    /// How many todos each list may hold
+    pub quota: QuotaPolicy,
+}
+
+impl Default for TodoConfig {
+    fn default() -> Self {
+        TodoConfig {
+            backend: None,
+            id_format: IdFormat::default(),
+            event_log: None,
+            event_retention: DEFAULT_EVENT_RETENTION,
+            metrics: true,
+            auth_issuer: None,
+            auth_audience: None,
+            api_key_file: None,
+            roles_file: None,
+            trash_file: None,
+            retention_schedule: None,
+            retention: RetentionPolicy::default(),
+            archive_file: None,
+            archive_schedule: None,
+            archive: ArchivePolicy::default(),
+            escalation_rules_file: None,
+            escalation_schedule: None,
+            quota: QuotaPolicy::default(),
+        }
+    }
+}
+
+/// Keys of the TOML file, all optional
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    backend: Option<String>,
+    id_format: Option<String>,
+    event_log: Option<String>,
+    event_retention: Option<usize>,
+    metrics: Option<bool>,
+    auth_issuer: Option<String>,
+    auth_audience: Option<String>,
+    api_key_file: Option<PathBuf>,
+    roles_file: Option<PathBuf>,
+    trash_file: Option<PathBuf>,
+    retention: Option<RetentionFile>,
+    archive_file: Option<PathBuf>,
+    archive: Option<ArchiveFile>,
+    escalation_rules_file: Option<PathBuf>,
+    escalation: Option<EscalationFile>,
+    quota: Option<QuotaFile>,
+}
+
+/// The `[retention]` table of the TOML file
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RetentionFile {
+    schedule: Option<String>,
+    #[serde(flatten)]
+    rules: RulesFile,
+    #[serde(default)]
+    tenants: HashMap<String, RulesFile>,
+}
+
+#[derive(Deserialize)]
+struct RulesFile {
+    done_todo_days: Option<u32>,
+    event_months: Option<u32>,
+    trash_days: Option<u32>,
+}
+
+impl From<RulesFile> for RetentionRules {
+    fn from(rules: RulesFile) -> Self {
+        RetentionRules {
+            done_todo_days: rules.done_todo_days,
+            event_months: rules.event_months,
+            trash_days: rules.trash_days,
+        }
+    }
+}
+
+/// The `[archive]` table of the TOML file
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ArchiveFile {
+    schedule: Option<String>,
+    done_days: Option<u32>,
+    #[serde(default)]
+    lists: HashMap<String, ListArchiveFile>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ListArchiveFile {
+    done_days: Option<u32>,
+}
+
+/// The `[escalation]` table of the TOML file
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EscalationFile {
+    schedule: Option<String>,
+}
+
+/// The `[quota]` table of the TOML file
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct QuotaFile {
+    #[serde(flatten)]
+    limits: LimitsFile,
+    #[serde(default)]
+    lists: HashMap<String, LimitsFile>,
+}
+
+#[derive(Deserialize)]
+struct LimitsFile {
+    max_open_todos: Option<u32>,
+    max_todos: Option<u32>,
+}
+
+impl From<LimitsFile> for Quota {
+    fn from(limits: LimitsFile) -> Self {
+        Quota {
+            max_open_todos: limits.max_open_todos,
+            max_todos: limits.max_todos,
+        }
+    }
+}
+
+impl TodoConfig {
+    /// Reads the file named by `TODO_CONFIG`, if set, then the environment variables
+    ///
+    /// # Returns
+    /// - `Ok(TodoConfig)`: The merged settings
+    /// - `Err(String)`: If the file cannot be read, or a setting is unknown or invalid
+    pub fn load() -> Result<Self, String> {
+        let config = match std::env::var_os("TODO_CONFIG") {
+            Some(path) => Self::from_file(Path::new(&path))?,
+            None => TodoConfig::default(),
+        };
+        config.with_env(|name| std::env::var(name).ok())
+    }
+
+    /// Reads the settings of a TOML file, defaulting the keys it does not set
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|err| format!("cannot read config {}: {}", path.display(), err))?;
+        Self::from_toml(&text).map_err(|err| format!("invalid config {}: {}", path.display(), err))
+    }
+
+    /// Reads the settings of a TOML document, defaulting the keys it does not set
+    pub fn from_toml(text: &str) -> Result<Self, String> {
+        let file: ConfigFile = toml::from_str(text).map_err(|err| err.message().to_string())?;
+        let mut config = TodoConfig::default();
+        if let Some(backend) = file.backend {
+            config.backend = Some(TodoBackend::parse(&backend)?);
+        }
+        if let Some(id_format) = file.id_format {
+            config.id_format = IdFormat::parse(&id_format)?;
+        }
+        if let Some(event_log) = file.event_log {
+            config.event_log = Some(EventLog::parse(&event_log)?);
+        }
+        if let Some(event_retention) = file.event_retention {
+            config.event_retention = event_retention;
+        }
+        if let Some(metrics) = file.metrics {
+            config.metrics = metrics;
+        }
+        if file.auth_issuer.is_some() {
+            config.auth_issuer = file.auth_issuer;
+        }
+        if file.auth_audience.is_some() {
+            config.auth_audience = file.auth_audience;
+        }
+        if file.api_key_file.is_some() {
+            config.api_key_file = file.api_key_file;
+        }
+        if file.roles_file.is_some() {
+            config.roles_file = file.roles_file;
+        }
+        if file.trash_file.is_some() {
+            config.trash_file = file.trash_file;
+        }
+        if let Some(retention) = file.retention {
+            config.retention_schedule = retention.schedule;
+            config.retention.default = retention.rules.into();
+            for (tenant, rules) in retention.tenants {
+                config
+                    .retention
+                    .tenants
+                    .insert(TenantId::new(tenant)?, rules.into());
+            }
+        }
+        if file.archive_file.is_some() {
+            config.archive_file = file.archive_file;
+        }
+        if let Some(archive) = file.archive {
+            config.archive_schedule = archive.schedule;
+            config.archive.done_days = archive.done_days;
+            for (list, settings) in archive.lists {
+                config
+                    .archive
+                    .lists
+                    .insert(TenantId::new(list)?, settings.done_days);
+            }
+        }
+        if file.escalation_rules_file.is_some() {
+            config.escalation_rules_file = file.escalation_rules_file;
+        }
+        if let Some(escalation) = file.escalation {
+            config.escalation_schedule = escalation.schedule;
+        }
+        if let Some(quota) = file.quota {
+            config.quota.default = quota.limits.into();
+            for (list, limits) in quota.lists {
+                config
+                    .quota
+                    .lists
+                    .insert(TenantId::new(list)?, limits.into());
+            }
+        }
+        config.validate()
+    }
+
+    /// Overrides settings with the variables `var` returns, such as `std::env::var`
+    pub fn with_env(mut self, var: impl Fn(&str) -> Option<String>) -> Result<Self, String> {
+        if let Some(backend) = var("TODO_BACKEND") {
+            self.backend = Some(TodoBackend::parse(&backend)?);
+        }
+        if let Some(id_format) = var("TODO_ID_FORMAT") {
+            self.id_format = IdFormat::parse(&id_format)?;
+        }
+        if let Some(event_log) = var("TODO_EVENT_LOG") {
+            self.event_log = Some(EventLog::parse(&event_log)?);
+        }
+        if let Some(event_retention) = var("TODO_EVENT_RETENTION") {
+            self.event_retention = event_retention.parse().map_err(|err| {
+                format!(
+                    "invalid TODO_EVENT_RETENTION {:?}: {}",
+                    event_retention, err
+                )
+            })?;
+        }
+        if let Some(metrics) = var("TODO_METRICS") {
+            self.metrics = match metrics.as_str() {
+                "true" | "1" => true,
+                "false" | "0" => false,
+                _ => {
+                    return Err(format!(
+                        "invalid TODO_METRICS {:?}, expected true or false",
+                        metrics
+                    ));
+                }
+            };
+        }
+        if let Some(issuer) = var("TODO_AUTH_ISSUER") {
+            self.auth_issuer = Some(issuer);
+        }
+        if let Some(audience) = var("TODO_AUTH_AUDIENCE") {
+            self.auth_audience = Some(audience);
+        }
+        if let Some(path) = var("TODO_API_KEY_FILE") {
+            self.api_key_file = Some(path.into());
+        }
+        if let Some(path) = var("TODO_ROLES_FILE") {
+            self.roles_file = Some(path.into());
+        }
+        if let Some(path) = var("TODO_TRASH_FILE") {
+            self.trash_file = Some(path.into());
+        }
+        if let Some(schedule) = var("TODO_RETENTION_SCHEDULE") {
+            self.retention_schedule = Some(schedule);
+        }
+        if let Some(days) = var("TODO_RETENTION_DONE_DAYS") {
+            self.retention.default.done_todo_days =
+                Some(days.parse().map_err(|err| {
+                    format!("invalid TODO_RETENTION_DONE_DAYS {:?}: {}", days, err)
+                })?);
+        }
+        if let Some(months) = var("TODO_RETENTION_EVENT_MONTHS") {
+            self.retention.default.event_months = Some(months.parse().map_err(|err| {
+                format!("invalid TODO_RETENTION_EVENT_MONTHS {:?}: {}", months, err)
+            })?);
+        }
+        if let Some(days) = var("TODO_RETENTION_TRASH_DAYS") {
+            self.retention.default.trash_days =
+                Some(days.parse().map_err(|err| {
+                    format!("invalid TODO_RETENTION_TRASH_DAYS {:?}: {}", days, err)
+                })?);
+        }
+        if let Some(path) = var("TODO_ARCHIVE_FILE") {
+            self.archive_file = Some(path.into());
+        }
+        if let Some(schedule) = var("TODO_ARCHIVE_SCHEDULE") {
+            self.archive_schedule = Some(schedule);
+        }
+        if let Some(days) = var("TODO_ARCHIVE_DONE_DAYS") {
+            self.archive.done_days =
+                Some(days.parse().map_err(|err| {
+                    format!("invalid TODO_ARCHIVE_DONE_DAYS {:?}: {}", days, err)
+                })?);
+        }
+        if let Some(path) = var("TODO_ESCALATION_RULES_FILE") {
+            self.escalation_rules_file = Some(path.into());
+        }
+        if let Some(schedule) = var("TODO_ESCALATION_SCHEDULE") {
+            self.escalation_schedule = Some(schedule);
+        }
+        if let Some(max) = var("TODO_QUOTA_MAX_OPEN_TODOS") {
+            self.quota.default.max_open_todos =
+                Some(max.parse().map_err(|err| {
+                    format!("invalid TODO_QUOTA_MAX_OPEN_TODOS {:?}: {}", max, err)
+                })?);
+        }
+        if let Some(max) = var("TODO_QUOTA_MAX_TODOS") {
+            self.quota.default.max_todos = Some(
+                max.parse()
+                    .map_err(|err| format!("invalid TODO_QUOTA_MAX_TODOS {:?}: {}", max, err))?,
+            );
+        }
+        self.validate()
+    }
+
+    fn validate(self) -> Result<Self, String> {
+        if self.event_retention == 0 {
+            return Err("event_retention must be at least 1".to_string());
+        }
+        if self.auth_issuer.is_some() != self.auth_audience.is_some() {
+            return Err("auth_issuer and auth_audience must be set together".to_string());
+        }
+        if self.archive.is_enabled() && self.archive_file.is_none() {
+            return Err("archive.done_days needs archive_file".to_string());
+        }
+        Ok(self)
+    }
+}
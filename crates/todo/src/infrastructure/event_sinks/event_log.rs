@@ -0,0 +1,47 @@
+use super::JsonEventSink;
+use crate::domain::todo::EventSink;
+use std::fs::OpenOptions;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Selects where a front end writes its domain event log
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EventLog {
+    /// `stdout`: one JSON line per event on standard output
+    Stdout,
+    /// `file:<path>`: one JSON line per event, appended to the file
+    File(PathBuf),
+    /// `off`: events are not recorded
+    Off,
+}
+
+impl EventLog {
+    /// Parses `stdout`, `file:<path>` or `off`
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.split_once(':') {
+            None if value == "stdout" => Ok(EventLog::Stdout),
+            None if value == "off" => Ok(EventLog::Off),
+            Some(("file", path)) if !path.is_empty() => Ok(EventLog::File(PathBuf::from(path))),
+            _ => Err(format!(
+                "unknown event log {:?}, expected stdout, file:<path> or off",
+                value
+            )),
+        }
+    }
+
+    /// Creates the selected sink, or `None` for `off`
+    pub fn sink(&self) -> Result<Option<Arc<dyn EventSink>>, String> {
+        match self {
+            EventLog::Stdout => Ok(Some(Arc::new(JsonEventSink::new(std::io::stdout())))),
+            EventLog::File(path) => {
+                let file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .map_err(|err| format!("cannot open event log {}: {}", path.display(), err))?;
+                Ok(Some(Arc::new(JsonEventSink::new(file))))
+            }
+            EventLog::Off => Ok(None),
+        }
+    }
+}
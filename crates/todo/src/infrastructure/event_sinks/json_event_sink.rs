@@ -0,0 +1,40 @@
+use crate::domain::todo::{EventSink, TodoError, TodoEvent};
+use crate::infrastructure::serialization::SerializationError;
+use std::io::Write;
+use std::sync::Mutex;
+
+/// EventSink writing each event as one line of JSON
+///
+/// Events use their serde shape, tagged with `type`:
+/// `{"type":"TODO_STATE_CHANGED","id":"…","from_state":"TODO","to_state":"IN_PROGRESS","changed_at":"…"}`.
+/// The writer is flushed after every change, so a file or pipe holds each committed change
+/// as soon as `record` returns.
+pub struct JsonEventSink<W> {
+    writer: Mutex<W>,
+}
+
+impl<W: Write + Send> JsonEventSink<W> {
+    /// Creates a new JsonEventSink appending to `writer`
+    pub fn new(writer: W) -> Self {
+        JsonEventSink {
+            writer: Mutex::new(writer),
+        }
+    }
+}
+
+impl<W: Write + Send> EventSink for JsonEventSink<W> {
+    fn record(&self, events: &[TodoEvent]) -> Result<(), TodoError> {
+        let mut writer = self
+            .writer
+            .lock()
+            .map_err(|_| TodoError::RepositoryError("event sink lock poisoned".to_string()))?;
+        let io = |err: std::io::Error| SerializationError::Io(err.to_string());
+        for event in events {
+            serde_json::to_writer(&mut *writer, event)
+                .map_err(|err| SerializationError::Io(err.to_string()))?;
+            writer.write_all(b"\n").map_err(io)?;
+        }
+        writer.flush().map_err(io)?;
+        Ok(())
+    }
+}
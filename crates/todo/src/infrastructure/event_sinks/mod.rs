@@ -0,0 +1,5 @@
+mod event_log;
+mod json_event_sink;
+
+pub use event_log::EventLog;
+pub use json_event_sink::JsonEventSink;
@@ -0,0 +1,118 @@
+use crate::domain::todo::{EventSink, EventStore, TodoError, TodoEvent};
+use crate::infrastructure::event_sinks::JsonEventSink;
+use crate::infrastructure::serialization::SerializationError;
+use chrono::{DateTime, Utc};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// EventStore appending events to a JSON lines file
+///
+/// The file has the format `JsonEventSink` writes, one tagged event per line, so a store
+/// can read the log a front end started with `TODO_EVENT_LOG=file:<path>`. The file is
+/// read on every `find_by_todo` and `find_all`, and `compact` and `remove_todo` rewrite it
+/// through a temporary file that is renamed over the original; a missing file holds no
+/// events.
+///
+/// Calls are serialized within a process; separate processes appending to the file are
+/// not coordinated.
+pub struct FileEventStore {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl FileEventStore {
+    /// Creates a new FileEventStore backed by the file at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileEventStore {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Path of the backing file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Every event of the file, in the order they were recorded
+    fn read(&self) -> Result<Vec<TodoEvent>, TodoError> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(SerializationError::Io(err.to_string()).into()),
+        };
+        let mut events = Vec::new();
+        for (index, line) in BufReader::new(file).lines().enumerate() {
+            let line = line.map_err(|err| SerializationError::Io(err.to_string()))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let event =
+                serde_json::from_str(&line).map_err(|err| SerializationError::InvalidRecord {
+                    record: index + 1,
+                    message: err.to_string(),
+                })?;
+            events.push(event);
+        }
+        Ok(events)
+    }
+
+    /// Rewrites the file without the events `removed` selects, returning how many there were
+    fn remove(&self, removed: impl Fn(&TodoEvent) -> bool) -> Result<usize, TodoError> {
+        let _guard = self.lock()?;
+        let mut events = self.read()?;
+        let count = events.len();
+        events.retain(|event| !removed(event));
+        if events.len() == count {
+            return Ok(0);
+        }
+        let io = |err: std::io::Error| SerializationError::Io(err.to_string());
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(".tmp");
+        JsonEventSink::new(File::create(&tmp_path).map_err(io)?).record(&events)?;
+        fs::rename(&tmp_path, &self.path).map_err(io)?;
+        Ok(count - events.len())
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, ()>, TodoError> {
+        self.lock
+            .lock()
+            .map_err(|_| TodoError::RepositoryError("event store lock poisoned".to_string()))
+    }
+}
+
+impl EventSink for FileEventStore {
+    fn record(&self, events: &[TodoEvent]) -> Result<(), TodoError> {
+        let _guard = self.lock()?;
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|err| SerializationError::Io(err.to_string()))?;
+        JsonEventSink::new(file).record(events)
+    }
+}
+
+impl EventStore for FileEventStore {
+    fn find_by_todo(&self, id: &str) -> Result<Vec<TodoEvent>, TodoError> {
+        let _guard = self.lock()?;
+        let mut events = self.read()?;
+        events.retain(|event| event.todo_id() == id);
+        Ok(events)
+    }
+
+    fn find_all(&self) -> Result<Vec<TodoEvent>, TodoError> {
+        let _guard = self.lock()?;
+        self.read()
+    }
+
+    fn compact(&self, before: DateTime<Utc>) -> Result<usize, TodoError> {
+        self.remove(|event| event.occurred_at() < before)
+    }
+
+    fn remove_todo(&self, id: &str) -> Result<usize, TodoError> {
+        self.remove(|event| event.todo_id() == id)
+    }
+}
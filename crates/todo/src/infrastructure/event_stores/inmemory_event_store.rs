@@ -0,0 +1,72 @@
+use crate::domain::todo::{EventSink, EventStore, TodoError, TodoEvent};
+use chrono::{DateTime, Utc};
+use std::sync::RwLock;
+
+/// In-memory implementation of EventStore
+///
+/// Keeps every recorded event in a list for the lifetime of the store, which suits tests
+/// and front ends that only need the history of the current process.
+#[derive(Default)]
+pub struct InMemoryEventStore {
+    events: RwLock<Vec<TodoEvent>>,
+}
+
+impl InMemoryEventStore {
+    /// Creates a new, empty InMemoryEventStore
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl InMemoryEventStore {
+    /// Removes the events `removed` selects, returning how many there were
+    fn remove(&self, removed: impl Fn(&TodoEvent) -> bool) -> Result<usize, TodoError> {
+        let mut events = self
+            .events
+            .write()
+            .map_err(|_| TodoError::RepositoryError("event store lock poisoned".to_string()))?;
+        let count = events.len();
+        events.retain(|event| !removed(event));
+        Ok(count - events.len())
+    }
+}
+
+impl EventSink for InMemoryEventStore {
+    fn record(&self, events: &[TodoEvent]) -> Result<(), TodoError> {
+        self.events
+            .write()
+            .map_err(|_| TodoError::RepositoryError("event store lock poisoned".to_string()))?
+            .extend_from_slice(events);
+        Ok(())
+    }
+}
+
+impl EventStore for InMemoryEventStore {
+    fn find_by_todo(&self, id: &str) -> Result<Vec<TodoEvent>, TodoError> {
+        let events = self
+            .events
+            .read()
+            .map_err(|_| TodoError::RepositoryError("event store lock poisoned".to_string()))?;
+        Ok(events
+            .iter()
+            .filter(|event| event.todo_id() == id)
+            .cloned()
+            .collect())
+    }
+
+    fn find_all(&self) -> Result<Vec<TodoEvent>, TodoError> {
+        let events = self
+            .events
+            .read()
+            .map_err(|_| TodoError::RepositoryError("event store lock poisoned".to_string()))?;
+        Ok(events.clone())
+    }
+
+    fn compact(&self, before: DateTime<Utc>) -> Result<usize, TodoError> {
+        self.remove(|event| event.occurred_at() < before)
+    }
+
+    fn remove_todo(&self, id: &str) -> Result<usize, TodoError> {
+        self.remove(|event| event.todo_id() == id)
+    }
+}
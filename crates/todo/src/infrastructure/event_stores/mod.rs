@@ -0,0 +1,9 @@
+#[cfg(feature = "serde")]
+mod file_event_store;
+mod inmemory_event_store;
+mod tenant_event_store;
+
+#[cfg(feature = "serde")]
+pub use file_event_store::FileEventStore;
+pub use inmemory_event_store::InMemoryEventStore;
+pub use tenant_event_store::TenantEventStore;
@@ -0,0 +1,74 @@
+use crate::domain::todo::{EventSink, EventStore, TenantId, TodoError, TodoEvent};
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+/// One tenant's view of an event sink or store shared by many tenants
+///
+/// Events are recorded in the inner sink with the ids of their todos under `<tenant>/<id>`,
+/// as a `TenantTodoRepository` stores the todos, and read back with the ids the tenant's
+/// handlers see. Histories read through a TenantEventStore therefore never include the
+/// events of another tenant's todos, even for an id that exists for another tenant.
+///
+/// `compact` removes old events of every tenant, as it does on the inner store.
+pub struct TenantEventStore<S: ?Sized = dyn EventStore> {
+    inner: Arc<S>,
+    tenant: TenantId,
+}
+
+impl<S: ?Sized> TenantEventStore<S> {
+    /// Creates a new TenantEventStore recording and reading `tenant`'s events in `inner`
+    pub fn new(inner: Arc<S>, tenant: TenantId) -> Self {
+        TenantEventStore { inner, tenant }
+    }
+
+    /// The tenant this view belongs to
+    pub fn tenant(&self) -> &TenantId {
+        &self.tenant
+    }
+
+    fn scoped(&self, event: &TodoEvent) -> TodoEvent {
+        event
+            .clone()
+            .map_todo_ids(|id| self.tenant.scope(id).into())
+    }
+
+    /// The event with the tenant prefix removed, or `None` if it is about another tenant's
+    /// todo
+    fn unscoped(&self, event: TodoEvent) -> Option<TodoEvent> {
+        self.tenant.unscope(event.todo_id())?;
+        Some(event.map_todo_ids(|id| self.tenant.unscope(id).unwrap_or(id).into()))
+    }
+}
+
+impl<S: EventSink + ?Sized> EventSink for TenantEventStore<S> {
+    fn record(&self, events: &[TodoEvent]) -> Result<(), TodoError> {
+        let events: Vec<TodoEvent> = events.iter().map(|event| self.scoped(event)).collect();
+        self.inner.record(&events)
+    }
+}
+
+impl<S: EventStore + ?Sized> EventStore for TenantEventStore<S> {
+    fn find_by_todo(&self, id: &str) -> Result<Vec<TodoEvent>, TodoError> {
+        let events = self.inner.find_by_todo(&self.tenant.scope(id))?;
+        Ok(events
+            .into_iter()
+            .filter_map(|event| self.unscoped(event))
+            .collect())
+    }
+
+    fn find_all(&self) -> Result<Vec<TodoEvent>, TodoError> {
+        let events = self.inner.find_all()?;
+        Ok(events
+            .into_iter()
+            .filter_map(|event| self.unscoped(event))
+            .collect())
+    }
+
+    fn compact(&self, before: DateTime<Utc>) -> Result<usize, TodoError> {
+        self.inner.compact(before)
+    }
+
+    fn remove_todo(&self, id: &str) -> Result<usize, TodoError> {
+        self.inner.remove_todo(&self.tenant.scope(id))
+    }
+}
@@ -0,0 +1,38 @@
+use crate::domain::todo::{IdGenerator, UlidGenerator, UuidV4Generator, UuidV7Generator};
+use std::sync::Arc;
+
+/// Selects the id generator a front end gives new todos
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IdFormat {
+    /// `uuid4`: random UUIDv4s
+    #[default]
+    UuidV4,
+    /// `uuid7`: time-sortable UUIDv7s
+    UuidV7,
+    /// `ulid`: time-sortable ULIDs
+    Ulid,
+}
+
+impl IdFormat {
+    /// Parses `uuid4`, `uuid7` or `ulid`
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "uuid4" => Ok(IdFormat::UuidV4),
+            "uuid7" => Ok(IdFormat::UuidV7),
+            "ulid" => Ok(IdFormat::Ulid),
+            _ => Err(format!(
+                "unknown id format {:?}, expected uuid4, uuid7 or ulid",
+                value
+            )),
+        }
+    }
+
+    /// Creates the selected generator
+    pub fn generator(&self) -> Arc<dyn IdGenerator> {
+        match self {
+            IdFormat::UuidV4 => Arc::new(UuidV4Generator),
+            IdFormat::UuidV7 => Arc::new(UuidV7Generator),
+            IdFormat::Ulid => Arc::new(UlidGenerator::new()),
+        }
+    }
+}
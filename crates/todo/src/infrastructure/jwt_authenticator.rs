@@ -0,0 +1,268 @@
+use crate::TenantId;
+use crate::application::auth::{AuthContext, AuthError, Authenticator};
+use async_trait::async_trait;
+use jsonwebtoken::jwk::{JwkSet, PublicKeyUse};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use serde::Deserialize;
+use serde_json::Value;
+use std::str::FromStr;
+use std::sync::RwLock;
+use std::time::Duration;
+
+/// Claim naming the caller's tenant, unless `with_tenant_claim` picks another
+pub const DEFAULT_TENANT_CLAIM: &str = "tenant";
+
+/// How long fetching the discovery document or the key set may take
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Validates JWT bearer tokens, signed with a shared secret or a key of an OIDC issuer
+///
+/// A token is accepted when its signature matches a trusted key, it has not expired, it
+/// has a `sub`, and its `iss` and `aud` match the issuer and audience, when set. The
+/// `AuthContext` takes:
+/// - `subject` from `sub`
+/// - `scopes` from `scope` (space-separated) or `scp` (a list)
+/// - `tenant` from the `tenant` claim, or the one set with `with_tenant_claim`
+pub struct JwtAuthenticator {
+    keys: RwLock<Vec<VerificationKey>>,
+    issuer: Option<String>,
+    audience: Option<String>,
+    tenant_claim: String,
+    /// Where `refresh` fetches the keys from, for discovered issuers
+    jwks_uri: Option<String>,
+}
+
+struct VerificationKey {
+    kid: Option<String>,
+    /// The only algorithm the key may be used with, if the key set says so
+    algorithm: Option<Algorithm>,
+    key: DecodingKey,
+}
+
+/// The fields of an OpenID Provider's discovery document used here
+#[derive(Deserialize)]
+struct ProviderMetadata {
+    issuer: String,
+    jwks_uri: String,
+}
+
+impl JwtAuthenticator {
+    /// Creates a new JwtAuthenticator accepting tokens signed with `secret` (HS256, HS384
+    /// or HS512)
+    pub fn with_secret(secret: &[u8]) -> Self {
+        Self::with_keys(vec![VerificationKey {
+            kid: None,
+            algorithm: None,
+            key: DecodingKey::from_secret(secret),
+        }])
+    }
+
+    /// Creates a new JwtAuthenticator accepting tokens signed with a key of a JWK Set
+    ///
+    /// # Returns
+    /// - `Err(String)`: If `jwks` is not a JWK Set or has no signature key
+    pub fn from_jwks(jwks: &str) -> Result<Self, String> {
+        Ok(Self::with_keys(parse_jwks(jwks)?))
+    }
+
+    /// Creates a new JwtAuthenticator for the tokens an OpenID Provider issues to `audience`
+    ///
+    /// Reads `<issuer>/.well-known/openid-configuration`, then the key set it points to.
+    /// Call `refresh` to pick up keys the provider rotates in later.
+    ///
+    /// # Returns
+    /// - `Err(AuthError::Unavailable)`: If either document cannot be fetched or read, or the
+    ///   discovery document names another issuer
+    pub async fn discover(issuer: &str, audience: &str) -> Result<Self, AuthError> {
+        let url = format!(
+            "{}/.well-known/openid-configuration",
+            issuer.trim_end_matches('/')
+        );
+        let metadata: ProviderMetadata = serde_json::from_str(&fetch(&url).await?)
+            .map_err(|err| AuthError::Unavailable(format!("invalid {}: {}", url, err)))?;
+        if metadata.issuer.trim_end_matches('/') != issuer.trim_end_matches('/') {
+            return Err(AuthError::Unavailable(format!(
+                "{} names issuer {:?}, expected {:?}",
+                url, metadata.issuer, issuer
+            )));
+        }
+        let keys = fetch_keys(&metadata.jwks_uri).await?;
+        Ok(JwtAuthenticator {
+            issuer: Some(metadata.issuer),
+            audience: Some(audience.to_string()),
+            jwks_uri: Some(metadata.jwks_uri),
+            ..Self::with_keys(keys)
+        })
+    }
+
+    /// Only accepts tokens whose `iss` is `issuer`
+    pub fn with_issuer(mut self, issuer: impl Into<String>) -> Self {
+        self.issuer = Some(issuer.into());
+        self
+    }
+
+    /// Only accepts tokens whose `aud` contains `audience`
+    pub fn with_audience(mut self, audience: impl Into<String>) -> Self {
+        self.audience = Some(audience.into());
+        self
+    }
+
+    /// Reads the tenant from the `claim` claim instead of `tenant`
+    pub fn with_tenant_claim(mut self, claim: impl Into<String>) -> Self {
+        self.tenant_claim = claim.into();
+        self
+    }
+
+    /// Fetches the issuer's key set again, replacing the trusted keys
+    ///
+    /// Does nothing for authenticators that were not created by `discover`. On error the
+    /// previous keys stay trusted.
+    pub async fn refresh(&self) -> Result<(), AuthError> {
+        let Some(jwks_uri) = &self.jwks_uri else {
+            return Ok(());
+        };
+        let keys = fetch_keys(jwks_uri).await?;
+        *self
+            .keys
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = keys;
+        Ok(())
+    }
+
+    fn with_keys(keys: Vec<VerificationKey>) -> Self {
+        JwtAuthenticator {
+            keys: RwLock::new(keys),
+            issuer: None,
+            audience: None,
+            tenant_claim: DEFAULT_TENANT_CLAIM.to_string(),
+            jwks_uri: None,
+        }
+    }
+
+    fn validation(&self, algorithm: Algorithm) -> Validation {
+        let mut validation = Validation::new(algorithm);
+        validation.set_required_spec_claims(&["exp", "sub"]);
+        if let Some(issuer) = &self.issuer {
+            validation.set_issuer(&[issuer]);
+            validation.required_spec_claims.insert("iss".to_string());
+        }
+        match &self.audience {
+            Some(audience) => {
+                validation.set_audience(&[audience]);
+                validation.required_spec_claims.insert("aud".to_string());
+            }
+            None => validation.validate_aud = false,
+        }
+        validation
+    }
+
+    fn context(&self, claims: &Value) -> Result<AuthContext, AuthError> {
+        let subject = match claims.get("sub") {
+            Some(Value::String(subject)) if !subject.is_empty() => subject.clone(),
+            _ => return Err(invalid("sub must be a non-empty string")),
+        };
+        let scopes = match (claims.get("scope"), claims.get("scp")) {
+            (Some(Value::String(scope)), _) | (None, Some(Value::String(scope))) => {
+                scope.split_whitespace().map(str::to_string).collect()
+            }
+            (None, Some(Value::Array(scopes))) => scopes
+                .iter()
+                .map(|scope| scope.as_str().map(str::to_string))
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| invalid("scp must be a list of strings"))?,
+            (None, None) => Vec::new(),
+            _ => return Err(invalid("scope must be a string")),
+        };
+        let tenant = match claims.get(&self.tenant_claim) {
+            None | Some(Value::Null) => None,
+            Some(Value::String(tenant)) => Some(TenantId::new(tenant.as_str()).map_err(invalid)?),
+            Some(_) => {
+                return Err(invalid(format!("{} must be a string", self.tenant_claim)));
+            }
+        };
+        Ok(AuthContext {
+            subject,
+            tenant,
+            scopes,
+        })
+    }
+}
+
+#[async_trait]
+impl Authenticator for JwtAuthenticator {
+    async fn authenticate(&self, token: &str) -> Result<AuthContext, AuthError> {
+        let header = jsonwebtoken::decode_header(token).map_err(invalid)?;
+        let keys = self
+            .keys
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        // Matching the family keeps a public key from being used as an HMAC secret
+        let key = keys
+            .iter()
+            .filter(|key| key.key.family() == header.alg.family())
+            .filter(|key| {
+                key.algorithm
+                    .is_none_or(|algorithm| algorithm == header.alg)
+            })
+            .find(|key| match (&header.kid, &key.kid) {
+                (Some(wanted), Some(kid)) => wanted == kid,
+                _ => true,
+            })
+            .ok_or_else(|| match &header.kid {
+                Some(kid) => invalid(format!("no {:?} key with kid {:?}", header.alg, kid)),
+                None => invalid(format!("no {:?} key", header.alg)),
+            })?;
+        let claims = jsonwebtoken::decode::<Value>(token, &key.key, &self.validation(header.alg))
+            .map_err(invalid)?
+            .claims;
+        self.context(&claims)
+    }
+}
+
+fn invalid(message: impl ToString) -> AuthError {
+    AuthError::InvalidToken(message.to_string())
+}
+
+async fn fetch(url: &str) -> Result<String, AuthError> {
+    let unavailable = |err: reqwest::Error| AuthError::Unavailable(format!("{}: {}", url, err));
+    reqwest::Client::builder()
+        .timeout(FETCH_TIMEOUT)
+        .build()
+        .map_err(unavailable)?
+        .get(url)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(unavailable)?
+        .text()
+        .await
+        .map_err(unavailable)
+}
+
+async fn fetch_keys(jwks_uri: &str) -> Result<Vec<VerificationKey>, AuthError> {
+    parse_jwks(&fetch(jwks_uri).await?)
+        .map_err(|err| AuthError::Unavailable(format!("invalid {}: {}", jwks_uri, err)))
+}
+
+fn parse_jwks(jwks: &str) -> Result<Vec<VerificationKey>, String> {
+    let jwks: JwkSet = serde_json::from_str(jwks).map_err(|err| err.to_string())?;
+    let keys: Vec<_> = jwks
+        .keys
+        .iter()
+        .filter(|jwk| !matches!(jwk.common.public_key_use, Some(PublicKeyUse::Encryption)))
+        .filter_map(|jwk| {
+            Some(VerificationKey {
+                kid: jwk.common.key_id.clone(),
+                algorithm: match jwk.common.key_algorithm {
+                    Some(algorithm) => Some(Algorithm::from_str(&algorithm.to_string()).ok()?),
+                    None => None,
+                },
+                key: DecodingKey::from_jwk(jwk).ok()?,
+            })
+        })
+        .collect();
+    if keys.is_empty() {
+        return Err("the key set has no signature key".to_string());
+    }
+    Ok(keys)
+}
@@ -0,0 +1,36 @@
+use async_trait::async_trait;
+use std::sync::Mutex;
+
+use crate::domain::notification::{EmailMessage, Mailer, NotificationError};
+
+/// Mailer keeping what it is asked to send instead of sending it, for tests and dry runs
+#[derive(Default)]
+pub struct InMemoryMailer {
+    sent: Mutex<Vec<EmailMessage>>,
+}
+
+impl InMemoryMailer {
+    /// Creates a new InMemoryMailer that has sent nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The messages sent so far, oldest first
+    pub fn sent(&self) -> Vec<EmailMessage> {
+        self.sent
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+}
+
+#[async_trait]
+impl Mailer for InMemoryMailer {
+    async fn send(&self, message: &EmailMessage) -> Result<(), NotificationError> {
+        self.sent
+            .lock()
+            .map_err(|_| NotificationError::DeliveryFailed("mailer lock poisoned".to_string()))?
+            .push(message.clone());
+        Ok(())
+    }
+}
@@ -0,0 +1,11 @@
+mod inmemory_mailer;
+#[cfg(feature = "ses")]
+mod ses_mailer;
+#[cfg(feature = "smtp")]
+mod smtp_mailer;
+
+pub use inmemory_mailer::InMemoryMailer;
+#[cfg(feature = "ses")]
+pub use ses_mailer::SesMailer;
+#[cfg(feature = "smtp")]
+pub use smtp_mailer::SmtpMailer;
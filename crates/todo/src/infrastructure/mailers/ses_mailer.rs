@@ -0,0 +1,189 @@
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::domain::notification::{EmailMessage, Mailer, NotificationError};
+use crate::{Clock, SystemClock};
+
+/// Path of the SES v2 `SendEmail` action
+const SEND_EMAIL_PATH: &str = "/v2/email/outbound-emails";
+
+/// How long a `SendEmail` request may take
+const SES_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Mailer sending through the Amazon SES v2 API, signing requests with AWS Signature
+/// Version 4
+pub struct SesMailer {
+    client: reqwest::Client,
+    endpoint: String,
+    region: String,
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+    clock: Arc<dyn Clock>,
+}
+
+impl SesMailer {
+    /// Creates a new SesMailer sending from `region` with an access key
+    pub fn new(
+        region: impl Into<String>,
+        access_key_id: impl Into<String>,
+        secret_access_key: impl Into<String>,
+    ) -> Self {
+        let region = region.into();
+        SesMailer {
+            client: reqwest::Client::new(),
+            endpoint: format!("https://email.{}.amazonaws.com", region),
+            region,
+            access_key_id: access_key_id.into(),
+            secret_access_key: secret_access_key.into(),
+            session_token: None,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Creates a new SesMailer from `AWS_REGION` (or `AWS_DEFAULT_REGION`),
+    /// `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and the optional `AWS_SESSION_TOKEN`
+    ///
+    /// # Returns
+    /// - `Err(String)`: Naming the first variable that is not set
+    pub fn from_env() -> Result<Self, String> {
+        let var = |name: &str| std::env::var(name).map_err(|_| format!("{} is not set", name));
+        let region = var("AWS_REGION").or_else(|_| var("AWS_DEFAULT_REGION"))?;
+        let mailer = Self::new(
+            region,
+            var("AWS_ACCESS_KEY_ID")?,
+            var("AWS_SECRET_ACCESS_KEY")?,
+        );
+        Ok(match std::env::var("AWS_SESSION_TOKEN") {
+            Ok(token) => mailer.with_session_token(token),
+            Err(_) => mailer,
+        })
+    }
+
+    /// Sends the temporary credentials' session token along
+    pub fn with_session_token(mut self, token: impl Into<String>) -> Self {
+        self.session_token = Some(token.into());
+        self
+    }
+
+    /// Sends to `endpoint`, such as a VPC endpoint or a local stand-in, instead of the
+    /// region's public one
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = endpoint.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Signs requests with the time of `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// The `Authorization` header of a `POST` of `body` with the given signed headers,
+    /// which must be sorted by name
+    fn authorization(&self, headers: &[(&str, &str)], body: &[u8], amz_date: &str) -> String {
+        let signed_headers = headers
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>()
+            .join(";");
+        let canonical_headers: String = headers
+            .iter()
+            .map(|(name, value)| format!("{}:{}\n", name, value.trim()))
+            .collect();
+        let canonical_request = format!(
+            "POST\n{}\n\n{}\n{}\n{}",
+            SEND_EMAIL_PATH,
+            canonical_headers,
+            signed_headers,
+            hex(&Sha256::digest(body))
+        );
+        let date = &amz_date[..8];
+        let scope = format!("{}/{}/ses/aws4_request", date, self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex(&Sha256::digest(canonical_request.as_bytes()))
+        );
+        let key = [self.region.as_str(), "ses", "aws4_request"].iter().fold(
+            hmac(format!("AWS4{}", self.secret_access_key).as_bytes(), date),
+            |key, part| hmac(&key, part),
+        );
+        format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.access_key_id,
+            scope,
+            signed_headers,
+            hex(&hmac(&key, &string_to_sign))
+        )
+    }
+}
+
+#[async_trait]
+impl Mailer for SesMailer {
+    async fn send(&self, message: &EmailMessage) -> Result<(), NotificationError> {
+        let url = reqwest::Url::parse(&format!("{}{}", self.endpoint, SEND_EMAIL_PATH))
+            .map_err(|err| failed(format!("invalid endpoint {}: {}", self.endpoint, err)))?;
+        let host = match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            (None, _) => return Err(failed(format!("endpoint {} has no host", self.endpoint))),
+        };
+        let body = json!({
+            "FromEmailAddress": message.from,
+            "Destination": { "ToAddresses": message.to },
+            "Content": {
+                "Simple": {
+                    "Subject": { "Data": message.subject, "Charset": "UTF-8" },
+                    "Body": { "Text": { "Data": message.text, "Charset": "UTF-8" } },
+                }
+            },
+        })
+        .to_string();
+        let amz_date = self.clock.now().format("%Y%m%dT%H%M%SZ").to_string();
+        let mut headers = vec![
+            ("content-type", "application/json"),
+            ("host", host.as_str()),
+            ("x-amz-date", amz_date.as_str()),
+        ];
+        if let Some(token) = &self.session_token {
+            headers.push(("x-amz-security-token", token.as_str()));
+        }
+        let authorization = self.authorization(&headers, body.as_bytes(), &amz_date);
+        let mut request = self
+            .client
+            .post(url)
+            .timeout(SES_TIMEOUT)
+            .header("authorization", authorization)
+            .body(body);
+        for (name, value) in headers.iter().filter(|(name, _)| *name != "host") {
+            request = request.header(*name, *value);
+        }
+        let response = request.send().await.map_err(failed)?;
+        if response.status().is_success() {
+            return Ok(());
+        }
+        let status = response.status();
+        let detail = response.text().await.unwrap_or_default();
+        Err(failed(format!("SES answered {}: {}", status, detail)))
+    }
+}
+
+fn hmac(key: &[u8], data: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn failed(message: impl ToString) -> NotificationError {
+    NotificationError::DeliveryFailed(message.to_string())
+}
@@ -0,0 +1,230 @@
+use async_trait::async_trait;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio_rustls::TlsConnector;
+use tokio_rustls::rustls::pki_types::ServerName;
+use tokio_rustls::rustls::{ClientConfig, RootCertStore, crypto};
+
+use crate::domain::notification::{EmailMessage, Mailer, NotificationError};
+use crate::{Clock, SystemClock};
+
+/// How long connecting and each exchange with the server may take
+const SMTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Mailer sending through an SMTP server, such as a local relay or a provider's submission
+/// port
+///
+/// Without `with_tls` the connection is plain text, which suits a relay on the same host or
+/// network. `with_tls` speaks SMTP over TLS from the start (port 465), checking the
+/// server's certificate against the Mozilla roots. STARTTLS is not supported.
+pub struct SmtpMailer {
+    host: String,
+    port: u16,
+    tls: bool,
+    credentials: Option<(String, String)>,
+    hello: String,
+}
+
+impl SmtpMailer {
+    /// Creates a new SmtpMailer sending in plain text through `host`, on port 25
+    pub fn new(host: impl Into<String>) -> Self {
+        SmtpMailer {
+            host: host.into(),
+            port: 25,
+            tls: false,
+            credentials: None,
+            hello: "localhost".to_string(),
+        }
+    }
+
+    /// Connects to `port` instead of 25
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// Speaks SMTP over TLS, usually on port 465
+    pub fn with_tls(mut self) -> Self {
+        self.tls = true;
+        self
+    }
+
+    /// Logs in with `AUTH PLAIN`, which is only done over TLS
+    pub fn with_credentials(
+        mut self,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        self.credentials = Some((username.into(), password.into()));
+        self
+    }
+
+    /// Greets the server as `name`, the domain of this host, instead of `localhost`
+    pub fn with_hello_name(mut self, name: impl Into<String>) -> Self {
+        self.hello = name.into();
+        self
+    }
+
+    async fn connect(&self) -> Result<TcpStream, NotificationError> {
+        let address = (self.host.as_str(), self.port);
+        tokio::time::timeout(SMTP_TIMEOUT, TcpStream::connect(address))
+            .await
+            .map_err(|_| failed("timed out connecting"))?
+            .map_err(failed)
+    }
+
+    async fn connect_tls(
+        &self,
+    ) -> Result<tokio_rustls::client::TlsStream<TcpStream>, NotificationError> {
+        let mut roots = RootCertStore::empty();
+        roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+        let config =
+            ClientConfig::builder_with_provider(Arc::new(crypto::ring::default_provider()))
+                .with_safe_default_protocol_versions()
+                .map_err(failed)?
+                .with_root_certificates(roots)
+                .with_no_client_auth();
+        let name = ServerName::try_from(self.host.clone()).map_err(failed)?;
+        let stream = self.connect().await?;
+        tokio::time::timeout(
+            SMTP_TIMEOUT,
+            TlsConnector::from(Arc::new(config)).connect(name, stream),
+        )
+        .await
+        .map_err(|_| failed("timed out negotiating TLS"))?
+        .map_err(failed)
+    }
+
+    async fn session<S>(&self, stream: S, message: &EmailMessage) -> Result<(), NotificationError>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let mut connection = Connection {
+            stream: BufReader::new(stream),
+        };
+        connection.expect(&[220]).await?;
+        connection
+            .command(&format!("EHLO {}", self.hello), &[250])
+            .await?;
+        if let Some((username, password)) = &self.credentials {
+            if !self.tls {
+                return Err(failed("refusing to send credentials without TLS"));
+            }
+            let token = BASE64.encode(format!("\0{}\0{}", username, password));
+            connection
+                .command(&format!("AUTH PLAIN {}", token), &[235])
+                .await?;
+        }
+        connection
+            .command(&format!("MAIL FROM:<{}>", message.from), &[250])
+            .await?;
+        for recipient in &message.to {
+            connection
+                .command(&format!("RCPT TO:<{}>", recipient), &[250, 251])
+                .await?;
+        }
+        connection.command("DATA", &[354]).await?;
+        connection.write(&render(message)).await?;
+        connection.expect(&[250]).await?;
+        // The message is accepted; a failed goodbye does not undo that
+        let _ = connection.command("QUIT", &[221]).await;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Mailer for SmtpMailer {
+    async fn send(&self, message: &EmailMessage) -> Result<(), NotificationError> {
+        if self.tls {
+            self.session(self.connect_tls().await?, message).await
+        } else {
+            self.session(self.connect().await?, message).await
+        }
+    }
+}
+
+struct Connection<S> {
+    stream: BufReader<S>,
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> Connection<S> {
+    async fn command(&mut self, line: &str, expected: &[u16]) -> Result<(), NotificationError> {
+        self.write(&format!("{}\r\n", line)).await?;
+        self.expect(expected).await
+    }
+
+    async fn write(&mut self, data: &str) -> Result<(), NotificationError> {
+        let stream = self.stream.get_mut();
+        tokio::time::timeout(SMTP_TIMEOUT, async {
+            stream.write_all(data.as_bytes()).await?;
+            stream.flush().await
+        })
+        .await
+        .map_err(|_| failed("timed out writing to the server"))?
+        .map_err(failed)
+    }
+
+    /// Reads a reply, of one or more lines, and checks its code is one of `expected`
+    async fn expect(&mut self, expected: &[u16]) -> Result<(), NotificationError> {
+        let mut reply = String::new();
+        loop {
+            let mut line = String::new();
+            let read = tokio::time::timeout(SMTP_TIMEOUT, self.stream.read_line(&mut line))
+                .await
+                .map_err(|_| failed("timed out waiting for the server"))?
+                .map_err(failed)?;
+            if read == 0 {
+                return Err(failed("server closed the connection"));
+            }
+            reply.push_str(line.trim_end());
+            // `250-` continues a reply, `250 ` ends it
+            if line.as_bytes().get(3) != Some(&b'-') {
+                break;
+            }
+            reply.push(' ');
+        }
+        match reply.get(..3).and_then(|code| code.parse::<u16>().ok()) {
+            Some(code) if expected.contains(&code) => Ok(()),
+            _ => Err(failed(format!("server replied {:?}", reply))),
+        }
+    }
+}
+
+/// The message as sent after `DATA`: headers, the text with CRLF line endings and leading
+/// dots doubled, and the closing `.`
+fn render(message: &EmailMessage) -> String {
+    let mut data = format!(
+        "From: {}\r\nTo: {}\r\nSubject: {}\r\nDate: {}\r\nMIME-Version: 1.0\r\n\
+         Content-Type: text/plain; charset=utf-8\r\nContent-Transfer-Encoding: 8bit\r\n\r\n",
+        message.from,
+        message.to.join(", "),
+        encode_header(&message.subject),
+        SystemClock.now().to_rfc2822(),
+    );
+    for line in message.text.lines() {
+        if line.starts_with('.') {
+            data.push('.');
+        }
+        data.push_str(line);
+        data.push_str("\r\n");
+    }
+    data.push_str(".\r\n");
+    data
+}
+
+/// Leaves ASCII text as is and encodes anything else as an RFC 2047 encoded word
+fn encode_header(value: &str) -> String {
+    if value.is_ascii() {
+        value.to_string()
+    } else {
+        format!("=?utf-8?B?{}?=", BASE64.encode(value))
+    }
+}
+
+fn failed(message: impl ToString) -> NotificationError {
+    NotificationError::DeliveryFailed(message.to_string())
+}
@@ -1,2 +1,16 @@
+pub mod blob_stores;
+#[cfg(feature = "config")]
+pub mod config;
+#[cfg(feature = "serde")]
+pub mod event_sinks;
+pub mod event_stores;
+pub mod id_format;
+#[cfg(feature = "jwt")]
+pub mod jwt_authenticator;
+pub mod mailers;
+#[cfg(any(feature = "fcm", feature = "apns"))]
+pub mod push_senders;
+#[cfg(any(feature = "caldav", feature = "jira", feature = "msgraph"))]
+pub mod remote_task_lists;
 pub mod repositories;
-
+pub mod serialization;
@@ -0,0 +1,140 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration as TimeDelta, Utc};
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use serde_json::{Map, Value, json};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::domain::notification::{Device, NotificationError, PushNotification, PushSender};
+use crate::{Clock, SystemClock};
+
+/// How long a request to APNs may take
+const APNS_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long a provider token is used; APNs refuses tokens older than an hour
+const PROVIDER_TOKEN_LIFETIME: TimeDelta = TimeDelta::minutes(50);
+
+/// PushSender for the Apple Push Notification service, over HTTP/2
+///
+/// Authenticates with a provider token: a JWT signed with a `.p8` key of the team, renewed
+/// every 50 minutes. The notification's data is sent beside the `aps` dictionary.
+pub struct ApnsPushSender {
+    client: reqwest::Client,
+    endpoint: String,
+    team_id: String,
+    key_id: String,
+    key: EncodingKey,
+    topic: String,
+    provider_token: Mutex<Option<(String, DateTime<Utc>)>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl ApnsPushSender {
+    /// Creates a new ApnsPushSender for the app with bundle id `topic`, signing with the
+    /// team's `.p8` key `key_id`
+    ///
+    /// # Returns
+    /// - `Err(String)`: If `p8_key` is not a PEM-encoded P-256 private key
+    pub fn new(
+        team_id: impl Into<String>,
+        key_id: impl Into<String>,
+        p8_key: &[u8],
+        topic: impl Into<String>,
+    ) -> Result<Self, String> {
+        let key = EncodingKey::from_ec_pem(p8_key)
+            .map_err(|err| format!("invalid APNs signing key: {}", err))?;
+        Ok(ApnsPushSender {
+            client: reqwest::Client::new(),
+            endpoint: "https://api.push.apple.com".to_string(),
+            team_id: team_id.into(),
+            key_id: key_id.into(),
+            key,
+            topic: topic.into(),
+            provider_token: Mutex::new(None),
+            clock: Arc::new(SystemClock),
+        })
+    }
+
+    /// Sends to the development environment, for apps signed with a development profile
+    pub fn with_sandbox(self) -> Self {
+        self.with_endpoint("https://api.sandbox.push.apple.com")
+    }
+
+    /// Sends to `endpoint`, such as a local stand-in, instead of `https://api.push.apple.com`
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = endpoint.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Dates provider tokens with `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// The current provider token, or a new one when it is due for renewal
+    fn provider_token(&self) -> Result<String, NotificationError> {
+        let now = self.clock.now();
+        let mut cached = self
+            .provider_token
+            .lock()
+            .map_err(|_| failed("provider token lock poisoned"))?;
+        if let Some((token, issued_at)) = &*cached
+            && now < *issued_at + PROVIDER_TOKEN_LIFETIME
+        {
+            return Ok(token.clone());
+        }
+        let mut header = Header::new(Algorithm::ES256);
+        header.kid = Some(self.key_id.clone());
+        let claims = json!({ "iss": self.team_id, "iat": now.timestamp() });
+        let token = jsonwebtoken::encode(&header, &claims, &self.key).map_err(failed)?;
+        *cached = Some((token.clone(), now));
+        Ok(token)
+    }
+}
+
+#[async_trait]
+impl PushSender for ApnsPushSender {
+    async fn send(
+        &self,
+        device: &Device,
+        notification: &PushNotification,
+    ) -> Result<(), NotificationError> {
+        let mut body: Map<String, Value> = notification
+            .data
+            .iter()
+            .map(|(key, value)| (key.clone(), Value::String(value.clone())))
+            .collect();
+        body.insert(
+            "aps".to_string(),
+            json!({ "alert": { "title": notification.title, "body": notification.body } }),
+        );
+        let url = format!("{}/3/device/{}", self.endpoint, device.token);
+        let response = self
+            .client
+            .post(url)
+            .timeout(APNS_TIMEOUT)
+            .bearer_auth(self.provider_token()?)
+            .header("apns-topic", &self.topic)
+            .header("apns-push-type", "alert")
+            .json(&body)
+            .send()
+            .await
+            .map_err(failed)?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        let detail = response.text().await.unwrap_or_default();
+        // 410 means the token is no longer active; BadDeviceToken that it never was valid
+        // for this environment
+        if status == reqwest::StatusCode::GONE || detail.contains("BadDeviceToken") {
+            return Err(NotificationError::DeviceUnregistered);
+        }
+        Err(failed(format!("APNs answered {}: {}", status, detail)))
+    }
+}
+
+fn failed(message: impl ToString) -> NotificationError {
+    NotificationError::DeliveryFailed(message.to_string())
+}
@@ -0,0 +1,173 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration as TimeDelta, Utc};
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use serde::Deserialize;
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::domain::notification::{Device, NotificationError, PushNotification, PushSender};
+use crate::{Clock, SystemClock};
+
+/// OAuth scope of the FCM HTTP v1 API
+const FCM_SCOPE: &str = "https://www.googleapis.com/auth/firebase.messaging";
+
+/// How long a request to Google may take
+const FCM_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The fields of a Google service account key file used here
+#[derive(Deserialize)]
+struct ServiceAccount {
+    project_id: String,
+    client_email: String,
+    private_key: String,
+    token_uri: String,
+}
+
+/// An OAuth access token and when it expires
+type CachedToken = Option<(String, DateTime<Utc>)>;
+
+#[derive(Deserialize)]
+struct AccessTokenResponse {
+    access_token: String,
+    expires_in: i64,
+}
+
+/// PushSender for Firebase Cloud Messaging, through the HTTP v1 API
+///
+/// Authenticates as a service account: it signs a JWT with the account's key, trades it for
+/// an OAuth access token, and reuses that token until shortly before it expires.
+pub struct FcmPushSender {
+    client: reqwest::Client,
+    endpoint: String,
+    project_id: String,
+    client_email: String,
+    key: EncodingKey,
+    token_uri: String,
+    access_token: Mutex<CachedToken>,
+    clock: Arc<dyn Clock>,
+}
+
+impl FcmPushSender {
+    /// Creates a new FcmPushSender from the JSON key file of a service account allowed to
+    /// send messages for its project
+    ///
+    /// # Returns
+    /// - `Err(String)`: If `json` is not a service account key with an RSA private key
+    pub fn from_service_account(json: &str) -> Result<Self, String> {
+        let account: ServiceAccount = serde_json::from_str(json)
+            .map_err(|err| format!("invalid service account key: {}", err))?;
+        let key = EncodingKey::from_rsa_pem(account.private_key.as_bytes())
+            .map_err(|err| format!("invalid service account private key: {}", err))?;
+        Ok(FcmPushSender {
+            client: reqwest::Client::new(),
+            endpoint: "https://fcm.googleapis.com".to_string(),
+            project_id: account.project_id,
+            client_email: account.client_email,
+            key,
+            token_uri: account.token_uri,
+            access_token: Mutex::new(None),
+            clock: Arc::new(SystemClock),
+        })
+    }
+
+    /// Sends to `endpoint`, such as a local stand-in, instead of `https://fcm.googleapis.com`
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = endpoint.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Dates the signed JWTs with `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// The cached access token, or a new one if it expires within a minute
+    async fn access_token(&self) -> Result<String, NotificationError> {
+        let now = self.clock.now();
+        if let Some((token, expires_at)) = &*self.cached()?
+            && *expires_at > now + TimeDelta::minutes(1)
+        {
+            return Ok(token.clone());
+        }
+        let claims = json!({
+            "iss": self.client_email,
+            "scope": FCM_SCOPE,
+            "aud": self.token_uri,
+            "iat": now.timestamp(),
+            "exp": (now + TimeDelta::hours(1)).timestamp(),
+        });
+        let assertion = jsonwebtoken::encode(&Header::new(Algorithm::RS256), &claims, &self.key)
+            .map_err(failed)?;
+        let response = self
+            .client
+            .post(&self.token_uri)
+            .timeout(FCM_TIMEOUT)
+            .form(&[
+                ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
+                ("assertion", assertion.as_str()),
+            ])
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|err| failed(format!("{}: {}", self.token_uri, err)))?;
+        let token: AccessTokenResponse = response
+            .json()
+            .await
+            .map_err(|err| failed(format!("{}: {}", self.token_uri, err)))?;
+        let expires_at = now + TimeDelta::seconds(token.expires_in);
+        *self.cached()? = Some((token.access_token.clone(), expires_at));
+        Ok(token.access_token)
+    }
+
+    fn cached(&self) -> Result<std::sync::MutexGuard<'_, CachedToken>, NotificationError> {
+        self.access_token
+            .lock()
+            .map_err(|_| failed("access token lock poisoned"))
+    }
+}
+
+#[async_trait]
+impl PushSender for FcmPushSender {
+    async fn send(
+        &self,
+        device: &Device,
+        notification: &PushNotification,
+    ) -> Result<(), NotificationError> {
+        let body = json!({
+            "message": {
+                "token": &*device.token,
+                "notification": { "title": notification.title, "body": notification.body },
+                "data": notification.data,
+            }
+        });
+        let url = format!(
+            "{}/v1/projects/{}/messages:send",
+            self.endpoint, self.project_id
+        );
+        let response = self
+            .client
+            .post(url)
+            .timeout(FCM_TIMEOUT)
+            .bearer_auth(self.access_token().await?)
+            .json(&body)
+            .send()
+            .await
+            .map_err(failed)?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        let detail = response.text().await.unwrap_or_default();
+        // FCM answers 404 with the error code UNREGISTERED for tokens of uninstalled apps
+        if status == reqwest::StatusCode::NOT_FOUND || detail.contains("UNREGISTERED") {
+            return Err(NotificationError::DeviceUnregistered);
+        }
+        Err(failed(format!("FCM answered {}: {}", status, detail)))
+    }
+}
+
+fn failed(message: impl ToString) -> NotificationError {
+    NotificationError::DeliveryFailed(message.to_string())
+}
@@ -0,0 +1,9 @@
+#[cfg(feature = "apns")]
+mod apns_push_sender;
+#[cfg(feature = "fcm")]
+mod fcm_push_sender;
+
+#[cfg(feature = "apns")]
+pub use apns_push_sender::ApnsPushSender;
+#[cfg(feature = "fcm")]
+pub use fcm_push_sender::FcmPushSender;
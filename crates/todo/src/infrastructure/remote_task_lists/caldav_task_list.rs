@@ -0,0 +1,431 @@
+use async_trait::async_trait;
+use reqwest::header::{CONTENT_TYPE, ETAG, IF_MATCH, IF_NONE_MATCH};
+use reqwest::{Method, StatusCode, Url};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+
+use crate::domain::sync::{RemoteChanges, RemoteTaskList};
+use crate::infrastructure::serialization::ical::{export_todos, import_todos, update_vtodo};
+use crate::{Todo, TodoError};
+
+/// How long a request to the CalDAV server may take
+const CALDAV_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Lists the etag of every VTODO resource of the collection
+const LIST_ETAGS: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<c:calendar-query xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:prop><d:getetag/></d:prop>
+  <c:filter><c:comp-filter name="VCALENDAR"><c:comp-filter name="VTODO"/></c:comp-filter></c:filter>
+</c:calendar-query>"#;
+
+/// What the last pull or push saw of a task
+struct KnownTask {
+    href: String,
+    /// `None` when the server did not return the etag of a push; the next pull fetches it
+    etag: Option<String>,
+    calendar: String,
+}
+
+/// RemoteTaskList over the VTODOs of a CalDAV calendar collection, such as a Nextcloud or
+/// Fastmail task list
+///
+/// Each task is one calendar resource, and its etag is its version. A pull lists the etags
+/// of the collection and fetches only the resources whose etag changed since the previous
+/// pull, so the first pull fetches every task. Updates are sent with `If-Match` the etag last
+/// seen, and a task changed on the server since is left for the next pull to merge; they
+/// keep the properties of the task the todo does not hold. New tasks are created as
+/// `<collection>/<id>.ics` with the todo's id as `UID`.
+///
+/// Requests are authorized with HTTP basic authentication, such as with an app password.
+pub struct CalDavTaskList {
+    client: reqwest::Client,
+    collection: Url,
+    username: String,
+    password: String,
+    tasks: Mutex<HashMap<String, KnownTask>>,
+}
+
+impl CalDavTaskList {
+    /// Creates a new CalDavTaskList over the calendar collection at `collection`
+    ///
+    /// # Returns
+    /// - `Ok(CalDavTaskList)`: The task list, with nothing pulled yet
+    /// - `Err(TodoError::ValidationError)`: If `collection` is not an absolute URL
+    pub fn new(
+        collection: &str,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Result<Self, TodoError> {
+        let mut collection = Url::parse(collection).map_err(|err| {
+            TodoError::ValidationError(format!("invalid collection URL {}: {}", collection, err))
+        })?;
+        if !collection.path().ends_with('/') {
+            collection.set_path(&format!("{}/", collection.path()));
+        }
+        Ok(CalDavTaskList {
+            client: reqwest::Client::new(),
+            collection,
+            username: username.into(),
+            password: password.into(),
+            tasks: Mutex::new(HashMap::new()),
+        })
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        url: &str,
+        headers: &[(&str, String)],
+        body: Option<String>,
+    ) -> Result<reqwest::Response, TodoError> {
+        let mut request = self
+            .client
+            .request(method, url)
+            .timeout(CALDAV_TIMEOUT)
+            .basic_auth(&self.username, Some(&self.password));
+        for (name, value) in headers {
+            request = request.header(*name, value);
+        }
+        if let Some(body) = body {
+            request = request.body(body);
+        }
+        request
+            .send()
+            .await
+            .map_err(|err| failed(format!("{}: {}", url, err)))
+    }
+
+    /// Sends a `REPORT` on the collection and reads its multistatus
+    async fn report(&self, body: String) -> Result<Vec<Resource>, TodoError> {
+        let headers = [
+            ("Depth", "1".to_string()),
+            (
+                CONTENT_TYPE.as_str(),
+                "application/xml; charset=utf-8".to_string(),
+            ),
+        ];
+        let method = Method::from_bytes(b"REPORT").map_err(failed)?;
+        let response = self
+            .send(method, self.collection.as_str(), &headers, Some(body))
+            .await?;
+        let response = check(response).await?;
+        let body = response.text().await.map_err(failed)?;
+        Ok(read_multistatus(&body))
+    }
+
+    fn url(&self, href: &str) -> Result<String, TodoError> {
+        self.collection
+            .join(href)
+            .map(String::from)
+            .map_err(|err| failed(format!("invalid href {}: {}", href, err)))
+    }
+}
+
+#[async_trait]
+impl RemoteTaskList for CalDavTaskList {
+    fn source(&self) -> &str {
+        "caldav"
+    }
+
+    async fn pull(&self) -> Result<RemoteChanges, TodoError> {
+        let listed: HashMap<String, Option<String>> = self
+            .report(LIST_ETAGS.to_string())
+            .await?
+            .into_iter()
+            .map(|resource| (resource.href, resource.etag))
+            .collect();
+        let (stale, removed) = {
+            let tasks = lock(&self.tasks)?;
+            let seen: HashMap<&str, &Option<String>> = tasks
+                .values()
+                .map(|task| (task.href.as_str(), &task.etag))
+                .collect();
+            let stale: Vec<String> = listed
+                .iter()
+                .filter(|(href, etag)| {
+                    etag.is_none() || seen.get(href.as_str()).is_none_or(|seen| seen != etag)
+                })
+                .map(|(href, _)| href.clone())
+                .collect();
+            let removed: Vec<String> = tasks
+                .iter()
+                .filter(|(_, task)| !listed.contains_key(&task.href))
+                .map(|(uid, _)| uid.clone())
+                .collect();
+            (stale, removed)
+        };
+
+        let mut changes = RemoteChanges::default();
+        if !stale.is_empty() {
+            let resources = self.report(multiget(&stale)).await?;
+            let mut tasks = lock(&self.tasks)?;
+            let fetched: HashSet<&str> = resources.iter().map(|resource| &*resource.href).collect();
+            tasks.retain(|_, task| !fetched.contains(task.href.as_str()));
+            for resource in resources {
+                let Some(calendar) = resource.calendar_data else {
+                    continue;
+                };
+                let Some(todo) = import_todos(calendar.as_bytes())?.into_iter().next() else {
+                    continue;
+                };
+                let etag = resource
+                    .etag
+                    .or_else(|| listed.get(&resource.href).cloned().flatten());
+                tasks.insert(
+                    todo.id.to_string(),
+                    KnownTask {
+                        href: resource.href,
+                        etag,
+                        calendar,
+                    },
+                );
+                changes.todos.push(todo);
+            }
+        }
+        let mut tasks = lock(&self.tasks)?;
+        for uid in &removed {
+            tasks.remove(uid);
+        }
+        changes.removed = removed;
+        Ok(changes)
+    }
+
+    async fn create(&self, todo: &Todo) -> Result<Option<String>, TodoError> {
+        let mut calendar = Vec::new();
+        export_todos(&mut calendar, std::slice::from_ref(todo))?;
+        let calendar = String::from_utf8(calendar).map_err(failed)?;
+        let href = format!("{}{}.ics", self.collection.path(), encode_segment(&todo.id));
+        let url = self.url(&href)?;
+        let headers = [
+            (IF_NONE_MATCH.as_str(), "*".to_string()),
+            (
+                CONTENT_TYPE.as_str(),
+                "text/calendar; charset=utf-8".to_string(),
+            ),
+        ];
+        let response = self
+            .send(Method::PUT, &url, &headers, Some(calendar.clone()))
+            .await?;
+        let response = check(response).await?;
+        lock(&self.tasks)?.insert(
+            todo.id.to_string(),
+            KnownTask {
+                href,
+                etag: etag(&response),
+                calendar,
+            },
+        );
+        Ok(Some(todo.id.to_string()))
+    }
+
+    async fn update(&self, remote_id: &str, todo: &Todo) -> Result<bool, TodoError> {
+        let (href, etag_seen, calendar) = {
+            let tasks = lock(&self.tasks)?;
+            let task = tasks
+                .get(remote_id)
+                .ok_or_else(|| failed(format!("CalDAV task {} was not pulled", remote_id)))?;
+            let task_todo = Todo {
+                id: remote_id.into(),
+                ..todo.clone()
+            };
+            (
+                task.href.clone(),
+                task.etag.clone(),
+                update_vtodo(&task.calendar, &task_todo)?,
+            )
+        };
+        let mut headers = vec![(
+            CONTENT_TYPE.as_str(),
+            "text/calendar; charset=utf-8".to_string(),
+        )];
+        if let Some(etag_seen) = etag_seen {
+            headers.push((IF_MATCH.as_str(), etag_seen));
+        }
+        let url = self.url(&href)?;
+        let response = self
+            .send(Method::PUT, &url, &headers, Some(calendar.clone()))
+            .await?;
+        if response.status() == StatusCode::PRECONDITION_FAILED {
+            return Ok(false);
+        }
+        let response = check(response).await?;
+        lock(&self.tasks)?.insert(
+            remote_id.to_string(),
+            KnownTask {
+                href,
+                etag: etag(&response),
+                calendar,
+            },
+        );
+        Ok(true)
+    }
+}
+
+/// One `response` of a `multistatus`
+#[derive(Debug, Default)]
+struct Resource {
+    href: String,
+    etag: Option<String>,
+    calendar_data: Option<String>,
+}
+
+/// Reads the resources of a WebDAV `multistatus`, skipping those without an `href`
+///
+/// Elements are matched by local name, whatever their namespace prefix.
+fn read_multistatus(xml: &str) -> Vec<Resource> {
+    let mut resources = Vec::new();
+    let mut current: Option<Resource> = None;
+    let mut text = String::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+        if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+            let end = cdata.find("]]>").unwrap_or(cdata.len());
+            text.push_str(&escape_xml(&cdata[..end]));
+            rest = cdata.get(end + 3..).unwrap_or("");
+            continue;
+        }
+        let end = rest.find('>').unwrap_or(rest.len());
+        let tag = &rest[1..end];
+        rest = rest.get(end + 1..).unwrap_or("");
+        if tag.starts_with(['?', '!']) {
+            continue;
+        }
+        let closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or("");
+        let local = name.rsplit(':').next().unwrap_or(name);
+        match (local, closing) {
+            ("response", false) => current = Some(Resource::default()),
+            ("response", true) => {
+                if let Some(resource) = current.take().filter(|resource| !resource.href.is_empty())
+                {
+                    resources.push(resource);
+                }
+            }
+            (_, false) => text.clear(),
+            (field, true) => {
+                let value = unescape_xml(text.trim());
+                if let Some(resource) = current.as_mut() {
+                    match field {
+                        "href" => resource.href = value,
+                        "getetag" if !value.is_empty() => resource.etag = Some(value),
+                        "calendar-data" if !value.is_empty() => {
+                            resource.calendar_data = Some(value)
+                        }
+                        _ => {}
+                    }
+                }
+                text.clear();
+            }
+        }
+    }
+    resources
+}
+
+/// A `calendar-multiget` of the resources at `hrefs`, with their etags and calendars
+fn multiget(hrefs: &[String]) -> String {
+    let hrefs: String = hrefs
+        .iter()
+        .map(|href| format!("  <d:href>{}</d:href>\n", escape_xml(href)))
+        .collect();
+    format!(
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<c:calendar-multiget xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:prop><d:getetag/><c:calendar-data/></d:prop>
+{}</c:calendar-multiget>"#,
+        hrefs
+    )
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn unescape_xml(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find(';') else {
+            break;
+        };
+        let entity = &rest[1..end];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => {
+                result.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Percent-encodes `value` for use as one path segment
+fn encode_segment(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+fn etag(response: &reqwest::Response) -> Option<String> {
+    response
+        .headers()
+        .get(ETAG)
+        .and_then(|etag| etag.to_str().ok())
+        .map(String::from)
+}
+
+async fn check(response: reqwest::Response) -> Result<reqwest::Response, TodoError> {
+    if response.status().is_success() {
+        return Ok(response);
+    }
+    let status = response.status();
+    let detail = response.text().await.unwrap_or_default();
+    Err(failed(format!(
+        "CalDAV server answered {}: {}",
+        status, detail
+    )))
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> Result<MutexGuard<'_, T>, TodoError> {
+    mutex
+        .lock()
+        .map_err(|_| failed("CalDAV task list lock poisoned"))
+}
+
+fn failed(message: impl ToString) -> TodoError {
+    TodoError::RepositoryError(message.to_string())
+}
@@ -0,0 +1,271 @@
+use async_trait::async_trait;
+use reqwest::{Method, Url};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+
+use crate::domain::sync::{RemoteChanges, RemoteTaskList};
+use crate::infrastructure::serialization::jira::{StatusMap, read_search_page};
+use crate::{Todo, TodoError, TodoState};
+
+/// How long a request to Jira may take
+const JIRA_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Deserialize)]
+struct Transitions {
+    transitions: Vec<Transition>,
+}
+
+#[derive(Deserialize)]
+struct Transition {
+    id: String,
+    to: TransitionTarget,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TransitionTarget {
+    name: String,
+    status_category: Option<StatusCategory>,
+}
+
+#[derive(Deserialize)]
+struct StatusCategory {
+    key: String,
+}
+
+#[derive(Deserialize)]
+struct CreatedIssue {
+    id: String,
+}
+
+/// RemoteTaskList over the Jira Cloud issues a JQL query finds
+///
+/// Issues are todos with the issue id as remote id. Each pull runs the query and returns the
+/// issues whose summary or mapped state changed since the previous pull, and as removed
+/// those it no longer finds, such as issues deleted or moved out of the filter. Pushed
+/// states are applied with the first workflow transition whose target status the
+/// `StatusMap` maps to the todo's state, and descriptions are set as the summary.
+///
+/// New issues are created only when `with_project` gives a project and issue type; without
+/// one, todos with no issue stay local. Requests are authorized with an Atlassian account's
+/// email and API token.
+pub struct JiraTaskList {
+    client: reqwest::Client,
+    site: String,
+    email: String,
+    api_token: String,
+    jql: String,
+    statuses: StatusMap,
+    project: Option<(String, String)>,
+    seen: Mutex<HashMap<String, (String, TodoState)>>,
+}
+
+impl JiraTaskList {
+    /// Creates a new JiraTaskList over the issues `jql` finds on the Jira Cloud site `site`,
+    /// such as `https://acme.atlassian.net`
+    pub fn new(
+        site: impl Into<String>,
+        email: impl Into<String>,
+        api_token: impl Into<String>,
+        jql: impl Into<String>,
+    ) -> Self {
+        JiraTaskList {
+            client: reqwest::Client::new(),
+            site: site.into().trim_end_matches('/').to_string(),
+            email: email.into(),
+            api_token: api_token.into(),
+            jql: jql.into(),
+            statuses: StatusMap::default(),
+            project: None,
+            seen: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Maps workflow statuses onto states with `statuses`, both ways, instead of by status
+    /// category only
+    pub fn with_status_map(mut self, statuses: StatusMap) -> Self {
+        self.statuses = statuses;
+        self
+    }
+
+    /// Creates issues of type `issue_type`, such as `Task`, in the project `project_key` for
+    /// todos without one
+    pub fn with_project(
+        mut self,
+        project_key: impl Into<String>,
+        issue_type: impl Into<String>,
+    ) -> Self {
+        self.project = Some((project_key.into(), issue_type.into()));
+        self
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        url: &str,
+        body: Option<Value>,
+    ) -> Result<reqwest::Response, TodoError> {
+        let mut request = self
+            .client
+            .request(method, url)
+            .timeout(JIRA_TIMEOUT)
+            .basic_auth(&self.email, Some(&self.api_token));
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|err| failed(format!("{}: {}", url, err)))?;
+        if response.status().is_success() {
+            return Ok(response);
+        }
+        let status = response.status();
+        let detail = response.text().await.unwrap_or_default();
+        Err(failed(format!("Jira answered {}: {}", status, detail)))
+    }
+
+    /// Moves the issue `id` to a status mapped to `state`
+    async fn transition(&self, id: &str, state: TodoState) -> Result<(), TodoError> {
+        let url = format!("{}/rest/api/3/issue/{}/transitions", self.site, id);
+        let transitions: Transitions = self
+            .send(Method::GET, &url, None)
+            .await?
+            .json()
+            .await
+            .map_err(failed)?;
+        let transition = transitions
+            .transitions
+            .into_iter()
+            .find(|transition| {
+                let category = transition
+                    .to
+                    .status_category
+                    .as_ref()
+                    .map(|category| category.key.as_str());
+                self.statuses.state(&transition.to.name, category) == Some(state)
+            })
+            .ok_or_else(|| {
+                failed(format!(
+                    "no transition of Jira issue {} leads to {:?}",
+                    id, state
+                ))
+            })?;
+        let body = json!({ "transition": { "id": transition.id } });
+        self.send(Method::POST, &url, Some(body)).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl RemoteTaskList for JiraTaskList {
+    fn source(&self) -> &str {
+        "jira"
+    }
+
+    async fn pull(&self) -> Result<RemoteChanges, TodoError> {
+        let mut found = Vec::new();
+        let mut next_page_token: Option<String> = None;
+        loop {
+            let mut url = Url::parse(&format!("{}/rest/api/3/search/jql", self.site))
+                .map_err(|err| failed(format!("invalid Jira site {}: {}", self.site, err)))?;
+            url.query_pairs_mut()
+                .append_pair("jql", &self.jql)
+                .append_pair("fields", "summary,status,created");
+            if let Some(token) = &next_page_token {
+                url.query_pairs_mut().append_pair("nextPageToken", token);
+            }
+            let body = self
+                .send(Method::GET, url.as_str(), None)
+                .await?
+                .bytes()
+                .await
+                .map_err(failed)?;
+            let page = read_search_page(&body[..], &self.statuses)?;
+            found.extend(page.todos);
+            next_page_token = page.next_page_token;
+            if next_page_token.is_none() {
+                break;
+            }
+        }
+
+        let mut seen = lock(&self.seen)?;
+        let mut changes = RemoteChanges::default();
+        let mut current = HashMap::new();
+        for todo in found {
+            let id = todo.id.to_string();
+            let values = (todo.description.to_string(), todo.state);
+            if seen.get(&id) != Some(&values) {
+                changes.todos.push(todo);
+            }
+            current.insert(id, values);
+        }
+        changes.removed = seen
+            .keys()
+            .filter(|id| !current.contains_key(*id))
+            .cloned()
+            .collect();
+        *seen = current;
+        Ok(changes)
+    }
+
+    async fn create(&self, todo: &Todo) -> Result<Option<String>, TodoError> {
+        let Some((project_key, issue_type)) = &self.project else {
+            return Ok(None);
+        };
+        let body = json!({ "fields": {
+            "project": { "key": project_key },
+            "issuetype": { "name": issue_type },
+            "summary": todo.description,
+        }});
+        let url = format!("{}/rest/api/3/issue", self.site);
+        let created: CreatedIssue = self
+            .send(Method::POST, &url, Some(body))
+            .await?
+            .json()
+            .await
+            .map_err(failed)?;
+        if todo.state != TodoState::Todo {
+            self.transition(&created.id, todo.state).await?;
+        }
+        lock(&self.seen)?.insert(
+            created.id.clone(),
+            (todo.description.to_string(), todo.state),
+        );
+        Ok(Some(created.id))
+    }
+
+    async fn update(&self, remote_id: &str, todo: &Todo) -> Result<bool, TodoError> {
+        // Without a pulled issue, both fields are pushed
+        let pulled = lock(&self.seen)?.get(remote_id).cloned();
+        if pulled
+            .as_ref()
+            .is_none_or(|(summary, _)| *summary != *todo.description)
+        {
+            let url = format!("{}/rest/api/3/issue/{}", self.site, remote_id);
+            let body = json!({ "fields": { "summary": todo.description } });
+            self.send(Method::PUT, &url, Some(body)).await?;
+        }
+        if pulled.is_none_or(|(_, state)| state != todo.state) {
+            self.transition(remote_id, todo.state).await?;
+        }
+        lock(&self.seen)?.insert(
+            remote_id.to_string(),
+            (todo.description.to_string(), todo.state),
+        );
+        Ok(true)
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> Result<MutexGuard<'_, T>, TodoError> {
+    mutex
+        .lock()
+        .map_err(|_| failed("Jira task list lock poisoned"))
+}
+
+fn failed(message: impl ToString) -> TodoError {
+    TodoError::RepositoryError(message.to_string())
+}
@@ -0,0 +1,171 @@
+use async_trait::async_trait;
+use reqwest::Method;
+use serde::Deserialize;
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+
+use crate::domain::sync::{RemoteChanges, RemoteTaskList};
+use crate::infrastructure::serialization::microsoft_todo::{read_delta_page, task_body};
+use crate::{Todo, TodoError};
+
+/// How long a request to Microsoft Graph may take
+const GRAPH_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Deserialize)]
+struct CreatedTask {
+    id: String,
+}
+
+/// RemoteTaskList over a Microsoft To Do task list, through the Microsoft Graph API
+///
+/// Pulls with delta queries: the first pull lists every task, and the next ones only the
+/// tasks changed or removed since, from the delta link the previous pull ended with.
+/// Requests are authorized with an OAuth access token for the Graph `Tasks.ReadWrite`
+/// permission; getting and refreshing it is left to the caller, through `set_access_token`.
+pub struct MicrosoftTodoTaskList {
+    client: reqwest::Client,
+    endpoint: String,
+    list_id: String,
+    access_token: Mutex<String>,
+    delta_link: Mutex<Option<String>>,
+}
+
+impl MicrosoftTodoTaskList {
+    /// Creates a new MicrosoftTodoTaskList over the signed-in user's list `list_id`
+    pub fn new(access_token: impl Into<String>, list_id: impl Into<String>) -> Self {
+        MicrosoftTodoTaskList {
+            client: reqwest::Client::new(),
+            endpoint: "https://graph.microsoft.com/v1.0".to_string(),
+            list_id: list_id.into(),
+            access_token: Mutex::new(access_token.into()),
+            delta_link: Mutex::new(None),
+        }
+    }
+
+    /// Sends to `endpoint`, such as a local stand-in, instead of
+    /// `https://graph.microsoft.com/v1.0`
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = endpoint.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Starts pulling from `delta_link`, kept from an earlier instance, instead of listing
+    /// every task
+    pub fn with_delta_link(mut self, delta_link: impl Into<String>) -> Self {
+        *self
+            .delta_link
+            .get_mut()
+            .unwrap_or_else(|err| err.into_inner()) = Some(delta_link.into());
+        self
+    }
+
+    /// Authorizes the next requests with `access_token`
+    pub fn set_access_token(&self, access_token: impl Into<String>) -> Result<(), TodoError> {
+        *lock(&self.access_token)? = access_token.into();
+        Ok(())
+    }
+
+    /// The delta link the last pull ended with, to keep for the next instance
+    pub fn delta_link(&self) -> Result<Option<String>, TodoError> {
+        Ok(lock(&self.delta_link)?.clone())
+    }
+
+    fn tasks_url(&self) -> String {
+        format!("{}/me/todo/lists/{}/tasks", self.endpoint, self.list_id)
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        url: &str,
+        body: Option<serde_json::Value>,
+    ) -> Result<reqwest::Response, TodoError> {
+        let access_token = lock(&self.access_token)?.clone();
+        let mut request = self
+            .client
+            .request(method, url)
+            .timeout(GRAPH_TIMEOUT)
+            .bearer_auth(access_token);
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|err| failed(format!("{}: {}", url, err)))?;
+        if response.status().is_success() {
+            return Ok(response);
+        }
+        let status = response.status();
+        let detail = response.text().await.unwrap_or_default();
+        Err(failed(format!(
+            "Microsoft Graph answered {}: {}",
+            status, detail
+        )))
+    }
+}
+
+#[async_trait]
+impl RemoteTaskList for MicrosoftTodoTaskList {
+    fn source(&self) -> &str {
+        "microsoft-todo"
+    }
+
+    async fn pull(&self) -> Result<RemoteChanges, TodoError> {
+        let mut url = match self.delta_link()? {
+            Some(delta_link) => delta_link,
+            None => format!("{}/delta", self.tasks_url()),
+        };
+        let mut changes = RemoteChanges::default();
+        loop {
+            let body = self
+                .send(Method::GET, &url, None)
+                .await?
+                .bytes()
+                .await
+                .map_err(failed)?;
+            let page = read_delta_page(&body[..])?;
+            changes.todos.extend(page.todos);
+            changes.removed.extend(page.removed);
+            match (page.next_link, page.delta_link) {
+                (Some(next_link), _) => url = next_link,
+                (None, Some(delta_link)) => {
+                    *lock(&self.delta_link)? = Some(delta_link);
+                    return Ok(changes);
+                }
+                (None, None) => {
+                    return Err(failed(
+                        "Microsoft Graph answered a delta page without a next or delta link",
+                    ));
+                }
+            }
+        }
+    }
+
+    async fn create(&self, todo: &Todo) -> Result<Option<String>, TodoError> {
+        let created: CreatedTask = self
+            .send(Method::POST, &self.tasks_url(), Some(task_body(todo)))
+            .await?
+            .json()
+            .await
+            .map_err(failed)?;
+        Ok(Some(created.id))
+    }
+
+    async fn update(&self, remote_id: &str, todo: &Todo) -> Result<bool, TodoError> {
+        let url = format!("{}/{}", self.tasks_url(), remote_id);
+        self.send(Method::PATCH, &url, Some(task_body(todo)))
+            .await
+            .map(|_| true)
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> Result<MutexGuard<'_, T>, TodoError> {
+    mutex
+        .lock()
+        .map_err(|_| failed("Microsoft To Do task list lock poisoned"))
+}
+
+fn failed(message: impl ToString) -> TodoError {
+    TodoError::RepositoryError(message.to_string())
+}
@@ -0,0 +1,13 @@
+#[cfg(feature = "caldav")]
+mod caldav_task_list;
+#[cfg(feature = "jira")]
+mod jira_task_list;
+#[cfg(feature = "msgraph")]
+mod microsoft_todo_task_list;
+
+#[cfg(feature = "caldav")]
+pub use caldav_task_list::CalDavTaskList;
+#[cfg(feature = "jira")]
+pub use jira_task_list::JiraTaskList;
+#[cfg(feature = "msgraph")]
+pub use microsoft_todo_task_list::MicrosoftTodoTaskList;
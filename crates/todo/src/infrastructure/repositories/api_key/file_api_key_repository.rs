@@ -0,0 +1,137 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::domain::api_key::{ApiKey, ApiKeyError, ApiKeyRepository, ApiKeyScope};
+
+/// An ApiKey as stored in the JSON file
+#[derive(Serialize, Deserialize)]
+struct ApiKeyRecord {
+    id: String,
+    name: String,
+    scope: String,
+    secret_hash: String,
+    created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    revoked_at: Option<DateTime<Utc>>,
+}
+
+impl ApiKeyRecord {
+    fn from_key(key: &ApiKey) -> Self {
+        ApiKeyRecord {
+            id: key.id.to_string(),
+            name: key.name.to_string(),
+            scope: key.scope.as_str().to_string(),
+            secret_hash: key.secret_hash.clone(),
+            created_at: key.created_at,
+            revoked_at: key.revoked_at,
+        }
+    }
+
+    fn into_key(self) -> Result<ApiKey, ApiKeyError> {
+        Ok(ApiKey {
+            scope: ApiKeyScope::parse(&self.scope).map_err(ApiKeyError::RepositoryError)?,
+            id: self.id.into(),
+            name: self.name.into(),
+            secret_hash: self.secret_hash,
+            created_at: self.created_at,
+            revoked_at: self.revoked_at,
+        })
+    }
+}
+
+/// JSON file implementation of ApiKeyRepository
+///
+/// Stores every key, with the hash of its secret, as a JSON array in a single file that is
+/// read on every call and rewritten through a temporary file on every `save`, like
+/// `FileTodoRepository`. A missing file is treated as an empty repository.
+///
+/// The REST and gRPC servers read the file on every request made with an API key, so
+/// keys issued or revoked by another process, such as `hk-todo api-key`, take effect at
+/// once.
+pub struct FileApiKeyRepository {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl FileApiKeyRepository {
+    /// Creates a new FileApiKeyRepository backed by the file at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileApiKeyRepository {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Path of the backing file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn read_records(&self) -> Result<Vec<ApiKeyRecord>, ApiKeyError> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(storage_error(&self.path, err)),
+        };
+        serde_json::from_str(&contents).map_err(|err| storage_error(&self.path, err))
+    }
+
+    fn write_records(&self, records: &[ApiKeyRecord]) -> Result<(), ApiKeyError> {
+        let contents =
+            serde_json::to_string_pretty(records).map_err(|err| storage_error(&self.path, err))?;
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(".tmp");
+
+        fs::write(&tmp_path, contents).map_err(|err| storage_error(&self.path, err))?;
+        fs::rename(&tmp_path, &self.path).map_err(|err| storage_error(&self.path, err))
+    }
+
+    fn read(&self) -> Result<Vec<ApiKeyRecord>, ApiKeyError> {
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|_| ApiKeyError::RepositoryError("file lock poisoned".to_string()))?;
+        self.read_records()
+    }
+}
+
+fn storage_error(path: &Path, err: impl std::fmt::Display) -> ApiKeyError {
+    ApiKeyError::RepositoryError(format!("{}: {}", path.display(), err))
+}
+
+#[async_trait]
+impl ApiKeyRepository for FileApiKeyRepository {
+    async fn save(&self, key: &ApiKey) -> Result<(), ApiKeyError> {
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|_| ApiKeyError::RepositoryError("file lock poisoned".to_string()))?;
+        let mut records = self.read_records()?;
+        let record = ApiKeyRecord::from_key(key);
+        match records.iter_mut().find(|existing| existing.id == record.id) {
+            Some(existing) => *existing = record,
+            None => records.push(record),
+        }
+        self.write_records(&records)
+    }
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<ApiKey>, ApiKeyError> {
+        self.read()?
+            .into_iter()
+            .find(|record| record.id == id)
+            .map(ApiKeyRecord::into_key)
+            .transpose()
+    }
+
+    async fn find_all(&self) -> Result<Vec<ApiKey>, ApiKeyError> {
+        self.read()?
+            .into_iter()
+            .map(ApiKeyRecord::into_key)
+            .collect()
+    }
+}
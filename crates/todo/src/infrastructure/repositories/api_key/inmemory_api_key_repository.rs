@@ -0,0 +1,47 @@
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use crate::domain::api_key::{ApiKey, ApiKeyError, ApiKeyRepository};
+
+/// In-memory implementation of ApiKeyRepository, whose keys are lost when it is dropped
+#[derive(Default)]
+pub struct InMemoryApiKeyRepository {
+    keys: RwLock<HashMap<Arc<str>, ApiKey>>,
+}
+
+impl InMemoryApiKeyRepository {
+    /// Creates a new, empty InMemoryApiKeyRepository
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+fn poisoned<T>(_: T) -> ApiKeyError {
+    ApiKeyError::RepositoryError("api key lock poisoned".to_string())
+}
+
+#[async_trait]
+impl ApiKeyRepository for InMemoryApiKeyRepository {
+    async fn save(&self, key: &ApiKey) -> Result<(), ApiKeyError> {
+        self.keys
+            .write()
+            .map_err(poisoned)?
+            .insert(key.id.clone(), key.clone());
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<ApiKey>, ApiKeyError> {
+        Ok(self.keys.read().map_err(poisoned)?.get(id).cloned())
+    }
+
+    async fn find_all(&self) -> Result<Vec<ApiKey>, ApiKeyError> {
+        Ok(self
+            .keys
+            .read()
+            .map_err(poisoned)?
+            .values()
+            .cloned()
+            .collect())
+    }
+}
@@ -0,0 +1,7 @@
+#[cfg(feature = "serde")]
+mod file_api_key_repository;
+mod inmemory_api_key_repository;
+
+#[cfg(feature = "serde")]
+pub use file_api_key_repository::FileApiKeyRepository;
+pub use inmemory_api_key_repository::InMemoryApiKeyRepository;
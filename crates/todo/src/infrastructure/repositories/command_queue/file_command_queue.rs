@@ -0,0 +1,294 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::domain::sync::{CommandQueue, QueuedCommand, TodoCommand};
+use crate::{Attachment, Priority, Subtask, Tag, Todo, TodoError, TodoState};
+
+/// A Todo as stored in a queued command
+#[derive(Serialize, Deserialize)]
+struct QueuedTodoRecord {
+    id: String,
+    description: String,
+    state: String,
+    created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    due_at: Option<DateTime<Utc>>,
+    /// `MEDIUM` when missing, as in files written before priorities
+    #[serde(default)]
+    priority: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    subtasks: Vec<SubtaskRecord>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    blocked_by: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    attachments: Vec<AttachmentRecord>,
+}
+
+/// A subtask as stored with its todo
+#[derive(Serialize, Deserialize)]
+struct SubtaskRecord {
+    id: String,
+    description: String,
+    done: bool,
+}
+
+impl SubtaskRecord {
+    fn from_subtask(subtask: &Subtask) -> Self {
+        SubtaskRecord {
+            id: subtask.id.to_string(),
+            description: subtask.description.to_string(),
+            done: subtask.done,
+        }
+    }
+
+    fn into_subtask(self) -> Subtask {
+        Subtask {
+            id: self.id.into(),
+            description: self.description.into(),
+            done: self.done,
+        }
+    }
+}
+
+/// An attachment as stored with its todo
+#[derive(Serialize, Deserialize)]
+struct AttachmentRecord {
+    content_hash: String,
+    name: String,
+    media_type: String,
+    size: u64,
+}
+
+impl AttachmentRecord {
+    fn from_attachment(attachment: &Attachment) -> Self {
+        AttachmentRecord {
+            content_hash: attachment.content_hash.to_string(),
+            name: attachment.name.to_string(),
+            media_type: attachment.media_type.to_string(),
+            size: attachment.size,
+        }
+    }
+
+    fn into_attachment(self) -> Attachment {
+        Attachment {
+            content_hash: self.content_hash.into(),
+            name: self.name.into(),
+            media_type: self.media_type.into(),
+            size: self.size,
+        }
+    }
+}
+
+impl QueuedTodoRecord {
+    fn from_todo(todo: &Todo) -> Self {
+        let state = match todo.state {
+            TodoState::Todo => "TODO",
+            TodoState::InProgress => "IN_PROGRESS",
+            TodoState::Done => "DONE",
+        };
+        QueuedTodoRecord {
+            id: todo.id.to_string(),
+            description: todo.description.to_string(),
+            state: state.to_string(),
+            created_at: todo.created_at,
+            due_at: todo.due_at,
+            priority: Some(todo.priority.name().to_string()),
+            tags: todo.tags.iter().map(ToString::to_string).collect(),
+            subtasks: todo
+                .subtasks
+                .iter()
+                .map(SubtaskRecord::from_subtask)
+                .collect(),
+            blocked_by: todo.blocked_by.iter().map(ToString::to_string).collect(),
+            attachments: todo
+                .attachments
+                .iter()
+                .map(AttachmentRecord::from_attachment)
+                .collect(),
+        }
+    }
+
+    fn into_todo(self) -> Result<Todo, TodoError> {
+        let state = match self.state.as_str() {
+            "TODO" => TodoState::Todo,
+            "IN_PROGRESS" => TodoState::InProgress,
+            "DONE" => TodoState::Done,
+            other => {
+                return Err(TodoError::RepositoryError(format!(
+                    "unknown todo state: {}",
+                    other
+                )));
+            }
+        };
+        let priority = match self.priority {
+            Some(name) => Priority::parse(&name).map_err(TodoError::RepositoryError)?,
+            None => Priority::default(),
+        };
+        let tags = self
+            .tags
+            .iter()
+            .map(|tag| Tag::new(tag))
+            .collect::<Result<_, _>>()
+            .map_err(TodoError::RepositoryError)?;
+        Ok(Todo {
+            id: self.id.into(),
+            created_at: self.created_at,
+            description: self.description.into(),
+            state,
+            due_at: self.due_at,
+            priority,
+            tags,
+            subtasks: self
+                .subtasks
+                .into_iter()
+                .map(SubtaskRecord::into_subtask)
+                .collect(),
+            blocked_by: self.blocked_by.into_iter().map(Into::into).collect(),
+            attachments: self
+                .attachments
+                .into_iter()
+                .map(AttachmentRecord::into_attachment)
+                .collect(),
+            dirty: Some(false),
+        })
+    }
+}
+
+/// A QueuedCommand as stored in the JSON file, saving `todo` or deleting `id`
+#[derive(Serialize, Deserialize)]
+struct CommandRecord {
+    key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    save: Option<QueuedTodoRecord>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    delete: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    base: Option<QueuedTodoRecord>,
+    queued_at: DateTime<Utc>,
+}
+
+impl CommandRecord {
+    fn from_command(queued: &QueuedCommand) -> Self {
+        let (save, delete) = match &queued.command {
+            TodoCommand::Save(todo) => (Some(QueuedTodoRecord::from_todo(todo)), None),
+            TodoCommand::Delete(id) => (None, Some(id.to_string())),
+        };
+        CommandRecord {
+            key: queued.key.to_string(),
+            save,
+            delete,
+            base: queued.base.as_ref().map(QueuedTodoRecord::from_todo),
+            queued_at: queued.queued_at,
+        }
+    }
+
+    fn into_command(self) -> Result<QueuedCommand, TodoError> {
+        let command = match (self.save, self.delete) {
+            (Some(todo), None) => TodoCommand::Save(todo.into_todo()?),
+            (None, Some(id)) => TodoCommand::Delete(id.into()),
+            _ => {
+                return Err(TodoError::RepositoryError(format!(
+                    "queued command {} must either save or delete",
+                    self.key
+                )));
+            }
+        };
+        Ok(QueuedCommand {
+            key: self.key.into(),
+            command,
+            base: self.base.map(QueuedTodoRecord::into_todo).transpose()?,
+            queued_at: self.queued_at,
+        })
+    }
+}
+
+/// JSON file implementation of CommandQueue
+///
+/// Stores the queue as a JSON array in a single file, read on every call and rewritten
+/// through a temporary file on every change, so commands recorded offline survive a restart
+/// of the app. A missing file is treated as an empty queue.
+pub struct FileCommandQueue {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl FileCommandQueue {
+    /// Creates a new FileCommandQueue backed by the file at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileCommandQueue {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Path of the backing file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn read_records(&self) -> Result<Vec<CommandRecord>, TodoError> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(storage_error(&self.path, err)),
+        };
+        serde_json::from_str(&contents).map_err(|err| storage_error(&self.path, err))
+    }
+
+    fn write_records(&self, records: &[CommandRecord]) -> Result<(), TodoError> {
+        let contents =
+            serde_json::to_string_pretty(records).map_err(|err| storage_error(&self.path, err))?;
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(".tmp");
+
+        fs::write(&tmp_path, contents).map_err(|err| storage_error(&self.path, err))?;
+        fs::rename(&tmp_path, &self.path).map_err(|err| storage_error(&self.path, err))
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, ()>, TodoError> {
+        self.lock
+            .lock()
+            .map_err(|_| TodoError::RepositoryError("file lock poisoned".to_string()))
+    }
+}
+
+fn storage_error(path: &Path, err: impl std::fmt::Display) -> TodoError {
+    TodoError::RepositoryError(format!("{}: {}", path.display(), err))
+}
+
+#[async_trait]
+impl CommandQueue for FileCommandQueue {
+    async fn push(&self, command: &QueuedCommand) -> Result<(), TodoError> {
+        let _guard = self.lock()?;
+        let mut records = self.read_records()?;
+        records.push(CommandRecord::from_command(command));
+        self.write_records(&records)
+    }
+
+    async fn find_all(&self) -> Result<Vec<QueuedCommand>, TodoError> {
+        let _guard = self.lock()?;
+        self.read_records()?
+            .into_iter()
+            .map(CommandRecord::into_command)
+            .collect()
+    }
+
+    async fn remove(&self, key: &str) -> Result<bool, TodoError> {
+        let _guard = self.lock()?;
+        let mut records = self.read_records()?;
+        let count = records.len();
+        records.retain(|record| record.key != key);
+        if records.len() == count {
+            return Ok(false);
+        }
+        self.write_records(&records)?;
+        Ok(true)
+    }
+}
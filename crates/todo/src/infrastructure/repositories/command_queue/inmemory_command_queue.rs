@@ -0,0 +1,44 @@
+use async_trait::async_trait;
+use std::sync::RwLock;
+
+use crate::TodoError;
+use crate::domain::sync::{CommandQueue, QueuedCommand};
+
+/// In-memory implementation of CommandQueue, whose commands are lost when it is dropped
+#[derive(Default)]
+pub struct InMemoryCommandQueue {
+    commands: RwLock<Vec<QueuedCommand>>,
+}
+
+impl InMemoryCommandQueue {
+    /// Creates a new, empty InMemoryCommandQueue
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+fn poisoned<T>(_: T) -> TodoError {
+    TodoError::RepositoryError("command queue lock poisoned".to_string())
+}
+
+#[async_trait]
+impl CommandQueue for InMemoryCommandQueue {
+    async fn push(&self, command: &QueuedCommand) -> Result<(), TodoError> {
+        self.commands
+            .write()
+            .map_err(poisoned)?
+            .push(command.clone());
+        Ok(())
+    }
+
+    async fn find_all(&self) -> Result<Vec<QueuedCommand>, TodoError> {
+        Ok(self.commands.read().map_err(poisoned)?.clone())
+    }
+
+    async fn remove(&self, key: &str) -> Result<bool, TodoError> {
+        let mut commands = self.commands.write().map_err(poisoned)?;
+        let count = commands.len();
+        commands.retain(|command| &*command.key != key);
+        Ok(commands.len() < count)
+    }
+}
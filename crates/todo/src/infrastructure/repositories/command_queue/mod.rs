@@ -0,0 +1,7 @@
+#[cfg(feature = "serde")]
+mod file_command_queue;
+mod inmemory_command_queue;
+
+#[cfg(feature = "serde")]
+pub use file_command_queue::FileCommandQueue;
+pub use inmemory_command_queue::InMemoryCommandQueue;
@@ -0,0 +1,59 @@
+use async_trait::async_trait;
+use std::sync::RwLock;
+
+use crate::TodoError;
+use crate::domain::sync::{PendingConflict, PendingConflictRepository};
+
+/// In-memory implementation of PendingConflictRepository, whose conflicts are lost when it
+/// is dropped
+#[derive(Default)]
+pub struct InMemoryPendingConflictRepository {
+    pending: RwLock<Vec<PendingConflict>>,
+}
+
+impl InMemoryPendingConflictRepository {
+    /// Creates a new, empty InMemoryPendingConflictRepository
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+fn poisoned<T>(_: T) -> TodoError {
+    TodoError::RepositoryError("pending conflict lock poisoned".to_string())
+}
+
+#[async_trait]
+impl PendingConflictRepository for InMemoryPendingConflictRepository {
+    async fn save(&self, pending: &PendingConflict) -> Result<(), TodoError> {
+        let mut conflicts = self.pending.write().map_err(poisoned)?;
+        conflicts.retain(|queued| queued.conflict.todo_id() != pending.conflict.todo_id());
+        conflicts.push(pending.clone());
+        Ok(())
+    }
+
+    async fn find(&self, todo_id: &str) -> Result<Option<PendingConflict>, TodoError> {
+        Ok(self
+            .pending
+            .read()
+            .map_err(poisoned)?
+            .iter()
+            .find(|pending| pending.conflict.todo_id() == todo_id)
+            .cloned())
+    }
+
+    async fn find_all(&self) -> Result<Vec<PendingConflict>, TodoError> {
+        let mut conflicts = self.pending.read().map_err(poisoned)?.clone();
+        conflicts.sort_by_key(|pending| pending.detected_at);
+        Ok(conflicts)
+    }
+
+    async fn remove(&self, todo_id: &str) -> Result<(), TodoError> {
+        let mut conflicts = self.pending.write().map_err(poisoned)?;
+        let count = conflicts.len();
+        conflicts.retain(|pending| pending.conflict.todo_id() != todo_id);
+        if conflicts.len() == count {
+            return Err(TodoError::TodoNotFound);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,3 @@
+mod inmemory_pending_conflict_repository;
+
+pub use inmemory_pending_conflict_repository::InMemoryPendingConflictRepository;
@@ -0,0 +1,127 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::domain::notification::{Device, DeviceRepository, NotificationError, PushPlatform};
+
+/// A Device as stored in the JSON file
+#[derive(Serialize, Deserialize)]
+struct DeviceRecord {
+    token: String,
+    platform: String,
+    subject: String,
+    registered_at: DateTime<Utc>,
+}
+
+impl DeviceRecord {
+    fn from_device(device: &Device) -> Self {
+        DeviceRecord {
+            token: device.token.to_string(),
+            platform: device.platform.as_str().to_string(),
+            subject: device.subject.to_string(),
+            registered_at: device.registered_at,
+        }
+    }
+
+    fn into_device(self) -> Result<Device, NotificationError> {
+        let platform =
+            PushPlatform::parse(&self.platform).map_err(NotificationError::RepositoryError)?;
+        Device::new(self.token, platform, self.subject, self.registered_at)
+    }
+}
+
+/// JSON file implementation of DeviceRepository
+///
+/// Stores every device as a JSON array of `{"token", "platform", "subject",
+/// "registered_at"}` objects in a single file, read on every call and rewritten through a
+/// temporary file on every change, like `FileMembershipRepository`. A missing file is
+/// treated as an empty repository.
+pub struct FileDeviceRepository {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl FileDeviceRepository {
+    /// Creates a new FileDeviceRepository backed by the file at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileDeviceRepository {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Path of the backing file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn read_records(&self) -> Result<Vec<DeviceRecord>, NotificationError> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(storage_error(&self.path, err)),
+        };
+        serde_json::from_str(&contents).map_err(|err| storage_error(&self.path, err))
+    }
+
+    fn write_records(&self, records: &[DeviceRecord]) -> Result<(), NotificationError> {
+        let contents =
+            serde_json::to_string_pretty(records).map_err(|err| storage_error(&self.path, err))?;
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(".tmp");
+
+        fs::write(&tmp_path, contents).map_err(|err| storage_error(&self.path, err))?;
+        fs::rename(&tmp_path, &self.path).map_err(|err| storage_error(&self.path, err))
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, ()>, NotificationError> {
+        self.lock
+            .lock()
+            .map_err(|_| NotificationError::RepositoryError("file lock poisoned".to_string()))
+    }
+}
+
+fn storage_error(path: &Path, err: impl std::fmt::Display) -> NotificationError {
+    NotificationError::RepositoryError(format!("{}: {}", path.display(), err))
+}
+
+#[async_trait]
+impl DeviceRepository for FileDeviceRepository {
+    async fn save(&self, device: &Device) -> Result<(), NotificationError> {
+        let _guard = self.lock()?;
+        let mut records = self.read_records()?;
+        let record = DeviceRecord::from_device(device);
+        match records
+            .iter_mut()
+            .find(|existing| existing.token == record.token)
+        {
+            Some(existing) => *existing = record,
+            None => records.push(record),
+        }
+        self.write_records(&records)
+    }
+
+    async fn remove(&self, token: &str) -> Result<(), NotificationError> {
+        let _guard = self.lock()?;
+        let mut records = self.read_records()?;
+        let count = records.len();
+        records.retain(|record| record.token != token);
+        if records.len() == count {
+            return Err(NotificationError::DeviceNotFound);
+        }
+        self.write_records(&records)
+    }
+
+    async fn find_by_subject(&self, subject: &str) -> Result<Vec<Device>, NotificationError> {
+        let _guard = self.lock()?;
+        self.read_records()?
+            .into_iter()
+            .filter(|record| record.subject == subject)
+            .map(DeviceRecord::into_device)
+            .collect()
+    }
+}
@@ -0,0 +1,58 @@
+use async_trait::async_trait;
+use std::sync::RwLock;
+
+use crate::domain::notification::{Device, DeviceRepository, NotificationError};
+
+/// In-memory implementation of DeviceRepository, whose devices are lost when it is dropped
+#[derive(Default)]
+pub struct InMemoryDeviceRepository {
+    /// In registration order, so `find_by_subject` returns the oldest first
+    devices: RwLock<Vec<Device>>,
+}
+
+impl InMemoryDeviceRepository {
+    /// Creates a new, empty InMemoryDeviceRepository
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+fn poisoned<T>(_: T) -> NotificationError {
+    NotificationError::RepositoryError("device lock poisoned".to_string())
+}
+
+#[async_trait]
+impl DeviceRepository for InMemoryDeviceRepository {
+    async fn save(&self, device: &Device) -> Result<(), NotificationError> {
+        let mut devices = self.devices.write().map_err(poisoned)?;
+        match devices
+            .iter_mut()
+            .find(|existing| existing.token == device.token)
+        {
+            Some(existing) => *existing = device.clone(),
+            None => devices.push(device.clone()),
+        }
+        Ok(())
+    }
+
+    async fn remove(&self, token: &str) -> Result<(), NotificationError> {
+        let mut devices = self.devices.write().map_err(poisoned)?;
+        let count = devices.len();
+        devices.retain(|device| &*device.token != token);
+        if devices.len() == count {
+            return Err(NotificationError::DeviceNotFound);
+        }
+        Ok(())
+    }
+
+    async fn find_by_subject(&self, subject: &str) -> Result<Vec<Device>, NotificationError> {
+        Ok(self
+            .devices
+            .read()
+            .map_err(poisoned)?
+            .iter()
+            .filter(|device| &*device.subject == subject)
+            .cloned()
+            .collect())
+    }
+}
@@ -0,0 +1,7 @@
+#[cfg(feature = "serde")]
+mod file_device_repository;
+mod inmemory_device_repository;
+
+#[cfg(feature = "serde")]
+pub use file_device_repository::FileDeviceRepository;
+pub use inmemory_device_repository::InMemoryDeviceRepository;
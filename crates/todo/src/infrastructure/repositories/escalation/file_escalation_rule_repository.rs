@@ -0,0 +1,136 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::domain::escalation::{EscalationRule, EscalationRuleRepository};
+use crate::{TodoError, TodoState};
+
+/// An EscalationRule as stored in the JSON file
+#[derive(Serialize, Deserialize)]
+struct RuleRecord {
+    id: String,
+    state: String,
+    after_days: u32,
+}
+
+impl RuleRecord {
+    fn from_rule(rule: &EscalationRule) -> Self {
+        let state = match rule.state {
+            TodoState::Todo => "TODO",
+            TodoState::InProgress => "IN_PROGRESS",
+            TodoState::Done => "DONE",
+        };
+        RuleRecord {
+            id: rule.id.to_string(),
+            state: state.to_string(),
+            after_days: rule.after_days,
+        }
+    }
+
+    fn into_rule(self) -> Result<EscalationRule, TodoError> {
+        let state = match self.state.as_str() {
+            "TODO" => TodoState::Todo,
+            "IN_PROGRESS" => TodoState::InProgress,
+            "DONE" => TodoState::Done,
+            other => {
+                return Err(TodoError::RepositoryError(format!(
+                    "unknown todo state: {}",
+                    other
+                )));
+            }
+        };
+        EscalationRule::new(self.id, state, self.after_days).map_err(TodoError::RepositoryError)
+    }
+}
+
+/// JSON file implementation of EscalationRuleRepository
+///
+/// Stores every rule as a JSON array of `{"id", "state", "after_days"}` objects in a single
+/// file, read on every call and rewritten through a temporary file on every change, like
+/// `FileMembershipRepository`. A missing file is treated as having no rules, so the servers
+/// apply rules added by another process, such as `hk-todo escalation`, on their next run.
+pub struct FileEscalationRuleRepository {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl FileEscalationRuleRepository {
+    /// Creates a new FileEscalationRuleRepository backed by the file at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileEscalationRuleRepository {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Path of the backing file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn read_records(&self) -> Result<Vec<RuleRecord>, TodoError> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(storage_error(&self.path, err)),
+        };
+        serde_json::from_str(&contents).map_err(|err| storage_error(&self.path, err))
+    }
+
+    fn write_records(&self, records: &[RuleRecord]) -> Result<(), TodoError> {
+        let contents =
+            serde_json::to_string_pretty(records).map_err(|err| storage_error(&self.path, err))?;
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(".tmp");
+
+        fs::write(&tmp_path, contents).map_err(|err| storage_error(&self.path, err))?;
+        fs::rename(&tmp_path, &self.path).map_err(|err| storage_error(&self.path, err))
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, ()>, TodoError> {
+        self.lock
+            .lock()
+            .map_err(|_| TodoError::RepositoryError("file lock poisoned".to_string()))
+    }
+}
+
+fn storage_error(path: &Path, err: impl std::fmt::Display) -> TodoError {
+    TodoError::RepositoryError(format!("{}: {}", path.display(), err))
+}
+
+#[async_trait]
+impl EscalationRuleRepository for FileEscalationRuleRepository {
+    async fn save(&self, rule: &EscalationRule) -> Result<(), TodoError> {
+        let _guard = self.lock()?;
+        let mut records = self.read_records()?;
+        let record = RuleRecord::from_rule(rule);
+        match records.iter_mut().find(|existing| existing.id == record.id) {
+            Some(existing) => *existing = record,
+            None => records.push(record),
+        }
+        self.write_records(&records)
+    }
+
+    async fn remove(&self, id: &str) -> Result<bool, TodoError> {
+        let _guard = self.lock()?;
+        let mut records = self.read_records()?;
+        let count = records.len();
+        records.retain(|record| record.id != id);
+        if records.len() == count {
+            return Ok(false);
+        }
+        self.write_records(&records)?;
+        Ok(true)
+    }
+
+    async fn find_all(&self) -> Result<Vec<EscalationRule>, TodoError> {
+        let _guard = self.lock()?;
+        self.read_records()?
+            .into_iter()
+            .map(RuleRecord::into_rule)
+            .collect()
+    }
+}
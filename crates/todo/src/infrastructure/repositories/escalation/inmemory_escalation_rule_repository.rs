@@ -0,0 +1,46 @@
+use async_trait::async_trait;
+use std::sync::RwLock;
+
+use crate::TodoError;
+use crate::domain::escalation::{EscalationRule, EscalationRuleRepository};
+
+/// In-memory implementation of EscalationRuleRepository, whose rules are lost when it is
+/// dropped
+#[derive(Default)]
+pub struct InMemoryEscalationRuleRepository {
+    rules: RwLock<Vec<EscalationRule>>,
+}
+
+impl InMemoryEscalationRuleRepository {
+    /// Creates a new, empty InMemoryEscalationRuleRepository
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+fn poisoned<T>(_: T) -> TodoError {
+    TodoError::RepositoryError("escalation rule lock poisoned".to_string())
+}
+
+#[async_trait]
+impl EscalationRuleRepository for InMemoryEscalationRuleRepository {
+    async fn save(&self, rule: &EscalationRule) -> Result<(), TodoError> {
+        let mut rules = self.rules.write().map_err(poisoned)?;
+        match rules.iter_mut().find(|existing| existing.id == rule.id) {
+            Some(existing) => *existing = rule.clone(),
+            None => rules.push(rule.clone()),
+        }
+        Ok(())
+    }
+
+    async fn remove(&self, id: &str) -> Result<bool, TodoError> {
+        let mut rules = self.rules.write().map_err(poisoned)?;
+        let count = rules.len();
+        rules.retain(|rule| &*rule.id != id);
+        Ok(rules.len() != count)
+    }
+
+    async fn find_all(&self) -> Result<Vec<EscalationRule>, TodoError> {
+        Ok(self.rules.read().map_err(poisoned)?.clone())
+    }
+}
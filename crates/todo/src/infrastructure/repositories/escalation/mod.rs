@@ -0,0 +1,7 @@
+#[cfg(feature = "serde")]
+mod file_escalation_rule_repository;
+mod inmemory_escalation_rule_repository;
+
+#[cfg(feature = "serde")]
+pub use file_escalation_rule_repository::FileEscalationRuleRepository;
+pub use inmemory_escalation_rule_repository::InMemoryEscalationRuleRepository;
@@ -0,0 +1,163 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::domain::import::{ImportMapping, ImportMappingRepository};
+use crate::{TodoError, TodoState};
+
+/// An ImportMapping as stored in the JSON file
+#[derive(Serialize, Deserialize)]
+struct MappingRecord {
+    source: String,
+    external_id: String,
+    todo_id: String,
+    description: String,
+    state: String,
+    imported_at: DateTime<Utc>,
+}
+
+impl MappingRecord {
+    fn from_mapping(mapping: &ImportMapping) -> Self {
+        let state = match mapping.state {
+            TodoState::Todo => "TODO",
+            TodoState::InProgress => "IN_PROGRESS",
+            TodoState::Done => "DONE",
+        };
+        MappingRecord {
+            source: mapping.source.to_string(),
+            external_id: mapping.external_id.to_string(),
+            todo_id: mapping.todo_id.to_string(),
+            description: mapping.description.to_string(),
+            state: state.to_string(),
+            imported_at: mapping.imported_at,
+        }
+    }
+
+    fn into_mapping(self) -> Result<ImportMapping, TodoError> {
+        let state = match self.state.as_str() {
+            "TODO" => TodoState::Todo,
+            "IN_PROGRESS" => TodoState::InProgress,
+            "DONE" => TodoState::Done,
+            other => {
+                return Err(TodoError::RepositoryError(format!(
+                    "unknown todo state: {}",
+                    other
+                )));
+            }
+        };
+        Ok(ImportMapping {
+            source: self.source.into(),
+            external_id: self.external_id.into(),
+            todo_id: self.todo_id.into(),
+            description: self.description.into(),
+            state,
+            imported_at: self.imported_at,
+        })
+    }
+
+    fn is(&self, source: &str, external_id: &str) -> bool {
+        self.source == source && self.external_id == external_id
+    }
+}
+
+/// JSON file implementation of ImportMappingRepository
+///
+/// Stores every mapping as a JSON array in a single file, read on every call and rewritten
+/// through a temporary file on every change, like `FileMembershipRepository`. A missing
+/// file is treated as an empty repository, so the first import creates it.
+pub struct FileImportMappingRepository {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl FileImportMappingRepository {
+    /// Creates a new FileImportMappingRepository backed by the file at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileImportMappingRepository {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Path of the backing file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn read_records(&self) -> Result<Vec<MappingRecord>, TodoError> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(storage_error(&self.path, err)),
+        };
+        serde_json::from_str(&contents).map_err(|err| storage_error(&self.path, err))
+    }
+
+    fn write_records(&self, records: &[MappingRecord]) -> Result<(), TodoError> {
+        let contents =
+            serde_json::to_string_pretty(records).map_err(|err| storage_error(&self.path, err))?;
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(".tmp");
+
+        fs::write(&tmp_path, contents).map_err(|err| storage_error(&self.path, err))?;
+        fs::rename(&tmp_path, &self.path).map_err(|err| storage_error(&self.path, err))
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, ()>, TodoError> {
+        self.lock
+            .lock()
+            .map_err(|_| TodoError::RepositoryError("file lock poisoned".to_string()))
+    }
+}
+
+fn storage_error(path: &Path, err: impl std::fmt::Display) -> TodoError {
+    TodoError::RepositoryError(format!("{}: {}", path.display(), err))
+}
+
+#[async_trait]
+impl ImportMappingRepository for FileImportMappingRepository {
+    async fn save_all(&self, mappings: &[ImportMapping]) -> Result<(), TodoError> {
+        if mappings.is_empty() {
+            return Ok(());
+        }
+        let _guard = self.lock()?;
+        let mut records = self.read_records()?;
+        for mapping in mappings {
+            let record = MappingRecord::from_mapping(mapping);
+            match records
+                .iter_mut()
+                .find(|existing| existing.is(&mapping.source, &mapping.external_id))
+            {
+                Some(existing) => *existing = record,
+                None => records.push(record),
+            }
+        }
+        self.write_records(&records)
+    }
+
+    async fn find(
+        &self,
+        source: &str,
+        external_id: &str,
+    ) -> Result<Option<ImportMapping>, TodoError> {
+        let _guard = self.lock()?;
+        self.read_records()?
+            .into_iter()
+            .find(|record| record.is(source, external_id))
+            .map(MappingRecord::into_mapping)
+            .transpose()
+    }
+
+    async fn find_by_source(&self, source: &str) -> Result<Vec<ImportMapping>, TodoError> {
+        let _guard = self.lock()?;
+        self.read_records()?
+            .into_iter()
+            .filter(|record| record.source == source)
+            .map(MappingRecord::into_mapping)
+            .collect()
+    }
+}
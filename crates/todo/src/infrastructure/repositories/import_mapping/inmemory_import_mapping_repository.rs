@@ -0,0 +1,64 @@
+use async_trait::async_trait;
+use std::sync::RwLock;
+
+use crate::TodoError;
+use crate::domain::import::{ImportMapping, ImportMappingRepository};
+
+/// In-memory implementation of ImportMappingRepository, whose mappings are lost when it is
+/// dropped
+#[derive(Default)]
+pub struct InMemoryImportMappingRepository {
+    mappings: RwLock<Vec<ImportMapping>>,
+}
+
+impl InMemoryImportMappingRepository {
+    /// Creates a new, empty InMemoryImportMappingRepository
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+fn poisoned<T>(_: T) -> TodoError {
+    TodoError::RepositoryError("import mapping lock poisoned".to_string())
+}
+
+#[async_trait]
+impl ImportMappingRepository for InMemoryImportMappingRepository {
+    async fn save_all(&self, mappings: &[ImportMapping]) -> Result<(), TodoError> {
+        let mut stored = self.mappings.write().map_err(poisoned)?;
+        for mapping in mappings {
+            match stored.iter_mut().find(|existing| {
+                existing.source == mapping.source && existing.external_id == mapping.external_id
+            }) {
+                Some(existing) => *existing = mapping.clone(),
+                None => stored.push(mapping.clone()),
+            }
+        }
+        Ok(())
+    }
+
+    async fn find(
+        &self,
+        source: &str,
+        external_id: &str,
+    ) -> Result<Option<ImportMapping>, TodoError> {
+        Ok(self
+            .mappings
+            .read()
+            .map_err(poisoned)?
+            .iter()
+            .find(|mapping| &*mapping.source == source && &*mapping.external_id == external_id)
+            .cloned())
+    }
+
+    async fn find_by_source(&self, source: &str) -> Result<Vec<ImportMapping>, TodoError> {
+        Ok(self
+            .mappings
+            .read()
+            .map_err(poisoned)?
+            .iter()
+            .filter(|mapping| &*mapping.source == source)
+            .cloned()
+            .collect())
+    }
+}
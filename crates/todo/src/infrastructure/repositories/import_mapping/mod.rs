@@ -0,0 +1,7 @@
+#[cfg(feature = "serde")]
+mod file_import_mapping_repository;
+mod inmemory_import_mapping_repository;
+
+#[cfg(feature = "serde")]
+pub use file_import_mapping_repository::FileImportMappingRepository;
+pub use inmemory_import_mapping_repository::InMemoryImportMappingRepository;
@@ -0,0 +1,152 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::TenantId;
+use crate::domain::access::{
+    AccessError, Invitation, InvitationRepository, InvitationStatus, Role,
+};
+
+/// An Invitation as stored in the JSON file
+#[derive(Serialize, Deserialize)]
+struct InvitationRecord {
+    id: String,
+    list: String,
+    invitee: String,
+    role: String,
+    invited_by: String,
+    status: String,
+    created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    answered_at: Option<DateTime<Utc>>,
+}
+
+impl InvitationRecord {
+    fn from_invitation(invitation: &Invitation) -> Self {
+        InvitationRecord {
+            id: invitation.id.to_string(),
+            list: invitation.list.to_string(),
+            invitee: invitation.invitee.to_string(),
+            role: invitation.role.as_str().to_string(),
+            invited_by: invitation.invited_by.to_string(),
+            status: invitation.status.as_str().to_string(),
+            created_at: invitation.created_at,
+            answered_at: invitation.answered_at,
+        }
+    }
+
+    fn into_invitation(self) -> Result<Invitation, AccessError> {
+        Ok(Invitation {
+            id: self.id.into(),
+            list: TenantId::new(self.list).map_err(AccessError::RepositoryError)?,
+            invitee: self.invitee.into(),
+            role: Role::parse(&self.role).map_err(AccessError::RepositoryError)?,
+            invited_by: self.invited_by.into(),
+            status: InvitationStatus::parse(&self.status).map_err(AccessError::RepositoryError)?,
+            created_at: self.created_at,
+            answered_at: self.answered_at,
+        })
+    }
+}
+
+/// JSON file implementation of InvitationRepository
+///
+/// Stores every invitation as a JSON array of objects in a single file, read on every call
+/// and rewritten through a temporary file on every change, like `FileMembershipRepository`.
+/// A missing file is treated as an empty repository.
+pub struct FileInvitationRepository {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl FileInvitationRepository {
+    /// Creates a new FileInvitationRepository backed by the file at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileInvitationRepository {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Path of the backing file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn read_records(&self) -> Result<Vec<InvitationRecord>, AccessError> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(storage_error(&self.path, err)),
+        };
+        serde_json::from_str(&contents).map_err(|err| storage_error(&self.path, err))
+    }
+
+    fn write_records(&self, records: &[InvitationRecord]) -> Result<(), AccessError> {
+        let contents =
+            serde_json::to_string_pretty(records).map_err(|err| storage_error(&self.path, err))?;
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(".tmp");
+
+        fs::write(&tmp_path, contents).map_err(|err| storage_error(&self.path, err))?;
+        fs::rename(&tmp_path, &self.path).map_err(|err| storage_error(&self.path, err))
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, ()>, AccessError> {
+        self.lock
+            .lock()
+            .map_err(|_| AccessError::RepositoryError("file lock poisoned".to_string()))
+    }
+
+    fn find_where(
+        &self,
+        predicate: impl Fn(&Invitation) -> bool,
+    ) -> Result<Vec<Invitation>, AccessError> {
+        let _guard = self.lock()?;
+        let mut found = Vec::new();
+        for record in self.read_records()? {
+            let invitation = record.into_invitation()?;
+            if predicate(&invitation) {
+                found.push(invitation);
+            }
+        }
+        Ok(found)
+    }
+}
+
+fn storage_error(path: &Path, err: impl std::fmt::Display) -> AccessError {
+    AccessError::RepositoryError(format!("{}: {}", path.display(), err))
+}
+
+#[async_trait]
+impl InvitationRepository for FileInvitationRepository {
+    async fn save(&self, invitation: &Invitation) -> Result<(), AccessError> {
+        let _guard = self.lock()?;
+        let mut records = self.read_records()?;
+        let record = InvitationRecord::from_invitation(invitation);
+        match records
+            .iter_mut()
+            .find(|existing| existing.id == *invitation.id)
+        {
+            Some(existing) => *existing = record,
+            None => records.push(record),
+        }
+        self.write_records(&records)
+    }
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<Invitation>, AccessError> {
+        Ok(self.find_where(|invitation| &*invitation.id == id)?.pop())
+    }
+
+    async fn find_by_list(&self, list: &TenantId) -> Result<Vec<Invitation>, AccessError> {
+        self.find_where(|invitation| &invitation.list == list)
+    }
+
+    async fn find_by_invitee(&self, subject: &str) -> Result<Vec<Invitation>, AccessError> {
+        self.find_where(|invitation| invitation.is_for(subject))
+    }
+}
@@ -0,0 +1,64 @@
+use async_trait::async_trait;
+use std::sync::RwLock;
+
+use crate::TenantId;
+use crate::domain::access::{AccessError, Invitation, InvitationRepository};
+
+/// In-memory implementation of InvitationRepository, whose invitations are lost when it is
+/// dropped
+#[derive(Default)]
+pub struct InMemoryInvitationRepository {
+    invitations: RwLock<Vec<Invitation>>,
+}
+
+impl InMemoryInvitationRepository {
+    /// Creates a new, empty InMemoryInvitationRepository
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn find_where(
+        &self,
+        predicate: impl Fn(&Invitation) -> bool,
+    ) -> Result<Vec<Invitation>, AccessError> {
+        Ok(self
+            .invitations
+            .read()
+            .map_err(poisoned)?
+            .iter()
+            .filter(|invitation| predicate(invitation))
+            .cloned()
+            .collect())
+    }
+}
+
+fn poisoned<T>(_: T) -> AccessError {
+    AccessError::RepositoryError("invitation lock poisoned".to_string())
+}
+
+#[async_trait]
+impl InvitationRepository for InMemoryInvitationRepository {
+    async fn save(&self, invitation: &Invitation) -> Result<(), AccessError> {
+        let mut invitations = self.invitations.write().map_err(poisoned)?;
+        match invitations
+            .iter_mut()
+            .find(|existing| existing.id == invitation.id)
+        {
+            Some(existing) => *existing = invitation.clone(),
+            None => invitations.push(invitation.clone()),
+        }
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<Invitation>, AccessError> {
+        Ok(self.find_where(|invitation| &*invitation.id == id)?.pop())
+    }
+
+    async fn find_by_list(&self, list: &TenantId) -> Result<Vec<Invitation>, AccessError> {
+        self.find_where(|invitation| &invitation.list == list)
+    }
+
+    async fn find_by_invitee(&self, subject: &str) -> Result<Vec<Invitation>, AccessError> {
+        self.find_where(|invitation| invitation.is_for(subject))
+    }
+}
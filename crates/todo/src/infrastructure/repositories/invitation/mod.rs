@@ -0,0 +1,7 @@
+#[cfg(feature = "serde")]
+mod file_invitation_repository;
+mod inmemory_invitation_repository;
+
+#[cfg(feature = "serde")]
+pub use file_invitation_repository::FileInvitationRepository;
+pub use inmemory_invitation_repository::InMemoryInvitationRepository;
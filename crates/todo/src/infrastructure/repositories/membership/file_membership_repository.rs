@@ -0,0 +1,153 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::TenantId;
+use crate::domain::access::{AccessError, Membership, MembershipRepository, Role};
+
+/// A Membership as stored in the JSON file
+#[derive(Serialize, Deserialize)]
+struct MembershipRecord {
+    list: String,
+    subject: String,
+    role: String,
+}
+
+impl MembershipRecord {
+    fn from_membership(membership: &Membership) -> Self {
+        MembershipRecord {
+            list: membership.list.to_string(),
+            subject: membership.subject.to_string(),
+            role: membership.role.as_str().to_string(),
+        }
+    }
+
+    fn into_membership(self) -> Result<Membership, AccessError> {
+        Ok(Membership {
+            list: TenantId::new(self.list).map_err(AccessError::RepositoryError)?,
+            subject: self.subject.into(),
+            role: Role::parse(&self.role).map_err(AccessError::RepositoryError)?,
+        })
+    }
+
+    fn is(&self, list: &TenantId, subject: &str) -> bool {
+        self.list == list.as_str() && self.subject == subject
+    }
+}
+
+/// JSON file implementation of MembershipRepository
+///
+/// Stores every membership as a JSON array of `{"list", "subject", "role"}` objects in a
+/// single file, read on every call and rewritten through a temporary file on every change,
+/// like `FileApiKeyRepository`. A missing file is treated as an empty repository, so the
+/// servers see roles granted by another process, such as `hk-todo role`, at once.
+pub struct FileMembershipRepository {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl FileMembershipRepository {
+    /// Creates a new FileMembershipRepository backed by the file at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileMembershipRepository {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Path of the backing file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn read_records(&self) -> Result<Vec<MembershipRecord>, AccessError> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(storage_error(&self.path, err)),
+        };
+        serde_json::from_str(&contents).map_err(|err| storage_error(&self.path, err))
+    }
+
+    fn write_records(&self, records: &[MembershipRecord]) -> Result<(), AccessError> {
+        let contents =
+            serde_json::to_string_pretty(records).map_err(|err| storage_error(&self.path, err))?;
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(".tmp");
+
+        fs::write(&tmp_path, contents).map_err(|err| storage_error(&self.path, err))?;
+        fs::rename(&tmp_path, &self.path).map_err(|err| storage_error(&self.path, err))
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, ()>, AccessError> {
+        self.lock
+            .lock()
+            .map_err(|_| AccessError::RepositoryError("file lock poisoned".to_string()))
+    }
+}
+
+fn storage_error(path: &Path, err: impl std::fmt::Display) -> AccessError {
+    AccessError::RepositoryError(format!("{}: {}", path.display(), err))
+}
+
+#[async_trait]
+impl MembershipRepository for FileMembershipRepository {
+    async fn save(&self, membership: &Membership) -> Result<(), AccessError> {
+        let _guard = self.lock()?;
+        let mut records = self.read_records()?;
+        let record = MembershipRecord::from_membership(membership);
+        match records
+            .iter_mut()
+            .find(|existing| existing.is(&membership.list, &membership.subject))
+        {
+            Some(existing) => *existing = record,
+            None => records.push(record),
+        }
+        self.write_records(&records)
+    }
+
+    async fn remove(&self, list: &TenantId, subject: &str) -> Result<(), AccessError> {
+        let _guard = self.lock()?;
+        let mut records = self.read_records()?;
+        let count = records.len();
+        records.retain(|record| !record.is(list, subject));
+        if records.len() == count {
+            return Err(AccessError::MembershipNotFound);
+        }
+        self.write_records(&records)
+    }
+
+    async fn find(
+        &self,
+        list: &TenantId,
+        subject: &str,
+    ) -> Result<Option<Membership>, AccessError> {
+        let _guard = self.lock()?;
+        self.read_records()?
+            .into_iter()
+            .find(|record| record.is(list, subject))
+            .map(MembershipRecord::into_membership)
+            .transpose()
+    }
+
+    async fn find_by_list(&self, list: &TenantId) -> Result<Vec<Membership>, AccessError> {
+        let _guard = self.lock()?;
+        self.read_records()?
+            .into_iter()
+            .filter(|record| record.list == list.as_str())
+            .map(MembershipRecord::into_membership)
+            .collect()
+    }
+
+    async fn find_by_subject(&self, subject: &str) -> Result<Vec<Membership>, AccessError> {
+        let _guard = self.lock()?;
+        self.read_records()?
+            .into_iter()
+            .filter(|record| record.subject == subject)
+            .map(MembershipRecord::into_membership)
+            .collect()
+    }
+}
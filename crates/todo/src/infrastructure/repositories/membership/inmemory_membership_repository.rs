@@ -0,0 +1,78 @@
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use crate::TenantId;
+use crate::domain::access::{AccessError, Membership, MembershipRepository};
+
+/// In-memory implementation of MembershipRepository, whose roles are lost when it is dropped
+#[derive(Default)]
+pub struct InMemoryMembershipRepository {
+    memberships: RwLock<HashMap<(TenantId, Arc<str>), Membership>>,
+}
+
+impl InMemoryMembershipRepository {
+    /// Creates a new, empty InMemoryMembershipRepository
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+fn poisoned<T>(_: T) -> AccessError {
+    AccessError::RepositoryError("membership lock poisoned".to_string())
+}
+
+#[async_trait]
+impl MembershipRepository for InMemoryMembershipRepository {
+    async fn save(&self, membership: &Membership) -> Result<(), AccessError> {
+        self.memberships.write().map_err(poisoned)?.insert(
+            (membership.list.clone(), membership.subject.clone()),
+            membership.clone(),
+        );
+        Ok(())
+    }
+
+    async fn remove(&self, list: &TenantId, subject: &str) -> Result<(), AccessError> {
+        self.memberships
+            .write()
+            .map_err(poisoned)?
+            .remove(&(list.clone(), subject.into()))
+            .map(|_| ())
+            .ok_or(AccessError::MembershipNotFound)
+    }
+
+    async fn find(
+        &self,
+        list: &TenantId,
+        subject: &str,
+    ) -> Result<Option<Membership>, AccessError> {
+        Ok(self
+            .memberships
+            .read()
+            .map_err(poisoned)?
+            .get(&(list.clone(), subject.into()))
+            .cloned())
+    }
+
+    async fn find_by_list(&self, list: &TenantId) -> Result<Vec<Membership>, AccessError> {
+        Ok(self
+            .memberships
+            .read()
+            .map_err(poisoned)?
+            .values()
+            .filter(|membership| &membership.list == list)
+            .cloned()
+            .collect())
+    }
+
+    async fn find_by_subject(&self, subject: &str) -> Result<Vec<Membership>, AccessError> {
+        Ok(self
+            .memberships
+            .read()
+            .map_err(poisoned)?
+            .values()
+            .filter(|membership| &*membership.subject == subject)
+            .cloned()
+            .collect())
+    }
+}
@@ -0,0 +1,7 @@
+#[cfg(feature = "serde")]
+mod file_membership_repository;
+mod inmemory_membership_repository;
+
+#[cfg(feature = "serde")]
+pub use file_membership_repository::FileMembershipRepository;
+pub use inmemory_membership_repository::InMemoryMembershipRepository;
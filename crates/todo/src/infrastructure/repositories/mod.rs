@@ -1,2 +1,10 @@
+pub mod api_key;
+pub mod command_queue;
+pub mod conflict;
+pub mod device;
+pub mod escalation;
+pub mod import_mapping;
+pub mod invitation;
+pub mod membership;
 pub mod todo;
-
+pub mod trash;
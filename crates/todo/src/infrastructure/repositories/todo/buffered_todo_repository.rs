@@ -0,0 +1,123 @@
+use crate::domain::todo::{Todo, TodoError, TodoRepository};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Write-behind implementation of TodoRepository
+///
+/// Wraps another repository and buffers every `save` in memory, keeping only the latest
+/// version of each todo, so rapid successive saves (such as reordering many todos) reach
+/// the inner repository as one `save_all` call. Reads see the buffered todos layered on top
+/// of the inner repository; `delete` drops the buffered todo and deletes through at once.
+///
+/// The buffer is flushed when it holds `capacity` todos and by an explicit `flush`. The
+/// crate has no runtime, so callers wanting a time bound call `flush` from their own timer.
+/// Buffered saves are lost if the repository is dropped without flushing.
+pub struct BufferedTodoRepository {
+    inner: Arc<dyn TodoRepository>,
+    capacity: usize,
+    pending: Mutex<Vec<Todo>>,
+}
+
+impl BufferedTodoRepository {
+    /// Creates a new BufferedTodoRepository flushing to `inner` once `capacity` todos are
+    /// buffered
+    pub fn new(inner: Arc<dyn TodoRepository>, capacity: usize) -> Self {
+        BufferedTodoRepository {
+            inner,
+            capacity: capacity.max(1),
+            pending: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Writes the buffered todos to the inner repository
+    ///
+    /// If the inner repository fails, the todos stay buffered and the next `flush` retries
+    /// them.
+    pub async fn flush(&self) -> Result<(), TodoError> {
+        let pending = std::mem::take(&mut *self.lock_pending()?);
+        if pending.is_empty() {
+            return Ok(());
+        }
+        if let Err(err) = self.inner.save_all(&pending).await {
+            let mut buffered = self.lock_pending()?;
+            let newer = std::mem::replace(&mut *buffered, pending);
+            for todo in newer {
+                buffer(&mut buffered, todo);
+            }
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    /// Number of todos waiting to be flushed
+    pub fn pending(&self) -> Result<usize, TodoError> {
+        Ok(self.lock_pending()?.len())
+    }
+
+    fn lock_pending(&self) -> Result<std::sync::MutexGuard<'_, Vec<Todo>>, TodoError> {
+        self.pending
+            .lock()
+            .map_err(|_| TodoError::RepositoryError("write buffer lock poisoned".to_string()))
+    }
+}
+
+/// Adds `todo` to the buffer, replacing an older version of it
+fn buffer(pending: &mut Vec<Todo>, todo: Todo) {
+    match pending.iter_mut().find(|buffered| buffered.id == todo.id) {
+        Some(buffered) => *buffered = todo,
+        None => pending.push(todo),
+    }
+}
+
+#[async_trait]
+impl TodoRepository for BufferedTodoRepository {
+    async fn save(&self, todo: &Todo) -> Result<(), TodoError> {
+        let mut saved = todo.clone();
+        saved.dirty = Some(false);
+        let full = {
+            let mut pending = self.lock_pending()?;
+            buffer(&mut pending, saved);
+            pending.len() >= self.capacity
+        };
+        if full {
+            self.flush().await?;
+        }
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<Todo>, TodoError> {
+        let buffered = self
+            .lock_pending()?
+            .iter()
+            .find(|todo| &*todo.id == id)
+            .cloned();
+        match buffered {
+            Some(todo) => Ok(Some(todo)),
+            None => self.inner.find_by_id(id).await,
+        }
+    }
+
+    async fn find_all(&self) -> Result<Vec<Todo>, TodoError> {
+        let mut todos: HashMap<Arc<str>, Todo> = self
+            .inner
+            .find_all()
+            .await?
+            .into_iter()
+            .map(|todo| (todo.id.clone(), todo))
+            .collect();
+        for todo in self.lock_pending()?.iter() {
+            todos.insert(todo.id.clone(), todo.clone());
+        }
+        Ok(todos.into_values().collect())
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), TodoError> {
+        self.lock_pending()?.retain(|todo| &*todo.id != id);
+        self.inner.delete(id).await
+    }
+
+    async fn health_check(&self) -> Result<(), TodoError> {
+        self.inner.health_check().await
+    }
+}
@@ -0,0 +1,141 @@
+use crate::domain::todo::{Todo, TodoError, TodoEvent, TodoRepository};
+use async_trait::async_trait;
+use lru::LruCache;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+
+/// A cached todo, and whether a change to it has been seen since it was read
+struct CachedTodo {
+    todo: Todo,
+    stale: bool,
+}
+
+/// Read-through LRU cache in front of another repository
+///
+/// Meant for repositories where each read is a round trip, such as a remote service.
+/// `find_by_id` answers from the cache when it can and remembers what it read from the
+/// inner repository, keeping the `capacity` most recently used todos. `save` and `delete`
+/// write through and update the cache; `find_all` is not cached.
+///
+/// Changes made elsewhere reach the cache through the change feed: `apply` marks the todos
+/// named by events as stale. A stale todo is still returned, and `revalidate` refetches all
+/// stale todos in one pass, so reads never wait on the feed. When the feed has gaps, such as
+/// after reconnecting, `invalidate_all` marks every cached todo stale.
+pub struct CachedTodoRepository {
+    inner: Arc<dyn TodoRepository>,
+    cache: Mutex<LruCache<Arc<str>, CachedTodo>>,
+}
+
+impl CachedTodoRepository {
+    /// Creates a new CachedTodoRepository keeping up to `capacity` todos read from `inner`
+    pub fn new(inner: Arc<dyn TodoRepository>, capacity: NonZeroUsize) -> Self {
+        CachedTodoRepository {
+            inner,
+            cache: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+
+    /// Marks the cached todos that `events` are about as stale
+    pub fn apply(&self, events: &[TodoEvent]) -> Result<(), TodoError> {
+        let mut cache = self.lock_cache()?;
+        for event in events {
+            if let Some(cached) = cache.peek_mut(event.todo_id()) {
+                cached.stale = true;
+            }
+        }
+        Ok(())
+    }
+
+    /// Marks every cached todo as stale
+    pub fn invalidate_all(&self) -> Result<(), TodoError> {
+        for (_, cached) in self.lock_cache()?.iter_mut() {
+            cached.stale = true;
+        }
+        Ok(())
+    }
+
+    /// Refetches the stale todos from the inner repository
+    ///
+    /// Todos the inner repository no longer has are dropped from the cache. If a fetch
+    /// fails, the todos not yet refetched stay stale.
+    pub async fn revalidate(&self) -> Result<(), TodoError> {
+        let stale: Vec<Arc<str>> = self
+            .lock_cache()?
+            .iter()
+            .filter(|(_, cached)| cached.stale)
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in stale {
+            let todo = self.inner.find_by_id(&id).await?;
+            let mut cache = self.lock_cache()?;
+            match todo {
+                Some(todo) => {
+                    // Only refresh todos still cached, without making them recently used
+                    if let Some(cached) = cache.peek_mut(&id) {
+                        *cached = CachedTodo { todo, stale: false };
+                    }
+                }
+                None => {
+                    cache.pop(&id);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn lock_cache(
+        &self,
+    ) -> Result<std::sync::MutexGuard<'_, LruCache<Arc<str>, CachedTodo>>, TodoError> {
+        self.cache
+            .lock()
+            .map_err(|_| TodoError::RepositoryError("cache lock poisoned".to_string()))
+    }
+}
+
+#[async_trait]
+impl TodoRepository for CachedTodoRepository {
+    async fn save(&self, todo: &Todo) -> Result<(), TodoError> {
+        self.inner.save(todo).await?;
+        let mut saved = todo.clone();
+        saved.dirty = Some(false);
+        self.lock_cache()?.put(
+            saved.id.clone(),
+            CachedTodo {
+                todo: saved,
+                stale: false,
+            },
+        );
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<Todo>, TodoError> {
+        if let Some(cached) = self.lock_cache()?.get(id) {
+            return Ok(Some(cached.todo.clone()));
+        }
+        let todo = self.inner.find_by_id(id).await?;
+        if let Some(todo) = &todo {
+            self.lock_cache()?.put(
+                todo.id.clone(),
+                CachedTodo {
+                    todo: todo.clone(),
+                    stale: false,
+                },
+            );
+        }
+        Ok(todo)
+    }
+
+    async fn find_all(&self) -> Result<Vec<Todo>, TodoError> {
+        self.inner.find_all().await
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), TodoError> {
+        self.inner.delete(id).await?;
+        self.lock_cache()?.pop(id);
+        Ok(())
+    }
+
+    async fn health_check(&self) -> Result<(), TodoError> {
+        self.inner.health_check().await
+    }
+}
@@ -0,0 +1,286 @@
+use crate::domain::todo::{
+    Attachment, Priority, Subtask, Tag, Todo, TodoError, TodoRepository, TodoState,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// A Todo as stored in the JSON file
+#[derive(Serialize, Deserialize)]
+struct TodoRecord {
+    id: String,
+    description: String,
+    state: String,
+    created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    due_at: Option<DateTime<Utc>>,
+    /// `MEDIUM` when missing, as in files written before priorities
+    #[serde(default)]
+    priority: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    subtasks: Vec<SubtaskRecord>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    blocked_by: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    attachments: Vec<AttachmentRecord>,
+}
+
+/// A subtask as stored with its todo
+#[derive(Serialize, Deserialize)]
+struct SubtaskRecord {
+    id: String,
+    description: String,
+    done: bool,
+}
+
+impl SubtaskRecord {
+    fn from_subtask(subtask: &Subtask) -> Self {
+        SubtaskRecord {
+            id: subtask.id.to_string(),
+            description: subtask.description.to_string(),
+            done: subtask.done,
+        }
+    }
+
+    fn into_subtask(self) -> Subtask {
+        Subtask {
+            id: self.id.into(),
+            description: self.description.into(),
+            done: self.done,
+        }
+    }
+}
+
+/// An attachment as stored with its todo
+#[derive(Serialize, Deserialize)]
+struct AttachmentRecord {
+    content_hash: String,
+    name: String,
+    media_type: String,
+    size: u64,
+}
+
+impl AttachmentRecord {
+    fn from_attachment(attachment: &Attachment) -> Self {
+        AttachmentRecord {
+            content_hash: attachment.content_hash.to_string(),
+            name: attachment.name.to_string(),
+            media_type: attachment.media_type.to_string(),
+            size: attachment.size,
+        }
+    }
+
+    fn into_attachment(self) -> Attachment {
+        Attachment {
+            content_hash: self.content_hash.into(),
+            name: self.name.into(),
+            media_type: self.media_type.into(),
+            size: self.size,
+        }
+    }
+}
+
+impl TodoRecord {
+    fn from_todo(todo: &Todo) -> Self {
+        let state = match todo.state {
+            TodoState::Todo => "TODO",
+            TodoState::InProgress => "IN_PROGRESS",
+            TodoState::Done => "DONE",
+        };
+        TodoRecord {
+            id: todo.id.to_string(),
+            description: todo.description.to_string(),
+            state: state.to_string(),
+            created_at: todo.created_at,
+            due_at: todo.due_at,
+            priority: Some(todo.priority.name().to_string()),
+            tags: todo.tags.iter().map(ToString::to_string).collect(),
+            subtasks: todo
+                .subtasks
+                .iter()
+                .map(SubtaskRecord::from_subtask)
+                .collect(),
+            blocked_by: todo.blocked_by.iter().map(ToString::to_string).collect(),
+            attachments: todo
+                .attachments
+                .iter()
+                .map(AttachmentRecord::from_attachment)
+                .collect(),
+        }
+    }
+
+    fn into_todo(self) -> Result<Todo, TodoError> {
+        let state = match self.state.as_str() {
+            "TODO" => TodoState::Todo,
+            "IN_PROGRESS" => TodoState::InProgress,
+            "DONE" => TodoState::Done,
+            other => {
+                return Err(TodoError::RepositoryError(format!(
+                    "unknown todo state: {}",
+                    other
+                )));
+            }
+        };
+        let priority = match self.priority {
+            Some(name) => Priority::parse(&name).map_err(TodoError::RepositoryError)?,
+            None => Priority::default(),
+        };
+        let tags = self
+            .tags
+            .iter()
+            .map(|tag| Tag::new(tag))
+            .collect::<Result<_, _>>()
+            .map_err(TodoError::RepositoryError)?;
+        Ok(Todo {
+            id: self.id.into(),
+            created_at: self.created_at,
+            description: self.description.into(),
+            state,
+            due_at: self.due_at,
+            priority,
+            tags,
+            subtasks: self
+                .subtasks
+                .into_iter()
+                .map(SubtaskRecord::into_subtask)
+                .collect(),
+            blocked_by: self.blocked_by.into_iter().map(Into::into).collect(),
+            attachments: self
+                .attachments
+                .into_iter()
+                .map(AttachmentRecord::into_attachment)
+                .collect(),
+            dirty: Some(false),
+        })
+    }
+}
+
+/// JSON file implementation of TodoRepository
+///
+/// Stores all todos as a JSON array in a single file, which is read on every call and
+/// rewritten on every `save`, `save_all` and `delete`. A missing file is treated as an empty
+/// repository. Writes go to a temporary file that is then renamed over the original, so
+/// a crash never leaves a half-written file behind.
+///
+/// Calls are serialized within a process; separate processes sharing the file are not
+/// coordinated.
+pub struct FileTodoRepository {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl FileTodoRepository {
+    /// Creates a new FileTodoRepository backed by the file at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileTodoRepository {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Path of the backing file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn read_records(&self) -> Result<Vec<TodoRecord>, TodoError> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(storage_error(&self.path, err)),
+        };
+        serde_json::from_str(&contents).map_err(|err| storage_error(&self.path, err))
+    }
+
+    fn write_records(&self, records: &[TodoRecord]) -> Result<(), TodoError> {
+        let contents =
+            serde_json::to_string_pretty(records).map_err(|err| storage_error(&self.path, err))?;
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(".tmp");
+
+        fs::write(&tmp_path, contents).map_err(|err| storage_error(&self.path, err))?;
+        fs::rename(&tmp_path, &self.path).map_err(|err| storage_error(&self.path, err))
+    }
+
+    fn update<T>(&self, change: impl FnOnce(&mut Vec<TodoRecord>) -> T) -> Result<T, TodoError> {
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|_| TodoError::RepositoryError("file lock poisoned".to_string()))?;
+        let mut records = self.read_records()?;
+        let result = change(&mut records);
+        self.write_records(&records)?;
+        Ok(result)
+    }
+
+    fn read(&self) -> Result<Vec<TodoRecord>, TodoError> {
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|_| TodoError::RepositoryError("file lock poisoned".to_string()))?;
+        self.read_records()
+    }
+}
+
+fn storage_error(path: &Path, err: impl std::fmt::Display) -> TodoError {
+    TodoError::RepositoryError(format!("{}: {}", path.display(), err))
+}
+
+#[async_trait]
+impl TodoRepository for FileTodoRepository {
+    async fn save(&self, todo: &Todo) -> Result<(), TodoError> {
+        let record = TodoRecord::from_todo(todo);
+        self.update(
+            |records| match records.iter_mut().find(|existing| existing.id == record.id) {
+                Some(existing) => *existing = record,
+                None => records.push(record),
+            },
+        )
+    }
+
+    async fn save_all(&self, todos: &[Todo]) -> Result<(), TodoError> {
+        self.update(|records| {
+            let mut positions: HashMap<String, usize> = records
+                .iter()
+                .enumerate()
+                .map(|(position, record)| (record.id.clone(), position))
+                .collect();
+            for todo in todos {
+                let record = TodoRecord::from_todo(todo);
+                match positions.get(&record.id) {
+                    Some(&position) => records[position] = record,
+                    None => {
+                        positions.insert(record.id.clone(), records.len());
+                        records.push(record);
+                    }
+                }
+            }
+        })
+    }
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<Todo>, TodoError> {
+        self.read()?
+            .into_iter()
+            .find(|record| record.id == id)
+            .map(TodoRecord::into_todo)
+            .transpose()
+    }
+
+    async fn find_all(&self) -> Result<Vec<Todo>, TodoError> {
+        self.read()?
+            .into_iter()
+            .map(TodoRecord::into_todo)
+            .collect()
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), TodoError> {
+        self.update(|records| records.retain(|record| record.id != id))
+    }
+}
@@ -1,23 +1,38 @@
 use async_trait::async_trait;
-use std::collections::HashMap;
+use futures::stream::{self, BoxStream, StreamExt};
 use std::sync::{Arc, RwLock};
-use crate::domain::todo::{Todo, TodoError, TodoRepository};
+use crate::domain::todo::{Todo, TodoError, TodoFilter, TodoRepository};
+
+type TodoMap = imbl::HashMap<Arc<str>, Todo>;
 
 /// In-memory implementation of TodoRepository
 /// 
-/// This implementation stores todos in a HashMap wrapped in Arc<RwLock> for thread-safe access.
-/// Todos are stored by their ID and can be retrieved, updated, or deleted.
+/// This implementation stores todos in a persistent HashMap wrapped in Arc<RwLock> for
+/// thread-safe access. Todos are stored by their ID and can be retrieved, updated, or deleted.
+///
+/// Cloning the persistent map is cheap and shares its structure, so `find_all` and
+/// `stream_all` take a snapshot under the read lock and release it before copying todos
+/// out; a writer only waits for the snapshot, not for the whole read.
 pub struct InMemoryTodoRepository {
-    todos: Arc<RwLock<HashMap<String, Todo>>>,
+    todos: Arc<RwLock<TodoMap>>,
 }
 
 impl InMemoryTodoRepository {
     /// Creates a new InMemoryTodoRepository instance
     pub fn new() -> Self {
         InMemoryTodoRepository {
-            todos: Arc::new(RwLock::new(HashMap::new())),
+            todos: Arc::new(RwLock::new(TodoMap::new())),
         }
     }
+
+    /// Current contents of the repository, unaffected by later writes
+    fn snapshot(&self) -> Result<TodoMap, TodoError> {
+        let todos = self.todos.read().map_err(|_| {
+            TodoError::TodoNotFound
+        })?;
+
+        Ok(todos.clone())
+    }
 }
 
 impl Default for InMemoryTodoRepository {
@@ -37,14 +52,8 @@ impl TodoRepository for InMemoryTodoRepository {
         })?;
         
         // Clone the todo to store it (insert or update)
-        // Since Todo doesn't implement Clone, we'll reconstruct it from its fields
-        let todo_to_store = Todo {
-            id: todo.id.clone(),
-            created_at: todo.created_at,
-            description: todo.description.clone(),
-            state: todo.state,
-            dirty: Some(false), // Reset dirty flag when saving
-        };
+        let mut todo_to_store = todo.clone();
+        todo_to_store.dirty = Some(false); // Reset dirty flag when saving
         
         todos.insert(todo.id.clone(), todo_to_store);
         Ok(())
@@ -55,40 +64,30 @@ impl TodoRepository for InMemoryTodoRepository {
             TodoError::TodoNotFound
         })?;
         
-        match todos.get(id) {
-            Some(todo) => {
-                // Clone the todo to return it
-                Ok(Some(Todo {
-                    id: todo.id.clone(),
-                    created_at: todo.created_at,
-                    description: todo.description.clone(),
-                    state: todo.state,
-                    dirty: Some(false),
-                }))
-            }
-            None => Ok(None),
-        }
+        Ok(todos.get(id).cloned())
     }
 
     async fn find_all(&self) -> Result<Vec<Todo>, TodoError> {
-        let todos = self.todos.read().map_err(|_| {
-            TodoError::TodoNotFound
-        })?;
+        let todos = self.snapshot()?;
         
-        let result: Vec<Todo> = todos
-            .values()
-            .map(|todo| Todo {
-                id: todo.id.clone(),
-                created_at: todo.created_at,
-                description: todo.description.clone(),
-                state: todo.state,
-                dirty: Some(false),
-            })
-            .collect();
+        let result: Vec<Todo> = todos.values().cloned().collect();
         
         Ok(result)
     }
 
+    async fn find_matching(&self, filter: &TodoFilter) -> Result<Vec<Todo>, TodoError> {
+        let todos = self.snapshot()?;
+
+        Ok(todos.values().filter(|todo| filter.matches(todo)).cloned().collect())
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Todo, TodoError>> {
+        match self.snapshot() {
+            Ok(todos) => stream::iter(todos.into_iter().map(|(_, todo)| Ok(todo))).boxed(),
+            Err(err) => stream::iter([Err(err)]).boxed(),
+        }
+    }
+
     async fn delete(&self, id: &str) -> Result<(), TodoError> {
         let mut todos = self.todos.write().map_err(|_| {
             TodoError::TodoNotFound
@@ -98,4 +97,3 @@ impl TodoRepository for InMemoryTodoRepository {
         Ok(())
     }
 }
-
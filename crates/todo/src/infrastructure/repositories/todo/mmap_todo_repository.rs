@@ -0,0 +1,70 @@
+use crate::domain::todo::{Todo, TodoError, TodoRepository};
+use crate::infrastructure::serialization::snapshot::SnapshotView;
+use async_trait::async_trait;
+use futures::stream::{self, BoxStream, StreamExt};
+use memmap2::Mmap;
+use std::fs::File;
+use std::path::Path;
+
+/// Read-only implementation of TodoRepository over a memory-mapped snapshot
+///
+/// Reads a file written by `serialization::snapshot::write_snapshot` in place: opening it only
+/// checks the header, `find_by_id` binary-searches the id-sorted entries, and `stream_all`
+/// decodes one todo at a time, so large datasets are never deserialized up front.
+///
+/// `save` and `delete` fail with `TodoError::RepositoryError`. The file must not be modified
+/// while it is open; replace it by writing a new file and renaming it over the old one.
+pub struct MmapTodoRepository {
+    map: Mmap,
+}
+
+impl MmapTodoRepository {
+    /// Maps the snapshot at `path`
+    ///
+    /// # Returns
+    /// - `Ok(MmapTodoRepository)`: A repository reading the snapshot
+    /// - `Err(TodoError::RepositoryError)`: If the file cannot be opened or is not a snapshot
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, TodoError> {
+        let path = path.as_ref();
+        let storage_error = |err: &dyn std::fmt::Display| {
+            TodoError::RepositoryError(format!("{}: {}", path.display(), err))
+        };
+        let file = File::open(path).map_err(|err| storage_error(&err))?;
+        // SAFETY: the map is only read, and callers are told not to modify the file while it
+        // is open; replacing it by rename leaves this mapping on the old contents.
+        let map = unsafe { Mmap::map(&file) }.map_err(|err| storage_error(&err))?;
+        SnapshotView::new(&map).map_err(|err| storage_error(&err))?;
+        Ok(MmapTodoRepository { map })
+    }
+
+    fn view(&self) -> SnapshotView<'_> {
+        SnapshotView::new(&self.map).expect("header checked in open")
+    }
+}
+
+fn read_only() -> TodoError {
+    TodoError::RepositoryError("snapshot repository is read-only".to_string())
+}
+
+#[async_trait]
+impl TodoRepository for MmapTodoRepository {
+    async fn save(&self, _todo: &Todo) -> Result<(), TodoError> {
+        Err(read_only())
+    }
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<Todo>, TodoError> {
+        Ok(self.view().find(id)?)
+    }
+
+    async fn find_all(&self) -> Result<Vec<Todo>, TodoError> {
+        Ok(self.view().iter().collect::<Result<_, _>>()?)
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Todo, TodoError>> {
+        stream::iter(self.view().iter().map(|todo| todo.map_err(TodoError::from))).boxed()
+    }
+
+    async fn delete(&self, _id: &str) -> Result<(), TodoError> {
+        Err(read_only())
+    }
+}
@@ -1,4 +1,23 @@
+mod buffered_todo_repository;
+mod cached_todo_repository;
+#[cfg(feature = "serde")]
+mod file_todo_repository;
 mod inmemory_todo_repository;
+#[cfg(feature = "mmap")]
+mod mmap_todo_repository;
+mod offline_todo_repository;
+mod tenant_todo_repository;
+mod todo_backend;
+mod transactional_todo_repository;
 
+pub use buffered_todo_repository::BufferedTodoRepository;
+pub use cached_todo_repository::CachedTodoRepository;
+#[cfg(feature = "serde")]
+pub use file_todo_repository::FileTodoRepository;
 pub use inmemory_todo_repository::InMemoryTodoRepository;
-
+#[cfg(feature = "mmap")]
+pub use mmap_todo_repository::MmapTodoRepository;
+pub use offline_todo_repository::{OfflineSyncReport, OfflineTodoRepository};
+pub use tenant_todo_repository::TenantTodoRepository;
+pub use todo_backend::TodoBackend;
+pub use transactional_todo_repository::TransactionalTodoRepository;
@@ -0,0 +1,231 @@
+use crate::domain::sync::{CommandQueue, QueuedCommand, RejectedCommand, TodoCommand};
+use crate::domain::todo::{Todo, TodoError, TodoRepository};
+use crate::{Clock, IdGenerator, SystemClock, UuidV4Generator};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// What `OfflineTodoRepository::sync` did with the queued commands
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OfflineSyncReport {
+    /// Commands sent to the remote repository
+    pub applied: usize,
+    /// Commands the remote repository already reflected, such as when an earlier sync
+    /// stopped after sending them, which were dropped without being sent again
+    pub already_applied: usize,
+    /// Commands not sent because the todo changed remotely since they were recorded
+    pub rejected: Vec<RejectedCommand>,
+}
+
+/// Repository for clients that must keep working while their remote repository cannot be
+/// reached, such as a mobile app talking to a server
+///
+/// Every change goes to the remote repository and to a local copy. When the remote fails
+/// with `TodoError::RepositoryError`, the change is made to the local copy only and
+/// recorded as a QueuedCommand in a CommandQueue, which `sync` replays once the remote can
+/// be reached again. While commands are queued, every change is queued behind them, so the
+/// remote receives them in order, and reads are answered from the local copy. Otherwise
+/// reads go to the remote and refresh the local copy, which answers them when the remote
+/// fails.
+///
+/// Each command carries an idempotency key, and is removed from the queue by it once the
+/// remote reflects it. Each also carries the version of the todo it was made against, and
+/// is only applied while the remote still holds that version.
+pub struct OfflineTodoRepository {
+    remote: Arc<dyn TodoRepository>,
+    local: Arc<dyn TodoRepository>,
+    queue: Arc<dyn CommandQueue>,
+    clock: Arc<dyn Clock>,
+    keys: Arc<dyn IdGenerator>,
+}
+
+impl OfflineTodoRepository {
+    /// Creates a new OfflineTodoRepository over `remote`, keeping a copy in `local` and the
+    /// commands recorded offline in `queue`
+    pub fn new(
+        remote: Arc<dyn TodoRepository>,
+        local: Arc<dyn TodoRepository>,
+        queue: Arc<dyn CommandQueue>,
+    ) -> Self {
+        OfflineTodoRepository {
+            remote,
+            local,
+            queue,
+            clock: Arc::new(SystemClock),
+            keys: Arc::new(UuidV4Generator),
+        }
+    }
+
+    /// Stamps queued commands with `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Gives queued commands idempotency keys from `keys` instead of random UUIDv4s
+    pub fn with_id_generator(mut self, keys: Arc<dyn IdGenerator>) -> Self {
+        self.keys = keys;
+        self
+    }
+
+    /// Number of commands waiting to be replayed
+    pub async fn pending(&self) -> Result<usize, TodoError> {
+        Ok(self.queue.find_all().await?.len())
+    }
+
+    /// Replays the queued commands on the remote repository, oldest first
+    ///
+    /// A command is sent when the remote still holds the version of the todo it was made
+    /// against, and dropped unsent when the remote reflects it already. Otherwise it is
+    /// rejected: the local copy takes the remote's version of the todo, and the command is
+    /// returned for the caller to settle, such as through a `ResolveConflictHandler`.
+    ///
+    /// # Returns
+    /// - `Ok(OfflineSyncReport)`: Once the queue is empty
+    /// - `Err(TodoError)`: If the remote fails, such as when it still cannot be reached; the
+    ///   commands not replayed stay queued for the next `sync`
+    pub async fn sync(&self) -> Result<OfflineSyncReport, TodoError> {
+        let mut report = OfflineSyncReport::default();
+        for queued in self.queue.find_all().await? {
+            let id = queued.command.todo_id();
+            let current = self.remote.find_by_id(id).await?;
+            let target = match &queued.command {
+                TodoCommand::Save(todo) => Some(todo),
+                TodoCommand::Delete(_) => None,
+            };
+            if same_version(current.as_ref(), target) {
+                report.already_applied += 1;
+            } else if same_version(current.as_ref(), queued.base.as_ref()) {
+                match &queued.command {
+                    TodoCommand::Save(todo) => self.remote.save(todo).await?,
+                    TodoCommand::Delete(id) => self.remote.delete(id).await?,
+                }
+                report.applied += 1;
+            } else {
+                match &current {
+                    Some(todo) => self.local.save(todo).await?,
+                    None => delete_if_present(self.local.as_ref(), id).await?,
+                }
+                report.rejected.push(RejectedCommand {
+                    command: queued.clone(),
+                    remote: current,
+                });
+            }
+            self.queue.remove(&queued.key).await?;
+        }
+        Ok(report)
+    }
+
+    /// Makes `command` on the local copy only and queues it, with `base` as the version of
+    /// the todo it was made against
+    async fn enqueue(&self, command: TodoCommand) -> Result<(), TodoError> {
+        let base = self.local.find_by_id(command.todo_id()).await?;
+        match &command {
+            TodoCommand::Save(todo) => self.local.save(todo).await?,
+            TodoCommand::Delete(id) => {
+                if base.is_none() {
+                    return Err(TodoError::TodoNotFound);
+                }
+                self.local.delete(id).await?
+            }
+        }
+        let queued = QueuedCommand {
+            key: self.keys.next_id().into(),
+            command,
+            base,
+            queued_at: self.clock.now(),
+        };
+        self.queue.push(&queued).await
+    }
+
+    async fn is_offline(&self) -> Result<bool, TodoError> {
+        Ok(self.pending().await? > 0)
+    }
+}
+
+/// Whether `a` and `b` are the same version of a todo, ignoring whether they were changed
+/// since loaded
+fn same_version(a: Option<&Todo>, b: Option<&Todo>) -> bool {
+    match (a, b) {
+        (None, None) => true,
+        (Some(a), Some(b)) => {
+            a.id == b.id
+                && a.created_at == b.created_at
+                && a.description == b.description
+                && a.state == b.state
+        }
+        _ => false,
+    }
+}
+
+async fn delete_if_present(repository: &dyn TodoRepository, id: &str) -> Result<(), TodoError> {
+    match repository.delete(id).await {
+        Ok(()) | Err(TodoError::TodoNotFound) => Ok(()),
+        Err(err) => Err(err),
+    }
+}
+
+#[async_trait]
+impl TodoRepository for OfflineTodoRepository {
+    async fn save(&self, todo: &Todo) -> Result<(), TodoError> {
+        if !self.is_offline().await? {
+            match self.remote.save(todo).await {
+                Ok(()) => return self.local.save(todo).await,
+                Err(TodoError::RepositoryError(_)) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        self.enqueue(TodoCommand::Save(todo.clone())).await
+    }
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<Todo>, TodoError> {
+        if !self.is_offline().await? {
+            match self.remote.find_by_id(id).await {
+                Ok(Some(todo)) => {
+                    self.local.save(&todo).await?;
+                    return Ok(Some(todo));
+                }
+                Ok(None) => {
+                    delete_if_present(self.local.as_ref(), id).await?;
+                    return Ok(None);
+                }
+                Err(TodoError::RepositoryError(_)) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        self.local.find_by_id(id).await
+    }
+
+    async fn find_all(&self) -> Result<Vec<Todo>, TodoError> {
+        if !self.is_offline().await? {
+            match self.remote.find_all().await {
+                Ok(todos) => {
+                    for stale in self.local.find_all().await? {
+                        if !todos.iter().any(|todo| todo.id == stale.id) {
+                            delete_if_present(self.local.as_ref(), &stale.id).await?;
+                        }
+                    }
+                    self.local.save_all(&todos).await?;
+                    return Ok(todos);
+                }
+                Err(TodoError::RepositoryError(_)) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        self.local.find_all().await
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), TodoError> {
+        if !self.is_offline().await? {
+            match self.remote.delete(id).await {
+                Ok(()) => return delete_if_present(self.local.as_ref(), id).await,
+                Err(TodoError::RepositoryError(_)) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        self.enqueue(TodoCommand::Delete(id.into())).await
+    }
+
+    async fn health_check(&self) -> Result<(), TodoError> {
+        self.remote.health_check().await
+    }
+}
@@ -0,0 +1,97 @@
+use crate::domain::todo::{TenantId, Todo, TodoError, TodoRepository};
+use async_trait::async_trait;
+use futures::stream::{BoxStream, StreamExt};
+use std::sync::Arc;
+
+/// One tenant's view of a repository shared by many tenants
+///
+/// Every todo is stored in the inner repository under `<tenant>/<id>`, and this view only
+/// reads and writes ids with its own tenant's prefix, handing out the ids without it. Handlers
+/// built over a TenantTodoRepository therefore never see, change or delete another tenant's
+/// todos, even when they are given an id that exists for another tenant.
+///
+/// The inner repository should only be reached through tenant views; todos saved to it
+/// directly are not visible to any tenant.
+pub struct TenantTodoRepository {
+    inner: Arc<dyn TodoRepository>,
+    tenant: TenantId,
+    prefix: String,
+}
+
+impl TenantTodoRepository {
+    /// Creates a new TenantTodoRepository showing `tenant`'s todos in `inner`
+    pub fn new(inner: Arc<dyn TodoRepository>, tenant: TenantId) -> Self {
+        TenantTodoRepository {
+            prefix: format!("{}/", tenant),
+            inner,
+            tenant,
+        }
+    }
+
+    /// The tenant this view belongs to
+    pub fn tenant(&self) -> &TenantId {
+        &self.tenant
+    }
+
+    fn scoped_id(&self, id: &str) -> String {
+        format!("{}{}", self.prefix, id)
+    }
+
+    fn scoped(&self, todo: &Todo) -> Todo {
+        Todo {
+            id: self.scoped_id(&todo.id).into(),
+            ..todo.clone()
+        }
+    }
+
+    /// The todo with the tenant prefix removed, or `None` if it belongs to another tenant
+    fn unscoped(&self, todo: Todo) -> Option<Todo> {
+        let id = todo.id.strip_prefix(&self.prefix)?.into();
+        Some(Todo { id, ..todo })
+    }
+}
+
+#[async_trait]
+impl TodoRepository for TenantTodoRepository {
+    async fn save(&self, todo: &Todo) -> Result<(), TodoError> {
+        self.inner.save(&self.scoped(todo)).await
+    }
+
+    async fn save_all(&self, todos: &[Todo]) -> Result<(), TodoError> {
+        let todos: Vec<Todo> = todos.iter().map(|todo| self.scoped(todo)).collect();
+        self.inner.save_all(&todos).await
+    }
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<Todo>, TodoError> {
+        let todo = self.inner.find_by_id(&self.scoped_id(id)).await?;
+        Ok(todo.and_then(|todo| self.unscoped(todo)))
+    }
+
+    async fn find_all(&self) -> Result<Vec<Todo>, TodoError> {
+        let todos = self.inner.find_all().await?;
+        Ok(todos
+            .into_iter()
+            .filter_map(|todo| self.unscoped(todo))
+            .collect())
+    }
+
+    fn stream_all(&self) -> BoxStream<'_, Result<Todo, TodoError>> {
+        self.inner
+            .stream_all()
+            .filter_map(move |result| async move {
+                match result {
+                    Ok(todo) => self.unscoped(todo).map(Ok),
+                    Err(err) => Some(Err(err)),
+                }
+            })
+            .boxed()
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), TodoError> {
+        self.inner.delete(&self.scoped_id(id)).await
+    }
+
+    async fn health_check(&self) -> Result<(), TodoError> {
+        self.inner.health_check().await
+    }
+}
@@ -0,0 +1,41 @@
+#[cfg(feature = "serde")]
+use super::FileTodoRepository;
+use super::InMemoryTodoRepository;
+use crate::domain::todo::TodoRepository;
+#[cfg(feature = "serde")]
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Selects the repository implementation a front end stores its todos in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TodoBackend {
+    /// `memory`: todos are lost when the process exits
+    Memory,
+    /// `file:<path>`: todos are stored in a JSON file; needs the `serde` feature
+    #[cfg(feature = "serde")]
+    File(PathBuf),
+}
+
+impl TodoBackend {
+    /// Parses `memory` or `file:<path>`
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.split_once(':') {
+            None if value == "memory" => Ok(TodoBackend::Memory),
+            #[cfg(feature = "serde")]
+            Some(("file", path)) if !path.is_empty() => Ok(TodoBackend::File(PathBuf::from(path))),
+            _ => Err(format!(
+                "unknown backend {:?}, expected memory or file:<path>",
+                value
+            )),
+        }
+    }
+
+    /// Creates the selected repository
+    pub fn repository(&self) -> Arc<dyn TodoRepository> {
+        match self {
+            TodoBackend::Memory => Arc::new(InMemoryTodoRepository::new()),
+            #[cfg(feature = "serde")]
+            TodoBackend::File(path) => Arc::new(FileTodoRepository::new(path)),
+        }
+    }
+}
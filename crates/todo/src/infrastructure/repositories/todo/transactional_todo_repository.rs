@@ -0,0 +1,116 @@
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use crate::domain::todo::{Todo, TodoError, TodoRepository};
+
+/// A change staged by a TransactionalTodoRepository
+enum StagedChange {
+    Save(Todo),
+    Delete(String),
+}
+
+/// Unit of Work implementation of TodoRepository
+///
+/// Wraps another repository and stages every `save` and `delete` in memory instead of
+/// writing through. Reads see the staged changes layered on top of the inner repository.
+/// `commit` replays the staged changes in order against the inner repository, and
+/// `rollback` discards them.
+///
+/// Staged changes are invisible to other users of the inner repository until committed.
+/// If the inner repository fails during `commit`, the changes already applied stay
+/// applied; the inner repository needs its own transactions for all-or-nothing writes.
+pub struct TransactionalTodoRepository {
+    inner: Arc<dyn TodoRepository>,
+    staged: Mutex<Vec<StagedChange>>,
+}
+
+impl TransactionalTodoRepository {
+    /// Creates a new TransactionalTodoRepository staging changes for `inner`
+    pub fn new(inner: Arc<dyn TodoRepository>) -> Self {
+        TransactionalTodoRepository {
+            inner,
+            staged: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Applies all staged changes to the inner repository, in the order they were made
+    pub async fn commit(&self) -> Result<(), TodoError> {
+        let staged = std::mem::take(&mut *self.lock_staged()?);
+
+        for change in staged {
+            match change {
+                StagedChange::Save(todo) => self.inner.save(&todo).await?,
+                StagedChange::Delete(id) => self.inner.delete(&id).await?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Discards all staged changes
+    pub fn rollback(&self) -> Result<(), TodoError> {
+        self.lock_staged()?.clear();
+        Ok(())
+    }
+
+    fn lock_staged(&self) -> Result<std::sync::MutexGuard<'_, Vec<StagedChange>>, TodoError> {
+        self.staged
+            .lock()
+            .map_err(|_| TodoError::RepositoryError("transaction lock poisoned".to_string()))
+    }
+}
+
+#[async_trait]
+impl TodoRepository for TransactionalTodoRepository {
+    async fn save(&self, todo: &Todo) -> Result<(), TodoError> {
+        let mut staged = todo.clone();
+        staged.dirty = Some(false);
+        self.lock_staged()?.push(StagedChange::Save(staged));
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<Todo>, TodoError> {
+        // The most recent staged change for this id wins over the inner repository
+        let staged = self.lock_staged()?.iter().rev().find_map(|change| match change {
+            StagedChange::Save(todo) if &*todo.id == id => Some(Some(todo.clone())),
+            StagedChange::Delete(deleted_id) if deleted_id == id => Some(None),
+            _ => None,
+        });
+
+        match staged {
+            Some(todo) => Ok(todo),
+            None => self.inner.find_by_id(id).await,
+        }
+    }
+
+    async fn find_all(&self) -> Result<Vec<Todo>, TodoError> {
+        let mut todos: HashMap<Arc<str>, Todo> = self
+            .inner
+            .find_all()
+            .await?
+            .into_iter()
+            .map(|todo| (todo.id.clone(), todo))
+            .collect();
+
+        for change in self.lock_staged()?.iter() {
+            match change {
+                StagedChange::Save(todo) => {
+                    todos.insert(todo.id.clone(), todo.clone());
+                }
+                StagedChange::Delete(id) => {
+                    todos.remove(id.as_str());
+                }
+            }
+        }
+
+        Ok(todos.into_values().collect())
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), TodoError> {
+        self.lock_staged()?.push(StagedChange::Delete(id.to_string()));
+        Ok(())
+    }
+
+    async fn health_check(&self) -> Result<(), TodoError> {
+        self.inner.health_check().await
+    }
+}
@@ -0,0 +1,259 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::domain::trash::{TrashRepository, TrashedTodo};
+use crate::{Attachment, Priority, Subtask, Tag, Todo, TodoError, TodoState};
+
+/// A TrashedTodo as stored in the JSON file: the todo's fields and when it was deleted
+#[derive(Serialize, Deserialize)]
+struct TrashRecord {
+    id: String,
+    description: String,
+    state: String,
+    created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    due_at: Option<DateTime<Utc>>,
+    /// `MEDIUM` when missing, as in files written before priorities
+    #[serde(default)]
+    priority: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    subtasks: Vec<SubtaskRecord>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    blocked_by: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    attachments: Vec<AttachmentRecord>,
+    deleted_at: DateTime<Utc>,
+}
+
+/// A subtask as stored with its todo
+#[derive(Serialize, Deserialize)]
+struct SubtaskRecord {
+    id: String,
+    description: String,
+    done: bool,
+}
+
+impl SubtaskRecord {
+    fn from_subtask(subtask: &Subtask) -> Self {
+        SubtaskRecord {
+            id: subtask.id.to_string(),
+            description: subtask.description.to_string(),
+            done: subtask.done,
+        }
+    }
+
+    fn into_subtask(self) -> Subtask {
+        Subtask {
+            id: self.id.into(),
+            description: self.description.into(),
+            done: self.done,
+        }
+    }
+}
+
+/// An attachment as stored with its todo
+#[derive(Serialize, Deserialize)]
+struct AttachmentRecord {
+    content_hash: String,
+    name: String,
+    media_type: String,
+    size: u64,
+}
+
+impl AttachmentRecord {
+    fn from_attachment(attachment: &Attachment) -> Self {
+        AttachmentRecord {
+            content_hash: attachment.content_hash.to_string(),
+            name: attachment.name.to_string(),
+            media_type: attachment.media_type.to_string(),
+            size: attachment.size,
+        }
+    }
+
+    fn into_attachment(self) -> Attachment {
+        Attachment {
+            content_hash: self.content_hash.into(),
+            name: self.name.into(),
+            media_type: self.media_type.into(),
+            size: self.size,
+        }
+    }
+}
+
+impl TrashRecord {
+    fn from_trashed(trashed: &TrashedTodo) -> Self {
+        let todo = &trashed.todo;
+        let state = match todo.state {
+            TodoState::Todo => "TODO",
+            TodoState::InProgress => "IN_PROGRESS",
+            TodoState::Done => "DONE",
+        };
+        TrashRecord {
+            id: todo.id.to_string(),
+            description: todo.description.to_string(),
+            state: state.to_string(),
+            created_at: todo.created_at,
+            due_at: todo.due_at,
+            priority: Some(todo.priority.name().to_string()),
+            tags: todo.tags.iter().map(ToString::to_string).collect(),
+            subtasks: todo
+                .subtasks
+                .iter()
+                .map(SubtaskRecord::from_subtask)
+                .collect(),
+            blocked_by: todo.blocked_by.iter().map(ToString::to_string).collect(),
+            attachments: todo
+                .attachments
+                .iter()
+                .map(AttachmentRecord::from_attachment)
+                .collect(),
+            deleted_at: trashed.deleted_at,
+        }
+    }
+
+    fn into_trashed(self) -> Result<TrashedTodo, TodoError> {
+        let state = match self.state.as_str() {
+            "TODO" => TodoState::Todo,
+            "IN_PROGRESS" => TodoState::InProgress,
+            "DONE" => TodoState::Done,
+            other => {
+                return Err(TodoError::RepositoryError(format!(
+                    "unknown todo state: {}",
+                    other
+                )));
+            }
+        };
+        let priority = match self.priority {
+            Some(name) => Priority::parse(&name).map_err(TodoError::RepositoryError)?,
+            None => Priority::default(),
+        };
+        let tags = self
+            .tags
+            .iter()
+            .map(|tag| Tag::new(tag))
+            .collect::<Result<_, _>>()
+            .map_err(TodoError::RepositoryError)?;
+        let todo = Todo {
+            id: self.id.into(),
+            created_at: self.created_at,
+            description: self.description.into(),
+            state,
+            due_at: self.due_at,
+            priority,
+            tags,
+            subtasks: self
+                .subtasks
+                .into_iter()
+                .map(SubtaskRecord::into_subtask)
+                .collect(),
+            blocked_by: self.blocked_by.into_iter().map(Into::into).collect(),
+            attachments: self
+                .attachments
+                .into_iter()
+                .map(AttachmentRecord::into_attachment)
+                .collect(),
+            dirty: Some(false),
+        };
+        Ok(TrashedTodo::new(todo, self.deleted_at))
+    }
+}
+
+/// JSON file implementation of TrashRepository
+///
+/// Stores every deleted todo as a JSON array in a single file, read on every call and
+/// rewritten through a temporary file on every change, like `FileMembershipRepository`. A
+/// missing file is treated as an empty trash, so the first deletion creates it.
+pub struct FileTrashRepository {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl FileTrashRepository {
+    /// Creates a new FileTrashRepository backed by the file at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileTrashRepository {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Path of the backing file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn read_records(&self) -> Result<Vec<TrashRecord>, TodoError> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(storage_error(&self.path, err)),
+        };
+        serde_json::from_str(&contents).map_err(|err| storage_error(&self.path, err))
+    }
+
+    fn write_records(&self, records: &[TrashRecord]) -> Result<(), TodoError> {
+        let contents =
+            serde_json::to_string_pretty(records).map_err(|err| storage_error(&self.path, err))?;
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(".tmp");
+
+        fs::write(&tmp_path, contents).map_err(|err| storage_error(&self.path, err))?;
+        fs::rename(&tmp_path, &self.path).map_err(|err| storage_error(&self.path, err))
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, ()>, TodoError> {
+        self.lock
+            .lock()
+            .map_err(|_| TodoError::RepositoryError("file lock poisoned".to_string()))
+    }
+}
+
+fn storage_error(path: &Path, err: impl std::fmt::Display) -> TodoError {
+    TodoError::RepositoryError(format!("{}: {}", path.display(), err))
+}
+
+#[async_trait]
+impl TrashRepository for FileTrashRepository {
+    async fn save(&self, trashed: &TrashedTodo) -> Result<(), TodoError> {
+        let _guard = self.lock()?;
+        let mut records = self.read_records()?;
+        records.retain(|record| record.id != trashed.id());
+        records.push(TrashRecord::from_trashed(trashed));
+        self.write_records(&records)
+    }
+
+    async fn find(&self, id: &str) -> Result<Option<TrashedTodo>, TodoError> {
+        let _guard = self.lock()?;
+        self.read_records()?
+            .into_iter()
+            .find(|record| record.id == id)
+            .map(TrashRecord::into_trashed)
+            .transpose()
+    }
+
+    async fn find_all(&self) -> Result<Vec<TrashedTodo>, TodoError> {
+        let _guard = self.lock()?;
+        self.read_records()?
+            .into_iter()
+            .map(TrashRecord::into_trashed)
+            .collect()
+    }
+
+    async fn remove(&self, id: &str) -> Result<(), TodoError> {
+        let _guard = self.lock()?;
+        let mut records = self.read_records()?;
+        let count = records.len();
+        records.retain(|record| record.id != id);
+        if records.len() == count {
+            return Err(TodoError::TodoNotFound);
+        }
+        self.write_records(&records)
+    }
+}
@@ -0,0 +1,57 @@
+use async_trait::async_trait;
+use std::sync::RwLock;
+
+use crate::TodoError;
+use crate::domain::trash::{TrashRepository, TrashedTodo};
+
+/// In-memory implementation of TrashRepository, whose deleted todos are lost when it is
+/// dropped
+#[derive(Default)]
+pub struct InMemoryTrashRepository {
+    trashed: RwLock<Vec<TrashedTodo>>,
+}
+
+impl InMemoryTrashRepository {
+    /// Creates a new, empty InMemoryTrashRepository
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+fn poisoned<T>(_: T) -> TodoError {
+    TodoError::RepositoryError("trash lock poisoned".to_string())
+}
+
+#[async_trait]
+impl TrashRepository for InMemoryTrashRepository {
+    async fn save(&self, trashed: &TrashedTodo) -> Result<(), TodoError> {
+        let mut stored = self.trashed.write().map_err(poisoned)?;
+        stored.retain(|existing| existing.id() != trashed.id());
+        stored.push(trashed.clone());
+        Ok(())
+    }
+
+    async fn find(&self, id: &str) -> Result<Option<TrashedTodo>, TodoError> {
+        Ok(self
+            .trashed
+            .read()
+            .map_err(poisoned)?
+            .iter()
+            .find(|trashed| trashed.id() == id)
+            .cloned())
+    }
+
+    async fn find_all(&self) -> Result<Vec<TrashedTodo>, TodoError> {
+        Ok(self.trashed.read().map_err(poisoned)?.clone())
+    }
+
+    async fn remove(&self, id: &str) -> Result<(), TodoError> {
+        let mut stored = self.trashed.write().map_err(poisoned)?;
+        let count = stored.len();
+        stored.retain(|trashed| trashed.id() != id);
+        if stored.len() == count {
+            return Err(TodoError::TodoNotFound);
+        }
+        Ok(())
+    }
+}
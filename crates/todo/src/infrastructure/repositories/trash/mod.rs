@@ -0,0 +1,9 @@
+#[cfg(feature = "serde")]
+mod file_trash_repository;
+mod inmemory_trash_repository;
+mod tenant_trash_repository;
+
+#[cfg(feature = "serde")]
+pub use file_trash_repository::FileTrashRepository;
+pub use inmemory_trash_repository::InMemoryTrashRepository;
+pub use tenant_trash_repository::TenantTrashRepository;
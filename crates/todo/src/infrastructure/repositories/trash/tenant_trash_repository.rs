@@ -0,0 +1,67 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::domain::todo::{TenantId, Todo, TodoError};
+use crate::domain::trash::{TrashRepository, TrashedTodo};
+
+/// One tenant's view of a trash shared by many tenants
+///
+/// Deleted todos are kept in the inner trash under `<tenant>/<id>`, as a
+/// `TenantTodoRepository` stores them, and this view only lists, restores and purges those
+/// with its own tenant's prefix, handing them out without it.
+pub struct TenantTrashRepository {
+    inner: Arc<dyn TrashRepository>,
+    tenant: TenantId,
+}
+
+impl TenantTrashRepository {
+    /// Creates a new TenantTrashRepository showing `tenant`'s deleted todos in `inner`
+    pub fn new(inner: Arc<dyn TrashRepository>, tenant: TenantId) -> Self {
+        TenantTrashRepository { inner, tenant }
+    }
+
+    /// The tenant this view belongs to
+    pub fn tenant(&self) -> &TenantId {
+        &self.tenant
+    }
+
+    /// The deleted todo with the tenant prefix removed, or `None` if it belongs to another
+    /// tenant
+    fn unscoped(&self, trashed: TrashedTodo) -> Option<TrashedTodo> {
+        let id = self.tenant.unscope(trashed.id())?.into();
+        Some(TrashedTodo {
+            todo: Todo { id, ..trashed.todo },
+            ..trashed
+        })
+    }
+}
+
+#[async_trait]
+impl TrashRepository for TenantTrashRepository {
+    async fn save(&self, trashed: &TrashedTodo) -> Result<(), TodoError> {
+        let todo = Todo {
+            id: self.tenant.scope(trashed.id()).into(),
+            ..trashed.todo.clone()
+        };
+        self.inner
+            .save(&TrashedTodo::new(todo, trashed.deleted_at))
+            .await
+    }
+
+    async fn find(&self, id: &str) -> Result<Option<TrashedTodo>, TodoError> {
+        let trashed = self.inner.find(&self.tenant.scope(id)).await?;
+        Ok(trashed.and_then(|trashed| self.unscoped(trashed)))
+    }
+
+    async fn find_all(&self) -> Result<Vec<TrashedTodo>, TodoError> {
+        let trashed = self.inner.find_all().await?;
+        Ok(trashed
+            .into_iter()
+            .filter_map(|trashed| self.unscoped(trashed))
+            .collect())
+    }
+
+    async fn remove(&self, id: &str) -> Result<(), TodoError> {
+        self.inner.remove(&self.tenant.scope(id)).await
+    }
+}
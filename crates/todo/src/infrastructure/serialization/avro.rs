@@ -0,0 +1,754 @@
+use apache_avro::types::Value;
+use apache_avro::{Schema, from_avro_datum, to_avro_datum};
+use chrono::{DateTime, Utc};
+use std::sync::{Arc, LazyLock};
+
+use crate::infrastructure::serialization::SerializationError;
+use crate::{Priority, Tag, TodoEvent, TodoState};
+
+/// Avro schema of `TodoEvent`, to register with a schema registry
+///
+/// Timestamps are `timestamp-micros`, so sub-microsecond precision is dropped. New event types
+/// are added as new union branches at the end, which older readers reject.
+pub const SCHEMA_JSON: &str = r#"{
+  "type": "record",
+  "name": "TodoEvent",
+  "namespace": "com.hungknow.todo.v1",
+  "fields": [
+    {
+      "name": "event",
+      "type": [
+        {
+          "type": "record",
+          "name": "TodoCreated",
+          "fields": [
+            {"name": "id", "type": "string"},
+            {"name": "description", "type": "string"},
+            {"name": "created_at", "type": {"type": "long", "logicalType": "timestamp-micros"}}
+          ]
+        },
+        {
+          "type": "record",
+          "name": "TodoStateChanged",
+          "fields": [
+            {"name": "id", "type": "string"},
+            {
+              "name": "from_state",
+              "type": {"type": "enum", "name": "TodoState", "symbols": ["TODO", "IN_PROGRESS", "DONE"]}
+            },
+            {"name": "to_state", "type": "TodoState"},
+            {"name": "changed_at", "type": {"type": "long", "logicalType": "timestamp-micros"}}
+          ]
+        },
+        {
+          "type": "record",
+          "name": "TodoArchived",
+          "fields": [
+            {"name": "id", "type": "string"},
+            {"name": "archived_at", "type": {"type": "long", "logicalType": "timestamp-micros"}}
+          ]
+        },
+        {
+          "type": "record",
+          "name": "TodoStale",
+          "fields": [
+            {"name": "id", "type": "string"},
+            {"name": "rule", "type": "string"},
+            {"name": "state", "type": "TodoState"},
+            {"name": "stale_at", "type": {"type": "long", "logicalType": "timestamp-micros"}}
+          ]
+        },
+        {
+          "type": "record",
+          "name": "TodoDueDateChanged",
+          "fields": [
+            {"name": "id", "type": "string"},
+            {"name": "due_at", "type": ["null", {"type": "long", "logicalType": "timestamp-micros"}]},
+            {"name": "changed_at", "type": {"type": "long", "logicalType": "timestamp-micros"}}
+          ]
+        },
+        {
+          "type": "record",
+          "name": "TodoPriorityChanged",
+          "fields": [
+            {"name": "id", "type": "string"},
+            {
+              "name": "from_priority",
+              "type": {"type": "enum", "name": "Priority", "symbols": ["LOW", "MEDIUM", "HIGH", "URGENT"]}
+            },
+            {"name": "to_priority", "type": "Priority"},
+            {"name": "changed_at", "type": {"type": "long", "logicalType": "timestamp-micros"}}
+          ]
+        },
+        {
+          "type": "record",
+          "name": "TodoTagAdded",
+          "fields": [
+            {"name": "id", "type": "string"},
+            {"name": "tag", "type": "string"},
+            {"name": "added_at", "type": {"type": "long", "logicalType": "timestamp-micros"}}
+          ]
+        },
+        {
+          "type": "record",
+          "name": "TodoTagRemoved",
+          "fields": [
+            {"name": "id", "type": "string"},
+            {"name": "tag", "type": "string"},
+            {"name": "removed_at", "type": {"type": "long", "logicalType": "timestamp-micros"}}
+          ]
+        },
+        {
+          "type": "record",
+          "name": "TodoSubtaskAdded",
+          "fields": [
+            {"name": "id", "type": "string"},
+            {"name": "subtask_id", "type": "string"},
+            {"name": "description", "type": "string"},
+            {"name": "added_at", "type": {"type": "long", "logicalType": "timestamp-micros"}}
+          ]
+        },
+        {
+          "type": "record",
+          "name": "TodoSubtaskToggled",
+          "fields": [
+            {"name": "id", "type": "string"},
+            {"name": "subtask_id", "type": "string"},
+            {"name": "done", "type": "boolean"},
+            {"name": "toggled_at", "type": {"type": "long", "logicalType": "timestamp-micros"}}
+          ]
+        },
+        {
+          "type": "record",
+          "name": "TodoSubtaskRemoved",
+          "fields": [
+            {"name": "id", "type": "string"},
+            {"name": "subtask_id", "type": "string"},
+            {"name": "removed_at", "type": {"type": "long", "logicalType": "timestamp-micros"}}
+          ]
+        },
+        {
+          "type": "record",
+          "name": "TodoDescriptionUpdated",
+          "fields": [
+            {"name": "id", "type": "string"},
+            {"name": "old", "type": "string"},
+            {"name": "new", "type": "string"},
+            {"name": "updated_at", "type": {"type": "long", "logicalType": "timestamp-micros"}}
+          ]
+        },
+        {
+          "type": "record",
+          "name": "TodoBlocked",
+          "fields": [
+            {"name": "id", "type": "string"},
+            {"name": "blocker_id", "type": "string"},
+            {"name": "blocked_at", "type": {"type": "long", "logicalType": "timestamp-micros"}}
+          ]
+        },
+        {
+          "type": "record",
+          "name": "TodoUnblocked",
+          "fields": [
+            {"name": "id", "type": "string"},
+            {"name": "blocker_id", "type": "string"},
+            {"name": "unblocked_at", "type": {"type": "long", "logicalType": "timestamp-micros"}}
+          ]
+        },
+        {
+          "type": "record",
+          "name": "TodoDeleted",
+          "fields": [
+            {"name": "id", "type": "string"},
+            {"name": "deleted_at", "type": {"type": "long", "logicalType": "timestamp-micros"}}
+          ]
+        },
+        {
+          "type": "record",
+          "name": "TodoRestored",
+          "fields": [
+            {"name": "id", "type": "string"},
+            {"name": "restored_at", "type": {"type": "long", "logicalType": "timestamp-micros"}}
+          ]
+        },
+        {
+          "type": "record",
+          "name": "TodoAttachmentAdded",
+          "fields": [
+            {"name": "id", "type": "string"},
+            {"name": "content_hash", "type": "string"},
+            {"name": "name", "type": "string"},
+            {"name": "media_type", "type": "string"},
+            {"name": "size", "type": "long"},
+            {"name": "added_at", "type": {"type": "long", "logicalType": "timestamp-micros"}}
+          ]
+        },
+        {
+          "type": "record",
+          "name": "TodoAttachmentRemoved",
+          "fields": [
+            {"name": "id", "type": "string"},
+            {"name": "content_hash", "type": "string"},
+            {"name": "removed_at", "type": {"type": "long", "logicalType": "timestamp-micros"}}
+          ]
+        }
+      ]
+    }
+  ]
+}"#;
+
+static SCHEMA: LazyLock<Schema> =
+    LazyLock::new(|| Schema::parse_str(SCHEMA_JSON).expect("SCHEMA_JSON is a valid Avro schema"));
+
+/// The parsed [`SCHEMA_JSON`]
+pub fn schema() -> &'static Schema {
+    &SCHEMA
+}
+
+/// Encodes an event as an Avro datum, without schema or framing
+pub fn encode_event(event: &TodoEvent) -> Result<Vec<u8>, SerializationError> {
+    let (branch, fields) = match event {
+        TodoEvent::TodoCreated {
+            id,
+            description,
+            created_at,
+        } => (
+            0,
+            vec![
+                ("id".to_string(), Value::String(id.to_string())),
+                (
+                    "description".to_string(),
+                    Value::String(description.to_string()),
+                ),
+                (
+                    "created_at".to_string(),
+                    Value::TimestampMicros(created_at.timestamp_micros()),
+                ),
+            ],
+        ),
+        TodoEvent::TodoStateChanged {
+            id,
+            from_state,
+            to_state,
+            changed_at,
+        } => (
+            1,
+            vec![
+                ("id".to_string(), Value::String(id.to_string())),
+                ("from_state".to_string(), state_to_avro(*from_state)),
+                ("to_state".to_string(), state_to_avro(*to_state)),
+                (
+                    "changed_at".to_string(),
+                    Value::TimestampMicros(changed_at.timestamp_micros()),
+                ),
+            ],
+        ),
+        TodoEvent::TodoArchived { id, archived_at } => (
+            2,
+            vec![
+                ("id".to_string(), Value::String(id.to_string())),
+                (
+                    "archived_at".to_string(),
+                    Value::TimestampMicros(archived_at.timestamp_micros()),
+                ),
+            ],
+        ),
+        TodoEvent::TodoStale {
+            id,
+            rule,
+            state,
+            stale_at,
+        } => (
+            3,
+            vec![
+                ("id".to_string(), Value::String(id.to_string())),
+                ("rule".to_string(), Value::String(rule.to_string())),
+                ("state".to_string(), state_to_avro(*state)),
+                (
+                    "stale_at".to_string(),
+                    Value::TimestampMicros(stale_at.timestamp_micros()),
+                ),
+            ],
+        ),
+        TodoEvent::TodoDueDateChanged {
+            id,
+            due_at,
+            changed_at,
+        } => (
+            4,
+            vec![
+                ("id".to_string(), Value::String(id.to_string())),
+                (
+                    "due_at".to_string(),
+                    match due_at {
+                        Some(due_at) => Value::Union(
+                            1,
+                            Box::new(Value::TimestampMicros(due_at.timestamp_micros())),
+                        ),
+                        None => Value::Union(0, Box::new(Value::Null)),
+                    },
+                ),
+                (
+                    "changed_at".to_string(),
+                    Value::TimestampMicros(changed_at.timestamp_micros()),
+                ),
+            ],
+        ),
+        TodoEvent::TodoPriorityChanged {
+            id,
+            from_priority,
+            to_priority,
+            changed_at,
+        } => (
+            5,
+            vec![
+                ("id".to_string(), Value::String(id.to_string())),
+                (
+                    "from_priority".to_string(),
+                    priority_to_avro(*from_priority),
+                ),
+                ("to_priority".to_string(), priority_to_avro(*to_priority)),
+                (
+                    "changed_at".to_string(),
+                    Value::TimestampMicros(changed_at.timestamp_micros()),
+                ),
+            ],
+        ),
+        TodoEvent::TodoTagAdded { id, tag, added_at } => (
+            6,
+            vec![
+                ("id".to_string(), Value::String(id.to_string())),
+                ("tag".to_string(), Value::String(tag.to_string())),
+                (
+                    "added_at".to_string(),
+                    Value::TimestampMicros(added_at.timestamp_micros()),
+                ),
+            ],
+        ),
+        TodoEvent::TodoTagRemoved {
+            id,
+            tag,
+            removed_at,
+        } => (
+            7,
+            vec![
+                ("id".to_string(), Value::String(id.to_string())),
+                ("tag".to_string(), Value::String(tag.to_string())),
+                (
+                    "removed_at".to_string(),
+                    Value::TimestampMicros(removed_at.timestamp_micros()),
+                ),
+            ],
+        ),
+        TodoEvent::TodoSubtaskAdded {
+            id,
+            subtask_id,
+            description,
+            added_at,
+        } => (
+            8,
+            vec![
+                ("id".to_string(), Value::String(id.to_string())),
+                (
+                    "subtask_id".to_string(),
+                    Value::String(subtask_id.to_string()),
+                ),
+                (
+                    "description".to_string(),
+                    Value::String(description.to_string()),
+                ),
+                (
+                    "added_at".to_string(),
+                    Value::TimestampMicros(added_at.timestamp_micros()),
+                ),
+            ],
+        ),
+        TodoEvent::TodoSubtaskToggled {
+            id,
+            subtask_id,
+            done,
+            toggled_at,
+        } => (
+            9,
+            vec![
+                ("id".to_string(), Value::String(id.to_string())),
+                (
+                    "subtask_id".to_string(),
+                    Value::String(subtask_id.to_string()),
+                ),
+                ("done".to_string(), Value::Boolean(*done)),
+                (
+                    "toggled_at".to_string(),
+                    Value::TimestampMicros(toggled_at.timestamp_micros()),
+                ),
+            ],
+        ),
+        TodoEvent::TodoSubtaskRemoved {
+            id,
+            subtask_id,
+            removed_at,
+        } => (
+            10,
+            vec![
+                ("id".to_string(), Value::String(id.to_string())),
+                (
+                    "subtask_id".to_string(),
+                    Value::String(subtask_id.to_string()),
+                ),
+                (
+                    "removed_at".to_string(),
+                    Value::TimestampMicros(removed_at.timestamp_micros()),
+                ),
+            ],
+        ),
+        TodoEvent::TodoDescriptionUpdated {
+            id,
+            old,
+            new,
+            updated_at,
+        } => (
+            11,
+            vec![
+                ("id".to_string(), Value::String(id.to_string())),
+                ("old".to_string(), Value::String(old.to_string())),
+                ("new".to_string(), Value::String(new.to_string())),
+                (
+                    "updated_at".to_string(),
+                    Value::TimestampMicros(updated_at.timestamp_micros()),
+                ),
+            ],
+        ),
+        TodoEvent::TodoBlocked {
+            id,
+            blocker_id,
+            blocked_at,
+        } => (
+            12,
+            vec![
+                ("id".to_string(), Value::String(id.to_string())),
+                (
+                    "blocker_id".to_string(),
+                    Value::String(blocker_id.to_string()),
+                ),
+                (
+                    "blocked_at".to_string(),
+                    Value::TimestampMicros(blocked_at.timestamp_micros()),
+                ),
+            ],
+        ),
+        TodoEvent::TodoUnblocked {
+            id,
+            blocker_id,
+            unblocked_at,
+        } => (
+            13,
+            vec![
+                ("id".to_string(), Value::String(id.to_string())),
+                (
+                    "blocker_id".to_string(),
+                    Value::String(blocker_id.to_string()),
+                ),
+                (
+                    "unblocked_at".to_string(),
+                    Value::TimestampMicros(unblocked_at.timestamp_micros()),
+                ),
+            ],
+        ),
+        TodoEvent::TodoDeleted { id, deleted_at } => (
+            14,
+            vec![
+                ("id".to_string(), Value::String(id.to_string())),
+                (
+                    "deleted_at".to_string(),
+                    Value::TimestampMicros(deleted_at.timestamp_micros()),
+                ),
+            ],
+        ),
+        TodoEvent::TodoRestored { id, restored_at } => (
+            15,
+            vec![
+                ("id".to_string(), Value::String(id.to_string())),
+                (
+                    "restored_at".to_string(),
+                    Value::TimestampMicros(restored_at.timestamp_micros()),
+                ),
+            ],
+        ),
+        TodoEvent::TodoAttachmentAdded {
+            id,
+            content_hash,
+            name,
+            media_type,
+            size,
+            added_at,
+        } => (
+            16,
+            vec![
+                ("id".to_string(), Value::String(id.to_string())),
+                (
+                    "content_hash".to_string(),
+                    Value::String(content_hash.to_string()),
+                ),
+                ("name".to_string(), Value::String(name.to_string())),
+                (
+                    "media_type".to_string(),
+                    Value::String(media_type.to_string()),
+                ),
+                (
+                    "size".to_string(),
+                    Value::Long(
+                        i64::try_from(*size)
+                            .map_err(|_| malformed("attachment size out of range"))?,
+                    ),
+                ),
+                (
+                    "added_at".to_string(),
+                    Value::TimestampMicros(added_at.timestamp_micros()),
+                ),
+            ],
+        ),
+        TodoEvent::TodoAttachmentRemoved {
+            id,
+            content_hash,
+            removed_at,
+        } => (
+            17,
+            vec![
+                ("id".to_string(), Value::String(id.to_string())),
+                (
+                    "content_hash".to_string(),
+                    Value::String(content_hash.to_string()),
+                ),
+                (
+                    "removed_at".to_string(),
+                    Value::TimestampMicros(removed_at.timestamp_micros()),
+                ),
+            ],
+        ),
+    };
+    let value = Value::Record(vec![(
+        "event".to_string(),
+        Value::Union(branch, Box::new(Value::Record(fields))),
+    )]);
+    to_avro_datum(schema(), value).map_err(|err| SerializationError::Malformed(err.to_string()))
+}
+
+/// Decodes an Avro datum written by [`encode_event`]
+pub fn decode_event(mut bytes: &[u8]) -> Result<TodoEvent, SerializationError> {
+    let value = from_avro_datum(schema(), &mut bytes, None)
+        .map_err(|err| SerializationError::Malformed(err.to_string()))?;
+    let Value::Record(mut fields) = value else {
+        return Err(malformed("expected a TodoEvent record"));
+    };
+    let Some((_, Value::Union(branch, event))) = fields.pop() else {
+        return Err(malformed("expected the event union"));
+    };
+    let Value::Record(fields) = *event else {
+        return Err(malformed("expected an event record"));
+    };
+    let field = |name: &str| {
+        fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value)
+            .ok_or_else(|| malformed(&format!("missing field {}", name)))
+    };
+    match branch {
+        0 => Ok(TodoEvent::TodoCreated {
+            id: string(field("id")?)?,
+            description: string(field("description")?)?,
+            created_at: timestamp(field("created_at")?)?,
+        }),
+        1 => Ok(TodoEvent::TodoStateChanged {
+            id: string(field("id")?)?,
+            from_state: state_from_avro(field("from_state")?)?,
+            to_state: state_from_avro(field("to_state")?)?,
+            changed_at: timestamp(field("changed_at")?)?,
+        }),
+        2 => Ok(TodoEvent::TodoArchived {
+            id: string(field("id")?)?,
+            archived_at: timestamp(field("archived_at")?)?,
+        }),
+        3 => Ok(TodoEvent::TodoStale {
+            id: string(field("id")?)?,
+            rule: string(field("rule")?)?,
+            state: state_from_avro(field("state")?)?,
+            stale_at: timestamp(field("stale_at")?)?,
+        }),
+        4 => Ok(TodoEvent::TodoDueDateChanged {
+            id: string(field("id")?)?,
+            due_at: match field("due_at")? {
+                Value::Union(_, value) if **value == Value::Null => None,
+                Value::Union(_, value) => Some(timestamp(value)?),
+                other => return Err(malformed(&format!("expected a union, found {:?}", other))),
+            },
+            changed_at: timestamp(field("changed_at")?)?,
+        }),
+        5 => Ok(TodoEvent::TodoPriorityChanged {
+            id: string(field("id")?)?,
+            from_priority: priority_from_avro(field("from_priority")?)?,
+            to_priority: priority_from_avro(field("to_priority")?)?,
+            changed_at: timestamp(field("changed_at")?)?,
+        }),
+        6 => Ok(TodoEvent::TodoTagAdded {
+            id: string(field("id")?)?,
+            tag: tag(field("tag")?)?,
+            added_at: timestamp(field("added_at")?)?,
+        }),
+        7 => Ok(TodoEvent::TodoTagRemoved {
+            id: string(field("id")?)?,
+            tag: tag(field("tag")?)?,
+            removed_at: timestamp(field("removed_at")?)?,
+        }),
+        8 => Ok(TodoEvent::TodoSubtaskAdded {
+            id: string(field("id")?)?,
+            subtask_id: string(field("subtask_id")?)?,
+            description: string(field("description")?)?,
+            added_at: timestamp(field("added_at")?)?,
+        }),
+        9 => Ok(TodoEvent::TodoSubtaskToggled {
+            id: string(field("id")?)?,
+            subtask_id: string(field("subtask_id")?)?,
+            done: boolean(field("done")?)?,
+            toggled_at: timestamp(field("toggled_at")?)?,
+        }),
+        10 => Ok(TodoEvent::TodoSubtaskRemoved {
+            id: string(field("id")?)?,
+            subtask_id: string(field("subtask_id")?)?,
+            removed_at: timestamp(field("removed_at")?)?,
+        }),
+        11 => Ok(TodoEvent::TodoDescriptionUpdated {
+            id: string(field("id")?)?,
+            old: string(field("old")?)?,
+            new: string(field("new")?)?,
+            updated_at: timestamp(field("updated_at")?)?,
+        }),
+        12 => Ok(TodoEvent::TodoBlocked {
+            id: string(field("id")?)?,
+            blocker_id: string(field("blocker_id")?)?,
+            blocked_at: timestamp(field("blocked_at")?)?,
+        }),
+        13 => Ok(TodoEvent::TodoUnblocked {
+            id: string(field("id")?)?,
+            blocker_id: string(field("blocker_id")?)?,
+            unblocked_at: timestamp(field("unblocked_at")?)?,
+        }),
+        14 => Ok(TodoEvent::TodoDeleted {
+            id: string(field("id")?)?,
+            deleted_at: timestamp(field("deleted_at")?)?,
+        }),
+        15 => Ok(TodoEvent::TodoRestored {
+            id: string(field("id")?)?,
+            restored_at: timestamp(field("restored_at")?)?,
+        }),
+        16 => Ok(TodoEvent::TodoAttachmentAdded {
+            id: string(field("id")?)?,
+            content_hash: string(field("content_hash")?)?,
+            name: string(field("name")?)?,
+            media_type: string(field("media_type")?)?,
+            size: size(field("size")?)?,
+            added_at: timestamp(field("added_at")?)?,
+        }),
+        17 => Ok(TodoEvent::TodoAttachmentRemoved {
+            id: string(field("id")?)?,
+            content_hash: string(field("content_hash")?)?,
+            removed_at: timestamp(field("removed_at")?)?,
+        }),
+        other => Err(malformed(&format!("unknown event branch {}", other))),
+    }
+}
+
+/// Encodes an event in the Confluent schema registry wire format
+///
+/// # Format
+/// A zero magic byte, the registry's `schema_id` as a big-endian `u32`, then the datum.
+pub fn encode_confluent(schema_id: u32, event: &TodoEvent) -> Result<Vec<u8>, SerializationError> {
+    let mut bytes = vec![0];
+    bytes.extend_from_slice(&schema_id.to_be_bytes());
+    bytes.extend(encode_event(event)?);
+    Ok(bytes)
+}
+
+/// Decodes a message written by [`encode_confluent`], returning the schema id and event
+pub fn decode_confluent(bytes: &[u8]) -> Result<(u32, TodoEvent), SerializationError> {
+    match bytes {
+        [0, a, b, c, d, datum @ ..] => {
+            Ok((u32::from_be_bytes([*a, *b, *c, *d]), decode_event(datum)?))
+        }
+        _ => Err(malformed("not in the schema registry wire format")),
+    }
+}
+
+fn state_to_avro(state: TodoState) -> Value {
+    match state {
+        TodoState::Todo => Value::Enum(0, "TODO".to_string()),
+        TodoState::InProgress => Value::Enum(1, "IN_PROGRESS".to_string()),
+        TodoState::Done => Value::Enum(2, "DONE".to_string()),
+    }
+}
+
+fn state_from_avro(value: &Value) -> Result<TodoState, SerializationError> {
+    match value {
+        Value::Enum(_, symbol) if symbol == "TODO" => Ok(TodoState::Todo),
+        Value::Enum(_, symbol) if symbol == "IN_PROGRESS" => Ok(TodoState::InProgress),
+        Value::Enum(_, symbol) if symbol == "DONE" => Ok(TodoState::Done),
+        other => Err(malformed(&format!("unknown todo state: {:?}", other))),
+    }
+}
+
+fn priority_to_avro(priority: Priority) -> Value {
+    let index = match priority {
+        Priority::Low => 0,
+        Priority::Medium => 1,
+        Priority::High => 2,
+        Priority::Urgent => 3,
+    };
+    Value::Enum(index, priority.name().to_string())
+}
+
+fn priority_from_avro(value: &Value) -> Result<Priority, SerializationError> {
+    match value {
+        Value::Enum(_, symbol) => Priority::parse(symbol).map_err(|err| malformed(&err)),
+        other => Err(malformed(&format!("unknown priority: {:?}", other))),
+    }
+}
+
+fn tag(value: &Value) -> Result<Tag, SerializationError> {
+    Tag::new(&string(value)?).map_err(|err| malformed(&err))
+}
+
+fn string(value: &Value) -> Result<Arc<str>, SerializationError> {
+    match value {
+        Value::String(value) => Ok(value.as_str().into()),
+        other => Err(malformed(&format!("expected a string, found {:?}", other))),
+    }
+}
+
+fn boolean(value: &Value) -> Result<bool, SerializationError> {
+    match value {
+        Value::Boolean(value) => Ok(*value),
+        other => Err(malformed(&format!("expected a boolean, found {:?}", other))),
+    }
+}
+
+fn size(value: &Value) -> Result<u64, SerializationError> {
+    match value {
+        Value::Long(size) => u64::try_from(*size).map_err(|_| malformed("negative size")),
+        other => Err(malformed(&format!("expected a long, found {:?}", other))),
+    }
+}
+
+fn timestamp(value: &Value) -> Result<DateTime<Utc>, SerializationError> {
+    match value {
+        Value::TimestampMicros(micros) => DateTime::from_timestamp_micros(*micros)
+            .ok_or_else(|| malformed("timestamp out of range")),
+        other => Err(malformed(&format!(
+            "expected a timestamp, found {:?}",
+            other
+        ))),
+    }
+}
+
+fn malformed(message: &str) -> SerializationError {
+    SerializationError::Malformed(message.to_string())
+}
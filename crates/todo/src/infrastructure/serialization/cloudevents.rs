@@ -0,0 +1,186 @@
+use serde::{Deserialize, Serialize};
+
+use crate::infrastructure::serialization::SerializationError;
+use crate::{IdGenerator, TodoEvent, UuidV4Generator};
+
+/// CloudEvents `specversion` written and accepted
+pub const SPEC_VERSION: &str = "1.0";
+/// `type` of `TodoEvent::TodoCreated`
+pub const TODO_CREATED_TYPE: &str = "com.hungknow.todo.created";
+/// `type` of `TodoEvent::TodoStateChanged`
+pub const TODO_STATE_CHANGED_TYPE: &str = "com.hungknow.todo.state_changed";
+/// `type` of `TodoEvent::TodoArchived`
+pub const TODO_ARCHIVED_TYPE: &str = "com.hungknow.todo.archived";
+/// `type` of `TodoEvent::TodoStale`
+pub const TODO_STALE_TYPE: &str = "com.hungknow.todo.stale";
+/// `type` of `TodoEvent::TodoDueDateChanged`
+pub const TODO_DUE_DATE_CHANGED_TYPE: &str = "com.hungknow.todo.due_date_changed";
+/// `type` of `TodoEvent::TodoPriorityChanged`
+pub const TODO_PRIORITY_CHANGED_TYPE: &str = "com.hungknow.todo.priority_changed";
+/// `type` of `TodoEvent::TodoTagAdded`
+pub const TODO_TAG_ADDED_TYPE: &str = "com.hungknow.todo.tag_added";
+/// `type` of `TodoEvent::TodoTagRemoved`
+pub const TODO_TAG_REMOVED_TYPE: &str = "com.hungknow.todo.tag_removed";
+/// `type` of `TodoEvent::TodoSubtaskAdded`
+pub const TODO_SUBTASK_ADDED_TYPE: &str = "com.hungknow.todo.subtask_added";
+/// `type` of `TodoEvent::TodoSubtaskToggled`
+pub const TODO_SUBTASK_TOGGLED_TYPE: &str = "com.hungknow.todo.subtask_toggled";
+/// `type` of `TodoEvent::TodoSubtaskRemoved`
+pub const TODO_SUBTASK_REMOVED_TYPE: &str = "com.hungknow.todo.subtask_removed";
+/// `type` of `TodoEvent::TodoDescriptionUpdated`
+pub const TODO_DESCRIPTION_UPDATED_TYPE: &str = "com.hungknow.todo.description_updated";
+/// `type` of `TodoEvent::TodoBlocked`
+pub const TODO_BLOCKED_TYPE: &str = "com.hungknow.todo.blocked";
+/// `type` of `TodoEvent::TodoUnblocked`
+pub const TODO_UNBLOCKED_TYPE: &str = "com.hungknow.todo.unblocked";
+/// `type` of `TodoEvent::TodoDeleted`
+pub const TODO_DELETED_TYPE: &str = "com.hungknow.todo.deleted";
+/// `type` of `TodoEvent::TodoRestored`
+pub const TODO_RESTORED_TYPE: &str = "com.hungknow.todo.restored";
+/// `type` of `TodoEvent::TodoAttachmentAdded`
+pub const TODO_ATTACHMENT_ADDED_TYPE: &str = "com.hungknow.todo.attachment_added";
+/// `type` of `TodoEvent::TodoAttachmentRemoved`
+pub const TODO_ATTACHMENT_REMOVED_TYPE: &str = "com.hungknow.todo.attachment_removed";
+
+/// A CloudEvents 1.0 event in the JSON event format
+///
+/// # Conventions
+/// - `type` is [`TODO_CREATED_TYPE`], [`TODO_STATE_CHANGED_TYPE`], [`TODO_ARCHIVED_TYPE`],
+///   [`TODO_STALE_TYPE`], [`TODO_DUE_DATE_CHANGED_TYPE`], [`TODO_PRIORITY_CHANGED_TYPE`],
+///   [`TODO_TAG_ADDED_TYPE`], [`TODO_TAG_REMOVED_TYPE`], [`TODO_SUBTASK_ADDED_TYPE`],
+///   [`TODO_SUBTASK_TOGGLED_TYPE`], [`TODO_SUBTASK_REMOVED_TYPE`],
+///   [`TODO_DESCRIPTION_UPDATED_TYPE`], [`TODO_BLOCKED_TYPE`], [`TODO_UNBLOCKED_TYPE`],
+///   [`TODO_DELETED_TYPE`], [`TODO_RESTORED_TYPE`], [`TODO_ATTACHMENT_ADDED_TYPE`] or
+///   [`TODO_ATTACHMENT_REMOVED_TYPE`]
+/// - `source` identifies the emitting process, such as `/hk-todo/server`
+/// - `subject` is the todo id
+/// - `time` is the event's own timestamp in RFC3339
+/// - `data` is the event in the domain's serde shape
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CloudEvent {
+    pub specversion: String,
+    pub id: String,
+    pub source: String,
+    #[serde(rename = "type")]
+    pub event_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub datacontenttype: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+}
+
+impl CloudEvent {
+    /// Wraps `event` with a new UUID as `id`
+    ///
+    /// Events carry no id of their own, so converting the same event twice yields two ids;
+    /// use [`CloudEvent::with_id`] when the caller has a stable one, such as a sequence number.
+    pub fn from_todo_event(event: &TodoEvent, source: &str) -> Self {
+        let (event_type, subject, time) = match event {
+            TodoEvent::TodoCreated { id, created_at, .. } => (TODO_CREATED_TYPE, id, created_at),
+            TodoEvent::TodoStateChanged { id, changed_at, .. } => {
+                (TODO_STATE_CHANGED_TYPE, id, changed_at)
+            }
+            TodoEvent::TodoArchived { id, archived_at } => (TODO_ARCHIVED_TYPE, id, archived_at),
+            TodoEvent::TodoStale { id, stale_at, .. } => (TODO_STALE_TYPE, id, stale_at),
+            TodoEvent::TodoDueDateChanged { id, changed_at, .. } => {
+                (TODO_DUE_DATE_CHANGED_TYPE, id, changed_at)
+            }
+            TodoEvent::TodoPriorityChanged { id, changed_at, .. } => {
+                (TODO_PRIORITY_CHANGED_TYPE, id, changed_at)
+            }
+            TodoEvent::TodoTagAdded { id, added_at, .. } => (TODO_TAG_ADDED_TYPE, id, added_at),
+            TodoEvent::TodoTagRemoved { id, removed_at, .. } => {
+                (TODO_TAG_REMOVED_TYPE, id, removed_at)
+            }
+            TodoEvent::TodoSubtaskAdded { id, added_at, .. } => {
+                (TODO_SUBTASK_ADDED_TYPE, id, added_at)
+            }
+            TodoEvent::TodoSubtaskToggled { id, toggled_at, .. } => {
+                (TODO_SUBTASK_TOGGLED_TYPE, id, toggled_at)
+            }
+            TodoEvent::TodoSubtaskRemoved { id, removed_at, .. } => {
+                (TODO_SUBTASK_REMOVED_TYPE, id, removed_at)
+            }
+            TodoEvent::TodoDescriptionUpdated { id, updated_at, .. } => {
+                (TODO_DESCRIPTION_UPDATED_TYPE, id, updated_at)
+            }
+            TodoEvent::TodoBlocked { id, blocked_at, .. } => (TODO_BLOCKED_TYPE, id, blocked_at),
+            TodoEvent::TodoUnblocked {
+                id, unblocked_at, ..
+            } => (TODO_UNBLOCKED_TYPE, id, unblocked_at),
+            TodoEvent::TodoDeleted { id, deleted_at } => (TODO_DELETED_TYPE, id, deleted_at),
+            TodoEvent::TodoRestored { id, restored_at } => (TODO_RESTORED_TYPE, id, restored_at),
+            TodoEvent::TodoAttachmentAdded { id, added_at, .. } => {
+                (TODO_ATTACHMENT_ADDED_TYPE, id, added_at)
+            }
+            TodoEvent::TodoAttachmentRemoved { id, removed_at, .. } => {
+                (TODO_ATTACHMENT_REMOVED_TYPE, id, removed_at)
+            }
+        };
+        CloudEvent {
+            specversion: SPEC_VERSION.to_string(),
+            id: UuidV4Generator.next_id(),
+            source: source.to_string(),
+            event_type: event_type.to_string(),
+            subject: Some(subject.to_string()),
+            time: Some(time.to_rfc3339()),
+            datacontenttype: Some("application/json".to_string()),
+            data: serde_json::to_value(event).ok(),
+        }
+    }
+
+    /// Replaces the generated `id`
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = id.into();
+        self
+    }
+}
+
+impl TryFrom<CloudEvent> for TodoEvent {
+    type Error = SerializationError;
+
+    /// Reads the todo event back, checking that `specversion` and `type` are known
+    fn try_from(event: CloudEvent) -> Result<Self, Self::Error> {
+        if event.specversion != SPEC_VERSION {
+            return Err(SerializationError::Malformed(format!(
+                "unsupported CloudEvents specversion: {}",
+                event.specversion
+            )));
+        }
+        if ![
+            TODO_CREATED_TYPE,
+            TODO_STATE_CHANGED_TYPE,
+            TODO_ARCHIVED_TYPE,
+            TODO_STALE_TYPE,
+            TODO_DUE_DATE_CHANGED_TYPE,
+            TODO_PRIORITY_CHANGED_TYPE,
+            TODO_TAG_ADDED_TYPE,
+            TODO_TAG_REMOVED_TYPE,
+            TODO_SUBTASK_ADDED_TYPE,
+            TODO_SUBTASK_TOGGLED_TYPE,
+            TODO_SUBTASK_REMOVED_TYPE,
+            TODO_DESCRIPTION_UPDATED_TYPE,
+            TODO_BLOCKED_TYPE,
+            TODO_UNBLOCKED_TYPE,
+            TODO_DELETED_TYPE,
+            TODO_RESTORED_TYPE,
+            TODO_ATTACHMENT_ADDED_TYPE,
+            TODO_ATTACHMENT_REMOVED_TYPE,
+        ]
+        .contains(&event.event_type.as_str())
+        {
+            return Err(SerializationError::Malformed(format!(
+                "not a todo event: {}",
+                event.event_type
+            )));
+        }
+        let data = event
+            .data
+            .ok_or_else(|| SerializationError::Malformed("event has no data".to_string()))?;
+        serde_json::from_value(data).map_err(|err| SerializationError::Malformed(err.to_string()))
+    }
+}
@@ -0,0 +1,263 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use std::collections::HashSet;
+use std::io::{Read, Write};
+
+use crate::infrastructure::serialization::SerializationError;
+use crate::{Todo, TodoState};
+
+/// A Todo field that can be mapped to a CSV column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CsvColumn {
+    Id,
+    Description,
+    State,
+    CreatedAt,
+}
+
+impl CsvColumn {
+    /// Header written for the column unless configured otherwise
+    pub fn default_header(&self) -> &'static str {
+        match self {
+            CsvColumn::Id => "id",
+            CsvColumn::Description => "description",
+            CsvColumn::State => "state",
+            CsvColumn::CreatedAt => "created_at",
+        }
+    }
+
+    /// Parses a default header name, ignoring case
+    pub fn parse(name: &str) -> Option<Self> {
+        [
+            CsvColumn::Id,
+            CsvColumn::Description,
+            CsvColumn::State,
+            CsvColumn::CreatedAt,
+        ]
+        .into_iter()
+        .find(|column| column.default_header().eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+/// How todos are laid out in a CSV file
+#[derive(Debug, Clone)]
+pub struct CsvOptions {
+    /// Columns written on export, in order, with their header names
+    ///
+    /// On import, a header matching one of these names, or a column's default header, ignoring
+    /// case, is read into that column; other columns are ignored.
+    pub columns: Vec<(CsvColumn, String)>,
+    /// `strftime` format of `created_at`, or `None` for RFC3339
+    ///
+    /// Formats without a time, such as `%Y-%m-%d`, import as midnight. Times are in UTC.
+    pub date_format: Option<String>,
+    pub delimiter: u8,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions {
+            columns: [
+                CsvColumn::Id,
+                CsvColumn::Description,
+                CsvColumn::State,
+                CsvColumn::CreatedAt,
+            ]
+            .into_iter()
+            .map(|column| (column, column.default_header().to_string()))
+            .collect(),
+            date_format: None,
+            delimiter: b',',
+        }
+    }
+}
+
+/// Todos read from a CSV file, and the rows that could not be read
+pub struct CsvImport {
+    pub todos: Vec<Todo>,
+    /// `SerializationError::InvalidRecord` for each rejected row, `record` being its line number
+    pub errors: Vec<SerializationError>,
+}
+
+/// Writes `todos` as CSV with a header row
+pub fn export_todos(
+    writer: impl Write,
+    todos: &[Todo],
+    options: &CsvOptions,
+) -> Result<(), SerializationError> {
+    let mut writer = ::csv::WriterBuilder::new()
+        .delimiter(options.delimiter)
+        .from_writer(writer);
+    writer
+        .write_record(options.columns.iter().map(|(_, header)| header))
+        .map_err(to_error)?;
+    for todo in todos {
+        let record = options.columns.iter().map(|(column, _)| match column {
+            CsvColumn::Id => todo.id.to_string(),
+            CsvColumn::Description => todo.description.to_string(),
+            CsvColumn::State => state_name(todo.state).to_string(),
+            CsvColumn::CreatedAt => format_date(&todo.created_at, options),
+        });
+        writer.write_record(record).map_err(to_error)?;
+    }
+    writer
+        .flush()
+        .map_err(|err| SerializationError::Io(err.to_string()))
+}
+
+/// Reads todos from CSV with a header row
+///
+/// # Returns
+/// - `Ok(CsvImport)`: The valid rows as todos, and an error for each invalid row
+/// - `Err(SerializationError)`: If the file cannot be read or has no description column
+///
+/// # Special Requirements
+/// - Rows without an id get a new one, without a state are `TODO`, and without a creation
+///   time are created now
+/// - States are read ignoring case, with spaces or dashes for underscores
+/// - Rows repeating an earlier id are rejected
+pub fn import_todos(
+    reader: impl Read,
+    options: &CsvOptions,
+) -> Result<CsvImport, SerializationError> {
+    let mut reader = ::csv::ReaderBuilder::new()
+        .delimiter(options.delimiter)
+        .flexible(true)
+        .from_reader(reader);
+    let columns: Vec<Option<CsvColumn>> = reader
+        .headers()
+        .map_err(to_error)?
+        .iter()
+        .map(|header| {
+            options
+                .columns
+                .iter()
+                .find(|(_, name)| name.trim().eq_ignore_ascii_case(header.trim()))
+                .map(|(column, _)| *column)
+                .or_else(|| CsvColumn::parse(header))
+        })
+        .collect();
+    if !columns.contains(&Some(CsvColumn::Description)) {
+        return Err(SerializationError::Malformed(
+            "no description column in the header".to_string(),
+        ));
+    }
+
+    let mut ids = HashSet::new();
+    let mut import = CsvImport {
+        todos: Vec::new(),
+        errors: Vec::new(),
+    };
+    for record in reader.records() {
+        let record = match record {
+            Ok(record) => record,
+            Err(err) if err.is_io_error() => return Err(to_error(err)),
+            Err(err) => {
+                let line = err
+                    .position()
+                    .map_or(0, |position| position.line() as usize);
+                import.errors.push(SerializationError::InvalidRecord {
+                    record: line,
+                    message: err.to_string(),
+                });
+                continue;
+            }
+        };
+        let line = record
+            .position()
+            .map_or(0, |position| position.line() as usize);
+        let todo = parse_todo(&columns, &record, options).and_then(|todo| {
+            if !ids.insert(todo.id.clone()) {
+                Err(format!("duplicate todo id {}", todo.id))
+            } else {
+                Ok(todo)
+            }
+        });
+        match todo {
+            Ok(todo) => import.todos.push(todo),
+            Err(message) => import.errors.push(SerializationError::InvalidRecord {
+                record: line,
+                message,
+            }),
+        }
+    }
+    Ok(import)
+}
+
+fn parse_todo(
+    columns: &[Option<CsvColumn>],
+    record: &::csv::StringRecord,
+    options: &CsvOptions,
+) -> Result<Todo, String> {
+    let field = |wanted: CsvColumn| {
+        columns
+            .iter()
+            .position(|column| *column == Some(wanted))
+            .and_then(|index| record.get(index))
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    };
+
+    let Some(description) = field(CsvColumn::Description) else {
+        return Err("todo description must not be empty".to_string());
+    };
+    let (mut todo, _) = Todo::new(description.to_string()).map_err(|err| err.to_string())?;
+    if let Some(id) = field(CsvColumn::Id) {
+        todo.id = id.into();
+    }
+    if let Some(state) = field(CsvColumn::State) {
+        todo.state = parse_state(state).ok_or_else(|| format!("unknown todo state: {}", state))?;
+    }
+    if let Some(created_at) = field(CsvColumn::CreatedAt) {
+        todo.created_at = parse_date(created_at, options)
+            .ok_or_else(|| format!("invalid created_at: {}", created_at))?;
+    }
+    Ok(todo)
+}
+
+fn state_name(state: TodoState) -> &'static str {
+    match state {
+        TodoState::Todo => "TODO",
+        TodoState::InProgress => "IN_PROGRESS",
+        TodoState::Done => "DONE",
+    }
+}
+
+fn parse_state(value: &str) -> Option<TodoState> {
+    match value.to_ascii_uppercase().replace([' ', '-'], "_").as_str() {
+        "TODO" => Some(TodoState::Todo),
+        "IN_PROGRESS" => Some(TodoState::InProgress),
+        "DONE" => Some(TodoState::Done),
+        _ => None,
+    }
+}
+
+fn format_date(value: &DateTime<Utc>, options: &CsvOptions) -> String {
+    match &options.date_format {
+        Some(format) => value.format(format).to_string(),
+        None => value.to_rfc3339(),
+    }
+}
+
+fn parse_date(value: &str, options: &CsvOptions) -> Option<DateTime<Utc>> {
+    let Some(format) = &options.date_format else {
+        return DateTime::parse_from_rfc3339(value)
+            .ok()
+            .map(|value| value.with_timezone(&Utc));
+    };
+    NaiveDateTime::parse_from_str(value, format)
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, format)
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })
+        .map(|value| value.and_utc())
+}
+
+fn to_error(err: ::csv::Error) -> SerializationError {
+    if err.is_io_error() {
+        SerializationError::Io(err.to_string())
+    } else {
+        SerializationError::Malformed(err.to_string())
+    }
+}
@@ -0,0 +1,141 @@
+use ring::aead::{AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use ring::pbkdf2::{self, PBKDF2_HMAC_SHA256};
+use ring::rand::{SecureRandom, SystemRandom};
+use std::num::NonZeroU32;
+
+use crate::infrastructure::serialization::SerializationError;
+
+/// First bytes of every encrypted archive
+pub const MAGIC: &[u8; 8] = b"HKTODOEN";
+/// Archive version written by [`encrypt`]; [`decrypt`] reads versions up to it
+pub const VERSION: u8 = 1;
+/// PBKDF2 iterations used by [`encrypt`]
+pub const ITERATIONS: u32 = 600_000;
+
+/// Most PBKDF2 iterations [`decrypt`] accepts, so a crafted archive cannot stall a restore
+const MAX_ITERATIONS: u32 = 10_000_000;
+const SALT_LEN: usize = 16;
+const KEY_LEN: usize = 32;
+/// Magic, version, iterations, salt and nonce
+const HEADER_LEN: usize = MAGIC.len() + 1 + 4 + SALT_LEN + NONCE_LEN;
+
+/// Encrypts an exported document with a key derived from `passphrase`
+///
+/// Any export format can be encrypted, as the document is taken as bytes. The archive can
+/// be stored where others may read or alter it: [`decrypt`] refuses any archive that was
+/// modified.
+///
+/// # Format
+/// `HKTODOEN`, the version byte, the PBKDF2-HMAC-SHA256 iterations as a big-endian `u32`,
+/// a 16-byte salt and a 12-byte nonce, then the document encrypted with AES-256-GCM and its
+/// 16-byte tag. The header is authenticated along with the document.
+pub fn encrypt(document: &[u8], passphrase: &str) -> Result<Vec<u8>, SerializationError> {
+    encrypt_with_iterations(document, passphrase, ITERATIONS)
+}
+
+/// Like [`encrypt`], deriving the key with `iterations` rounds of PBKDF2 instead of
+/// [`ITERATIONS`]
+///
+/// Fewer iterations make a weak passphrase quicker to guess; this is meant for tests and
+/// for slow devices.
+pub fn encrypt_with_iterations(
+    document: &[u8],
+    passphrase: &str,
+    iterations: u32,
+) -> Result<Vec<u8>, SerializationError> {
+    let iterations = NonZeroU32::new(iterations)
+        .filter(|iterations| iterations.get() <= MAX_ITERATIONS)
+        .ok_or_else(|| {
+            SerializationError::Malformed(format!(
+                "iterations must be between 1 and {}",
+                MAX_ITERATIONS
+            ))
+        })?;
+    let random = SystemRandom::new();
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    random
+        .fill(&mut salt)
+        .and_then(|_| random.fill(&mut nonce))
+        .map_err(|_| SerializationError::Io("no secure random source".to_string()))?;
+
+    let mut archive = Vec::with_capacity(HEADER_LEN + document.len() + AES_256_GCM.tag_len());
+    archive.extend_from_slice(MAGIC);
+    archive.push(VERSION);
+    archive.extend_from_slice(&iterations.get().to_be_bytes());
+    archive.extend_from_slice(&salt);
+    archive.extend_from_slice(&nonce);
+
+    let mut sealed = document.to_vec();
+    key(passphrase, &salt, iterations)
+        .seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(&archive[..HEADER_LEN]),
+            &mut sealed,
+        )
+        .map_err(|_| SerializationError::Malformed("document too large".to_string()))?;
+    archive.extend_from_slice(&sealed);
+    Ok(archive)
+}
+
+/// Decrypts an archive written by [`encrypt`], checking that it was not altered
+///
+/// # Returns
+/// - `Ok(Vec<u8>)`: The exported document
+/// - `Err(SerializationError::IntegrityCheckFailed)`: If the passphrase is wrong or the
+///   archive was modified; the two cannot be told apart
+/// - `Err(SerializationError)`: If `archive` is not an encrypted archive, or from a newer
+///   version
+pub fn decrypt(archive: &[u8], passphrase: &str) -> Result<Vec<u8>, SerializationError> {
+    if !is_encrypted(archive) {
+        return Err(SerializationError::Malformed(
+            "not an encrypted hk-todo archive".to_string(),
+        ));
+    }
+    if archive.len() < HEADER_LEN + AES_256_GCM.tag_len() {
+        return Err(SerializationError::Malformed(
+            "encrypted archive is truncated".to_string(),
+        ));
+    }
+    let (header, sealed) = archive.split_at(HEADER_LEN);
+    let version = header[MAGIC.len()];
+    if version == 0 || version > VERSION {
+        return Err(SerializationError::UnsupportedVersion(version.into()));
+    }
+    let (iterations, rest) = header[MAGIC.len() + 1..].split_at(4);
+    let (salt, nonce) = rest.split_at(SALT_LEN);
+    let iterations = u32::from_be_bytes(iterations.try_into().unwrap_or_default());
+    let iterations = NonZeroU32::new(iterations)
+        .filter(|iterations| iterations.get() <= MAX_ITERATIONS)
+        .ok_or_else(|| {
+            SerializationError::Malformed(format!("invalid iteration count {}", iterations))
+        })?;
+    let nonce = Nonce::try_assume_unique_for_key(nonce)
+        .map_err(|_| SerializationError::Malformed("invalid nonce".to_string()))?;
+
+    let mut document = sealed.to_vec();
+    let opened = key(passphrase, salt, iterations)
+        .open_in_place(nonce, Aad::from(header), &mut document)
+        .map_err(|_| SerializationError::IntegrityCheckFailed)?;
+    let len = opened.len();
+    document.truncate(len);
+    Ok(document)
+}
+
+/// Checks whether `bytes` start like an archive written by [`encrypt`]
+pub fn is_encrypted(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+fn key(passphrase: &str, salt: &[u8], iterations: NonZeroU32) -> LessSafeKey {
+    let mut key = [0u8; KEY_LEN];
+    pbkdf2::derive(
+        PBKDF2_HMAC_SHA256,
+        iterations,
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    let key = UnboundKey::new(&AES_256_GCM, &key).expect("AES-256 keys are 32 bytes");
+    LessSafeKey::new(key)
+}
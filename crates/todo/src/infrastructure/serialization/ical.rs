@@ -0,0 +1,276 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use std::io::{Read, Write};
+
+use crate::infrastructure::serialization::SerializationError;
+use crate::{Clock, SystemClock, Todo, TodoError, TodoState};
+
+/// `PRODID` of exported calendars
+pub const PRODID: &str = "-//hungknow//hk-todo//EN";
+
+/// Writes `todos` as an iCalendar (RFC 5545) `VCALENDAR` holding one `VTODO` each
+///
+/// # Format
+/// - `UID` is the todo id and `SUMMARY` its description
+/// - `STATUS` is `NEEDS-ACTION`, `IN-PROCESS` or `COMPLETED`
+/// - `CREATED` is the creation time in UTC
+pub fn export_todos(mut writer: impl Write, todos: &[Todo]) -> Result<(), SerializationError> {
+    let stamp = format_date(&SystemClock.now());
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{}", PRODID),
+    ];
+    for todo in todos {
+        lines.extend([
+            "BEGIN:VTODO".to_string(),
+            format!("UID:{}", escape(&todo.id)),
+            format!("DTSTAMP:{}", stamp),
+            format!("CREATED:{}", format_date(&todo.created_at)),
+            format!("SUMMARY:{}", escape(&todo.description)),
+            format!("STATUS:{}", status(todo.state)),
+            "END:VTODO".to_string(),
+        ]);
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    for line in lines {
+        writer
+            .write_all(fold(&line).as_bytes())
+            .map_err(|err| SerializationError::Io(err.to_string()))?;
+    }
+    writer
+        .flush()
+        .map_err(|err| SerializationError::Io(err.to_string()))
+}
+
+/// Reads the `VTODO` components of an iCalendar document
+///
+/// # Returns
+/// - `Ok(Vec<Todo>)`: One todo per `VTODO`, in document order
+/// - `Err(SerializationError)`: If the document is not iCalendar, or a `VTODO` has no
+///   `SUMMARY`, a `CANCELLED` status or an unreadable date; `record` counts `VTODO`s from 1
+///
+/// # Special Requirements
+/// - `VTODO`s without a `UID` get a new id, and without `CREATED` use `DTSTAMP` or now
+/// - Dates with a `TZID` are read as UTC
+/// - Other components, such as `VEVENT`, and those nested in a `VTODO`, such as `VALARM`,
+///   are ignored
+pub fn import_todos(mut reader: impl Read) -> Result<Vec<Todo>, SerializationError> {
+    let mut contents = String::new();
+    reader
+        .read_to_string(&mut contents)
+        .map_err(|err| SerializationError::Io(err.to_string()))?;
+    let lines = unfold(&contents);
+    if lines.first().map(String::as_str) != Some("BEGIN:VCALENDAR") {
+        return Err(SerializationError::Malformed(
+            "expected BEGIN:VCALENDAR".to_string(),
+        ));
+    }
+
+    let mut todos = Vec::new();
+    let mut current: Option<Vec<(String, String)>> = None;
+    // Components nested in the current VTODO, such as VALARMs, whose properties are skipped
+    let mut nested = 0;
+    for line in &lines {
+        let Some((name, value)) = parse_line(line) else {
+            continue;
+        };
+        match (name.as_str(), current.as_mut()) {
+            ("BEGIN", None) if value.eq_ignore_ascii_case("VTODO") => current = Some(Vec::new()),
+            ("BEGIN", Some(_)) => nested += 1,
+            ("END", Some(_)) if nested > 0 => nested -= 1,
+            (_, Some(_)) if nested > 0 => {}
+            ("END", Some(properties)) if value.eq_ignore_ascii_case("VTODO") => {
+                let todo = parse_todo(properties).map_err(|message| {
+                    SerializationError::InvalidRecord {
+                        record: todos.len() + 1,
+                        message,
+                    }
+                })?;
+                todos.push(todo);
+                current = None;
+            }
+            (_, Some(properties)) => properties.push((name, value)),
+            _ => {}
+        }
+    }
+    if current.is_some() {
+        return Err(SerializationError::Malformed(
+            "VTODO is missing END:VTODO".to_string(),
+        ));
+    }
+    Ok(todos)
+}
+
+/// Rewrites the first `VTODO` of `calendar` from `todo`, keeping everything else
+///
+/// `SUMMARY` and `STATUS` take the todo's description and state, and `DTSTAMP` the current
+/// time. Other properties, such as `DESCRIPTION` or `VALARM`s, and other components stay as
+/// they are, so a task edited elsewhere loses nothing the todo does not hold.
+///
+/// # Returns
+/// - `Ok(String)`: The calendar, with folded lines
+/// - `Err(SerializationError::Malformed)`: If `calendar` is not iCalendar or has no `VTODO`
+pub fn update_vtodo(calendar: &str, todo: &Todo) -> Result<String, SerializationError> {
+    let lines = unfold(calendar);
+    if lines.first().map(String::as_str) != Some("BEGIN:VCALENDAR") {
+        return Err(SerializationError::Malformed(
+            "expected BEGIN:VCALENDAR".to_string(),
+        ));
+    }
+
+    let replaced = [
+        ("DTSTAMP", format_date(&SystemClock.now())),
+        ("SUMMARY", escape(&todo.description)),
+        ("STATUS", status(todo.state).to_string()),
+    ];
+    let mut updated = String::with_capacity(calendar.len());
+    let mut depth = 0;
+    let mut found = false;
+    for line in lines {
+        let (name, value) = parse_line(&line).unwrap_or_default();
+        let in_todo = depth > 0;
+        match name.as_str() {
+            "BEGIN" if in_todo => depth += 1,
+            "BEGIN" if !found && value.eq_ignore_ascii_case("VTODO") => {
+                depth = 1;
+                found = true;
+            }
+            "END" if in_todo => {
+                depth -= 1;
+                if depth == 0 {
+                    for (name, value) in &replaced {
+                        updated.push_str(&fold(&format!("{}:{}", name, value)));
+                    }
+                }
+            }
+            _ if depth == 1 && replaced.iter().any(|(replaced, _)| *replaced == name) => {
+                continue;
+            }
+            _ => {}
+        }
+        updated.push_str(&fold(&line));
+    }
+    if !found {
+        return Err(SerializationError::Malformed(
+            "calendar has no VTODO".to_string(),
+        ));
+    }
+    Ok(updated)
+}
+
+fn parse_todo(properties: &[(String, String)]) -> Result<Todo, String> {
+    let property = |wanted: &str| {
+        properties
+            .iter()
+            .find(|(name, _)| name == wanted)
+            .map(|(_, value)| value.as_str())
+    };
+
+    let description = unescape(property("SUMMARY").unwrap_or_default());
+    let (mut todo, _) = Todo::new(description).map_err(|err| match err {
+        TodoError::EmptyDescription => "VTODO has no SUMMARY".to_string(),
+        err => err.to_string(),
+    })?;
+    if let Some(uid) = property("UID").filter(|uid| !uid.is_empty()) {
+        todo.id = unescape(uid).into();
+    }
+    if let Some(status) = property("STATUS") {
+        todo.state = match status.to_ascii_uppercase().as_str() {
+            "NEEDS-ACTION" => TodoState::Todo,
+            "IN-PROCESS" => TodoState::InProgress,
+            "COMPLETED" => TodoState::Done,
+            other => return Err(format!("unsupported VTODO status: {}", other)),
+        };
+    }
+    if let Some(created) = property("CREATED").or_else(|| property("DTSTAMP")) {
+        todo.created_at =
+            parse_date(created).ok_or_else(|| format!("invalid date: {}", created))?;
+    }
+    Ok(todo)
+}
+
+fn status(state: TodoState) -> &'static str {
+    match state {
+        TodoState::Todo => "NEEDS-ACTION",
+        TodoState::InProgress => "IN-PROCESS",
+        TodoState::Done => "COMPLETED",
+    }
+}
+
+fn format_date(value: &DateTime<Utc>) -> String {
+    value.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim_end_matches('Z');
+    NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y%m%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })
+        .map(|value| value.and_utc())
+}
+
+/// Splits `NAME;PARAM=...:value` into the upper-cased name and the value
+fn parse_line(line: &str) -> Option<(String, String)> {
+    let (head, value) = line.split_once(':')?;
+    let name = head.split(';').next().unwrap_or(head);
+    Some((name.to_ascii_uppercase(), value.to_string()))
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+fn unescape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => result.push('\n'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+/// Folds a content line at 75 octets, without splitting a UTF-8 character
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 4);
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(c);
+        width += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+/// Joins folded lines back into content lines
+fn unfold(contents: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in contents.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some(last)) => last.push_str(continuation),
+            _ if line.is_empty() => {}
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
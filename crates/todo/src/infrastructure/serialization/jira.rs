@@ -0,0 +1,137 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::io::Read;
+
+use crate::infrastructure::serialization::SerializationError;
+use crate::{Todo, TodoState};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchResults {
+    issues: Vec<serde_json::Value>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Issue {
+    id: String,
+    key: String,
+    fields: Fields,
+}
+
+#[derive(Deserialize)]
+struct Fields {
+    summary: String,
+    status: Status,
+    created: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Status {
+    name: String,
+    status_category: Option<StatusCategory>,
+}
+
+#[derive(Deserialize)]
+struct StatusCategory {
+    key: String,
+}
+
+/// How Jira workflow statuses map onto todo states
+#[derive(Debug, Clone, Default)]
+pub struct StatusMap {
+    /// Workflow status names, matched ignoring case, and the state their issues get
+    ///
+    /// Statuses not listed fall back to their category: `new` is `TODO`, `indeterminate`
+    /// is `IN_PROGRESS` and `done` is `DONE`.
+    pub statuses: Vec<(String, TodoState)>,
+}
+
+impl StatusMap {
+    /// The state of an issue in `status`, or `None` if it is neither listed nor categorized
+    pub fn state(&self, status: &str, category: Option<&str>) -> Option<TodoState> {
+        self.statuses
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(status))
+            .map(|(_, state)| *state)
+            .or(match category {
+                Some("new") => Some(TodoState::Todo),
+                Some("indeterminate") => Some(TodoState::InProgress),
+                Some("done") => Some(TodoState::Done),
+                _ => None,
+            })
+    }
+}
+
+/// One page of a Jira Cloud `GET /rest/api/3/search/jql` response
+pub struct SearchPage {
+    /// The page's issues, with the issue id as todo id
+    pub todos: Vec<Todo>,
+    /// Issue keys, such as `PROJ-12`, in the same order as `todos`
+    pub keys: Vec<String>,
+    /// Token to request the next page with; `None` on the last page
+    pub next_page_token: Option<String>,
+}
+
+/// Reads a page of Jira issues found by a JQL search
+///
+/// The search must request at least the `summary`, `status` and `created` fields.
+///
+/// # Returns
+/// - `Ok(SearchPage)`: The page's issues as todos, in result order, and the next page token
+/// - `Err(SerializationError)`: If the page is not valid JSON, or an issue has an empty
+///   summary, a status `statuses` cannot map or an unreadable `created`; `record` counts
+///   issues from 1
+pub fn read_search_page(
+    mut reader: impl Read,
+    statuses: &StatusMap,
+) -> Result<SearchPage, SerializationError> {
+    let mut contents = String::new();
+    reader
+        .read_to_string(&mut contents)
+        .map_err(|err| SerializationError::Io(err.to_string()))?;
+    let results: SearchResults = serde_json::from_str(&contents)
+        .map_err(|err| SerializationError::Malformed(err.to_string()))?;
+
+    let mut todos = Vec::new();
+    let mut keys = Vec::new();
+    for (index, value) in results.issues.into_iter().enumerate() {
+        let invalid = |message: String| SerializationError::InvalidRecord {
+            record: index + 1,
+            message,
+        };
+        let issue: Issue = serde_json::from_value(value).map_err(|err| invalid(err.to_string()))?;
+        let status = &issue.fields.status;
+        let category = status
+            .status_category
+            .as_ref()
+            .map(|category| category.key.as_str());
+        let state = statuses
+            .state(&status.name, category)
+            .ok_or_else(|| invalid(format!("unmapped status: {}", status.name)))?;
+        let (mut todo, _) =
+            Todo::new(issue.fields.summary).map_err(|err| invalid(err.to_string()))?;
+        todo.id = issue.id.into();
+        todo.state = state;
+        if let Some(created) = issue.fields.created {
+            todo.created_at = parse_date(&created)
+                .ok_or_else(|| invalid(format!("invalid created date: {}", created)))?;
+        }
+        todos.push(todo);
+        keys.push(issue.key);
+    }
+    Ok(SearchPage {
+        todos,
+        keys,
+        next_page_token: results.next_page_token,
+    })
+}
+
+/// Parses Jira's `2024-01-02T03:04:05.000+0000`, or RFC3339
+fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f%z")
+        .or_else(|_| DateTime::parse_from_rfc3339(value))
+        .map(|value| value.with_timezone(&Utc))
+        .ok()
+}
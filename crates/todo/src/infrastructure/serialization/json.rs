@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::io::{Read, Write};
+
+use crate::infrastructure::serialization::SerializationError;
+use crate::{Clock, SystemClock, Todo, TodoError};
+
+/// Value of the envelope's `format` field
+pub const FORMAT: &str = "hk-todo";
+/// Envelope version written by [`export_todos`]; [`import_todos`] reads versions up to it
+pub const VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct ExportEnvelope<'a> {
+    format: &'static str,
+    version: u32,
+    exported_at: String,
+    todos: &'a [Todo],
+}
+
+#[derive(Deserialize)]
+struct ImportEnvelope {
+    format: String,
+    version: u32,
+    todos: Vec<serde_json::Value>,
+}
+
+/// Writes `todos` as a versioned JSON document
+///
+/// # Format
+/// `{"format": "hk-todo", "version": 1, "exported_at": "<RFC3339>", "todos": [...]}`, each
+/// todo in the domain's serde shape
+pub fn export_todos(writer: impl Write, todos: &[Todo]) -> Result<(), SerializationError> {
+    let envelope = ExportEnvelope {
+        format: FORMAT,
+        version: VERSION,
+        exported_at: SystemClock.now().to_rfc3339(),
+        todos,
+    };
+    serde_json::to_writer_pretty(writer, &envelope).map_err(|err| {
+        if err.is_io() {
+            SerializationError::Io(err.to_string())
+        } else {
+            SerializationError::Malformed(err.to_string())
+        }
+    })
+}
+
+/// Reads the todos of a document written by [`export_todos`]
+///
+/// # Returns
+/// - `Ok(Vec<Todo>)`: Every todo in the document, in document order
+/// - `Err(SerializationError)`: If the document is not valid JSON, not an `hk-todo` envelope,
+///   from a newer version, or holds an invalid todo or a duplicate id
+pub fn import_todos(reader: impl Read) -> Result<Vec<Todo>, SerializationError> {
+    let envelope: ImportEnvelope = serde_json::from_reader(reader).map_err(|err| {
+        if err.is_io() {
+            SerializationError::Io(err.to_string())
+        } else {
+            SerializationError::Malformed(err.to_string())
+        }
+    })?;
+    if envelope.format != FORMAT {
+        return Err(SerializationError::Malformed(format!(
+            "expected format \"{}\", found \"{}\"",
+            FORMAT, envelope.format
+        )));
+    }
+    if envelope.version == 0 || envelope.version > VERSION {
+        return Err(SerializationError::UnsupportedVersion(envelope.version));
+    }
+
+    let mut ids = HashSet::new();
+    let mut todos = Vec::with_capacity(envelope.todos.len());
+    for (index, value) in envelope.todos.into_iter().enumerate() {
+        let invalid = |message: String| SerializationError::InvalidRecord {
+            record: index + 1,
+            message,
+        };
+        let todo: Todo = serde_json::from_value(value).map_err(|err| invalid(err.to_string()))?;
+        if todo.id.trim().is_empty() {
+            return Err(invalid("todo id must not be empty".to_string()));
+        }
+        if todo.description.trim().is_empty() {
+            return Err(invalid(TodoError::EmptyDescription.to_string()));
+        }
+        if !ids.insert(todo.id.clone()) {
+            return Err(invalid(format!("duplicate todo id {}", todo.id)));
+        }
+        todos.push(todo);
+    }
+    Ok(todos)
+}
@@ -0,0 +1,20 @@
+use schemars::{JsonSchema, schema_for};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use crate::{Todo, TodoError, TodoEvent, TodoState};
+
+/// JSON Schemas (draft 2020-12) of the domain types' serde representation, keyed by type name
+pub fn schemas() -> BTreeMap<&'static str, Value> {
+    BTreeMap::from([
+        ("Todo", schema::<Todo>()),
+        ("TodoError", schema::<TodoError>()),
+        ("TodoEvent", schema::<TodoEvent>()),
+        ("TodoState", schema::<TodoState>()),
+    ])
+}
+
+/// The JSON Schema of `T`, with its referenced definitions under `$defs`
+pub fn schema<T: JsonSchema>() -> Value {
+    schema_for!(T).to_value()
+}
@@ -0,0 +1,171 @@
+use std::collections::HashSet;
+use std::io::{Read, Write};
+
+use crate::infrastructure::serialization::SerializationError;
+use crate::{Todo, TodoState};
+
+/// Writes `todos` as a Markdown task list
+///
+/// # Format
+/// `- [ ] <description> <!-- id: <id> -->`, with `[/]` for `IN_PROGRESS` and `[x]` for `DONE`,
+/// followed by the todo's subtasks as items nested two spaces deeper, `[ ]` or `[x]`, with
+/// their own id comments. The id comment is hidden when the Markdown is rendered and lets
+/// [`import_todos`] recognize todos it has seen before. Newlines in descriptions are replaced
+/// by spaces.
+pub fn export_todos(mut writer: impl Write, todos: &[Todo]) -> Result<(), SerializationError> {
+    let mut document = String::new();
+    for todo in todos {
+        document.push_str(&format!(
+            "- [{}] {} <!-- id: {} -->\n",
+            marker(todo.state),
+            single_line(&todo.description),
+            todo.id
+        ));
+        for subtask in &todo.subtasks {
+            document.push_str(&format!(
+                "  - [{}] {} <!-- id: {} -->\n",
+                if subtask.done { 'x' } else { ' ' },
+                single_line(&subtask.description),
+                subtask.id
+            ));
+        }
+    }
+    writer
+        .write_all(document.as_bytes())
+        .and_then(|_| writer.flush())
+        .map_err(|err| SerializationError::Io(err.to_string()))
+}
+
+/// Reads the task list items of a Markdown document
+///
+/// # Returns
+/// - `Ok(Vec<Todo>)`: One todo per top-level item, in document order
+/// - `Err(SerializationError)`: If two items, or two subtasks of a todo, carry the same id
+///   comment; `record` is the line
+///
+/// # Special Requirements
+/// - Items start with `-`, `*` or `+`, at any indentation; items nested under an item, at
+///   any depth, become subtasks of its todo, in document order
+/// - `[ ]` is `TODO`, `[/]` is `IN_PROGRESS`, and `[x]` or `[X]` is `DONE`; only `[x]` and
+///   `[X]` subtasks are done
+/// - Items without an id comment get a new id; other lines and empty items are ignored
+pub fn import_todos(mut reader: impl Read) -> Result<Vec<Todo>, SerializationError> {
+    let mut contents = String::new();
+    reader
+        .read_to_string(&mut contents)
+        .map_err(|err| SerializationError::Io(err.to_string()))?;
+
+    let mut ids = HashSet::new();
+    let mut todos: Vec<Todo> = Vec::new();
+    // Indentation of the last top-level item, while it has a todo
+    let mut parent_indent = None;
+    for (index, line) in contents.lines().enumerate() {
+        let Some((state, text)) = parse_item(line) else {
+            continue;
+        };
+        let (description, id) = split_id(text);
+        let indent = line.len() - line.trim_start().len();
+        if let (Some(parent_indent), Some(todo)) = (parent_indent, todos.last_mut())
+            && indent > parent_indent
+        {
+            add_subtask(todo, description, state, id).map_err(|message| {
+                SerializationError::InvalidRecord {
+                    record: index + 1,
+                    message,
+                }
+            })?;
+            continue;
+        }
+        parent_indent = None;
+        let Ok((mut todo, _)) = Todo::new(description.to_string()) else {
+            continue;
+        };
+        todo.state = state;
+        if let Some(id) = id {
+            if !ids.insert(id.to_string()) {
+                return Err(SerializationError::InvalidRecord {
+                    record: index + 1,
+                    message: format!("duplicate todo id {}", id),
+                });
+            }
+            todo.id = id.into();
+        }
+        parent_indent = Some(indent);
+        todos.push(todo);
+    }
+    Ok(todos)
+}
+
+/// Adds a nested item to `todo`'s checklist, skipping empty items
+///
+/// # Returns
+/// - `Err(String)`: If `todo` already has a subtask with `id`
+fn add_subtask(
+    todo: &mut Todo,
+    description: &str,
+    state: TodoState,
+    id: Option<&str>,
+) -> Result<(), String> {
+    if let Some(id) = id
+        && todo.subtasks.iter().any(|subtask| &*subtask.id == id)
+    {
+        return Err(format!("duplicate subtask id {}", id));
+    }
+    if todo.add_subtask(description.to_string()).is_err() {
+        return Ok(());
+    }
+    if let Some(subtask) = todo.subtasks.last_mut() {
+        subtask.done = state == TodoState::Done;
+        if let Some(id) = id {
+            subtask.id = id.into();
+        }
+    }
+    Ok(())
+}
+
+fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn marker(state: TodoState) -> char {
+    match state {
+        TodoState::Todo => ' ',
+        TodoState::InProgress => '/',
+        TodoState::Done => 'x',
+    }
+}
+
+/// Splits `- [x] text` into the state and the text
+fn parse_item(line: &str) -> Option<(TodoState, &str)> {
+    let rest = line.trim_start().strip_prefix(['-', '*', '+'])?;
+    let rest = rest.strip_prefix(' ')?.trim_start().strip_prefix('[')?;
+    let mut chars = rest.chars();
+    let state = match chars.next()? {
+        ' ' => TodoState::Todo,
+        '/' => TodoState::InProgress,
+        'x' | 'X' => TodoState::Done,
+        _ => return None,
+    };
+    let text = chars.as_str().strip_prefix(']')?;
+    if !text.is_empty() && !text.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some((state, text.trim()))
+}
+
+/// Splits a trailing `<!-- id: ... -->` off the item text
+fn split_id(text: &str) -> (&str, Option<&str>) {
+    let Some(start) = text.rfind("<!--") else {
+        return (text, None);
+    };
+    let comment = text[start + 4..].trim_end();
+    let id = comment
+        .strip_suffix("-->")
+        .and_then(|comment| comment.trim().strip_prefix("id:"))
+        .map(str::trim)
+        .filter(|id| !id.is_empty());
+    match id {
+        Some(id) => (text[..start].trim_end(), Some(id)),
+        None => (text, None),
+    }
+}
@@ -0,0 +1,157 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::io::Read;
+
+use crate::infrastructure::serialization::SerializationError;
+use crate::{Priority, Todo, TodoState};
+
+/// A Microsoft Graph `todoTask`; fields the domain has no place for are not read
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Task {
+    id: String,
+    title: Option<String>,
+    status: Option<String>,
+    importance: Option<String>,
+    due_date_time: Option<DateTimeTimeZone>,
+    created_date_time: Option<String>,
+    #[serde(rename = "@removed")]
+    removed: Option<Value>,
+}
+
+/// A Graph `dateTimeTimeZone`, such as `{"dateTime": "2024-01-10T17:00:00.0000000", "timeZone":
+/// "UTC"}`
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DateTimeTimeZone {
+    date_time: String,
+}
+
+#[derive(Deserialize)]
+struct Page {
+    value: Vec<Value>,
+    #[serde(rename = "@odata.nextLink")]
+    next_link: Option<String>,
+    #[serde(rename = "@odata.deltaLink")]
+    delta_link: Option<String>,
+}
+
+/// One page of a `GET /me/todo/lists/{id}/tasks/delta` response
+pub struct DeltaPage {
+    /// Tasks added or changed since the previous delta link, with the Graph task id as todo id
+    pub todos: Vec<Todo>,
+    /// Ids of tasks deleted since the previous delta link
+    pub removed: Vec<String>,
+    /// URL of the next page; `None` on the last page
+    pub next_link: Option<String>,
+    /// URL to request the next round of changes from; only set on the last page
+    pub delta_link: Option<String>,
+}
+
+/// Reads a page of a Microsoft To Do delta query, or of a plain task list
+///
+/// # Returns
+/// - `Ok(DeltaPage)`: The changed and removed tasks, in document order, and the paging links
+/// - `Err(SerializationError)`: If the page is not valid JSON, or a changed task has no title,
+///   an unknown status or importance, or an unreadable `createdDateTime` or `dueDateTime`;
+///   `record` counts tasks from 1
+///
+/// # Special Requirements
+/// - `notStarted` and `deferred` tasks are `TODO`, `inProgress` and `waitingOnOthers` tasks
+///   are `IN_PROGRESS`, and `completed` tasks are `DONE`
+/// - `low`, `normal` and `high` importance are `LOW`, `MEDIUM` and `HIGH` priority
+/// - `dueDateTime` is the due date, read as UTC, as Graph returns it unless asked otherwise;
+///   a due date before the creation time is moved to it
+/// - Tasks without a status are `TODO`, without importance `MEDIUM`, and without
+///   `createdDateTime` are created now
+pub fn read_delta_page(mut reader: impl Read) -> Result<DeltaPage, SerializationError> {
+    let mut contents = String::new();
+    reader
+        .read_to_string(&mut contents)
+        .map_err(|err| SerializationError::Io(err.to_string()))?;
+    let page: Page = serde_json::from_str(&contents)
+        .map_err(|err| SerializationError::Malformed(err.to_string()))?;
+
+    let mut todos = Vec::new();
+    let mut removed = Vec::new();
+    for (index, value) in page.value.into_iter().enumerate() {
+        let invalid = |message: String| SerializationError::InvalidRecord {
+            record: index + 1,
+            message,
+        };
+        let task: Task = serde_json::from_value(value).map_err(|err| invalid(err.to_string()))?;
+        if task.removed.is_some() {
+            removed.push(task.id);
+            continue;
+        }
+        let state = match task.status.as_deref() {
+            None | Some("notStarted") | Some("deferred") => TodoState::Todo,
+            Some("inProgress") | Some("waitingOnOthers") => TodoState::InProgress,
+            Some("completed") => TodoState::Done,
+            Some(other) => return Err(invalid(format!("unknown task status: {}", other))),
+        };
+        let (mut todo, _) =
+            Todo::new(task.title.unwrap_or_default()).map_err(|err| invalid(err.to_string()))?;
+        todo.id = task.id.into();
+        todo.state = state;
+        todo.priority = match task.importance.as_deref() {
+            Some("low") => Priority::Low,
+            None | Some("normal") => Priority::Medium,
+            Some("high") => Priority::High,
+            Some(other) => return Err(invalid(format!("unknown task importance: {}", other))),
+        };
+        if let Some(created) = task.created_date_time {
+            todo.created_at = DateTime::parse_from_rfc3339(&created)
+                .map(|value| value.with_timezone(&Utc))
+                .map_err(|_| invalid(format!("invalid createdDateTime: {}", created)))?;
+        }
+        if let Some(due) = task.due_date_time {
+            let due_at = NaiveDateTime::parse_from_str(&due.date_time, "%Y-%m-%dT%H:%M:%S%.f")
+                .map_err(|_| invalid(format!("invalid dueDateTime: {}", due.date_time)))?
+                .and_utc();
+            todo.due_at = Some(due_at.max(todo.created_at));
+        }
+        todos.push(todo);
+    }
+    Ok(DeltaPage {
+        todos,
+        removed,
+        next_link: page.next_link,
+        delta_link: page.delta_link,
+    })
+}
+
+/// The `todoTask` body to create or update `todo` with
+///
+/// # Format
+/// `{"title": description, "status": "notStarted" | "inProgress" | "completed", "importance":
+/// "low" | "normal" | "high", "dueDateTime": {"dateTime", "timeZone": "UTC"} | null}`, with
+/// `URGENT` todos of `high` importance
+pub fn task_body(todo: &Todo) -> Value {
+    json!({
+        "title": todo.description,
+        "status": status(todo.state),
+        "importance": importance(todo.priority),
+        "dueDateTime": todo.due_at.map(|due_at| json!({
+            "dateTime": due_at.format("%Y-%m-%dT%H:%M:%S%.7f").to_string(),
+            "timeZone": "UTC",
+        })),
+    })
+}
+
+fn importance(priority: Priority) -> &'static str {
+    match priority {
+        Priority::Low => "low",
+        Priority::Medium => "normal",
+        Priority::High | Priority::Urgent => "high",
+    }
+}
+
+fn status(state: TodoState) -> &'static str {
+    match state {
+        TodoState::Todo => "notStarted",
+        TodoState::InProgress => "inProgress",
+        TodoState::Done => "completed",
+    }
+}
@@ -0,0 +1,73 @@
+#[cfg(feature = "csv")]
+pub mod csv;
+pub mod ical;
+#[cfg(feature = "serde")]
+pub mod jira;
+pub mod markdown;
+#[cfg(feature = "serde")]
+pub mod microsoft_todo;
+#[cfg(feature = "serde")]
+pub mod ndjson;
+pub mod org;
+pub mod snapshot;
+#[cfg(feature = "protobuf")]
+pub mod protobuf;
+#[cfg(feature = "serde")]
+pub mod taskwarrior;
+#[cfg(feature = "avro")]
+pub mod avro;
+#[cfg(feature = "serde")]
+pub mod cloudevents;
+#[cfg(feature = "encryption")]
+pub mod encrypted;
+#[cfg(feature = "serde")]
+pub mod json;
+#[cfg(feature = "schemars")]
+pub mod json_schema;
+#[cfg(feature = "serde")]
+pub mod user_archive;
+
+use crate::TodoError;
+
+/// Error types for reading and writing todos in an exchange format
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SerializationError {
+    /// Returned when the underlying reader or writer fails
+    Io(String),
+    /// Returned when the input cannot be parsed in the expected format
+    Malformed(String),
+    /// Returned when the input was written by an unsupported format version
+    UnsupportedVersion(u32),
+    /// Returned when a parsed record is not a valid todo; `record` is 1-based
+    InvalidRecord { record: usize, message: String },
+    /// Returned when an encrypted archive fails its integrity check: the passphrase is wrong
+    /// or the archive was altered
+    IntegrityCheckFailed,
+}
+
+impl std::fmt::Display for SerializationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SerializationError::Io(message) => write!(f, "i/o error: {}", message),
+            SerializationError::Malformed(message) => write!(f, "malformed input: {}", message),
+            SerializationError::UnsupportedVersion(version) => {
+                write!(f, "unsupported format version: {}", version)
+            }
+            SerializationError::InvalidRecord { record, message } => {
+                write!(f, "record {}: {}", record, message)
+            }
+            SerializationError::IntegrityCheckFailed => write!(
+                f,
+                "integrity check failed: wrong passphrase or corrupted archive"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SerializationError {}
+
+impl From<SerializationError> for TodoError {
+    fn from(err: SerializationError) -> Self {
+        TodoError::RepositoryError(err.to_string())
+    }
+}
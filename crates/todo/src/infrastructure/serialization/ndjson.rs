@@ -0,0 +1,16 @@
+use std::io::Write;
+
+use crate::Todo;
+use crate::infrastructure::serialization::SerializationError;
+
+/// Writes `todo` as one line of newline-delimited JSON
+///
+/// # Format
+/// The todo's serde representation, `{id, created_at, description, state}`, followed by `\n`
+pub fn write_todo(mut writer: impl Write, todo: &Todo) -> Result<(), SerializationError> {
+    serde_json::to_writer(&mut writer, todo)
+        .map_err(|err| SerializationError::Io(err.to_string()))?;
+    writer
+        .write_all(b"\n")
+        .map_err(|err| SerializationError::Io(err.to_string()))
+}
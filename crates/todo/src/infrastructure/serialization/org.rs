@@ -0,0 +1,65 @@
+use std::io::Write;
+
+use crate::infrastructure::serialization::SerializationError;
+use crate::{Todo, TodoState};
+
+/// Writes `todos` as an Org document with one headline each
+///
+/// # Format
+/// - A `#+TODO: TODO DOING | DONE` line declares the keywords, so Org treats `DOING` as open
+/// - Each headline is `* <KEYWORD> <description>`, with newlines in the description replaced
+///   by spaces, followed by the todo's tags as Org tags, `:work:docs:`; characters Org does
+///   not allow in tags are replaced by `_`
+/// - The due date is a `DEADLINE` active timestamp in UTC on the line after the headline
+/// - A property drawer holds the todo's `ID` and its `CREATED` inactive timestamp in UTC
+pub fn export_todos(mut writer: impl Write, todos: &[Todo]) -> Result<(), SerializationError> {
+    let mut document = String::from("#+TITLE: Todos\n#+TODO: TODO DOING | DONE\n");
+    for todo in todos {
+        let mut headline = todo
+            .description
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        if !todo.tags.is_empty() {
+            let tags: Vec<String> = todo.tags.iter().map(|tag| org_tag(tag.as_str())).collect();
+            headline.push_str(&format!(" :{}:", tags.join(":")));
+        }
+        document.push_str(&format!("\n* {} {}\n", keyword(todo.state), headline));
+        if let Some(due_at) = todo.due_at {
+            document.push_str(&format!(
+                "  DEADLINE: {}\n",
+                due_at.format("<%Y-%m-%d %a %H:%M>")
+            ));
+        }
+        document.push_str(&format!(
+            "  :PROPERTIES:\n  :ID:       {}\n  :CREATED:  {}\n  :END:\n",
+            todo.id,
+            todo.created_at.format("[%Y-%m-%d %a %H:%M]"),
+        ));
+    }
+    writer
+        .write_all(document.as_bytes())
+        .and_then(|_| writer.flush())
+        .map_err(|err| SerializationError::Io(err.to_string()))
+}
+
+/// Org tags are made of letters, numbers, `_`, `@`, `#` and `%`
+fn org_tag(tag: &str) -> String {
+    tag.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '_' | '@' | '#' | '%') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn keyword(state: TodoState) -> &'static str {
+    match state {
+        TodoState::Todo => "TODO",
+        TodoState::InProgress => "DOING",
+        TodoState::Done => "DONE",
+    }
+}
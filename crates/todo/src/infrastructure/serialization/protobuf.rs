@@ -0,0 +1,513 @@
+use chrono::{DateTime, Utc};
+use prost::Message;
+use prost_types::Timestamp;
+
+use crate::infrastructure::serialization::SerializationError;
+use crate::{Attachment, Priority, Subtask, Tag, Todo, TodoEvent, TodoState};
+
+/// Messages generated from `proto/todo/v1/entities.proto`
+pub mod proto {
+    include!(concat!(env!("OUT_DIR"), "/todo.v1.rs"));
+}
+
+/// Encodes a todo as a `todo.v1.Todo` message
+pub fn encode_todo(todo: &Todo) -> Vec<u8> {
+    proto::Todo::from(todo).encode_to_vec()
+}
+
+/// Decodes a `todo.v1.Todo` message
+pub fn decode_todo(bytes: &[u8]) -> Result<Todo, SerializationError> {
+    let message =
+        proto::Todo::decode(bytes).map_err(|err| SerializationError::Malformed(err.to_string()))?;
+    Todo::try_from(message)
+}
+
+/// Encodes an event as a `todo.v1.TodoEvent` message
+pub fn encode_event(event: &TodoEvent) -> Vec<u8> {
+    proto::TodoEvent::from(event.clone()).encode_to_vec()
+}
+
+/// Decodes a `todo.v1.TodoEvent` message
+pub fn decode_event(bytes: &[u8]) -> Result<TodoEvent, SerializationError> {
+    let message = proto::TodoEvent::decode(bytes)
+        .map_err(|err| SerializationError::Malformed(err.to_string()))?;
+    TodoEvent::try_from(message)
+}
+
+/// Converts a protobuf timestamp, rejecting values chrono cannot represent
+pub fn timestamp_from_proto(
+    timestamp: Option<Timestamp>,
+) -> Result<DateTime<Utc>, SerializationError> {
+    let timestamp = timestamp.ok_or_else(|| malformed("missing timestamp"))?;
+    u32::try_from(timestamp.nanos)
+        .ok()
+        .and_then(|nanos| DateTime::from_timestamp(timestamp.seconds, nanos))
+        .ok_or_else(|| malformed("timestamp out of range"))
+}
+
+pub fn timestamp_to_proto(time: DateTime<Utc>) -> Timestamp {
+    Timestamp {
+        seconds: time.timestamp(),
+        nanos: time.timestamp_subsec_nanos() as i32,
+    }
+}
+
+/// Reads a state field; `None` when it is unspecified
+pub fn state_from_proto(value: i32) -> Result<Option<TodoState>, SerializationError> {
+    match proto::TodoState::try_from(value) {
+        Ok(proto::TodoState::Unspecified) => Ok(None),
+        Ok(proto::TodoState::Todo) => Ok(Some(TodoState::Todo)),
+        Ok(proto::TodoState::InProgress) => Ok(Some(TodoState::InProgress)),
+        Ok(proto::TodoState::Done) => Ok(Some(TodoState::Done)),
+        Err(_) => Err(malformed(&format!("unknown todo state: {}", value))),
+    }
+}
+
+/// Reads a priority field; unspecified is `Medium`
+pub fn priority_from_proto(value: i32) -> Result<Priority, SerializationError> {
+    match proto::Priority::try_from(value) {
+        Ok(proto::Priority::Unspecified | proto::Priority::Medium) => Ok(Priority::Medium),
+        Ok(proto::Priority::Low) => Ok(Priority::Low),
+        Ok(proto::Priority::High) => Ok(Priority::High),
+        Ok(proto::Priority::Urgent) => Ok(Priority::Urgent),
+        Err(_) => Err(malformed(&format!("unknown priority: {}", value))),
+    }
+}
+
+fn tag_from_proto(tag: String) -> Result<Tag, SerializationError> {
+    Tag::new(&tag).map_err(|message| malformed(&message))
+}
+
+fn required_state(value: i32) -> Result<TodoState, SerializationError> {
+    state_from_proto(value)?.ok_or_else(|| malformed("todo state is unspecified"))
+}
+
+fn malformed(message: &str) -> SerializationError {
+    SerializationError::Malformed(message.to_string())
+}
+
+impl From<TodoState> for proto::TodoState {
+    fn from(state: TodoState) -> Self {
+        match state {
+            TodoState::Todo => proto::TodoState::Todo,
+            TodoState::InProgress => proto::TodoState::InProgress,
+            TodoState::Done => proto::TodoState::Done,
+        }
+    }
+}
+
+impl From<Priority> for proto::Priority {
+    fn from(priority: Priority) -> Self {
+        match priority {
+            Priority::Low => proto::Priority::Low,
+            Priority::Medium => proto::Priority::Medium,
+            Priority::High => proto::Priority::High,
+            Priority::Urgent => proto::Priority::Urgent,
+        }
+    }
+}
+
+impl From<&Subtask> for proto::Subtask {
+    fn from(subtask: &Subtask) -> Self {
+        proto::Subtask {
+            id: subtask.id.to_string(),
+            description: subtask.description.to_string(),
+            done: subtask.done,
+        }
+    }
+}
+
+impl From<proto::Subtask> for Subtask {
+    fn from(message: proto::Subtask) -> Self {
+        Subtask {
+            id: message.id.into(),
+            description: message.description.into(),
+            done: message.done,
+        }
+    }
+}
+
+impl From<&Attachment> for proto::Attachment {
+    fn from(attachment: &Attachment) -> Self {
+        proto::Attachment {
+            content_hash: attachment.content_hash.to_string(),
+            name: attachment.name.to_string(),
+            media_type: attachment.media_type.to_string(),
+            size: attachment.size,
+        }
+    }
+}
+
+impl From<proto::Attachment> for Attachment {
+    fn from(message: proto::Attachment) -> Self {
+        Attachment {
+            content_hash: message.content_hash.into(),
+            name: message.name.into(),
+            media_type: message.media_type.into(),
+            size: message.size,
+        }
+    }
+}
+
+impl From<&Todo> for proto::Todo {
+    fn from(todo: &Todo) -> Self {
+        proto::Todo {
+            id: todo.id.to_string(),
+            description: todo.description.to_string(),
+            state: proto::TodoState::from(todo.state).into(),
+            created_at: Some(timestamp_to_proto(todo.created_at)),
+            due_at: todo.due_at.map(timestamp_to_proto),
+            priority: proto::Priority::from(todo.priority).into(),
+            tags: todo.tags.iter().map(ToString::to_string).collect(),
+            subtasks: todo.subtasks.iter().map(proto::Subtask::from).collect(),
+            blocked_by: todo.blocked_by.iter().map(ToString::to_string).collect(),
+            attachments: todo
+                .attachments
+                .iter()
+                .map(proto::Attachment::from)
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<proto::Todo> for Todo {
+    type Error = SerializationError;
+
+    fn try_from(message: proto::Todo) -> Result<Self, Self::Error> {
+        if message.id.is_empty() {
+            return Err(malformed("todo id must not be empty"));
+        }
+        if message.description.trim().is_empty() {
+            return Err(malformed("todo description must not be empty"));
+        }
+        Ok(Todo {
+            id: message.id.into(),
+            created_at: timestamp_from_proto(message.created_at)?,
+            description: message.description.into(),
+            state: required_state(message.state)?,
+            due_at: message
+                .due_at
+                .map(|due_at| timestamp_from_proto(Some(due_at)))
+                .transpose()?,
+            priority: priority_from_proto(message.priority)?,
+            tags: message
+                .tags
+                .into_iter()
+                .map(tag_from_proto)
+                .collect::<Result<_, _>>()?,
+            subtasks: message.subtasks.into_iter().map(Subtask::from).collect(),
+            blocked_by: message.blocked_by.into_iter().map(Into::into).collect(),
+            attachments: message
+                .attachments
+                .into_iter()
+                .map(Attachment::from)
+                .collect(),
+            dirty: Some(false),
+        })
+    }
+}
+
+impl From<TodoEvent> for proto::TodoEvent {
+    fn from(event: TodoEvent) -> Self {
+        let event = match event {
+            TodoEvent::TodoCreated {
+                id,
+                description,
+                created_at,
+            } => proto::todo_event::Event::Created(proto::TodoCreated {
+                id: id.to_string(),
+                description: description.to_string(),
+                created_at: Some(timestamp_to_proto(created_at)),
+            }),
+            TodoEvent::TodoStateChanged {
+                id,
+                from_state,
+                to_state,
+                changed_at,
+            } => proto::todo_event::Event::StateChanged(proto::TodoStateChanged {
+                id: id.to_string(),
+                from_state: proto::TodoState::from(from_state).into(),
+                to_state: proto::TodoState::from(to_state).into(),
+                changed_at: Some(timestamp_to_proto(changed_at)),
+            }),
+            TodoEvent::TodoArchived { id, archived_at } => {
+                proto::todo_event::Event::Archived(proto::TodoArchived {
+                    id: id.to_string(),
+                    archived_at: Some(timestamp_to_proto(archived_at)),
+                })
+            }
+            TodoEvent::TodoStale {
+                id,
+                rule,
+                state,
+                stale_at,
+            } => proto::todo_event::Event::Stale(proto::TodoStale {
+                id: id.to_string(),
+                rule: rule.to_string(),
+                state: proto::TodoState::from(state).into(),
+                stale_at: Some(timestamp_to_proto(stale_at)),
+            }),
+            TodoEvent::TodoDueDateChanged {
+                id,
+                due_at,
+                changed_at,
+            } => proto::todo_event::Event::DueDateChanged(proto::TodoDueDateChanged {
+                id: id.to_string(),
+                due_at: due_at.map(timestamp_to_proto),
+                changed_at: Some(timestamp_to_proto(changed_at)),
+            }),
+            TodoEvent::TodoPriorityChanged {
+                id,
+                from_priority,
+                to_priority,
+                changed_at,
+            } => proto::todo_event::Event::PriorityChanged(proto::TodoPriorityChanged {
+                id: id.to_string(),
+                from_priority: proto::Priority::from(from_priority).into(),
+                to_priority: proto::Priority::from(to_priority).into(),
+                changed_at: Some(timestamp_to_proto(changed_at)),
+            }),
+            TodoEvent::TodoTagAdded { id, tag, added_at } => {
+                proto::todo_event::Event::TagAdded(proto::TodoTagAdded {
+                    id: id.to_string(),
+                    tag: tag.to_string(),
+                    added_at: Some(timestamp_to_proto(added_at)),
+                })
+            }
+            TodoEvent::TodoTagRemoved {
+                id,
+                tag,
+                removed_at,
+            } => proto::todo_event::Event::TagRemoved(proto::TodoTagRemoved {
+                id: id.to_string(),
+                tag: tag.to_string(),
+                removed_at: Some(timestamp_to_proto(removed_at)),
+            }),
+            TodoEvent::TodoSubtaskAdded {
+                id,
+                subtask_id,
+                description,
+                added_at,
+            } => proto::todo_event::Event::SubtaskAdded(proto::TodoSubtaskAdded {
+                id: id.to_string(),
+                subtask_id: subtask_id.to_string(),
+                description: description.to_string(),
+                added_at: Some(timestamp_to_proto(added_at)),
+            }),
+            TodoEvent::TodoSubtaskToggled {
+                id,
+                subtask_id,
+                done,
+                toggled_at,
+            } => proto::todo_event::Event::SubtaskToggled(proto::TodoSubtaskToggled {
+                id: id.to_string(),
+                subtask_id: subtask_id.to_string(),
+                done,
+                toggled_at: Some(timestamp_to_proto(toggled_at)),
+            }),
+            TodoEvent::TodoSubtaskRemoved {
+                id,
+                subtask_id,
+                removed_at,
+            } => proto::todo_event::Event::SubtaskRemoved(proto::TodoSubtaskRemoved {
+                id: id.to_string(),
+                subtask_id: subtask_id.to_string(),
+                removed_at: Some(timestamp_to_proto(removed_at)),
+            }),
+            TodoEvent::TodoDescriptionUpdated {
+                id,
+                old,
+                new,
+                updated_at,
+            } => proto::todo_event::Event::DescriptionUpdated(proto::TodoDescriptionUpdated {
+                id: id.to_string(),
+                old: old.to_string(),
+                new: new.to_string(),
+                updated_at: Some(timestamp_to_proto(updated_at)),
+            }),
+            TodoEvent::TodoBlocked {
+                id,
+                blocker_id,
+                blocked_at,
+            } => proto::todo_event::Event::Blocked(proto::TodoBlocked {
+                id: id.to_string(),
+                blocker_id: blocker_id.to_string(),
+                blocked_at: Some(timestamp_to_proto(blocked_at)),
+            }),
+            TodoEvent::TodoUnblocked {
+                id,
+                blocker_id,
+                unblocked_at,
+            } => proto::todo_event::Event::Unblocked(proto::TodoUnblocked {
+                id: id.to_string(),
+                blocker_id: blocker_id.to_string(),
+                unblocked_at: Some(timestamp_to_proto(unblocked_at)),
+            }),
+            TodoEvent::TodoDeleted { id, deleted_at } => {
+                proto::todo_event::Event::Deleted(proto::TodoDeleted {
+                    id: id.to_string(),
+                    deleted_at: Some(timestamp_to_proto(deleted_at)),
+                })
+            }
+            TodoEvent::TodoRestored { id, restored_at } => {
+                proto::todo_event::Event::Restored(proto::TodoRestored {
+                    id: id.to_string(),
+                    restored_at: Some(timestamp_to_proto(restored_at)),
+                })
+            }
+            TodoEvent::TodoAttachmentAdded {
+                id,
+                content_hash,
+                name,
+                media_type,
+                size,
+                added_at,
+            } => proto::todo_event::Event::AttachmentAdded(proto::TodoAttachmentAdded {
+                id: id.to_string(),
+                content_hash: content_hash.to_string(),
+                name: name.to_string(),
+                media_type: media_type.to_string(),
+                size,
+                added_at: Some(timestamp_to_proto(added_at)),
+            }),
+            TodoEvent::TodoAttachmentRemoved {
+                id,
+                content_hash,
+                removed_at,
+            } => proto::todo_event::Event::AttachmentRemoved(proto::TodoAttachmentRemoved {
+                id: id.to_string(),
+                content_hash: content_hash.to_string(),
+                removed_at: Some(timestamp_to_proto(removed_at)),
+            }),
+        };
+        proto::TodoEvent { event: Some(event) }
+    }
+}
+
+impl TryFrom<proto::TodoEvent> for TodoEvent {
+    type Error = SerializationError;
+
+    fn try_from(message: proto::TodoEvent) -> Result<Self, Self::Error> {
+        match message.event {
+            Some(proto::todo_event::Event::Created(created)) => Ok(TodoEvent::TodoCreated {
+                id: created.id.into(),
+                description: created.description.into(),
+                created_at: timestamp_from_proto(created.created_at)?,
+            }),
+            Some(proto::todo_event::Event::StateChanged(changed)) => {
+                Ok(TodoEvent::TodoStateChanged {
+                    id: changed.id.into(),
+                    from_state: required_state(changed.from_state)?,
+                    to_state: required_state(changed.to_state)?,
+                    changed_at: timestamp_from_proto(changed.changed_at)?,
+                })
+            }
+            Some(proto::todo_event::Event::Archived(archived)) => Ok(TodoEvent::TodoArchived {
+                id: archived.id.into(),
+                archived_at: timestamp_from_proto(archived.archived_at)?,
+            }),
+            Some(proto::todo_event::Event::Stale(stale)) => Ok(TodoEvent::TodoStale {
+                id: stale.id.into(),
+                rule: stale.rule.into(),
+                state: required_state(stale.state)?,
+                stale_at: timestamp_from_proto(stale.stale_at)?,
+            }),
+            Some(proto::todo_event::Event::DueDateChanged(changed)) => {
+                Ok(TodoEvent::TodoDueDateChanged {
+                    id: changed.id.into(),
+                    due_at: changed
+                        .due_at
+                        .map(|due_at| timestamp_from_proto(Some(due_at)))
+                        .transpose()?,
+                    changed_at: timestamp_from_proto(changed.changed_at)?,
+                })
+            }
+            Some(proto::todo_event::Event::PriorityChanged(changed)) => {
+                Ok(TodoEvent::TodoPriorityChanged {
+                    id: changed.id.into(),
+                    from_priority: priority_from_proto(changed.from_priority)?,
+                    to_priority: priority_from_proto(changed.to_priority)?,
+                    changed_at: timestamp_from_proto(changed.changed_at)?,
+                })
+            }
+            Some(proto::todo_event::Event::TagAdded(added)) => Ok(TodoEvent::TodoTagAdded {
+                id: added.id.into(),
+                tag: tag_from_proto(added.tag)?,
+                added_at: timestamp_from_proto(added.added_at)?,
+            }),
+            Some(proto::todo_event::Event::TagRemoved(removed)) => Ok(TodoEvent::TodoTagRemoved {
+                id: removed.id.into(),
+                tag: tag_from_proto(removed.tag)?,
+                removed_at: timestamp_from_proto(removed.removed_at)?,
+            }),
+            Some(proto::todo_event::Event::SubtaskAdded(added)) => {
+                Ok(TodoEvent::TodoSubtaskAdded {
+                    id: added.id.into(),
+                    subtask_id: added.subtask_id.into(),
+                    description: added.description.into(),
+                    added_at: timestamp_from_proto(added.added_at)?,
+                })
+            }
+            Some(proto::todo_event::Event::SubtaskToggled(toggled)) => {
+                Ok(TodoEvent::TodoSubtaskToggled {
+                    id: toggled.id.into(),
+                    subtask_id: toggled.subtask_id.into(),
+                    done: toggled.done,
+                    toggled_at: timestamp_from_proto(toggled.toggled_at)?,
+                })
+            }
+            Some(proto::todo_event::Event::SubtaskRemoved(removed)) => {
+                Ok(TodoEvent::TodoSubtaskRemoved {
+                    id: removed.id.into(),
+                    subtask_id: removed.subtask_id.into(),
+                    removed_at: timestamp_from_proto(removed.removed_at)?,
+                })
+            }
+            Some(proto::todo_event::Event::DescriptionUpdated(updated)) => {
+                Ok(TodoEvent::TodoDescriptionUpdated {
+                    id: updated.id.into(),
+                    old: updated.old.into(),
+                    new: updated.new.into(),
+                    updated_at: timestamp_from_proto(updated.updated_at)?,
+                })
+            }
+            Some(proto::todo_event::Event::Blocked(blocked)) => Ok(TodoEvent::TodoBlocked {
+                id: blocked.id.into(),
+                blocker_id: blocked.blocker_id.into(),
+                blocked_at: timestamp_from_proto(blocked.blocked_at)?,
+            }),
+            Some(proto::todo_event::Event::Unblocked(unblocked)) => Ok(TodoEvent::TodoUnblocked {
+                id: unblocked.id.into(),
+                blocker_id: unblocked.blocker_id.into(),
+                unblocked_at: timestamp_from_proto(unblocked.unblocked_at)?,
+            }),
+            Some(proto::todo_event::Event::Deleted(deleted)) => Ok(TodoEvent::TodoDeleted {
+                id: deleted.id.into(),
+                deleted_at: timestamp_from_proto(deleted.deleted_at)?,
+            }),
+            Some(proto::todo_event::Event::Restored(restored)) => Ok(TodoEvent::TodoRestored {
+                id: restored.id.into(),
+                restored_at: timestamp_from_proto(restored.restored_at)?,
+            }),
+            Some(proto::todo_event::Event::AttachmentAdded(added)) => {
+                Ok(TodoEvent::TodoAttachmentAdded {
+                    id: added.id.into(),
+                    content_hash: added.content_hash.into(),
+                    name: added.name.into(),
+                    media_type: added.media_type.into(),
+                    size: added.size,
+                    added_at: timestamp_from_proto(added.added_at)?,
+                })
+            }
+            Some(proto::todo_event::Event::AttachmentRemoved(removed)) => {
+                Ok(TodoEvent::TodoAttachmentRemoved {
+                    id: removed.id.into(),
+                    content_hash: removed.content_hash.into(),
+                    removed_at: timestamp_from_proto(removed.removed_at)?,
+                })
+            }
+            // Written by a newer version with an event this one does not know
+            None => Err(malformed("unknown todo event")),
+        }
+    }
+}
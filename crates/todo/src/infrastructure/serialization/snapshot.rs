@@ -0,0 +1,437 @@
+use chrono::DateTime;
+use std::io::Write;
+use std::sync::Arc;
+
+use crate::infrastructure::serialization::SerializationError;
+use crate::{Attachment, Priority, Subtask, Tag, Todo, TodoState};
+
+/// First bytes of every snapshot
+pub const MAGIC: &[u8; 4] = b"HKTS";
+
+/// Snapshot layout written by this version
+pub const VERSION: u32 = 7;
+
+const HEADER_LEN: usize = 12;
+const ENTRY_LEN: usize = 80;
+/// Entries of version 1 snapshots, which had no due date
+const ENTRY_LEN_V1: usize = 32;
+/// Entries of version 2 and 3 snapshots, which had no tags
+const ENTRY_LEN_V2: usize = 48;
+/// Entries of version 4 snapshots, which had no subtasks
+const ENTRY_LEN_V4: usize = 56;
+/// Entries of version 5 snapshots, which had no blockers
+const ENTRY_LEN_V5: usize = 64;
+/// Entries of version 6 snapshots, which had no attachments
+const ENTRY_LEN_V6: usize = 72;
+
+/// Writes todos as a compact binary snapshot, readable in place by [`SnapshotView`]
+///
+/// The layout, all integers little-endian:
+/// - header: `MAGIC`, `VERSION` as u32, todo count as u32
+/// - one 80-byte entry per todo, sorted by id: id offset and length, description offset and
+///   length (u32 each, into the string area), `created_at` seconds (i64) and nanoseconds (u32),
+///   state (u8: 0 `TODO`, 1 `IN_PROGRESS`, 2 `DONE`), whether the todo has a due date (u8),
+///   priority (u8: 0 `LOW`, 1 `MEDIUM`, 2 `HIGH`, 3 `URGENT`), 1 padding byte, `due_at`
+///   seconds (i64) and nanoseconds (u32), zero without a due date, 4 padding bytes, and the
+///   offsets and lengths of the tags, of the subtasks, of the blockers and of the attachments
+///   (u32 each, into the string area)
+/// - the string area: ids, descriptions and tags as UTF-8, unseparated, except for the tags of
+///   a todo, which are joined with single spaces, the subtasks of each todo, one after the
+///   other as the length (u32) and UTF-8 of the id, the same of the description, and whether
+///   it is done (u8), the ids of each todo's blockers, each as its length (u32) and UTF-8, and
+///   the attachments of each todo, one after the other as the length (u32) and UTF-8 of the
+///   content hash, the same of the name and of the media type, and the size (u64)
+///
+/// # Returns
+/// - `Ok(())`: The snapshot was written
+/// - `Err(SerializationError)`: If two todos share an id, the strings exceed 4 GiB, or the
+///   writer fails
+pub fn write_snapshot(mut writer: impl Write, todos: &[Todo]) -> Result<(), SerializationError> {
+    let mut sorted: Vec<&Todo> = todos.iter().collect();
+    sorted.sort_by(|a, b| a.id.cmp(&b.id));
+    if let Some(pair) = sorted.windows(2).find(|pair| pair[0].id == pair[1].id) {
+        return Err(SerializationError::Malformed(format!(
+            "duplicate todo id {}",
+            pair[0].id
+        )));
+    }
+    let too_large = || SerializationError::Malformed("snapshot exceeds 4 GiB".to_string());
+    let count = u32::try_from(sorted.len()).map_err(|_| too_large())?;
+
+    let mut entries = Vec::with_capacity(sorted.len() * ENTRY_LEN);
+    let mut strings = Vec::new();
+    for todo in sorted {
+        let tags = todo
+            .tags
+            .iter()
+            .map(Tag::as_str)
+            .collect::<Vec<_>>()
+            .join(" ");
+        for text in [&todo.id, &todo.description] {
+            let offset = u32::try_from(strings.len()).map_err(|_| too_large())?;
+            let len = u32::try_from(text.len()).map_err(|_| too_large())?;
+            entries.extend_from_slice(&offset.to_le_bytes());
+            entries.extend_from_slice(&len.to_le_bytes());
+            strings.extend_from_slice(text.as_bytes());
+        }
+        // The last string must end within u32 range too
+        u32::try_from(strings.len()).map_err(|_| too_large())?;
+        entries.extend_from_slice(&todo.created_at.timestamp().to_le_bytes());
+        entries.extend_from_slice(&todo.created_at.timestamp_subsec_nanos().to_le_bytes());
+        let state: u8 = match todo.state {
+            TodoState::Todo => 0,
+            TodoState::InProgress => 1,
+            TodoState::Done => 2,
+        };
+        let priority: u8 = match todo.priority {
+            Priority::Low => 0,
+            Priority::Medium => 1,
+            Priority::High => 2,
+            Priority::Urgent => 3,
+        };
+        entries.extend_from_slice(&[state, u8::from(todo.due_at.is_some()), priority, 0]);
+        let due_at = todo.due_at.map_or((0, 0), |due_at| {
+            (due_at.timestamp(), due_at.timestamp_subsec_nanos())
+        });
+        entries.extend_from_slice(&due_at.0.to_le_bytes());
+        entries.extend_from_slice(&due_at.1.to_le_bytes());
+        entries.extend_from_slice(&[0; 4]);
+        let offset = u32::try_from(strings.len()).map_err(|_| too_large())?;
+        let len = u32::try_from(tags.len()).map_err(|_| too_large())?;
+        entries.extend_from_slice(&offset.to_le_bytes());
+        entries.extend_from_slice(&len.to_le_bytes());
+        strings.extend_from_slice(tags.as_bytes());
+        u32::try_from(strings.len()).map_err(|_| too_large())?;
+        let offset = u32::try_from(strings.len()).map_err(|_| too_large())?;
+        for subtask in &todo.subtasks {
+            for text in [&subtask.id, &subtask.description] {
+                let len = u32::try_from(text.len()).map_err(|_| too_large())?;
+                strings.extend_from_slice(&len.to_le_bytes());
+                strings.extend_from_slice(text.as_bytes());
+            }
+            strings.push(u8::from(subtask.done));
+        }
+        let len = u32::try_from(strings.len() - offset as usize).map_err(|_| too_large())?;
+        entries.extend_from_slice(&offset.to_le_bytes());
+        entries.extend_from_slice(&len.to_le_bytes());
+        u32::try_from(strings.len()).map_err(|_| too_large())?;
+        let offset = u32::try_from(strings.len()).map_err(|_| too_large())?;
+        for blocker_id in &todo.blocked_by {
+            let len = u32::try_from(blocker_id.len()).map_err(|_| too_large())?;
+            strings.extend_from_slice(&len.to_le_bytes());
+            strings.extend_from_slice(blocker_id.as_bytes());
+        }
+        let len = u32::try_from(strings.len() - offset as usize).map_err(|_| too_large())?;
+        entries.extend_from_slice(&offset.to_le_bytes());
+        entries.extend_from_slice(&len.to_le_bytes());
+        u32::try_from(strings.len()).map_err(|_| too_large())?;
+        let offset = u32::try_from(strings.len()).map_err(|_| too_large())?;
+        for attachment in &todo.attachments {
+            for text in [
+                &attachment.content_hash,
+                &attachment.name,
+                &attachment.media_type,
+            ] {
+                let len = u32::try_from(text.len()).map_err(|_| too_large())?;
+                strings.extend_from_slice(&len.to_le_bytes());
+                strings.extend_from_slice(text.as_bytes());
+            }
+            strings.extend_from_slice(&attachment.size.to_le_bytes());
+        }
+        let len = u32::try_from(strings.len() - offset as usize).map_err(|_| too_large())?;
+        entries.extend_from_slice(&offset.to_le_bytes());
+        entries.extend_from_slice(&len.to_le_bytes());
+        u32::try_from(strings.len()).map_err(|_| too_large())?;
+    }
+
+    let io = |err: std::io::Error| SerializationError::Io(err.to_string());
+    writer.write_all(MAGIC).map_err(io)?;
+    writer.write_all(&VERSION.to_le_bytes()).map_err(io)?;
+    writer.write_all(&count.to_le_bytes()).map_err(io)?;
+    writer.write_all(&entries).map_err(io)?;
+    writer.write_all(&strings).map_err(io)?;
+    writer.flush().map_err(io)
+}
+
+/// A snapshot written by [`write_snapshot`], read in place
+///
+/// Creating a view only checks the header and table size; each todo is decoded, and its
+/// strings checked, when it is read. Lookups by id binary-search the entry table without
+/// decoding the other todos. Older snapshots are read too: version 1 as todos without a due
+/// date, versions 1 and 2 as todos of `MEDIUM` priority, versions 1 to 3 as todos without
+/// tags, versions 1 to 4 as todos without subtasks, versions 1 to 5 as todos without
+/// blockers, and versions 1 to 6 as todos without attachments.
+#[derive(Clone, Copy)]
+pub struct SnapshotView<'a> {
+    entries: &'a [u8],
+    version: u32,
+    entry_len: usize,
+    strings: &'a [u8],
+}
+
+impl<'a> SnapshotView<'a> {
+    /// Reads the header of `bytes`
+    ///
+    /// # Returns
+    /// - `Ok(SnapshotView)`: A view over the snapshot's todos
+    /// - `Err(SerializationError)`: If `bytes` is not a snapshot, is from a newer version, or
+    ///   is shorter than its entry table
+    pub fn new(bytes: &'a [u8]) -> Result<Self, SerializationError> {
+        if bytes.len() < HEADER_LEN || &bytes[..4] != MAGIC {
+            return Err(SerializationError::Malformed(
+                "not an hk-todo snapshot".to_string(),
+            ));
+        }
+        let version = read_u32(bytes, 4);
+        if version == 0 || version > VERSION {
+            return Err(SerializationError::UnsupportedVersion(version));
+        }
+        let entry_len = match version {
+            1 => ENTRY_LEN_V1,
+            2 | 3 => ENTRY_LEN_V2,
+            4 => ENTRY_LEN_V4,
+            5 => ENTRY_LEN_V5,
+            6 => ENTRY_LEN_V6,
+            _ => ENTRY_LEN,
+        };
+        let count = read_u32(bytes, 8) as usize;
+        let table_end = count
+            .checked_mul(entry_len)
+            .and_then(|len| len.checked_add(HEADER_LEN))
+            .filter(|&end| end <= bytes.len())
+            .ok_or_else(|| SerializationError::Malformed("snapshot is truncated".to_string()))?;
+        Ok(SnapshotView {
+            entries: &bytes[HEADER_LEN..table_end],
+            version,
+            entry_len,
+            strings: &bytes[table_end..],
+        })
+    }
+
+    /// Number of todos in the snapshot
+    pub fn len(&self) -> usize {
+        self.entries.len() / self.entry_len
+    }
+
+    /// Whether the snapshot holds no todos
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Decodes the todo at `index`, in id order
+    ///
+    /// # Panics
+    /// - If `index` is not less than `len()`
+    pub fn get(&self, index: usize) -> Result<Todo, SerializationError> {
+        let entry = self.entry(index);
+        let invalid = |message: String| SerializationError::InvalidRecord {
+            record: index + 1,
+            message,
+        };
+        let id = self.text(entry, 0).map_err(invalid)?;
+        let description = self.text(entry, 8).map_err(invalid)?;
+        let seconds = i64::from_le_bytes(entry[16..24].try_into().unwrap());
+        let created_at = DateTime::from_timestamp(seconds, read_u32(entry, 24))
+            .ok_or_else(|| invalid("created_at out of range".to_string()))?;
+        let state = match entry[28] {
+            0 => TodoState::Todo,
+            1 => TodoState::InProgress,
+            2 => TodoState::Done,
+            other => return Err(invalid(format!("unknown todo state: {}", other))),
+        };
+        let due_at = match (self.entry_len, entry[29]) {
+            (ENTRY_LEN_V1, _) | (_, 0) => None,
+            (_, 1) => {
+                let seconds = i64::from_le_bytes(entry[32..40].try_into().unwrap());
+                Some(
+                    DateTime::from_timestamp(seconds, read_u32(entry, 40))
+                        .ok_or_else(|| invalid("due_at out of range".to_string()))?,
+                )
+            }
+            (_, other) => return Err(invalid(format!("unknown due date flag: {}", other))),
+        };
+        let priority = match (self.version, entry[30]) {
+            (1 | 2, _) | (_, 1) => Priority::Medium,
+            (_, 0) => Priority::Low,
+            (_, 2) => Priority::High,
+            (_, 3) => Priority::Urgent,
+            (_, other) => return Err(invalid(format!("unknown priority: {}", other))),
+        };
+        let tags = if self.version < 4 {
+            Vec::new()
+        } else {
+            self.text(entry, 48)
+                .map_err(invalid)?
+                .split_whitespace()
+                .map(Tag::new)
+                .collect::<Result<_, _>>()
+                .map_err(invalid)?
+        };
+        let subtasks = if self.version < 5 {
+            Vec::new()
+        } else {
+            self.subtasks(entry).map_err(invalid)?
+        };
+        let blocked_by = if self.version < 6 {
+            Vec::new()
+        } else {
+            self.blocked_by(entry).map_err(invalid)?
+        };
+        let attachments = if self.version < 7 {
+            Vec::new()
+        } else {
+            self.attachments(entry).map_err(invalid)?
+        };
+        Ok(Todo {
+            id: id.into(),
+            created_at,
+            description: description.into(),
+            state,
+            due_at,
+            priority,
+            tags,
+            subtasks,
+            blocked_by,
+            attachments,
+            dirty: Some(false),
+        })
+    }
+
+    /// Decodes the todo with `id`, if the snapshot holds one
+    pub fn find(&self, id: &str) -> Result<Option<Todo>, SerializationError> {
+        let (mut low, mut high) = (0, self.len());
+        while low < high {
+            let middle = low + (high - low) / 2;
+            let key = self.text(self.entry(middle), 0).map_err(|message| {
+                SerializationError::InvalidRecord {
+                    record: middle + 1,
+                    message,
+                }
+            })?;
+            match key.cmp(id) {
+                std::cmp::Ordering::Less => low = middle + 1,
+                std::cmp::Ordering::Greater => high = middle,
+                std::cmp::Ordering::Equal => return self.get(middle).map(Some),
+            }
+        }
+        Ok(None)
+    }
+
+    /// Decodes the todos one at a time, in id order
+    pub fn iter(&self) -> impl Iterator<Item = Result<Todo, SerializationError>> + 'a {
+        let view = *self;
+        (0..view.len()).map(move |index| view.get(index))
+    }
+
+    fn entry(&self, index: usize) -> &'a [u8] {
+        &self.entries[index * self.entry_len..(index + 1) * self.entry_len]
+    }
+
+    /// The string whose offset and length are at `at` in `entry`
+    fn text(&self, entry: &[u8], at: usize) -> Result<&'a str, String> {
+        let offset = read_u32(entry, at) as usize;
+        let len = read_u32(entry, at + 4) as usize;
+        let bytes = self
+            .strings
+            .get(offset..offset.saturating_add(len))
+            .ok_or_else(|| "string out of bounds".to_string())?;
+        std::str::from_utf8(bytes).map_err(|err| err.to_string())
+    }
+
+    /// The subtasks whose records' offset and length are at byte 56 of `entry`
+    fn subtasks(&self, entry: &[u8]) -> Result<Vec<Subtask>, String> {
+        let offset = read_u32(entry, 56) as usize;
+        let len = read_u32(entry, 60) as usize;
+        let mut records = self
+            .strings
+            .get(offset..offset.saturating_add(len))
+            .ok_or_else(|| "subtasks out of bounds".to_string())?;
+        let truncated = || "subtask is truncated".to_string();
+        let mut subtasks = Vec::new();
+        while !records.is_empty() {
+            let mut texts = [""; 2];
+            for text in &mut texts {
+                let len = records.get(..4).ok_or_else(truncated)?;
+                let len = read_u32(len, 0) as usize;
+                let bytes = records
+                    .get(4..4usize.saturating_add(len))
+                    .ok_or_else(truncated)?;
+                *text = std::str::from_utf8(bytes).map_err(|err| err.to_string())?;
+                records = &records[4 + len..];
+            }
+            let done = match records.first().ok_or_else(truncated)? {
+                0 => false,
+                1 => true,
+                other => return Err(format!("unknown subtask done flag: {}", other)),
+            };
+            records = &records[1..];
+            subtasks.push(Subtask {
+                id: texts[0].into(),
+                description: texts[1].into(),
+                done,
+            });
+        }
+        Ok(subtasks)
+    }
+
+    /// The blocker ids whose records' offset and length are at byte 64 of `entry`
+    fn blocked_by(&self, entry: &[u8]) -> Result<Vec<Arc<str>>, String> {
+        let offset = read_u32(entry, 64) as usize;
+        let len = read_u32(entry, 68) as usize;
+        let mut records = self
+            .strings
+            .get(offset..offset.saturating_add(len))
+            .ok_or_else(|| "blockers out of bounds".to_string())?;
+        let truncated = || "blocker id is truncated".to_string();
+        let mut blocked_by = Vec::new();
+        while !records.is_empty() {
+            let len = records.get(..4).ok_or_else(truncated)?;
+            let len = read_u32(len, 0) as usize;
+            let bytes = records
+                .get(4..4usize.saturating_add(len))
+                .ok_or_else(truncated)?;
+            let blocker_id = std::str::from_utf8(bytes).map_err(|err| err.to_string())?;
+            blocked_by.push(blocker_id.into());
+            records = &records[4 + len..];
+        }
+        Ok(blocked_by)
+    }
+
+    /// The attachments whose records' offset and length are at byte 72 of `entry`
+    fn attachments(&self, entry: &[u8]) -> Result<Vec<Attachment>, String> {
+        let offset = read_u32(entry, 72) as usize;
+        let len = read_u32(entry, 76) as usize;
+        let mut records = self
+            .strings
+            .get(offset..offset.saturating_add(len))
+            .ok_or_else(|| "attachments out of bounds".to_string())?;
+        let truncated = || "attachment is truncated".to_string();
+        let mut attachments = Vec::new();
+        while !records.is_empty() {
+            let mut texts = [""; 3];
+            for text in &mut texts {
+                let len = records.get(..4).ok_or_else(truncated)?;
+                let len = read_u32(len, 0) as usize;
+                let bytes = records
+                    .get(4..4usize.saturating_add(len))
+                    .ok_or_else(truncated)?;
+                *text = std::str::from_utf8(bytes).map_err(|err| err.to_string())?;
+                records = &records[4 + len..];
+            }
+            let size = records.get(..8).ok_or_else(truncated)?;
+            let size = u64::from_le_bytes(size.try_into().unwrap());
+            records = &records[8..];
+            attachments.push(Attachment {
+                content_hash: texts[0].into(),
+                name: texts[1].into(),
+                media_type: texts[2].into(),
+                size,
+            });
+        }
+        Ok(attachments)
+    }
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+}
@@ -0,0 +1,175 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::io::{Read, Write};
+
+use crate::infrastructure::serialization::SerializationError;
+use crate::{Todo, TodoState};
+
+const DATE_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// A task as written by `task export` and read by `task import`; fields the domain has no
+/// place for are not read
+#[derive(Deserialize, Serialize)]
+struct Task {
+    uuid: String,
+    description: String,
+    status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    entry: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    due: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    annotations: Vec<Annotation>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct Annotation {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    entry: Option<String>,
+    description: String,
+}
+
+/// Writes `todos` as a JSON array that Taskwarrior's `task import` reads
+///
+/// # Format
+/// Each todo is a task with its id as `uuid`, so Taskwarrior only accepts todos whose ids
+/// are UUIDs. `TODO` and `IN_PROGRESS` todos are `pending`, `IN_PROGRESS` ones started at
+/// their creation time, and `DONE` todos are `completed`. The due date becomes `due`, tags
+/// become `tags`, and subtasks become `annotations` made at the todo's creation time; whether
+/// a subtask is done is not written.
+pub fn export_todos(mut writer: impl Write, todos: &[Todo]) -> Result<(), SerializationError> {
+    let tasks: Vec<Task> = todos
+        .iter()
+        .map(|todo| {
+            let entry = format_date(todo.created_at);
+            Task {
+                uuid: todo.id.to_string(),
+                description: todo.description.to_string(),
+                status: match todo.state {
+                    TodoState::Done => "completed",
+                    TodoState::Todo | TodoState::InProgress => "pending",
+                }
+                .to_string(),
+                start: (todo.state == TodoState::InProgress).then(|| entry.clone()),
+                due: todo.due_at.map(format_date),
+                tags: todo.tags.iter().map(ToString::to_string).collect(),
+                annotations: todo
+                    .subtasks
+                    .iter()
+                    .map(|subtask| Annotation {
+                        entry: Some(entry.clone()),
+                        description: subtask.description.to_string(),
+                    })
+                    .collect(),
+                entry: Some(entry),
+            }
+        })
+        .collect();
+    serde_json::to_writer(&mut writer, &tasks)
+        .map_err(|err| SerializationError::Io(err.to_string()))?;
+    writer
+        .flush()
+        .map_err(|err| SerializationError::Io(err.to_string()))
+}
+
+/// Reads the output of Taskwarrior's `task export`
+///
+/// Both the JSON array written by Taskwarrior 2.6+ and the one-object-per-line output of
+/// older versions are accepted.
+///
+/// # Returns
+/// - `Ok(Vec<Todo>)`: One todo per imported task, in document order, with the task's `uuid`
+///   as id so importing again can skip it
+/// - `Err(SerializationError)`: If the document is not valid JSON, or a task has an empty
+///   description, an unknown status, an unreadable date, a due date before its entry, a tag
+///   with whitespace or a repeated uuid
+///
+/// # Special Requirements
+/// - `pending` and `waiting` tasks are `TODO`, or `IN_PROGRESS` when started; `completed`
+///   tasks are `DONE`
+/// - `deleted` tasks and `recurring` templates are skipped
+/// - `entry` becomes the creation time, `due` the due date and `tags` the tags
+/// - Each annotation becomes a subtask, not done, in the order of `annotations`
+pub fn import_todos(mut reader: impl Read) -> Result<Vec<Todo>, SerializationError> {
+    let mut contents = String::new();
+    reader
+        .read_to_string(&mut contents)
+        .map_err(|err| SerializationError::Io(err.to_string()))?;
+    let values: Vec<serde_json::Value> = if contents.trim_start().starts_with('[') {
+        serde_json::from_str(&contents)
+            .map_err(|err| SerializationError::Malformed(err.to_string()))?
+    } else {
+        contents
+            .lines()
+            .map(|line| line.trim().trim_end_matches(','))
+            .filter(|line| !line.is_empty())
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()
+            .map_err(|err| SerializationError::Malformed(err.to_string()))?
+    };
+
+    let mut ids = HashSet::new();
+    let mut todos = Vec::new();
+    for (index, value) in values.into_iter().enumerate() {
+        let invalid = |message: String| SerializationError::InvalidRecord {
+            record: index + 1,
+            message,
+        };
+        let task: Task = serde_json::from_value(value).map_err(|err| invalid(err.to_string()))?;
+        let state = match task.status.as_str() {
+            "pending" | "waiting" if task.start.is_some() => TodoState::InProgress,
+            "pending" | "waiting" => TodoState::Todo,
+            "completed" => TodoState::Done,
+            "deleted" | "recurring" => continue,
+            other => return Err(invalid(format!("unknown task status: {}", other))),
+        };
+        if !ids.insert(task.uuid.clone()) {
+            return Err(invalid(format!("duplicate task uuid {}", task.uuid)));
+        }
+        let (mut todo, _) = Todo::new(task.description).map_err(|err| invalid(err.to_string()))?;
+        todo.id = task.uuid.into();
+        todo.state = state;
+        if let Some(entry) = task.entry {
+            todo.created_at = parse_date(&entry)
+                .ok_or_else(|| invalid(format!("invalid entry date: {}", entry)))?;
+        }
+        if let Some(due) = task.due {
+            let due_at =
+                parse_date(&due).ok_or_else(|| invalid(format!("invalid due date: {}", due)))?;
+            todo.set_due_date(due_at)
+                .map_err(|err| invalid(err.to_string()))?;
+        }
+        for tag in task.tags {
+            if !todo.tags.iter().any(|existing| existing.as_str() == tag) {
+                todo.add_tag(&tag).map_err(|err| invalid(err.to_string()))?;
+            }
+        }
+        for annotation in task.annotations {
+            // Empty annotations have nothing to keep
+            let _ = todo.add_subtask(annotation.description);
+        }
+        todos.push(todo);
+    }
+    Ok(todos)
+}
+
+fn format_date(date: DateTime<Utc>) -> String {
+    date.format(DATE_FORMAT).to_string()
+}
+
+/// Parses Taskwarrior's `20240102T030405Z`, or RFC3339
+fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(value, DATE_FORMAT)
+        .map(|value| value.and_utc())
+        .ok()
+        .or_else(|| {
+            DateTime::parse_from_rfc3339(value)
+                .ok()
+                .map(|value| value.with_timezone(&Utc))
+        })
+}
@@ -0,0 +1,81 @@
+use serde::Serialize;
+use std::io::Write;
+
+use crate::application::user_data_handler::UserData;
+use crate::infrastructure::serialization::SerializationError;
+use crate::{Clock, SystemClock, Todo, TodoEvent};
+
+/// Value of the archive's `format` field
+pub const FORMAT: &str = "hk-todo-user-archive";
+/// Archive version written by [`write_user_archive`]
+pub const VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct Archive<'a> {
+    format: &'static str,
+    version: u32,
+    exported_at: String,
+    subject: &'a str,
+    tenant: &'a str,
+    todos: &'a [Todo],
+    events: &'a [TodoEvent],
+    devices: Vec<DeviceRecord<'a>>,
+    memberships: Vec<MembershipRecord<'a>>,
+}
+
+#[derive(Serialize)]
+struct DeviceRecord<'a> {
+    token: &'a str,
+    platform: &'static str,
+    registered_at: String,
+}
+
+#[derive(Serialize)]
+struct MembershipRecord<'a> {
+    list: &'a str,
+    role: &'static str,
+}
+
+/// Writes the data of a user as a portable JSON archive, for data subject access requests
+///
+/// # Format
+/// `{"format": "hk-todo-user-archive", "version": 1, "exported_at": "<RFC3339>", "subject",
+/// "tenant", "todos": [...], "events": [...], "devices": [{"token", "platform",
+/// "registered_at"}], "memberships": [{"list", "role"}]}`, todos and events in the domain's
+/// serde shape. `todos` can be read back by `json::import_todos` once the envelope's format
+/// is changed to `hk-todo`.
+pub fn write_user_archive(writer: impl Write, data: &UserData) -> Result<(), SerializationError> {
+    let archive = Archive {
+        format: FORMAT,
+        version: VERSION,
+        exported_at: SystemClock.now().to_rfc3339(),
+        subject: &data.subject,
+        tenant: data.tenant.as_str(),
+        todos: &data.todos,
+        events: &data.events,
+        devices: data
+            .devices
+            .iter()
+            .map(|device| DeviceRecord {
+                token: &device.token,
+                platform: device.platform.as_str(),
+                registered_at: device.registered_at.to_rfc3339(),
+            })
+            .collect(),
+        memberships: data
+            .memberships
+            .iter()
+            .map(|membership| MembershipRecord {
+                list: membership.list.as_str(),
+                role: membership.role.as_str(),
+            })
+            .collect(),
+    };
+    serde_json::to_writer_pretty(writer, &archive).map_err(|err| {
+        if err.is_io() {
+            SerializationError::Io(err.to_string())
+        } else {
+            SerializationError::Malformed(err.to_string())
+        }
+    })
+}
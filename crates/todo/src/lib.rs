@@ -1,12 +1,18 @@
 pub mod domain;
 pub mod infrastructure;
 pub mod application;
+pub mod prelude;
 
 #[cfg(feature = "python")]
 pub mod python;
 
+#[cfg(feature = "proptest")]
+pub mod arbitrary;
+
 // Re-export commonly used domain types for convenience
 pub use domain::todo::{
-    Todo, TodoError, TodoEvent, TodoRepository, TodoState,
+    Attachment, Clock, EventSink, EventStore, FixedClock, IdGenerator, Priority, SequentialIdGenerator, SteppingClock,
+    Subtask, SystemClock, Tag, TenantId, Todo, TodoError, TodoEvent, TodoFilter, TodoRepository, TodoState, UlidGenerator,
+    UuidV4Generator, UuidV7Generator, set_platform_clock, set_platform_random,
 };
 
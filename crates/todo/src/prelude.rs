@@ -0,0 +1,15 @@
+//! The types most programs need, in one import
+//!
+//! ```
+//! use todo::prelude::*;
+//! ```
+
+pub use crate::application::add_todo_handler::AddTodoHandler;
+pub use crate::application::change_todo_state_handler::ChangeTodoStateHandler;
+pub use crate::application::delete_todo_handler::DeleteTodoHandler;
+pub use crate::application::get_todos_handler::GetTodosHandler;
+pub use crate::application::todo_app::TodoApp;
+#[cfg(feature = "serde")]
+pub use crate::infrastructure::repositories::todo::FileTodoRepository;
+pub use crate::infrastructure::repositories::todo::{InMemoryTodoRepository, TodoBackend};
+pub use crate::{EventSink, Todo, TodoError, TodoEvent, TodoRepository, TodoState};
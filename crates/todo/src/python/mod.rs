@@ -1,10 +1,35 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use chrono::{DateTime, FixedOffset, Utc};
+use pyo3::exceptions::{PyKeyError, PyValueError};
 use pyo3::prelude::*;
-use pyo3::types::PyModule;
-use crate::{Todo, TodoState, TodoError, TodoEvent};
+use pyo3::types::{PyDict, PyList, PyModule};
+use pyo3_stub_gen::derive::{
+    gen_stub_pyclass, gen_stub_pyclass_complex_enum, gen_stub_pyclass_enum, gen_stub_pymethods,
+};
+use crate::{Attachment, Priority, Subtask, Tag, Todo, TodoState, TodoError, TodoEvent};
+
+mod py_event_page;
+mod py_todo_collection;
+mod py_todo_exceptions;
+mod py_todo_repository;
+mod py_todo_service;
+mod py_todo_transaction;
+
+pub use py_event_page::PyEventPage;
+pub use py_todo_collection::{PyTodoCollection, PyTodoCollectionIterator};
+pub use py_todo_exceptions::{
+    EmptyDescriptionError, InvalidStateTransitionError, QuotaExceededError, TodoException,
+    TodoNotFoundError, TodoRepositoryError, TodoValidationError,
+};
+pub use py_todo_repository::PyTodoRepository;
+pub use py_todo_service::PyTodoService;
+pub use py_todo_transaction::PyTodoTransaction;
 
 /// Python bindings for TodoState enum
-#[pyclass]
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[gen_stub_pyclass_enum]
+#[pyclass(module = "py_todo", eq, eq_int)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum PyTodoState {
     #[pyo3(name = "TODO")]
     Todo,
@@ -14,6 +39,94 @@ pub enum PyTodoState {
     Done,
 }
 
+impl PyTodoState {
+    /// Name of the state as seen from Python
+    fn name(&self) -> &'static str {
+        match self {
+            PyTodoState::Todo => "TODO",
+            PyTodoState::InProgress => "IN_PROGRESS",
+            PyTodoState::Done => "DONE",
+        }
+    }
+
+    /// Parses a state from its Python name
+    fn from_name(name: &str) -> PyResult<Self> {
+        match name {
+            "TODO" => Ok(PyTodoState::Todo),
+            "IN_PROGRESS" => Ok(PyTodoState::InProgress),
+            "DONE" => Ok(PyTodoState::Done),
+            _ => Err(PyValueError::new_err(format!("Unknown todo state: {}", name))),
+        }
+    }
+}
+
+/// Python bindings for the Priority value object, ordered from `LOW` to `URGENT`
+#[gen_stub_pyclass_enum]
+#[pyclass(module = "py_todo", eq, eq_int, ord)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum PyPriority {
+    #[pyo3(name = "LOW")]
+    Low,
+    #[pyo3(name = "MEDIUM")]
+    Medium,
+    #[pyo3(name = "HIGH")]
+    High,
+    #[pyo3(name = "URGENT")]
+    Urgent,
+}
+
+impl From<Priority> for PyPriority {
+    fn from(priority: Priority) -> Self {
+        match priority {
+            Priority::Low => PyPriority::Low,
+            Priority::Medium => PyPriority::Medium,
+            Priority::High => PyPriority::High,
+            Priority::Urgent => PyPriority::Urgent,
+        }
+    }
+}
+
+impl From<PyPriority> for Priority {
+    fn from(priority: PyPriority) -> Self {
+        match priority {
+            PyPriority::Low => Priority::Low,
+            PyPriority::Medium => Priority::Medium,
+            PyPriority::High => Priority::High,
+            PyPriority::Urgent => Priority::Urgent,
+        }
+    }
+}
+
+impl PyPriority {
+    /// Name of the priority as seen from Python
+    fn name(&self) -> &'static str {
+        Priority::from(*self).name()
+    }
+
+    /// Parses a priority from its Python name
+    fn from_name(name: &str) -> PyResult<Self> {
+        Priority::parse(name).map(Into::into).map_err(PyValueError::new_err)
+    }
+}
+
+/// Reads a required key from a dict produced by `to_dict()`
+fn get_item<'py, T>(data: &Bound<'py, PyDict>, key: &str) -> PyResult<T>
+where
+    T: for<'a> FromPyObject<'a, 'py, Error = PyErr>,
+{
+    match data.get_item(key)? {
+        Some(value) => value.extract(),
+        None => Err(PyKeyError::new_err(key.to_string())),
+    }
+}
+
+/// Parses an RFC3339 timestamp produced by `to_dict()`
+fn parse_timestamp(value: &str) -> PyResult<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+        .map_err(|e| PyValueError::new_err(format!("Invalid timestamp {:?}: {}", value, e)))
+}
+
 impl From<TodoState> for PyTodoState {
     fn from(state: TodoState) -> Self {
         match state {
@@ -35,7 +148,8 @@ impl From<PyTodoState> for TodoState {
 }
 
 /// Python bindings for TodoError enum
-#[pyclass]
+#[gen_stub_pyclass_enum]
+#[pyclass(module = "py_todo", eq, eq_int)]
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub enum PyTodoError {
     #[pyo3(name = "EMPTY_DESCRIPTION")]
@@ -44,6 +158,12 @@ pub enum PyTodoError {
     InvalidStateTransition,
     #[pyo3(name = "TODO_NOT_FOUND")]
     TodoNotFound,
+    #[pyo3(name = "REPOSITORY_ERROR")]
+    RepositoryError,
+    #[pyo3(name = "QUOTA_EXCEEDED")]
+    QuotaExceeded,
+    #[pyo3(name = "VALIDATION_ERROR")]
+    ValidationError,
 }
 
 impl From<TodoError> for PyTodoError {
@@ -52,6 +172,9 @@ impl From<TodoError> for PyTodoError {
             TodoError::EmptyDescription => PyTodoError::EmptyDescription,
             TodoError::InvalidStateTransition => PyTodoError::InvalidStateTransition,
             TodoError::TodoNotFound => PyTodoError::TodoNotFound,
+            TodoError::RepositoryError(_) => PyTodoError::RepositoryError,
+            TodoError::QuotaExceeded => PyTodoError::QuotaExceeded,
+            TodoError::ValidationError(_) => PyTodoError::ValidationError,
         }
     }
 }
@@ -62,36 +185,162 @@ impl From<PyTodoError> for TodoError {
             PyTodoError::EmptyDescription => TodoError::EmptyDescription,
             PyTodoError::InvalidStateTransition => TodoError::InvalidStateTransition,
             PyTodoError::TodoNotFound => TodoError::TodoNotFound,
+            PyTodoError::RepositoryError => TodoError::RepositoryError(String::new()),
+            PyTodoError::QuotaExceeded => TodoError::QuotaExceeded,
+            PyTodoError::ValidationError => TodoError::ValidationError(String::new()),
         }
     }
 }
 
 /// Python bindings for Todo struct
-#[pyclass]
+#[gen_stub_pyclass]
+#[pyclass(module = "py_todo")]
 pub struct PyTodo {
     inner: Todo,
 }
 
+impl From<&Todo> for PyTodo {
+    fn from(todo: &Todo) -> Self {
+        PyTodo {
+            inner: todo.clone(),
+        }
+    }
+}
+
+impl From<&PyTodo> for Todo {
+    fn from(py_todo: &PyTodo) -> Self {
+        py_todo.inner.clone()
+    }
+}
+
+/// Python bindings for Subtask struct, a read-only copy of one checklist item
+#[gen_stub_pyclass]
+#[pyclass(module = "py_todo")]
+pub struct PySubtask {
+    inner: Subtask,
+}
+
+#[gen_stub_pymethods]
+#[pymethods]
+impl PySubtask {
+    /// Get the subtask ID, unique within its todo
+    #[getter]
+    fn id(&self) -> String {
+        self.inner.id.to_string()
+    }
+
+    /// Get the subtask description
+    #[getter]
+    fn description(&self) -> String {
+        self.inner.description.to_string()
+    }
+
+    /// Whether the subtask is checked off
+    #[getter]
+    fn done(&self) -> bool {
+        self.inner.done
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PySubtask(id={:?}, description={:?}, done={})",
+            &*self.inner.id,
+            &*self.inner.description,
+            if self.inner.done { "True" } else { "False" }
+        )
+    }
+}
+
+/// Python bindings for Attachment struct, a read-only copy of one attached file's metadata
+#[gen_stub_pyclass]
+#[pyclass(module = "py_todo")]
+pub struct PyAttachment {
+    inner: Attachment,
+}
+
+#[gen_stub_pymethods]
+#[pymethods]
+impl PyAttachment {
+    /// Get the SHA-256 of the content in lowercase hex, unique within its todo
+    #[getter]
+    fn content_hash(&self) -> String {
+        self.inner.content_hash.to_string()
+    }
+
+    /// Get the file name
+    #[getter]
+    fn name(&self) -> String {
+        self.inner.name.to_string()
+    }
+
+    /// Get the media type of the content
+    #[getter]
+    fn media_type(&self) -> String {
+        self.inner.media_type.to_string()
+    }
+
+    /// Get the size of the content in bytes
+    #[getter]
+    fn size(&self) -> u64 {
+        self.inner.size
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PyAttachment(content_hash={:?}, name={:?}, media_type={:?}, size={})",
+            &*self.inner.content_hash, &*self.inner.name, &*self.inner.media_type, self.inner.size
+        )
+    }
+}
+
+#[gen_stub_pymethods]
 #[pymethods]
 impl PyTodo {
-    /// Creates a new Todo instance
+    /// Creates a new Todo instance, due at the timezone-aware `datetime` `due_date` when
+    /// given, of `MEDIUM` priority unless `priority` is given, and with `tags`
     #[new]
-    fn new(description: String) -> PyResult<Self> {
-        let (todo, _events) = Todo::new(description)
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(
-                format!("Failed to create todo: {:?}", e)
-            ))?;
-        
+    #[pyo3(signature = (description, due_date=None, priority=None, tags=None))]
+    fn new(
+        description: String,
+        due_date: Option<DateTime<FixedOffset>>,
+        priority: Option<PyPriority>,
+        tags: Option<Vec<String>>,
+    ) -> PyResult<Self> {
+        let (mut todo, _events) = Todo::new(description)?;
+        if let Some(due_date) = due_date {
+            todo.set_due_date(due_date.with_timezone(&Utc))?;
+        }
+        if let Some(priority) = priority {
+            todo.set_priority(priority.into());
+        }
+        for tag in tags.unwrap_or_default() {
+            todo.add_tag(&tag)?;
+        }
+        todo.dirty = Some(false);
+
         Ok(PyTodo { inner: todo })
     }
 
-    /// Creates a new Todo instance and returns the created events
+    /// Creates a new Todo instance, taking the same arguments as the constructor, and
+    /// returns the created events
     #[staticmethod]
-    fn create(description: String) -> PyResult<(Self, Vec<PyTodoEvent>)> {
-        let (todo, events) = Todo::new(description)
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(
-                format!("Failed to create todo: {:?}", e)
-            ))?;
+    #[pyo3(signature = (description, due_date=None, priority=None, tags=None))]
+    fn create(
+        description: String,
+        due_date: Option<DateTime<FixedOffset>>,
+        priority: Option<PyPriority>,
+        tags: Option<Vec<String>>,
+    ) -> PyResult<(PyTodo, Vec<PyTodoEvent>)> {
+        let (mut todo, mut events) = Todo::new(description)?;
+        if let Some(due_date) = due_date {
+            events.extend(todo.set_due_date(due_date.with_timezone(&Utc))?);
+        }
+        if let Some(priority) = priority {
+            events.extend(todo.set_priority(priority.into()));
+        }
+        for tag in tags.unwrap_or_default() {
+            events.extend(todo.add_tag(&tag)?);
+        }
         
         let py_events: Vec<PyTodoEvent> = events.into_iter().map(|e| e.into()).collect();
         Ok((PyTodo { inner: todo }, py_events))
@@ -100,13 +349,21 @@ impl PyTodo {
     /// Get the todo ID
     #[getter]
     fn id(&self) -> String {
-        self.inner.id.clone()
+        self.inner.id.to_string()
     }
 
     /// Get the todo description
     #[getter]
     fn description(&self) -> String {
-        self.inner.description.clone()
+        self.inner.description.to_string()
+    }
+
+    /// Replaces the description; an empty one raises EmptyDescriptionError, and the
+    /// current one returns no event
+    fn update_description(&mut self, description: String) -> PyResult<Vec<PyTodoEvent>> {
+        let events = self.inner.update_description(description)?;
+
+        Ok(events.into_iter().map(|e| e.into()).collect())
     }
 
     /// Get the todo state
@@ -121,41 +378,331 @@ impl PyTodo {
         self.inner.created_at.to_rfc3339()
     }
 
-    /// Updates the Todo state with validation
+    /// Get the due date, or `None` when the todo has none
+    #[getter]
+    fn due_at(&self) -> Option<String> {
+        self.inner.due_at.map(|due_at| due_at.to_rfc3339())
+    }
+
+    /// Get the priority
+    #[getter]
+    fn priority(&self) -> PyPriority {
+        self.inner.priority.into()
+    }
+
+    /// Sets the priority
+    fn set_priority(&mut self, priority: PyPriority) -> Vec<PyTodoEvent> {
+        let events = self.inner.set_priority(priority.into());
+
+        events.into_iter().map(|e| e.into()).collect()
+    }
+
+    /// Get the tags, in the order they were added
+    #[getter]
+    fn tags(&self) -> Vec<String> {
+        self.inner.tags.iter().map(ToString::to_string).collect()
+    }
+
+    /// Adds a tag; an empty tag, one that is not a single word, or one the todo already has
+    /// raises TodoValidationError
+    fn add_tag(&mut self, tag: String) -> PyResult<Vec<PyTodoEvent>> {
+        let events = self.inner.add_tag(&tag)?;
+
+        Ok(events.into_iter().map(|e| e.into()).collect())
+    }
+
+    /// Removes a tag; a tag the todo does not have returns no event
+    fn remove_tag(&mut self, tag: String) -> PyResult<Vec<PyTodoEvent>> {
+        let events = self.inner.remove_tag(&tag)?;
+
+        Ok(events.into_iter().map(|e| e.into()).collect())
+    }
+
+    /// Get the checklist, in the order the subtasks were added
+    #[getter]
+    fn subtasks(&self) -> Vec<PySubtask> {
+        self.inner
+            .subtasks
+            .iter()
+            .map(|subtask| PySubtask {
+                inner: subtask.clone(),
+            })
+            .collect()
+    }
+
+    /// Adds a subtask, not yet done; its id is in the returned event
+    fn add_subtask(&mut self, description: String) -> PyResult<Vec<PyTodoEvent>> {
+        let events = self.inner.add_subtask(description)?;
+
+        Ok(events.into_iter().map(|e| e.into()).collect())
+    }
+
+    /// Checks off a subtask, or unchecks it if it was done; an unknown id raises
+    /// TodoValidationError
+    fn toggle_subtask(&mut self, subtask_id: String) -> PyResult<Vec<PyTodoEvent>> {
+        let events = self.inner.toggle_subtask(&subtask_id)?;
+
+        Ok(events.into_iter().map(|e| e.into()).collect())
+    }
+
+    /// Removes a subtask; an unknown id returns no event
+    fn remove_subtask(&mut self, subtask_id: String) -> Vec<PyTodoEvent> {
+        let events = self.inner.remove_subtask(&subtask_id);
+
+        events.into_iter().map(|e| e.into()).collect()
+    }
+
+    /// Get the ids of the todos that must be done first, in the order they were added
+    #[getter]
+    fn blocked_by(&self) -> Vec<String> {
+        self.inner.blocked_by.iter().map(ToString::to_string).collect()
+    }
+
+    /// Makes the todo wait on the todo `blocker_id`, which then has to be done before this
+    /// one can be started or finished; its own id raises TodoValidationError
+    fn block_by(&mut self, blocker_id: String) -> PyResult<Vec<PyTodoEvent>> {
+        let events = self.inner.block_by(&blocker_id)?;
+
+        Ok(events.into_iter().map(|e| e.into()).collect())
+    }
+
+    /// Stops the todo from waiting on the todo `blocker_id`; an unknown id returns no event
+    fn unblock(&mut self, blocker_id: String) -> Vec<PyTodoEvent> {
+        let events = self.inner.unblock(&blocker_id);
+
+        events.into_iter().map(|e| e.into()).collect()
+    }
+
+    /// Get the attached files, in the order they were attached; their content is kept in
+    /// a blob store, not on the todo
+    #[getter]
+    fn attachments(&self) -> Vec<PyAttachment> {
+        self.inner
+            .attachments
+            .iter()
+            .map(|attachment| PyAttachment {
+                inner: attachment.clone(),
+            })
+            .collect()
+    }
+
+    /// Share of the subtasks that are done, from 0 to 100, or None without subtasks
+    fn completion_percentage(&self) -> Option<u8> {
+        self.inner.completion_percentage()
+    }
+
+    /// Sets the due date from an RFC3339 timestamp, which must not be before the creation
+    fn set_due_date(&mut self, due_at: String) -> PyResult<Vec<PyTodoEvent>> {
+        let events = self.inner.set_due_date(parse_timestamp(&due_at)?)?;
+
+        Ok(events.into_iter().map(|e| e.into()).collect())
+    }
+
+    /// Removes the due date
+    fn clear_due_date(&mut self) -> PyResult<Vec<PyTodoEvent>> {
+        let events = self.inner.clear_due_date()?;
+
+        Ok(events.into_iter().map(|e| e.into()).collect())
+    }
+
+    /// Updates the Todo state with validation; starting or finishing a blocked todo raises
+    /// TodoValidationError
+    ///
+    /// Only this copy changes, so finishing it does not unblock the todos waiting on it.
+    /// Use `PyTodoService.change_state` for stored todos, or `PyTodoService.release` after
+    /// saving a todo finished here.
     fn update_state(&mut self, new_state: PyTodoState) -> PyResult<Vec<PyTodoEvent>> {
         let state: TodoState = new_state.into();
-        let events = self.inner.update_state(state)
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(
-                format!("Failed to update state: {:?}", e)
-            ))?;
+        let events = self.inner.update_state(state)?;
         
         Ok(events.into_iter().map(|e| e.into()).collect())
     }
 
     /// Transitions to the next state in the workflow
+    ///
+    /// Like `update_state`, finishing the todo does not unblock the todos waiting on it.
     fn change_to_next_state(&mut self) -> PyResult<Vec<PyTodoEvent>> {
-        let events = self.inner.change_to_next_state()
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(
-                format!("Failed to change to next state: {:?}", e)
-            ))?;
+        let events = self.inner.change_to_next_state()?;
         
         Ok(events.into_iter().map(|e| e.into()).collect())
     }
 
     /// Transitions to the previous state in the workflow
     fn change_to_previous_state(&mut self) -> PyResult<Vec<PyTodoEvent>> {
-        let events = self.inner.change_to_previous_state()
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(
-                format!("Failed to change to previous state: {:?}", e)
-            ))?;
+        let events = self.inner.change_to_previous_state()?;
         
         Ok(events.into_iter().map(|e| e.into()).collect())
     }
+
+    /// Returns the todo as a JSON-serializable dict
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("id", &*self.inner.id)?;
+        dict.set_item("description", &*self.inner.description)?;
+        dict.set_item("state", PyTodoState::from(self.inner.state).name())?;
+        dict.set_item("created_at", self.inner.created_at.to_rfc3339())?;
+        dict.set_item("due_at", self.due_at())?;
+        dict.set_item("priority", self.priority().name())?;
+        dict.set_item("tags", self.tags())?;
+        let subtasks = PyList::empty(py);
+        for subtask in &self.inner.subtasks {
+            let item = PyDict::new(py);
+            item.set_item("id", &*subtask.id)?;
+            item.set_item("description", &*subtask.description)?;
+            item.set_item("done", subtask.done)?;
+            subtasks.append(item)?;
+        }
+        dict.set_item("subtasks", subtasks)?;
+        dict.set_item("blocked_by", self.blocked_by())?;
+        let attachments = PyList::empty(py);
+        for attachment in &self.inner.attachments {
+            let item = PyDict::new(py);
+            item.set_item("content_hash", &*attachment.content_hash)?;
+            item.set_item("name", &*attachment.name)?;
+            item.set_item("media_type", &*attachment.media_type)?;
+            item.set_item("size", attachment.size)?;
+            attachments.append(item)?;
+        }
+        dict.set_item("attachments", attachments)?;
+        Ok(dict)
+    }
+
+    /// Rebuilds a todo from a dict produced by `to_dict()`
+    #[staticmethod]
+    fn from_dict(data: &Bound<'_, PyDict>) -> PyResult<Self> {
+        let description: String = get_item(data, "description")?;
+        if description.trim().is_empty() {
+            return Err(TodoError::EmptyDescription.into());
+        }
+        let state: String = get_item(data, "state")?;
+        let created_at: String = get_item(data, "created_at")?;
+        // Dicts written before due dates have no `due_at`
+        let due_at = match data.get_item("due_at")? {
+            Some(value) => value.extract::<Option<String>>()?,
+            None => None,
+        };
+        // and those written before priorities no `priority`
+        let priority = match data.get_item("priority")? {
+            Some(value) => PyPriority::from_name(&value.extract::<String>()?)?.into(),
+            None => Priority::default(),
+        };
+        // and those written before tags no `tags`
+        let tags = match data.get_item("tags")? {
+            Some(value) => value
+                .extract::<Vec<String>>()?
+                .iter()
+                .map(|tag| Tag::new(tag))
+                .collect::<Result<_, _>>()
+                .map_err(TodoError::ValidationError)?,
+            None => Vec::new(),
+        };
+        // and those written before subtasks no `subtasks`
+        let subtasks = match data.get_item("subtasks")? {
+            Some(value) => value
+                .extract::<Vec<Bound<'_, PyDict>>>()?
+                .iter()
+                .map(|item| {
+                    Ok(Subtask {
+                        id: get_item::<String>(item, "id")?.into(),
+                        description: get_item::<String>(item, "description")?.into(),
+                        done: get_item(item, "done")?,
+                    })
+                })
+                .collect::<PyResult<_>>()?,
+            None => Vec::new(),
+        };
+        // and those written before dependencies no `blocked_by`
+        let blocked_by = match data.get_item("blocked_by")? {
+            Some(value) => value
+                .extract::<Vec<String>>()?
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            None => Vec::new(),
+        };
+        // and those written before attachments no `attachments`
+        let attachments = match data.get_item("attachments")? {
+            Some(value) => value
+                .extract::<Vec<Bound<'_, PyDict>>>()?
+                .iter()
+                .map(|item| {
+                    Ok(Attachment {
+                        content_hash: get_item::<String>(item, "content_hash")?.into(),
+                        name: get_item::<String>(item, "name")?.into(),
+                        media_type: get_item::<String>(item, "media_type")?.into(),
+                        size: get_item(item, "size")?,
+                    })
+                })
+                .collect::<PyResult<_>>()?,
+            None => Vec::new(),
+        };
+
+        Ok(PyTodo {
+            inner: Todo {
+                id: get_item::<String>(data, "id")?.into(),
+                created_at: parse_timestamp(&created_at)?,
+                description: description.into(),
+                state: PyTodoState::from_name(&state)?.into(),
+                due_at: due_at.as_deref().map(parse_timestamp).transpose()?,
+                priority,
+                tags,
+                subtasks,
+                blocked_by,
+                attachments,
+                dirty: Some(false),
+            },
+        })
+    }
+
+    /// Arguments passed to `__new__` when unpickling, the state is restored afterwards
+    fn __getnewargs__(&self) -> (String,) {
+        (self.inner.description.to_string(),)
+    }
+
+    /// Pickle state, identical to `to_dict()`
+    fn __getstate__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        self.to_dict(py)
+    }
+
+    /// Restores the pickled state, keeping the original id and timestamps
+    fn __setstate__(&mut self, state: &Bound<'_, PyDict>) -> PyResult<()> {
+        self.inner = PyTodo::from_dict(state)?.inner;
+        Ok(())
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PyTodo(id={:?}, description={:?}, state=PyTodoState.{}, created_at={:?})",
+            self.inner.id,
+            self.inner.description,
+            PyTodoState::from(self.inner.state).name(),
+            self.inner.created_at.to_rfc3339(),
+        )
+    }
+
+    fn __str__(&self) -> String {
+        format!("[{}] {}", PyTodoState::from(self.inner.state).name(), self.inner.description)
+    }
+
+    /// Todos are equal when they share the same id
+    fn __eq__(
+        &self,
+        #[gen_stub(override_type(type_repr = "builtins.object", imports = ("builtins")))] other: &Self,
+    ) -> bool {
+        self.inner.id == other.inner.id
+    }
+
+    fn __hash__(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.inner.id.hash(&mut hasher);
+        hasher.finish()
+    }
 }
 
 /// Python bindings for TodoEvent enum
-#[pyclass]
-#[derive(Clone, Debug)]
+#[gen_stub_pyclass_complex_enum]
+#[pyclass(module = "py_todo")]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub enum PyTodoEvent {
     #[pyo3(name = "TODO_CREATED")]
     TodoCreated {
@@ -170,6 +717,107 @@ pub enum PyTodoEvent {
         to_state: PyTodoState,
         changed_at: String,
     },
+    #[pyo3(name = "TODO_ARCHIVED")]
+    TodoArchived {
+        id: String,
+        archived_at: String,
+    },
+    #[pyo3(name = "TODO_STALE")]
+    TodoStale {
+        id: String,
+        rule: String,
+        state: PyTodoState,
+        stale_at: String,
+    },
+    #[pyo3(name = "TODO_DUE_DATE_CHANGED")]
+    TodoDueDateChanged {
+        id: String,
+        due_at: Option<String>,
+        changed_at: String,
+    },
+    #[pyo3(name = "TODO_PRIORITY_CHANGED")]
+    TodoPriorityChanged {
+        id: String,
+        from_priority: PyPriority,
+        to_priority: PyPriority,
+        changed_at: String,
+    },
+    #[pyo3(name = "TODO_TAG_ADDED")]
+    TodoTagAdded {
+        id: String,
+        tag: String,
+        added_at: String,
+    },
+    #[pyo3(name = "TODO_TAG_REMOVED")]
+    TodoTagRemoved {
+        id: String,
+        tag: String,
+        removed_at: String,
+    },
+    #[pyo3(name = "TODO_SUBTASK_ADDED")]
+    TodoSubtaskAdded {
+        id: String,
+        subtask_id: String,
+        description: String,
+        added_at: String,
+    },
+    #[pyo3(name = "TODO_SUBTASK_TOGGLED")]
+    TodoSubtaskToggled {
+        id: String,
+        subtask_id: String,
+        done: bool,
+        toggled_at: String,
+    },
+    #[pyo3(name = "TODO_SUBTASK_REMOVED")]
+    TodoSubtaskRemoved {
+        id: String,
+        subtask_id: String,
+        removed_at: String,
+    },
+    #[pyo3(name = "TODO_DESCRIPTION_UPDATED")]
+    TodoDescriptionUpdated {
+        id: String,
+        old: String,
+        new: String,
+        updated_at: String,
+    },
+    #[pyo3(name = "TODO_BLOCKED")]
+    TodoBlocked {
+        id: String,
+        blocker_id: String,
+        blocked_at: String,
+    },
+    #[pyo3(name = "TODO_UNBLOCKED")]
+    TodoUnblocked {
+        id: String,
+        blocker_id: String,
+        unblocked_at: String,
+    },
+    #[pyo3(name = "TODO_DELETED")]
+    TodoDeleted {
+        id: String,
+        deleted_at: String,
+    },
+    #[pyo3(name = "TODO_RESTORED")]
+    TodoRestored {
+        id: String,
+        restored_at: String,
+    },
+    #[pyo3(name = "TODO_ATTACHMENT_ADDED")]
+    TodoAttachmentAdded {
+        id: String,
+        content_hash: String,
+        name: String,
+        media_type: String,
+        size: u64,
+        added_at: String,
+    },
+    #[pyo3(name = "TODO_ATTACHMENT_REMOVED")]
+    TodoAttachmentRemoved {
+        id: String,
+        content_hash: String,
+        removed_at: String,
+    },
 }
 
 impl From<TodoEvent> for PyTodoEvent {
@@ -177,30 +825,580 @@ impl From<TodoEvent> for PyTodoEvent {
         match event {
             TodoEvent::TodoCreated { id, description, created_at } => {
                 PyTodoEvent::TodoCreated {
-                    id,
-                    description,
+                    id: id.to_string(),
+                    description: description.to_string(),
                     created_at: created_at.to_rfc3339(),
                 }
             }
             TodoEvent::TodoStateChanged { id, from_state, to_state, changed_at } => {
                 PyTodoEvent::TodoStateChanged {
-                    id,
+                    id: id.to_string(),
                     from_state: from_state.into(),
                     to_state: to_state.into(),
                     changed_at: changed_at.to_rfc3339(),
                 }
             }
+            TodoEvent::TodoArchived { id, archived_at } => PyTodoEvent::TodoArchived {
+                id: id.to_string(),
+                archived_at: archived_at.to_rfc3339(),
+            },
+            TodoEvent::TodoStale { id, rule, state, stale_at } => PyTodoEvent::TodoStale {
+                id: id.to_string(),
+                rule: rule.to_string(),
+                state: state.into(),
+                stale_at: stale_at.to_rfc3339(),
+            },
+            TodoEvent::TodoDueDateChanged { id, due_at, changed_at } => {
+                PyTodoEvent::TodoDueDateChanged {
+                    id: id.to_string(),
+                    due_at: due_at.map(|due_at| due_at.to_rfc3339()),
+                    changed_at: changed_at.to_rfc3339(),
+                }
+            }
+            TodoEvent::TodoPriorityChanged { id, from_priority, to_priority, changed_at } => {
+                PyTodoEvent::TodoPriorityChanged {
+                    id: id.to_string(),
+                    from_priority: from_priority.into(),
+                    to_priority: to_priority.into(),
+                    changed_at: changed_at.to_rfc3339(),
+                }
+            }
+            TodoEvent::TodoTagAdded { id, tag, added_at } => PyTodoEvent::TodoTagAdded {
+                id: id.to_string(),
+                tag: tag.to_string(),
+                added_at: added_at.to_rfc3339(),
+            },
+            TodoEvent::TodoTagRemoved { id, tag, removed_at } => PyTodoEvent::TodoTagRemoved {
+                id: id.to_string(),
+                tag: tag.to_string(),
+                removed_at: removed_at.to_rfc3339(),
+            },
+            TodoEvent::TodoSubtaskAdded { id, subtask_id, description, added_at } => {
+                PyTodoEvent::TodoSubtaskAdded {
+                    id: id.to_string(),
+                    subtask_id: subtask_id.to_string(),
+                    description: description.to_string(),
+                    added_at: added_at.to_rfc3339(),
+                }
+            }
+            TodoEvent::TodoSubtaskToggled { id, subtask_id, done, toggled_at } => {
+                PyTodoEvent::TodoSubtaskToggled {
+                    id: id.to_string(),
+                    subtask_id: subtask_id.to_string(),
+                    done,
+                    toggled_at: toggled_at.to_rfc3339(),
+                }
+            }
+            TodoEvent::TodoSubtaskRemoved { id, subtask_id, removed_at } => {
+                PyTodoEvent::TodoSubtaskRemoved {
+                    id: id.to_string(),
+                    subtask_id: subtask_id.to_string(),
+                    removed_at: removed_at.to_rfc3339(),
+                }
+            }
+            TodoEvent::TodoDescriptionUpdated { id, old, new, updated_at } => {
+                PyTodoEvent::TodoDescriptionUpdated {
+                    id: id.to_string(),
+                    old: old.to_string(),
+                    new: new.to_string(),
+                    updated_at: updated_at.to_rfc3339(),
+                }
+            }
+            TodoEvent::TodoBlocked { id, blocker_id, blocked_at } => {
+                PyTodoEvent::TodoBlocked {
+                    id: id.to_string(),
+                    blocker_id: blocker_id.to_string(),
+                    blocked_at: blocked_at.to_rfc3339(),
+                }
+            }
+            TodoEvent::TodoUnblocked { id, blocker_id, unblocked_at } => {
+                PyTodoEvent::TodoUnblocked {
+                    id: id.to_string(),
+                    blocker_id: blocker_id.to_string(),
+                    unblocked_at: unblocked_at.to_rfc3339(),
+                }
+            }
+            TodoEvent::TodoDeleted { id, deleted_at } => PyTodoEvent::TodoDeleted {
+                id: id.to_string(),
+                deleted_at: deleted_at.to_rfc3339(),
+            },
+            TodoEvent::TodoRestored { id, restored_at } => PyTodoEvent::TodoRestored {
+                id: id.to_string(),
+                restored_at: restored_at.to_rfc3339(),
+            },
+            TodoEvent::TodoAttachmentAdded { id, content_hash, name, media_type, size, added_at } => {
+                PyTodoEvent::TodoAttachmentAdded {
+                    id: id.to_string(),
+                    content_hash: content_hash.to_string(),
+                    name: name.to_string(),
+                    media_type: media_type.to_string(),
+                    size,
+                    added_at: added_at.to_rfc3339(),
+                }
+            }
+            TodoEvent::TodoAttachmentRemoved { id, content_hash, removed_at } => {
+                PyTodoEvent::TodoAttachmentRemoved {
+                    id: id.to_string(),
+                    content_hash: content_hash.to_string(),
+                    removed_at: removed_at.to_rfc3339(),
+                }
+            }
         }
     }
 }
 
+#[gen_stub_pymethods]
+#[pymethods]
+impl PyTodoEvent {
+    /// Returns the event as a JSON-serializable dict, tagged with its `type`
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        match self {
+            PyTodoEvent::TodoCreated { id, description, created_at } => {
+                dict.set_item("type", "TODO_CREATED")?;
+                dict.set_item("id", id)?;
+                dict.set_item("description", description)?;
+                dict.set_item("created_at", created_at)?;
+            }
+            PyTodoEvent::TodoStateChanged { id, from_state, to_state, changed_at } => {
+                dict.set_item("type", "TODO_STATE_CHANGED")?;
+                dict.set_item("id", id)?;
+                dict.set_item("from_state", from_state.name())?;
+                dict.set_item("to_state", to_state.name())?;
+                dict.set_item("changed_at", changed_at)?;
+            }
+            PyTodoEvent::TodoArchived { id, archived_at } => {
+                dict.set_item("type", "TODO_ARCHIVED")?;
+                dict.set_item("id", id)?;
+                dict.set_item("archived_at", archived_at)?;
+            }
+            PyTodoEvent::TodoStale { id, rule, state, stale_at } => {
+                dict.set_item("type", "TODO_STALE")?;
+                dict.set_item("id", id)?;
+                dict.set_item("rule", rule)?;
+                dict.set_item("state", state.name())?;
+                dict.set_item("stale_at", stale_at)?;
+            }
+            PyTodoEvent::TodoDueDateChanged { id, due_at, changed_at } => {
+                dict.set_item("type", "TODO_DUE_DATE_CHANGED")?;
+                dict.set_item("id", id)?;
+                dict.set_item("due_at", due_at)?;
+                dict.set_item("changed_at", changed_at)?;
+            }
+            PyTodoEvent::TodoPriorityChanged { id, from_priority, to_priority, changed_at } => {
+                dict.set_item("type", "TODO_PRIORITY_CHANGED")?;
+                dict.set_item("id", id)?;
+                dict.set_item("from_priority", from_priority.name())?;
+                dict.set_item("to_priority", to_priority.name())?;
+                dict.set_item("changed_at", changed_at)?;
+            }
+            PyTodoEvent::TodoTagAdded { id, tag, added_at } => {
+                dict.set_item("type", "TODO_TAG_ADDED")?;
+                dict.set_item("id", id)?;
+                dict.set_item("tag", tag)?;
+                dict.set_item("added_at", added_at)?;
+            }
+            PyTodoEvent::TodoTagRemoved { id, tag, removed_at } => {
+                dict.set_item("type", "TODO_TAG_REMOVED")?;
+                dict.set_item("id", id)?;
+                dict.set_item("tag", tag)?;
+                dict.set_item("removed_at", removed_at)?;
+            }
+            PyTodoEvent::TodoSubtaskAdded { id, subtask_id, description, added_at } => {
+                dict.set_item("type", "TODO_SUBTASK_ADDED")?;
+                dict.set_item("id", id)?;
+                dict.set_item("subtask_id", subtask_id)?;
+                dict.set_item("description", description)?;
+                dict.set_item("added_at", added_at)?;
+            }
+            PyTodoEvent::TodoSubtaskToggled { id, subtask_id, done, toggled_at } => {
+                dict.set_item("type", "TODO_SUBTASK_TOGGLED")?;
+                dict.set_item("id", id)?;
+                dict.set_item("subtask_id", subtask_id)?;
+                dict.set_item("done", done)?;
+                dict.set_item("toggled_at", toggled_at)?;
+            }
+            PyTodoEvent::TodoSubtaskRemoved { id, subtask_id, removed_at } => {
+                dict.set_item("type", "TODO_SUBTASK_REMOVED")?;
+                dict.set_item("id", id)?;
+                dict.set_item("subtask_id", subtask_id)?;
+                dict.set_item("removed_at", removed_at)?;
+            }
+            PyTodoEvent::TodoDescriptionUpdated { id, old, new, updated_at } => {
+                dict.set_item("type", "TODO_DESCRIPTION_UPDATED")?;
+                dict.set_item("id", id)?;
+                dict.set_item("old", old)?;
+                dict.set_item("new", new)?;
+                dict.set_item("updated_at", updated_at)?;
+            }
+            PyTodoEvent::TodoBlocked { id, blocker_id, blocked_at } => {
+                dict.set_item("type", "TODO_BLOCKED")?;
+                dict.set_item("id", id)?;
+                dict.set_item("blocker_id", blocker_id)?;
+                dict.set_item("blocked_at", blocked_at)?;
+            }
+            PyTodoEvent::TodoUnblocked { id, blocker_id, unblocked_at } => {
+                dict.set_item("type", "TODO_UNBLOCKED")?;
+                dict.set_item("id", id)?;
+                dict.set_item("blocker_id", blocker_id)?;
+                dict.set_item("unblocked_at", unblocked_at)?;
+            }
+            PyTodoEvent::TodoDeleted { id, deleted_at } => {
+                dict.set_item("type", "TODO_DELETED")?;
+                dict.set_item("id", id)?;
+                dict.set_item("deleted_at", deleted_at)?;
+            }
+            PyTodoEvent::TodoRestored { id, restored_at } => {
+                dict.set_item("type", "TODO_RESTORED")?;
+                dict.set_item("id", id)?;
+                dict.set_item("restored_at", restored_at)?;
+            }
+            PyTodoEvent::TodoAttachmentAdded { id, content_hash, name, media_type, size, added_at } => {
+                dict.set_item("type", "TODO_ATTACHMENT_ADDED")?;
+                dict.set_item("id", id)?;
+                dict.set_item("content_hash", content_hash)?;
+                dict.set_item("name", name)?;
+                dict.set_item("media_type", media_type)?;
+                dict.set_item("size", size)?;
+                dict.set_item("added_at", added_at)?;
+            }
+            PyTodoEvent::TodoAttachmentRemoved { id, content_hash, removed_at } => {
+                dict.set_item("type", "TODO_ATTACHMENT_REMOVED")?;
+                dict.set_item("id", id)?;
+                dict.set_item("content_hash", content_hash)?;
+                dict.set_item("removed_at", removed_at)?;
+            }
+        }
+        Ok(dict)
+    }
+
+    /// Rebuilds an event from a dict produced by `to_dict()`
+    #[staticmethod]
+    fn from_dict(data: &Bound<'_, PyDict>) -> PyResult<Self> {
+        let event_type: String = get_item(data, "type")?;
+        match event_type.as_str() {
+            "TODO_CREATED" => {
+                let created_at: String = get_item(data, "created_at")?;
+                parse_timestamp(&created_at)?;
+                Ok(PyTodoEvent::TodoCreated {
+                    id: get_item(data, "id")?,
+                    description: get_item(data, "description")?,
+                    created_at,
+                })
+            }
+            "TODO_STATE_CHANGED" => {
+                let from_state: String = get_item(data, "from_state")?;
+                let to_state: String = get_item(data, "to_state")?;
+                let changed_at: String = get_item(data, "changed_at")?;
+                parse_timestamp(&changed_at)?;
+                Ok(PyTodoEvent::TodoStateChanged {
+                    id: get_item(data, "id")?,
+                    from_state: PyTodoState::from_name(&from_state)?,
+                    to_state: PyTodoState::from_name(&to_state)?,
+                    changed_at,
+                })
+            }
+            "TODO_ARCHIVED" => {
+                let archived_at: String = get_item(data, "archived_at")?;
+                parse_timestamp(&archived_at)?;
+                Ok(PyTodoEvent::TodoArchived { id: get_item(data, "id")?, archived_at })
+            }
+            "TODO_STALE" => {
+                let state: String = get_item(data, "state")?;
+                let stale_at: String = get_item(data, "stale_at")?;
+                parse_timestamp(&stale_at)?;
+                Ok(PyTodoEvent::TodoStale {
+                    id: get_item(data, "id")?,
+                    rule: get_item(data, "rule")?,
+                    state: PyTodoState::from_name(&state)?,
+                    stale_at,
+                })
+            }
+            "TODO_DUE_DATE_CHANGED" => {
+                let due_at: Option<String> = get_item(data, "due_at")?;
+                let changed_at: String = get_item(data, "changed_at")?;
+                due_at.as_deref().map(parse_timestamp).transpose()?;
+                parse_timestamp(&changed_at)?;
+                Ok(PyTodoEvent::TodoDueDateChanged { id: get_item(data, "id")?, due_at, changed_at })
+            }
+            "TODO_PRIORITY_CHANGED" => {
+                let from_priority: String = get_item(data, "from_priority")?;
+                let to_priority: String = get_item(data, "to_priority")?;
+                let changed_at: String = get_item(data, "changed_at")?;
+                parse_timestamp(&changed_at)?;
+                Ok(PyTodoEvent::TodoPriorityChanged {
+                    id: get_item(data, "id")?,
+                    from_priority: PyPriority::from_name(&from_priority)?,
+                    to_priority: PyPriority::from_name(&to_priority)?,
+                    changed_at,
+                })
+            }
+            "TODO_TAG_ADDED" => {
+                let added_at: String = get_item(data, "added_at")?;
+                parse_timestamp(&added_at)?;
+                Ok(PyTodoEvent::TodoTagAdded { id: get_item(data, "id")?, tag: get_item(data, "tag")?, added_at })
+            }
+            "TODO_TAG_REMOVED" => {
+                let removed_at: String = get_item(data, "removed_at")?;
+                parse_timestamp(&removed_at)?;
+                Ok(PyTodoEvent::TodoTagRemoved { id: get_item(data, "id")?, tag: get_item(data, "tag")?, removed_at })
+            }
+            "TODO_SUBTASK_ADDED" => {
+                let added_at: String = get_item(data, "added_at")?;
+                parse_timestamp(&added_at)?;
+                Ok(PyTodoEvent::TodoSubtaskAdded {
+                    id: get_item(data, "id")?,
+                    subtask_id: get_item(data, "subtask_id")?,
+                    description: get_item(data, "description")?,
+                    added_at,
+                })
+            }
+            "TODO_SUBTASK_TOGGLED" => {
+                let toggled_at: String = get_item(data, "toggled_at")?;
+                parse_timestamp(&toggled_at)?;
+                Ok(PyTodoEvent::TodoSubtaskToggled {
+                    id: get_item(data, "id")?,
+                    subtask_id: get_item(data, "subtask_id")?,
+                    done: get_item(data, "done")?,
+                    toggled_at,
+                })
+            }
+            "TODO_SUBTASK_REMOVED" => {
+                let removed_at: String = get_item(data, "removed_at")?;
+                parse_timestamp(&removed_at)?;
+                Ok(PyTodoEvent::TodoSubtaskRemoved {
+                    id: get_item(data, "id")?,
+                    subtask_id: get_item(data, "subtask_id")?,
+                    removed_at,
+                })
+            }
+            "TODO_DESCRIPTION_UPDATED" => {
+                let updated_at: String = get_item(data, "updated_at")?;
+                parse_timestamp(&updated_at)?;
+                Ok(PyTodoEvent::TodoDescriptionUpdated {
+                    id: get_item(data, "id")?,
+                    old: get_item(data, "old")?,
+                    new: get_item(data, "new")?,
+                    updated_at,
+                })
+            }
+            "TODO_BLOCKED" => {
+                let blocked_at: String = get_item(data, "blocked_at")?;
+                parse_timestamp(&blocked_at)?;
+                Ok(PyTodoEvent::TodoBlocked {
+                    id: get_item(data, "id")?,
+                    blocker_id: get_item(data, "blocker_id")?,
+                    blocked_at,
+                })
+            }
+            "TODO_UNBLOCKED" => {
+                let unblocked_at: String = get_item(data, "unblocked_at")?;
+                parse_timestamp(&unblocked_at)?;
+                Ok(PyTodoEvent::TodoUnblocked {
+                    id: get_item(data, "id")?,
+                    blocker_id: get_item(data, "blocker_id")?,
+                    unblocked_at,
+                })
+            }
+            "TODO_DELETED" => {
+                let deleted_at: String = get_item(data, "deleted_at")?;
+                parse_timestamp(&deleted_at)?;
+                Ok(PyTodoEvent::TodoDeleted { id: get_item(data, "id")?, deleted_at })
+            }
+            "TODO_RESTORED" => {
+                let restored_at: String = get_item(data, "restored_at")?;
+                parse_timestamp(&restored_at)?;
+                Ok(PyTodoEvent::TodoRestored { id: get_item(data, "id")?, restored_at })
+            }
+            "TODO_ATTACHMENT_ADDED" => {
+                let added_at: String = get_item(data, "added_at")?;
+                parse_timestamp(&added_at)?;
+                Ok(PyTodoEvent::TodoAttachmentAdded {
+                    id: get_item(data, "id")?,
+                    content_hash: get_item(data, "content_hash")?,
+                    name: get_item(data, "name")?,
+                    media_type: get_item(data, "media_type")?,
+                    size: get_item(data, "size")?,
+                    added_at,
+                })
+            }
+            "TODO_ATTACHMENT_REMOVED" => {
+                let removed_at: String = get_item(data, "removed_at")?;
+                parse_timestamp(&removed_at)?;
+                Ok(PyTodoEvent::TodoAttachmentRemoved {
+                    id: get_item(data, "id")?,
+                    content_hash: get_item(data, "content_hash")?,
+                    removed_at,
+                })
+            }
+            _ => Err(PyValueError::new_err(format!("Unknown todo event type: {}", event_type))),
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        match self {
+            PyTodoEvent::TodoCreated { id, description, created_at } => format!(
+                "PyTodoEvent.TODO_CREATED(id={:?}, description={:?}, created_at={:?})",
+                id, description, created_at
+            ),
+            PyTodoEvent::TodoStateChanged { id, from_state, to_state, changed_at } => format!(
+                "PyTodoEvent.TODO_STATE_CHANGED(id={:?}, from_state=PyTodoState.{}, to_state=PyTodoState.{}, changed_at={:?})",
+                id, from_state.name(), to_state.name(), changed_at
+            ),
+            PyTodoEvent::TodoArchived { id, archived_at } => format!(
+                "PyTodoEvent.TODO_ARCHIVED(id={:?}, archived_at={:?})",
+                id, archived_at
+            ),
+            PyTodoEvent::TodoStale { id, rule, state, stale_at } => format!(
+                "PyTodoEvent.TODO_STALE(id={:?}, rule={:?}, state=PyTodoState.{}, stale_at={:?})",
+                id, rule, state.name(), stale_at
+            ),
+            PyTodoEvent::TodoDueDateChanged { id, due_at, changed_at } => format!(
+                "PyTodoEvent.TODO_DUE_DATE_CHANGED(id={:?}, due_at={}, changed_at={:?})",
+                id,
+                due_at.as_ref().map_or("None".to_string(), |due_at| format!("{:?}", due_at)),
+                changed_at
+            ),
+            PyTodoEvent::TodoPriorityChanged { id, from_priority, to_priority, changed_at } => format!(
+                "PyTodoEvent.TODO_PRIORITY_CHANGED(id={:?}, from_priority=PyPriority.{}, to_priority=PyPriority.{}, changed_at={:?})",
+                id, from_priority.name(), to_priority.name(), changed_at
+            ),
+            PyTodoEvent::TodoTagAdded { id, tag, added_at } => format!(
+                "PyTodoEvent.TODO_TAG_ADDED(id={:?}, tag={:?}, added_at={:?})",
+                id, tag, added_at
+            ),
+            PyTodoEvent::TodoTagRemoved { id, tag, removed_at } => format!(
+                "PyTodoEvent.TODO_TAG_REMOVED(id={:?}, tag={:?}, removed_at={:?})",
+                id, tag, removed_at
+            ),
+            PyTodoEvent::TodoSubtaskAdded { id, subtask_id, description, added_at } => format!(
+                "PyTodoEvent.TODO_SUBTASK_ADDED(id={:?}, subtask_id={:?}, description={:?}, added_at={:?})",
+                id, subtask_id, description, added_at
+            ),
+            PyTodoEvent::TodoSubtaskToggled { id, subtask_id, done, toggled_at } => format!(
+                "PyTodoEvent.TODO_SUBTASK_TOGGLED(id={:?}, subtask_id={:?}, done={}, toggled_at={:?})",
+                id, subtask_id, if *done { "True" } else { "False" }, toggled_at
+            ),
+            PyTodoEvent::TodoSubtaskRemoved { id, subtask_id, removed_at } => format!(
+                "PyTodoEvent.TODO_SUBTASK_REMOVED(id={:?}, subtask_id={:?}, removed_at={:?})",
+                id, subtask_id, removed_at
+            ),
+            PyTodoEvent::TodoDescriptionUpdated { id, old, new, updated_at } => format!(
+                "PyTodoEvent.TODO_DESCRIPTION_UPDATED(id={:?}, old={:?}, new={:?}, updated_at={:?})",
+                id, old, new, updated_at
+            ),
+            PyTodoEvent::TodoBlocked { id, blocker_id, blocked_at } => format!(
+                "PyTodoEvent.TODO_BLOCKED(id={:?}, blocker_id={:?}, blocked_at={:?})",
+                id, blocker_id, blocked_at
+            ),
+            PyTodoEvent::TodoUnblocked { id, blocker_id, unblocked_at } => format!(
+                "PyTodoEvent.TODO_UNBLOCKED(id={:?}, blocker_id={:?}, unblocked_at={:?})",
+                id, blocker_id, unblocked_at
+            ),
+            PyTodoEvent::TodoDeleted { id, deleted_at } => format!(
+                "PyTodoEvent.TODO_DELETED(id={:?}, deleted_at={:?})",
+                id, deleted_at
+            ),
+            PyTodoEvent::TodoRestored { id, restored_at } => format!(
+                "PyTodoEvent.TODO_RESTORED(id={:?}, restored_at={:?})",
+                id, restored_at
+            ),
+            PyTodoEvent::TodoAttachmentAdded { id, content_hash, name, media_type, size, added_at } => format!(
+                "PyTodoEvent.TODO_ATTACHMENT_ADDED(id={:?}, content_hash={:?}, name={:?}, media_type={:?}, size={}, added_at={:?})",
+                id, content_hash, name, media_type, size, added_at
+            ),
+            PyTodoEvent::TodoAttachmentRemoved { id, content_hash, removed_at } => format!(
+                "PyTodoEvent.TODO_ATTACHMENT_REMOVED(id={:?}, content_hash={:?}, removed_at={:?})",
+                id, content_hash, removed_at
+            ),
+        }
+    }
+
+    fn __str__(&self) -> String {
+        match self {
+            PyTodoEvent::TodoCreated { id, description, .. } => {
+                format!("todo {} created: {}", id, description)
+            }
+            PyTodoEvent::TodoStateChanged { id, from_state, to_state, .. } => {
+                format!("todo {} changed from {} to {}", id, from_state.name(), to_state.name())
+            }
+            PyTodoEvent::TodoArchived { id, .. } => format!("todo {} archived", id),
+            PyTodoEvent::TodoStale { id, state, .. } => {
+                format!("todo {} stale in {}", id, state.name())
+            }
+            PyTodoEvent::TodoDueDateChanged { id, due_at: Some(due_at), .. } => {
+                format!("todo {} due {}", id, due_at)
+            }
+            PyTodoEvent::TodoDueDateChanged { id, due_at: None, .. } => {
+                format!("todo {} due date cleared", id)
+            }
+            PyTodoEvent::TodoPriorityChanged { id, from_priority, to_priority, .. } => {
+                format!("todo {} priority changed from {} to {}", id, from_priority.name(), to_priority.name())
+            }
+            PyTodoEvent::TodoTagAdded { id, tag, .. } => format!("todo {} tagged {}", id, tag),
+            PyTodoEvent::TodoTagRemoved { id, tag, .. } => format!("todo {} untagged {}", id, tag),
+            PyTodoEvent::TodoSubtaskAdded { id, description, .. } => {
+                format!("todo {} subtask added: {}", id, description)
+            }
+            PyTodoEvent::TodoSubtaskToggled { id, subtask_id, done: true, .. } => {
+                format!("todo {} subtask {} checked off", id, subtask_id)
+            }
+            PyTodoEvent::TodoSubtaskToggled { id, subtask_id, done: false, .. } => {
+                format!("todo {} subtask {} unchecked", id, subtask_id)
+            }
+            PyTodoEvent::TodoSubtaskRemoved { id, subtask_id, .. } => {
+                format!("todo {} subtask {} removed", id, subtask_id)
+            }
+            PyTodoEvent::TodoDescriptionUpdated { id, new, .. } => {
+                format!("todo {} renamed to {}", id, new)
+            }
+            PyTodoEvent::TodoBlocked { id, blocker_id, .. } => {
+                format!("todo {} blocked by {}", id, blocker_id)
+            }
+            PyTodoEvent::TodoUnblocked { id, blocker_id, .. } => {
+                format!("todo {} no longer blocked by {}", id, blocker_id)
+            }
+            PyTodoEvent::TodoDeleted { id, .. } => format!("todo {} deleted", id),
+            PyTodoEvent::TodoRestored { id, .. } => format!("todo {} restored", id),
+            PyTodoEvent::TodoAttachmentAdded { id, name, .. } => {
+                format!("todo {} attachment added: {}", id, name)
+            }
+            PyTodoEvent::TodoAttachmentRemoved { id, content_hash, .. } => {
+                format!("todo {} attachment {} removed", id, content_hash)
+            }
+        }
+    }
+
+    /// Events are equal when they are the same variant with the same fields
+    fn __eq__(
+        &self,
+        #[gen_stub(override_type(type_repr = "builtins.object", imports = ("builtins")))] other: &Self,
+    ) -> bool {
+        self == other
+    }
+
+    fn __hash__(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
 /// Python module initialization
 #[pymodule]
 pub fn todo(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyTodo>()?;
+    m.add_class::<PySubtask>()?;
+    m.add_class::<PyAttachment>()?;
     m.add_class::<PyTodoState>()?;
+    m.add_class::<PyPriority>()?;
     m.add_class::<PyTodoError>()?;
     m.add_class::<PyTodoEvent>()?;
+    m.add_class::<PyTodoService>()?;
+    m.add_class::<PyTodoCollection>()?;
+    m.add_class::<PyTodoTransaction>()?;
+    m.add_class::<PyEventPage>()?;
+    py_todo_exceptions::register(m)?;
     Ok(())
 }
 
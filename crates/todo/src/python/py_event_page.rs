@@ -0,0 +1,38 @@
+use pyo3::prelude::*;
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+use crate::application::poll_events_handler::EventPage;
+use super::PyTodoEvent;
+
+/// Python bindings for the events returned by one `poll_events` call
+#[gen_stub_pyclass]
+#[pyclass(module = "py_todo")]
+pub struct PyEventPage {
+    page: EventPage,
+}
+
+impl From<EventPage> for PyEventPage {
+    fn from(page: EventPage) -> Self {
+        PyEventPage { page }
+    }
+}
+
+#[gen_stub_pymethods]
+#[pymethods]
+impl PyEventPage {
+    /// The events, oldest first
+    #[getter]
+    fn events(&self) -> Vec<PyTodoEvent> {
+        self.page.events.iter().cloned().map(Into::into).collect()
+    }
+
+    /// The cursor to pass to the next `poll_events` call, or `None` while no event was
+    /// returned from the start
+    #[getter]
+    fn cursor(&self) -> Option<String> {
+        self.page.cursor.map(|cursor| cursor.to_string())
+    }
+
+    fn __len__(&self) -> usize {
+        self.page.events.len()
+    }
+}
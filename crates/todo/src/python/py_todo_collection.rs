@@ -0,0 +1,106 @@
+use std::sync::Arc;
+use pyo3::exceptions::PyIndexError;
+use pyo3::prelude::*;
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+use crate::Todo;
+use super::{PyTodo, PyTodoState};
+
+/// Python bindings for a read-only sequence of todos
+///
+/// Todos are only converted into `PyTodo` objects when accessed, and filtering shares
+/// the underlying todos instead of copying them. Each access returns a fresh copy, so
+/// mutating an item does not change the collection.
+#[gen_stub_pyclass]
+#[pyclass(module = "py_todo")]
+pub struct PyTodoCollection {
+    todos: Arc<Vec<Todo>>,
+    indices: Vec<usize>,
+}
+
+impl PyTodoCollection {
+    /// Creates a new collection over all the given todos
+    pub fn new(todos: Vec<Todo>) -> Self {
+        let indices = (0..todos.len()).collect();
+        PyTodoCollection {
+            todos: Arc::new(todos),
+            indices,
+        }
+    }
+
+    fn get(&self, position: usize) -> Option<PyTodo> {
+        self.indices
+            .get(position)
+            .map(|&index| PyTodo::from(&self.todos[index]))
+    }
+}
+
+#[gen_stub_pymethods]
+#[pymethods]
+impl PyTodoCollection {
+    /// Returns a new collection with the todos matching all given criteria
+    #[pyo3(signature = (*, state=None))]
+    fn filter(&self, state: Option<PyTodoState>) -> Self {
+        let indices = self
+            .indices
+            .iter()
+            .copied()
+            .filter(|&index| match state {
+                Some(state) => PyTodoState::from(self.todos[index].state) == state,
+                None => true,
+            })
+            .collect();
+
+        PyTodoCollection {
+            todos: self.todos.clone(),
+            indices,
+        }
+    }
+
+    fn __len__(&self) -> usize {
+        self.indices.len()
+    }
+
+    fn __getitem__(&self, index: isize) -> PyResult<PyTodo> {
+        let len = self.indices.len() as isize;
+        let position = if index < 0 { index + len } else { index };
+        if position < 0 {
+            return Err(PyIndexError::new_err("todo index out of range"));
+        }
+        self.get(position as usize)
+            .ok_or_else(|| PyIndexError::new_err("todo index out of range"))
+    }
+
+    fn __iter__(slf: PyRef<'_, Self>) -> PyTodoCollectionIterator {
+        PyTodoCollectionIterator {
+            collection: slf.into(),
+            position: 0,
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!("PyTodoCollection(len={})", self.indices.len())
+    }
+}
+
+/// Iterator over a PyTodoCollection
+#[gen_stub_pyclass]
+#[pyclass(module = "py_todo")]
+pub struct PyTodoCollectionIterator {
+    collection: Py<PyTodoCollection>,
+    position: usize,
+}
+
+#[gen_stub_pymethods]
+#[pymethods]
+impl PyTodoCollectionIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    #[gen_stub(override_return_type(type_repr = "PyTodo", imports = ()))]
+    fn __next__(&mut self, py: Python<'_>) -> Option<PyTodo> {
+        let todo = self.collection.borrow(py).get(self.position)?;
+        self.position += 1;
+        Some(todo)
+    }
+}
@@ -0,0 +1,108 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3_stub_gen::create_exception;
+use crate::TodoError;
+use super::PyTodoError;
+
+create_exception!(
+    py_todo,
+    TodoException,
+    PyValueError,
+    "Base class for all todo errors. Carries the `error` code and a human readable `message`."
+);
+/// Creates a TodoException subclass
+///
+/// `pyo3_stub_gen::create_exception!` always renders the base class as a builtin, so the
+/// subclasses register their stub information themselves with `TodoException` as base.
+macro_rules! create_todo_exception {
+    ($name:ident, $doc:expr) => {
+        pyo3::create_exception!(py_todo, $name, TodoException, $doc);
+
+        pyo3_stub_gen::inventory::submit! {
+            pyo3_stub_gen::type_info::PyClassInfo {
+                pyclass_name: stringify!($name),
+                struct_id: std::any::TypeId::of::<$name>,
+                getters: &[],
+                setters: &[],
+                module: Some("py_todo"),
+                doc: $doc,
+                bases: &[|| pyo3_stub_gen::TypeInfo::unqualified("TodoException")],
+                has_eq: false,
+                has_ord: false,
+                has_hash: false,
+                has_str: false,
+                subclass: true,
+            }
+        }
+    };
+}
+
+create_todo_exception!(EmptyDescriptionError, "Raised when a todo description is empty");
+create_todo_exception!(InvalidStateTransitionError, "Raised when a state transition is not allowed");
+create_todo_exception!(TodoNotFoundError, "Raised when a todo does not exist");
+create_todo_exception!(TodoRepositoryError, "Raised when the underlying repository fails");
+create_todo_exception!(QuotaExceededError, "Raised when a todo list is over its quota");
+create_todo_exception!(TodoValidationError, "Raised when a value is rejected, such as a due date before the todo was created");
+
+/// Converts a TodoError into the matching TodoException subclass
+///
+/// The raised exception exposes `error` (a `PyTodoError` code) and `message` attributes,
+/// so callers can catch specific failures instead of parsing strings.
+impl From<TodoError> for PyErr {
+    fn from(err: TodoError) -> Self {
+        let message = err.to_string();
+        let py_err = match err {
+            TodoError::EmptyDescription => EmptyDescriptionError::new_err(message.clone()),
+            TodoError::InvalidStateTransition => InvalidStateTransitionError::new_err(message.clone()),
+            TodoError::TodoNotFound => TodoNotFoundError::new_err(message.clone()),
+            TodoError::RepositoryError(_) => TodoRepositoryError::new_err(message.clone()),
+            TodoError::QuotaExceeded => QuotaExceededError::new_err(message.clone()),
+            TodoError::ValidationError(_) => TodoValidationError::new_err(message.clone()),
+        };
+
+        Python::attach(|py| {
+            let value = py_err.value(py);
+            value.setattr("error", PyTodoError::from(err))?;
+            value.setattr("message", message)
+        })
+        .map(|_| py_err)
+        .unwrap_or_else(|e| e)
+    }
+}
+
+/// Converts an exception raised by Python code back into a TodoError
+///
+/// TodoException subclasses map onto their matching variants, anything else is
+/// reported as `TodoError::RepositoryError` with the exception's text.
+pub(crate) fn todo_error_from_py(err: PyErr) -> TodoError {
+    Python::attach(|py| {
+        if err.is_instance_of::<EmptyDescriptionError>(py) {
+            TodoError::EmptyDescription
+        } else if err.is_instance_of::<InvalidStateTransitionError>(py) {
+            TodoError::InvalidStateTransition
+        } else if err.is_instance_of::<TodoNotFoundError>(py) {
+            TodoError::TodoNotFound
+        } else if err.is_instance_of::<QuotaExceededError>(py) {
+            TodoError::QuotaExceeded
+        } else if err.is_instance_of::<TodoValidationError>(py) {
+            let message = err.value(py).to_string();
+            let message = message.strip_prefix("validation error: ").unwrap_or(&message);
+            TodoError::ValidationError(message.to_string())
+        } else {
+            TodoError::RepositoryError(err.to_string())
+        }
+    })
+}
+
+/// Registers the exception hierarchy on the Python module
+pub(crate) fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = m.py();
+    m.add("TodoException", py.get_type::<TodoException>())?;
+    m.add("EmptyDescriptionError", py.get_type::<EmptyDescriptionError>())?;
+    m.add("InvalidStateTransitionError", py.get_type::<InvalidStateTransitionError>())?;
+    m.add("TodoNotFoundError", py.get_type::<TodoNotFoundError>())?;
+    m.add("TodoRepositoryError", py.get_type::<TodoRepositoryError>())?;
+    m.add("QuotaExceededError", py.get_type::<QuotaExceededError>())?;
+    m.add("TodoValidationError", py.get_type::<TodoValidationError>())?;
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use async_trait::async_trait;
+use pyo3::prelude::*;
+use crate::{Todo, TodoError, TodoRepository};
+use super::PyTodo;
+use super::py_todo_exceptions::todo_error_from_py;
+
+/// Adapter exposing a Python object as a TodoRepository
+///
+/// The wrapped object must provide `save(todo)`, `find_by_id(id)`, `find_all()` and
+/// `delete(id)` methods, e.g. a thin class over a Django model or SQLAlchemy session.
+/// Every call attaches to the interpreter for its duration, and exceptions raised by
+/// the Python side are translated back into `TodoError` (unknown exceptions become
+/// `TodoError::RepositoryError`).
+pub struct PyTodoRepository {
+    inner: Py<PyAny>,
+}
+
+impl PyTodoRepository {
+    /// Creates a new PyTodoRepository wrapping the given Python object
+    pub fn new(inner: Py<PyAny>) -> Self {
+        PyTodoRepository { inner }
+    }
+}
+
+fn extract_todo(obj: &Bound<'_, PyAny>) -> PyResult<Todo> {
+    let py_todo = obj.cast::<PyTodo>()?.borrow();
+    Ok(Todo::from(&*py_todo))
+}
+
+#[async_trait]
+impl TodoRepository for PyTodoRepository {
+    async fn save(&self, todo: &Todo) -> Result<(), TodoError> {
+        Python::attach(|py| {
+            let py_todo = Py::new(py, PyTodo::from(todo))?;
+            self.inner.call_method1(py, "save", (py_todo,))?;
+            Ok(())
+        })
+        .map_err(todo_error_from_py)
+    }
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<Todo>, TodoError> {
+        Python::attach(|py| {
+            let result = self.inner.call_method1(py, "find_by_id", (id,))?;
+            let result = result.bind(py);
+            if result.is_none() {
+                return Ok(None);
+            }
+            extract_todo(result).map(Some)
+        })
+        .map_err(todo_error_from_py)
+    }
+
+    async fn find_all(&self) -> Result<Vec<Todo>, TodoError> {
+        Python::attach(|py| {
+            let result = self.inner.call_method0(py, "find_all")?;
+            result
+                .bind(py)
+                .try_iter()?
+                .map(|item| extract_todo(&item?))
+                .collect::<PyResult<Vec<Todo>>>()
+        })
+        .map_err(todo_error_from_py)
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), TodoError> {
+        Python::attach(|py| {
+            self.inner.call_method1(py, "delete", (id,))?;
+            Ok(())
+        })
+        .map_err(todo_error_from_py)
+    }
+}
@@ -0,0 +1,225 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use futures::executor::block_on;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+use crate::application::add_todo_handler::AddTodoHandler;
+use crate::application::change_todo_state_handler::ChangeTodoStateHandler;
+use crate::application::dependency_service::DependencyService;
+use crate::application::get_todos_handler::GetTodosHandler;
+use crate::application::poll_events_handler::{EventCursor, PollEventsHandler};
+use crate::infrastructure::event_stores::{FileEventStore, InMemoryEventStore};
+use crate::infrastructure::repositories::todo::InMemoryTodoRepository;
+use crate::{EventStore, TodoFilter, TodoRepository};
+use crate::infrastructure::repositories::todo::TransactionalTodoRepository;
+use super::{
+    PyEventPage, PyTodoCollection, PyTodoEvent, PyTodoRepository, PyTodoState, PyTodoTransaction,
+};
+
+/// Events returned by `poll_events` when no limit is given
+const DEFAULT_POLL_LIMIT: usize = 100;
+
+/// Python bindings for the application handlers
+///
+/// All handlers share one repository: the given Python repository object, or an
+/// in-memory repository when none is provided. The GIL is released while a handler
+/// runs, so other Python threads keep going during storage I/O; a Python repository
+/// re-acquires it only for the duration of each of its calls.
+///
+/// The events of every change are recorded in an event store, in memory or in the JSON
+/// lines file at `events_path`, from which `poll_events` reads them.
+#[gen_stub_pyclass]
+#[pyclass(module = "py_todo")]
+pub struct PyTodoService {
+    repository: Arc<dyn TodoRepository>,
+    add_todo_handler: AddTodoHandler<Arc<dyn TodoRepository>>,
+    get_todos_handler: GetTodosHandler<Arc<dyn TodoRepository>>,
+    change_todo_state_handler: ChangeTodoStateHandler<Arc<dyn TodoRepository>>,
+    dependency_service: DependencyService<Arc<dyn TodoRepository>>,
+    poll_events_handler: PollEventsHandler,
+    event_store: Arc<dyn EventStore>,
+}
+
+impl PyTodoService {
+    /// Creates a new service whose handlers share `repository`, recording events in memory
+    pub fn from_repository(repository: Arc<dyn TodoRepository>) -> Self {
+        PyTodoService::with_event_store(repository, Arc::new(InMemoryEventStore::new()))
+    }
+
+    /// Creates a new service whose handlers share `repository` and record their events in
+    /// `event_store`
+    pub fn with_event_store(
+        repository: Arc<dyn TodoRepository>,
+        event_store: Arc<dyn EventStore>,
+    ) -> Self {
+        PyTodoService {
+            add_todo_handler: AddTodoHandler::new(repository.clone())
+                .with_event_sink(event_store.clone()),
+            get_todos_handler: GetTodosHandler::new(repository.clone()),
+            change_todo_state_handler: ChangeTodoStateHandler::new(repository.clone())
+                .with_event_sink(event_store.clone()),
+            dependency_service: DependencyService::new(repository.clone())
+                .with_event_sink(event_store.clone()),
+            poll_events_handler: PollEventsHandler::new(event_store.clone()),
+            event_store,
+            repository,
+        }
+    }
+}
+
+#[gen_stub_pymethods]
+#[pymethods]
+impl PyTodoService {
+    /// Creates a new service backed by `repository`, or in memory when omitted, recording
+    /// events to the file at `events_path`, or in memory when omitted
+    #[new]
+    #[pyo3(signature = (repository=None, events_path=None))]
+    fn new(repository: Option<Py<PyAny>>, events_path: Option<PathBuf>) -> Self {
+        let repository: Arc<dyn TodoRepository> = match repository {
+            Some(repository) => Arc::new(PyTodoRepository::new(repository)),
+            None => Arc::new(InMemoryTodoRepository::new()),
+        };
+        let event_store: Arc<dyn EventStore> = match events_path {
+            Some(path) => Arc::new(FileEventStore::new(path)),
+            None => Arc::new(InMemoryEventStore::new()),
+        };
+
+        PyTodoService::with_event_store(repository, event_store)
+    }
+
+    /// Starts a transaction, used as `with service.transaction() as tx:`
+    ///
+    /// Changes made through `tx` are staged and committed together when the block
+    /// exits normally, or rolled back when it raises. Their events are recorded when
+    /// they are committed.
+    fn transaction(&self, py: Python<'_>) -> PyResult<PyTodoTransaction> {
+        let unit_of_work = Arc::new(TransactionalTodoRepository::new(self.repository.clone()));
+        let staged_events = Arc::new(InMemoryEventStore::new());
+        let service = Py::new(
+            py,
+            PyTodoService::with_event_store(unit_of_work.clone(), staged_events.clone()),
+        )?;
+
+        Ok(PyTodoTransaction::new(
+            unit_of_work,
+            service,
+            staged_events,
+            self.event_store.clone(),
+        ))
+    }
+
+    /// Creates and stores a new todo, returning the created events
+    fn add_todo(&self, py: Python<'_>, description: String) -> PyResult<Vec<PyTodoEvent>> {
+        let (_, events) = py.detach(|| block_on(self.add_todo_handler.new_todo(description)))?;
+
+        Ok(events.into_iter().map(|e| e.into()).collect())
+    }
+
+    /// Returns all stored todos as a lazily converted collection
+    fn get_todos(&self, py: Python<'_>) -> PyResult<PyTodoCollection> {
+        let todos = py.detach(|| block_on(self.get_todos_handler.get_todos()))?;
+
+        Ok(PyTodoCollection::new(todos))
+    }
+
+    /// Returns the stored todos matching a search query, such as
+    /// `state:in_progress -created<2025-01-01 "quarterly report"`
+    ///
+    /// Raises `ValueError` if the query is invalid.
+    fn search(&self, py: Python<'_>, query: &str) -> PyResult<PyTodoCollection> {
+        let filter = TodoFilter::parse(query).map_err(PyValueError::new_err)?;
+        let todos = py.detach(|| block_on(self.get_todos_handler.search_todos(&filter)))?;
+
+        Ok(PyTodoCollection::new(todos))
+    }
+
+    /// Changes the state of a stored todo, returning the state changed events, followed by
+    /// the unblocked events of the todos waiting on it when it is finished
+    fn change_state(
+        &self,
+        py: Python<'_>,
+        id: String,
+        new_state: PyTodoState,
+    ) -> PyResult<Vec<PyTodoEvent>> {
+        let (_, events) = py.detach(|| {
+            block_on(
+                self.change_todo_state_handler
+                    .change_state(id, new_state.into()),
+            )
+        })?;
+
+        Ok(events.into_iter().map(|e| e.into()).collect())
+    }
+
+    /// Makes the stored todo `id` wait on the stored todo `blocker_id`, returning the blocked
+    /// events; none when it already waits on it
+    ///
+    /// Raises `TodoNotFoundError` if either todo is missing, and `TodoValidationError` if
+    /// `blocker_id` is `id`, is done, or already waits on `id`.
+    fn block(
+        &self,
+        py: Python<'_>,
+        id: String,
+        blocker_id: String,
+    ) -> PyResult<Vec<PyTodoEvent>> {
+        let (_, events) =
+            py.detach(|| block_on(self.dependency_service.block(id, blocker_id)))?;
+
+        Ok(events.into_iter().map(|e| e.into()).collect())
+    }
+
+    /// Stops the stored todo `id` from waiting on `blocker_id`, returning the unblocked
+    /// events; none when it did not wait on it
+    ///
+    /// Raises `TodoNotFoundError` if there is no todo `id`.
+    fn unblock(
+        &self,
+        py: Python<'_>,
+        id: String,
+        blocker_id: String,
+    ) -> PyResult<Vec<PyTodoEvent>> {
+        let (_, events) =
+            py.detach(|| block_on(self.dependency_service.unblock(id, blocker_id)))?;
+
+        Ok(events.into_iter().map(|e| e.into()).collect())
+    }
+
+    /// Unblocks the stored todos waiting on the done todo `id`, such as one finished with
+    /// `PyTodo.update_state` and saved, returning their unblocked events
+    ///
+    /// Raises `TodoValidationError` if the todo is not done.
+    fn release(&self, py: Python<'_>, id: String) -> PyResult<Vec<PyTodoEvent>> {
+        let events = py.detach(|| block_on(self.dependency_service.release(id)))?;
+
+        Ok(events.into_iter().map(|e| e.into()).collect())
+    }
+
+    /// Returns up to `limit` recorded events, 100 when omitted, after `cursor`, or from the
+    /// oldest when omitted
+    ///
+    /// Pass the returned page's `cursor` to the next call to resume where it left off,
+    /// also after a restart when events are recorded to `events_path`. Raises
+    /// `ValueError` if the cursor is invalid.
+    #[pyo3(signature = (cursor=None, limit=None))]
+    fn poll_events(
+        &self,
+        py: Python<'_>,
+        cursor: Option<&str>,
+        limit: Option<usize>,
+    ) -> PyResult<PyEventPage> {
+        let limit = limit.unwrap_or(DEFAULT_POLL_LIMIT);
+        let cursor = cursor
+            .map(EventCursor::parse)
+            .transpose()
+            .map_err(PyValueError::new_err)?;
+        let page = py.detach(|| {
+            block_on(
+                self.poll_events_handler
+                    .poll_events(cursor.as_ref(), limit),
+            )
+        })?;
+
+        Ok(page.into())
+    }
+}
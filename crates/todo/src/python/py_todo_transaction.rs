@@ -0,0 +1,92 @@
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use futures::executor::block_on;
+use pyo3::prelude::*;
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+use crate::infrastructure::event_stores::InMemoryEventStore;
+use crate::infrastructure::repositories::todo::TransactionalTodoRepository;
+use crate::{EventStore, TodoError, TodoEvent};
+use super::PyTodoService;
+
+/// Python context manager for a Unit of Work
+///
+/// Entering returns a `PyTodoService` whose changes are staged; leaving the block
+/// commits them, or rolls them back if the block raised.
+#[gen_stub_pyclass]
+#[pyclass(module = "py_todo")]
+pub struct PyTodoTransaction {
+    unit_of_work: Arc<TransactionalTodoRepository>,
+    service: Py<PyTodoService>,
+    staged_events: Arc<InMemoryEventStore>,
+    event_store: Arc<dyn EventStore>,
+}
+
+impl PyTodoTransaction {
+    /// Creates a new transaction over `unit_of_work`, exposed through `service`
+    ///
+    /// `service` records its events in `staged_events`, which are moved to `event_store`
+    /// on commit.
+    pub fn new(
+        unit_of_work: Arc<TransactionalTodoRepository>,
+        service: Py<PyTodoService>,
+        staged_events: Arc<InMemoryEventStore>,
+        event_store: Arc<dyn EventStore>,
+    ) -> Self {
+        PyTodoTransaction {
+            unit_of_work,
+            service,
+            staged_events,
+            event_store,
+        }
+    }
+
+    /// Removes and returns the staged events
+    fn take_staged_events(&self) -> Result<Vec<TodoEvent>, TodoError> {
+        let events = self.staged_events.find_all()?;
+        self.staged_events.compact(DateTime::<Utc>::MAX_UTC)?;
+        Ok(events)
+    }
+}
+
+#[gen_stub_pymethods]
+#[pymethods]
+impl PyTodoTransaction {
+    /// Returns the service operating inside this transaction
+    fn __enter__(&self, py: Python<'_>) -> Py<PyTodoService> {
+        self.service.clone_ref(py)
+    }
+
+    /// Commits when the block succeeded, rolls back when it raised
+    ///
+    /// Never suppresses the exception raised by the block.
+    fn __exit__(
+        &self,
+        py: Python<'_>,
+        exc_type: Option<Py<PyAny>>,
+        _exc_value: Option<Py<PyAny>>,
+        _traceback: Option<Py<PyAny>>,
+    ) -> PyResult<bool> {
+        if exc_type.is_some() {
+            self.rollback()?;
+        } else {
+            self.commit(py)?;
+        }
+        Ok(false)
+    }
+
+    /// Applies the staged changes to the service's repository and records their events
+    fn commit(&self, py: Python<'_>) -> PyResult<()> {
+        py.detach(|| {
+            block_on(self.unit_of_work.commit())?;
+            self.event_store.record(&self.take_staged_events()?)
+        })?;
+        Ok(())
+    }
+
+    /// Discards the staged changes and their events
+    fn rollback(&self) -> PyResult<()> {
+        self.unit_of_work.rollback()?;
+        self.take_staged_events()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,145 @@
+mod common;
+
+use std::sync::Arc;
+use todo::TenantId;
+use todo::application::access_control::AccessControl;
+use todo::application::auth::{AuthContext, AuthError};
+use todo::domain::access::{AccessPolicy, Membership, MembershipRepository, Role, TodoAction};
+use todo::infrastructure::repositories::membership::InMemoryMembershipRepository;
+
+fn caller(subject: &str, tenant: Option<&str>) -> AuthContext {
+    AuthContext {
+        subject: subject.to_string(),
+        tenant: tenant.map(|tenant| TenantId::new(tenant).unwrap()),
+        scopes: Vec::new(),
+    }
+}
+
+#[test]
+fn test_default_policy_grants_more_to_each_higher_role() {
+    let policy = AccessPolicy::default();
+    let actions = [
+        TodoAction::View,
+        TodoAction::Add,
+        TodoAction::ChangeState,
+        TodoAction::Delete,
+        TodoAction::ManageWebhooks,
+    ];
+
+    let permitted = |role| {
+        actions
+            .iter()
+            .filter(|action| policy.permits(role, **action))
+            .count()
+    };
+
+    assert_eq!(permitted(Role::Viewer), 1);
+    assert_eq!(permitted(Role::Editor), 3);
+    assert!(!policy.permits(Role::Editor, TodoAction::Delete));
+    assert_eq!(permitted(Role::Owner), 5);
+}
+
+#[tokio::test]
+async fn test_access_control_checks_the_role_on_the_callers_list() {
+    // Arrange
+    let repository = Arc::new(InMemoryMembershipRepository::new());
+    let team = TenantId::new("team").unwrap();
+    let default = TenantId::new("default").unwrap();
+    repository
+        .save(&Membership::new(team.clone(), "alice", Role::Viewer))
+        .await
+        .unwrap();
+    repository
+        .save(&Membership::new(default, "alice", Role::Owner))
+        .await
+        .unwrap();
+    let access = AccessControl::new(repository);
+
+    // Act
+    let viewing = access
+        .authorize(&caller("alice", Some("team")), TodoAction::View)
+        .await;
+    let adding = access
+        .authorize(&caller("alice", Some("team")), TodoAction::Add)
+        .await;
+    let outsider = access
+        .authorize(&caller("bob", Some("team")), TodoAction::View)
+        .await;
+    let without_tenant = access
+        .authorize(&caller("alice", None), TodoAction::Delete)
+        .await;
+
+    // Assert
+    assert_eq!(viewing, Ok(Role::Viewer));
+    assert_eq!(
+        adding,
+        Err(AuthError::Forbidden(
+            "role viewer does not permit add on list team".to_string()
+        ))
+    );
+    assert_eq!(
+        outsider,
+        Err(AuthError::Forbidden("no role on list team".to_string()))
+    );
+    assert_eq!(without_tenant, Ok(Role::Owner));
+}
+
+#[tokio::test]
+async fn test_access_control_applies_a_stricter_policy() {
+    // Arrange
+    let repository = Arc::new(InMemoryMembershipRepository::new());
+    let team = TenantId::new("team").unwrap();
+    repository
+        .save(&Membership::new(team, "alice", Role::Editor))
+        .await
+        .unwrap();
+    let policy = AccessPolicy::default().deny(Role::Editor, TodoAction::ChangeState);
+    let access = AccessControl::new(repository).with_policy(policy);
+
+    // Act
+    let adding = access
+        .authorize(&caller("alice", Some("team")), TodoAction::Add)
+        .await;
+    let changing = access
+        .authorize(&caller("alice", Some("team")), TodoAction::ChangeState)
+        .await;
+
+    // Assert
+    assert_eq!(adding, Ok(Role::Editor));
+    assert!(matches!(changing, Err(AuthError::Forbidden(_))));
+}
+
+#[cfg(feature = "serde")]
+#[tokio::test]
+async fn test_file_repository_replaces_and_removes_memberships() {
+    use todo::domain::access::AccessError;
+    use todo::infrastructure::repositories::membership::FileMembershipRepository;
+
+    // Arrange
+    let path = common::temp_path("memberships.json");
+    let team = TenantId::new("team").unwrap();
+    let repository = FileMembershipRepository::new(&path);
+    repository
+        .save(&Membership::new(team.clone(), "alice", Role::Viewer))
+        .await
+        .unwrap();
+    repository
+        .save(&Membership::new(team.clone(), "bob", Role::Editor))
+        .await
+        .unwrap();
+
+    // Act
+    repository
+        .save(&Membership::new(team.clone(), "alice", Role::Owner))
+        .await
+        .unwrap();
+    repository.remove(&team, "bob").await.unwrap();
+    let removed_again = repository.remove(&team, "bob").await;
+    let reopened = FileMembershipRepository::new(&path);
+
+    // Assert
+    let members = reopened.find_by_list(&team).await.unwrap();
+    assert_eq!(members, vec![Membership::new(team, "alice", Role::Owner)]);
+    assert_eq!(removed_again, Err(AccessError::MembershipNotFound));
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,202 @@
+use chrono::{DateTime, TimeZone, Utc};
+use todo::application::activity_feed::{ActivityFeed, ActivityFilter, ActivityPage};
+use todo::{EventSink, TenantId, TodoEvent, TodoState};
+
+fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 1, 2, hour, minute, 0).unwrap()
+}
+
+fn created(id: &str, description: &str, created_at: DateTime<Utc>) -> TodoEvent {
+    TodoEvent::TodoCreated {
+        id: id.into(),
+        description: description.into(),
+        created_at,
+    }
+}
+
+fn changed(
+    id: &str,
+    from_state: TodoState,
+    to_state: TodoState,
+    changed_at: DateTime<Utc>,
+) -> TodoEvent {
+    TodoEvent::TodoStateChanged {
+        id: id.into(),
+        from_state,
+        to_state,
+        changed_at,
+    }
+}
+
+#[test]
+fn test_activity_feed_pages_through_recorded_events() {
+    // Arrange
+    let feed = ActivityFeed::new();
+    feed.record(&[created("1", "Write docs", at(9, 0))])
+        .unwrap();
+    feed.record(&[created("2", "Buy milk", at(9, 5))]).unwrap();
+    feed.record(&[changed(
+        "1",
+        TodoState::Todo,
+        TodoState::InProgress,
+        at(10, 0),
+    )])
+    .unwrap();
+    feed.record(&[changed(
+        "1",
+        TodoState::InProgress,
+        TodoState::Done,
+        at(11, 0),
+    )])
+    .unwrap();
+
+    // Act
+    let first = feed.page(None, 3);
+    let second = feed.page(first.next_cursor, 3);
+
+    // Assert
+    let sequences = |page: &ActivityPage| -> Vec<u64> {
+        page.activities
+            .iter()
+            .map(|activity| activity.sequence)
+            .collect()
+    };
+    assert_eq!(sequences(&first), vec![4, 3, 2]);
+    assert_eq!(first.next_cursor, Some(2));
+    assert_eq!(sequences(&second), vec![1]);
+    assert_eq!(second.next_cursor, None);
+    assert_eq!(first.activities[0].todo_id(), "1");
+    // The state change is named after the todo's creation
+    assert_eq!(&*first.activities[0].description, "Write docs");
+    assert_eq!(first.activities[0].occurred_at(), at(11, 0));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_activity_feed_describes_activities_relative_to_now() {
+    use chrono::Duration;
+    use todo::application::messages::MessageCatalog;
+
+    // Arrange
+    let catalog = MessageCatalog::default();
+    let feed = ActivityFeed::new();
+    feed.record(&[
+        created("1", "Write docs", at(9, 0)),
+        changed("1", TodoState::Todo, TodoState::InProgress, at(10, 0)),
+        changed("1", TodoState::InProgress, TodoState::Done, at(11, 0)),
+        // Created before the feed existed
+        changed("7", TodoState::Todo, TodoState::InProgress, at(12, 59)),
+    ])
+    .unwrap();
+    let now = at(13, 0);
+
+    // Act
+    let page = feed.page(None, 10);
+    let describe = |index: usize, locale: &str, now: DateTime<Utc>| {
+        page.activities[index].describe(&catalog, locale, now)
+    };
+
+    // Assert
+    assert_eq!(describe(0, "en", now), "Started \"7\" a minute ago");
+    assert_eq!(
+        describe(1, "en", now),
+        "Completed \"Write docs\" 2 hours ago"
+    );
+    assert_eq!(describe(2, "en", now), "Started \"Write docs\" 3 hours ago");
+    assert_eq!(describe(3, "en", now), "Created \"Write docs\" 4 hours ago");
+    assert_eq!(
+        describe(1, "vi", now),
+        "Đã hoàn thành \"Write docs\" 2 giờ trước"
+    );
+    assert_eq!(
+        describe(1, "en", at(11, 0)),
+        "Completed \"Write docs\" just now"
+    );
+    assert_eq!(
+        describe(1, "en", at(11, 45)),
+        "Completed \"Write docs\" 45 minutes ago"
+    );
+    assert_eq!(
+        describe(1, "en", at(11, 0) + Duration::days(1)),
+        "Completed \"Write docs\" yesterday"
+    );
+    assert_eq!(
+        describe(1, "en", at(11, 0) + Duration::days(3)),
+        "Completed \"Write docs\" 3 days ago"
+    );
+}
+
+#[test]
+fn test_activity_feed_keeps_the_last_activities() {
+    let feed = ActivityFeed::with_retention(2);
+
+    for minute in 0..5 {
+        feed.record(&[created(&minute.to_string(), "Todo", at(9, minute))])
+            .unwrap();
+    }
+    let page = feed.page(None, 10);
+
+    let ids: Vec<&str> = page
+        .activities
+        .iter()
+        .map(|activity| activity.todo_id())
+        .collect();
+    assert_eq!(ids, vec!["4", "3"]);
+    assert_eq!(page.next_cursor, None);
+}
+
+#[test]
+fn test_activity_feed_pages_through_one_list_or_actor() {
+    // Arrange
+    let feed = ActivityFeed::new();
+    let team = TenantId::new("team").unwrap();
+    let other = TenantId::new("other").unwrap();
+    feed.record_change(
+        Some(&team),
+        Some("alice"),
+        &[created("1", "Write docs", at(9, 0))],
+    )
+    .unwrap();
+    feed.record_change(
+        Some(&other),
+        Some("bob"),
+        &[created("1", "Buy milk", at(9, 5))],
+    )
+    .unwrap();
+    feed.record_change(
+        Some(&team),
+        Some("carol"),
+        &[changed("1", TodoState::Todo, TodoState::Done, at(10, 0))],
+    )
+    .unwrap();
+
+    // Act
+    let of_list = |list: &TenantId| ActivityFilter {
+        list: Some(list.clone()),
+        actor: None,
+    };
+    let team_page = feed.page_where(&of_list(&team), None, 10);
+    let other_page = feed.page_where(&of_list(&other), None, 10);
+    let carol = ActivityFilter {
+        list: Some(team.clone()),
+        actor: Some("carol".into()),
+    };
+    let carol_page = feed.page_where(&carol, None, 10);
+    let forgotten = feed.forget(Some(&other), "1");
+
+    // Assert
+    let sequences = |page: &ActivityPage| -> Vec<u64> {
+        page.activities
+            .iter()
+            .map(|activity| activity.sequence)
+            .collect()
+    };
+    assert_eq!(sequences(&team_page), vec![3, 1]);
+    // Each list names its own todo "1"
+    assert_eq!(&*team_page.activities[0].description, "Write docs");
+    assert_eq!(sequences(&other_page), vec![2]);
+    assert_eq!(sequences(&carol_page), vec![3]);
+    assert_eq!(carol_page.activities[0].actor.as_deref(), Some("carol"));
+    assert_eq!(forgotten, 1);
+    assert_eq!(sequences(&feed.page(None, 10)), vec![3, 1]);
+}
@@ -1,12 +1,13 @@
+use std::sync::Arc;
 use todo::application::add_todo_handler::AddTodoHandler;
 use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
-use todo::{TodoError, TodoEvent};
+use todo::{TodoError, TodoEvent, TodoRepository};
 
 #[tokio::test]
 async fn test_add_todo_success() {
     // Arrange
-    let repository = Box::new(InMemoryTodoRepository::new()) as Box<dyn todo::TodoRepository>;
-    let handler = AddTodoHandler::new(repository);
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let handler = AddTodoHandler::new(repository.clone());
     let description = "Test todo description".to_string();
 
     // Act
@@ -14,7 +15,9 @@ async fn test_add_todo_success() {
 
     // Assert
     assert!(events.is_ok());
-    let events = events.unwrap();
+    let (todo, events) = events.unwrap();
+    let stored = repository.find_by_id(&todo.id).await.unwrap();
+    assert_eq!(stored, Some(todo));
     assert_eq!(events.len(), 1);
     
     match &events[0] {
@@ -24,7 +27,7 @@ async fn test_add_todo_success() {
             created_at,
         } => {
             assert!(!id.is_empty());
-            assert_eq!(event_description, &description);
+            assert_eq!(&**event_description, &description);
             assert!(!created_at.to_string().is_empty());
         }
         _ => panic!("Expected TodoCreated event"),
@@ -34,7 +37,7 @@ async fn test_add_todo_success() {
 #[tokio::test]
 async fn test_add_todo_empty_description_error() {
     // Arrange
-    let repository = Box::new(InMemoryTodoRepository::new()) as Box<dyn todo::TodoRepository>;
+    let repository = InMemoryTodoRepository::new();
     let handler = AddTodoHandler::new(repository);
     let empty_description = "".to_string();
 
@@ -49,7 +52,7 @@ async fn test_add_todo_empty_description_error() {
 #[tokio::test]
 async fn test_add_todo_whitespace_only_description_error() {
     // Arrange
-    let repository = Box::new(InMemoryTodoRepository::new()) as Box<dyn todo::TodoRepository>;
+    let repository = InMemoryTodoRepository::new();
     let handler = AddTodoHandler::new(repository);
     let whitespace_description = "   ".to_string();
 
@@ -0,0 +1,148 @@
+mod common;
+
+use async_trait::async_trait;
+use std::sync::Arc;
+use todo::SequentialIdGenerator;
+use todo::application::api_key_authenticator::ApiKeyAuthenticator;
+use todo::application::auth::{AuthContext, AuthError, Authenticator, READ_SCOPE, WRITE_SCOPE};
+use todo::application::issue_api_key_handler::IssueApiKeyHandler;
+use todo::application::revoke_api_key_handler::RevokeApiKeyHandler;
+use todo::domain::api_key::{ApiKeyError, ApiKeyRepository, ApiKeyScope};
+use todo::infrastructure::repositories::api_key::InMemoryApiKeyRepository;
+
+/// Accepts every token as the subject `jwt`
+struct AcceptAll;
+
+#[async_trait]
+impl Authenticator for AcceptAll {
+    async fn authenticate(&self, _token: &str) -> Result<AuthContext, AuthError> {
+        Ok(AuthContext {
+            subject: "jwt".to_string(),
+            tenant: None,
+            scopes: Vec::new(),
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_issued_keys_authenticate_with_their_scope() {
+    // Arrange
+    let repository: Arc<dyn ApiKeyRepository> = Arc::new(InMemoryApiKeyRepository::new());
+    let issue = IssueApiKeyHandler::new(repository.clone())
+        .with_id_generator(Arc::new(SequentialIdGenerator::new("key-")));
+    let authenticator = ApiKeyAuthenticator::new(repository.clone());
+
+    // Act
+    let reader = issue
+        .issue("dashboard".to_string(), ApiKeyScope::ReadOnly)
+        .await
+        .unwrap();
+    let writer = issue
+        .issue("importer".to_string(), ApiKeyScope::ReadWrite)
+        .await
+        .unwrap();
+
+    // Assert
+    assert!(reader.token.starts_with("hkt_key-1_"));
+    assert!(!reader.key.secret_hash.contains(&reader.token[10..]));
+    let context = authenticator.authenticate(&reader.token).await.unwrap();
+    assert_eq!(context.subject, "api-key:key-1");
+    assert_eq!(context.scopes, vec![READ_SCOPE.to_string()]);
+    assert_eq!(
+        context.require(WRITE_SCOPE),
+        Err(AuthError::InsufficientScope(WRITE_SCOPE.to_string()))
+    );
+    let context = authenticator.authenticate(&writer.token).await.unwrap();
+    assert!(context.require(WRITE_SCOPE).is_ok());
+}
+
+#[tokio::test]
+async fn test_revoked_and_forged_keys_are_rejected() {
+    // Arrange
+    let repository: Arc<dyn ApiKeyRepository> = Arc::new(InMemoryApiKeyRepository::new());
+    let issued = IssueApiKeyHandler::new(repository.clone())
+        .issue("ci".to_string(), ApiKeyScope::ReadWrite)
+        .await
+        .unwrap();
+    let authenticator = ApiKeyAuthenticator::new(repository.clone());
+    let forged = format!("hkt_{}_{}", issued.key.id, "0".repeat(64));
+
+    // Act
+    let revoked = RevokeApiKeyHandler::new(repository.clone())
+        .revoke(issued.key.id.to_string())
+        .await
+        .unwrap();
+
+    // Assert
+    assert!(revoked.is_revoked());
+    for token in [issued.token.as_str(), forged.as_str(), "hkt_", "not-a-key"] {
+        assert!(
+            matches!(
+                authenticator.authenticate(token).await,
+                Err(AuthError::InvalidToken(_))
+            ),
+            "{}",
+            token
+        );
+    }
+    assert_eq!(
+        RevokeApiKeyHandler::new(repository)
+            .revoke("unknown".to_string())
+            .await
+            .unwrap_err(),
+        ApiKeyError::ApiKeyNotFound
+    );
+}
+
+#[tokio::test]
+async fn test_other_tokens_go_to_the_fallback() {
+    let authenticator = ApiKeyAuthenticator::new(
+        Arc::new(InMemoryApiKeyRepository::new()) as Arc<dyn ApiKeyRepository>
+    )
+    .with_fallback(Arc::new(AcceptAll));
+
+    let context = authenticator.authenticate("eyJhbGciOi").await.unwrap();
+
+    assert_eq!(context.subject, "jwt");
+    assert!(
+        authenticator
+            .authenticate("hkt_missing_secret")
+            .await
+            .is_err()
+    );
+}
+
+#[cfg(feature = "serde")]
+#[tokio::test]
+async fn test_file_repository_persists_keys_without_their_secret() {
+    use todo::infrastructure::repositories::api_key::FileApiKeyRepository;
+
+    // Arrange
+    let path = common::temp_path("api-keys.json");
+    let issued = IssueApiKeyHandler::new(FileApiKeyRepository::new(&path))
+        .issue("backup".to_string(), ApiKeyScope::ReadOnly)
+        .await
+        .unwrap();
+
+    // Act
+    let keys = FileApiKeyRepository::new(&path).find_all().await.unwrap();
+    let contents = std::fs::read_to_string(&path).unwrap();
+    let context = ApiKeyAuthenticator::new(FileApiKeyRepository::new(&path))
+        .authenticate(&issued.token)
+        .await;
+
+    // Assert
+    assert_eq!(keys, vec![issued.key.clone()]);
+    let (_, secret) = issued.token.rsplit_once('_').unwrap();
+    assert!(!contents.contains(secret));
+    assert!(context.is_ok());
+    assert_eq!(
+        IssueApiKeyHandler::new(FileApiKeyRepository::new(&path))
+            .issue(" ".to_string(), ApiKeyScope::ReadOnly)
+            .await
+            .unwrap_err(),
+        ApiKeyError::EmptyName
+    );
+
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,147 @@
+use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+use std::sync::Arc;
+use todo::application::archive::{ArchivePolicy, ArchiveTodosHandler};
+use todo::application::change_todo_state_handler::ChangeTodoStateHandler;
+use todo::infrastructure::event_stores::InMemoryEventStore;
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::{EventStore, FixedClock, TenantId, Todo, TodoEvent, TodoRepository, TodoState};
+
+fn start() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+}
+
+async fn saved_todo(repository: &InMemoryTodoRepository, id: &str, state: TodoState) -> Todo {
+    let (mut todo, _) = Todo::new(format!("Todo {}", id)).unwrap();
+    todo.id = id.into();
+    todo.created_at = start() - TimeDelta::days(60);
+    if state != TodoState::Todo {
+        todo.update_state(TodoState::InProgress).unwrap();
+    }
+    if state == TodoState::Done {
+        todo.update_state(TodoState::Done).unwrap();
+    }
+    repository.save(&todo).await.unwrap();
+    repository.find_by_id(&todo.id).await.unwrap().unwrap()
+}
+
+#[tokio::test]
+async fn test_todos_done_for_long_enough_move_to_the_archive() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let archive = Arc::new(InMemoryTodoRepository::new());
+    let store = Arc::new(InMemoryEventStore::new());
+    let clock = Arc::new(FixedClock::new(start() - TimeDelta::days(20)));
+    let old = saved_todo(&repository, "old", TodoState::InProgress).await;
+    let recent = saved_todo(&repository, "recent", TodoState::InProgress).await;
+    saved_todo(&repository, "open", TodoState::InProgress).await;
+    let change = ChangeTodoStateHandler::new(repository.clone())
+        .with_clock(clock.clone())
+        .with_event_sink(store.clone());
+    change
+        .change_state(old.id.to_string(), TodoState::Done)
+        .await
+        .unwrap();
+    clock.set(start() - TimeDelta::days(3));
+    change
+        .change_state(recent.id.to_string(), TodoState::Done)
+        .await
+        .unwrap();
+    let handler = ArchiveTodosHandler::new(
+        repository.clone(),
+        archive.clone(),
+        ArchivePolicy::new(Some(14)),
+    )
+    .with_event_store(store.clone())
+    .with_clock(Arc::new(FixedClock::new(start())));
+
+    // Act
+    let events = handler.archive().await.unwrap();
+
+    // Assert
+    let archived = TodoEvent::TodoArchived {
+        id: old.id.clone(),
+        archived_at: start(),
+    };
+    assert_eq!(events, vec![archived.clone()]);
+    assert_eq!(repository.find_by_id("old").await.unwrap(), None);
+    let moved = archive.find_by_id("old").await.unwrap().unwrap();
+    assert_eq!(moved.state, TodoState::Done);
+    assert_eq!(moved.description, old.description);
+    assert!(repository.find_by_id("recent").await.unwrap().is_some());
+    assert!(repository.find_by_id("open").await.unwrap().is_some());
+    assert_eq!(store.find_by_todo("old").unwrap().last(), Some(&archived));
+}
+
+#[tokio::test]
+async fn test_lists_override_the_archive_period() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let archive = Arc::new(InMemoryTodoRepository::new());
+    for id in ["default", "acme/kept", "globex/quick"] {
+        saved_todo(&repository, id, TodoState::Done).await;
+    }
+    let policy = ArchivePolicy::new(Some(30))
+        .with_list(TenantId::new("acme").unwrap(), None)
+        .with_list(TenantId::new("globex").unwrap(), Some(1));
+    let handler = ArchiveTodosHandler::new(repository.clone(), archive.clone(), policy)
+        .with_clock(Arc::new(FixedClock::new(start())));
+
+    // Act
+    let events = handler.archive().await.unwrap();
+
+    // Assert
+    let mut ids: Vec<&str> = events.iter().map(TodoEvent::todo_id).collect();
+    ids.sort();
+    assert_eq!(ids, vec!["default", "globex/quick"]);
+    let kept: Vec<Arc<str>> = repository
+        .find_all()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|todo| todo.id)
+        .collect();
+    assert_eq!(kept, vec![Arc::<str>::from("acme/kept")]);
+    assert_eq!(archive.find_all().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_default_policy_archives_nothing() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let archive = Arc::new(InMemoryTodoRepository::new());
+    saved_todo(&repository, "done", TodoState::Done).await;
+    let policy = ArchivePolicy::default();
+    assert!(!policy.is_enabled());
+    assert!(
+        ArchivePolicy::default()
+            .with_list(TenantId::new("acme").unwrap(), Some(7))
+            .is_enabled()
+    );
+    let handler = ArchiveTodosHandler::new(repository.clone(), archive.clone(), policy);
+
+    // Act
+    let events = handler.archive().await.unwrap();
+
+    // Assert
+    assert!(events.is_empty());
+    assert!(archive.find_all().await.unwrap().is_empty());
+    assert_eq!(repository.find_all().await.unwrap().len(), 1);
+}
+
+#[test]
+fn test_replay_skips_archived_events() {
+    // Arrange
+    let (mut todo, mut events) = Todo::new("Write docs".to_string()).unwrap();
+    events.extend(todo.update_state(TodoState::InProgress).unwrap());
+    events.push(TodoEvent::TodoArchived {
+        id: todo.id.clone(),
+        archived_at: start(),
+    });
+
+    // Act
+    let replayed = Todo::replay(&events).unwrap();
+
+    // Assert
+    assert_eq!(replayed.state, TodoState::InProgress);
+    assert_eq!(replayed.id, todo.id);
+}
@@ -0,0 +1,237 @@
+mod common;
+
+use common::{at, saved};
+use std::sync::Arc;
+use todo::application::attachment_service::AttachmentService;
+use todo::domain::blob::{BlobStore, content_hash};
+use todo::infrastructure::blob_stores::InMemoryBlobStore;
+use todo::infrastructure::event_stores::InMemoryEventStore;
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::{Attachment, EventStore, FixedClock, Todo, TodoError, TodoEvent, TodoRepository};
+
+fn receipt() -> Attachment {
+    Attachment {
+        content_hash: content_hash(b"receipt").into(),
+        name: "receipt.pdf".into(),
+        media_type: "application/pdf".into(),
+        size: 7,
+    }
+}
+
+#[test]
+fn test_attach_and_detach_emit_events() {
+    // Arrange
+    let clock = FixedClock::new(at(0));
+    let (mut todo, _) = Todo::new_with_clock("File taxes".to_string(), &clock).unwrap();
+    clock.set(at(1));
+    let hash = receipt().content_hash;
+
+    // Act
+    let attached = todo.attach_with_clock(receipt(), &clock).unwrap();
+    let attached_again = todo.attach_with_clock(receipt(), &clock).unwrap();
+    let attachments = todo.attachments.clone();
+    let detached = todo.detach_with_clock(&hash, &clock);
+    let detached_again = todo.detach_with_clock(&hash, &clock);
+
+    // Assert
+    assert_eq!(attachments, [receipt()]);
+    assert_eq!(
+        attached,
+        [TodoEvent::TodoAttachmentAdded {
+            id: todo.id.clone(),
+            content_hash: hash.clone(),
+            name: "receipt.pdf".into(),
+            media_type: "application/pdf".into(),
+            size: 7,
+            added_at: at(1),
+        }]
+    );
+    assert!(attached_again.is_empty());
+    assert_eq!(
+        detached,
+        [TodoEvent::TodoAttachmentRemoved {
+            id: todo.id.clone(),
+            content_hash: hash,
+            removed_at: at(1),
+        }]
+    );
+    assert!(detached_again.is_empty());
+    assert!(todo.attachments.is_empty());
+}
+
+#[test]
+fn test_attach_rejects_an_empty_name_or_an_invalid_hash() {
+    // Arrange
+    let (mut todo, _) = Todo::new("File taxes".to_string()).unwrap();
+    let unnamed = Attachment {
+        name: " ".into(),
+        ..receipt()
+    };
+    let unhashed = Attachment {
+        content_hash: "../receipt".into(),
+        ..receipt()
+    };
+
+    // Act
+    let unnamed_result = todo.attach(unnamed);
+    let unhashed_result = todo.attach(unhashed);
+
+    // Assert
+    assert!(matches!(unnamed_result, Err(TodoError::ValidationError(_))));
+    assert!(matches!(
+        unhashed_result,
+        Err(TodoError::ValidationError(_))
+    ));
+    assert!(todo.attachments.is_empty());
+}
+
+#[test]
+fn test_replay_restores_the_attachments() {
+    // Arrange
+    let other = Attachment {
+        content_hash: content_hash(b"invoice").into(),
+        name: "invoice.pdf".into(),
+        ..receipt()
+    };
+    let (mut todo, mut events) = Todo::new("File taxes".to_string()).unwrap();
+    events.extend(todo.attach(receipt()).unwrap());
+    events.extend(todo.attach(other.clone()).unwrap());
+    events.extend(todo.detach(&receipt().content_hash));
+
+    // Act
+    let replayed = Todo::replay(&events).unwrap();
+
+    // Assert
+    assert_eq!(replayed.attachments, [other]);
+}
+
+#[tokio::test]
+async fn test_attachment_service_stores_the_content_and_records_the_change() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let blobs = Arc::new(InMemoryBlobStore::new());
+    let store = Arc::new(InMemoryEventStore::new());
+    let taxes = saved(&repository, "File taxes").await;
+    let service = AttachmentService::new(repository.clone(), blobs.clone())
+        .with_clock(Arc::new(FixedClock::new(at(1))))
+        .with_event_sink(store.clone());
+
+    // Act
+    let (attached, events) = service
+        .attach(
+            taxes.id.to_string(),
+            "receipt.pdf".to_string(),
+            "application/pdf".to_string(),
+            b"receipt".to_vec(),
+        )
+        .await
+        .unwrap();
+    let (_, unchanged) = service
+        .attach(
+            taxes.id.to_string(),
+            "copy.pdf".to_string(),
+            "application/pdf".to_string(),
+            b"receipt".to_vec(),
+        )
+        .await
+        .unwrap();
+    let opened = service
+        .open(taxes.id.to_string(), receipt().content_hash.to_string())
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(attached.attachments, [receipt()]);
+    assert!(unchanged.is_empty());
+    let found = repository.find_by_id(&taxes.id).await.unwrap().unwrap();
+    assert_eq!(found, attached);
+    assert_eq!(store.find_by_todo(&taxes.id).unwrap(), events);
+    assert_eq!(opened, (receipt(), b"receipt".to_vec()));
+    assert_eq!(
+        blobs.get(&receipt().content_hash).await.unwrap(),
+        Some(b"receipt".to_vec())
+    );
+}
+
+#[tokio::test]
+async fn test_attachment_service_keeps_content_another_todo_refers_to() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let blobs = Arc::new(InMemoryBlobStore::new());
+    let taxes = saved(&repository, "File taxes").await;
+    let expenses = saved(&repository, "Claim expenses").await;
+    let service = AttachmentService::new(repository.clone(), blobs.clone());
+    for todo in [&taxes, &expenses] {
+        service
+            .attach(
+                todo.id.to_string(),
+                "receipt.pdf".to_string(),
+                "application/pdf".to_string(),
+                b"receipt".to_vec(),
+            )
+            .await
+            .unwrap();
+    }
+    let hash = receipt().content_hash.to_string();
+
+    // Act
+    let (detached, events) = service
+        .detach(taxes.id.to_string(), hash.clone())
+        .await
+        .unwrap();
+    let shared = blobs.get(&hash).await.unwrap();
+    service
+        .detach(expenses.id.to_string(), hash.clone())
+        .await
+        .unwrap();
+    let unshared = blobs.get(&hash).await.unwrap();
+
+    // Assert
+    assert!(detached.attachments.is_empty());
+    assert_eq!(events.len(), 1);
+    assert_eq!(shared, Some(b"receipt".to_vec()));
+    assert_eq!(unshared, None);
+}
+
+#[tokio::test]
+async fn test_attachment_service_rejects_unknown_todos_and_attachments() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let taxes = saved(&repository, "File taxes").await;
+    let service = AttachmentService::new(repository.clone(), InMemoryBlobStore::new());
+    let hash = receipt().content_hash.to_string();
+
+    // Act
+    let missing_todo = service
+        .attach(
+            "missing".to_string(),
+            "receipt.pdf".to_string(),
+            "application/pdf".to_string(),
+            b"receipt".to_vec(),
+        )
+        .await;
+    let missing_attachment = service.open(taxes.id.to_string(), hash.clone()).await;
+    let (_, nothing_detached) = service.detach(taxes.id.to_string(), hash).await.unwrap();
+
+    // Assert
+    assert!(matches!(missing_todo, Err(TodoError::TodoNotFound)));
+    assert!(matches!(
+        missing_attachment,
+        Err(TodoError::ValidationError(_))
+    ));
+    assert!(nothing_detached.is_empty());
+}
+
+#[cfg(feature = "serde")]
+#[tokio::test]
+async fn test_file_repository_persists_the_attachments() {
+    // Arrange
+    let (mut todo, _) = Todo::new("File taxes".to_string()).unwrap();
+    todo.attach(receipt()).unwrap();
+
+    // Act
+    let found = common::reloaded_from_file(&todo).await;
+
+    // Assert
+    assert_eq!(found.attachments, todo.attachments);
+}
@@ -0,0 +1,277 @@
+#![cfg(feature = "avro")]
+
+use todo::domain::blob::content_hash;
+use todo::infrastructure::serialization::SerializationError;
+use todo::infrastructure::serialization::avro::{
+    decode_confluent, decode_event, encode_confluent, encode_event,
+};
+use todo::{Attachment, Priority, Todo, TodoEvent, TodoState};
+
+fn truncate_to_micros(event: TodoEvent) -> TodoEvent {
+    let micros = |at: chrono::DateTime<chrono::Utc>| {
+        chrono::DateTime::from_timestamp_micros(at.timestamp_micros()).unwrap()
+    };
+    match event {
+        TodoEvent::TodoCreated {
+            id,
+            description,
+            created_at,
+        } => TodoEvent::TodoCreated {
+            id,
+            description,
+            created_at: micros(created_at),
+        },
+        TodoEvent::TodoStateChanged {
+            id,
+            from_state,
+            to_state,
+            changed_at,
+        } => TodoEvent::TodoStateChanged {
+            id,
+            from_state,
+            to_state,
+            changed_at: micros(changed_at),
+        },
+        TodoEvent::TodoArchived { id, archived_at } => TodoEvent::TodoArchived {
+            id,
+            archived_at: micros(archived_at),
+        },
+        TodoEvent::TodoStale {
+            id,
+            rule,
+            state,
+            stale_at,
+        } => TodoEvent::TodoStale {
+            id,
+            rule,
+            state,
+            stale_at: micros(stale_at),
+        },
+        TodoEvent::TodoDueDateChanged {
+            id,
+            due_at,
+            changed_at,
+        } => TodoEvent::TodoDueDateChanged {
+            id,
+            due_at: due_at.map(micros),
+            changed_at: micros(changed_at),
+        },
+        TodoEvent::TodoPriorityChanged {
+            id,
+            from_priority,
+            to_priority,
+            changed_at,
+        } => TodoEvent::TodoPriorityChanged {
+            id,
+            from_priority,
+            to_priority,
+            changed_at: micros(changed_at),
+        },
+        TodoEvent::TodoTagAdded { id, tag, added_at } => TodoEvent::TodoTagAdded {
+            id,
+            tag,
+            added_at: micros(added_at),
+        },
+        TodoEvent::TodoTagRemoved {
+            id,
+            tag,
+            removed_at,
+        } => TodoEvent::TodoTagRemoved {
+            id,
+            tag,
+            removed_at: micros(removed_at),
+        },
+        TodoEvent::TodoSubtaskAdded {
+            id,
+            subtask_id,
+            description,
+            added_at,
+        } => TodoEvent::TodoSubtaskAdded {
+            id,
+            subtask_id,
+            description,
+            added_at: micros(added_at),
+        },
+        TodoEvent::TodoSubtaskToggled {
+            id,
+            subtask_id,
+            done,
+            toggled_at,
+        } => TodoEvent::TodoSubtaskToggled {
+            id,
+            subtask_id,
+            done,
+            toggled_at: micros(toggled_at),
+        },
+        TodoEvent::TodoSubtaskRemoved {
+            id,
+            subtask_id,
+            removed_at,
+        } => TodoEvent::TodoSubtaskRemoved {
+            id,
+            subtask_id,
+            removed_at: micros(removed_at),
+        },
+        TodoEvent::TodoDescriptionUpdated {
+            id,
+            old,
+            new,
+            updated_at,
+        } => TodoEvent::TodoDescriptionUpdated {
+            id,
+            old,
+            new,
+            updated_at: micros(updated_at),
+        },
+        TodoEvent::TodoBlocked {
+            id,
+            blocker_id,
+            blocked_at,
+        } => TodoEvent::TodoBlocked {
+            id,
+            blocker_id,
+            blocked_at: micros(blocked_at),
+        },
+        TodoEvent::TodoUnblocked {
+            id,
+            blocker_id,
+            unblocked_at,
+        } => TodoEvent::TodoUnblocked {
+            id,
+            blocker_id,
+            unblocked_at: micros(unblocked_at),
+        },
+        TodoEvent::TodoDeleted { id, deleted_at } => TodoEvent::TodoDeleted {
+            id,
+            deleted_at: micros(deleted_at),
+        },
+        TodoEvent::TodoRestored { id, restored_at } => TodoEvent::TodoRestored {
+            id,
+            restored_at: micros(restored_at),
+        },
+        TodoEvent::TodoAttachmentAdded {
+            id,
+            content_hash,
+            name,
+            media_type,
+            size,
+            added_at,
+        } => TodoEvent::TodoAttachmentAdded {
+            id,
+            content_hash,
+            name,
+            media_type,
+            size,
+            added_at: micros(added_at),
+        },
+        TodoEvent::TodoAttachmentRemoved {
+            id,
+            content_hash,
+            removed_at,
+        } => TodoEvent::TodoAttachmentRemoved {
+            id,
+            content_hash,
+            removed_at: micros(removed_at),
+        },
+    }
+}
+
+#[test]
+fn test_events_round_trip_through_avro() {
+    // Arrange
+    let (mut todo, created) = Todo::new("Write docs".to_string()).unwrap();
+    let changed = todo.update_state(TodoState::InProgress).unwrap();
+    let archived = TodoEvent::TodoArchived {
+        id: todo.id.clone(),
+        archived_at: chrono::Utc::now(),
+    };
+    let stale = TodoEvent::TodoStale {
+        id: todo.id.clone(),
+        rule: "stuck".into(),
+        state: TodoState::InProgress,
+        stale_at: chrono::Utc::now(),
+    };
+    let due = todo
+        .set_due_date(todo.created_at + chrono::Duration::days(1))
+        .unwrap();
+    let cleared = todo.clear_due_date().unwrap();
+    let prioritized = todo.set_priority(Priority::High);
+    let tagged = todo.add_tag("work").unwrap();
+    let untagged = todo.remove_tag("work").unwrap();
+    let added = todo.add_subtask("Outline".to_string()).unwrap();
+    let subtask_id = todo.subtasks[0].id.clone();
+    let toggled = todo.toggle_subtask(&subtask_id).unwrap();
+    let removed = todo.remove_subtask(&subtask_id);
+    let renamed = todo
+        .update_description("Write the docs".to_string())
+        .unwrap();
+    let blocked = todo.block_by("blocker").unwrap();
+    let unblocked = todo.unblock("blocker");
+    let attached = todo
+        .attach(Attachment {
+            content_hash: content_hash(b"outline").into(),
+            name: "outline.md".into(),
+            media_type: "text/markdown".into(),
+            size: 7,
+        })
+        .unwrap();
+    let detached = todo.detach(&content_hash(b"outline"));
+    let deleted = TodoEvent::TodoDeleted {
+        id: todo.id.clone(),
+        deleted_at: chrono::Utc::now(),
+    };
+    let restored = TodoEvent::TodoRestored {
+        id: todo.id.clone(),
+        restored_at: chrono::Utc::now(),
+    };
+    let events = vec![
+        created[0].clone(),
+        changed[0].clone(),
+        archived,
+        stale,
+        due[0].clone(),
+        cleared[0].clone(),
+        prioritized[0].clone(),
+        tagged[0].clone(),
+        untagged[0].clone(),
+        added[0].clone(),
+        toggled[0].clone(),
+        removed[0].clone(),
+        renamed[0].clone(),
+        blocked[0].clone(),
+        unblocked[0].clone(),
+        deleted,
+        restored,
+        attached[0].clone(),
+        detached[0].clone(),
+    ];
+
+    // Act
+    let decoded: Vec<_> = events
+        .iter()
+        .map(|event| decode_event(&encode_event(event).unwrap()).unwrap())
+        .collect();
+
+    // Assert
+    let expected: Vec<_> = events.into_iter().map(truncate_to_micros).collect();
+    assert_eq!(decoded, expected);
+}
+
+#[test]
+fn test_confluent_framing_carries_the_schema_id() {
+    // Arrange
+    let (_, created) = Todo::new("Write docs".to_string()).unwrap();
+
+    // Act
+    let bytes = encode_confluent(42, &created[0]).unwrap();
+    let (schema_id, decoded) = decode_confluent(&bytes).unwrap();
+
+    // Assert
+    assert_eq!(&bytes[..5], &[0, 0, 0, 0, 42]);
+    assert_eq!(schema_id, 42);
+    assert_eq!(decoded, truncate_to_micros(created[0].clone()));
+    assert!(matches!(
+        decode_confluent(&bytes[1..]),
+        Err(SerializationError::Malformed(_))
+    ));
+}
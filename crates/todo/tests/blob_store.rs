@@ -0,0 +1,154 @@
+mod common;
+
+use todo::TodoError;
+use todo::domain::blob::{BlobStore, content_hash, is_content_hash};
+use todo::infrastructure::blob_stores::{FileBlobStore, InMemoryBlobStore};
+
+const HELLO_HASH: &str = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+async fn round_trip(store: &dyn BlobStore) {
+    // Act
+    let hash = store.put(b"hello").await.unwrap();
+    let again = store.put(b"hello").await.unwrap();
+    let content = store.get(&hash).await.unwrap();
+    store.delete(&hash).await.unwrap();
+    let deleted = store.get(&hash).await.unwrap();
+    let deleted_again = store.delete(&hash).await;
+    let invalid = store.get("../../etc/passwd").await;
+
+    // Assert
+    assert_eq!(hash, HELLO_HASH);
+    assert_eq!(again, hash);
+    assert_eq!(content.as_deref(), Some(&b"hello"[..]));
+    assert_eq!(deleted, None);
+    assert!(deleted_again.is_ok());
+    assert!(matches!(invalid, Err(TodoError::RepositoryError(_))));
+}
+
+#[test]
+fn test_content_hash_is_lowercase_hex_sha256() {
+    assert_eq!(content_hash(b"hello"), HELLO_HASH);
+    assert!(is_content_hash(HELLO_HASH));
+    assert!(!is_content_hash(&HELLO_HASH.to_uppercase()));
+    assert!(!is_content_hash(&HELLO_HASH[1..]));
+}
+
+#[tokio::test]
+async fn test_inmemory_blob_store_keeps_content_by_hash() {
+    round_trip(&InMemoryBlobStore::new()).await;
+}
+
+#[tokio::test]
+async fn test_file_blob_store_keeps_content_by_hash() {
+    let dir = common::temp_path("blobs");
+    let store = FileBlobStore::new(&dir);
+
+    round_trip(&store).await;
+    let hash = store.put(b"kept").await.unwrap();
+
+    assert_eq!(
+        std::fs::read(dir.join(&hash[..2]).join(&hash)).unwrap(),
+        b"kept"
+    );
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[cfg(feature = "s3")]
+mod s3 {
+    use super::HELLO_HASH;
+    use chrono::{TimeZone, Utc};
+    use std::sync::Arc;
+    use todo::domain::blob::BlobStore;
+    use todo::infrastructure::blob_stores::S3BlobStore;
+    use todo::{FixedClock, TodoError};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tokio::task::JoinHandle;
+
+    /// Answers one request with `status` and `body`, and returns its head and body
+    async fn start_server(
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, JoinHandle<(String, Vec<u8>)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let request = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut data = Vec::new();
+            let mut buffer = [0; 4096];
+            let (head, length) = loop {
+                let read = stream.read(&mut buffer).await.unwrap();
+                data.extend_from_slice(&buffer[..read]);
+                let text = String::from_utf8_lossy(&data).to_string();
+                if let Some(end) = text.find("\r\n\r\n") {
+                    let head = text[..end].to_lowercase();
+                    let length: usize = head
+                        .lines()
+                        .find_map(|line| line.strip_prefix("content-length: "))
+                        .map_or(0, |length| length.parse().unwrap());
+                    break (head, end + 4 + length);
+                }
+            };
+            while data.len() < length {
+                let read = stream.read(&mut buffer).await.unwrap();
+                data.extend_from_slice(&buffer[..read]);
+            }
+            let response = format!(
+                "HTTP/1.1 {}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+            let content = data[head.len() + 4..length].to_vec();
+            (head, content)
+        });
+        (endpoint, request)
+    }
+
+    fn store(endpoint: &str) -> S3BlobStore {
+        let clock = FixedClock::new(Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap());
+        S3BlobStore::new("eu-west-1", "todos", "AKIDEXAMPLE", "secret")
+            .with_endpoint(endpoint)
+            .with_prefix("attachments/")
+            .with_clock(Arc::new(clock))
+    }
+
+    #[tokio::test]
+    async fn test_s3_blob_store_puts_a_signed_object_named_by_its_hash() {
+        // Arrange
+        let (endpoint, request) = start_server("200 OK", "").await;
+
+        // Act
+        let hash = store(&endpoint).put(b"hello").await.unwrap();
+        let (head, content) = request.await.unwrap();
+
+        // Assert
+        assert_eq!(hash, HELLO_HASH);
+        assert!(head.starts_with(&format!("put /todos/attachments/{} http/1.1", HELLO_HASH)));
+        assert!(head.contains(&format!("x-amz-content-sha256: {}", HELLO_HASH)));
+        assert!(head.contains(
+            "authorization: aws4-hmac-sha256 credential=akidexample/20240102/eu-west-1/s3/aws4_request, signedheaders=host;x-amz-content-sha256;x-amz-date, signature="
+        ));
+        assert_eq!(content, b"hello");
+    }
+
+    #[tokio::test]
+    async fn test_s3_blob_store_gets_objects_and_treats_missing_ones_as_none() {
+        let (endpoint, _request) = start_server("200 OK", "hello").await;
+        let found = store(&endpoint).get(HELLO_HASH).await.unwrap();
+        let (endpoint, _request) = start_server("404 Not Found", "<Error/>").await;
+        let missing = store(&endpoint).get(HELLO_HASH).await.unwrap();
+        let (endpoint, _request) = start_server("403 Forbidden", "denied").await;
+        let refused = store(&endpoint).get(HELLO_HASH).await;
+
+        assert_eq!(found.as_deref(), Some(&b"hello"[..]));
+        assert_eq!(missing, None);
+        assert_eq!(
+            refused,
+            Err(TodoError::RepositoryError(
+                "S3 answered 403 Forbidden: denied".to_string()
+            ))
+        );
+    }
+}
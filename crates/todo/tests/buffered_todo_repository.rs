@@ -0,0 +1,62 @@
+use std::sync::Arc;
+use todo::infrastructure::repositories::todo::{BufferedTodoRepository, InMemoryTodoRepository};
+use todo::{Todo, TodoRepository, TodoState};
+
+#[tokio::test]
+async fn test_buffered_saves_are_coalesced_until_flush() {
+    // Arrange
+    let inner = Arc::new(InMemoryTodoRepository::new());
+    let buffered = BufferedTodoRepository::new(inner.clone(), 100);
+    let (mut todo, _) = Todo::new("Buffered todo".to_string()).unwrap();
+
+    // Act
+    buffered.save(&todo).await.unwrap();
+    todo.update_state(TodoState::InProgress).unwrap();
+    buffered.save(&todo).await.unwrap();
+
+    // Assert
+    assert_eq!(buffered.pending().unwrap(), 1);
+    assert!(inner.find_by_id(&todo.id).await.unwrap().is_none());
+    let read = buffered.find_by_id(&todo.id).await.unwrap().unwrap();
+    assert_eq!(read.state, TodoState::InProgress);
+    assert_eq!(buffered.find_all().await.unwrap().len(), 1);
+
+    buffered.flush().await.unwrap();
+    assert_eq!(buffered.pending().unwrap(), 0);
+    let flushed = inner.find_by_id(&todo.id).await.unwrap().unwrap();
+    assert_eq!(flushed.state, TodoState::InProgress);
+}
+
+#[tokio::test]
+async fn test_buffer_flushes_at_capacity() {
+    // Arrange
+    let inner = Arc::new(InMemoryTodoRepository::new());
+    let buffered = BufferedTodoRepository::new(inner.clone(), 2);
+    let (first, _) = Todo::new("First todo".to_string()).unwrap();
+    let (second, _) = Todo::new("Second todo".to_string()).unwrap();
+
+    // Act
+    buffered.save(&first).await.unwrap();
+    buffered.save(&second).await.unwrap();
+
+    // Assert
+    assert_eq!(buffered.pending().unwrap(), 0);
+    assert_eq!(inner.find_all().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_delete_drops_buffered_save() {
+    // Arrange
+    let inner = Arc::new(InMemoryTodoRepository::new());
+    let buffered = BufferedTodoRepository::new(inner.clone(), 100);
+    let (todo, _) = Todo::new("Deleted todo".to_string()).unwrap();
+    buffered.save(&todo).await.unwrap();
+
+    // Act
+    buffered.delete(&todo.id).await.unwrap();
+    buffered.flush().await.unwrap();
+
+    // Assert
+    assert!(buffered.find_by_id(&todo.id).await.unwrap().is_none());
+    assert!(inner.find_all().await.unwrap().is_empty());
+}
@@ -0,0 +1,48 @@
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use todo::infrastructure::repositories::todo::{CachedTodoRepository, InMemoryTodoRepository};
+use todo::{Todo, TodoRepository, TodoState};
+
+#[tokio::test]
+async fn test_cache_serves_stale_todos_until_revalidated() {
+    // Arrange
+    let remote = Arc::new(InMemoryTodoRepository::new());
+    let (mut todo, _) = Todo::new("Cached todo".to_string()).unwrap();
+    remote.save(&todo).await.unwrap();
+    let cached = CachedTodoRepository::new(remote.clone(), NonZeroUsize::new(10).unwrap());
+    cached.find_by_id(&todo.id).await.unwrap();
+
+    // Act
+    let events = todo.update_state(TodoState::InProgress).unwrap();
+    remote.save(&todo).await.unwrap();
+    cached.apply(&events).unwrap();
+
+    // Assert
+    let stale = cached.find_by_id(&todo.id).await.unwrap().unwrap();
+    assert_eq!(stale.state, TodoState::Todo);
+    cached.revalidate().await.unwrap();
+    let fresh = cached.find_by_id(&todo.id).await.unwrap().unwrap();
+    assert_eq!(fresh.state, TodoState::InProgress);
+}
+
+#[tokio::test]
+async fn test_cache_evicts_least_recently_used_and_drops_deleted() {
+    // Arrange
+    let remote = Arc::new(InMemoryTodoRepository::new());
+    let cached = CachedTodoRepository::new(remote.clone(), NonZeroUsize::new(1).unwrap());
+    let (first, _) = Todo::new("First todo".to_string()).unwrap();
+    let (second, _) = Todo::new("Second todo".to_string()).unwrap();
+    cached.save(&first).await.unwrap();
+    cached.save(&second).await.unwrap();
+
+    // Act
+    remote.delete(&first.id).await.unwrap();
+    remote.delete(&second.id).await.unwrap();
+
+    // Assert
+    assert!(cached.find_by_id(&first.id).await.unwrap().is_none());
+    assert!(cached.find_by_id(&second.id).await.unwrap().is_some());
+    cached.invalidate_all().unwrap();
+    cached.revalidate().await.unwrap();
+    assert!(cached.find_by_id(&second.id).await.unwrap().is_none());
+}
@@ -0,0 +1,72 @@
+use chrono::{DateTime, Duration};
+use std::sync::Arc;
+use todo::application::add_todo_handler::AddTodoHandler;
+use todo::application::change_todo_state_handler::ChangeTodoStateHandler;
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::{Clock, FixedClock, SteppingClock, Todo, TodoEvent, TodoRepository, TodoState};
+
+#[test]
+fn test_todo_reads_timestamps_from_the_clock() {
+    // Arrange
+    let created_at = DateTime::parse_from_rfc3339("2024-01-01T09:00:00Z")
+        .unwrap()
+        .to_utc();
+    let changed_at = DateTime::parse_from_rfc3339("2024-01-02T17:30:00Z")
+        .unwrap()
+        .to_utc();
+    let clock = FixedClock::new(created_at);
+
+    // Act
+    let (mut todo, created) = Todo::new_with_clock("Write docs".to_string(), &clock).unwrap();
+    clock.set(changed_at);
+    let changed = todo
+        .update_state_with_clock(TodoState::InProgress, &clock)
+        .unwrap();
+
+    // Assert
+    assert_eq!(todo.created_at, created_at);
+    assert!(matches!(
+        &created[0],
+        TodoEvent::TodoCreated { created_at: at, .. } if *at == created_at
+    ));
+    assert!(matches!(
+        &changed[0],
+        TodoEvent::TodoStateChanged { changed_at: at, .. } if *at == changed_at
+    ));
+}
+
+#[tokio::test]
+async fn test_handlers_stamp_events_with_their_clock() {
+    // Arrange
+    let start = DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z")
+        .unwrap()
+        .to_utc();
+    let clock: Arc<dyn Clock> = Arc::new(SteppingClock::new(start, Duration::seconds(1)));
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let add = AddTodoHandler::new(repository.clone()).with_clock(clock.clone());
+    let change = ChangeTodoStateHandler::new(repository.clone()).with_clock(clock);
+
+    // Act
+    let (_, created) = add.new_todo("Replayed todo".to_string()).await.unwrap();
+    let TodoEvent::TodoCreated { id, .. } = &created[0] else {
+        panic!("expected TodoCreated");
+    };
+    let (_, started) = change
+        .change_state(id.to_string(), TodoState::InProgress)
+        .await
+        .unwrap();
+    let (_, finished) = change
+        .change_state(id.to_string(), TodoState::Done)
+        .await
+        .unwrap();
+
+    // Assert
+    let todo = repository.find_by_id(id).await.unwrap().unwrap();
+    assert_eq!(todo.created_at, start);
+    let changed_at = |events: &[TodoEvent]| match &events[0] {
+        TodoEvent::TodoStateChanged { changed_at, .. } => *changed_at,
+        other => panic!("expected TodoStateChanged, got {:?}", other),
+    };
+    assert_eq!(changed_at(&started), start + Duration::seconds(1));
+    assert_eq!(changed_at(&finished), start + Duration::seconds(2));
+}
@@ -0,0 +1,51 @@
+#![cfg(feature = "serde")]
+
+use serde_json::json;
+use todo::infrastructure::serialization::cloudevents::CloudEvent;
+use todo::{Todo, TodoEvent, TodoState};
+
+#[test]
+fn test_todo_events_convert_to_cloudevents_json() {
+    // Arrange
+    let (mut todo, _) = Todo::new("Write docs".to_string()).unwrap();
+    let changed = todo.update_state(TodoState::InProgress).unwrap();
+
+    // Act
+    let event = CloudEvent::from_todo_event(&changed[0], "/hk-todo/server").with_id("42");
+    let value = serde_json::to_value(&event).unwrap();
+    let restored =
+        TodoEvent::try_from(serde_json::from_value::<CloudEvent>(value.clone()).unwrap());
+
+    // Assert
+    let TodoEvent::TodoStateChanged { changed_at, .. } = &changed[0] else {
+        unreachable!()
+    };
+    assert_eq!(
+        value,
+        json!({
+            "specversion": "1.0",
+            "id": "42",
+            "source": "/hk-todo/server",
+            "type": "com.hungknow.todo.state_changed",
+            "subject": todo.id,
+            "time": changed_at.to_rfc3339(),
+            "datacontenttype": "application/json",
+            "data": serde_json::to_value(&changed[0]).unwrap(),
+        })
+    );
+    assert_eq!(restored.unwrap(), changed[0]);
+}
+
+#[test]
+fn test_foreign_cloudevents_are_rejected() {
+    let event = serde_json::from_value::<CloudEvent>(json!({
+        "specversion": "1.0",
+        "id": "1",
+        "source": "/other",
+        "type": "com.example.order.created",
+        "data": {}
+    }))
+    .unwrap();
+
+    assert!(TodoEvent::try_from(event).is_err());
+}
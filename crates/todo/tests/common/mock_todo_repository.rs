@@ -9,7 +9,7 @@ pub struct MockTodoRepository {
 
 impl MockTodoRepository {
     /// Creates a new MockTodoRepository
-    /// 
+    ///
     /// # Parameters
     /// - `should_return_none`: If `true`, `find_by_id` will return `None`. If `false`, it will return a test todo.
     pub fn new(should_return_none: bool) -> Self {
@@ -43,4 +43,3 @@ impl TodoRepository for MockTodoRepository {
         Ok(())
     }
 }
-
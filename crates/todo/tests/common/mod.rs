@@ -1,3 +1,43 @@
+// Each test crate uses only some of the helpers
+#![allow(dead_code, unused_imports)]
+
 pub mod mock_todo_repository;
 
-pub use mock_todo_repository::MockTodoRepository;
\ No newline at end of file
+pub use mock_todo_repository::MockTodoRepository;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use std::path::PathBuf;
+use todo::{Todo, TodoRepository};
+
+/// `hour` hours after 09:00 UTC on 2025-01-01
+pub fn at(hour: i64) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 1, 1, 9, 0, 0).unwrap() + Duration::hours(hour)
+}
+
+/// A path in the temp directory no other test uses, ending in `file_name`
+pub fn temp_path(file_name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("{}-{}", uuid::Uuid::new_v4(), file_name))
+}
+
+/// Saves a new todo with `description` to `repository`
+pub async fn saved(repository: &impl TodoRepository, description: &str) -> Todo {
+    let (todo, _) = Todo::new(description.to_string()).unwrap();
+    repository.save(&todo).await.unwrap();
+    todo
+}
+
+/// Saves `todo` with one `FileTodoRepository` and reads it back with another on the same file
+#[cfg(feature = "serde")]
+pub async fn reloaded_from_file(todo: &Todo) -> Todo {
+    use todo::infrastructure::repositories::todo::FileTodoRepository;
+
+    let path = temp_path("todos.json");
+    FileTodoRepository::new(&path).save(todo).await.unwrap();
+    let found = FileTodoRepository::new(&path)
+        .find_by_id(&todo.id)
+        .await
+        .unwrap()
+        .unwrap();
+    std::fs::remove_file(path).unwrap();
+    found
+}
@@ -0,0 +1,151 @@
+#![cfg(feature = "config")]
+
+use std::collections::HashMap;
+use todo::TenantId;
+use todo::application::archive::ArchivePolicy;
+use todo::application::quota::{Quota, QuotaPolicy};
+use todo::application::retention::{RetentionPolicy, RetentionRules};
+use todo::infrastructure::config::TodoConfig;
+use todo::infrastructure::event_sinks::EventLog;
+use todo::infrastructure::id_format::IdFormat;
+use todo::infrastructure::repositories::todo::TodoBackend;
+
+#[test]
+fn test_environment_overrides_the_file() {
+    // Arrange
+    let file = r#"
+        backend = "file:/var/lib/todos.json"
+        id_format = "uuid7"
+        event_log = "off"
+        event_retention = 4096
+        auth_issuer = "https://id.example.com"
+        auth_audience = "todo-api"
+        api_key_file = "/var/lib/api-keys.json"
+        roles_file = "/var/lib/roles.json"
+        trash_file = "/var/lib/trash.json"
+        archive_file = "/var/lib/archive.json"
+        escalation_rules_file = "/var/lib/escalation-rules.json"
+
+        [retention]
+        schedule = "0 3 * * *"
+        done_todo_days = 30
+
+        [retention.tenants.acme]
+        done_todo_days = 7
+        event_months = 1
+        trash_days = 3
+
+        [archive]
+        schedule = "@weekly"
+        done_days = 14
+
+        [archive.lists.acme]
+
+        [archive.lists.globex]
+        done_days = 2
+
+        [escalation]
+        schedule = "@hourly"
+
+        [quota]
+        max_open_todos = 50
+        max_todos = 500
+
+        [quota.lists.acme]
+        max_todos = 20
+    "#;
+    let env = HashMap::from([
+        ("TODO_ID_FORMAT", "ulid"),
+        ("TODO_METRICS", "false"),
+        ("TODO_AUTH_AUDIENCE", "todo-admin"),
+        ("TODO_RETENTION_EVENT_MONTHS", "6"),
+        ("TODO_RETENTION_TRASH_DAYS", "30"),
+        ("TODO_ARCHIVE_DONE_DAYS", "7"),
+        ("TODO_ESCALATION_SCHEDULE", "0 9 * * *"),
+        ("TODO_QUOTA_MAX_TODOS", "1000"),
+    ]);
+
+    // Act
+    let config = TodoConfig::from_toml(file)
+        .unwrap()
+        .with_env(|name| env.get(name).map(|value| value.to_string()))
+        .unwrap();
+
+    // Assert
+    assert_eq!(
+        config,
+        TodoConfig {
+            backend: Some(TodoBackend::File("/var/lib/todos.json".into())),
+            id_format: IdFormat::Ulid,
+            event_log: Some(EventLog::Off),
+            event_retention: 4096,
+            metrics: false,
+            auth_issuer: Some("https://id.example.com".to_string()),
+            auth_audience: Some("todo-admin".to_string()),
+            api_key_file: Some("/var/lib/api-keys.json".into()),
+            roles_file: Some("/var/lib/roles.json".into()),
+            trash_file: Some("/var/lib/trash.json".into()),
+            retention_schedule: Some("0 3 * * *".to_string()),
+            retention: RetentionPolicy::new(RetentionRules {
+                done_todo_days: Some(30),
+                event_months: Some(6),
+                trash_days: Some(30),
+            })
+            .with_tenant(
+                TenantId::new("acme").unwrap(),
+                RetentionRules {
+                    done_todo_days: Some(7),
+                    event_months: Some(1),
+                    trash_days: Some(3),
+                }
+            ),
+            archive_file: Some("/var/lib/archive.json".into()),
+            archive_schedule: Some("@weekly".to_string()),
+            archive: ArchivePolicy::new(Some(7))
+                .with_list(TenantId::new("acme").unwrap(), None)
+                .with_list(TenantId::new("globex").unwrap(), Some(2)),
+            escalation_rules_file: Some("/var/lib/escalation-rules.json".into()),
+            escalation_schedule: Some("0 9 * * *".to_string()),
+            quota: QuotaPolicy::new(Quota {
+                max_open_todos: Some(50),
+                max_todos: Some(1000),
+            })
+            .with_list(
+                TenantId::new("acme").unwrap(),
+                Quota {
+                    max_open_todos: None,
+                    max_todos: Some(20),
+                }
+            ),
+        }
+    );
+}
+
+#[test]
+fn test_missing_settings_take_their_defaults() {
+    let config = TodoConfig::from_toml("")
+        .unwrap()
+        .with_env(|_| None)
+        .unwrap();
+
+    assert_eq!(config, TodoConfig::default());
+    assert_eq!(config.backend, None);
+    assert_eq!(config.id_format, IdFormat::UuidV4);
+    assert_eq!(config.event_retention, 1024);
+    assert!(config.metrics);
+}
+
+#[test]
+fn test_invalid_settings_are_rejected() {
+    assert!(TodoConfig::from_toml("backend = \"sqlite:todos.db\"").is_err());
+    assert!(TodoConfig::from_toml("event_retention = 0").is_err());
+    assert!(TodoConfig::from_toml("pool_size = 4").is_err());
+    assert!(TodoConfig::from_toml("metrics = \"yes\"").is_err());
+    assert!(TodoConfig::from_toml("auth_issuer = \"https://id.example.com\"").is_err());
+    assert!(TodoConfig::from_toml("[retention.tenants.\"a/b\"]").is_err());
+    assert!(TodoConfig::from_toml("[retention]\ndone_days = 7").is_err());
+    assert!(TodoConfig::from_toml("[archive]\ndone_days = 7").is_err());
+    assert!(TodoConfig::from_toml("[quota]\nmax_done_todos = 7").is_err());
+    let env = |name: &str| (name == "TODO_EVENT_RETENTION").then(|| "many".to_string());
+    assert!(TodoConfig::default().with_env(env).is_err());
+}
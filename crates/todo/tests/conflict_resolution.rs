@@ -0,0 +1,107 @@
+use chrono::{DateTime, TimeZone, Utc};
+use std::sync::Arc;
+use todo::application::resolve_conflict_handler::ResolveConflictHandler;
+use todo::domain::sync::{
+    ConflictResolver, LastWriterWins, ManualQueue, PreferLocal, PreferRemote, Resolution,
+    SyncConflict,
+};
+use todo::infrastructure::repositories::conflict::InMemoryPendingConflictRepository;
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::{FixedClock, Todo, TodoError, TodoRepository, TodoState};
+
+fn at(hour: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 1, 1, hour, 0, 0).unwrap()
+}
+
+/// A todo edited locally and, to `IN_PROGRESS`, remotely
+fn conflict(local_hour: u32, remote_hour: u32) -> SyncConflict {
+    let (local, _) = Todo::new("Shared".to_string()).unwrap();
+    let mut remote = local.clone();
+    remote.update_state(TodoState::InProgress).unwrap();
+    SyncConflict {
+        local,
+        remote,
+        local_modified_at: at(local_hour),
+        remote_modified_at: at(remote_hour),
+    }
+}
+
+#[tokio::test]
+async fn test_strategies_pick_a_side() {
+    let older_local = conflict(1, 2);
+    let newer_local = conflict(3, 2);
+
+    assert_eq!(
+        LastWriterWins.resolve(&older_local).await,
+        Ok(Resolution::Remote)
+    );
+    assert_eq!(
+        LastWriterWins.resolve(&newer_local).await,
+        Ok(Resolution::Local)
+    );
+    assert_eq!(
+        PreferLocal.resolve(&older_local).await,
+        Ok(Resolution::Local)
+    );
+    assert_eq!(
+        PreferRemote.resolve(&newer_local).await,
+        Ok(Resolution::Remote)
+    );
+}
+
+#[tokio::test]
+async fn test_settle_saves_the_remote_todo_when_it_wins() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let conflict = conflict(1, 2);
+    repository.save(&conflict.local).await.unwrap();
+    let handler = ResolveConflictHandler::new(
+        repository.clone(),
+        Arc::new(InMemoryPendingConflictRepository::new()),
+    );
+
+    // Act
+    let resolution = handler.settle(&conflict, &LastWriterWins).await.unwrap();
+
+    // Assert
+    assert_eq!(resolution, Resolution::Remote);
+    let saved = repository.find_by_id(&conflict.local.id).await.unwrap();
+    assert_eq!(saved.unwrap().state, TodoState::InProgress);
+}
+
+#[tokio::test]
+async fn test_manual_queue_defers_conflicts_until_decided() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let pending = Arc::new(InMemoryPendingConflictRepository::new());
+    let queue = ManualQueue::new(pending.clone()).with_clock(Arc::new(FixedClock::new(at(5))));
+    let handler = ResolveConflictHandler::new(repository.clone(), pending);
+    let first = conflict(1, 2);
+    let second = conflict(1, 2);
+    repository.save(&first.local).await.unwrap();
+
+    // Act
+    let deferred = handler.settle(&first, &queue).await.unwrap();
+    handler.settle(&second, &queue).await.unwrap();
+    let listed = handler.pending_conflicts().await.unwrap();
+    let still_pending = handler
+        .decide(first.todo_id(), Resolution::Deferred)
+        .await
+        .unwrap();
+    let kept = handler
+        .decide(first.todo_id(), Resolution::Remote)
+        .await
+        .unwrap();
+    let decided_twice = handler.decide(first.todo_id(), Resolution::Local).await;
+
+    // Assert
+    assert_eq!(deferred, Resolution::Deferred);
+    assert_eq!(listed.len(), 2);
+    assert_eq!(listed[0].detected_at, at(5));
+    assert_eq!(still_pending, first.local);
+    assert_eq!(kept, first.remote);
+    let saved = repository.find_by_id(first.todo_id()).await.unwrap();
+    assert_eq!(saved.unwrap().state, TodoState::InProgress);
+    assert_eq!(decided_twice, Err(TodoError::TodoNotFound));
+    assert_eq!(handler.pending_conflicts().await.unwrap().len(), 1);
+}
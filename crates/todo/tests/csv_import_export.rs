@@ -0,0 +1,94 @@
+#![cfg(feature = "csv")]
+
+use std::sync::Arc;
+use todo::application::export_todos_handler::ExportTodosHandler;
+use todo::application::import_todos_handler::ImportTodosHandler;
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::infrastructure::serialization::SerializationError;
+use todo::infrastructure::serialization::csv::{CsvColumn, CsvOptions, import_todos};
+use todo::{Todo, TodoRepository, TodoState};
+
+#[tokio::test]
+async fn test_csv_round_trip_through_handlers() {
+    // Arrange
+    let source = Arc::new(InMemoryTodoRepository::new());
+    let (mut todo, _) = Todo::new("Buy \"milk\", eggs".to_string()).unwrap();
+    todo.update_state(TodoState::InProgress).unwrap();
+    source.save(&todo).await.unwrap();
+    let target = Arc::new(InMemoryTodoRepository::new());
+    let options = CsvOptions::default();
+    let mut document = Vec::new();
+
+    // Act
+    ExportTodosHandler::new(source)
+        .export_csv(&mut document, &options)
+        .await
+        .unwrap();
+    let (events, errors) = ImportTodosHandler::new(target.clone())
+        .import_csv(document.as_slice(), &options)
+        .await
+        .unwrap();
+
+    // Assert
+    assert!(
+        String::from_utf8(document)
+            .unwrap()
+            .starts_with("id,description,state,created_at\n")
+    );
+    assert_eq!(events.len(), 1);
+    assert!(errors.is_empty());
+    let imported = target.find_by_id(&todo.id).await.unwrap().unwrap();
+    assert_eq!(&*imported.description, "Buy \"milk\", eggs");
+    assert_eq!(imported.state, TodoState::InProgress);
+    assert_eq!(imported.created_at, todo.created_at);
+}
+
+#[test]
+fn test_csv_import_maps_headers_and_reports_invalid_rows() {
+    // Arrange
+    let options = CsvOptions {
+        columns: vec![
+            (CsvColumn::Description, "Task".to_string()),
+            (CsvColumn::State, "Status".to_string()),
+            (CsvColumn::CreatedAt, "Added".to_string()),
+        ],
+        date_format: Some("%d/%m/%Y".to_string()),
+        delimiter: b';',
+    };
+    let document = "Task;Status;Added;Notes\n\
+                    Write docs;in progress;02/01/2024;ignored\n\
+                    ;DONE;;\n\
+                    Buy milk;blocked;;\n\
+                    Call Bob;;31/02/2024;\n\
+                    Water plants;;;\n";
+
+    // Act
+    let import = import_todos(document.as_bytes(), &options).unwrap();
+
+    // Assert
+    let descriptions: Vec<&str> = import
+        .todos
+        .iter()
+        .map(|todo| &*todo.description)
+        .collect();
+    assert_eq!(descriptions, vec!["Write docs", "Water plants"]);
+    assert_eq!(import.todos[0].state, TodoState::InProgress);
+    assert_eq!(
+        import.todos[0].created_at.to_rfc3339(),
+        "2024-01-02T00:00:00+00:00"
+    );
+    assert_eq!(import.todos[1].state, TodoState::Todo);
+    let records: Vec<usize> = import
+        .errors
+        .iter()
+        .map(|error| match error {
+            SerializationError::InvalidRecord { record, .. } => *record,
+            other => panic!("unexpected error {}", other),
+        })
+        .collect();
+    assert_eq!(records, vec![3, 4, 5]);
+    assert!(matches!(
+        import_todos("id,state\n1,TODO\n".as_bytes(), &options),
+        Err(SerializationError::Malformed(_))
+    ));
+}
@@ -0,0 +1,35 @@
+mod common;
+
+use common::saved;
+use std::sync::Arc;
+use todo::application::delete_todo_handler::DeleteTodoHandler;
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::{TodoError, TodoRepository};
+
+#[tokio::test]
+async fn test_delete_todo_success() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let todo = saved(&repository, "Test todo").await;
+    let handler = DeleteTodoHandler::new(repository.clone());
+
+    // Act
+    let result = handler.delete_todo(todo.id.to_string()).await;
+
+    // Assert
+    assert!(result.is_ok());
+    assert!(repository.find_by_id(&todo.id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_delete_todo_not_found_error() {
+    // Arrange
+    let repository = InMemoryTodoRepository::new();
+    let handler = DeleteTodoHandler::new(repository);
+
+    // Act
+    let result = handler.delete_todo("non-existent-id".to_string()).await;
+
+    // Assert
+    assert_eq!(result.unwrap_err(), TodoError::TodoNotFound);
+}
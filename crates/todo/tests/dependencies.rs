@@ -0,0 +1,378 @@
+mod common;
+
+use common::{at, saved};
+use std::sync::Arc;
+use todo::application::change_todo_state_handler::ChangeTodoStateHandler;
+use todo::application::delete_todo_handler::DeleteTodoHandler;
+use todo::application::dependency_service::DependencyService;
+use todo::infrastructure::event_stores::InMemoryEventStore;
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::{EventStore, FixedClock, Todo, TodoError, TodoEvent, TodoRepository, TodoState};
+
+#[test]
+fn test_block_and_unblock_emit_events() {
+    // Arrange
+    let clock = FixedClock::new(at(0));
+    let (mut todo, _) = Todo::new_with_clock("Publish docs".to_string(), &clock).unwrap();
+    clock.set(at(1));
+
+    // Act
+    let blocked = todo.block_by_with_clock("review", &clock).unwrap();
+    let blocked_again = todo.block_by_with_clock("review", &clock).unwrap();
+    let blocked_by = todo.blocked_by.clone();
+    let unblocked = todo.unblock_with_clock("review", &clock);
+    let unblocked_again = todo.unblock_with_clock("review", &clock);
+
+    // Assert
+    assert_eq!(blocked_by, [Arc::from("review")]);
+    assert_eq!(
+        blocked,
+        [TodoEvent::TodoBlocked {
+            id: todo.id.clone(),
+            blocker_id: "review".into(),
+            blocked_at: at(1),
+        }]
+    );
+    assert!(blocked_again.is_empty());
+    assert_eq!(
+        unblocked,
+        [TodoEvent::TodoUnblocked {
+            id: todo.id.clone(),
+            blocker_id: "review".into(),
+            unblocked_at: at(1),
+        }]
+    );
+    assert!(unblocked_again.is_empty());
+    assert!(todo.blocked_by.is_empty());
+}
+
+#[test]
+fn test_a_todo_cannot_block_itself() {
+    // Arrange
+    let (mut todo, _) = Todo::new("Publish docs".to_string()).unwrap();
+    let id = todo.id.clone();
+
+    // Act
+    let result = todo.block_by(&id);
+
+    // Assert
+    assert!(matches!(result, Err(TodoError::ValidationError(_))));
+    assert!(todo.blocked_by.is_empty());
+}
+
+#[test]
+fn test_a_blocked_todo_can_only_move_back_to_todo() {
+    // Arrange
+    let (mut todo, _) = Todo::new("Publish docs".to_string()).unwrap();
+    todo.update_state(TodoState::InProgress).unwrap();
+    todo.block_by("review").unwrap();
+
+    // Act
+    let finished = todo.update_state(TodoState::Done);
+    let next = todo.change_to_next_state();
+    let reopened = todo.update_state(TodoState::Todo);
+    let started = todo.update_state(TodoState::InProgress);
+    todo.unblock("review");
+    let started_unblocked = todo.update_state(TodoState::InProgress);
+
+    // Assert
+    assert!(matches!(finished, Err(TodoError::ValidationError(_))));
+    assert!(matches!(next, Err(TodoError::ValidationError(_))));
+    assert!(reopened.is_ok());
+    assert!(matches!(started, Err(TodoError::ValidationError(_))));
+    assert!(started_unblocked.is_ok());
+    assert_eq!(todo.state, TodoState::InProgress);
+}
+
+#[test]
+fn test_replay_restores_the_blockers() {
+    // Arrange
+    let (mut todo, mut events) = Todo::new("Publish docs".to_string()).unwrap();
+    events.extend(todo.block_by("review").unwrap());
+    events.extend(todo.block_by("translate").unwrap());
+    events.extend(todo.unblock("review"));
+
+    // Act
+    let replayed = Todo::replay(&events).unwrap();
+
+    // Assert
+    assert_eq!(replayed.blocked_by, [Arc::from("translate")]);
+}
+
+#[tokio::test]
+async fn test_dependency_service_blocks_and_records_the_change() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let store = Arc::new(InMemoryEventStore::new());
+    let publish = saved(&repository, "Publish docs").await;
+    let review = saved(&repository, "Review docs").await;
+    let service = DependencyService::new(repository.clone())
+        .with_clock(Arc::new(FixedClock::new(at(1))))
+        .with_event_sink(store.clone());
+
+    // Act
+    let (blocked, events) = service
+        .block(publish.id.to_string(), review.id.to_string())
+        .await
+        .unwrap();
+    let (_, unchanged) = service
+        .block(publish.id.to_string(), review.id.to_string())
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(blocked.blocked_by, vec![review.id]);
+    assert!(unchanged.is_empty());
+    let found = repository.find_by_id(&publish.id).await.unwrap().unwrap();
+    assert_eq!(found, blocked);
+    assert_eq!(store.find_by_todo(&publish.id).unwrap(), events);
+}
+
+#[tokio::test]
+async fn test_dependency_service_rejects_cycles() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let draft = saved(&repository, "Draft docs").await;
+    let review = saved(&repository, "Review docs").await;
+    let publish = saved(&repository, "Publish docs").await;
+    let service = DependencyService::new(repository.clone());
+    service
+        .block(review.id.to_string(), draft.id.to_string())
+        .await
+        .unwrap();
+    service
+        .block(publish.id.to_string(), review.id.to_string())
+        .await
+        .unwrap();
+
+    // Act
+    let direct = service
+        .block(review.id.to_string(), publish.id.to_string())
+        .await;
+    let indirect = service
+        .block(draft.id.to_string(), publish.id.to_string())
+        .await;
+    let itself = service
+        .block(draft.id.to_string(), draft.id.to_string())
+        .await;
+
+    // Assert
+    assert!(matches!(direct, Err(TodoError::ValidationError(_))));
+    assert!(matches!(indirect, Err(TodoError::ValidationError(_))));
+    assert!(matches!(itself, Err(TodoError::ValidationError(_))));
+    let found = repository.find_by_id(&draft.id).await.unwrap().unwrap();
+    assert!(found.blocked_by.is_empty());
+}
+
+#[tokio::test]
+async fn test_dependency_service_rejects_missing_and_done_blockers() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let publish = saved(&repository, "Publish docs").await;
+    let mut review = saved(&repository, "Review docs").await;
+    review.update_state(TodoState::InProgress).unwrap();
+    review.update_state(TodoState::Done).unwrap();
+    repository.save(&review).await.unwrap();
+    let service = DependencyService::new(repository.clone());
+
+    // Act
+    let missing_todo = service
+        .block("missing".to_string(), review.id.to_string())
+        .await;
+    let missing_blocker = service
+        .block(publish.id.to_string(), "missing".to_string())
+        .await;
+    let done_blocker = service
+        .block(publish.id.to_string(), review.id.to_string())
+        .await;
+
+    // Assert
+    assert!(matches!(missing_todo, Err(TodoError::TodoNotFound)));
+    assert!(matches!(missing_blocker, Err(TodoError::TodoNotFound)));
+    assert!(matches!(done_blocker, Err(TodoError::ValidationError(_))));
+}
+
+#[tokio::test]
+async fn test_dependency_service_releases_the_todos_waiting_on_a_done_blocker() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let store = Arc::new(InMemoryEventStore::new());
+    let mut review = saved(&repository, "Review docs").await;
+    let publish = saved(&repository, "Publish docs").await;
+    let announce = saved(&repository, "Announce docs").await;
+    let service = DependencyService::new(repository.clone()).with_event_sink(store.clone());
+    for dependent in [&publish, &announce] {
+        service
+            .block(dependent.id.to_string(), review.id.to_string())
+            .await
+            .unwrap();
+    }
+
+    // Act
+    let early = service.release(review.id.to_string()).await;
+    review.update_state(TodoState::InProgress).unwrap();
+    review.update_state(TodoState::Done).unwrap();
+    repository.save(&review).await.unwrap();
+    let released = service.release(review.id.to_string()).await.unwrap();
+
+    // Assert
+    assert!(matches!(early, Err(TodoError::ValidationError(_))));
+    assert_eq!(released.len(), 2);
+    assert!(
+        released
+            .iter()
+            .all(|event| matches!(event, TodoEvent::TodoUnblocked { .. }))
+    );
+    let mut found = repository.find_by_id(&publish.id).await.unwrap().unwrap();
+    assert!(found.blocked_by.is_empty());
+    assert!(found.update_state(TodoState::InProgress).is_ok());
+    assert_eq!(store.find_by_todo(&announce.id).unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_finishing_a_blocker_releases_the_todos_waiting_on_it() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let store = Arc::new(InMemoryEventStore::new());
+    let review = saved(&repository, "Review docs").await;
+    let publish = saved(&repository, "Publish docs").await;
+    let translate = saved(&repository, "Translate docs").await;
+    let service = DependencyService::new(repository.clone());
+    service
+        .block(publish.id.to_string(), review.id.to_string())
+        .await
+        .unwrap();
+    service
+        .block(publish.id.to_string(), translate.id.to_string())
+        .await
+        .unwrap();
+    let handler = ChangeTodoStateHandler::new(repository.clone()).with_event_sink(store.clone());
+    handler
+        .change_state(review.id.to_string(), TodoState::InProgress)
+        .await
+        .unwrap();
+
+    // Act
+    let (_, finished) = handler
+        .change_state(review.id.to_string(), TodoState::Done)
+        .await
+        .unwrap();
+    let blocked = handler
+        .change_state(publish.id.to_string(), TodoState::InProgress)
+        .await;
+
+    // Assert
+    assert_eq!(finished.len(), 2);
+    assert!(matches!(
+        &finished[1],
+        TodoEvent::TodoUnblocked { id, blocker_id, .. }
+            if *id == publish.id && *blocker_id == review.id
+    ));
+    let found = repository.find_by_id(&publish.id).await.unwrap().unwrap();
+    assert_eq!(found.blocked_by, vec![translate.id.clone()]);
+    assert!(matches!(blocked, Err(TodoError::ValidationError(_))));
+    assert_eq!(store.find_by_todo(&publish.id).unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_repository_finds_the_todos_blocked_by_a_todo() {
+    // Arrange
+    let repository = InMemoryTodoRepository::new();
+    let review = saved(&repository, "Review docs").await;
+    let mut publish = saved(&repository, "Publish docs").await;
+    saved(&repository, "Translate docs").await;
+    publish.block_by(&review.id).unwrap();
+    repository.save(&publish).await.unwrap();
+
+    // Act
+    let dependents = repository.find_blocked_by(&review.id).await.unwrap();
+    let none = repository.find_blocked_by(&publish.id).await.unwrap();
+
+    // Assert
+    let ids: Vec<_> = dependents.iter().map(|todo| todo.id.clone()).collect();
+    assert_eq!(ids, vec![publish.id.clone()]);
+    assert!(none.is_empty());
+}
+
+#[tokio::test]
+async fn test_deleting_a_blocker_releases_the_todos_waiting_on_it() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let review = saved(&repository, "Review docs").await;
+    let publish = saved(&repository, "Publish docs").await;
+    DependencyService::new(repository.clone())
+        .block(publish.id.to_string(), review.id.to_string())
+        .await
+        .unwrap();
+    let handler = ChangeTodoStateHandler::new(repository.clone());
+
+    // Act
+    let deleted = DeleteTodoHandler::new(repository.clone())
+        .delete_todo(review.id.to_string())
+        .await
+        .unwrap();
+    handler
+        .change_state(publish.id.to_string(), TodoState::InProgress)
+        .await
+        .unwrap();
+    let (done, _) = handler
+        .change_state(publish.id.to_string(), TodoState::Done)
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(deleted.len(), 2);
+    assert!(matches!(
+        &deleted[1],
+        TodoEvent::TodoUnblocked { id, blocker_id, .. }
+            if *id == publish.id && *blocker_id == review.id
+    ));
+    assert_eq!(done.state, TodoState::Done);
+    assert!(done.blocked_by.is_empty());
+}
+
+#[tokio::test]
+async fn test_reopening_a_blocker_does_not_block_its_released_todos_again() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let review = saved(&repository, "Review docs").await;
+    let publish = saved(&repository, "Publish docs").await;
+    DependencyService::new(repository.clone())
+        .block(publish.id.to_string(), review.id.to_string())
+        .await
+        .unwrap();
+    let handler = ChangeTodoStateHandler::new(repository.clone());
+    for state in [TodoState::InProgress, TodoState::Done] {
+        handler
+            .change_state(review.id.to_string(), state)
+            .await
+            .unwrap();
+    }
+
+    // Act
+    let (reopened, events) = handler
+        .change_state(review.id.to_string(), TodoState::InProgress)
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(reopened.state, TodoState::InProgress);
+    assert_eq!(events.len(), 1);
+    let found = repository.find_by_id(&publish.id).await.unwrap().unwrap();
+    assert!(found.blocked_by.is_empty());
+}
+
+#[cfg(feature = "serde")]
+#[tokio::test]
+async fn test_file_repository_persists_the_blockers() {
+    // Arrange
+    let (mut todo, _) = Todo::new("Publish docs".to_string()).unwrap();
+    todo.block_by("review").unwrap();
+    todo.block_by("translate").unwrap();
+
+    // Act
+    let found = common::reloaded_from_file(&todo).await;
+
+    // Assert
+    assert_eq!(found.blocked_by, todo.blocked_by);
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use common::{at, saved};
+use std::sync::Arc;
+use todo::application::update_todo_description_handler::UpdateTodoDescriptionHandler;
+use todo::infrastructure::event_stores::InMemoryEventStore;
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::{EventStore, FixedClock, Todo, TodoError, TodoEvent, TodoRepository};
+
+#[test]
+fn test_update_description_emits_an_event() {
+    // Arrange
+    let clock = FixedClock::new(at(0));
+    let (mut todo, _) = Todo::new_with_clock("Write docs".to_string(), &clock).unwrap();
+    clock.set(at(1));
+
+    // Act
+    let updated = todo
+        .update_description_with_clock("Write the docs".to_string(), &clock)
+        .unwrap();
+    let unchanged = todo
+        .update_description_with_clock("Write the docs".to_string(), &clock)
+        .unwrap();
+
+    // Assert
+    assert_eq!(&*todo.description, "Write the docs");
+    assert_eq!(
+        updated,
+        [TodoEvent::TodoDescriptionUpdated {
+            id: todo.id.clone(),
+            old: "Write docs".into(),
+            new: "Write the docs".into(),
+            updated_at: at(1),
+        }]
+    );
+    assert!(unchanged.is_empty());
+}
+
+#[test]
+fn test_update_description_rejects_an_empty_description() {
+    // Arrange
+    let (mut todo, _) = Todo::new("Write docs".to_string()).unwrap();
+
+    // Act
+    let empty = todo.update_description(String::new());
+    let blank = todo.update_description("  ".to_string());
+
+    // Assert
+    assert!(matches!(empty, Err(TodoError::EmptyDescription)));
+    assert!(matches!(blank, Err(TodoError::EmptyDescription)));
+    assert_eq!(&*todo.description, "Write docs");
+}
+
+#[test]
+fn test_replay_restores_the_updated_description() {
+    // Arrange
+    let (mut todo, mut events) = Todo::new("Write docs".to_string()).unwrap();
+    events.extend(
+        todo.update_description("Write the docs".to_string())
+            .unwrap(),
+    );
+    events.extend(
+        todo.update_description("Publish the docs".to_string())
+            .unwrap(),
+    );
+
+    // Act
+    let replayed = Todo::replay(&events).unwrap();
+
+    // Assert
+    assert_eq!(&*replayed.description, "Publish the docs");
+}
+
+#[tokio::test]
+async fn test_update_description_handler_saves_and_records_the_change() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let store = Arc::new(InMemoryEventStore::new());
+    let clock = FixedClock::new(at(0));
+    let (todo, _) = Todo::new_with_clock("Write docs".to_string(), &clock).unwrap();
+    repository.save(&todo).await.unwrap();
+    let handler = UpdateTodoDescriptionHandler::new(repository.clone())
+        .with_clock(Arc::new(FixedClock::new(at(1))))
+        .with_event_sink(store.clone());
+
+    // Act
+    let (saved, events) = handler
+        .update_description(todo.id.to_string(), "Write the docs".to_string())
+        .await
+        .unwrap();
+    let (_, unchanged) = handler
+        .update_description(todo.id.to_string(), "Write the docs".to_string())
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(&*saved.description, "Write the docs");
+    assert!(unchanged.is_empty());
+    let found = repository.find_by_id(&todo.id).await.unwrap().unwrap();
+    assert_eq!(found, saved);
+    assert_eq!(store.find_by_todo(&todo.id).unwrap(), events);
+}
+
+#[tokio::test]
+async fn test_update_description_handler_fails_for_an_invalid_change() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let todo = saved(&repository, "Write docs").await;
+    let handler = UpdateTodoDescriptionHandler::new(repository.clone());
+
+    // Act
+    let missing = handler
+        .update_description("missing".to_string(), "Write the docs".to_string())
+        .await;
+    let empty = handler
+        .update_description(todo.id.to_string(), " ".to_string())
+        .await;
+
+    // Assert
+    assert!(matches!(missing, Err(TodoError::TodoNotFound)));
+    assert!(matches!(empty, Err(TodoError::EmptyDescription)));
+    let found = repository.find_by_id(&todo.id).await.unwrap().unwrap();
+    assert_eq!(&*found.description, "Write docs");
+}
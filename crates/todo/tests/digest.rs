@@ -0,0 +1,209 @@
+use chrono::{DateTime, TimeZone, Utc};
+use std::sync::Arc;
+use todo::application::add_todo_handler::AddTodoHandler;
+use todo::application::change_todo_state_handler::ChangeTodoStateHandler;
+use todo::application::digest::{Digest, DigestEntry, DigestHandler};
+use todo::domain::notification::NotificationError;
+use todo::infrastructure::event_stores::InMemoryEventStore;
+use todo::infrastructure::mailers::InMemoryMailer;
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::{FixedClock, SequentialIdGenerator, TodoState};
+
+fn day(day: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 1, day, 12, 0, 0).unwrap()
+}
+
+/// A repository and event store filled through the handlers, at chosen days
+///
+/// Ids follow the order the todos are added in, so that todos created on the same day
+/// come out in that order.
+struct Scenario {
+    repository: Arc<InMemoryTodoRepository>,
+    store: Arc<InMemoryEventStore>,
+    clock: Arc<FixedClock>,
+    ids: Arc<SequentialIdGenerator>,
+}
+
+impl Scenario {
+    fn new() -> Self {
+        Scenario {
+            repository: Arc::new(InMemoryTodoRepository::new()),
+            store: Arc::new(InMemoryEventStore::new()),
+            clock: Arc::new(FixedClock::new(day(1))),
+            ids: Arc::new(SequentialIdGenerator::new("todo-")),
+        }
+    }
+
+    /// Adds a todo on day `created` and moves it through `changes`, each on its day
+    async fn todo(&self, description: &str, created: u32, changes: &[(TodoState, u32)]) {
+        self.clock.set(day(created));
+        let (_, events) = AddTodoHandler::new(self.repository.clone())
+            .with_clock(self.clock.clone())
+            .with_event_sink(self.store.clone())
+            .with_id_generator(self.ids.clone())
+            .new_todo(description.to_string())
+            .await
+            .unwrap();
+        let id = events[0].todo_id().to_string();
+        let change = ChangeTodoStateHandler::new(self.repository.clone())
+            .with_clock(self.clock.clone())
+            .with_event_sink(self.store.clone());
+        for (state, on) in changes {
+            self.clock.set(day(*on));
+            change.change_state(id.clone(), *state).await.unwrap();
+        }
+    }
+
+    fn handler(&self) -> DigestHandler<Arc<InMemoryTodoRepository>> {
+        DigestHandler::new(self.repository.clone(), self.store.clone())
+            .with_clock(self.clock.clone())
+    }
+}
+
+fn descriptions(entries: &[DigestEntry]) -> Vec<&str> {
+    entries.iter().map(|entry| &*entry.description).collect()
+}
+
+#[tokio::test]
+async fn test_digest_sorts_todos_by_what_happened_in_the_period() {
+    // Arrange
+    use TodoState::{Done, InProgress};
+    let scenario = Scenario::new();
+    scenario
+        .todo("Finished", 2, &[(InProgress, 3), (Done, 10)])
+        .await;
+    scenario.todo("Waiting", 3, &[]).await;
+    scenario
+        .todo("Done before", 2, &[(InProgress, 4), (Done, 5)])
+        .await;
+    scenario.todo("New", 9, &[(InProgress, 10)]).await;
+    scenario
+        .todo("New and finished", 9, &[(InProgress, 11), (Done, 12)])
+        .await;
+    scenario
+        .todo("Finished later", 2, &[(InProgress, 3), (Done, 16)])
+        .await;
+    scenario.todo("Too new", 16, &[]).await;
+
+    // Act
+    let digest = scenario.handler().digest(day(8), day(15)).await.unwrap();
+
+    // Assert
+    assert_eq!(
+        descriptions(&digest.completed),
+        vec!["Finished", "New and finished"]
+    );
+    assert_eq!(
+        descriptions(&digest.created),
+        vec!["New", "New and finished"]
+    );
+    assert_eq!(
+        descriptions(&digest.carried_over),
+        vec!["Finished later", "Waiting"]
+    );
+    // States are those at the end of the period
+    assert_eq!(digest.carried_over[0].state, InProgress);
+    assert_eq!(digest.summary(), "2 completed, 2 created, 2 carried over");
+}
+
+#[tokio::test]
+async fn test_last_week_ends_now() {
+    // Arrange
+    let scenario = Scenario::new();
+    scenario.todo("Old", 1, &[]).await;
+    scenario.todo("Recent", 10, &[]).await;
+    scenario.clock.set(day(15));
+
+    // Act
+    let digest = scenario.handler().last_week().await.unwrap();
+
+    // Assert
+    assert_eq!(digest.start, day(8));
+    assert_eq!(digest.end, day(15));
+    assert_eq!(descriptions(&digest.created), vec!["Recent"]);
+    assert_eq!(descriptions(&digest.carried_over), vec!["Old"]);
+}
+
+fn sample() -> Digest {
+    let entry = |description: &str| DigestEntry {
+        id: Arc::from("id"),
+        description: Arc::from(description),
+        created_at: day(1),
+        state: TodoState::Todo,
+    };
+    Digest {
+        start: Utc.with_ymd_and_hms(2024, 1, 8, 0, 0, 0).unwrap(),
+        end: Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap(),
+        completed: vec![entry("Ship <b>v1</b> & docs")],
+        created: Vec::new(),
+        carried_over: vec![entry("Write tests")],
+    }
+}
+
+#[test]
+fn test_digest_renders_markdown_and_escaped_html() {
+    let digest = sample();
+
+    assert_eq!(
+        digest.to_markdown(),
+        "# Todo digest 2024-01-08 to 2024-01-14\n\n\
+         1 completed, 0 created, 1 carried over\n\n\
+         ## Completed\n\n\
+         - Ship <b>v1</b> & docs\n\n\
+         ## Carried over\n\n\
+         - Write tests\n"
+    );
+    assert_eq!(
+        digest.to_html(),
+        "<h1>Todo digest 2024-01-08 to 2024-01-14</h1>\n\
+         <p>1 completed, 0 created, 1 carried over</p>\n\
+         <h2>Completed</h2>\n<ul>\n\
+         <li>Ship &lt;b&gt;v1&lt;/b&gt; &amp; docs</li>\n</ul>\n\
+         <h2>Carried over</h2>\n<ul>\n\
+         <li>Write tests</li>\n</ul>\n"
+    );
+}
+
+#[tokio::test]
+async fn test_digest_is_emailed_as_markdown() {
+    // Arrange
+    let scenario = Scenario::new();
+    let mailer = Arc::new(InMemoryMailer::new());
+    let handler = scenario.handler().with_mailer(
+        mailer.clone(),
+        "todo@example.com".to_string(),
+        vec!["alice@example.com".to_string()],
+    );
+    let digest = sample();
+
+    // Act
+    handler.deliver(&digest).await.unwrap();
+
+    // Assert
+    let sent = mailer.sent();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].subject, "Todo digest 2024-01-08 to 2024-01-14");
+    assert_eq!(sent[0].text, digest.to_markdown());
+}
+
+#[tokio::test]
+async fn test_delivery_reports_an_invalid_recipient() {
+    // Arrange
+    let scenario = Scenario::new();
+    let mailer = Arc::new(InMemoryMailer::new());
+    let handler = scenario.handler().with_mailer(
+        mailer.clone(),
+        "todo@example.com".to_string(),
+        vec!["alice".to_string()],
+    );
+
+    // Act
+    let result = handler.deliver(&sample()).await;
+
+    // Assert
+    assert_eq!(
+        result,
+        Err(NotificationError::InvalidAddress("alice".to_string()))
+    );
+    assert!(mailer.sent().is_empty());
+}
@@ -0,0 +1,184 @@
+mod common;
+
+use chrono::{FixedOffset, Utc};
+use common::at;
+use std::sync::Arc;
+use todo::application::change_todo_due_date_handler::ChangeTodoDueDateHandler;
+use todo::application::get_todos_handler::GetTodosHandler;
+use todo::infrastructure::event_stores::InMemoryEventStore;
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::{EventStore, FixedClock, Todo, TodoError, TodoEvent, TodoRepository, TodoState};
+
+#[test]
+fn test_set_and_clear_due_date_emit_events() {
+    // Arrange
+    let clock = FixedClock::new(at(0));
+    let (mut todo, _) = Todo::new_with_clock("Write docs".to_string(), &clock).unwrap();
+    clock.set(at(1));
+
+    // Act
+    let set = todo.set_due_date_with_clock(at(48), &clock).unwrap();
+    let set_again = todo.set_due_date_with_clock(at(48), &clock).unwrap();
+    let due_at = todo.due_at;
+    let cleared = todo.clear_due_date_with_clock(&clock).unwrap();
+    let cleared_again = todo.clear_due_date_with_clock(&clock).unwrap();
+
+    // Assert
+    assert_eq!(due_at, Some(at(48)));
+    assert_eq!(
+        set,
+        [TodoEvent::TodoDueDateChanged {
+            id: todo.id.clone(),
+            due_at: Some(at(48)),
+            changed_at: at(1),
+        }]
+    );
+    assert!(set_again.is_empty());
+    assert_eq!(todo.due_at, None);
+    assert!(matches!(
+        &cleared[..],
+        [TodoEvent::TodoDueDateChanged { due_at: None, .. }]
+    ));
+    assert!(cleared_again.is_empty());
+}
+
+#[test]
+fn test_set_due_date_rejects_a_date_before_creation() {
+    // Arrange
+    let clock = FixedClock::new(at(2));
+    let (mut todo, _) = Todo::new_with_clock("Write docs".to_string(), &clock).unwrap();
+
+    // Act
+    let result = todo.set_due_date(at(1));
+
+    // Assert
+    assert!(matches!(result, Err(TodoError::ValidationError(_))));
+    assert_eq!(todo.due_at, None);
+    assert!(todo.set_due_date(at(2)).is_ok());
+}
+
+#[test]
+fn test_replay_restores_the_due_date() {
+    // Arrange
+    let clock = FixedClock::new(at(0));
+    let (mut todo, mut events) = Todo::new_with_clock("Write docs".to_string(), &clock).unwrap();
+    events.extend(todo.set_due_date_with_clock(at(24), &clock).unwrap());
+    events.extend(todo.set_due_date_with_clock(at(48), &clock).unwrap());
+
+    // Act
+    let replayed = Todo::replay(&events).unwrap();
+
+    // Assert
+    assert_eq!(replayed.due_at, Some(at(48)));
+}
+
+#[tokio::test]
+async fn test_change_due_date_handler_saves_and_records_the_change() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let store = Arc::new(InMemoryEventStore::new());
+    let clock = FixedClock::new(at(0));
+    let (todo, _) = Todo::new_with_clock("Write docs".to_string(), &clock).unwrap();
+    repository.save(&todo).await.unwrap();
+    let handler = ChangeTodoDueDateHandler::new(repository.clone())
+        .with_clock(Arc::new(FixedClock::new(at(1))))
+        .with_event_sink(store.clone());
+
+    // Act
+    let (saved, events) = handler
+        .change_due_date(todo.id.to_string(), Some(at(24)))
+        .await
+        .unwrap();
+    let (_, unchanged) = handler
+        .change_due_date(todo.id.to_string(), Some(at(24)))
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(saved.due_at, Some(at(24)));
+    assert!(unchanged.is_empty());
+    let found = repository.find_by_id(&todo.id).await.unwrap().unwrap();
+    assert_eq!(found, saved);
+    assert_eq!(store.find_by_todo(&todo.id).unwrap(), events);
+}
+
+#[tokio::test]
+async fn test_change_due_date_handler_fails_for_an_invalid_change() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let (todo, _) =
+        Todo::new_with_clock("Write docs".to_string(), &FixedClock::new(at(2))).unwrap();
+    repository.save(&todo).await.unwrap();
+    let handler = ChangeTodoDueDateHandler::new(repository.clone());
+
+    // Act
+    let missing = handler.change_due_date("missing".to_string(), None).await;
+    let too_early = handler
+        .change_due_date(todo.id.to_string(), Some(at(1)))
+        .await;
+
+    // Assert
+    assert!(matches!(missing, Err(TodoError::TodoNotFound)));
+    assert!(matches!(too_early, Err(TodoError::ValidationError(_))));
+    let found = repository.find_by_id(&todo.id).await.unwrap().unwrap();
+    assert_eq!(found.due_at, None);
+}
+
+#[tokio::test]
+async fn test_get_todos_by_due_date_buckets_relative_to_now_and_the_timezone() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let clock = FixedClock::new(at(-10));
+    for (description, due_at, state) in [
+        ("Pay rent", Some(at(-1)), TodoState::Todo),
+        ("File taxes", Some(at(-2)), TodoState::Done),
+        ("Call mum", Some(at(5)), TodoState::Todo),
+        ("Water plants", Some(at(4)), TodoState::InProgress),
+        ("Book flights", Some(at(48)), TodoState::Todo),
+        ("Read a book", None, TodoState::Todo),
+    ] {
+        let (mut todo, _) = Todo::new_with_clock(description.to_string(), &clock).unwrap();
+        if let Some(due_at) = due_at {
+            todo.set_due_date_with_clock(due_at, &clock).unwrap();
+        }
+        todo.state = state;
+        repository.save(&todo).await.unwrap();
+    }
+    let handler = GetTodosHandler::new(repository);
+    let descriptions = |todos: &[Todo]| -> Vec<String> {
+        todos
+            .iter()
+            .map(|todo| todo.description.to_string())
+            .collect()
+    };
+
+    // Act
+    let sydney = handler
+        .get_todos_by_due_date(&FixedOffset::east_opt(10 * 3600).unwrap(), at(0))
+        .await
+        .unwrap();
+    let utc = handler.get_todos_by_due_date(&Utc, at(0)).await.unwrap();
+
+    // Assert
+    assert_eq!(descriptions(&sydney.overdue), ["Pay rent"]);
+    assert_eq!(descriptions(&sydney.today), ["Water plants"]);
+    assert_eq!(descriptions(&sydney.upcoming), ["Call mum", "Book flights"]);
+    assert_eq!(descriptions(&utc.overdue), ["Pay rent"]);
+    assert_eq!(descriptions(&utc.today), ["Water plants", "Call mum"]);
+    assert_eq!(descriptions(&utc.upcoming), ["Book flights"]);
+}
+
+#[cfg(feature = "serde")]
+#[tokio::test]
+async fn test_file_repository_persists_the_due_date() {
+    // Arrange
+    let (mut todo, _) = Todo::new("Write docs".to_string()).unwrap();
+    let due_at = todo.created_at + chrono::Duration::days(2);
+    todo.set_due_date(due_at).unwrap();
+
+    // Act
+    let found = common::reloaded_from_file(&todo).await;
+
+    // Assert
+    assert_eq!(found.due_at, Some(due_at));
+}
@@ -0,0 +1,127 @@
+#![cfg(feature = "encryption")]
+
+use todo::infrastructure::serialization::SerializationError;
+use todo::infrastructure::serialization::encrypted::{
+    MAGIC, decrypt, encrypt, encrypt_with_iterations, is_encrypted,
+};
+
+const DOCUMENT: &[u8] = br#"{"format":"hk-todo","version":1,"todos":[]}"#;
+
+#[test]
+fn test_encrypted_archive_round_trips() {
+    // Act
+    let archive = encrypt(DOCUMENT, "correct horse").unwrap();
+
+    // Assert
+    assert!(is_encrypted(&archive));
+    assert!(!is_encrypted(DOCUMENT));
+    assert!(
+        !archive
+            .windows(b"hk-todo".len())
+            .any(|window| window == b"hk-todo")
+    );
+    assert_eq!(decrypt(&archive, "correct horse").unwrap(), DOCUMENT);
+}
+
+#[test]
+fn test_encrypting_twice_gives_different_archives() {
+    // Act
+    let first = encrypt_with_iterations(DOCUMENT, "secret", 1).unwrap();
+    let second = encrypt_with_iterations(DOCUMENT, "secret", 1).unwrap();
+
+    // Assert
+    assert_ne!(first, second);
+}
+
+#[test]
+fn test_wrong_passphrase_fails_the_integrity_check() {
+    // Arrange
+    let archive = encrypt_with_iterations(DOCUMENT, "secret", 1).unwrap();
+
+    // Act
+    let result = decrypt(&archive, "guess");
+
+    // Assert
+    assert_eq!(result, Err(SerializationError::IntegrityCheckFailed));
+}
+
+#[test]
+fn test_any_altered_byte_fails_the_integrity_check() {
+    // Arrange
+    let archive = encrypt_with_iterations(DOCUMENT, "secret", 1).unwrap();
+
+    // Act & Assert: the header after the magic and version, and the ciphertext and tag
+    for index in MAGIC.len() + 1..archive.len() {
+        if index < MAGIC.len() + 5 {
+            // Changing the iterations derives another key, unless it becomes invalid
+            continue;
+        }
+        let mut altered = archive.clone();
+        altered[index] ^= 0x01;
+        assert_eq!(
+            decrypt(&altered, "secret"),
+            Err(SerializationError::IntegrityCheckFailed),
+            "byte {} was altered",
+            index
+        );
+    }
+}
+
+#[test]
+fn test_altered_iterations_fail_the_integrity_check() {
+    // Arrange
+    let archive = encrypt_with_iterations(DOCUMENT, "secret", 2).unwrap();
+    let mut altered = archive.clone();
+    altered[MAGIC.len() + 4] = 1;
+
+    // Act
+    let result = decrypt(&altered, "secret");
+
+    // Assert
+    assert_eq!(result, Err(SerializationError::IntegrityCheckFailed));
+}
+
+#[test]
+fn test_truncated_archive_is_rejected() {
+    // Arrange
+    let archive = encrypt_with_iterations(DOCUMENT, "secret", 1).unwrap();
+
+    // Act
+    let truncated = decrypt(&archive[..archive.len() - 1], "secret");
+    let header_only = decrypt(&archive[..MAGIC.len() + 2], "secret");
+
+    // Assert
+    assert_eq!(truncated, Err(SerializationError::IntegrityCheckFailed));
+    assert!(matches!(header_only, Err(SerializationError::Malformed(_))));
+}
+
+#[test]
+fn test_newer_version_is_rejected() {
+    // Arrange
+    let mut archive = encrypt_with_iterations(DOCUMENT, "secret", 1).unwrap();
+    archive[MAGIC.len()] = 2;
+
+    // Act
+    let result = decrypt(&archive, "secret");
+
+    // Assert
+    assert_eq!(result, Err(SerializationError::UnsupportedVersion(2)));
+}
+
+#[test]
+fn test_plain_document_is_not_decrypted() {
+    // Act
+    let result = decrypt(DOCUMENT, "secret");
+
+    // Assert
+    assert!(matches!(result, Err(SerializationError::Malformed(_))));
+}
+
+#[test]
+fn test_zero_iterations_are_rejected() {
+    // Act
+    let result = encrypt_with_iterations(DOCUMENT, "secret", 0);
+
+    // Assert
+    assert!(matches!(result, Err(SerializationError::Malformed(_))));
+}
@@ -0,0 +1,112 @@
+use todo::TodoError;
+use todo::application::auth::AuthError;
+use todo::application::error_mapping::ProblemDetails;
+
+#[test]
+fn test_every_error_maps_to_each_transport() {
+    let cases = [
+        (
+            TodoError::EmptyDescription,
+            "EMPTY_DESCRIPTION",
+            422,
+            3,
+            -32001,
+        ),
+        (
+            TodoError::InvalidStateTransition,
+            "INVALID_STATE_TRANSITION",
+            409,
+            9,
+            -32002,
+        ),
+        (TodoError::TodoNotFound, "TODO_NOT_FOUND", 404, 5, -32003),
+        (
+            TodoError::RepositoryError("disk full".to_string()),
+            "REPOSITORY_ERROR",
+            500,
+            13,
+            -32004,
+        ),
+        (TodoError::QuotaExceeded, "QUOTA_EXCEEDED", 409, 8, -32005),
+    ];
+
+    for (err, code, http, grpc, jsonrpc) in cases {
+        assert_eq!(err.code(), code);
+        assert_eq!(err.http_status(), http, "{}", code);
+        assert_eq!(err.grpc_code(), grpc, "{}", code);
+        assert_eq!(err.jsonrpc_code(), jsonrpc, "{}", code);
+    }
+}
+
+#[test]
+fn test_problem_details_describe_the_error() {
+    let problem = TodoError::RepositoryError("disk full".to_string()).problem();
+
+    assert_eq!(
+        problem,
+        ProblemDetails {
+            problem_type: "urn:hk-todo:error:REPOSITORY_ERROR".to_string(),
+            title: "Repository failure".to_string(),
+            status: 500,
+            detail: "repository error: disk full".to_string(),
+            code: "REPOSITORY_ERROR".to_string(),
+        }
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_problem_details_serialize_as_problem_json() {
+    let problem = TodoError::TodoNotFound.problem();
+
+    let value = serde_json::to_value(&problem).unwrap();
+
+    assert_eq!(
+        value,
+        serde_json::json!({
+            "type": "urn:hk-todo:error:TODO_NOT_FOUND",
+            "title": "Todo not found",
+            "status": 404,
+            "detail": "todo not found",
+            "code": "TODO_NOT_FOUND",
+        })
+    );
+}
+
+#[test]
+fn test_auth_errors_map_to_each_transport() {
+    let cases = [
+        (AuthError::MissingCredentials, "MISSING_CREDENTIALS", 401, 16),
+        (
+            AuthError::InvalidToken("expired".to_string()),
+            "INVALID_TOKEN",
+            401,
+            16,
+        ),
+        (
+            AuthError::InsufficientScope("todos:write".to_string()),
+            "INSUFFICIENT_SCOPE",
+            403,
+            7,
+        ),
+        (
+            AuthError::Forbidden("viewers may not add todos".to_string()),
+            "FORBIDDEN",
+            403,
+            7,
+        ),
+        (
+            AuthError::Unavailable("timed out".to_string()),
+            "AUTHENTICATION_UNAVAILABLE",
+            503,
+            14,
+        ),
+    ];
+
+    for (err, code, http, grpc) in cases {
+        assert_eq!(err.code(), code);
+        assert_eq!(err.http_status(), http, "{}", code);
+        assert_eq!(err.grpc_code(), grpc, "{}", code);
+        assert_eq!(err.problem().status, http, "{}", code);
+    }
+}
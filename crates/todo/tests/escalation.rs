@@ -0,0 +1,237 @@
+mod common;
+
+use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+use common::saved;
+use std::sync::Arc;
+use todo::application::change_todo_state_handler::ChangeTodoStateHandler;
+use todo::application::escalation::EscalateTodosHandler;
+use todo::domain::escalation::{EscalationRule, EscalationRuleRepository};
+use todo::infrastructure::event_stores::InMemoryEventStore;
+use todo::infrastructure::repositories::escalation::InMemoryEscalationRuleRepository;
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::{EventStore, FixedClock, Priority, Todo, TodoEvent, TodoRepository, TodoState};
+
+fn start() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+}
+
+async fn saved_todo(repository: &InMemoryTodoRepository, id: &str) -> Todo {
+    let (mut todo, _) = Todo::new(format!("Todo {}", id)).unwrap();
+    todo.id = id.into();
+    todo.created_at = start() - TimeDelta::days(60);
+    repository.save(&todo).await.unwrap();
+    repository.find_by_id(&todo.id).await.unwrap().unwrap()
+}
+
+async fn rules(rules: &[(&str, TodoState, u32)]) -> Arc<InMemoryEscalationRuleRepository> {
+    let repository = Arc::new(InMemoryEscalationRuleRepository::new());
+    for (id, state, after_days) in rules {
+        let rule = EscalationRule::new(*id, *state, *after_days).unwrap();
+        repository.save(&rule).await.unwrap();
+    }
+    repository
+}
+
+/// The `TodoStale` events of `events`
+fn stale_only(events: Vec<TodoEvent>) -> Vec<TodoEvent> {
+    events
+        .into_iter()
+        .filter(|event| matches!(event, TodoEvent::TodoStale { .. }))
+        .collect()
+}
+
+fn stale(id: &str, rule: &str, state: TodoState, stale_at: DateTime<Utc>) -> TodoEvent {
+    TodoEvent::TodoStale {
+        id: id.into(),
+        rule: rule.into(),
+        state,
+        stale_at,
+    }
+}
+
+#[tokio::test]
+async fn test_todos_left_in_a_state_too_long_are_reported_once_per_stay() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let store = Arc::new(InMemoryEventStore::new());
+    let clock = Arc::new(FixedClock::new(start() - TimeDelta::days(10)));
+    saved_todo(&repository, "stuck").await;
+    saved_todo(&repository, "started").await;
+    saved_todo(&repository, "forgotten").await;
+    let change = ChangeTodoStateHandler::new(repository.clone())
+        .with_clock(clock.clone())
+        .with_event_sink(store.clone());
+    change
+        .change_state("stuck".to_string(), TodoState::InProgress)
+        .await
+        .unwrap();
+    clock.set(start() - TimeDelta::days(2));
+    change
+        .change_state("started".to_string(), TodoState::InProgress)
+        .await
+        .unwrap();
+    let rules = rules(&[
+        ("stuck", TodoState::InProgress, 7),
+        ("forgotten", TodoState::Todo, 30),
+    ])
+    .await;
+    let handler = EscalateTodosHandler::new(repository.clone(), rules)
+        .with_event_store(store.clone())
+        .with_clock(clock.clone());
+
+    // Act
+    clock.set(start());
+    let first = stale_only(handler.escalate().await.unwrap());
+    let second = handler.escalate().await.unwrap();
+    clock.set(start() + TimeDelta::hours(1));
+    change
+        .change_state("stuck".to_string(), TodoState::Todo)
+        .await
+        .unwrap();
+    change
+        .change_state("stuck".to_string(), TodoState::InProgress)
+        .await
+        .unwrap();
+    clock.set(start() + TimeDelta::days(8));
+    let mut third = stale_only(handler.escalate().await.unwrap());
+
+    // Assert
+    let mut first_ids: Vec<_> = first.iter().map(TodoEvent::todo_id).collect();
+    first_ids.sort();
+    assert_eq!(first_ids, vec!["forgotten", "stuck"]);
+    assert!(first.contains(&stale("stuck", "stuck", TodoState::InProgress, start())));
+    assert!(second.is_empty());
+    third.sort_by(|a, b| a.todo_id().cmp(b.todo_id()));
+    let later = start() + TimeDelta::days(8);
+    assert_eq!(
+        third,
+        vec![
+            stale("started", "stuck", TodoState::InProgress, later),
+            stale("stuck", "stuck", TodoState::InProgress, later),
+        ]
+    );
+    assert_eq!(
+        store.find_by_todo("forgotten").unwrap(),
+        vec![
+            TodoEvent::TodoPriorityChanged {
+                id: "forgotten".into(),
+                from_priority: Priority::Medium,
+                to_priority: Priority::High,
+                changed_at: start(),
+            },
+            stale("forgotten", "forgotten", TodoState::Todo, start()),
+        ]
+    );
+    let priority = |todo: Option<Todo>| todo.unwrap().priority;
+    assert_eq!(
+        priority(repository.find_by_id("stuck").await.unwrap()),
+        Priority::Urgent
+    );
+    assert_eq!(
+        priority(repository.find_by_id("started").await.unwrap()),
+        Priority::High
+    );
+    assert_eq!(
+        priority(repository.find_by_id("forgotten").await.unwrap()),
+        Priority::High
+    );
+}
+
+#[tokio::test]
+async fn test_escalation_raises_the_priority_once_per_run_up_to_urgent() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let mut urgent = saved_todo(&repository, "urgent").await;
+    urgent.set_priority(Priority::Urgent);
+    repository.save(&urgent).await.unwrap();
+    saved_todo(&repository, "old").await;
+    let rules = rules(&[("week", TodoState::Todo, 7), ("month", TodoState::Todo, 30)]).await;
+    let handler = EscalateTodosHandler::new(repository.clone(), rules)
+        .with_clock(Arc::new(FixedClock::new(start())));
+
+    // Act
+    let events = handler.escalate().await.unwrap();
+
+    // Assert
+    let raised: Vec<_> = events
+        .iter()
+        .filter_map(|event| match event {
+            TodoEvent::TodoPriorityChanged {
+                id, to_priority, ..
+            } => Some((&**id, *to_priority)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(raised, vec![("old", Priority::High)]);
+    assert_eq!(stale_only(events.clone()).len(), 4);
+    let old = repository.find_by_id("old").await.unwrap().unwrap();
+    assert_eq!(old.priority, Priority::High);
+}
+
+#[tokio::test]
+async fn test_escalated_lists_the_todos_breaking_each_rule() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let old = saved_todo(&repository, "old").await;
+    saved(&repository, "Recent").await;
+    let rules = rules(&[
+        ("week", TodoState::Todo, 7),
+        ("month", TodoState::Todo, 30),
+        ("quarter", TodoState::Todo, 90),
+        ("stuck", TodoState::InProgress, 1),
+    ])
+    .await;
+    let handler = EscalateTodosHandler::new(repository.clone(), rules)
+        .with_clock(Arc::new(FixedClock::new(start())));
+
+    // Act
+    let escalated = handler.escalated().await.unwrap();
+
+    // Assert
+    let broken: Vec<_> = escalated
+        .iter()
+        .map(|escalation| (&*escalation.todo.id, &*escalation.rule.id))
+        .collect();
+    assert_eq!(broken, vec![("old", "week"), ("old", "month")]);
+    assert!(
+        escalated
+            .iter()
+            .all(|escalation| escalation.since == old.created_at)
+    );
+}
+
+#[cfg(feature = "serde")]
+#[tokio::test]
+async fn test_rules_are_stored_in_a_file() {
+    use todo::infrastructure::repositories::escalation::FileEscalationRuleRepository;
+
+    // Arrange
+    let path = common::temp_path("escalation-rules.json");
+    let repository = FileEscalationRuleRepository::new(&path);
+    let stuck = EscalationRule::new("stuck", TodoState::InProgress, 7).unwrap();
+    let forgotten = EscalationRule::new("forgotten", TodoState::Todo, 30).unwrap();
+
+    // Act
+    let empty = repository.find_all().await.unwrap();
+    repository.save(&stuck).await.unwrap();
+    repository.save(&forgotten).await.unwrap();
+    let stuck = EscalationRule::new("stuck", TodoState::InProgress, 3).unwrap();
+    repository.save(&stuck).await.unwrap();
+    let removed = repository.remove("forgotten").await.unwrap();
+    let removed_again = repository.remove("forgotten").await.unwrap();
+    let reopened = FileEscalationRuleRepository::new(&path);
+
+    // Assert
+    assert!(empty.is_empty());
+    assert!(removed);
+    assert!(!removed_again);
+    assert_eq!(reopened.find_all().await.unwrap(), vec![stuck]);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_invalid_rules_are_rejected() {
+    assert!(EscalationRule::new("", TodoState::Todo, 7).is_err());
+    assert!(EscalationRule::new("two words", TodoState::Todo, 7).is_err());
+    assert!(EscalationRule::new("never", TodoState::Todo, 0).is_err());
+}
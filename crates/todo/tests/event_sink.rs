@@ -0,0 +1,63 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use std::fs::File;
+use std::sync::Arc;
+use todo::application::add_todo_handler::AddTodoHandler;
+use todo::application::change_todo_state_handler::ChangeTodoStateHandler;
+use todo::infrastructure::event_sinks::{EventLog, JsonEventSink};
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::{TodoEvent, TodoState};
+
+#[tokio::test]
+async fn test_handlers_write_committed_events_as_json_lines() {
+    // Arrange
+    let path = common::temp_path("events.jsonl");
+    let sink = Arc::new(JsonEventSink::new(File::create(&path).unwrap()));
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let add = AddTodoHandler::new(repository.clone()).with_event_sink(sink.clone());
+    let change = ChangeTodoStateHandler::new(repository).with_event_sink(sink);
+
+    // Act
+    let (_, created) = add.new_todo("Logged todo".to_string()).await.unwrap();
+    let TodoEvent::TodoCreated { id, .. } = &created[0] else {
+        panic!("expected TodoCreated");
+    };
+    let (_, changed) = change
+        .change_state(id.to_string(), TodoState::InProgress)
+        .await
+        .unwrap();
+    let rejected = change
+        .change_state(id.to_string(), TodoState::InProgress)
+        .await;
+
+    // Assert
+    assert!(rejected.is_err());
+    let log = std::fs::read_to_string(&path).unwrap();
+    let logged: Vec<TodoEvent> = log
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(logged, [created, changed].concat());
+    assert!(
+        log.lines()
+            .next()
+            .unwrap()
+            .starts_with("{\"type\":\"TODO_CREATED\"")
+    );
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_event_log_parse() {
+    assert_eq!(EventLog::parse("stdout").unwrap(), EventLog::Stdout);
+    assert_eq!(EventLog::parse("off").unwrap(), EventLog::Off);
+    assert_eq!(
+        EventLog::parse("file:events.jsonl").unwrap(),
+        EventLog::File("events.jsonl".into())
+    );
+    assert!(EventLog::parse("kafka:events").is_err());
+    assert!(EventLog::Off.sink().unwrap().is_none());
+}
@@ -0,0 +1,98 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use todo::infrastructure::repositories::todo::FileTodoRepository;
+use todo::{Todo, TodoRepository, TodoState};
+
+#[tokio::test]
+async fn test_file_repository_persists_todos_across_instances() {
+    // Arrange
+    let path = common::temp_path("todos.json");
+    let (mut todo, _) = Todo::new("Test todo".to_string()).unwrap();
+    todo.update_state(TodoState::InProgress).unwrap();
+    FileTodoRepository::new(&path).save(&todo).await.unwrap();
+
+    // Act
+    let found = FileTodoRepository::new(&path)
+        .find_by_id(&todo.id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Assert
+    assert_eq!(found.id, todo.id);
+    assert_eq!(&*found.description, "Test todo");
+    assert_eq!(found.state, TodoState::InProgress);
+    assert_eq!(found.created_at, todo.created_at);
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[tokio::test]
+async fn test_file_repository_updates_and_deletes_todos() {
+    // Arrange
+    let path = common::temp_path("todos.json");
+    let repository = FileTodoRepository::new(&path);
+    let (mut first, _) = Todo::new("First".to_string()).unwrap();
+    let (second, _) = Todo::new("Second".to_string()).unwrap();
+    repository.save(&first).await.unwrap();
+    repository.save(&second).await.unwrap();
+
+    // Act
+    first.update_state(TodoState::InProgress).unwrap();
+    repository.save(&first).await.unwrap();
+    repository.delete(&second.id).await.unwrap();
+
+    // Assert
+    let todos = repository.find_all().await.unwrap();
+    assert_eq!(todos.len(), 1);
+    assert_eq!(todos[0].id, first.id);
+    assert_eq!(todos[0].state, TodoState::InProgress);
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[tokio::test]
+async fn test_file_repository_save_all_inserts_and_replaces() {
+    // Arrange
+    let path = common::temp_path("todos.json");
+    let repository = FileTodoRepository::new(&path);
+    let (mut first, _) = Todo::new("First".to_string()).unwrap();
+    let (second, _) = Todo::new("Second".to_string()).unwrap();
+    repository.save(&first).await.unwrap();
+
+    // Act
+    first.update_state(TodoState::InProgress).unwrap();
+    repository
+        .save_all(&[first.clone(), second.clone()])
+        .await
+        .unwrap();
+
+    // Assert
+    let todos = repository.find_all().await.unwrap();
+    assert_eq!(todos.len(), 2);
+    let saved = repository.find_by_id(&first.id).await.unwrap().unwrap();
+    assert_eq!(saved.state, TodoState::InProgress);
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[tokio::test]
+async fn test_file_repository_missing_file_is_empty() {
+    // Arrange
+    let repository = FileTodoRepository::new(common::temp_path("todos.json"));
+
+    // Act
+    let todos = repository.find_all().await.unwrap();
+
+    // Assert
+    assert!(todos.is_empty());
+    assert!(
+        repository
+            .find_by_id("non-existent-id")
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
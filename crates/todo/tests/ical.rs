@@ -0,0 +1,272 @@
+use todo::infrastructure::serialization::SerializationError;
+use todo::infrastructure::serialization::ical::{export_todos, import_todos, update_vtodo};
+use todo::{Todo, TodoState};
+
+#[test]
+fn test_vtodo_round_trip_escapes_and_folds() {
+    // Arrange
+    let description = format!("Call Bob; bring cake, candles\n{}", "é".repeat(60));
+    let (mut todo, _) = Todo::new(description.clone()).unwrap();
+    todo.update_state(TodoState::InProgress).unwrap();
+    let mut document = Vec::new();
+
+    // Act
+    export_todos(&mut document, &[todo]).unwrap();
+    let imported = import_todos(document.as_slice()).unwrap();
+
+    // Assert
+    let text = String::from_utf8(document).unwrap();
+    assert!(text.contains("STATUS:IN-PROCESS\r\n"));
+    assert!(text.contains(r"SUMMARY:Call Bob\; bring cake\, candles\n"));
+    assert!(text.split("\r\n").all(|line| line.len() <= 75));
+    assert_eq!(imported.len(), 1);
+    assert_eq!(&*imported[0].description, description);
+    assert_eq!(imported[0].state, TodoState::InProgress);
+}
+
+#[test]
+fn test_import_reads_vtodos_from_a_caldav_calendar() {
+    // Arrange
+    let document = "BEGIN:VCALENDAR\r\n\
+                    VERSION:2.0\r\n\
+                    PRODID:-//Nextcloud Tasks v0.16//EN\r\n\
+                    BEGIN:VEVENT\r\n\
+                    UID:event-1\r\n\
+                    SUMMARY:Standup\r\n\
+                    END:VEVENT\r\n\
+                    BEGIN:VTODO\r\n\
+                    UID:task-1\r\n\
+                    CREATED;VALUE=DATE-TIME:20240102T030405Z\r\n\
+                    SUMMARY:Write the quarterly\r\n \u{20}report\r\n\
+                    STATUS:COMPLETED\r\n\
+                    END:VTODO\r\n\
+                    END:VCALENDAR\r\n";
+    let cancelled = document.replace("STATUS:COMPLETED", "STATUS:CANCELLED");
+
+    // Act
+    let todos = import_todos(document.as_bytes()).unwrap();
+
+    // Assert
+    assert_eq!(todos.len(), 1);
+    assert_eq!(&*todos[0].id, "task-1");
+    assert_eq!(&*todos[0].description, "Write the quarterly report");
+    assert_eq!(todos[0].state, TodoState::Done);
+    assert_eq!(
+        todos[0].created_at.to_rfc3339(),
+        "2024-01-02T03:04:05+00:00"
+    );
+    assert!(matches!(
+        import_todos(cancelled.as_bytes()),
+        Err(SerializationError::InvalidRecord { record: 1, .. })
+    ));
+    assert!(matches!(
+        import_todos("not a calendar".as_bytes()),
+        Err(SerializationError::Malformed(_))
+    ));
+}
+
+#[test]
+fn test_update_vtodo_keeps_the_properties_the_todo_does_not_hold() {
+    // Arrange
+    let calendar = "BEGIN:VCALENDAR\r\n\
+                    VERSION:2.0\r\n\
+                    BEGIN:VTODO\r\n\
+                    UID:task-1\r\n\
+                    DTSTAMP:20240102T030405Z\r\n\
+                    SUMMARY:Write docs\r\n\
+                    DESCRIPTION:For the release\r\n\
+                    STATUS:NEEDS-ACTION\r\n\
+                    BEGIN:VALARM\r\n\
+                    ACTION:DISPLAY\r\n\
+                    SUMMARY:Reminder\r\n\
+                    END:VALARM\r\n\
+                    END:VTODO\r\n\
+                    END:VCALENDAR\r\n";
+    let (mut todo, _) = Todo::new("Write the docs".to_string()).unwrap();
+    todo.update_state(TodoState::InProgress).unwrap();
+
+    // Act
+    let updated = update_vtodo(calendar, &todo).unwrap();
+
+    // Assert
+    assert!(updated.contains("SUMMARY:Write the docs\r\n"));
+    assert!(updated.contains("STATUS:IN-PROCESS\r\n"));
+    assert!(!updated.contains("SUMMARY:Write docs\r\n"));
+    assert!(!updated.contains("DTSTAMP:20240102T030405Z"));
+    assert!(updated.contains("DESCRIPTION:For the release\r\n"));
+    assert!(updated.contains("SUMMARY:Reminder\r\n"));
+    let imported = import_todos(updated.as_bytes()).unwrap();
+    assert_eq!(&*imported[0].id, "task-1");
+    assert_eq!(&*imported[0].description, "Write the docs");
+    assert!(matches!(
+        update_vtodo("BEGIN:VCALENDAR\r\nEND:VCALENDAR\r\n", &todo),
+        Err(SerializationError::Malformed(_))
+    ));
+}
+
+#[cfg(feature = "caldav")]
+mod caldav {
+    use todo::domain::sync::RemoteTaskList;
+    use todo::infrastructure::remote_task_lists::CalDavTaskList;
+    use todo::{Todo, TodoState};
+    use tokio::task::JoinHandle;
+
+    /// Answers one request per `(status, headers, body)` response, and returns the heads and
+    /// bodies of the requests
+    async fn start_server(
+        responses: Vec<(&'static str, &'static str, String)>,
+    ) -> (String, JoinHandle<Vec<(String, String)>>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let requests = tokio::spawn(async move {
+            let mut requests = Vec::new();
+            for (status, headers, body) in responses {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut data = Vec::new();
+                let mut buffer = [0; 4096];
+                loop {
+                    let read = stream.read(&mut buffer).await.unwrap();
+                    data.extend_from_slice(&buffer[..read]);
+                    let text = String::from_utf8_lossy(&data).to_string();
+                    let Some(end) = text.find("\r\n\r\n") else {
+                        continue;
+                    };
+                    let head = text[..end].to_string();
+                    let length: usize = head
+                        .lines()
+                        .find_map(|line| line.strip_prefix("content-length: "))
+                        .map_or(0, |length| length.parse().unwrap());
+                    if data.len() >= end + 4 + length {
+                        requests.push((head, text[end + 4..].to_string()));
+                        break;
+                    }
+                }
+                let response = format!(
+                    "HTTP/1.1 {}\r\n{}content-length: {}\r\nconnection: close\r\n\r\n{}",
+                    status,
+                    headers,
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+            requests
+        });
+        (endpoint, requests)
+    }
+
+    fn calendar(uid: &str, summary: &str) -> String {
+        format!(
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nBEGIN:VTODO\r\nUID:{}\r\nSUMMARY:{}\r\n\
+             DESCRIPTION:Kept\r\nEND:VTODO\r\nEND:VCALENDAR\r\n",
+            uid, summary
+        )
+    }
+
+    fn etags(resources: &[(&str, &str)]) -> String {
+        let responses: String = resources
+            .iter()
+            .map(|(href, etag)| {
+                format!(
+                    "<d:response><d:href>{}</d:href><d:propstat><d:prop>\
+                     <d:getetag>&quot;{}&quot;</d:getetag></d:prop>\
+                     <d:status>HTTP/1.1 200 OK</d:status></d:propstat></d:response>",
+                    href, etag
+                )
+            })
+            .collect();
+        format!(
+            r#"<?xml version="1.0"?><d:multistatus xmlns:d="DAV:">{}</d:multistatus>"#,
+            responses
+        )
+    }
+
+    #[tokio::test]
+    async fn test_task_list_fetches_changed_tasks_and_pushes_with_etags() {
+        // Arrange
+        let (local, _) = Todo::new("Call mom".to_string()).unwrap();
+        let local_href = format!("/tasks/{}.ics", local.id);
+        let multiget = format!(
+            r#"<?xml version="1.0"?>
+<D:multistatus xmlns:D="DAV:" xmlns:C="urn:ietf:params:xml:ns:caldav">
+  <D:response><D:href>/tasks/task-1.ics</D:href><D:propstat><D:prop>
+    <D:getetag>"1"</D:getetag><C:calendar-data><![CDATA[{}]]></C:calendar-data>
+  </D:prop></D:propstat></D:response>
+  <D:response><D:href>/tasks/task-2.ics</D:href><D:propstat><D:prop>
+    <D:getetag>"2"</D:getetag><C:calendar-data>{}</C:calendar-data>
+  </D:prop></D:propstat></D:response>
+</D:multistatus>"#,
+            calendar("task-1", "Write docs"),
+            calendar("task-2", "Review & merge"),
+        );
+        let (endpoint, requests) = start_server(vec![
+            (
+                "207 Multi-Status",
+                "",
+                etags(&[("/tasks/task-1.ics", "1"), ("/tasks/task-2.ics", "2")]),
+            ),
+            ("207 Multi-Status", "", multiget),
+            ("204 No Content", "etag: \"3\"\r\n", String::new()),
+            ("412 Precondition Failed", "", String::new()),
+            ("201 Created", "etag: \"4\"\r\n", String::new()),
+            (
+                "207 Multi-Status",
+                "",
+                etags(&[("/tasks/task-1.ics", "3"), (&local_href, "4")]),
+            ),
+        ])
+        .await;
+        let list = CalDavTaskList::new(&format!("{}/tasks", endpoint), "alice", "secret").unwrap();
+
+        // Act
+        let pulled = list.pull().await.unwrap();
+        let mut started = pulled.todos[0].clone();
+        started.update_state(TodoState::InProgress).unwrap();
+        let updated = list.update("task-1", &started).await.unwrap();
+        let stale = list.update("task-2", &pulled.todos[1]).await.unwrap();
+        let created = list.create(&local).await.unwrap();
+        let again = list.pull().await.unwrap();
+        let requests = requests.await.unwrap();
+
+        // Assert
+        let summaries: Vec<_> = pulled
+            .todos
+            .iter()
+            .map(|todo| (&*todo.id, &*todo.description))
+            .collect();
+        assert_eq!(
+            summaries,
+            vec![("task-1", "Write docs"), ("task-2", "Review & merge")]
+        );
+        assert!(updated);
+        assert!(!stale);
+        assert_eq!(created.as_deref(), Some(&*local.id));
+        assert!(again.todos.is_empty());
+        assert_eq!(again.removed, vec!["task-2"]);
+        let lines: Vec<_> = requests
+            .iter()
+            .map(|(head, _)| head.lines().next().unwrap())
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                "REPORT /tasks/ HTTP/1.1".to_string(),
+                "REPORT /tasks/ HTTP/1.1".to_string(),
+                "PUT /tasks/task-1.ics HTTP/1.1".to_string(),
+                "PUT /tasks/task-2.ics HTTP/1.1".to_string(),
+                format!("PUT {} HTTP/1.1", local_href),
+                "REPORT /tasks/ HTTP/1.1".to_string(),
+            ]
+        );
+        assert!(requests[0].0.contains("depth: 1"));
+        assert!(requests[1].1.contains("<d:href>/tasks/task-1.ics</d:href>"));
+        let (put_head, put_body) = &requests[2];
+        assert!(put_head.contains("if-match: \"1\""));
+        assert!(put_body.contains("STATUS:IN-PROCESS\r\n"));
+        assert!(put_body.contains("DESCRIPTION:Kept\r\n"));
+        assert!(requests[4].0.contains("if-none-match: *"));
+        assert!(requests[4].1.contains(&format!("UID:{}\r\n", local.id)));
+    }
+}
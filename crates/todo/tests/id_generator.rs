@@ -0,0 +1,53 @@
+use std::sync::Arc;
+use todo::application::add_todo_handler::AddTodoHandler;
+use todo::infrastructure::id_format::IdFormat;
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::{SequentialIdGenerator, TodoEvent, TodoRepository};
+
+#[tokio::test]
+async fn test_add_todo_handler_takes_ids_from_its_generator() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let handler = AddTodoHandler::new(repository.clone())
+        .with_id_generator(Arc::new(SequentialIdGenerator::new("todo-")));
+
+    // Act
+    let (_, first) = handler.new_todo("First".to_string()).await.unwrap();
+    let (_, second) = handler.new_todo("Second".to_string()).await.unwrap();
+
+    // Assert
+    let ids: Vec<&str> = [&first[0], &second[0]]
+        .into_iter()
+        .map(|event| match event {
+            TodoEvent::TodoCreated { id, .. } => &**id,
+            other => panic!("expected TodoCreated, got {:?}", other),
+        })
+        .collect();
+    assert_eq!(ids, ["todo-1", "todo-2"]);
+    let todo = repository.find_by_id("todo-2").await.unwrap().unwrap();
+    assert_eq!(&*todo.description, "Second");
+}
+
+#[test]
+fn test_time_based_ids_sort_in_creation_order() {
+    for (format, len) in [(IdFormat::UuidV7, 36), (IdFormat::Ulid, 26)] {
+        let generator = format.generator();
+
+        let ids: Vec<String> = (0..1000).map(|_| generator.next_id()).collect();
+
+        let mut sorted = ids.clone();
+        sorted.sort();
+        assert_eq!(sorted, ids, "{:?}", format);
+        sorted.dedup();
+        assert_eq!(sorted.len(), ids.len(), "{:?}", format);
+        assert!(ids.iter().all(|id| id.len() == len), "{:?}", format);
+    }
+}
+
+#[test]
+fn test_id_format_parse() {
+    assert_eq!(IdFormat::parse("uuid4"), Ok(IdFormat::UuidV4));
+    assert_eq!(IdFormat::parse("uuid7"), Ok(IdFormat::UuidV7));
+    assert_eq!(IdFormat::parse("ulid"), Ok(IdFormat::Ulid));
+    assert!(IdFormat::parse("uuid").is_err());
+}
@@ -0,0 +1,264 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use chrono::{TimeZone, Utc};
+use common::saved;
+use std::sync::Arc;
+use todo::application::import_todos_handler::ImportTodosHandler;
+use todo::domain::import::{ImportMapping, ImportMappingRepository, ImportPolicy};
+use todo::infrastructure::repositories::import_mapping::{
+    FileImportMappingRepository, InMemoryImportMappingRepository,
+};
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::{Todo, TodoEvent, TodoRepository, TodoState};
+
+/// A Taskwarrior export with one task, `a1`
+fn export(description: &str, status: &str, start: bool) -> String {
+    let start = if start {
+        r#","start":"20240104T000000Z""#
+    } else {
+        ""
+    };
+    format!(
+        r#"[{{"description":"{}","entry":"20240102T030405Z","status":"{}","uuid":"a1"{}}}]"#,
+        description, status, start
+    )
+}
+
+fn handler(
+    repository: &Arc<InMemoryTodoRepository>,
+    mappings: &Arc<InMemoryImportMappingRepository>,
+    policy: ImportPolicy,
+) -> ImportTodosHandler<Arc<InMemoryTodoRepository>> {
+    ImportTodosHandler::new(repository.clone())
+        .with_import_mappings(mappings.clone())
+        .with_policy(policy)
+}
+
+async fn todo(repository: &InMemoryTodoRepository, id: &str) -> Todo {
+    repository.find_by_id(id).await.unwrap().unwrap()
+}
+
+#[tokio::test]
+async fn test_reimport_skips_known_items_by_default() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let mappings = Arc::new(InMemoryImportMappingRepository::new());
+    let handler = handler(&repository, &mappings, ImportPolicy::default());
+    handler
+        .import_taskwarrior(export("Write docs", "pending", false).as_bytes())
+        .await
+        .unwrap();
+
+    // Act
+    let events = handler
+        .import_taskwarrior(export("Write the docs", "completed", false).as_bytes())
+        .await
+        .unwrap();
+
+    // Assert
+    assert!(events.is_empty());
+    let kept = todo(&repository, "a1").await;
+    assert_eq!(kept.description.as_ref(), "Write docs");
+    assert_eq!(kept.state, TodoState::Todo);
+    let mapping = mappings.find("taskwarrior", "a1").await.unwrap().unwrap();
+    assert_eq!(mapping.todo_id.as_ref(), "a1");
+    assert_eq!(mapping.description.as_ref(), "Write the docs");
+}
+
+#[tokio::test]
+async fn test_reimport_updates_known_items() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let mappings = Arc::new(InMemoryImportMappingRepository::new());
+    let handler = handler(&repository, &mappings, ImportPolicy::Update);
+    handler
+        .import_taskwarrior(export("Write docs", "pending", false).as_bytes())
+        .await
+        .unwrap();
+
+    // Act
+    let events = handler
+        .import_taskwarrior(export("Write the docs", "completed", false).as_bytes())
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(repository.find_all().await.unwrap().len(), 1);
+    let updated = todo(&repository, "a1").await;
+    assert_eq!(updated.description.as_ref(), "Write the docs");
+    assert_eq!(updated.state, TodoState::Done);
+    assert!(matches!(
+        events.as_slice(),
+        [TodoEvent::TodoStateChanged {
+            from_state: TodoState::Todo,
+            to_state: TodoState::Done,
+            ..
+        }]
+    ));
+}
+
+#[tokio::test]
+async fn test_merge_keeps_local_changes_and_takes_remote_ones() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let mappings = Arc::new(InMemoryImportMappingRepository::new());
+    let handler = handler(&repository, &mappings, ImportPolicy::Merge);
+    handler
+        .import_taskwarrior(export("Write docs", "pending", false).as_bytes())
+        .await
+        .unwrap();
+    let mut local = todo(&repository, "a1").await;
+    local.update_state(TodoState::InProgress).unwrap();
+    repository.save(&local).await.unwrap();
+
+    // Act: the description changed remotely, the state on both sides
+    let events = handler
+        .import_taskwarrior(export("Write the docs", "completed", false).as_bytes())
+        .await
+        .unwrap();
+
+    // Assert
+    assert!(events.is_empty());
+    let merged = todo(&repository, "a1").await;
+    assert_eq!(merged.description.as_ref(), "Write the docs");
+    assert_eq!(merged.state, TodoState::InProgress);
+}
+
+#[tokio::test]
+async fn test_merge_takes_remote_state_when_unchanged_locally() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let mappings = Arc::new(InMemoryImportMappingRepository::new());
+    let handler = handler(&repository, &mappings, ImportPolicy::Merge);
+    handler
+        .import_taskwarrior(export("Write docs", "pending", false).as_bytes())
+        .await
+        .unwrap();
+
+    // Act
+    let events = handler
+        .import_taskwarrior(export("Write docs", "pending", true).as_bytes())
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(events.len(), 1);
+    assert_eq!(todo(&repository, "a1").await.state, TodoState::InProgress);
+}
+
+#[tokio::test]
+async fn test_mapping_links_items_to_todos_with_other_ids() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let mappings = Arc::new(InMemoryImportMappingRepository::new());
+    let local = saved(&repository, "Write docs").await;
+    let imported_at = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let mut item = local.clone();
+    item.id = "a1".into();
+    mappings
+        .save_all(&[ImportMapping::new(
+            "taskwarrior",
+            &item,
+            local.id.clone(),
+            imported_at,
+        )])
+        .await
+        .unwrap();
+    let handler = handler(&repository, &mappings, ImportPolicy::Update);
+
+    // Act
+    handler
+        .import_taskwarrior(export("Write the docs", "pending", false).as_bytes())
+        .await
+        .unwrap();
+
+    // Assert
+    let todos = repository.find_all().await.unwrap();
+    assert_eq!(todos.len(), 1);
+    assert_eq!(todos[0].id, local.id);
+    assert_eq!(todos[0].description.as_ref(), "Write the docs");
+}
+
+#[tokio::test]
+async fn test_items_deleted_locally_are_not_imported_again() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let mappings = Arc::new(InMemoryImportMappingRepository::new());
+    let handler = handler(&repository, &mappings, ImportPolicy::Update);
+    handler
+        .import_taskwarrior(export("Write docs", "pending", false).as_bytes())
+        .await
+        .unwrap();
+    repository.delete("a1").await.unwrap();
+
+    // Act
+    let events = handler
+        .import_taskwarrior(export("Write docs", "pending", false).as_bytes())
+        .await
+        .unwrap();
+
+    // Assert
+    assert!(events.is_empty());
+    assert!(repository.find_all().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_update_without_mappings_matches_by_id() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let handler = ImportTodosHandler::new(repository.clone()).with_policy(ImportPolicy::Update);
+    handler
+        .import_taskwarrior(export("Write docs", "pending", false).as_bytes())
+        .await
+        .unwrap();
+
+    // Act
+    handler
+        .import_taskwarrior(export("Write the docs", "pending", false).as_bytes())
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(
+        todo(&repository, "a1").await.description.as_ref(),
+        "Write the docs"
+    );
+}
+
+#[tokio::test]
+async fn test_file_mappings_persist_between_repositories() {
+    // Arrange
+    let path = common::temp_path("mappings.json");
+    let imported_at = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let (item, _) = Todo::new("Write docs".to_string()).unwrap();
+    let mapping = ImportMapping::new("taskwarrior", &item, "local".into(), imported_at);
+    FileImportMappingRepository::new(&path)
+        .save_all(std::slice::from_ref(&mapping))
+        .await
+        .unwrap();
+
+    // Act
+    let reopened = FileImportMappingRepository::new(&path);
+    let found = reopened.find("taskwarrior", &item.id).await.unwrap();
+    let other_source = reopened.find_by_source("markdown").await.unwrap();
+
+    // Assert
+    assert_eq!(found, Some(mapping));
+    assert!(other_source.is_empty());
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_import_policy_parses_its_names() {
+    for policy in [
+        ImportPolicy::Skip,
+        ImportPolicy::Update,
+        ImportPolicy::Merge,
+    ] {
+        assert_eq!(ImportPolicy::parse(policy.as_str()), Ok(policy));
+    }
+    assert!(ImportPolicy::parse("overwrite").is_err());
+}
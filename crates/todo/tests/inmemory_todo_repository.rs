@@ -0,0 +1,23 @@
+use futures::StreamExt;
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::{Todo, TodoRepository};
+
+#[tokio::test]
+async fn test_stream_all_reads_a_snapshot() {
+    // Arrange
+    let repository = InMemoryTodoRepository::new();
+    let (first, _) = Todo::new("First todo".to_string()).unwrap();
+    let (second, _) = Todo::new("Second todo".to_string()).unwrap();
+    repository.save(&first).await.unwrap();
+    let stream = repository.stream_all();
+
+    // Act
+    repository.save(&second).await.unwrap();
+    repository.delete(&first.id).await.unwrap();
+    let streamed: Vec<Todo> = stream.map(Result::unwrap).collect().await;
+
+    // Assert
+    assert_eq!(streamed, vec![first]);
+    let todos = repository.find_all().await.unwrap();
+    assert_eq!(todos, vec![second]);
+}
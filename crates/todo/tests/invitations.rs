@@ -0,0 +1,231 @@
+mod common;
+
+use chrono::{TimeZone, Utc};
+use std::sync::Arc;
+use todo::application::add_todo_handler::AddTodoHandler;
+use todo::application::invitations::InvitationHandler;
+use todo::application::shared_lists::GetSharedListsHandler;
+use todo::domain::access::{
+    AccessError, InvitationEvent, InvitationStatus, Membership, MembershipRepository, Role,
+};
+use todo::infrastructure::repositories::invitation::InMemoryInvitationRepository;
+use todo::infrastructure::repositories::membership::InMemoryMembershipRepository;
+use todo::infrastructure::repositories::todo::{InMemoryTodoRepository, TenantTodoRepository};
+use todo::{FixedClock, SequentialIdGenerator, TenantId, TodoRepository};
+
+fn list(id: &str) -> TenantId {
+    TenantId::new(id).unwrap()
+}
+
+/// A handler over empty repositories, with `alice` owning the list `team`
+async fn handler() -> (InvitationHandler, Arc<InMemoryMembershipRepository>) {
+    let memberships = Arc::new(InMemoryMembershipRepository::new());
+    memberships
+        .save(&Membership::new(list("team"), "alice", Role::Owner))
+        .await
+        .unwrap();
+    let clock = FixedClock::new(Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap());
+    let handler = InvitationHandler::new(
+        Arc::new(InMemoryInvitationRepository::new()),
+        memberships.clone(),
+    )
+    .with_clock(Arc::new(clock))
+    .with_id_generator(Arc::new(SequentialIdGenerator::new("inv-")));
+    (handler, memberships)
+}
+
+#[tokio::test]
+async fn test_accepting_an_invitation_makes_the_invitee_a_member() {
+    // Arrange
+    let (handler, memberships) = handler().await;
+    let at = Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+
+    // Act
+    let (invitation, sent) = handler
+        .invite("alice", &list("team"), "bob", Role::Editor)
+        .await
+        .unwrap();
+    let pending = handler.pending("bob").await.unwrap();
+    let (membership, accepted) = handler.accept("inv-1", "bob").await.unwrap();
+    let pending_after = handler.pending("bob").await.unwrap();
+    let again = handler.accept("inv-1", "bob").await;
+
+    // Assert
+    assert_eq!(invitation.status, InvitationStatus::Pending);
+    assert_eq!(
+        sent,
+        InvitationEvent::InvitationSent {
+            id: "inv-1".into(),
+            list: list("team"),
+            invitee: "bob".into(),
+            role: Role::Editor,
+            invited_by: "alice".into(),
+            sent_at: at,
+        }
+    );
+    assert_eq!(pending, vec![invitation]);
+    assert_eq!(
+        membership,
+        Membership::new(list("team"), "bob", Role::Editor)
+    );
+    assert_eq!(
+        accepted,
+        InvitationEvent::InvitationAccepted {
+            id: "inv-1".into(),
+            list: list("team"),
+            subject: "bob".into(),
+            role: Role::Editor,
+            accepted_at: at,
+        }
+    );
+    assert_eq!(
+        memberships.find(&list("team"), "bob").await.unwrap(),
+        Some(membership)
+    );
+    assert!(pending_after.is_empty());
+    assert!(matches!(again, Err(AccessError::InvalidInvitation(_))));
+}
+
+#[tokio::test]
+async fn test_declining_leaves_the_list_alone() {
+    let (handler, memberships) = handler().await;
+    handler
+        .invite("alice", &list("team"), "Bob@Example.com", Role::Viewer)
+        .await
+        .unwrap();
+
+    let event = handler.decline("inv-1", "bob@example.com").await.unwrap();
+    let sent = handler.sent("alice", &list("team")).await.unwrap();
+
+    assert_eq!(event.invitation_id(), "inv-1");
+    assert!(matches!(event, InvitationEvent::InvitationDeclined { .. }));
+    assert_eq!(sent[0].status, InvitationStatus::Declined);
+    assert_eq!(
+        memberships
+            .find(&list("team"), "bob@example.com")
+            .await
+            .unwrap(),
+        None
+    );
+}
+
+#[tokio::test]
+async fn test_only_owners_invite_and_only_invitees_answer() {
+    // Arrange
+    let (handler, memberships) = handler().await;
+    memberships
+        .save(&Membership::new(list("team"), "carol", Role::Editor))
+        .await
+        .unwrap();
+    handler
+        .invite("alice", &list("team"), "bob", Role::Viewer)
+        .await
+        .unwrap();
+
+    // Act
+    let by_editor = handler
+        .invite("carol", &list("team"), "dave", Role::Viewer)
+        .await;
+    let to_member = handler
+        .invite("alice", &list("team"), "carol", Role::Owner)
+        .await;
+    let twice = handler
+        .invite("alice", &list("team"), "bob", Role::Editor)
+        .await;
+    let blank = handler
+        .invite("alice", &list("team"), " ", Role::Viewer)
+        .await;
+    let by_other = handler.accept("inv-1", "mallory").await;
+    let sent_to_editor = handler.sent("carol", &list("team")).await;
+
+    // Assert
+    assert!(matches!(by_editor, Err(AccessError::NotPermitted(_))));
+    assert!(matches!(to_member, Err(AccessError::InvalidInvitation(_))));
+    assert!(matches!(twice, Err(AccessError::InvalidInvitation(_))));
+    assert!(matches!(blank, Err(AccessError::InvalidInvitation(_))));
+    assert_eq!(by_other, Err(AccessError::InvitationNotFound));
+    assert!(matches!(sent_to_editor, Err(AccessError::NotPermitted(_))));
+    assert_eq!(
+        memberships.find(&list("team"), "mallory").await.unwrap(),
+        None
+    );
+}
+
+#[tokio::test]
+async fn test_shared_lists_appear_for_every_member() {
+    // Arrange
+    let todos: Arc<dyn TodoRepository> = Arc::new(InMemoryTodoRepository::new());
+    for (tenant, description) in [("team", "Plan sprint"), ("bob", "Buy milk")] {
+        AddTodoHandler::new(TenantTodoRepository::new(todos.clone(), list(tenant)))
+            .new_todo(description.to_string())
+            .await
+            .unwrap();
+    }
+    let (handler, memberships) = handler().await;
+    memberships
+        .save(&Membership::new(list("bob"), "bob", Role::Owner))
+        .await
+        .unwrap();
+    handler
+        .invite("alice", &list("team"), "bob", Role::Viewer)
+        .await
+        .unwrap();
+    let shared = GetSharedListsHandler::new(todos, memberships);
+    let before = shared.shared_lists("bob").await.unwrap();
+
+    // Act
+    handler.accept("inv-1", "bob").await.unwrap();
+    let after = shared.shared_lists("bob").await.unwrap();
+    let owner = shared.shared_lists("alice").await.unwrap();
+
+    // Assert
+    assert_eq!(before.len(), 1);
+    let lists: Vec<_> = after
+        .iter()
+        .map(|shared| (shared.list.as_str(), shared.role, shared.todos.len()))
+        .collect();
+    assert_eq!(
+        lists,
+        vec![("bob", Role::Owner, 1), ("team", Role::Viewer, 1)]
+    );
+    assert_eq!(&*after[1].todos[0].description, "Plan sprint");
+    assert_eq!(owner[0].todos, after[1].todos);
+}
+
+#[cfg(feature = "serde")]
+#[tokio::test]
+async fn test_file_repository_keeps_invitations_across_instances() {
+    use todo::domain::access::{Invitation, InvitationRepository};
+    use todo::infrastructure::repositories::invitation::FileInvitationRepository;
+
+    let path = common::temp_path("invitations.json");
+    let at = Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+    let (mut invitation, _) =
+        Invitation::new("inv-1", list("team"), "bob", Role::Editor, "alice", at).unwrap();
+    FileInvitationRepository::new(&path)
+        .save(&invitation)
+        .await
+        .unwrap();
+    invitation.accept("bob", at).unwrap();
+    FileInvitationRepository::new(&path)
+        .save(&invitation)
+        .await
+        .unwrap();
+
+    let reopened = FileInvitationRepository::new(&path);
+
+    assert_eq!(
+        reopened.find_by_id("inv-1").await.unwrap(),
+        Some(invitation.clone())
+    );
+    assert_eq!(
+        reopened.find_by_list(&list("team")).await.unwrap(),
+        vec![invitation.clone()]
+    );
+    assert_eq!(
+        reopened.find_by_invitee("bob").await.unwrap(),
+        vec![invitation]
+    );
+    assert!(reopened.find_by_invitee("carol").await.unwrap().is_empty());
+    std::fs::remove_file(&path).unwrap();
+}
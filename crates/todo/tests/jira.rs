@@ -0,0 +1,235 @@
+#![cfg(feature = "serde")]
+
+use todo::TodoState;
+use todo::infrastructure::serialization::SerializationError;
+use todo::infrastructure::serialization::jira::{StatusMap, read_search_page};
+
+const PAGE: &str = r#"{
+  "issues": [
+    {"id": "10001", "key": "PROJ-1", "fields": {"summary": "Write docs", "created": "2024-01-02T03:04:05.000+0700",
+      "status": {"name": "Backlog", "statusCategory": {"key": "new"}}}},
+    {"id": "10002", "key": "PROJ-2", "fields": {"summary": "Review PR", "created": "2024-01-03T00:00:00.000+0000",
+      "status": {"name": "In Review", "statusCategory": {"key": "indeterminate"}}}},
+    {"id": "10003", "key": "PROJ-3", "fields": {"summary": "Release", "created": "2024-01-04T00:00:00.000+0000",
+      "status": {"name": "Closed", "statusCategory": {"key": "done"}}}}
+  ],
+  "nextPageToken": "p2",
+  "isLast": false
+}"#;
+
+#[test]
+fn test_search_page_maps_statuses_through_map_and_categories() {
+    // Arrange
+    let statuses = StatusMap {
+        statuses: vec![("in review".to_string(), TodoState::Todo)],
+    };
+
+    // Act
+    let page = read_search_page(PAGE.as_bytes(), &statuses).unwrap();
+
+    // Assert
+    let states: Vec<_> = page
+        .todos
+        .iter()
+        .map(|todo| (&*todo.id, todo.state))
+        .collect();
+    assert_eq!(
+        states,
+        vec![
+            ("10001", TodoState::Todo),
+            ("10002", TodoState::Todo),
+            ("10003", TodoState::Done),
+        ]
+    );
+    assert_eq!(page.keys, vec!["PROJ-1", "PROJ-2", "PROJ-3"]);
+    assert_eq!(&*page.todos[0].description, "Write docs");
+    assert_eq!(
+        page.todos[0].created_at.to_rfc3339(),
+        "2024-01-01T20:04:05+00:00"
+    );
+    assert_eq!(page.next_page_token.as_deref(), Some("p2"));
+}
+
+#[test]
+fn test_search_page_rejects_unmapped_statuses() {
+    let page = r#"{"issues": [{"id": "1", "key": "P-1", "fields": {"summary": "One", "status": {"name": "Triage"}}}]}"#;
+
+    let result = read_search_page(page.as_bytes(), &StatusMap::default());
+
+    assert!(matches!(
+        result,
+        Err(SerializationError::InvalidRecord { record: 1, message }) if message == "unmapped status: Triage"
+    ));
+}
+
+#[cfg(feature = "jira")]
+mod sync {
+    use serde_json::{Value, json};
+    use todo::domain::sync::RemoteTaskList;
+    use todo::infrastructure::remote_task_lists::JiraTaskList;
+    use todo::{Todo, TodoState};
+    use tokio::task::JoinHandle;
+
+    /// Answers one request per response, and returns the heads and bodies of the requests
+    async fn start_server(
+        responses: Vec<(&'static str, String)>,
+    ) -> (String, JoinHandle<Vec<(String, String)>>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let requests = tokio::spawn(async move {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut data = Vec::new();
+                let mut buffer = [0; 4096];
+                loop {
+                    let read = stream.read(&mut buffer).await.unwrap();
+                    data.extend_from_slice(&buffer[..read]);
+                    let text = String::from_utf8_lossy(&data).to_string();
+                    let Some(end) = text.find("\r\n\r\n") else {
+                        continue;
+                    };
+                    let head = text[..end].to_string();
+                    let length: usize = head
+                        .lines()
+                        .find_map(|line| line.strip_prefix("content-length: "))
+                        .map_or(0, |length| length.parse().unwrap());
+                    if data.len() >= end + 4 + length {
+                        requests.push((head, text[end + 4..].to_string()));
+                        break;
+                    }
+                }
+                let response = format!(
+                    "HTTP/1.1 {}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+            requests
+        });
+        (endpoint, requests)
+    }
+
+    fn issue(id: &str, summary: &str, status: &str, category: &str) -> Value {
+        json!({ "id": id, "key": format!("PROJ-{}", id), "fields": {
+            "summary": summary,
+            "status": { "name": status, "statusCategory": { "key": category } },
+        }})
+    }
+
+    #[tokio::test]
+    async fn test_task_list_pulls_changed_issues_and_pushes_through_transitions() {
+        // Arrange
+        let (endpoint, requests) = start_server(vec![
+            (
+                "200 OK",
+                json!({ "issues": [
+                    issue("1", "Write docs", "To Do", "new"),
+                    issue("2", "Review PR", "To Do", "new"),
+                ], "nextPageToken": "p2" })
+                .to_string(),
+            ),
+            (
+                "200 OK",
+                json!({ "issues": [issue("3", "Release", "Closed", "done")] }).to_string(),
+            ),
+            ("204 No Content", String::new()),
+            (
+                "200 OK",
+                json!({ "transitions": [
+                    { "id": "31", "to": { "name": "Closed", "statusCategory": { "key": "done" } } },
+                    { "id": "21", "to": { "name": "In Progress", "statusCategory": { "key": "indeterminate" } } },
+                ]})
+                .to_string(),
+            ),
+            ("204 No Content", String::new()),
+            (
+                "200 OK",
+                json!({ "issues": [
+                    issue("1", "Write the docs", "In Progress", "indeterminate"),
+                    issue("2", "Review PR", "To Do", "new"),
+                ]})
+                .to_string(),
+            ),
+        ])
+        .await;
+        let list = JiraTaskList::new(&endpoint, "alice@example.com", "token", "project = PROJ");
+        let (local, _) = Todo::new("Call mom".to_string()).unwrap();
+
+        // Act
+        let pulled = list.pull().await.unwrap();
+        let mut started = pulled.todos[0].clone();
+        started
+            .update_description("Write the docs".to_string())
+            .unwrap();
+        started.update_state(TodoState::InProgress).unwrap();
+        let updated = list.update("1", &started).await.unwrap();
+        let created = list.create(&local).await.unwrap();
+        let again = list.pull().await.unwrap();
+        let requests = requests.await.unwrap();
+
+        // Assert
+        let ids: Vec<_> = pulled.todos.iter().map(|todo| &*todo.id).collect();
+        assert_eq!(ids, vec!["1", "2", "3"]);
+        assert!(updated);
+        assert_eq!(created, None);
+        assert!(again.todos.is_empty());
+        assert_eq!(again.removed, vec!["3"]);
+        let lines: Vec<_> = requests
+            .iter()
+            .map(|(head, _)| head.lines().next().unwrap())
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                "GET /rest/api/3/search/jql?jql=project+%3D+PROJ&fields=summary%2Cstatus%2Ccreated HTTP/1.1",
+                "GET /rest/api/3/search/jql?jql=project+%3D+PROJ&fields=summary%2Cstatus%2Ccreated&nextPageToken=p2 HTTP/1.1",
+                "PUT /rest/api/3/issue/1 HTTP/1.1",
+                "GET /rest/api/3/issue/1/transitions HTTP/1.1",
+                "POST /rest/api/3/issue/1/transitions HTTP/1.1",
+                "GET /rest/api/3/search/jql?jql=project+%3D+PROJ&fields=summary%2Cstatus%2Ccreated HTTP/1.1",
+            ]
+        );
+        assert!(requests[0].0.contains("authorization: Basic "));
+        let summary: Value = serde_json::from_str(&requests[2].1).unwrap();
+        assert_eq!(
+            summary,
+            json!({ "fields": { "summary": "Write the docs" } })
+        );
+        let transition: Value = serde_json::from_str(&requests[4].1).unwrap();
+        assert_eq!(transition, json!({ "transition": { "id": "21" } }));
+    }
+
+    #[tokio::test]
+    async fn test_task_list_creates_issues_in_the_configured_project() {
+        // Arrange
+        let (endpoint, requests) = start_server(vec![(
+            "201 Created",
+            json!({ "id": "10", "key": "PROJ-10" }).to_string(),
+        )])
+        .await;
+        let list = JiraTaskList::new(&endpoint, "alice@example.com", "token", "project = PROJ")
+            .with_project("PROJ", "Task");
+        let (local, _) = Todo::new("Call mom".to_string()).unwrap();
+
+        // Act
+        let created = list.create(&local).await.unwrap();
+        let requests = requests.await.unwrap();
+
+        // Assert
+        assert_eq!(created.as_deref(), Some("10"));
+        let body: Value = serde_json::from_str(&requests[0].1).unwrap();
+        assert_eq!(
+            body,
+            json!({ "fields": {
+                "project": { "key": "PROJ" },
+                "issuetype": { "name": "Task" },
+                "summary": "Call mom",
+            }})
+        );
+    }
+}
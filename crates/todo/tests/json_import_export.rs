@@ -0,0 +1,82 @@
+#![cfg(feature = "serde")]
+
+use serde_json::json;
+use std::sync::Arc;
+use todo::application::export_todos_handler::ExportTodosHandler;
+use todo::application::import_todos_handler::ImportTodosHandler;
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::infrastructure::serialization::SerializationError;
+use todo::infrastructure::serialization::json::import_todos;
+use todo::{Todo, TodoEvent, TodoRepository, TodoState};
+
+#[tokio::test]
+async fn test_export_then_import_into_another_repository() {
+    // Arrange
+    let source = Arc::new(InMemoryTodoRepository::new());
+    let (mut todo, _) = Todo::new("Write docs".to_string()).unwrap();
+    todo.update_state(TodoState::InProgress).unwrap();
+    source.save(&todo).await.unwrap();
+    let target = Arc::new(InMemoryTodoRepository::new());
+    let mut document = Vec::new();
+
+    // Act
+    let exported = ExportTodosHandler::new(source)
+        .export_todos(&mut document)
+        .await
+        .unwrap();
+    let import_handler = ImportTodosHandler::new(target.clone());
+    let events = import_handler
+        .import_todos(document.as_slice())
+        .await
+        .unwrap();
+    let reimported = import_handler
+        .import_todos(document.as_slice())
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(exported, 1);
+    let envelope: serde_json::Value = serde_json::from_slice(&document).unwrap();
+    assert_eq!(envelope["format"], "hk-todo");
+    assert_eq!(envelope["version"], 1);
+    assert!(matches!(&events[..], [TodoEvent::TodoCreated { id, .. }] if *id == todo.id));
+    assert!(reimported.is_empty());
+    let imported = target.find_by_id(&todo.id).await.unwrap().unwrap();
+    assert_eq!(&*imported.description, "Write docs");
+    assert_eq!(imported.state, TodoState::InProgress);
+    assert_eq!(imported.created_at, todo.created_at);
+}
+
+#[test]
+fn test_import_rejects_invalid_documents() {
+    let todo = json!({
+        "id": "1",
+        "created_at": "2024-01-01T00:00:00+00:00",
+        "description": "Write docs",
+        "state": "TODO",
+    });
+    let import = |document: serde_json::Value| import_todos(document.to_string().as_bytes());
+
+    assert!(matches!(
+        import(json!({ "format": "hk-todo", "version": 2, "todos": [] })),
+        Err(SerializationError::UnsupportedVersion(2))
+    ));
+    assert!(matches!(
+        import(json!({ "format": "other", "version": 1, "todos": [] })),
+        Err(SerializationError::Malformed(_))
+    ));
+    assert!(matches!(
+        import(json!([todo])),
+        Err(SerializationError::Malformed(_))
+    ));
+    assert!(matches!(
+        import(json!({ "format": "hk-todo", "version": 1, "todos": [todo, todo] })),
+        Err(SerializationError::InvalidRecord { record: 2, .. })
+    ));
+    let mut empty = todo.clone();
+    empty["description"] = json!(" ");
+    assert!(matches!(
+        import(json!({ "format": "hk-todo", "version": 1, "todos": [empty] })),
+        Err(SerializationError::InvalidRecord { record: 1, .. })
+    ));
+}
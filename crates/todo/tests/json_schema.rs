@@ -0,0 +1,31 @@
+#![cfg(feature = "schemars")]
+
+use todo::Todo;
+use todo::infrastructure::serialization::json_schema::schemas;
+
+#[test]
+fn test_schemas_describe_the_serde_representation() {
+    // Arrange
+    let (todo, events) = Todo::new("Write docs".to_string()).unwrap();
+
+    // Act
+    let schemas = schemas();
+    let todo_json = serde_json::to_value(&todo).unwrap();
+    let event_json = serde_json::to_value(&events[0]).unwrap();
+
+    // Assert
+    let todo_schema = &schemas["Todo"];
+    assert_eq!(
+        todo_schema["properties"]["created_at"]["format"],
+        "date-time"
+    );
+    for field in todo_schema["required"].as_array().unwrap() {
+        assert!(todo_json.get(field.as_str().unwrap()).is_some());
+    }
+    let created = &schemas["TodoEvent"]["oneOf"][0];
+    assert_eq!(created["properties"]["type"]["const"], event_json["type"]);
+    assert_eq!(
+        schemas["TodoError"]["oneOf"][2]["properties"]["code"]["const"],
+        "TODO_NOT_FOUND"
+    );
+}
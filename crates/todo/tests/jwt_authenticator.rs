@@ -0,0 +1,121 @@
+#![cfg(feature = "jwt")]
+
+use jsonwebtoken::{EncodingKey, Header, encode};
+use serde_json::{Value, json};
+use todo::TenantId;
+use todo::application::auth::{AuthContext, AuthError, Authenticator, bearer_token};
+use todo::infrastructure::jwt_authenticator::JwtAuthenticator;
+
+const SECRET: &[u8] = b"test-secret";
+
+fn token(claims: Value) -> String {
+    sign(&Header::default(), claims, SECRET)
+}
+
+fn sign(header: &Header, claims: Value, secret: &[u8]) -> String {
+    encode(header, &claims, &EncodingKey::from_secret(secret)).unwrap()
+}
+
+fn expires_in(seconds: i64) -> i64 {
+    chrono::Utc::now().timestamp() + seconds
+}
+
+#[tokio::test]
+async fn test_accepts_a_signed_token() {
+    // Arrange
+    let authenticator = JwtAuthenticator::with_secret(SECRET)
+        .with_issuer("https://id.example.com")
+        .with_audience("todo-api");
+    let token = token(json!({
+        "sub": "alice",
+        "iss": "https://id.example.com",
+        "aud": "todo-api",
+        "exp": expires_in(300),
+        "scope": "todos:read todos:write",
+        "tenant": "acme",
+    }));
+
+    // Act
+    let context = authenticator.authenticate(&token).await.unwrap();
+
+    // Assert
+    assert_eq!(
+        context,
+        AuthContext {
+            subject: "alice".to_string(),
+            tenant: Some(TenantId::new("acme").unwrap()),
+            scopes: vec!["todos:read".to_string(), "todos:write".to_string()],
+        }
+    );
+    assert!(context.has_scope("todos:write"));
+    assert!(!context.has_scope("admin"));
+}
+
+#[tokio::test]
+async fn test_rejects_tokens_that_fail_validation() {
+    let authenticator = JwtAuthenticator::with_secret(SECRET).with_audience("todo-api");
+    let valid = json!({ "sub": "alice", "aud": "todo-api", "exp": expires_in(300) });
+    let mut expired = valid.clone();
+    expired["exp"] = json!(expires_in(-3600));
+    let mut other_audience = valid.clone();
+    other_audience["aud"] = json!("billing-api");
+    let mut no_subject = valid.clone();
+    no_subject.as_object_mut().unwrap().remove("sub");
+    let mut bad_tenant = valid.clone();
+    bad_tenant["tenant"] = json!("acme/eu");
+
+    let cases = [
+        ("expired", token(expired)),
+        ("other audience", token(other_audience)),
+        ("no subject", token(no_subject)),
+        ("bad tenant", token(bad_tenant)),
+        ("other secret", sign(&Header::default(), valid, b"other")),
+        ("not a jwt", "not-a-jwt".to_string()),
+    ];
+
+    for (case, token) in cases {
+        assert!(
+            matches!(
+                authenticator.authenticate(&token).await,
+                Err(AuthError::InvalidToken(_))
+            ),
+            "{}",
+            case
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_selects_the_key_set_key_by_kid() {
+    // Arrange: two HMAC keys, base64url encoded
+    let authenticator = JwtAuthenticator::from_jwks(
+        r#"{"keys": [
+            {"kty": "oct", "kid": "old", "alg": "HS256", "k": "b2xkLXNlY3JldA"},
+            {"kty": "oct", "kid": "new", "alg": "HS256", "k": "bmV3LXNlY3JldA"}
+        ]}"#,
+    )
+    .unwrap();
+    let claims = json!({ "sub": "alice", "exp": expires_in(300), "scp": ["todos:read"] });
+    let header = Header {
+        kid: Some("new".to_string()),
+        ..Header::default()
+    };
+
+    // Act
+    let signed_with_new = sign(&header, claims.clone(), b"new-secret");
+    let signed_with_old = sign(&header, claims, b"old-secret");
+
+    // Assert
+    let context = authenticator.authenticate(&signed_with_new).await.unwrap();
+    assert_eq!(context.scopes, vec!["todos:read".to_string()]);
+    assert!(authenticator.authenticate(&signed_with_old).await.is_err());
+    assert!(JwtAuthenticator::from_jwks(r#"{"keys": []}"#).is_err());
+}
+
+#[test]
+fn test_bearer_token() {
+    assert_eq!(bearer_token("Bearer abc.def.ghi"), Ok("abc.def.ghi"));
+    assert_eq!(bearer_token("bearer abc"), Ok("abc"));
+    assert!(bearer_token("Basic dXNlcjpwYXNz").is_err());
+    assert!(bearer_token("Bearer ").is_err());
+}
@@ -0,0 +1,245 @@
+use todo::domain::notification::{EmailMessage, Mailer, NotificationError};
+use todo::infrastructure::mailers::InMemoryMailer;
+
+fn message() -> EmailMessage {
+    EmailMessage::new(
+        "todo@example.com",
+        vec!["alice@example.com".to_string()],
+        "2 todos are due",
+        "Write docs\n.hidden line",
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_email_message_checks_addresses_and_subject() {
+    let to = |address: &str| vec![address.to_string()];
+
+    let no_recipient = EmailMessage::new("todo@example.com", vec![], "Due", "");
+    let bad_from = EmailMessage::new("todo", to("alice@example.com"), "Due", "");
+    let injected = EmailMessage::new("todo@example.com", to("a@x.com>,<b@y.com"), "Due", "");
+    let multiline_subject = EmailMessage::new(
+        "todo@example.com",
+        to("alice@example.com"),
+        "Due\r\nBcc: eve@example.com",
+        "",
+    );
+
+    assert_eq!(
+        no_recipient,
+        Err(NotificationError::InvalidMessage(
+            "no recipients".to_string()
+        ))
+    );
+    assert_eq!(
+        bad_from,
+        Err(NotificationError::InvalidAddress("todo".to_string()))
+    );
+    assert!(matches!(
+        injected,
+        Err(NotificationError::InvalidAddress(_))
+    ));
+    assert!(matches!(
+        multiline_subject,
+        Err(NotificationError::InvalidMessage(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_inmemory_mailer_keeps_sent_messages() {
+    let mailer = InMemoryMailer::new();
+
+    mailer.send(&message()).await.unwrap();
+
+    assert_eq!(mailer.sent(), vec![message()]);
+}
+
+#[cfg(feature = "smtp")]
+mod smtp {
+    use super::message;
+    use todo::domain::notification::{Mailer, NotificationError};
+    use todo::infrastructure::mailers::SmtpMailer;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+    use tokio::task::JoinHandle;
+
+    /// Accepts one session, answering every command with success, and returns what the
+    /// client sent
+    async fn start_server() -> (u16, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let session = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (reader, mut writer) = stream.into_split();
+            let mut lines = BufReader::new(reader).lines();
+            let mut received = Vec::new();
+            let mut in_data = false;
+            writer.write_all(b"220 mail.example.com\r\n").await.unwrap();
+            while let Some(line) = lines.next_line().await.unwrap() {
+                received.push(line.clone());
+                let reply: &[u8] = if in_data {
+                    if line != "." {
+                        continue;
+                    }
+                    in_data = false;
+                    b"250 queued\r\n"
+                } else if line.starts_with("EHLO") {
+                    b"250-mail.example.com\r\n250 8BITMIME\r\n"
+                } else if line == "DATA" {
+                    in_data = true;
+                    b"354 go ahead\r\n"
+                } else if line == "QUIT" {
+                    writer.write_all(b"221 bye\r\n").await.unwrap();
+                    break;
+                } else {
+                    b"250 ok\r\n"
+                };
+                writer.write_all(reply).await.unwrap();
+            }
+            received
+        });
+        (port, session)
+    }
+
+    #[tokio::test]
+    async fn test_smtp_mailer_sends_through_the_server() {
+        // Arrange
+        let (port, session) = start_server().await;
+        let mailer = SmtpMailer::new("127.0.0.1")
+            .with_port(port)
+            .with_hello_name("todo.example.com");
+
+        // Act
+        mailer.send(&message()).await.unwrap();
+        let received = session.await.unwrap();
+
+        // Assert
+        assert_eq!(received[0], "EHLO todo.example.com");
+        assert_eq!(received[1], "MAIL FROM:<todo@example.com>");
+        assert_eq!(received[2], "RCPT TO:<alice@example.com>");
+        assert_eq!(received[3], "DATA");
+        assert!(received.contains(&"Subject: 2 todos are due".to_string()));
+        assert!(received.contains(&"To: alice@example.com".to_string()));
+        let text = received.iter().position(|line| line.is_empty()).unwrap();
+        assert_eq!(
+            received[text + 1..],
+            ["Write docs", "..hidden line", ".", "QUIT"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_smtp_mailer_keeps_credentials_off_plain_connections() {
+        let (port, _session) = start_server().await;
+        let mailer = SmtpMailer::new("127.0.0.1")
+            .with_port(port)
+            .with_credentials("alice", "secret");
+
+        let result = mailer.send(&message()).await;
+
+        assert_eq!(
+            result,
+            Err(NotificationError::DeliveryFailed(
+                "refusing to send credentials without TLS".to_string()
+            ))
+        );
+    }
+}
+
+#[cfg(feature = "ses")]
+mod ses {
+    use super::message;
+    use chrono::{TimeZone, Utc};
+    use serde_json::{Value, json};
+    use std::sync::Arc;
+    use todo::FixedClock;
+    use todo::domain::notification::{Mailer, NotificationError};
+    use todo::infrastructure::mailers::SesMailer;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tokio::task::JoinHandle;
+
+    /// Answers one request with `status` and returns its head and body
+    async fn start_server(status: &'static str) -> (String, JoinHandle<(String, Value)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let request = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut data = Vec::new();
+            let mut buffer = [0; 4096];
+            let (head, length) = loop {
+                let read = stream.read(&mut buffer).await.unwrap();
+                data.extend_from_slice(&buffer[..read]);
+                let text = String::from_utf8_lossy(&data).to_string();
+                if let Some(end) = text.find("\r\n\r\n") {
+                    let head = text[..end].to_lowercase();
+                    let length: usize = head
+                        .lines()
+                        .find_map(|line| line.strip_prefix("content-length: "))
+                        .unwrap()
+                        .parse()
+                        .unwrap();
+                    break (head, end + 4 + length);
+                }
+            };
+            while data.len() < length {
+                let read = stream.read(&mut buffer).await.unwrap();
+                data.extend_from_slice(&buffer[..read]);
+            }
+            let response = format!(
+                "HTTP/1.1 {}\r\ncontent-length: 18\r\nconnection: close\r\n\r\n{{\"message\":\"nope\"}}",
+                status
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+            let body = serde_json::from_slice(&data[head.len() + 4..length]).unwrap();
+            (head, body)
+        });
+        (endpoint, request)
+    }
+
+    fn mailer(endpoint: &str) -> SesMailer {
+        let clock = FixedClock::new(Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap());
+        SesMailer::new("eu-west-1", "AKIDEXAMPLE", "secret")
+            .with_endpoint(endpoint)
+            .with_clock(Arc::new(clock))
+    }
+
+    #[tokio::test]
+    async fn test_ses_mailer_posts_a_signed_send_email_request() {
+        // Arrange
+        let (endpoint, request) = start_server("200 OK").await;
+
+        // Act
+        mailer(&endpoint).send(&message()).await.unwrap();
+        let (head, body) = request.await.unwrap();
+
+        // Assert
+        assert!(head.starts_with("post /v2/email/outbound-emails http/1.1"));
+        assert!(head.contains("x-amz-date: 20240102t030405z"));
+        assert!(head.contains(
+            "authorization: aws4-hmac-sha256 credential=akidexample/20240102/eu-west-1/ses/aws4_request, signedheaders=content-type;host;x-amz-date, signature="
+        ));
+        assert_eq!(body["FromEmailAddress"], "todo@example.com");
+        assert_eq!(
+            body["Destination"],
+            json!({ "ToAddresses": ["alice@example.com"] })
+        );
+        assert_eq!(
+            body["Content"]["Simple"]["Subject"]["Data"],
+            "2 todos are due"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ses_mailer_reports_refused_messages() {
+        let (endpoint, _request) = start_server("400 Bad Request").await;
+
+        let result = mailer(&endpoint).send(&message()).await;
+
+        assert_eq!(
+            result,
+            Err(NotificationError::DeliveryFailed(
+                "SES answered 400 Bad Request: {\"message\":\"nope\"}".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use todo::infrastructure::serialization::SerializationError;
+use todo::infrastructure::serialization::markdown::{export_todos, import_todos};
+use todo::{Todo, TodoState};
+
+#[test]
+fn test_markdown_round_trip_keeps_ids_and_states() {
+    // Arrange
+    let (todo, _) = Todo::new("Write docs".to_string()).unwrap();
+    let (mut started, _) = Todo::new("Review PR".to_string()).unwrap();
+    started.update_state(TodoState::InProgress).unwrap();
+    started.add_subtask("Read the diff".to_string()).unwrap();
+    let read = started.subtasks[0].id.to_string();
+    started.toggle_subtask(&read).unwrap();
+    started.add_subtask("Run the tests".to_string()).unwrap();
+    let subtasks = started.subtasks.clone();
+    let mut document = Vec::new();
+
+    // Act
+    export_todos(&mut document, &[todo, started]).unwrap();
+    let imported = import_todos(document.as_slice()).unwrap();
+
+    // Assert
+    let text = String::from_utf8(document).unwrap();
+    assert!(text.starts_with("- [ ] Write docs <!-- id: "));
+    assert!(text.contains("\n- [/] Review PR <!-- id: "));
+    let summary: Vec<(&str, TodoState)> = imported
+        .iter()
+        .map(|todo| (&*todo.description, todo.state))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("Write docs", TodoState::Todo),
+            ("Review PR", TodoState::InProgress)
+        ]
+    );
+    assert!(text.contains(&*imported[0].id));
+    assert!(text.contains(&format!("\n  - [x] Read the diff <!-- id: {} -->\n", read)));
+    assert_eq!(imported[1].subtasks, subtasks);
+    assert!(imported[0].subtasks.is_empty());
+}
+
+#[test]
+fn test_markdown_import_reads_readme_task_lists() {
+    // Arrange
+    let document = "# Roadmap\n\
+                    \n\
+                    - [x] Ship v1\n\
+                    * [ ] Plan v2\n  \
+                      - [X] Collect feedback <!-- id: feedback -->\n    \
+                        + [ ] Nested item\n\
+                    - [ ]\n\
+                    - [link](https://example.com)\n\
+                    - [?] Unknown marker\n\
+                    Some prose with [ ] brackets.\n";
+
+    // Act
+    let todos = import_todos(document.as_bytes()).unwrap();
+
+    // Assert
+    let summary: Vec<(&str, TodoState)> = todos
+        .iter()
+        .map(|todo| (&*todo.description, todo.state))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![("Ship v1", TodoState::Done), ("Plan v2", TodoState::Todo)]
+    );
+    let subtasks: Vec<(&str, bool)> = todos[1]
+        .subtasks
+        .iter()
+        .map(|subtask| (&*subtask.description, subtask.done))
+        .collect();
+    assert_eq!(
+        subtasks,
+        vec![("Collect feedback", true), ("Nested item", false)]
+    );
+    assert_eq!(&*todos[1].subtasks[0].id, "feedback");
+    let duplicate = "- [ ] A <!-- id: 1 -->\n- [ ] B <!-- id: 1 -->\n";
+    assert!(matches!(
+        import_todos(duplicate.as_bytes()),
+        Err(SerializationError::InvalidRecord { record: 2, .. })
+    ));
+    let duplicate_subtask = "- [ ] A\n  - [ ] B <!-- id: 1 -->\n  - [ ] C <!-- id: 1 -->\n";
+    assert!(matches!(
+        import_todos(duplicate_subtask.as_bytes()),
+        Err(SerializationError::InvalidRecord { record: 3, .. })
+    ));
+}
@@ -0,0 +1,110 @@
+#![cfg(feature = "serde")]
+
+use chrono::{TimeZone, Utc};
+use todo::application::auth::AuthError;
+use todo::application::messages::MessageCatalog;
+use todo::{TodoError, TodoEvent, TodoState};
+
+#[test]
+fn test_message_catalog_negotiates_the_preferred_locale() {
+    let catalog =
+        MessageCatalog::default().with_messages("fr", [("TODO_NOT_FOUND", "tâche introuvable")]);
+
+    assert_eq!(catalog.locales(), vec!["en", "fr", "vi"]);
+    assert_eq!(catalog.negotiate("vi-VN,en;q=0.8"), "vi");
+    assert_eq!(catalog.negotiate("de, fr-CA;q=0.9, vi;q=0.5"), "fr");
+    assert_eq!(catalog.negotiate("vi;q=0.5, FR;q=0.7"), "fr");
+    assert_eq!(catalog.negotiate("vi;q=0, de"), "en");
+    assert_eq!(catalog.negotiate("*"), "en");
+    assert_eq!(catalog.negotiate(""), "en");
+}
+
+#[test]
+fn test_message_catalog_describes_errors_and_events() {
+    // Arrange
+    let catalog =
+        MessageCatalog::default().with_messages("fr", [("TODO_NOT_FOUND", "tâche introuvable")]);
+    let at = Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+    let changed = TodoEvent::TodoStateChanged {
+        id: "1".into(),
+        from_state: TodoState::Todo,
+        to_state: TodoState::InProgress,
+        changed_at: at,
+    };
+
+    // Act & Assert
+    assert_eq!(
+        catalog.message("fr-CA", &TodoError::TodoNotFound),
+        "tâche introuvable"
+    );
+    // Missing from fr, so in the default locale
+    assert_eq!(
+        catalog.message("fr", &TodoError::EmptyDescription),
+        "todo description must not be empty"
+    );
+    assert_eq!(
+        catalog.message("vi", &TodoError::RepositoryError("disk full".to_string())),
+        "lỗi kho lưu trữ: disk full"
+    );
+    assert_eq!(
+        catalog.message(
+            "en",
+            &AuthError::InsufficientScope("todos:write".to_string())
+        ),
+        "missing scope todos:write"
+    );
+    assert_eq!(
+        catalog.message("en", &changed),
+        "Todo 1 moved from to do to in progress"
+    );
+    assert_eq!(
+        catalog.message("vi", &changed),
+        "Công việc 1 đã chuyển từ cần làm sang đang làm"
+    );
+    // Unknown codes fall back to the code itself
+    assert_eq!(
+        MessageCatalog::new("en").message("en", &TodoError::TodoNotFound),
+        "TODO_NOT_FOUND"
+    );
+}
+
+#[test]
+fn test_message_catalog_matches_the_error_messages_in_english() {
+    let catalog = MessageCatalog::default();
+    let todo_errors = [
+        TodoError::EmptyDescription,
+        TodoError::InvalidStateTransition,
+        TodoError::TodoNotFound,
+        TodoError::RepositoryError("disk full".to_string()),
+        TodoError::QuotaExceeded,
+    ];
+    let auth_errors = [
+        AuthError::MissingCredentials,
+        AuthError::InvalidToken("expired".to_string()),
+        AuthError::InsufficientScope("todos:write".to_string()),
+        AuthError::Forbidden("viewer".to_string()),
+        AuthError::Unavailable("timeout".to_string()),
+    ];
+
+    for err in todo_errors {
+        let problem = catalog.localize_problem("en", err.problem(), &err);
+        assert_eq!(problem, err.problem());
+    }
+    for err in auth_errors {
+        let problem = catalog.localize_problem("en", err.problem(), &err);
+        assert_eq!(problem, err.problem());
+    }
+}
+
+#[test]
+fn test_message_catalog_loads_json_and_keeps_unknown_placeholders() {
+    let catalog = MessageCatalog::new("en")
+        .with_json("en", r#"{ "REPOSITORY_ERROR": "{message} ({unknown})" }"#)
+        .unwrap();
+
+    assert_eq!(
+        catalog.message("en", &TodoError::RepositoryError("disk full".to_string())),
+        "disk full ({unknown})"
+    );
+    assert!(MessageCatalog::new("en").with_json("en", "[1]").is_err());
+}
@@ -0,0 +1,224 @@
+#![cfg(feature = "serde")]
+
+use todo::infrastructure::serialization::SerializationError;
+use todo::infrastructure::serialization::microsoft_todo::{read_delta_page, task_body};
+use todo::{Priority, TodoState};
+
+const PAGE: &str = r#"{
+  "@odata.context": "https://graph.microsoft.com/v1.0/$metadata#Collection(todoTask)",
+  "@odata.deltaLink": "https://graph.microsoft.com/v1.0/me/todo/lists/L1/tasks/delta?$deltatoken=t2",
+  "value": [
+    {"@odata.etag": "W/\"1\"", "id": "AAMk1", "title": "Write docs", "status": "notStarted", "importance": "high", "createdDateTime": "2024-01-02T03:04:05.1234567Z"},
+    {"id": "AAMk2", "title": "Review PR", "status": "waitingOnOthers", "importance": "normal", "createdDateTime": "2024-01-02T00:00:00Z", "dueDateTime": {"dateTime": "2024-01-05T09:30:00.0000000", "timeZone": "UTC"}},
+    {"id": "AAMk3", "title": "Buy milk", "status": "completed"},
+    {"id": "AAMk4", "@removed": {"reason": "deleted"}}
+  ]
+}"#;
+
+#[test]
+fn test_delta_page_maps_status_and_collects_removed_tasks() {
+    // Act
+    let page = read_delta_page(PAGE.as_bytes()).unwrap();
+
+    // Assert
+    let states: Vec<_> = page
+        .todos
+        .iter()
+        .map(|todo| (&*todo.id, todo.state))
+        .collect();
+    assert_eq!(
+        states,
+        vec![
+            ("AAMk1", TodoState::Todo),
+            ("AAMk2", TodoState::InProgress),
+            ("AAMk3", TodoState::Done),
+        ]
+    );
+    assert_eq!(&*page.todos[0].description, "Write docs");
+    assert_eq!(
+        page.todos[0].created_at.to_rfc3339(),
+        "2024-01-02T03:04:05.123456700+00:00"
+    );
+    let priorities: Vec<_> = page.todos.iter().map(|todo| todo.priority).collect();
+    assert_eq!(
+        priorities,
+        vec![Priority::High, Priority::Medium, Priority::Medium]
+    );
+    assert_eq!(
+        page.todos[1].due_at.unwrap().to_rfc3339(),
+        "2024-01-05T09:30:00+00:00"
+    );
+    assert_eq!(page.removed, vec!["AAMk4"]);
+    assert!(page.next_link.is_none());
+    assert!(page.delta_link.unwrap().ends_with("$deltatoken=t2"));
+    assert_eq!(
+        task_body(&page.todos[2]),
+        serde_json::json!({
+            "title": "Buy milk",
+            "status": "completed",
+            "importance": "normal",
+            "dueDateTime": null,
+        })
+    );
+}
+
+#[test]
+fn test_delta_page_rejects_invalid_tasks() {
+    let page = r#"{"value": [{"id": "A", "title": "One"}, {"id": "B", "title": "Two", "status": "archived"}]}"#;
+
+    let result = read_delta_page(page.as_bytes());
+
+    assert!(matches!(
+        result,
+        Err(SerializationError::InvalidRecord { record: 2, .. })
+    ));
+    assert!(matches!(
+        read_delta_page("[]".as_bytes()),
+        Err(SerializationError::Malformed(_))
+    ));
+}
+
+#[cfg(feature = "msgraph")]
+mod graph {
+    use serde_json::{Value, json};
+    use todo::domain::sync::RemoteTaskList;
+    use todo::infrastructure::remote_task_lists::MicrosoftTodoTaskList;
+    use todo::{Todo, TodoError};
+    use tokio::task::JoinHandle;
+
+    /// Answers one request per response, built from the server's endpoint, and returns the
+    /// heads and bodies of the requests
+    async fn start_server(
+        responses: impl FnOnce(&str) -> Vec<(&'static str, String)>,
+    ) -> (String, JoinHandle<Vec<(String, String)>>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let responses = responses(&endpoint);
+        let requests = tokio::spawn(async move {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut data = Vec::new();
+                let mut buffer = [0; 4096];
+                loop {
+                    let read = stream.read(&mut buffer).await.unwrap();
+                    data.extend_from_slice(&buffer[..read]);
+                    let text = String::from_utf8_lossy(&data).to_string();
+                    let Some(end) = text.find("\r\n\r\n") else {
+                        continue;
+                    };
+                    let head = text[..end].to_string();
+                    let length: usize = head
+                        .lines()
+                        .find_map(|line| line.strip_prefix("content-length: "))
+                        .map_or(0, |length| length.parse().unwrap());
+                    if data.len() >= end + 4 + length {
+                        requests.push((head, text[end + 4..].to_string()));
+                        break;
+                    }
+                }
+                let response = format!(
+                    "HTTP/1.1 {}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+            requests
+        });
+        (endpoint, requests)
+    }
+
+    #[tokio::test]
+    async fn test_task_list_pulls_deltas_and_pushes_tasks() {
+        // Arrange
+        let (endpoint, requests) = start_server(|endpoint| {
+            vec![
+                (
+                    "200 OK",
+                    json!({
+                        "@odata.nextLink": format!("{}/me/todo/lists/L1/tasks/delta?$skiptoken=s1", endpoint),
+                        "value": [{ "id": "AAMk1", "title": "Write docs" }],
+                    })
+                    .to_string(),
+                ),
+                (
+                    "200 OK",
+                    json!({
+                        "@odata.deltaLink": format!("{}/me/todo/lists/L1/tasks/delta?$deltatoken=t2", endpoint),
+                        "value": [{ "id": "AAMk2", "@removed": { "reason": "deleted" } }],
+                    })
+                    .to_string(),
+                ),
+                ("201 Created", json!({ "id": "AAMk3" }).to_string()),
+                ("200 OK", json!({ "id": "AAMk1" }).to_string()),
+            ]
+        })
+        .await;
+        let list = MicrosoftTodoTaskList::new("token", "L1").with_endpoint(&endpoint);
+        let (todo, _) = Todo::new("Call mom".to_string()).unwrap();
+
+        // Act
+        let changes = list.pull().await.unwrap();
+        let created = list.create(&todo).await.unwrap();
+        let updated = list.update("AAMk1", &todo).await.unwrap();
+        let requests = requests.await.unwrap();
+
+        // Assert
+        assert_eq!(&*changes.todos[0].id, "AAMk1");
+        assert_eq!(changes.removed, vec!["AAMk2"]);
+        assert!(
+            list.delta_link()
+                .unwrap()
+                .unwrap()
+                .ends_with("$deltatoken=t2")
+        );
+        assert_eq!(created.as_deref(), Some("AAMk3"));
+        assert!(updated);
+        let lines: Vec<_> = requests
+            .iter()
+            .map(|(head, _)| head.lines().next().unwrap())
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                "GET /me/todo/lists/L1/tasks/delta HTTP/1.1",
+                "GET /me/todo/lists/L1/tasks/delta?$skiptoken=s1 HTTP/1.1",
+                "POST /me/todo/lists/L1/tasks HTTP/1.1",
+                "PATCH /me/todo/lists/L1/tasks/AAMk1 HTTP/1.1",
+            ]
+        );
+        assert!(
+            requests
+                .iter()
+                .all(|(head, _)| head.contains("authorization: Bearer token"))
+        );
+        let body: Value = serde_json::from_str(&requests[2].1).unwrap();
+        assert_eq!(body["title"], "Call mom");
+    }
+
+    #[tokio::test]
+    async fn test_task_list_reports_refused_requests() {
+        let (endpoint, _requests) = start_server(|_| {
+            vec![(
+                "401 Unauthorized",
+                json!({ "error": { "code": "InvalidAuthenticationToken" } }).to_string(),
+            )]
+        })
+        .await;
+
+        let result = MicrosoftTodoTaskList::new("expired", "L1")
+            .with_endpoint(&endpoint)
+            .pull()
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(TodoError::RepositoryError(message))
+                if message.starts_with("Microsoft Graph answered 401")
+        ));
+    }
+}
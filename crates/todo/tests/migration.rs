@@ -0,0 +1,113 @@
+use std::sync::Arc;
+use todo::application::add_todo_handler::AddTodoHandler;
+use todo::application::change_todo_state_handler::ChangeTodoStateHandler;
+use todo::application::migration::{MigrateDataHandler, MigrationReport};
+use todo::infrastructure::event_stores::InMemoryEventStore;
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::{EventSink, EventStore, Todo, TodoError, TodoRepository, TodoState};
+
+/// A repository of `count` todos, the first one started, with their events in a store
+async fn source(count: usize) -> (Arc<InMemoryTodoRepository>, Arc<InMemoryEventStore>) {
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let store = Arc::new(InMemoryEventStore::new());
+    let add = AddTodoHandler::new(repository.clone()).with_event_sink(store.clone());
+    for index in 0..count {
+        add.new_todo(format!("Todo {}", index)).await.unwrap();
+    }
+    let first = repository.find_all().await.unwrap()[0].id.to_string();
+    ChangeTodoStateHandler::new(repository.clone())
+        .with_event_sink(store.clone())
+        .change_state(first, TodoState::InProgress)
+        .await
+        .unwrap();
+    (repository, store)
+}
+
+fn sorted(mut todos: Vec<Todo>) -> Vec<(String, String, TodoState)> {
+    todos.sort_by(|a, b| a.id.cmp(&b.id));
+    todos
+        .into_iter()
+        .map(|todo| {
+            (
+                todo.id.to_string(),
+                todo.description.to_string(),
+                todo.state,
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_migration_copies_every_todo_and_event_in_batches() {
+    // Arrange
+    let (repository, store) = source(7).await;
+    let target = Arc::new(InMemoryTodoRepository::new());
+    let target_store = Arc::new(InMemoryEventStore::new());
+    let handler = MigrateDataHandler::new(repository.clone(), target.clone())
+        .with_batch_size(3)
+        .with_events(store.clone(), target_store.clone());
+
+    // Act
+    let report = handler.migrate_data().await.unwrap();
+
+    // Assert
+    assert_eq!(
+        report,
+        MigrationReport {
+            todos: 7,
+            todos_copied: 7,
+            todos_present: 0,
+            events_copied: 8,
+            events_present: 0,
+        }
+    );
+    assert_eq!(
+        sorted(target.find_all().await.unwrap()),
+        sorted(repository.find_all().await.unwrap())
+    );
+    assert_eq!(target_store.find_all().unwrap(), store.find_all().unwrap());
+}
+
+#[tokio::test]
+async fn test_migration_resumes_where_an_interrupted_run_stopped() {
+    // Arrange
+    let (repository, store) = source(4).await;
+    let todos = repository.find_all().await.unwrap();
+    let events = store.find_all().unwrap();
+    let target = Arc::new(InMemoryTodoRepository::new());
+    target.save(&todos[1]).await.unwrap();
+    target.save(&todos[2]).await.unwrap();
+    let target_store = Arc::new(InMemoryEventStore::new());
+    target_store.record(&events[..3]).unwrap();
+    let handler = MigrateDataHandler::new(repository.clone(), target.clone())
+        .with_events(store.clone(), target_store.clone());
+
+    // Act
+    let report = handler.migrate_data().await.unwrap();
+    let again = handler.migrate_data().await.unwrap();
+
+    // Assert
+    assert_eq!((report.todos_copied, report.todos_present), (2, 2));
+    assert_eq!((report.events_copied, report.events_present), (2, 3));
+    assert_eq!((again.todos_copied, again.todos_present), (0, 4));
+    assert_eq!((again.events_copied, again.events_present), (0, 5));
+    assert_eq!(target_store.find_all().unwrap(), events);
+}
+
+#[tokio::test]
+async fn test_migration_refuses_a_target_store_with_other_events() {
+    // Arrange
+    let (repository, store) = source(2).await;
+    let (_, other) = Todo::new("Elsewhere".to_string()).unwrap();
+    let target_store = Arc::new(InMemoryEventStore::new());
+    target_store.record(&other).unwrap();
+    let handler = MigrateDataHandler::new(repository, Arc::new(InMemoryTodoRepository::new()))
+        .with_events(store, target_store.clone());
+
+    // Act
+    let result = handler.migrate_data().await;
+
+    // Assert
+    assert!(matches!(result, Err(TodoError::RepositoryError(_))));
+    assert_eq!(target_store.find_all().unwrap(), other);
+}
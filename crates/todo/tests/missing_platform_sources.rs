@@ -0,0 +1,18 @@
+// Run with `cargo test -p todo --no-default-features --test missing_platform_sources`
+#![cfg(not(any(feature = "std-clock", feature = "getrandom")))]
+
+use todo::{Clock, IdGenerator, SystemClock, UuidV4Generator};
+
+#[test]
+#[should_panic(expected = "install one with `set_platform_clock`")]
+fn test_reading_the_system_clock_without_an_installed_clock_panics() {
+    // Act
+    SystemClock.now();
+}
+
+#[test]
+#[should_panic(expected = "install one with `set_platform_random`")]
+fn test_generating_an_id_without_an_installed_random_source_panics() {
+    // Act
+    UuidV4Generator.next_id();
+}
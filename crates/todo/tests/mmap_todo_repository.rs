@@ -0,0 +1,140 @@
+#![cfg(feature = "mmap")]
+
+mod common;
+
+use futures::StreamExt;
+use std::fs::File;
+use todo::domain::blob::content_hash;
+use todo::infrastructure::repositories::todo::MmapTodoRepository;
+use todo::infrastructure::serialization::SerializationError;
+use todo::infrastructure::serialization::snapshot::{SnapshotView, write_snapshot};
+use todo::{Attachment, Priority, Todo, TodoError, TodoRepository, TodoState};
+
+#[tokio::test]
+async fn test_mmap_repository_reads_snapshot_in_place() {
+    // Arrange
+    let path = common::temp_path("todos.snapshot");
+    let (first, _) = Todo::new("First todo".to_string()).unwrap();
+    let (mut second, _) = Todo::new("Second todo, with ünïcode".to_string()).unwrap();
+    second.update_state(TodoState::InProgress).unwrap();
+    second
+        .set_due_date(second.created_at + chrono::Duration::days(1))
+        .unwrap();
+    second.set_priority(Priority::Urgent);
+    second.add_tag("work").unwrap();
+    second.add_tag("q3-report").unwrap();
+    second.add_subtask("Draft the outline".to_string()).unwrap();
+    second
+        .add_subtask("Ask ünïcode reviewers".to_string())
+        .unwrap();
+    let outline = second.subtasks[0].id.clone();
+    second.toggle_subtask(&outline).unwrap();
+    second.block_by(&first.id).unwrap();
+    second.block_by("ünïcode-blocker").unwrap();
+    second
+        .attach(Attachment {
+            content_hash: content_hash(b"outline").into(),
+            name: "ünïcode-outline.md".into(),
+            media_type: "text/markdown".into(),
+            size: 7,
+        })
+        .unwrap();
+    let todos = vec![first.clone(), second.clone()];
+    write_snapshot(File::create(&path).unwrap(), &todos).unwrap();
+
+    // Act
+    let repository = MmapTodoRepository::open(&path).unwrap();
+
+    // Assert
+    let found = repository.find_by_id(&second.id).await.unwrap().unwrap();
+    assert_eq!(found.description, second.description);
+    assert_eq!(found.state, TodoState::InProgress);
+    assert_eq!(found.created_at, second.created_at);
+    assert_eq!(found.due_at, second.due_at);
+    assert_eq!(found.priority, Priority::Urgent);
+    assert_eq!(found.tags, second.tags);
+    assert_eq!(found.subtasks, second.subtasks);
+    assert_eq!(found.blocked_by, second.blocked_by);
+    assert_eq!(found.attachments, second.attachments);
+    assert_eq!(
+        repository
+            .find_by_id(&first.id)
+            .await
+            .unwrap()
+            .unwrap()
+            .due_at,
+        None
+    );
+    assert!(repository.find_by_id("missing").await.unwrap().is_none());
+    let streamed: Vec<Todo> = repository.stream_all().map(Result::unwrap).collect().await;
+    assert_eq!(streamed.len(), 2);
+    assert_eq!(repository.find_all().await.unwrap().len(), 2);
+    assert!(matches!(
+        repository.save(&first).await,
+        Err(TodoError::RepositoryError(_))
+    ));
+    assert!(matches!(
+        repository.delete(&first.id).await,
+        Err(TodoError::RepositoryError(_))
+    ));
+
+    drop(repository);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_snapshot_view_rejects_invalid_input() {
+    assert!(matches!(
+        SnapshotView::new(b"not a snapshot"),
+        Err(SerializationError::Malformed(_))
+    ));
+
+    let mut newer = Vec::new();
+    write_snapshot(&mut newer, &[]).unwrap();
+    newer[4] = 8;
+    assert!(matches!(
+        SnapshotView::new(&newer),
+        Err(SerializationError::UnsupportedVersion(8))
+    ));
+
+    let (todo, _) = Todo::new("Truncated todo".to_string()).unwrap();
+    let mut truncated = Vec::new();
+    write_snapshot(&mut truncated, std::slice::from_ref(&todo)).unwrap();
+    truncated.truncate(truncated.len() - 1);
+    let view = SnapshotView::new(&truncated).unwrap();
+    assert!(matches!(
+        view.get(0),
+        Err(SerializationError::InvalidRecord { record: 1, .. })
+    ));
+
+    assert!(write_snapshot(Vec::new(), &[todo.clone(), todo]).is_err());
+}
+
+#[test]
+fn test_snapshot_view_reads_version_1_snapshots() {
+    // A version 1 header, one 32-byte entry for todo "a" created at 1000s, in `DONE`, and
+    // the strings "a" and "Old"
+    let mut bytes = b"HKTS".to_vec();
+    bytes.extend_from_slice(&1u32.to_le_bytes());
+    bytes.extend_from_slice(&1u32.to_le_bytes());
+    for value in [0u32, 1, 1, 3] {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    bytes.extend_from_slice(&1000i64.to_le_bytes());
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+    bytes.extend_from_slice(&[2, 0, 0, 0]);
+    bytes.extend_from_slice(b"aOld");
+
+    let todo = SnapshotView::new(&bytes).unwrap().get(0).unwrap();
+
+    assert_eq!(&*todo.id, "a");
+    assert_eq!(&*todo.description, "Old");
+    assert_eq!(todo.state, TodoState::Done);
+    assert_eq!(todo.created_at.timestamp(), 1000);
+    assert_eq!(todo.due_at, None);
+    assert_eq!(todo.priority, Priority::Medium);
+    assert!(todo.tags.is_empty());
+    assert!(todo.subtasks.is_empty());
+    assert!(todo.blocked_by.is_empty());
+    assert!(todo.attachments.is_empty());
+}
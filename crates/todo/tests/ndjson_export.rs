@@ -0,0 +1,41 @@
+#![cfg(feature = "serde")]
+
+use futures::TryStreamExt;
+use std::sync::Arc;
+use todo::application::export_todos_handler::ExportTodosHandler;
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::{Todo, TodoRepository};
+
+#[tokio::test]
+async fn test_ndjson_export_writes_one_todo_per_line() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    for description in ["Write docs", "Review PR"] {
+        let (todo, _) = Todo::new(description.to_string()).unwrap();
+        repository.save(&todo).await.unwrap();
+    }
+    let handler = ExportTodosHandler::new(repository.clone());
+    let mut output = Vec::new();
+
+    // Act
+    let count = handler.export_ndjson(&mut output).await.unwrap();
+
+    // Assert
+    let streamed: Vec<Todo> = repository.stream_all().try_collect().await.unwrap();
+    assert_eq!(count, 2);
+    assert_eq!(streamed.len(), 2);
+    let text = String::from_utf8(output).unwrap();
+    let lines: Vec<serde_json::Value> = text
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert!(text.ends_with('\n'));
+    assert_eq!(lines.len(), 2);
+    let mut descriptions: Vec<_> = lines
+        .iter()
+        .map(|line| line["description"].as_str().unwrap())
+        .collect();
+    descriptions.sort();
+    assert_eq!(descriptions, vec!["Review PR", "Write docs"]);
+    assert_eq!(lines[0]["state"], "TODO");
+}
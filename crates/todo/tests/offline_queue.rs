@@ -0,0 +1,210 @@
+mod common;
+
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use todo::domain::sync::{CommandQueue, TodoCommand};
+use todo::infrastructure::repositories::command_queue::InMemoryCommandQueue;
+use todo::infrastructure::repositories::todo::{InMemoryTodoRepository, OfflineTodoRepository};
+use todo::{SequentialIdGenerator, Todo, TodoError, TodoRepository, TodoState};
+
+/// A remote repository that fails with a `RepositoryError` while it is down
+#[derive(Default)]
+struct Remote {
+    todos: InMemoryTodoRepository,
+    down: AtomicBool,
+}
+
+impl Remote {
+    fn set_down(&self, down: bool) {
+        self.down.store(down, Ordering::SeqCst);
+    }
+
+    fn check(&self) -> Result<(), TodoError> {
+        if self.down.load(Ordering::SeqCst) {
+            return Err(TodoError::RepositoryError("connection refused".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TodoRepository for Remote {
+    async fn save(&self, todo: &Todo) -> Result<(), TodoError> {
+        self.check()?;
+        self.todos.save(todo).await
+    }
+
+    async fn find_by_id(&self, id: &str) -> Result<Option<Todo>, TodoError> {
+        self.check()?;
+        self.todos.find_by_id(id).await
+    }
+
+    async fn find_all(&self) -> Result<Vec<Todo>, TodoError> {
+        self.check()?;
+        self.todos.find_all().await
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), TodoError> {
+        self.check()?;
+        self.todos.delete(id).await
+    }
+}
+
+fn setup() -> (
+    Arc<Remote>,
+    Arc<InMemoryCommandQueue>,
+    OfflineTodoRepository,
+) {
+    let remote = Arc::new(Remote::default());
+    let queue = Arc::new(InMemoryCommandQueue::new());
+    let repository = OfflineTodoRepository::new(
+        remote.clone(),
+        Arc::new(InMemoryTodoRepository::new()),
+        queue.clone(),
+    )
+    .with_id_generator(Arc::new(SequentialIdGenerator::new("cmd-")));
+    (remote, queue, repository)
+}
+
+fn todo(description: &str) -> Todo {
+    Todo::new(description.to_string()).unwrap().0
+}
+
+#[tokio::test]
+async fn test_changes_made_offline_are_queued_and_replayed_in_order() {
+    // Arrange
+    let (remote, queue, repository) = setup();
+    let mut kept = todo("Write docs");
+    let dropped = todo("Old idea");
+    repository.save(&kept).await.unwrap();
+    repository.save(&dropped).await.unwrap();
+    remote.set_down(true);
+
+    // Act
+    let added = todo("Ship it");
+    repository.save(&added).await.unwrap();
+    kept.update_state(TodoState::InProgress).unwrap();
+    repository.save(&kept).await.unwrap();
+    repository.delete(&dropped.id).await.unwrap();
+    let offline_reads = repository.find_all().await.unwrap().len();
+    let keys: Vec<_> = queue
+        .find_all()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|queued| queued.key.to_string())
+        .collect();
+    let still_down = repository.sync().await;
+    remote.set_down(false);
+    let report = repository.sync().await.unwrap();
+
+    // Assert
+    assert_eq!(offline_reads, 2);
+    assert_eq!(keys, ["cmd-1", "cmd-2", "cmd-3"]);
+    assert!(matches!(still_down, Err(TodoError::RepositoryError(_))));
+    assert_eq!((report.applied, report.already_applied), (3, 0));
+    assert!(report.rejected.is_empty());
+    assert_eq!(repository.pending().await.unwrap(), 0);
+    let stored = remote.todos.find_by_id(&kept.id).await.unwrap().unwrap();
+    assert_eq!(stored.state, TodoState::InProgress);
+    assert!(remote.todos.find_by_id(&added.id).await.unwrap().is_some());
+    assert_eq!(remote.todos.find_by_id(&dropped.id).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_replay_skips_commands_the_remote_already_reflects() {
+    // Arrange
+    let (remote, _queue, repository) = setup();
+    remote.set_down(true);
+    let added = todo("Write docs");
+    repository.save(&added).await.unwrap();
+    remote.set_down(false);
+    // An earlier sync sent the command, then stopped before dropping it from the queue
+    remote.todos.save(&added).await.unwrap();
+
+    // Act
+    let report = repository.sync().await.unwrap();
+
+    // Assert
+    assert_eq!((report.applied, report.already_applied), (0, 1));
+    assert_eq!(repository.pending().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_replay_rejects_commands_whose_todo_changed_remotely() {
+    // Arrange
+    let (remote, _queue, repository) = setup();
+    let mut local = todo("Write docs");
+    repository.save(&local).await.unwrap();
+    let mut elsewhere = local.clone();
+    remote.set_down(true);
+    local.update_state(TodoState::InProgress).unwrap();
+    repository.save(&local).await.unwrap();
+    remote.set_down(false);
+    // Another client renamed the todo in the meantime
+    elsewhere.description = "Write the docs".into();
+    remote.todos.save(&elsewhere).await.unwrap();
+
+    // Act
+    let report = repository.sync().await.unwrap();
+    let after = repository.find_by_id(&local.id).await.unwrap().unwrap();
+
+    // Assert
+    assert_eq!(report.applied, 0);
+    assert_eq!(report.rejected.len(), 1);
+    assert_eq!(
+        report.rejected[0].command.command,
+        TodoCommand::Save(local.clone())
+    );
+    assert_eq!(
+        report.rejected[0]
+            .remote
+            .as_ref()
+            .map(|todo| todo.description.clone()),
+        Some("Write the docs".into())
+    );
+    assert_eq!(&*after.description, "Write the docs");
+    assert_eq!(after.state, TodoState::Todo);
+}
+
+#[cfg(feature = "serde")]
+#[tokio::test]
+async fn test_file_queue_keeps_commands_across_instances() {
+    use todo::domain::sync::QueuedCommand;
+    use todo::infrastructure::repositories::command_queue::FileCommandQueue;
+
+    let path = common::temp_path("command-queue.json");
+    let base = todo("Write docs");
+    let mut changed = base.clone();
+    changed.update_state(TodoState::InProgress).unwrap();
+    let save = QueuedCommand {
+        key: "cmd-1".into(),
+        command: TodoCommand::Save(changed.clone()),
+        base: Some(base.clone()),
+        queued_at: base.created_at,
+    };
+    let delete = QueuedCommand {
+        key: "cmd-2".into(),
+        command: TodoCommand::Delete(base.id.clone()),
+        base: None,
+        queued_at: base.created_at,
+    };
+    let queue = FileCommandQueue::new(&path);
+    queue.push(&save).await.unwrap();
+    queue.push(&delete).await.unwrap();
+
+    let reopened = FileCommandQueue::new(&path);
+    let all = reopened.find_all().await.unwrap();
+    let removed = reopened.remove("cmd-1").await.unwrap();
+    let removed_again = reopened.remove("cmd-1").await.unwrap();
+
+    assert_eq!(all.len(), 2);
+    assert_eq!(all[1], delete);
+    assert_eq!(all[0].key, save.key);
+    assert_eq!(all[0].command.todo_id(), &*base.id);
+    assert!(removed);
+    assert!(!removed_again);
+    assert_eq!(reopened.find_all().await.unwrap(), vec![delete]);
+    std::fs::remove_file(&path).unwrap();
+}
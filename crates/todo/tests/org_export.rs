@@ -0,0 +1,32 @@
+use chrono::{TimeZone, Utc};
+use todo::infrastructure::serialization::org::export_todos;
+use todo::{Todo, TodoState};
+
+#[test]
+fn test_org_export_writes_headlines_with_keywords() {
+    // Arrange
+    let (mut started, _) = Todo::new("Write\nthe  docs".to_string()).unwrap();
+    started.update_state(TodoState::InProgress).unwrap();
+    started.created_at = Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+    started
+        .set_due_date(Utc.with_ymd_and_hms(2024, 1, 10, 17, 0, 0).unwrap())
+        .unwrap();
+    started.add_tag("work").unwrap();
+    started.add_tag("follow-up").unwrap();
+    let (mut done, _) = Todo::new("Buy milk".to_string()).unwrap();
+    done.update_state(TodoState::InProgress).unwrap();
+    done.update_state(TodoState::Done).unwrap();
+    let mut document = Vec::new();
+
+    // Act
+    export_todos(&mut document, &[started, done]).unwrap();
+
+    // Assert
+    let text = String::from_utf8(document).unwrap();
+    assert!(text.starts_with("#+TITLE: Todos\n#+TODO: TODO DOING | DONE\n"));
+    assert!(text.contains(
+        "\n* DOING Write the docs :work:follow_up:\n  DEADLINE: <2024-01-10 Wed 17:00>\n  :PROPERTIES:\n"
+    ));
+    assert!(text.contains("  :CREATED:  [2024-01-02 Tue 03:04]\n  :END:\n"));
+    assert!(text.contains("\n* DONE Buy milk\n  :PROPERTIES:\n"));
+}
@@ -0,0 +1,68 @@
+// Also run with `cargo test -p todo --no-default-features --test platform`, where the
+// installed sources are the only ones
+use chrono::{DateTime, TimeZone, Utc};
+use std::sync::Once;
+use todo::{
+    Clock, FixedClock, IdGenerator, SystemClock, Todo, UlidGenerator, UuidV4Generator,
+    UuidV7Generator, set_platform_clock, set_platform_random,
+};
+
+fn start() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap()
+}
+
+fn fill(bytes: &mut [u8]) {
+    bytes.fill(0x42);
+}
+
+/// Installs the clock and random source once for the tests of this process
+fn install() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        assert!(set_platform_clock(Box::new(FixedClock::new(start()))).is_ok());
+        assert!(set_platform_random(fill).is_ok());
+    });
+}
+
+#[test]
+fn test_installed_sources_replace_the_system_ones() {
+    install();
+
+    assert_eq!(SystemClock.now(), start());
+    assert_eq!(
+        Todo::new("Write docs".to_string()).unwrap().0.created_at,
+        start()
+    );
+    assert_eq!(
+        UuidV4Generator.next_id(),
+        "42424242-4242-4242-8242-424242424242"
+    );
+}
+
+#[test]
+fn test_sortable_ids_count_up_within_a_millisecond_of_the_installed_clock() {
+    install();
+    let millis = format!("{:012x}", start().timestamp_millis());
+
+    let uuids = [UuidV7Generator.next_id(), UuidV7Generator.next_id()];
+    let ulids = UlidGenerator::new();
+    let ulids = [ulids.next_id(), ulids.next_id()];
+
+    for uuid in &uuids {
+        assert_eq!(uuid.replace('-', "")[..12], millis);
+    }
+    assert!(uuids[0] < uuids[1]);
+    for ulid in &ulids {
+        let ulid = ulid::Ulid::from_string(ulid).unwrap();
+        assert_eq!(ulid.timestamp_ms(), start().timestamp_millis() as u64);
+    }
+    assert!(ulids[0] < ulids[1]);
+}
+
+#[test]
+fn test_sources_are_installed_once() {
+    install();
+
+    assert!(set_platform_clock(Box::new(SystemClock)).is_err());
+    assert!(set_platform_random(fill).is_err());
+}
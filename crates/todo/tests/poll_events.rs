@@ -0,0 +1,100 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use std::sync::Arc;
+use todo::application::poll_events_handler::{EventCursor, PollEventsHandler};
+use todo::infrastructure::event_stores::InMemoryEventStore;
+use todo::{EventSink, EventStore, TodoEvent};
+
+fn at(minute: i64) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 1, 1, 9, 0, 0).unwrap() + Duration::minutes(minute)
+}
+
+fn created(id: &str, minute: i64) -> TodoEvent {
+    TodoEvent::TodoCreated {
+        id: id.into(),
+        description: format!("Todo {}", id).into(),
+        created_at: at(minute),
+    }
+}
+
+fn ids(events: &[TodoEvent]) -> Vec<&str> {
+    events.iter().map(TodoEvent::todo_id).collect()
+}
+
+#[tokio::test]
+async fn test_poll_events_pages_through_the_store_in_order() {
+    // Arrange
+    let store = Arc::new(InMemoryEventStore::new());
+    store
+        .record(&[created("b", 2), created("a", 1), created("c", 3)])
+        .unwrap();
+    let handler = PollEventsHandler::new(store);
+
+    // Act
+    let first = handler.poll_events(None, 2).await.unwrap();
+    let second = handler.poll_events(first.cursor.as_ref(), 2).await.unwrap();
+    let caught_up = handler
+        .poll_events(second.cursor.as_ref(), 2)
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(ids(&first.events), ["a", "b"]);
+    assert_eq!(ids(&second.events), ["c"]);
+    assert!(caught_up.events.is_empty());
+    assert_eq!(caught_up.cursor, second.cursor);
+}
+
+#[tokio::test]
+async fn test_poll_events_splits_events_of_the_same_time_across_pages() {
+    // Arrange
+    let store = Arc::new(InMemoryEventStore::new());
+    store
+        .record(&[created("a", 1), created("b", 1), created("c", 1)])
+        .unwrap();
+    let handler = PollEventsHandler::new(store);
+
+    // Act
+    let first = handler.poll_events(None, 2).await.unwrap();
+    let second = handler.poll_events(first.cursor.as_ref(), 2).await.unwrap();
+
+    // Assert
+    assert_eq!(ids(&first.events), ["a", "b"]);
+    assert_eq!(ids(&second.events), ["c"]);
+}
+
+#[tokio::test]
+async fn test_poll_events_resumes_from_a_persisted_cursor() {
+    // Arrange
+    let store = Arc::new(InMemoryEventStore::new());
+    store.record(&[created("a", 1)]).unwrap();
+    let cursor = PollEventsHandler::new(store.clone())
+        .poll_events(None, 10)
+        .await
+        .unwrap()
+        .cursor
+        .unwrap()
+        .to_string();
+    store.record(&[created("b", 2)]).unwrap();
+    store.compact(at(2)).unwrap();
+
+    // Act
+    let cursor = EventCursor::parse(&cursor).unwrap();
+    let page = PollEventsHandler::new(store)
+        .poll_events(Some(&cursor), 10)
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(cursor.to_string(), "1@2025-01-01T09:01:00.000000000Z");
+    assert_eq!(ids(&page.events), ["b"]);
+}
+
+#[test]
+fn test_event_cursor_rejects_malformed_text() {
+    for text in ["", "1", "x@2025-01-01T09:00:00Z", "1@yesterday"] {
+        assert_eq!(
+            EventCursor::parse(text),
+            Err(format!("invalid event cursor {:?}", text))
+        );
+    }
+}
@@ -0,0 +1,109 @@
+mod common;
+
+use common::at;
+use std::sync::Arc;
+use todo::application::get_todos_handler::GetTodosHandler;
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::{FixedClock, Priority, Todo, TodoEvent, TodoRepository};
+
+#[test]
+fn test_priorities_are_ordered_from_low_to_urgent() {
+    assert!(Priority::Low < Priority::Medium);
+    assert!(Priority::Medium < Priority::High);
+    assert!(Priority::High < Priority::Urgent);
+    assert_eq!(Priority::default(), Priority::Medium);
+}
+
+#[test]
+fn test_priority_parses_its_name() {
+    for priority in Priority::ALL {
+        assert_eq!(Priority::parse(priority.name()), Ok(priority));
+    }
+    assert_eq!(Priority::parse("urgent"), Ok(Priority::Urgent));
+    assert!(Priority::parse("critical").is_err());
+}
+
+#[test]
+fn test_set_priority_emits_an_event_when_it_changes() {
+    // Arrange
+    let clock = FixedClock::new(at(0));
+    let (mut todo, _) = Todo::new_with_clock("Write docs".to_string(), &clock).unwrap();
+    clock.set(at(1));
+
+    // Act
+    let raised = todo.set_priority_with_clock(Priority::High, &clock);
+    let unchanged = todo.set_priority_with_clock(Priority::High, &clock);
+
+    // Assert
+    assert_eq!(todo.priority, Priority::High);
+    assert_eq!(
+        raised,
+        [TodoEvent::TodoPriorityChanged {
+            id: todo.id.clone(),
+            from_priority: Priority::Medium,
+            to_priority: Priority::High,
+            changed_at: at(1),
+        }]
+    );
+    assert!(unchanged.is_empty());
+}
+
+#[test]
+fn test_replay_restores_the_priority() {
+    // Arrange
+    let (mut todo, mut events) = Todo::new("Write docs".to_string()).unwrap();
+    events.extend(todo.set_priority(Priority::Low));
+    events.extend(todo.set_priority(Priority::Urgent));
+
+    // Act
+    let replayed = Todo::replay(&events).unwrap();
+
+    // Assert
+    assert_eq!(replayed.priority, Priority::Urgent);
+}
+
+#[tokio::test]
+async fn test_get_todos_by_priority_lists_the_most_urgent_first() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let clock = FixedClock::new(at(0));
+    let mut todos = Vec::new();
+    for (hour, priority) in [
+        (0, Priority::Low),
+        (1, Priority::High),
+        (2, Priority::Urgent),
+        (3, Priority::Medium),
+        (4, Priority::High),
+    ] {
+        clock.set(at(hour));
+        let (mut todo, _) = Todo::new_with_clock(format!("Todo {}", hour), &clock).unwrap();
+        todo.set_priority(priority);
+        repository.save(&todo).await.unwrap();
+        todos.push(todo);
+    }
+    let handler = GetTodosHandler::new(repository);
+
+    // Act
+    let sorted = handler.get_todos_by_priority().await.unwrap();
+
+    // Assert
+    let descriptions: Vec<_> = sorted.iter().map(|todo| &*todo.description).collect();
+    assert_eq!(
+        descriptions,
+        ["Todo 2", "Todo 1", "Todo 4", "Todo 3", "Todo 0"]
+    );
+}
+
+#[cfg(feature = "serde")]
+#[tokio::test]
+async fn test_file_repository_persists_the_priority() {
+    // Arrange
+    let (mut todo, _) = Todo::new("Write docs".to_string()).unwrap();
+    todo.set_priority(Priority::Low);
+
+    // Act
+    let found = common::reloaded_from_file(&todo).await;
+
+    // Assert
+    assert_eq!(found.priority, Priority::Low);
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "proptest")]
+
+use proptest::prelude::*;
+use std::collections::HashSet;
+use todo::arbitrary::{EventStream, TodoHistory, transitions};
+use todo::{Todo, TodoEvent, TodoState};
+
+proptest! {
+    #[test]
+    fn test_replaying_a_history_reproduces_the_todo(history: TodoHistory) {
+        prop_assert_eq!(Todo::replay(&history.events)?, history.todo);
+    }
+
+    #[test]
+    fn test_replaying_a_stream_reproduces_every_todo(stream: EventStream) {
+        let ids: HashSet<&str> = stream.todos.iter().map(|todo| &*todo.id).collect();
+        prop_assert_eq!(ids.len(), stream.todos.len());
+        for todo in &stream.todos {
+            let events: Vec<TodoEvent> = stream
+                .events
+                .iter()
+                .skip_while(|event| event.todo_id() != &*todo.id)
+                .cloned()
+                .collect();
+            prop_assert_eq!(&Todo::replay(&events)?, todo);
+        }
+    }
+
+    #[test]
+    fn test_stream_events_are_ordered_by_time(stream: EventStream) {
+        prop_assert!(stream
+            .events
+            .windows(2)
+            .all(|pair| pair[0].occurred_at() <= pair[1].occurred_at()));
+    }
+
+    #[test]
+    fn test_generated_transitions_are_all_allowed(
+        (from, states) in any::<TodoState>()
+            .prop_flat_map(|from| (Just(from), transitions(from, 20))),
+    ) {
+        let mut todo = Todo::new("Transitions".to_string()).unwrap().0;
+        todo.state = from;
+        for state in states {
+            prop_assert!(todo.update_state(state).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_arbitrary_todos_have_valid_descriptions(todo: Todo) {
+        prop_assert!(!todo.description.trim().is_empty());
+        prop_assert!(Todo::new(todo.description.to_string()).is_ok());
+    }
+
+    #[test]
+    fn test_arbitrary_state_changes_are_allowed_transitions(event: TodoEvent) {
+        if let TodoEvent::TodoStateChanged { from_state, to_state, .. } = event {
+            prop_assert!(from_state.can_transition_to(to_state));
+        }
+    }
+}
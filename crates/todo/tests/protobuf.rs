@@ -0,0 +1,124 @@
+#![cfg(feature = "protobuf")]
+
+use prost::Message;
+use todo::domain::blob::content_hash;
+use todo::infrastructure::serialization::SerializationError;
+use todo::infrastructure::serialization::protobuf::{
+    decode_event, decode_todo, encode_event, encode_todo, proto,
+};
+use todo::{Attachment, Priority, Todo, TodoState};
+
+#[test]
+fn test_todo_and_events_round_trip_through_protobuf() {
+    // Arrange
+    let (mut todo, created) = Todo::new("Write docs".to_string()).unwrap();
+    let changed = todo.update_state(TodoState::InProgress).unwrap();
+    let due = todo
+        .set_due_date(todo.created_at + chrono::Duration::days(1))
+        .unwrap();
+    let prioritized = todo.set_priority(Priority::Urgent);
+    let tagged = todo.add_tag("work").unwrap();
+    let untagged = todo.remove_tag("work").unwrap();
+    todo.add_tag("docs").unwrap();
+    let added = todo.add_subtask("Outline".to_string()).unwrap();
+    let subtask_id = todo.subtasks[0].id.clone();
+    let toggled = todo.toggle_subtask(&subtask_id).unwrap();
+    let removed = todo.remove_subtask(&subtask_id);
+    todo.add_subtask("Review".to_string()).unwrap();
+    let renamed = todo
+        .update_description("Write the docs".to_string())
+        .unwrap();
+    let blocked = todo.block_by("outline").unwrap();
+    let unblocked = todo.unblock("outline");
+    todo.block_by("review").unwrap();
+    let outline = Attachment {
+        content_hash: content_hash(b"outline").into(),
+        name: "outline.md".into(),
+        media_type: "text/markdown".into(),
+        size: 7,
+    };
+    let attached = todo.attach(outline.clone()).unwrap();
+    let detached = todo.detach(&outline.content_hash);
+    todo.attach(Attachment {
+        content_hash: content_hash(b"review").into(),
+        ..outline
+    })
+    .unwrap();
+
+    // Act
+    let decoded = decode_todo(&encode_todo(&todo)).unwrap();
+    let events: Vec<_> = created
+        .iter()
+        .chain(&changed)
+        .chain(&due)
+        .chain(&prioritized)
+        .chain(&tagged)
+        .chain(&untagged)
+        .chain(&added)
+        .chain(&toggled)
+        .chain(&removed)
+        .chain(&renamed)
+        .chain(&blocked)
+        .chain(&unblocked)
+        .chain(&attached)
+        .chain(&detached)
+        .map(|event| decode_event(&encode_event(event)).unwrap())
+        .collect();
+
+    // Assert
+    assert_eq!(decoded.id, todo.id);
+    assert_eq!(&*decoded.description, "Write the docs");
+    assert_eq!(decoded.state, TodoState::InProgress);
+    assert_eq!(decoded.created_at, todo.created_at);
+    assert_eq!(decoded.due_at, todo.due_at);
+    assert_eq!(decoded.priority, Priority::Urgent);
+    assert_eq!(decoded.tags, todo.tags);
+    assert_eq!(decoded.subtasks, todo.subtasks);
+    assert_eq!(decoded.blocked_by, todo.blocked_by);
+    assert_eq!(decoded.attachments, todo.attachments);
+    assert_eq!(
+        events,
+        vec![
+            created[0].clone(),
+            changed[0].clone(),
+            due[0].clone(),
+            prioritized[0].clone(),
+            tagged[0].clone(),
+            untagged[0].clone(),
+            added[0].clone(),
+            toggled[0].clone(),
+            removed[0].clone(),
+            renamed[0].clone(),
+            blocked[0].clone(),
+            unblocked[0].clone(),
+            attached[0].clone(),
+            detached[0].clone()
+        ]
+    );
+}
+
+#[test]
+fn test_protobuf_decoding_rejects_incomplete_messages() {
+    let unspecified = proto::Todo {
+        id: "1".to_string(),
+        description: "Write docs".to_string(),
+        state: proto::TodoState::Unspecified.into(),
+        created_at: None,
+        due_at: None,
+        priority: proto::Priority::Unspecified.into(),
+        tags: Vec::new(),
+        subtasks: Vec::new(),
+        blocked_by: Vec::new(),
+        attachments: Vec::new(),
+    };
+    assert!(matches!(
+        decode_todo(&unspecified.encode_to_vec()),
+        Err(SerializationError::Malformed(_))
+    ));
+    // An event type added by a newer writer decodes to an empty oneof
+    assert!(matches!(
+        decode_event(&proto::TodoEvent { event: None }.encode_to_vec()),
+        Err(SerializationError::Malformed(_))
+    ));
+    assert!(decode_event(&[0xff]).is_err());
+}
@@ -0,0 +1,101 @@
+use std::sync::Arc;
+use todo::application::add_todo_handler::AddTodoHandler;
+use todo::application::change_todo_state_handler::ChangeTodoStateHandler;
+use todo::application::quota::{GetQuotaUsageHandler, Quota, QuotaPolicy, QuotaUsage};
+use todo::infrastructure::repositories::todo::{InMemoryTodoRepository, TenantTodoRepository};
+use todo::{TenantId, TodoError, TodoRepository, TodoState};
+
+fn quota(max_open_todos: Option<u32>, max_todos: Option<u32>) -> Quota {
+    Quota {
+        max_open_todos,
+        max_todos,
+    }
+}
+
+async fn finish(repository: Arc<InMemoryTodoRepository>, id: String) {
+    let handler = ChangeTodoStateHandler::new(repository);
+    handler
+        .change_state(id.clone(), TodoState::InProgress)
+        .await
+        .unwrap();
+    handler.change_state(id, TodoState::Done).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_open_todos_are_limited_until_one_is_done() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let handler = AddTodoHandler::new(repository.clone()).with_quota(quota(Some(2), Some(3)));
+    let (_, first) = handler.new_todo("First".to_string()).await.unwrap();
+    handler.new_todo("Second".to_string()).await.unwrap();
+
+    // Act
+    let refused = handler.new_todo("Third".to_string()).await;
+    finish(repository.clone(), first[0].todo_id().to_string()).await;
+    let added = handler.new_todo("Third".to_string()).await;
+    let over_total = handler.new_todo("Fourth".to_string()).await;
+
+    // Assert
+    assert_eq!(refused, Err(TodoError::QuotaExceeded));
+    assert!(added.is_ok());
+    assert_eq!(over_total, Err(TodoError::QuotaExceeded));
+    assert_eq!(repository.find_all().await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_tenant_lists_count_only_their_todos() {
+    // Arrange
+    let inner: Arc<dyn TodoRepository> = Arc::new(InMemoryTodoRepository::new());
+    let acme = TenantId::new("acme").unwrap();
+    let globex = TenantId::new("globex").unwrap();
+    let policy = QuotaPolicy::new(quota(None, Some(1))).with_list(globex.clone(), Quota::default());
+    let add = |tenant: &TenantId| {
+        AddTodoHandler::new(TenantTodoRepository::new(inner.clone(), tenant.clone()))
+            .with_quota(policy.for_list(tenant))
+    };
+
+    // Act
+    let acme_first = add(&acme).new_todo("Acme".to_string()).await;
+    let acme_second = add(&acme).new_todo("Acme again".to_string()).await;
+    let globex_first = add(&globex).new_todo("Globex".to_string()).await;
+    let globex_second = add(&globex).new_todo("Globex again".to_string()).await;
+
+    // Assert
+    assert!(acme_first.is_ok());
+    assert_eq!(acme_second, Err(TodoError::QuotaExceeded));
+    assert!(globex_first.is_ok());
+    assert!(globex_second.is_ok());
+}
+
+#[tokio::test]
+async fn test_usage_reports_the_remaining_capacity() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let add = AddTodoHandler::new(repository.clone());
+    let (_, done) = add.new_todo("Done".to_string()).await.unwrap();
+    add.new_todo("Open".to_string()).await.unwrap();
+    finish(repository.clone(), done[0].todo_id().to_string()).await;
+    let handler = GetQuotaUsageHandler::new(repository.clone(), quota(Some(5), Some(3)));
+
+    // Act
+    let usage = handler.usage().await.unwrap();
+
+    // Assert
+    assert_eq!(usage.open_todos, 1);
+    assert_eq!(usage.todos, 2);
+    assert_eq!(usage.remaining_open_todos(), Some(4));
+    assert_eq!(usage.remaining_todos(), Some(1));
+    assert_eq!(usage.remaining(), Some(1));
+}
+
+#[test]
+fn test_unlimited_quota_has_no_remaining_count() {
+    let usage = QuotaUsage::new(&[], Quota::default());
+
+    assert!(!usage.quota.is_limited());
+    assert_eq!(usage.remaining(), None);
+    assert_eq!(
+        QuotaUsage::new(&[], quota(Some(0), None)).remaining(),
+        Some(0)
+    );
+}
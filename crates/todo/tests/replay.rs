@@ -0,0 +1,167 @@
+mod common;
+
+use chrono::{TimeDelta, TimeZone, Utc};
+use std::sync::{Arc, Mutex};
+use todo::application::activity_feed::ActivityFeed;
+use todo::application::add_todo_handler::AddTodoHandler;
+use todo::application::change_todo_state_handler::ChangeTodoStateHandler;
+use todo::application::replay::{ReplayEventsHandler, ReplayProgress, TodoProjection};
+use todo::infrastructure::event_stores::InMemoryEventStore;
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::{EventSink, EventStore, FixedClock, TodoEvent, TodoRepository, TodoState};
+
+/// Records two todos, one started and then done, in `store` through `repository`
+async fn record_changes(repository: Arc<InMemoryTodoRepository>, store: Arc<dyn EventStore>) {
+    let add = AddTodoHandler::new(repository.clone()).with_event_sink(store.clone());
+    let (_, events) = add.new_todo("Write docs".to_string()).await.unwrap();
+    add.new_todo("Ship it".to_string()).await.unwrap();
+    let change = ChangeTodoStateHandler::new(repository).with_event_sink(store);
+    let id = events[0].todo_id().to_string();
+    change
+        .change_state(id.clone(), TodoState::InProgress)
+        .await
+        .unwrap();
+    change.change_state(id, TodoState::Done).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_replay_rebuilds_a_repository_and_can_run_again() {
+    // Arrange
+    let source = Arc::new(InMemoryTodoRepository::new());
+    let store = Arc::new(InMemoryEventStore::new());
+    record_changes(source.clone(), store.clone()).await;
+    let rebuilt = Arc::new(InMemoryTodoRepository::new());
+    let projection = TodoProjection::new(rebuilt.clone());
+    let handler = ReplayEventsHandler::new(store);
+
+    // Act
+    let first = handler
+        .replay_events(None, None, &projection)
+        .await
+        .unwrap();
+    let second = handler
+        .replay_events(None, None, &projection)
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!((first, second), (4, 4));
+    let mut expected = source.find_all().await.unwrap();
+    let mut actual = rebuilt.find_all().await.unwrap();
+    expected.sort_by(|a, b| a.id.cmp(&b.id));
+    actual.sort_by(|a, b| a.id.cmp(&b.id));
+    assert_eq!(actual, expected);
+}
+
+#[tokio::test]
+async fn test_replay_applies_only_the_events_in_range_and_reports_progress() {
+    // Arrange
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let clock = Arc::new(FixedClock::new(start));
+    let source = Arc::new(InMemoryTodoRepository::new());
+    let store = Arc::new(InMemoryEventStore::new());
+    let (_, events) = AddTodoHandler::new(source.clone())
+        .with_clock(clock.clone())
+        .with_event_sink(store.clone())
+        .new_todo("Write docs".to_string())
+        .await
+        .unwrap();
+    let id = events[0].todo_id().to_string();
+    let change = ChangeTodoStateHandler::new(source.clone())
+        .with_clock(clock.clone())
+        .with_event_sink(store.clone());
+    clock.set(start + TimeDelta::days(1));
+    change
+        .change_state(id.clone(), TodoState::InProgress)
+        .await
+        .unwrap();
+    clock.set(start + TimeDelta::days(2));
+    change
+        .change_state(id.clone(), TodoState::Done)
+        .await
+        .unwrap();
+    let rebuilt = Arc::new(InMemoryTodoRepository::new());
+    let projection = TodoProjection::new(rebuilt.clone());
+    let progress = Arc::new(Mutex::new(Vec::new()));
+    let reported = progress.clone();
+    let handler = ReplayEventsHandler::new(store)
+        .with_progress(Arc::new(move |step| reported.lock().unwrap().push(step)));
+
+    // Act
+    let created = handler
+        .replay_events(None, Some(start + TimeDelta::hours(1)), &projection)
+        .await
+        .unwrap();
+    let rest = handler
+        .replay_events(Some(start + TimeDelta::days(1)), None, &projection)
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!((created, rest), (1, 2));
+    let todo = rebuilt.find_by_id(&id).await.unwrap().unwrap();
+    assert_eq!(todo.state, TodoState::Done);
+    assert_eq!(
+        *progress.lock().unwrap(),
+        vec![
+            ReplayProgress {
+                replayed: 1,
+                total: 1
+            },
+            ReplayProgress {
+                replayed: 1,
+                total: 2
+            },
+            ReplayProgress {
+                replayed: 2,
+                total: 2
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_replay_into_an_activity_feed_skips_the_activities_it_holds() {
+    // Arrange
+    let source = Arc::new(InMemoryTodoRepository::new());
+    let store = Arc::new(InMemoryEventStore::new());
+    record_changes(source, store.clone()).await;
+    let all = store.find_all().unwrap();
+    let feed = ActivityFeed::new();
+    feed.record(&all[..2]).unwrap();
+
+    // Act
+    ReplayEventsHandler::new(store)
+        .replay_events(None, None, &feed)
+        .await
+        .unwrap();
+
+    // Assert
+    let page = feed.page(None, 10);
+    let mut replayed: Vec<TodoEvent> = page
+        .activities
+        .into_iter()
+        .map(|activity| activity.event)
+        .collect();
+    replayed.reverse();
+    assert_eq!(replayed, all);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_file_store_returns_every_event_in_order() {
+    use todo::infrastructure::event_stores::FileEventStore;
+
+    let path = common::temp_path("events.jsonl");
+    let store = FileEventStore::new(&path);
+    let (_, created) = todo::Todo::new("Write docs".to_string()).unwrap();
+    let (_, other) = todo::Todo::new("Ship it".to_string()).unwrap();
+
+    assert!(store.find_all().unwrap().is_empty());
+    store.record(&created).unwrap();
+    store.record(&other).unwrap();
+    let all = store.find_all().unwrap();
+
+    assert_eq!(all, [created, other].concat());
+    std::fs::remove_file(path).unwrap();
+}
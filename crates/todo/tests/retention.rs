@@ -0,0 +1,174 @@
+use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+use std::sync::Arc;
+use todo::application::add_todo_handler::AddTodoHandler;
+use todo::application::change_todo_state_handler::ChangeTodoStateHandler;
+use todo::application::retention::{
+    ApplyRetentionHandler, RetentionPolicy, RetentionReport, RetentionRules,
+};
+use todo::infrastructure::event_stores::InMemoryEventStore;
+use todo::infrastructure::repositories::todo::{InMemoryTodoRepository, TenantTodoRepository};
+use todo::{Clock, EventStore, FixedClock, TenantId, TodoRepository, TodoState};
+
+fn start() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+}
+
+fn advance(clock: &FixedClock, by: TimeDelta) {
+    clock.set(clock.now() + by);
+}
+
+/// Adds a todo at the clock's time and moves it to `DONE` `done_after` later
+async fn done_todo(
+    repository: Arc<dyn TodoRepository>,
+    store: &Arc<InMemoryEventStore>,
+    clock: &Arc<FixedClock>,
+    done_after: TimeDelta,
+) -> String {
+    let add = AddTodoHandler::new(repository.clone())
+        .with_clock(clock.clone())
+        .with_event_sink(store.clone());
+    let (_, created) = add.new_todo("Finished".to_string()).await.unwrap();
+    let id = created[0].todo_id().to_string();
+    advance(clock, done_after);
+    let change = ChangeTodoStateHandler::new(repository)
+        .with_clock(clock.clone())
+        .with_event_sink(store.clone());
+    for state in [TodoState::InProgress, TodoState::Done] {
+        change.change_state(id.clone(), state).await.unwrap();
+    }
+    id
+}
+
+#[tokio::test]
+async fn test_done_todos_are_deleted_days_after_completion() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let store = Arc::new(InMemoryEventStore::new());
+    let clock = Arc::new(FixedClock::new(start()));
+    let old = done_todo(repository.clone(), &store, &clock, TimeDelta::zero()).await;
+    // Created long ago but only just finished
+    let recent = done_todo(repository.clone(), &store, &clock, TimeDelta::days(29)).await;
+    let add = AddTodoHandler::new(repository.clone()).with_clock(clock.clone());
+    add.new_todo("Still open".to_string()).await.unwrap();
+    advance(&clock, TimeDelta::days(2));
+    let policy = RetentionPolicy::new(RetentionRules {
+        done_todo_days: Some(30),
+        event_months: None,
+        trash_days: None,
+    });
+    let handler = ApplyRetentionHandler::new(repository.clone(), policy)
+        .with_event_store(store.clone())
+        .with_clock(clock.clone());
+
+    // Act
+    let report = handler.apply().await.unwrap();
+
+    // Assert
+    assert_eq!(report.deleted_todos, vec![old.clone().into()]);
+    assert_eq!(report.compacted_events, 0);
+    assert!(repository.find_by_id(&old).await.unwrap().is_none());
+    assert!(repository.find_by_id(&recent).await.unwrap().is_some());
+    assert_eq!(repository.find_all().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_tenant_overrides_replace_the_default_rules() {
+    // Arrange
+    let shared: Arc<dyn TodoRepository> = Arc::new(InMemoryTodoRepository::new());
+    let store = Arc::new(InMemoryEventStore::new());
+    let clock = Arc::new(FixedClock::new(start()));
+    let acme = TenantId::new("acme").unwrap();
+    let globex = TenantId::new("globex").unwrap();
+    let acme_todo = done_todo(
+        Arc::new(TenantTodoRepository::new(shared.clone(), acme.clone())),
+        &store,
+        &clock,
+        TimeDelta::zero(),
+    )
+    .await;
+    done_todo(
+        Arc::new(TenantTodoRepository::new(shared.clone(), globex)),
+        &store,
+        &clock,
+        TimeDelta::zero(),
+    )
+    .await;
+    advance(&clock, TimeDelta::days(10));
+    let policy = RetentionPolicy::new(RetentionRules {
+        done_todo_days: Some(30),
+        event_months: None,
+        trash_days: None,
+    })
+    .with_tenant(
+        acme,
+        RetentionRules {
+            done_todo_days: Some(7),
+            event_months: None,
+            trash_days: None,
+        },
+    );
+    let handler = ApplyRetentionHandler::new(shared.clone(), policy)
+        .with_event_store(store)
+        .with_clock(clock);
+
+    // Act
+    let report = handler.apply().await.unwrap();
+
+    // Assert
+    assert_eq!(
+        report.deleted_todos,
+        vec![format!("acme/{}", acme_todo).into()]
+    );
+    assert_eq!(shared.find_all().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_old_events_are_compacted() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let store = Arc::new(InMemoryEventStore::new());
+    let clock = Arc::new(FixedClock::new(start()));
+    let old = done_todo(repository.clone(), &store, &clock, TimeDelta::zero()).await;
+    advance(&clock, TimeDelta::days(100));
+    let recent = done_todo(repository.clone(), &store, &clock, TimeDelta::zero()).await;
+    advance(&clock, TimeDelta::days(1));
+    let policy = RetentionPolicy::new(RetentionRules {
+        done_todo_days: None,
+        event_months: Some(3),
+        trash_days: None,
+    });
+    let handler = ApplyRetentionHandler::new(repository.clone(), policy)
+        .with_event_store(store.clone())
+        .with_clock(clock);
+
+    // Act
+    let report = handler.apply().await.unwrap();
+
+    // Assert
+    assert_eq!(
+        report,
+        RetentionReport {
+            deleted_todos: Vec::new(),
+            compacted_events: 3,
+            emptied_trash: Vec::new(),
+        }
+    );
+    assert!(store.find_by_todo(&old).unwrap().is_empty());
+    assert_eq!(store.find_by_todo(&recent).unwrap().len(), 3);
+    // The repository keeps the compacted todo as it was
+    assert!(repository.find_by_id(&old).await.unwrap().is_some());
+}
+
+#[test]
+fn test_policy_is_enabled_by_any_rule() {
+    assert!(!RetentionPolicy::default().is_enabled());
+    let tenant_only = RetentionPolicy::default().with_tenant(
+        TenantId::new("acme").unwrap(),
+        RetentionRules {
+            done_todo_days: Some(1),
+            event_months: None,
+            trash_days: None,
+        },
+    );
+    assert!(tenant_only.is_enabled());
+}
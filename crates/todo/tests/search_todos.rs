@@ -0,0 +1,171 @@
+mod common;
+
+use chrono::{TimeZone, Utc};
+use todo::domain::todo::FilterTerm;
+use todo::{FixedClock, SequentialIdGenerator, Todo, TodoFilter, TodoState};
+
+/// Three todos created on 2024-12-30, 2024-12-31 and 2025-01-01, the first two due on
+/// 2025-01-02 and 2024-12-31
+fn todos() -> Vec<Todo> {
+    let ids = SequentialIdGenerator::new("todo-");
+    [
+        (
+            "Write quarterly report",
+            30,
+            TodoState::InProgress,
+            Some(Utc.with_ymd_and_hms(2025, 1, 2, 9, 0, 0).unwrap()),
+        ),
+        (
+            "Report the bug",
+            31,
+            TodoState::Done,
+            Some(Utc.with_ymd_and_hms(2024, 12, 31, 23, 0, 0).unwrap()),
+        ),
+        ("Buy milk", 1, TodoState::Todo, None),
+    ]
+    .into_iter()
+    .map(|(description, day, state, due_at)| {
+        let year = if day == 1 { 2025 } else { 2024 };
+        let month = if day == 1 { 1 } else { 12 };
+        let clock = FixedClock::new(Utc.with_ymd_and_hms(year, month, day, 12, 0, 0).unwrap());
+        let (mut todo, _) = Todo::new_with(description.to_string(), &clock, &ids).unwrap();
+        todo.state = state;
+        todo.due_at = due_at;
+        todo
+    })
+    .collect()
+}
+
+fn descriptions(query: &str) -> Vec<String> {
+    let filter = TodoFilter::parse(query).unwrap();
+    todos()
+        .into_iter()
+        .filter(|todo| filter.matches(todo))
+        .map(|todo| todo.description.to_string())
+        .collect()
+}
+
+#[test]
+fn test_todo_filter_parses_search_queries() {
+    assert_eq!(
+        descriptions(""),
+        ["Write quarterly report", "Report the bug", "Buy milk"]
+    );
+    assert_eq!(
+        descriptions("REPORT"),
+        ["Write quarterly report", "Report the bug"]
+    );
+    assert_eq!(
+        descriptions("report -state:done"),
+        ["Write quarterly report"]
+    );
+    assert_eq!(
+        descriptions("\"quarterly report\""),
+        ["Write quarterly report"]
+    );
+    assert_eq!(
+        descriptions("-\"the bug\" -milk"),
+        ["Write quarterly report"]
+    );
+    assert_eq!(
+        descriptions("state:In_Progress"),
+        ["Write quarterly report"]
+    );
+    assert_eq!(
+        descriptions("created<2024-12-31"),
+        ["Write quarterly report"]
+    );
+    assert_eq!(
+        descriptions("created<=2024-12-31"),
+        ["Write quarterly report", "Report the bug"]
+    );
+    assert_eq!(descriptions("created>2024-12-31"), ["Buy milk"]);
+    assert_eq!(
+        descriptions("created>=2024-12-31"),
+        ["Report the bug", "Buy milk"]
+    );
+    assert_eq!(descriptions("created:2024-12-31"), ["Report the bug"]);
+    assert_eq!(
+        descriptions("-created:2024-12-31"),
+        ["Write quarterly report", "Buy milk"]
+    );
+    assert_eq!(descriptions("due<2025-01-01"), ["Report the bug"]);
+    assert_eq!(
+        descriptions("due<=2025-01-02"),
+        ["Write quarterly report", "Report the bug"]
+    );
+    assert_eq!(descriptions("due>2024-12-31"), ["Write quarterly report"]);
+    assert_eq!(descriptions("due:2024-12-31"), ["Report the bug"]);
+    assert_eq!(
+        descriptions("-due:2024-12-31"),
+        ["Write quarterly report", "Buy milk"]
+    );
+    assert_eq!(
+        TodoFilter::parse("state:done milk").unwrap(),
+        TodoFilter::new()
+            .with(FilterTerm::State(TodoState::Done))
+            .with(FilterTerm::Text("milk".to_string()))
+    );
+}
+
+#[test]
+fn test_todo_filter_rejects_invalid_queries() {
+    for (query, error) in [
+        ("start<2025-01-01", "unknown field \"start\""),
+        (
+            "due<2025-01-32",
+            "invalid date \"2025-01-32\", expected YYYY-MM-DD",
+        ),
+        ("tag>home", "tag only supports \":\", in \"tag>home\""),
+        ("tag:", "tag must not be empty"),
+        (
+            "state:blocked",
+            "invalid state \"blocked\", expected todo, in_progress or done",
+        ),
+        ("state>done", "state only supports \":\", in \"state>done\""),
+        (
+            "created<2025-13-01",
+            "invalid date \"2025-13-01\", expected YYYY-MM-DD",
+        ),
+        (
+            "\"quarterly report",
+            "unclosed quote in \"\\\"quarterly report\"",
+        ),
+    ] {
+        assert_eq!(
+            TodoFilter::parse(query),
+            Err(error.to_string()),
+            "{}",
+            query
+        );
+    }
+}
+
+#[cfg(feature = "serde")]
+#[tokio::test]
+async fn test_search_todos_uses_the_repository_filter() {
+    use todo::TodoRepository;
+    use todo::application::get_todos_handler::GetTodosHandler;
+    use todo::infrastructure::repositories::todo::FileTodoRepository;
+    use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+
+    // Arrange
+    let path = common::temp_path("todos.json");
+    let repositories: Vec<Box<dyn TodoRepository>> = vec![
+        Box::new(InMemoryTodoRepository::new()),
+        Box::new(FileTodoRepository::new(&path)),
+    ];
+    let filter = TodoFilter::parse("report -state:done").unwrap();
+
+    for repository in repositories {
+        repository.save_all(&todos()).await.unwrap();
+        let handler = GetTodosHandler::new(repository);
+
+        // Act
+        let found = handler.search_todos(&filter).await.unwrap();
+
+        // Assert
+        assert_eq!(found, vec![todos().remove(0)]);
+    }
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "serde")]
+
+use serde_json::json;
+use todo::{Todo, TodoError, TodoEvent, TodoState};
+
+#[test]
+fn test_todo_round_trips_with_stable_field_names() {
+    // Arrange
+    let (mut todo, _) = Todo::new("Write docs".to_string()).unwrap();
+    todo.update_state(TodoState::InProgress).unwrap();
+
+    // Act
+    let value = serde_json::to_value(&todo).unwrap();
+    let restored: Todo = serde_json::from_value(value.clone()).unwrap();
+
+    // Assert
+    assert_eq!(
+        value,
+        json!({
+            "id": todo.id,
+            "created_at": todo.created_at.to_rfc3339(),
+            "description": "Write docs",
+            "state": "IN_PROGRESS",
+            "priority": "MEDIUM",
+        })
+    );
+    assert_eq!(restored.id, todo.id);
+    assert_eq!(restored.created_at, todo.created_at);
+    assert_eq!(restored.state, TodoState::InProgress);
+}
+
+#[test]
+fn test_events_are_tagged_with_their_type() {
+    // Arrange
+    let (mut todo, created) = Todo::new("Write docs".to_string()).unwrap();
+    let changed = todo.update_state(TodoState::InProgress).unwrap();
+
+    // Act
+    let created_value = serde_json::to_value(&created[0]).unwrap();
+    let changed_value = serde_json::to_value(&changed[0]).unwrap();
+
+    // Assert
+    assert_eq!(created_value["type"], "TODO_CREATED");
+    assert_eq!(changed_value["type"], "TODO_STATE_CHANGED");
+    assert_eq!(changed_value["from_state"], "TODO");
+    assert_eq!(changed_value["to_state"], "IN_PROGRESS");
+    let restored: TodoEvent = serde_json::from_value(changed_value).unwrap();
+    assert_eq!(restored, changed[0]);
+}
+
+#[test]
+fn test_errors_serialize_with_their_code() {
+    assert_eq!(
+        serde_json::to_value(TodoError::TodoNotFound).unwrap(),
+        json!({ "code": "TODO_NOT_FOUND" })
+    );
+    let error = TodoError::RepositoryError("disk full".to_string());
+    let value = serde_json::to_value(&error).unwrap();
+    assert_eq!(
+        value,
+        json!({ "code": "REPOSITORY_ERROR", "message": "disk full" })
+    );
+    assert_eq!(serde_json::from_value::<TodoError>(value).unwrap(), error);
+    assert!(serde_json::from_value::<TodoState>(json!("todo")).is_err());
+}
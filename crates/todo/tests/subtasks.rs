@@ -0,0 +1,155 @@
+mod common;
+
+use common::at;
+use todo::{FixedClock, SequentialIdGenerator, Subtask, Todo, TodoError, TodoEvent};
+
+#[test]
+fn test_subtask_changes_emit_events() {
+    // Arrange
+    let clock = FixedClock::new(at(0));
+    let ids = SequentialIdGenerator::new("subtask-");
+    let (mut todo, _) = Todo::new_with_clock("Write docs".to_string(), &clock).unwrap();
+    clock.set(at(1));
+
+    // Act
+    let added = todo
+        .add_subtask_with("Outline".to_string(), &clock, &ids)
+        .unwrap();
+    let subtask_id = todo.subtasks[0].id.clone();
+    let toggled = todo.toggle_subtask_with_clock(&subtask_id, &clock).unwrap();
+    let subtasks = todo.subtasks.clone();
+    let removed = todo.remove_subtask_with_clock(&subtask_id, &clock);
+    let removed_again = todo.remove_subtask_with_clock(&subtask_id, &clock);
+
+    // Assert
+    assert_eq!(
+        subtasks,
+        [Subtask {
+            id: subtask_id.clone(),
+            description: "Outline".into(),
+            done: true,
+        }]
+    );
+    assert_eq!(
+        added,
+        [TodoEvent::TodoSubtaskAdded {
+            id: todo.id.clone(),
+            subtask_id: subtask_id.clone(),
+            description: "Outline".into(),
+            added_at: at(1),
+        }]
+    );
+    assert_eq!(
+        toggled,
+        [TodoEvent::TodoSubtaskToggled {
+            id: todo.id.clone(),
+            subtask_id: subtask_id.clone(),
+            done: true,
+            toggled_at: at(1),
+        }]
+    );
+    assert_eq!(
+        removed,
+        [TodoEvent::TodoSubtaskRemoved {
+            id: todo.id.clone(),
+            subtask_id,
+            removed_at: at(1),
+        }]
+    );
+    assert!(removed_again.is_empty());
+    assert!(todo.subtasks.is_empty());
+}
+
+#[test]
+fn test_subtask_changes_reject_empty_descriptions_and_unknown_ids() {
+    // Arrange
+    let (mut todo, _) = Todo::new("Write docs".to_string()).unwrap();
+
+    // Act
+    let empty = todo.add_subtask("  ".to_string());
+    let unknown = todo.toggle_subtask("missing");
+
+    // Assert
+    assert!(matches!(empty, Err(TodoError::EmptyDescription)));
+    assert!(matches!(unknown, Err(TodoError::ValidationError(_))));
+    assert!(todo.subtasks.is_empty());
+}
+
+#[test]
+fn test_toggling_twice_unchecks_the_subtask() {
+    // Arrange
+    let (mut todo, _) = Todo::new("Write docs".to_string()).unwrap();
+    todo.add_subtask("Outline".to_string()).unwrap();
+    let subtask_id = todo.subtasks[0].id.clone();
+
+    // Act
+    todo.toggle_subtask(&subtask_id).unwrap();
+    let unchecked = todo.toggle_subtask(&subtask_id).unwrap();
+
+    // Assert
+    assert!(!todo.subtasks[0].done);
+    assert!(matches!(
+        unchecked[..],
+        [TodoEvent::TodoSubtaskToggled { done: false, .. }]
+    ));
+}
+
+#[test]
+fn test_completion_percentage_counts_the_done_subtasks() {
+    // Arrange
+    let (mut todo, _) = Todo::new("Write docs".to_string()).unwrap();
+    let without_subtasks = todo.completion_percentage();
+    for description in ["Outline", "Draft", "Review"] {
+        todo.add_subtask(description.to_string()).unwrap();
+    }
+    let first = todo.subtasks[0].id.clone();
+    let second = todo.subtasks[1].id.clone();
+
+    // Act
+    let none_done = todo.completion_percentage();
+    todo.toggle_subtask(&first).unwrap();
+    let one_done = todo.completion_percentage();
+    todo.toggle_subtask(&second).unwrap();
+    let two_done = todo.completion_percentage();
+
+    // Assert
+    assert_eq!(without_subtasks, None);
+    assert_eq!(none_done, Some(0));
+    assert_eq!(one_done, Some(33));
+    assert_eq!(two_done, Some(66));
+}
+
+#[test]
+fn test_replay_restores_the_subtasks() {
+    // Arrange
+    let (mut todo, mut events) = Todo::new("Write docs".to_string()).unwrap();
+    events.extend(todo.add_subtask("Outline".to_string()).unwrap());
+    events.extend(todo.add_subtask("Draft".to_string()).unwrap());
+    let outline = todo.subtasks[0].id.clone();
+    let draft = todo.subtasks[1].id.clone();
+    events.extend(todo.toggle_subtask(&draft).unwrap());
+    events.extend(todo.remove_subtask(&outline));
+
+    // Act
+    let replayed = Todo::replay(&events).unwrap();
+
+    // Assert
+    assert_eq!(replayed.subtasks, todo.subtasks);
+}
+
+#[cfg(feature = "serde")]
+#[tokio::test]
+async fn test_file_repository_persists_the_subtasks() {
+    // Arrange
+    let (mut todo, _) = Todo::new("Write docs".to_string()).unwrap();
+    todo.add_subtask("Outline".to_string()).unwrap();
+    todo.add_subtask("Draft".to_string()).unwrap();
+    let outline = todo.subtasks[0].id.clone();
+    todo.toggle_subtask(&outline).unwrap();
+
+    // Act
+    let found = common::reloaded_from_file(&todo).await;
+
+    // Assert
+    assert_eq!(found.subtasks, todo.subtasks);
+}
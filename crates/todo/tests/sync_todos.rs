@@ -0,0 +1,182 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use async_trait::async_trait;
+use common::saved;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use todo::application::sync_todos_handler::SyncTodosHandler;
+use todo::domain::import::ImportMappingRepository;
+use todo::domain::sync::{RemoteChanges, RemoteTaskList};
+use todo::infrastructure::repositories::import_mapping::InMemoryImportMappingRepository;
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::{Todo, TodoError, TodoEvent, TodoRepository, TodoState};
+
+/// Hands out the changes queued with `push`, records what is sent to it, and refuses
+/// updates of the tasks marked `stale`
+#[derive(Default)]
+struct FakeTaskList {
+    changes: Mutex<RemoteChanges>,
+    created: Mutex<Vec<String>>,
+    updated: Mutex<Vec<(String, TodoState)>>,
+    stale: Mutex<HashSet<String>>,
+}
+
+impl FakeTaskList {
+    fn push(&self, id: &str, description: &str) {
+        let (mut todo, _) = Todo::new(description.to_string()).unwrap();
+        todo.id = id.into();
+        self.changes.lock().unwrap().todos.push(todo);
+    }
+
+    fn remove(&self, id: &str) {
+        self.changes.lock().unwrap().removed.push(id.to_string());
+    }
+}
+
+#[async_trait]
+impl RemoteTaskList for FakeTaskList {
+    fn source(&self) -> &str {
+        "fake"
+    }
+
+    async fn pull(&self) -> Result<RemoteChanges, TodoError> {
+        Ok(std::mem::take(&mut *self.changes.lock().unwrap()))
+    }
+
+    async fn create(&self, todo: &Todo) -> Result<Option<String>, TodoError> {
+        let mut created = self.created.lock().unwrap();
+        created.push(todo.description.to_string());
+        Ok(Some(format!("R{}", created.len())))
+    }
+
+    async fn update(&self, remote_id: &str, todo: &Todo) -> Result<bool, TodoError> {
+        if self.stale.lock().unwrap().contains(remote_id) {
+            return Ok(false);
+        }
+        self.updated
+            .lock()
+            .unwrap()
+            .push((remote_id.to_string(), todo.state));
+        Ok(true)
+    }
+}
+
+fn handler(
+    repository: &Arc<InMemoryTodoRepository>,
+    remote: &Arc<FakeTaskList>,
+    mappings: &Arc<InMemoryImportMappingRepository>,
+) -> SyncTodosHandler<Arc<InMemoryTodoRepository>> {
+    SyncTodosHandler::new(repository.clone(), remote.clone(), mappings.clone())
+}
+
+#[tokio::test]
+async fn test_sync_pulls_remote_tasks_and_pushes_local_todos() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let remote = Arc::new(FakeTaskList::default());
+    let mappings = Arc::new(InMemoryImportMappingRepository::new());
+    let local = saved(&repository, "Call mom").await;
+    remote.push("T1", "Write docs");
+    let handler = handler(&repository, &remote, &mappings);
+
+    // Act
+    let report = handler.sync().await.unwrap();
+    let again = handler.sync().await.unwrap();
+
+    // Assert
+    let pulled = repository.find_by_id("T1").await.unwrap().unwrap();
+    assert_eq!(pulled.description.as_ref(), "Write docs");
+    assert!(matches!(
+        report.events.as_slice(),
+        [TodoEvent::TodoCreated { .. }]
+    ));
+    assert_eq!((report.created, report.updated), (1, 0));
+    assert_eq!(*remote.created.lock().unwrap(), vec!["Call mom"]);
+    let pushed = mappings.find("fake", "R1").await.unwrap().unwrap();
+    assert_eq!(pushed.todo_id, local.id);
+    assert!(again.events.is_empty());
+    assert_eq!((again.created, again.updated), (0, 0));
+}
+
+#[tokio::test]
+async fn test_sync_pushes_local_changes_and_deletes_removed_tasks() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let remote = Arc::new(FakeTaskList::default());
+    let mappings = Arc::new(InMemoryImportMappingRepository::new());
+    remote.push("T1", "Write docs");
+    remote.push("T2", "Review PR");
+    let handler = handler(&repository, &remote, &mappings);
+    handler.sync().await.unwrap();
+    let mut started = repository.find_by_id("T1").await.unwrap().unwrap();
+    started.update_state(TodoState::InProgress).unwrap();
+    repository.save(&started).await.unwrap();
+    remote.remove("T2");
+
+    // Act
+    let report = handler.sync().await.unwrap();
+
+    // Assert
+    assert_eq!((report.created, report.updated), (0, 1));
+    assert_eq!(
+        *remote.updated.lock().unwrap(),
+        vec![("T1".to_string(), TodoState::InProgress)]
+    );
+    assert!(repository.find_by_id("T2").await.unwrap().is_none());
+    assert!(matches!(
+        report.events.as_slice(),
+        [TodoEvent::TodoDeleted { id, .. }] if id.as_ref() == "T2"
+    ));
+}
+
+#[tokio::test]
+async fn test_sync_pushes_a_changed_todo_whose_task_was_removed() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let remote = Arc::new(FakeTaskList::default());
+    let mappings = Arc::new(InMemoryImportMappingRepository::new());
+    remote.push("T1", "Write docs");
+    let handler = handler(&repository, &remote, &mappings);
+    handler.sync().await.unwrap();
+    let mut changed = repository.find_by_id("T1").await.unwrap().unwrap();
+    changed
+        .update_description("Write the docs".to_string())
+        .unwrap();
+    repository.save(&changed).await.unwrap();
+    remote.remove("T1");
+
+    // Act
+    let report = handler.sync().await.unwrap();
+
+    // Assert
+    assert!(report.events.is_empty());
+    assert_eq!((report.created, report.updated), (1, 0));
+    assert!(repository.find_by_id("T1").await.unwrap().is_some());
+    assert_eq!(*remote.created.lock().unwrap(), vec!["Write the docs"]);
+}
+
+#[tokio::test]
+async fn test_sync_defers_todos_whose_task_changed_since_the_pull() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let remote = Arc::new(FakeTaskList::default());
+    let mappings = Arc::new(InMemoryImportMappingRepository::new());
+    remote.push("T1", "Write docs");
+    let handler = handler(&repository, &remote, &mappings);
+    handler.sync().await.unwrap();
+    let mut started = repository.find_by_id("T1").await.unwrap().unwrap();
+    started.update_state(TodoState::InProgress).unwrap();
+    repository.save(&started).await.unwrap();
+    remote.stale.lock().unwrap().insert("T1".to_string());
+
+    // Act
+    let deferred = handler.sync().await.unwrap();
+    remote.stale.lock().unwrap().clear();
+    let pushed = handler.sync().await.unwrap();
+
+    // Assert
+    assert_eq!((deferred.updated, deferred.deferred), (0, 1));
+    assert_eq!((pushed.updated, pushed.deferred), (1, 0));
+}
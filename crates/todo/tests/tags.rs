@@ -0,0 +1,215 @@
+mod common;
+
+use common::at;
+use std::sync::Arc;
+use todo::application::rename_tag_handler::RenameTagHandler;
+use todo::infrastructure::event_stores::InMemoryEventStore;
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::{EventStore, FixedClock, Tag, Todo, TodoError, TodoEvent, TodoFilter, TodoRepository};
+
+#[test]
+fn test_tag_must_be_a_single_word() {
+    assert_eq!(Tag::new("  work ").unwrap().as_str(), "work");
+    assert!(Tag::new("").is_err());
+    assert!(Tag::new("   ").is_err());
+    assert!(Tag::new("two words").is_err());
+}
+
+#[test]
+fn test_add_and_remove_tag_emit_events() {
+    // Arrange
+    let clock = FixedClock::new(at(0));
+    let (mut todo, _) = Todo::new_with_clock("Write docs".to_string(), &clock).unwrap();
+    clock.set(at(1));
+
+    // Act
+    let added = todo.add_tag_with_clock("work", &clock).unwrap();
+    todo.add_tag_with_clock("docs", &clock).unwrap();
+    let tags: Vec<_> = todo.tags.iter().map(ToString::to_string).collect();
+    let removed = todo.remove_tag_with_clock("work", &clock).unwrap();
+    let removed_again = todo.remove_tag_with_clock("work", &clock).unwrap();
+
+    // Assert
+    assert_eq!(tags, ["work", "docs"]);
+    assert_eq!(
+        added,
+        [TodoEvent::TodoTagAdded {
+            id: todo.id.clone(),
+            tag: Tag::new("work").unwrap(),
+            added_at: at(1),
+        }]
+    );
+    assert_eq!(
+        removed,
+        [TodoEvent::TodoTagRemoved {
+            id: todo.id.clone(),
+            tag: Tag::new("work").unwrap(),
+            removed_at: at(1),
+        }]
+    );
+    assert!(removed_again.is_empty());
+    assert_eq!(todo.tags, [Tag::new("docs").unwrap()]);
+}
+
+#[test]
+fn test_add_tag_rejects_empty_and_duplicate_tags() {
+    // Arrange
+    let (mut todo, _) = Todo::new("Write docs".to_string()).unwrap();
+    todo.add_tag("work").unwrap();
+
+    // Act
+    let empty = todo.add_tag(" ");
+    let duplicate = todo.add_tag("work");
+
+    // Assert
+    assert!(matches!(empty, Err(TodoError::ValidationError(_))));
+    assert!(matches!(duplicate, Err(TodoError::ValidationError(_))));
+    assert_eq!(todo.tags, [Tag::new("work").unwrap()]);
+    assert!(matches!(
+        todo.remove_tag(""),
+        Err(TodoError::ValidationError(_))
+    ));
+}
+
+#[test]
+fn test_replay_restores_the_tags() {
+    // Arrange
+    let (mut todo, mut events) = Todo::new("Write docs".to_string()).unwrap();
+    events.extend(todo.add_tag("work").unwrap());
+    events.extend(todo.add_tag("docs").unwrap());
+    events.extend(todo.remove_tag("work").unwrap());
+
+    // Act
+    let replayed = Todo::replay(&events).unwrap();
+
+    // Assert
+    assert_eq!(replayed.tags, todo.tags);
+}
+
+#[tokio::test]
+async fn test_find_by_tag_returns_the_tagged_todos() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let (mut report, _) = Todo::new("Write report".to_string()).unwrap();
+    report.add_tag("work").unwrap();
+    let (mut milk, _) = Todo::new("Buy milk".to_string()).unwrap();
+    milk.add_tag("errands").unwrap();
+    repository.save(&report).await.unwrap();
+    repository.save(&milk).await.unwrap();
+
+    // Act
+    let work = repository
+        .find_by_tag(&Tag::new("work").unwrap())
+        .await
+        .unwrap();
+    let none = repository
+        .find_by_tag(&Tag::new("home").unwrap())
+        .await
+        .unwrap();
+    let searched = repository
+        .find_matching(&TodoFilter::parse("-tag:work").unwrap())
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(work.len(), 1);
+    assert_eq!(work[0].id, report.id);
+    assert!(none.is_empty());
+    assert_eq!(searched.len(), 1);
+    assert_eq!(searched[0].id, milk.id);
+}
+
+#[tokio::test]
+async fn test_rename_tag_renames_and_merges_across_todos() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let store = Arc::new(InMemoryEventStore::new());
+    let (mut report, _) = Todo::new("Write report".to_string()).unwrap();
+    report.add_tag("wrk").unwrap();
+    report.add_tag("docs").unwrap();
+    let (mut review, _) = Todo::new("Review PR".to_string()).unwrap();
+    review.add_tag("wrk").unwrap();
+    review.add_tag("work").unwrap();
+    let (mut milk, _) = Todo::new("Buy milk".to_string()).unwrap();
+    milk.add_tag("errands").unwrap();
+    repository
+        .save_all(&[report.clone(), review.clone(), milk.clone()])
+        .await
+        .unwrap();
+    let handler = RenameTagHandler::new(repository.clone())
+        .with_clock(Arc::new(FixedClock::new(at(1))))
+        .with_event_sink(store.clone());
+
+    // Act
+    let (saved, events) = handler.rename_tag("wrk", "work").await.unwrap();
+    let (unchanged, no_events) = handler.rename_tag("wrk", "work").await.unwrap();
+
+    // Assert
+    assert_eq!(saved.len(), 2);
+    let tags = |todo: &Todo| -> Vec<String> { todo.tags.iter().map(ToString::to_string).collect() };
+    let report = repository.find_by_id(&report.id).await.unwrap().unwrap();
+    let review = repository.find_by_id(&review.id).await.unwrap().unwrap();
+    let milk = repository.find_by_id(&milk.id).await.unwrap().unwrap();
+    assert_eq!(tags(&report), ["docs", "work"]);
+    assert_eq!(tags(&review), ["work"]);
+    assert_eq!(tags(&milk), ["errands"]);
+    assert_eq!(
+        store.find_by_todo(&report.id).unwrap(),
+        [
+            TodoEvent::TodoTagRemoved {
+                id: report.id.clone(),
+                tag: Tag::new("wrk").unwrap(),
+                removed_at: at(1),
+            },
+            TodoEvent::TodoTagAdded {
+                id: report.id.clone(),
+                tag: Tag::new("work").unwrap(),
+                added_at: at(1),
+            },
+        ]
+    );
+    assert_eq!(
+        store.find_by_todo(&review.id).unwrap(),
+        [TodoEvent::TodoTagRemoved {
+            id: review.id.clone(),
+            tag: Tag::new("wrk").unwrap(),
+            removed_at: at(1),
+        }]
+    );
+    assert_eq!(events.len(), 3);
+    assert!(unchanged.is_empty());
+    assert!(no_events.is_empty());
+}
+
+#[tokio::test]
+async fn test_rename_tag_rejects_an_invalid_tag() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let (mut todo, _) = Todo::new("Write report".to_string()).unwrap();
+    todo.add_tag("work").unwrap();
+    repository.save(&todo).await.unwrap();
+    let handler = RenameTagHandler::new(repository.clone());
+
+    // Act
+    let result = handler.rename_tag("work", "two words").await;
+
+    // Assert
+    assert!(matches!(result, Err(TodoError::ValidationError(_))));
+    let found = repository.find_by_id(&todo.id).await.unwrap().unwrap();
+    assert_eq!(found.tags, todo.tags);
+}
+
+#[cfg(feature = "serde")]
+#[tokio::test]
+async fn test_file_repository_persists_the_tags() {
+    // Arrange
+    let (mut todo, _) = Todo::new("Write docs".to_string()).unwrap();
+    todo.add_tag("work").unwrap();
+    todo.add_tag("docs").unwrap();
+
+    // Act
+    let found = common::reloaded_from_file(&todo).await;
+
+    // Assert
+    assert_eq!(found.tags, todo.tags);
+}
@@ -0,0 +1,141 @@
+#![cfg(feature = "serde")]
+
+use std::sync::Arc;
+use todo::application::import_todos_handler::ImportTodosHandler;
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::infrastructure::serialization::SerializationError;
+use todo::infrastructure::serialization::taskwarrior::{export_todos, import_todos};
+use todo::{Tag, Todo, TodoRepository, TodoState};
+
+const EXPORT: &str = r#"[
+{"id":1,"description":"Write docs","entry":"20240102T030405Z","modified":"20240102T030405Z","due":"20240110T170000Z","status":"pending","uuid":"a1","tags":["work","docs"],"urgency":0.9},
+{"id":2,"description":"Review PR","entry":"20240103T000000Z","start":"20240104T000000Z","status":"pending","uuid":"a2"},
+{"id":0,"description":"Buy milk","end":"20240105T000000Z","entry":"20240101T000000Z","status":"completed","uuid":"a3","annotations":[{"entry":"20240101T000000Z","description":"2 litres"}]},
+{"id":0,"description":"Old idea","entry":"20240101T000000Z","status":"deleted","uuid":"a4"}
+]"#;
+
+#[tokio::test]
+async fn test_taskwarrior_import_maps_status_and_skips_known_uuids() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let handler = ImportTodosHandler::new(repository.clone());
+
+    // Act
+    let events = handler.import_taskwarrior(EXPORT.as_bytes()).await.unwrap();
+    let reimported = handler.import_taskwarrior(EXPORT.as_bytes()).await.unwrap();
+
+    // Assert
+    assert_eq!(events.len(), 3);
+    assert!(reimported.is_empty());
+    let state = |todo: Option<todo::Todo>| todo.unwrap().state;
+    assert_eq!(
+        state(repository.find_by_id("a1").await.unwrap()),
+        TodoState::Todo
+    );
+    assert_eq!(
+        state(repository.find_by_id("a2").await.unwrap()),
+        TodoState::InProgress
+    );
+    assert_eq!(
+        state(repository.find_by_id("a3").await.unwrap()),
+        TodoState::Done
+    );
+    assert!(repository.find_by_id("a4").await.unwrap().is_none());
+    let written = repository.find_by_id("a1").await.unwrap().unwrap();
+    assert_eq!(written.created_at.to_rfc3339(), "2024-01-02T03:04:05+00:00");
+    assert_eq!(
+        written.due_at.unwrap().to_rfc3339(),
+        "2024-01-10T17:00:00+00:00"
+    );
+    assert_eq!(
+        written.tags,
+        [Tag::new("work").unwrap(), Tag::new("docs").unwrap()]
+    );
+    let milk = repository.find_by_id("a3").await.unwrap().unwrap();
+    assert_eq!(milk.subtasks.len(), 1);
+    assert_eq!(&*milk.subtasks[0].description, "2 litres");
+    assert!(!milk.subtasks[0].done);
+}
+
+#[test]
+fn test_taskwarrior_export_round_trips_tags_due_dates_and_annotations() {
+    // Arrange
+    let (mut todo, _) = Todo::new("Write docs".to_string()).unwrap();
+    todo.id = "0f8fad5b-d9cb-469f-a165-70867728950e".into();
+    todo.update_state(TodoState::InProgress).unwrap();
+    todo.set_due_date(todo.created_at + chrono::Duration::days(2))
+        .unwrap();
+    todo.add_tag("work").unwrap();
+    todo.add_subtask("Outline".to_string()).unwrap();
+    let (mut done, _) = Todo::new("Buy milk".to_string()).unwrap();
+    done.update_state(TodoState::InProgress).unwrap();
+    done.update_state(TodoState::Done).unwrap();
+    let mut document = Vec::new();
+
+    // Act
+    export_todos(&mut document, &[todo.clone(), done]).unwrap();
+    let imported = import_todos(document.as_slice()).unwrap();
+
+    // Assert
+    let tasks: serde_json::Value = serde_json::from_slice(&document).unwrap();
+    assert_eq!(tasks[0]["uuid"], "0f8fad5b-d9cb-469f-a165-70867728950e");
+    assert_eq!(tasks[0]["status"], "pending");
+    assert_eq!(tasks[0]["tags"], serde_json::json!(["work"]));
+    assert_eq!(tasks[0]["annotations"][0]["description"], "Outline");
+    assert_eq!(tasks[1]["status"], "completed");
+    assert_eq!(imported[0].id, todo.id);
+    assert_eq!(imported[0].state, TodoState::InProgress);
+    assert_eq!(
+        imported[0].due_at.map(|due_at| due_at.timestamp()),
+        todo.due_at.map(|due_at| due_at.timestamp())
+    );
+    assert_eq!(imported[0].tags, todo.tags);
+    assert_eq!(&*imported[0].subtasks[0].description, "Outline");
+    assert_eq!(imported[1].state, TodoState::Done);
+}
+
+#[test]
+fn test_taskwarrior_import_reads_line_export_and_rejects_invalid_tasks() {
+    let lines = "{\"description\":\"One\",\"status\":\"waiting\",\"uuid\":\"b1\"}\n\
+                 {\"description\":\"Two\",\"status\":\"pending\",\"uuid\":\"b2\"}\n";
+    assert_eq!(import_todos(lines.as_bytes()).unwrap().len(), 2);
+
+    let unknown = r#"[{"description":"One","status":"blocked","uuid":"c1"}]"#;
+    assert!(matches!(
+        import_todos(unknown.as_bytes()),
+        Err(SerializationError::InvalidRecord { record: 1, .. })
+    ));
+    let duplicate = r#"[{"description":"One","status":"pending","uuid":"c1"},
+                        {"description":"Two","status":"pending","uuid":"c1"}]"#;
+    assert!(matches!(
+        import_todos(duplicate.as_bytes()),
+        Err(SerializationError::InvalidRecord { record: 2, .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_taskwarrior_import_saves_large_exports_in_order() {
+    // Arrange
+    let tasks: Vec<String> = (0..25_000)
+        .map(|index| {
+            format!(
+                r#"{{"description":"Task {}","status":"pending","uuid":"d{}"}}"#,
+                index, index
+            )
+        })
+        .collect();
+    let export = format!("[{}]", tasks.join(","));
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let handler = ImportTodosHandler::new(repository.clone());
+
+    // Act
+    let events = handler.import_taskwarrior(export.as_bytes()).await.unwrap();
+
+    // Assert
+    assert_eq!(events.len(), 25_000);
+    assert!(matches!(
+        &events[24_999],
+        todo::TodoEvent::TodoCreated { id, .. } if &**id == "d24999"
+    ));
+    assert_eq!(repository.find_all().await.unwrap().len(), 25_000);
+}
@@ -0,0 +1,169 @@
+mod common;
+
+use common::at;
+use std::sync::Arc;
+use todo::application::add_todo_handler::AddTodoHandler;
+use todo::application::change_todo_state_handler::ChangeTodoStateHandler;
+use todo::application::delete_todo_handler::DeleteTodoHandler;
+use todo::application::get_todos_handler::GetTodosHandler;
+use todo::application::restore_todo_handler::RestoreTodoHandler;
+use todo::domain::trash::TrashRepository;
+use todo::infrastructure::event_stores::{InMemoryEventStore, TenantEventStore};
+use todo::infrastructure::repositories::todo::{InMemoryTodoRepository, TenantTodoRepository};
+use todo::infrastructure::repositories::trash::{InMemoryTrashRepository, TenantTrashRepository};
+use todo::{EventSink, EventStore, TenantId, TodoError, TodoEvent, TodoRepository, TodoState};
+
+fn tenant(id: &str, shared: &Arc<dyn TodoRepository>) -> Arc<TenantTodoRepository> {
+    Arc::new(TenantTodoRepository::new(
+        shared.clone(),
+        TenantId::new(id).unwrap(),
+    ))
+}
+
+#[tokio::test]
+async fn test_tenants_only_see_their_own_todos() {
+    // Arrange
+    let shared: Arc<dyn TodoRepository> = Arc::new(InMemoryTodoRepository::new());
+    let acme = tenant("acme", &shared);
+    let globex = tenant("globex", &shared);
+    let (_, events) = AddTodoHandler::new(acme.clone())
+        .new_todo("Acme plan".to_string())
+        .await
+        .unwrap();
+    let TodoEvent::TodoCreated { id, .. } = &events[0] else {
+        panic!("expected TodoCreated");
+    };
+    AddTodoHandler::new(globex.clone())
+        .new_todo("Globex plan".to_string())
+        .await
+        .unwrap();
+
+    // Act
+    let acme_todos = GetTodosHandler::new(acme.clone())
+        .get_todos()
+        .await
+        .unwrap();
+    let globex_todos = GetTodosHandler::new(globex.clone())
+        .get_todos()
+        .await
+        .unwrap();
+    let foreign_delete = DeleteTodoHandler::new(globex.clone())
+        .delete_todo(id.to_string())
+        .await;
+
+    // Assert
+    assert_eq!(acme_todos.len(), 1);
+    assert_eq!(acme_todos[0].id, *id);
+    assert_eq!(&*acme_todos[0].description, "Acme plan");
+    assert_eq!(globex_todos.len(), 1);
+    assert_eq!(&*globex_todos[0].description, "Globex plan");
+    assert_eq!(globex.find_by_id(id).await.unwrap(), None);
+    assert_eq!(foreign_delete, Err(TodoError::TodoNotFound));
+    assert!(acme.find_by_id(id).await.unwrap().is_some());
+    let stored = shared.find_by_id(&format!("acme/{}", id)).await.unwrap();
+    assert_eq!(stored.unwrap().description, acme_todos[0].description);
+}
+
+#[tokio::test]
+async fn test_handlers_change_todos_within_their_tenant() {
+    let shared: Arc<dyn TodoRepository> = Arc::new(InMemoryTodoRepository::new());
+    let acme = tenant("acme", &shared);
+    let (_, events) = AddTodoHandler::new(acme.clone())
+        .new_todo("Ship".to_string())
+        .await
+        .unwrap();
+    let TodoEvent::TodoCreated { id, .. } = &events[0] else {
+        panic!("expected TodoCreated");
+    };
+
+    ChangeTodoStateHandler::new(acme.clone())
+        .change_state(id.to_string(), TodoState::InProgress)
+        .await
+        .unwrap();
+
+    let todo = acme.find_by_id(id).await.unwrap().unwrap();
+    assert_eq!(todo.state, TodoState::InProgress);
+    assert_eq!(shared.find_all().await.unwrap().len(), 1);
+}
+
+#[test]
+fn test_tenant_ids_cannot_overlap_the_separator() {
+    assert!(TenantId::new("acme").is_ok());
+    assert!(TenantId::new("").is_err());
+    assert!(TenantId::new("acme/../globex").is_err());
+}
+
+#[test]
+fn test_tenant_event_stores_only_read_their_own_events() {
+    // Arrange
+    let shared: Arc<dyn EventStore> = Arc::new(InMemoryEventStore::new());
+    let acme = TenantEventStore::new(shared.clone(), TenantId::new("acme").unwrap());
+    let globex = TenantEventStore::new(shared.clone(), TenantId::new("globex").unwrap());
+    let blocked = TodoEvent::TodoBlocked {
+        id: "1".into(),
+        blocker_id: "2".into(),
+        blocked_at: at(0),
+    };
+    acme.record(std::slice::from_ref(&blocked)).unwrap();
+
+    // Act
+    let acme_history = acme.find_by_todo("1").unwrap();
+    let globex_history = globex.find_by_todo("1").unwrap();
+
+    // Assert
+    assert_eq!(acme_history, vec![blocked.clone()]);
+    assert_eq!(globex_history, vec![]);
+    assert_eq!(globex.find_all().unwrap(), vec![]);
+    let stored = shared.find_by_todo("acme/1").unwrap();
+    assert!(matches!(
+        &stored[..],
+        [TodoEvent::TodoBlocked { blocker_id, .. }] if &**blocker_id == "acme/2"
+    ));
+}
+
+#[tokio::test]
+async fn test_tenant_trashes_only_reach_their_own_todos() {
+    // Arrange
+    let shared: Arc<dyn TodoRepository> = Arc::new(InMemoryTodoRepository::new());
+    let trash: Arc<dyn TrashRepository> = Arc::new(InMemoryTrashRepository::new());
+    let acme = tenant("acme", &shared);
+    let acme_trash = Arc::new(TenantTrashRepository::new(
+        trash.clone(),
+        TenantId::new("acme").unwrap(),
+    ));
+    let globex = tenant("globex", &shared);
+    let globex_trash = Arc::new(TenantTrashRepository::new(
+        trash.clone(),
+        TenantId::new("globex").unwrap(),
+    ));
+    let (todo, _) = AddTodoHandler::new(acme.clone())
+        .new_todo("Acme plan".to_string())
+        .await
+        .unwrap();
+    DeleteTodoHandler::new(acme.clone())
+        .with_trash(acme_trash.clone())
+        .delete_todo(todo.id.to_string())
+        .await
+        .unwrap();
+    let globex_restore = RestoreTodoHandler::new(globex.clone(), globex_trash);
+
+    // Act
+    let listed = globex_restore.trashed().await.unwrap();
+    let restored = globex_restore.restore(todo.id.to_string()).await;
+    let purged = globex_restore.purge(todo.id.to_string()).await;
+
+    // Assert
+    assert_eq!(listed, vec![]);
+    assert_eq!(restored, Err(TodoError::TodoNotFound));
+    assert_eq!(purged, Err(TodoError::TodoNotFound));
+    assert_eq!(globex.find_all().await.unwrap(), vec![]);
+    let acme_trashed = acme_trash.find(&todo.id).await.unwrap().unwrap();
+    assert_eq!(acme_trashed.todo, todo);
+    assert!(
+        trash
+            .find(&format!("acme/{}", todo.id))
+            .await
+            .unwrap()
+            .is_some()
+    );
+}
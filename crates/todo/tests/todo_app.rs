@@ -0,0 +1,51 @@
+mod common;
+
+use todo::prelude::*;
+
+#[tokio::test]
+async fn test_todo_app_adds_lists_and_completes_todos() {
+    // Arrange
+    let app = TodoApp::in_memory();
+
+    // Act
+    let docs = app.add("Write docs").await.unwrap();
+    let milk = app.add("Buy milk").await.unwrap();
+    let completed = app.complete(&docs.id).await.unwrap();
+    let started = app.start(&milk.id).await.unwrap();
+
+    // Assert
+    assert_eq!(completed.state, TodoState::Done);
+    assert_eq!(started.state, TodoState::InProgress);
+    let todos = app.list().await.unwrap();
+    let descriptions: Vec<&str> = todos.iter().map(|todo| &*todo.description).collect();
+    assert_eq!(descriptions, ["Write docs", "Buy milk"]);
+}
+
+#[tokio::test]
+async fn test_todo_app_reports_domain_errors() {
+    let app = TodoApp::in_memory();
+    let todo = app.add("Ship it").await.unwrap();
+    app.complete(&todo.id).await.unwrap();
+
+    assert_eq!(app.add("  ").await, Err(TodoError::EmptyDescription));
+    assert_eq!(
+        app.complete(&todo.id).await,
+        Err(TodoError::InvalidStateTransition)
+    );
+    assert_eq!(app.start("missing").await, Err(TodoError::TodoNotFound));
+    assert_eq!(app.delete("missing").await, Err(TodoError::TodoNotFound));
+    app.delete(&todo.id).await.unwrap();
+    assert_eq!(app.get(&todo.id).await, Err(TodoError::TodoNotFound));
+}
+
+#[cfg(feature = "serde")]
+#[tokio::test]
+async fn test_todo_app_keeps_todos_in_a_file() {
+    let path = common::temp_path("todos.json");
+
+    let added = TodoApp::file(&path).add("Persisted").await.unwrap();
+    let reopened = TodoApp::file(&path).get(&added.id).await.unwrap();
+
+    assert_eq!(reopened.description, added.description);
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,123 @@
+mod common;
+
+use std::sync::Arc;
+use todo::application::add_todo_handler::AddTodoHandler;
+use todo::application::change_todo_state_handler::ChangeTodoStateHandler;
+use todo::application::delete_todo_handler::DeleteTodoHandler;
+use todo::application::get_todo_history_handler::GetTodoHistoryHandler;
+use todo::infrastructure::event_stores::InMemoryEventStore;
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::{TodoError, TodoEvent, TodoState};
+
+#[tokio::test]
+async fn test_get_history_returns_events_of_one_todo_in_order() {
+    // Arrange
+    let store = Arc::new(InMemoryEventStore::new());
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let add = AddTodoHandler::new(repository.clone()).with_event_sink(store.clone());
+    let change = ChangeTodoStateHandler::new(repository.clone()).with_event_sink(store.clone());
+    let delete = DeleteTodoHandler::new(repository);
+    let handler = GetTodoHistoryHandler::new(store);
+
+    // Act
+    let (_, created) = add.new_todo("Tracked".to_string()).await.unwrap();
+    let id = created[0].todo_id().to_string();
+    add.new_todo("Other".to_string()).await.unwrap();
+    let (_, started) = change
+        .change_state(id.clone(), TodoState::InProgress)
+        .await
+        .unwrap();
+    let (_, done) = change
+        .change_state(id.clone(), TodoState::Done)
+        .await
+        .unwrap();
+    delete.delete_todo(id.clone()).await.unwrap();
+
+    // Assert
+    let history = handler.get_history(&id).await.unwrap();
+    assert_eq!(history, [created, started, done].concat());
+    assert!(matches!(
+        history[2],
+        TodoEvent::TodoStateChanged {
+            to_state: TodoState::Done,
+            ..
+        }
+    ));
+}
+
+#[tokio::test]
+async fn test_get_history_of_unknown_todo_fails() {
+    let handler = GetTodoHistoryHandler::new(Arc::new(InMemoryEventStore::new()));
+
+    let result = handler.get_history("missing").await;
+
+    assert!(matches!(result, Err(TodoError::TodoNotFound)));
+}
+
+#[cfg(feature = "serde")]
+#[tokio::test]
+async fn test_file_event_store_reads_back_the_event_log() {
+    use todo::EventStore;
+    use todo::infrastructure::event_stores::FileEventStore;
+
+    // Arrange
+    let path = common::temp_path("events.jsonl");
+    let store = FileEventStore::new(&path);
+    assert!(store.find_by_todo("a").unwrap().is_empty());
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let add = AddTodoHandler::new(repository.clone())
+        .with_event_sink(Arc::new(FileEventStore::new(&path)));
+    let change = ChangeTodoStateHandler::new(repository)
+        .with_event_sink(Arc::new(FileEventStore::new(&path)));
+
+    // Act
+    let (_, created) = add.new_todo("Logged".to_string()).await.unwrap();
+    let id = created[0].todo_id().to_string();
+    let (_, started) = change
+        .change_state(id.clone(), TodoState::InProgress)
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(
+        store.find_by_todo(&id).unwrap(),
+        [created, started].concat()
+    );
+    std::fs::write(&path, "not json\n").unwrap();
+    let err = store.find_by_todo(&id).unwrap_err();
+    assert!(err.to_string().contains("record 1"), "{}", err);
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_file_event_store_compacts_old_events() {
+    use chrono::{TimeZone, Utc};
+    use todo::infrastructure::event_stores::FileEventStore;
+    use todo::{EventSink, EventStore};
+
+    // Arrange
+    let path = common::temp_path("events.jsonl");
+    let store = FileEventStore::new(&path);
+    let created = |id: &str, year: i32| TodoEvent::TodoCreated {
+        id: id.into(),
+        description: "Kept".into(),
+        created_at: Utc.with_ymd_and_hms(year, 1, 1, 0, 0, 0).unwrap(),
+    };
+    store
+        .record(&[created("old", 2020), created("new", 2024)])
+        .unwrap();
+
+    // Act
+    let removed = store
+        .compact(Utc.with_ymd_and_hms(2023, 1, 1, 0, 0, 0).unwrap())
+        .unwrap();
+
+    // Assert
+    assert_eq!(removed, 1);
+    assert!(store.find_by_todo("old").unwrap().is_empty());
+    assert_eq!(store.find_by_todo("new").unwrap(), [created("new", 2024)]);
+
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,81 @@
+use chrono::{TimeZone, Utc};
+use std::sync::Arc;
+use todo::{FixedClock, Todo, TodoError, TodoEvent, TodoState};
+
+fn created(id: &str) -> TodoEvent {
+    TodoEvent::TodoCreated {
+        id: Arc::from(id),
+        description: Arc::from("Write docs"),
+        created_at: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+    }
+}
+
+fn changed(id: &str, from_state: TodoState, to_state: TodoState) -> TodoEvent {
+    TodoEvent::TodoStateChanged {
+        id: Arc::from(id),
+        from_state,
+        to_state,
+        changed_at: Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap(),
+    }
+}
+
+#[test]
+fn test_replay_rebuilds_the_todo_from_its_events() {
+    // Arrange
+    let clock = FixedClock::new(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap());
+    let (mut todo, mut events) = Todo::new_with_clock("Write docs".to_string(), &clock).unwrap();
+    events.extend(todo.update_state(TodoState::InProgress).unwrap());
+    events.extend(todo.update_state(TodoState::Done).unwrap());
+
+    // Act
+    let replayed = Todo::replay(&events).unwrap();
+
+    // Assert
+    assert_eq!(replayed.id, todo.id);
+    assert_eq!(replayed.description, todo.description);
+    assert_eq!(replayed.created_at, todo.created_at);
+    assert_eq!(replayed.state, TodoState::Done);
+}
+
+#[test]
+fn test_replay_skips_events_of_other_todos() {
+    // Arrange
+    let events = [
+        created("a"),
+        created("b"),
+        changed("b", TodoState::Todo, TodoState::InProgress),
+        changed("a", TodoState::Todo, TodoState::InProgress),
+        changed("a", TodoState::InProgress, TodoState::Todo),
+    ];
+
+    // Act
+    let replayed = Todo::replay(&events).unwrap();
+
+    // Assert
+    assert_eq!(replayed.id.as_ref(), "a");
+    assert_eq!(replayed.state, TodoState::Todo);
+}
+
+#[test]
+fn test_replay_needs_a_creation_first() {
+    assert_eq!(Todo::replay(&[]), Err(TodoError::TodoNotFound));
+    assert_eq!(
+        Todo::replay(&[changed("a", TodoState::Todo, TodoState::InProgress)]),
+        Err(TodoError::TodoNotFound)
+    );
+}
+
+#[test]
+fn test_replay_rejects_changes_that_do_not_apply() {
+    // A change from the wrong state, a skipped state, and a second creation
+    for event in [
+        changed("a", TodoState::InProgress, TodoState::Done),
+        changed("a", TodoState::Todo, TodoState::Done),
+        created("a"),
+    ] {
+        assert_eq!(
+            Todo::replay(&[created("a"), event]),
+            Err(TodoError::InvalidStateTransition)
+        );
+    }
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "tracing")]
+
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use todo::application::add_todo_handler::AddTodoHandler;
+use todo::application::change_todo_state_handler::ChangeTodoStateHandler;
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::{TodoEvent, TodoState};
+use tracing_subscriber::fmt::format::FmtSpan;
+
+/// Collects formatted log lines for assertions
+#[derive(Clone, Default)]
+struct Captured(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Captured {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_handlers_record_command_and_state_fields() {
+    // Arrange
+    let captured = Captured::default();
+    let writer = captured.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::DEBUG)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_ansi(false)
+        .with_writer(move || writer.clone())
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let add = AddTodoHandler::new(repository.clone());
+    let change = ChangeTodoStateHandler::new(repository);
+
+    // Act
+    let (_, events) = add.new_todo("Traced todo".to_string()).await.unwrap();
+    let TodoEvent::TodoCreated { id, .. } = &events[0] else {
+        panic!("expected TodoCreated");
+    };
+    change
+        .change_state(id.to_string(), TodoState::InProgress)
+        .await
+        .unwrap();
+    let rejected = change
+        .change_state(id.to_string(), TodoState::InProgress)
+        .await;
+
+    // Assert
+    assert!(rejected.is_err());
+    let output = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+    assert!(output.contains("command=\"add_todo\""));
+    assert!(output.contains(&format!("todo.id={}", id)));
+    assert!(output.contains("from_state=Todo to_state=InProgress"));
+    assert!(output.contains("invalid todo state transition"));
+}
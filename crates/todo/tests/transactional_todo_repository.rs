@@ -0,0 +1,99 @@
+use std::sync::Arc;
+use todo::infrastructure::repositories::todo::{InMemoryTodoRepository, TransactionalTodoRepository};
+use todo::{Todo, TodoRepository, TodoState};
+
+#[tokio::test]
+async fn test_transaction_commit_applies_staged_changes() {
+    // Arrange
+    let inner = Arc::new(InMemoryTodoRepository::new());
+    let (existing, _) = Todo::new("Existing todo".to_string()).unwrap();
+    inner.save(&existing).await.unwrap();
+    let transaction = TransactionalTodoRepository::new(inner.clone());
+    let (created, _) = Todo::new("Created todo".to_string()).unwrap();
+
+    // Act
+    transaction.save(&created).await.unwrap();
+    transaction.delete(&existing.id).await.unwrap();
+    transaction.commit().await.unwrap();
+
+    // Assert
+    let todos = inner.find_all().await.unwrap();
+    assert_eq!(todos.len(), 1);
+    assert_eq!(todos[0].id, created.id);
+}
+
+#[tokio::test]
+async fn test_transaction_changes_are_invisible_until_commit() {
+    // Arrange
+    let inner = Arc::new(InMemoryTodoRepository::new());
+    let transaction = TransactionalTodoRepository::new(inner.clone());
+    let (todo, _) = Todo::new("Staged todo".to_string()).unwrap();
+
+    // Act
+    transaction.save(&todo).await.unwrap();
+
+    // Assert
+    assert!(inner.find_by_id(&todo.id).await.unwrap().is_none());
+    assert!(transaction.find_by_id(&todo.id).await.unwrap().is_some());
+    assert_eq!(transaction.find_all().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_transaction_reads_latest_staged_change() {
+    // Arrange
+    let inner = Arc::new(InMemoryTodoRepository::new());
+    let (mut todo, _) = Todo::new("Todo".to_string()).unwrap();
+    inner.save(&todo).await.unwrap();
+    let transaction = TransactionalTodoRepository::new(inner.clone());
+
+    // Act
+    todo.update_state(TodoState::InProgress).unwrap();
+    transaction.save(&todo).await.unwrap();
+
+    // Assert
+    let found = transaction.find_by_id(&todo.id).await.unwrap().unwrap();
+    assert_eq!(found.state, TodoState::InProgress);
+    let stored = inner.find_by_id(&todo.id).await.unwrap().unwrap();
+    assert_eq!(stored.state, TodoState::Todo);
+
+    // Act
+    transaction.delete(&todo.id).await.unwrap();
+
+    // Assert
+    assert!(transaction.find_by_id(&todo.id).await.unwrap().is_none());
+    assert!(transaction.find_all().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_transaction_rollback_discards_staged_changes() {
+    // Arrange
+    let inner = Arc::new(InMemoryTodoRepository::new());
+    let transaction = TransactionalTodoRepository::new(inner.clone());
+    let (todo, _) = Todo::new("Discarded todo".to_string()).unwrap();
+    transaction.save(&todo).await.unwrap();
+
+    // Act
+    transaction.rollback().unwrap();
+    transaction.commit().await.unwrap();
+
+    // Assert
+    assert!(inner.find_all().await.unwrap().is_empty());
+    assert!(transaction.find_by_id(&todo.id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_saved_todos_read_back_equal() {
+    // Arrange
+    let inner = Arc::new(InMemoryTodoRepository::new());
+    let transaction = TransactionalTodoRepository::new(inner.clone());
+    let (todo, _) = Todo::new("Todo".to_string()).unwrap();
+
+    // Act
+    transaction.save(&todo).await.unwrap();
+    let staged = transaction.find_by_id(&todo.id).await.unwrap();
+    transaction.commit().await.unwrap();
+
+    // Assert
+    assert_eq!(staged, Some(todo.clone()));
+    assert_eq!(inner.find_all().await.unwrap(), vec![todo]);
+}
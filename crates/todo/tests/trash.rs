@@ -0,0 +1,215 @@
+mod common;
+
+use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+use std::sync::Arc;
+use todo::application::delete_todo_handler::DeleteTodoHandler;
+use todo::application::restore_todo_handler::RestoreTodoHandler;
+use todo::application::retention::{ApplyRetentionHandler, RetentionPolicy, RetentionRules};
+use todo::domain::trash::{TrashRepository, TrashedTodo};
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::infrastructure::repositories::trash::InMemoryTrashRepository;
+use todo::{FixedClock, TenantId, Todo, TodoError, TodoEvent, TodoRepository, TodoState};
+
+fn start() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+}
+
+async fn saved_todo(repository: &InMemoryTodoRepository, description: &str) -> Todo {
+    let (mut todo, _) = Todo::new(description.to_string()).unwrap();
+    todo.update_state(TodoState::InProgress).unwrap();
+    repository.save(&todo).await.unwrap();
+    repository.find_by_id(&todo.id).await.unwrap().unwrap()
+}
+
+#[tokio::test]
+async fn test_deleted_todo_is_restored_as_it_was() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let trash = Arc::new(InMemoryTrashRepository::new());
+    let clock = Arc::new(FixedClock::new(start()));
+    let todo = saved_todo(&repository, "Write docs").await;
+    let delete = DeleteTodoHandler::new(repository.clone())
+        .with_trash(trash.clone())
+        .with_clock(clock.clone());
+    let restore = RestoreTodoHandler::new(repository.clone(), trash.clone()).with_clock(clock);
+
+    // Act
+    let deleted = delete.delete_todo(todo.id.to_string()).await.unwrap();
+    let trashed = restore.trashed().await.unwrap();
+    let (restored, events) = restore.restore(todo.id.to_string()).await.unwrap();
+
+    // Assert
+    assert_eq!(
+        deleted,
+        [TodoEvent::TodoDeleted {
+            id: todo.id.clone(),
+            deleted_at: start(),
+        }]
+    );
+    assert_eq!(trashed, vec![TrashedTodo::new(todo.clone(), start())]);
+    assert_eq!(restored, todo);
+    assert_eq!(
+        events,
+        [TodoEvent::TodoRestored {
+            id: todo.id.clone(),
+            restored_at: start(),
+        }]
+    );
+    assert_eq!(repository.find_by_id(&todo.id).await.unwrap(), Some(todo));
+    assert!(trash.find_all().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_delete_without_trash_is_permanent() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let trash = Arc::new(InMemoryTrashRepository::new());
+    let todo = saved_todo(&repository, "Write docs").await;
+    let restore = RestoreTodoHandler::new(repository.clone(), trash);
+
+    // Act
+    DeleteTodoHandler::new(repository.clone())
+        .delete_todo(todo.id.to_string())
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(
+        restore.restore(todo.id.to_string()).await.unwrap_err(),
+        TodoError::TodoNotFound
+    );
+}
+
+#[tokio::test]
+async fn test_restore_refuses_an_id_in_use_and_keeps_the_todo_in_the_trash() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let trash = Arc::new(InMemoryTrashRepository::new());
+    let todo = saved_todo(&repository, "Write docs").await;
+    trash
+        .save(&TrashedTodo::new(todo.clone(), start()))
+        .await
+        .unwrap();
+    let restore = RestoreTodoHandler::new(repository.clone(), trash.clone());
+
+    // Act
+    let result = restore.restore(todo.id.to_string()).await;
+
+    // Assert
+    assert!(matches!(result, Err(TodoError::RepositoryError(_))));
+    assert!(trash.find(&todo.id).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn test_trash_lists_the_most_recently_deleted_first_and_purges() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let trash = Arc::new(InMemoryTrashRepository::new());
+    let clock = Arc::new(FixedClock::new(start()));
+    let first = saved_todo(&repository, "First").await;
+    let second = saved_todo(&repository, "Second").await;
+    let delete = DeleteTodoHandler::new(repository.clone())
+        .with_trash(trash.clone())
+        .with_clock(clock.clone());
+    let restore = RestoreTodoHandler::new(repository.clone(), trash.clone());
+    delete.delete_todo(first.id.to_string()).await.unwrap();
+    clock.set(start() + TimeDelta::hours(1));
+    delete.delete_todo(second.id.to_string()).await.unwrap();
+
+    // Act
+    let listed: Vec<String> = restore
+        .trashed()
+        .await
+        .unwrap()
+        .iter()
+        .map(|trashed| trashed.id().to_string())
+        .collect();
+    restore.purge(second.id.to_string()).await.unwrap();
+
+    // Assert
+    assert_eq!(listed, vec![second.id.to_string(), first.id.to_string()]);
+    assert_eq!(
+        restore.purge(second.id.to_string()).await.unwrap_err(),
+        TodoError::TodoNotFound
+    );
+    assert_eq!(trash.find_all().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_retention_empties_the_trash_after_the_tenant_period() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let trash = Arc::new(InMemoryTrashRepository::new());
+    let deleted = |id: &str, days_ago: i64| {
+        let (mut todo, _) = Todo::new("Deleted".to_string()).unwrap();
+        todo.id = id.into();
+        TrashedTodo::new(todo, start() - TimeDelta::days(days_ago))
+    };
+    for trashed in [
+        deleted("old", 31),
+        deleted("recent", 29),
+        deleted("acme/recent", 8),
+    ] {
+        trash.save(&trashed).await.unwrap();
+    }
+    let policy = RetentionPolicy::new(RetentionRules {
+        trash_days: Some(30),
+        ..RetentionRules::default()
+    })
+    .with_tenant(
+        TenantId::new("acme").unwrap(),
+        RetentionRules {
+            trash_days: Some(7),
+            ..RetentionRules::default()
+        },
+    );
+    assert!(policy.is_enabled());
+    let handler = ApplyRetentionHandler::new(repository, policy)
+        .with_trash(trash.clone())
+        .with_clock(Arc::new(FixedClock::new(start())));
+
+    // Act
+    let report = handler.apply().await.unwrap();
+
+    // Assert
+    assert_eq!(
+        report.emptied_trash,
+        vec![Arc::<str>::from("old"), Arc::from("acme/recent")]
+    );
+    let kept: Vec<String> = trash
+        .find_all()
+        .await
+        .unwrap()
+        .iter()
+        .map(|trashed| trashed.id().to_string())
+        .collect();
+    assert_eq!(kept, vec!["recent".to_string()]);
+}
+
+#[cfg(feature = "serde")]
+#[tokio::test]
+async fn test_file_trash_is_shared_between_instances() {
+    use todo::infrastructure::repositories::trash::FileTrashRepository;
+
+    // Arrange
+    let path = common::temp_path("trash.json");
+    let writer = FileTrashRepository::new(&path);
+    let reader = FileTrashRepository::new(&path);
+    assert!(reader.find_all().await.unwrap().is_empty());
+    let todo = saved_todo(&InMemoryTodoRepository::new(), "Write docs").await;
+    let trashed = TrashedTodo::new(todo.clone(), start());
+
+    // Act
+    writer.save(&trashed).await.unwrap();
+    let found = reader.find(&todo.id).await.unwrap();
+    reader.remove(&todo.id).await.unwrap();
+
+    // Assert
+    assert_eq!(found, Some(trashed));
+    assert!(writer.find_all().await.unwrap().is_empty());
+    assert_eq!(
+        writer.remove(&todo.id).await.unwrap_err(),
+        TodoError::TodoNotFound
+    );
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,196 @@
+use chrono::Duration;
+use std::sync::Arc;
+use todo::application::add_todo_handler::AddTodoHandler;
+use todo::application::change_todo_due_date_handler::ChangeTodoDueDateHandler;
+use todo::application::change_todo_state_handler::ChangeTodoStateHandler;
+use todo::application::undo_last_change_handler::UndoLastChangeHandler;
+use todo::application::update_todo_description_handler::UpdateTodoDescriptionHandler;
+use todo::infrastructure::event_stores::InMemoryEventStore;
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::{
+    EventSink, EventStore, Priority, Tag, Todo, TodoError, TodoEvent, TodoRepository, TodoState,
+};
+
+struct Fixture {
+    repository: Arc<InMemoryTodoRepository>,
+    store: Arc<InMemoryEventStore>,
+    add: AddTodoHandler<Arc<InMemoryTodoRepository>>,
+    change: ChangeTodoStateHandler<Arc<InMemoryTodoRepository>>,
+    undo: UndoLastChangeHandler<Arc<InMemoryTodoRepository>>,
+}
+
+fn fixture() -> Fixture {
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let store = Arc::new(InMemoryEventStore::new());
+    Fixture {
+        add: AddTodoHandler::new(repository.clone()).with_event_sink(store.clone()),
+        change: ChangeTodoStateHandler::new(repository.clone()).with_event_sink(store.clone()),
+        undo: UndoLastChangeHandler::new(repository.clone(), store.clone()),
+        repository,
+        store,
+    }
+}
+
+/// Applies `change` to the todo with `id`, saving it and recording its events
+async fn apply(fixture: &Fixture, id: &str, change: impl FnOnce(&mut Todo) -> Vec<TodoEvent>) {
+    let mut todo = fixture.repository.find_by_id(id).await.unwrap().unwrap();
+    let events = change(&mut todo);
+    fixture.repository.save(&todo).await.unwrap();
+    fixture.store.record(&events).unwrap();
+}
+
+#[tokio::test]
+async fn test_undo_reverts_the_last_state_change_with_a_new_event() {
+    // Arrange
+    let fixture = fixture();
+    let (created, _) = fixture.add.new_todo("Undo me".to_string()).await.unwrap();
+    let id = created.id.to_string();
+    fixture
+        .change
+        .change_state(id.clone(), TodoState::InProgress)
+        .await
+        .unwrap();
+    fixture
+        .change
+        .change_state(id.clone(), TodoState::Done)
+        .await
+        .unwrap();
+
+    // Act
+    let (undone, events) = fixture.undo.undo_last_change(id.clone()).await.unwrap();
+
+    // Assert
+    assert!(matches!(
+        events[..],
+        [TodoEvent::TodoStateChanged {
+            from_state: TodoState::Done,
+            to_state: TodoState::InProgress,
+            ..
+        }]
+    ));
+    let todo = fixture.repository.find_by_id(&id).await.unwrap().unwrap();
+    assert_eq!(todo.state, TodoState::InProgress);
+    let history = fixture.store.find_by_todo(&id).unwrap();
+    assert_eq!(history.len(), 4);
+    assert_eq!(history.last(), events.last());
+    assert_eq!(
+        Some(undone),
+        fixture.repository.find_by_id(&id).await.unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_undo_twice_redoes_the_change() {
+    let fixture = fixture();
+    let (created, _) = fixture.add.new_todo("Toggle".to_string()).await.unwrap();
+    let id = created.id.to_string();
+    fixture
+        .change
+        .change_state(id.clone(), TodoState::InProgress)
+        .await
+        .unwrap();
+
+    fixture.undo.undo_last_change(id.clone()).await.unwrap();
+    fixture.undo.undo_last_change(id.clone()).await.unwrap();
+
+    let todo = fixture.repository.find_by_id(&id).await.unwrap().unwrap();
+    assert_eq!(todo.state, TodoState::InProgress);
+}
+
+#[tokio::test]
+async fn test_undo_reverts_description_priority_and_tag_changes() {
+    // Arrange
+    let fixture = fixture();
+    let (created, _) = fixture.add.new_todo("Draft".to_string()).await.unwrap();
+    let id = created.id.to_string();
+    let describe = UpdateTodoDescriptionHandler::new(fixture.repository.clone())
+        .with_event_sink(fixture.store.clone());
+
+    // Act
+    describe
+        .update_description(id.clone(), "Final".to_string())
+        .await
+        .unwrap();
+    let (described, _) = fixture.undo.undo_last_change(id.clone()).await.unwrap();
+    apply(&fixture, &id, |todo| todo.set_priority(Priority::Urgent)).await;
+    let (prioritized, _) = fixture.undo.undo_last_change(id.clone()).await.unwrap();
+    apply(&fixture, &id, |todo| todo.add_tag("work").unwrap()).await;
+    let (untagged, _) = fixture.undo.undo_last_change(id.clone()).await.unwrap();
+    let (retagged, events) = fixture.undo.undo_last_change(id.clone()).await.unwrap();
+
+    // Assert
+    assert_eq!(&*described.description, "Draft");
+    assert_eq!(prioritized.priority, created.priority);
+    assert!(untagged.tags.is_empty());
+    assert_eq!(retagged.tags, [Tag::new("work").unwrap()]);
+    assert!(matches!(&events[..], [TodoEvent::TodoTagAdded { .. }]));
+}
+
+#[tokio::test]
+async fn test_undo_restores_the_previous_due_date() {
+    // Arrange
+    let fixture = fixture();
+    let (created, _) = fixture.add.new_todo("Plan".to_string()).await.unwrap();
+    let id = created.id.to_string();
+    let due = ChangeTodoDueDateHandler::new(fixture.repository.clone())
+        .with_event_sink(fixture.store.clone());
+    let (other, _) = fixture.add.new_todo("Other".to_string()).await.unwrap();
+    let first = created.created_at + Duration::days(1);
+    let second = first + Duration::days(1);
+
+    // Act
+    due.change_due_date(id.clone(), Some(first)).await.unwrap();
+    due.change_due_date(id.clone(), Some(second)).await.unwrap();
+    let (restored, _) = fixture.undo.undo_last_change(id.clone()).await.unwrap();
+    let (redone, _) = fixture.undo.undo_last_change(id.clone()).await.unwrap();
+    due.change_due_date(other.id.to_string(), Some(first))
+        .await
+        .unwrap();
+    let (cleared, _) = fixture
+        .undo
+        .undo_last_change(other.id.to_string())
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(restored.due_at, Some(first));
+    assert_eq!(redone.due_at, Some(second));
+    assert_eq!(cleared.due_at, None);
+}
+
+#[tokio::test]
+async fn test_undo_rejects_creation_and_unknown_todos() {
+    let fixture = fixture();
+    let (created, _) = fixture.add.new_todo("Fresh".to_string()).await.unwrap();
+    let id = created.id.to_string();
+
+    let creation = fixture.undo.undo_last_change(id).await;
+    let unknown = fixture.undo.undo_last_change("missing".to_string()).await;
+
+    assert_eq!(creation, Err(TodoError::InvalidStateTransition));
+    assert_eq!(unknown, Err(TodoError::TodoNotFound));
+}
+
+#[tokio::test]
+async fn test_undo_rejects_changes_the_store_did_not_see() {
+    // Arrange
+    let fixture = fixture();
+    let (created, _) = fixture.add.new_todo("Drifted".to_string()).await.unwrap();
+    let id = created.id.to_string();
+    fixture
+        .change
+        .change_state(id.clone(), TodoState::InProgress)
+        .await
+        .unwrap();
+    let unrecorded = ChangeTodoStateHandler::new(fixture.repository.clone());
+    unrecorded
+        .change_state(id.clone(), TodoState::Done)
+        .await
+        .unwrap();
+
+    // Act
+    let result = fixture.undo.undo_last_change(id).await;
+
+    // Assert
+    assert_eq!(result, Err(TodoError::InvalidStateTransition));
+}
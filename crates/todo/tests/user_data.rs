@@ -0,0 +1,237 @@
+use chrono::{TimeZone, Utc};
+use std::sync::Arc;
+use todo::application::activity_feed::ActivityFeed;
+use todo::application::add_todo_handler::AddTodoHandler;
+use todo::application::change_todo_state_handler::ChangeTodoStateHandler;
+use todo::application::user_data_handler::{ErasureReport, UserDataHandler};
+use todo::domain::access::{Membership, MembershipRepository, Role};
+use todo::domain::notification::{Device, DeviceRepository, PushPlatform};
+use todo::infrastructure::event_stores::InMemoryEventStore;
+use todo::infrastructure::repositories::device::InMemoryDeviceRepository;
+use todo::infrastructure::repositories::membership::InMemoryMembershipRepository;
+use todo::infrastructure::repositories::todo::{InMemoryTodoRepository, TenantTodoRepository};
+use todo::{EventSink, EventStore, TenantId, TodoEvent, TodoRepository, TodoState};
+
+struct Stores {
+    todos: Arc<InMemoryTodoRepository>,
+    events: Arc<InMemoryEventStore>,
+    devices: Arc<InMemoryDeviceRepository>,
+    memberships: Arc<InMemoryMembershipRepository>,
+    activity: Arc<ActivityFeed>,
+}
+
+impl Stores {
+    fn new() -> Self {
+        Stores {
+            todos: Arc::new(InMemoryTodoRepository::new()),
+            events: Arc::new(InMemoryEventStore::new()),
+            devices: Arc::new(InMemoryDeviceRepository::new()),
+            memberships: Arc::new(InMemoryMembershipRepository::new()),
+            activity: Arc::new(ActivityFeed::new()),
+        }
+    }
+
+    fn handler(&self) -> UserDataHandler {
+        UserDataHandler::new(self.todos.clone())
+            .with_event_store(self.events.clone())
+            .with_devices(self.devices.clone())
+            .with_memberships(self.memberships.clone())
+            .with_activity_feed(self.activity.clone())
+    }
+
+    /// Adds a todo to `tenant`'s list and starts it, recording the events to the store and
+    /// the feed
+    async fn add(&self, tenant: &TenantId, description: &str) -> String {
+        let repository = TenantTodoRepository::new(self.todos.clone(), tenant.clone());
+        let repository: Arc<dyn TodoRepository> = Arc::new(repository);
+        let (todo, events) = AddTodoHandler::new(repository.clone())
+            .new_todo(description.to_string())
+            .await
+            .unwrap();
+        let id = todo.id.to_string();
+        let (_, changed) = ChangeTodoStateHandler::new(repository)
+            .change_state(id.clone(), TodoState::InProgress)
+            .await
+            .unwrap();
+        for batch in [events, changed] {
+            self.events.record(&batch).unwrap();
+            self.activity.record(&batch).unwrap();
+        }
+        id
+    }
+
+    async fn register(&self, token: &str, subject: &str) {
+        let registered_at = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let device = Device::new(token, PushPlatform::Fcm, subject, registered_at).unwrap();
+        self.devices.save(&device).await.unwrap();
+    }
+
+    async fn grant(&self, list: &TenantId, subject: &str, role: Role) {
+        let membership = Membership::new(list.clone(), subject, role);
+        self.memberships.save(&membership).await.unwrap();
+    }
+}
+
+fn tenant(id: &str) -> TenantId {
+    TenantId::new(id).unwrap()
+}
+
+#[tokio::test]
+async fn test_export_collects_only_the_users_data() {
+    // Arrange
+    let stores = Stores::new();
+    let (alice, bob) = (tenant("alice"), tenant("bob"));
+    let id = stores.add(&alice, "Write docs").await;
+    stores.add(&bob, "Review PR").await;
+    stores.register("alice-phone", "alice").await;
+    stores.register("bob-phone", "bob").await;
+    stores.grant(&alice, "alice", Role::Owner).await;
+    stores.grant(&bob, "alice", Role::Viewer).await;
+    stores.grant(&bob, "bob", Role::Owner).await;
+
+    // Act
+    let data = stores.handler().export("alice", &alice).await.unwrap();
+
+    // Assert
+    assert_eq!(data.subject.as_ref(), "alice");
+    assert_eq!(data.todos.len(), 1);
+    assert_eq!(data.todos[0].id.as_ref(), id);
+    assert_eq!(data.todos[0].state, TodoState::InProgress);
+    assert_eq!(data.events.len(), 2);
+    assert!(data.events.iter().all(|event| event.todo_id() == id));
+    assert_eq!(data.devices.len(), 1);
+    assert_eq!(data.devices[0].token.as_ref(), "alice-phone");
+    let mut lists: Vec<&str> = data.memberships.iter().map(|m| m.list.as_str()).collect();
+    lists.sort_unstable();
+    assert_eq!(lists, ["alice", "bob"]);
+}
+
+#[tokio::test]
+async fn test_erase_removes_the_users_data_everywhere() {
+    // Arrange
+    let stores = Stores::new();
+    let (alice, bob) = (tenant("alice"), tenant("bob"));
+    let id = stores.add(&alice, "Write docs").await;
+    stores.add(&alice, "Buy milk").await;
+    let kept = stores.add(&bob, "Review PR").await;
+    stores.register("alice-phone", "alice").await;
+    stores.register("bob-phone", "bob").await;
+    stores.grant(&alice, "alice", Role::Owner).await;
+    stores.grant(&bob, "alice", Role::Editor).await;
+    stores.grant(&bob, "bob", Role::Owner).await;
+    let handler = stores.handler();
+
+    // Act
+    let report = handler.erase("alice", &alice).await.unwrap();
+
+    // Assert
+    assert_eq!(
+        report,
+        ErasureReport {
+            todos: 2,
+            events: 4,
+            devices: 1,
+            memberships: 2,
+            activities: 4,
+        }
+    );
+    assert!(handler.export("alice", &alice).await.unwrap().is_empty());
+    assert!(stores.events.find_by_todo(&id).unwrap().is_empty());
+    let feed = stores.activity.page(None, 10);
+    assert!(
+        feed.activities
+            .iter()
+            .all(|activity| activity.todo_id() == kept)
+    );
+    assert_eq!(feed.activities.len(), 2);
+    let bob_data = handler.export("bob", &bob).await.unwrap();
+    assert_eq!(bob_data.todos.len(), 1);
+    assert_eq!(bob_data.events.len(), 2);
+    assert_eq!(bob_data.devices.len(), 1);
+    assert_eq!(bob_data.memberships.len(), 1);
+}
+
+#[tokio::test]
+async fn test_erase_finds_events_recorded_under_the_stored_id() {
+    // Arrange
+    let stores = Stores::new();
+    let alice = tenant("alice");
+    let id = stores.add(&alice, "Write docs").await;
+    let scoped: Arc<str> = format!("alice/{}", id).into();
+    stores
+        .events
+        .record(&[TodoEvent::TodoStateChanged {
+            id: scoped.clone(),
+            from_state: TodoState::InProgress,
+            to_state: TodoState::Done,
+            changed_at: Utc.with_ymd_and_hms(2025, 1, 1, 9, 0, 0).unwrap(),
+        }])
+        .unwrap();
+
+    // Act
+    let report = stores.handler().erase("alice", &alice).await.unwrap();
+
+    // Assert
+    assert_eq!(report.events, 3);
+    assert!(stores.events.find_by_todo(&scoped).unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_erase_without_optional_stores_only_deletes_todos() {
+    // Arrange
+    let stores = Stores::new();
+    let alice = tenant("alice");
+    let id = stores.add(&alice, "Write docs").await;
+    stores.register("alice-phone", "alice").await;
+    let handler = UserDataHandler::new(stores.todos.clone());
+
+    // Act
+    let report = handler.erase("alice", &alice).await.unwrap();
+
+    // Assert
+    assert_eq!(
+        report,
+        ErasureReport {
+            todos: 1,
+            ..ErasureReport::default()
+        }
+    );
+    assert!(stores.todos.find_all().await.unwrap().is_empty());
+    assert_eq!(stores.events.find_by_todo(&id).unwrap().len(), 2);
+    assert_eq!(
+        stores.devices.find_by_subject("alice").await.unwrap().len(),
+        1
+    );
+}
+
+#[cfg(feature = "serde")]
+#[tokio::test]
+async fn test_user_archive_holds_every_section() {
+    use todo::infrastructure::serialization::user_archive::{FORMAT, write_user_archive};
+
+    // Arrange
+    let stores = Stores::new();
+    let alice = tenant("alice");
+    stores.add(&alice, "Write docs").await;
+    stores.register("alice-phone", "alice").await;
+    stores.grant(&alice, "alice", Role::Owner).await;
+    let data = stores.handler().export("alice", &alice).await.unwrap();
+
+    // Act
+    let mut buffer = Vec::new();
+    write_user_archive(&mut buffer, &data).unwrap();
+
+    // Assert
+    let archive: serde_json::Value = serde_json::from_slice(&buffer).unwrap();
+    assert_eq!(archive["format"], FORMAT);
+    assert_eq!(archive["version"], 1);
+    assert_eq!(archive["subject"], "alice");
+    assert_eq!(archive["tenant"], "alice");
+    assert_eq!(archive["todos"][0]["description"], "Write docs");
+    assert_eq!(archive["events"].as_array().unwrap().len(), 2);
+    assert_eq!(archive["events"][0]["type"], "TODO_CREATED");
+    assert_eq!(archive["devices"][0]["token"], "alice-phone");
+    assert_eq!(archive["devices"][0]["platform"], "fcm");
+    assert_eq!(archive["memberships"][0]["list"], "alice");
+    assert_eq!(archive["memberships"][0]["role"], "owner");
+}
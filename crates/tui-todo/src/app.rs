@@ -0,0 +1,260 @@
+use futures::executor::block_on;
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use std::sync::Arc;
+use todo::application::add_todo_handler::AddTodoHandler;
+use todo::application::change_todo_state_handler::ChangeTodoStateHandler;
+use todo::application::delete_todo_handler::DeleteTodoHandler;
+use todo::application::get_todos_handler::GetTodosHandler;
+use todo::{Todo, TodoError, TodoRepository, TodoState};
+
+/// What keystrokes currently edit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputMode {
+    /// Keys navigate the list and run commands
+    Normal,
+    /// Keys edit the description of a new todo
+    Adding,
+    /// Keys edit the search text
+    Searching,
+}
+
+/// State of the terminal UI, driving the application handlers
+pub struct App {
+    add_todo_handler: AddTodoHandler<Arc<dyn TodoRepository>>,
+    get_todos_handler: GetTodosHandler<Arc<dyn TodoRepository>>,
+    change_todo_state_handler: ChangeTodoStateHandler<Arc<dyn TodoRepository>>,
+    delete_todo_handler: DeleteTodoHandler<Arc<dyn TodoRepository>>,
+    todos: Vec<Todo>,
+    selected: usize,
+    state_filter: Option<TodoState>,
+    search: String,
+    input: String,
+    mode: InputMode,
+    status: Option<String>,
+    should_quit: bool,
+}
+
+impl App {
+    /// Creates the app and loads the todos from `repository`
+    pub fn new(repository: Arc<dyn TodoRepository>) -> Self {
+        let mut app = App {
+            add_todo_handler: AddTodoHandler::new(repository.clone()),
+            get_todos_handler: GetTodosHandler::new(repository.clone()),
+            change_todo_state_handler: ChangeTodoStateHandler::new(repository.clone()),
+            delete_todo_handler: DeleteTodoHandler::new(repository),
+            todos: Vec::new(),
+            selected: 0,
+            state_filter: None,
+            search: String::new(),
+            input: String::new(),
+            mode: InputMode::Normal,
+            status: None,
+            should_quit: false,
+        };
+        app.refresh();
+        app
+    }
+
+    /// Reloads the todos from the repository, keeping the selected todo selected
+    pub fn refresh(&mut self) {
+        let selected_id = self.selected_todo().map(|todo| todo.id.clone());
+        match block_on(self.get_todos_handler.get_todos()) {
+            Ok(mut todos) => {
+                todos.sort_by_key(|todo| todo.created_at);
+                self.todos = todos;
+            }
+            Err(err) => self.status = Some(err.to_string()),
+        }
+        if let Some(id) = selected_id {
+            self.select_id(&id);
+        }
+        self.clamp_selection();
+    }
+
+    /// Todos matching the state filter and search text, oldest first
+    pub fn visible_todos(&self) -> Vec<&Todo> {
+        let search = self.search.to_lowercase();
+        self.todos
+            .iter()
+            .filter(|todo| self.state_filter.is_none_or(|state| todo.state == state))
+            .filter(|todo| todo.description.to_lowercase().contains(&search))
+            .collect()
+    }
+
+    pub fn selected_todo(&self) -> Option<&Todo> {
+        self.visible_todos().get(self.selected).copied()
+    }
+
+    /// Index of the selected todo within `visible_todos`
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn state_filter(&self) -> Option<TodoState> {
+        self.state_filter
+    }
+
+    pub fn search(&self) -> &str {
+        &self.search
+    }
+
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    pub fn mode(&self) -> InputMode {
+        self.mode
+    }
+
+    /// Result of the last command, or the last error
+    pub fn status(&self) -> Option<&str> {
+        self.status.as_deref()
+    }
+
+    pub fn should_quit(&self) -> bool {
+        self.should_quit
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        match self.mode {
+            InputMode::Normal => self.handle_normal_key(key.code),
+            InputMode::Adding => self.handle_adding_key(key.code),
+            InputMode::Searching => self.handle_searching_key(key.code),
+        }
+    }
+
+    fn handle_normal_key(&mut self, code: KeyCode) {
+        self.status = None;
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => self.should_quit = true,
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            KeyCode::Char('a') => {
+                self.input.clear();
+                self.mode = InputMode::Adding;
+            }
+            KeyCode::Char('/') => self.mode = InputMode::Searching,
+            KeyCode::Right | KeyCode::Char('l') | KeyCode::Enter => {
+                self.change_selected_state(true)
+            }
+            KeyCode::Left | KeyCode::Char('h') => self.change_selected_state(false),
+            KeyCode::Char('d') => self.delete_selected(),
+            KeyCode::Char('f') => self.cycle_state_filter(),
+            KeyCode::Char('r') => self.refresh(),
+            _ => {}
+        }
+    }
+
+    fn handle_adding_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Enter => {
+                let description = std::mem::take(&mut self.input);
+                self.mode = InputMode::Normal;
+                match block_on(self.add_todo_handler.new_todo(description)) {
+                    Ok(_) => {
+                        self.refresh();
+                        self.status = Some("Todo added".to_string());
+                    }
+                    Err(err) => self.status = Some(err.to_string()),
+                }
+            }
+            KeyCode::Esc => {
+                self.input.clear();
+                self.mode = InputMode::Normal;
+            }
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Char(c) => self.input.push(c),
+            _ => {}
+        }
+    }
+
+    fn handle_searching_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Enter => self.mode = InputMode::Normal,
+            KeyCode::Esc => {
+                self.search.clear();
+                self.mode = InputMode::Normal;
+            }
+            KeyCode::Backspace => {
+                self.search.pop();
+            }
+            KeyCode::Char(c) => self.search.push(c),
+            _ => {}
+        }
+        self.clamp_selection();
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let len = self.visible_todos().len();
+        if len > 0 {
+            self.selected = self.selected.saturating_add_signed(delta).min(len - 1);
+        }
+    }
+
+    fn change_selected_state(&mut self, forward: bool) {
+        let Some(todo) = self.selected_todo() else {
+            return;
+        };
+        let new_state = match (todo.state, forward) {
+            (TodoState::Todo, true) => Some(TodoState::InProgress),
+            (TodoState::InProgress, true) => Some(TodoState::Done),
+            (TodoState::InProgress, false) => Some(TodoState::Todo),
+            (TodoState::Done, false) => Some(TodoState::InProgress),
+            _ => None,
+        };
+        let id = todo.id.to_string();
+        let result = match new_state {
+            Some(new_state) => block_on(
+                self.change_todo_state_handler
+                    .change_state(id.clone(), new_state),
+            ),
+            None => Err(TodoError::InvalidStateTransition),
+        };
+        match result {
+            Ok(_) => {
+                self.refresh();
+                self.select_id(&id);
+                self.clamp_selection();
+            }
+            Err(err) => self.status = Some(err.to_string()),
+        }
+    }
+
+    fn delete_selected(&mut self) {
+        let Some(id) = self.selected_todo().map(|todo| todo.id.to_string()) else {
+            return;
+        };
+        match block_on(self.delete_todo_handler.delete_todo(id)) {
+            Ok(_) => {
+                self.refresh();
+                self.status = Some("Todo deleted".to_string());
+            }
+            Err(err) => self.status = Some(err.to_string()),
+        }
+    }
+
+    /// All → TODO → IN_PROGRESS → DONE → All
+    fn cycle_state_filter(&mut self) {
+        self.state_filter = match self.state_filter {
+            None => Some(TodoState::Todo),
+            Some(TodoState::Todo) => Some(TodoState::InProgress),
+            Some(TodoState::InProgress) => Some(TodoState::Done),
+            Some(TodoState::Done) => None,
+        };
+        self.selected = 0;
+    }
+
+    fn select_id(&mut self, id: &str) {
+        if let Some(index) = self.visible_todos().iter().position(|todo| &*todo.id == id) {
+            self.selected = index;
+        }
+    }
+
+    fn clamp_selection(&mut self) {
+        self.selected = self
+            .selected
+            .min(self.visible_todos().len().saturating_sub(1));
+    }
+}
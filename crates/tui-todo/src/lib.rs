@@ -0,0 +1,4 @@
+pub mod app;
+pub mod ui;
+
+pub use app::{App, InputMode};
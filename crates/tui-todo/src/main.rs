@@ -0,0 +1,53 @@
+use ratatui::DefaultTerminal;
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use todo::infrastructure::repositories::todo::FileTodoRepository;
+use tui_todo::{App, ui};
+
+/// How often the todos are reloaded, picking up changes made by other processes
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The file given as the first argument, `HK_TODO_FILE`, or `$HOME/.hk-todo.json`
+fn todo_file() -> PathBuf {
+    std::env::args_os()
+        .nth(1)
+        .or_else(|| std::env::var_os("HK_TODO_FILE"))
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            std::env::var_os("HOME")
+                .map(PathBuf::from)
+                .unwrap_or_default()
+                .join(".hk-todo.json")
+        })
+}
+
+fn run(terminal: &mut DefaultTerminal, app: &mut App) -> io::Result<()> {
+    let mut last_refresh = Instant::now();
+    while !app.should_quit() {
+        terminal.draw(|frame| ui::draw(frame, app))?;
+
+        if event::poll(Duration::from_millis(250))?
+            && let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            app.handle_key(key);
+        }
+
+        if last_refresh.elapsed() >= REFRESH_INTERVAL {
+            app.refresh();
+            last_refresh = Instant::now();
+        }
+    }
+    Ok(())
+}
+
+fn main() -> io::Result<()> {
+    let mut app = App::new(Arc::new(FileTodoRepository::new(todo_file())));
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal, &mut app);
+    ratatui::restore();
+    result
+}
@@ -0,0 +1,86 @@
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph};
+use todo::TodoState;
+
+use crate::app::{App, InputMode};
+
+const HELP: &str = "j/k move  l/enter advance  h back  a add  d delete  f filter  / search  q quit";
+
+pub fn draw(frame: &mut Frame, app: &App) {
+    let [header, list, footer] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(1),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    frame.render_widget(Paragraph::new(header_line(app)), header);
+
+    let todos = app.visible_todos();
+    let items: Vec<ListItem> = todos
+        .iter()
+        .map(|todo| {
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("[{}] ", state_name(todo.state)),
+                    state_style(todo.state),
+                ),
+                Span::raw(&*todo.description),
+            ]))
+        })
+        .collect();
+    let title = format!(" Todos ({}) ", todos.len());
+    let list_widget = List::new(items)
+        .block(Block::bordered().title(title))
+        .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+    let mut state =
+        ListState::default().with_selected((!todos.is_empty()).then_some(app.selected()));
+    frame.render_stateful_widget(list_widget, list, &mut state);
+
+    frame.render_widget(Paragraph::new(footer_line(app)), footer);
+}
+
+fn header_line(app: &App) -> Line<'_> {
+    let filter = app.state_filter().map_or("ALL", state_name);
+    let mut spans = vec![
+        Span::raw("hk-todo  "),
+        Span::styled(
+            format!("state: {}", filter),
+            Style::new().add_modifier(Modifier::BOLD),
+        ),
+    ];
+    if !app.search().is_empty() {
+        spans.push(Span::raw(format!("  search: {}", app.search())));
+    }
+    Line::from(spans)
+}
+
+fn footer_line(app: &App) -> Line<'_> {
+    match app.mode() {
+        InputMode::Adding => Line::from(format!("New todo: {}_", app.input())),
+        InputMode::Searching => Line::from(format!("Search: {}_", app.search())),
+        InputMode::Normal => match app.status() {
+            Some(status) => Line::styled(status, Style::new().fg(Color::Yellow)),
+            None => Line::styled(HELP, Style::new().fg(Color::DarkGray)),
+        },
+    }
+}
+
+fn state_name(state: TodoState) -> &'static str {
+    match state {
+        TodoState::Todo => "TODO",
+        TodoState::InProgress => "IN_PROGRESS",
+        TodoState::Done => "DONE",
+    }
+}
+
+fn state_style(state: TodoState) -> Style {
+    match state {
+        TodoState::Todo => Style::new().fg(Color::Blue),
+        TodoState::InProgress => Style::new().fg(Color::Yellow),
+        TodoState::Done => Style::new().fg(Color::Green),
+    }
+}
@@ -0,0 +1,83 @@
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use std::sync::Arc;
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::{TodoRepository, TodoState};
+use tui_todo::{App, InputMode, ui};
+
+fn press(app: &mut App, codes: &[KeyCode]) {
+    for code in codes {
+        app.handle_key(KeyEvent::from(*code));
+    }
+}
+
+fn type_text(app: &mut App, text: &str) {
+    for c in text.chars() {
+        app.handle_key(KeyEvent::from(KeyCode::Char(c)));
+    }
+}
+
+#[test]
+fn test_add_and_advance_todo_with_keys() {
+    // Arrange
+    let repository = Arc::new(InMemoryTodoRepository::new());
+    let mut app = App::new(repository.clone());
+
+    // Act
+    press(&mut app, &[KeyCode::Char('a')]);
+    assert_eq!(app.mode(), InputMode::Adding);
+    type_text(&mut app, "Write docs");
+    press(&mut app, &[KeyCode::Enter, KeyCode::Char('l')]);
+
+    // Assert
+    let todos = futures::executor::block_on(repository.find_all()).unwrap();
+    assert_eq!(todos.len(), 1);
+    assert_eq!(&*todos[0].description, "Write docs");
+    assert_eq!(todos[0].state, TodoState::InProgress);
+    assert_eq!(app.selected_todo().unwrap().state, TodoState::InProgress);
+}
+
+#[test]
+fn test_filter_search_and_delete() {
+    // Arrange
+    let mut app = App::new(Arc::new(InMemoryTodoRepository::new()));
+    for description in ["Buy milk", "Write docs"] {
+        press(&mut app, &[KeyCode::Char('a')]);
+        type_text(&mut app, description);
+        press(&mut app, &[KeyCode::Enter]);
+    }
+
+    // Act & Assert
+    press(&mut app, &[KeyCode::Char('/')]);
+    type_text(&mut app, "DOCS");
+    press(&mut app, &[KeyCode::Enter]);
+    assert_eq!(app.visible_todos().len(), 1);
+    assert_eq!(&*app.selected_todo().unwrap().description, "Write docs");
+
+    press(&mut app, &[KeyCode::Char('l'), KeyCode::Char('f')]);
+    assert_eq!(app.state_filter(), Some(TodoState::Todo));
+    assert!(app.visible_todos().is_empty());
+
+    press(&mut app, &[KeyCode::Char('f'), KeyCode::Char('d')]);
+    assert_eq!(app.state_filter(), Some(TodoState::InProgress));
+    assert!(app.visible_todos().is_empty());
+    assert_eq!(app.status(), Some("Todo deleted"));
+}
+
+#[test]
+fn test_errors_are_shown_in_the_status_line() {
+    // Arrange
+    let mut app = App::new(Arc::new(InMemoryTodoRepository::new()));
+    let mut terminal = Terminal::new(TestBackend::new(60, 5)).unwrap();
+
+    // Act
+    press(&mut app, &[KeyCode::Char('a'), KeyCode::Enter]);
+    terminal.draw(|frame| ui::draw(frame, &app)).unwrap();
+
+    // Assert
+    assert_eq!(app.status(), Some("todo description must not be empty"));
+    let buffer = terminal.backend().buffer();
+    let footer: String = (0..60).map(|x| buffer[(x, 4)].symbol()).collect();
+    assert!(footer.starts_with("todo description must not be empty"));
+}
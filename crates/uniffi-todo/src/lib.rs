@@ -0,0 +1,416 @@
+use futures::executor::block_on;
+use std::sync::Arc;
+use std::time::SystemTime;
+use todo::application::add_todo_handler::AddTodoHandler;
+use todo::application::change_todo_state_handler::ChangeTodoStateHandler;
+use todo::application::delete_todo_handler::DeleteTodoHandler;
+use todo::application::get_todos_handler::GetTodosHandler;
+use todo::infrastructure::repositories::todo::InMemoryTodoRepository;
+use todo::{Priority, TodoError, TodoRepository, TodoState};
+
+uniffi::include_scaffolding!("todo");
+
+/// Todo record handed to Swift and Kotlin
+#[derive(Debug, Clone, PartialEq)]
+pub struct Todo {
+    pub id: String,
+    pub description: String,
+    pub state: TodoState,
+    pub created_at: SystemTime,
+    pub due_at: Option<SystemTime>,
+    pub priority: Priority,
+    pub tags: Vec<String>,
+    pub subtasks: Vec<Subtask>,
+    pub blocked_by: Vec<String>,
+    pub attachments: Vec<Attachment>,
+}
+
+impl From<&todo::Todo> for Todo {
+    fn from(todo: &todo::Todo) -> Self {
+        Todo {
+            id: todo.id.to_string(),
+            description: todo.description.to_string(),
+            state: todo.state,
+            created_at: todo.created_at.into(),
+            due_at: todo.due_at.map(Into::into),
+            priority: todo.priority,
+            tags: todo.tags.iter().map(ToString::to_string).collect(),
+            subtasks: todo.subtasks.iter().map(Subtask::from).collect(),
+            blocked_by: todo.blocked_by.iter().map(ToString::to_string).collect(),
+            attachments: todo.attachments.iter().map(Attachment::from).collect(),
+        }
+    }
+}
+
+/// Checklist item of a todo handed to Swift and Kotlin
+#[derive(Debug, Clone, PartialEq)]
+pub struct Subtask {
+    pub id: String,
+    pub description: String,
+    pub done: bool,
+}
+
+impl From<&todo::Subtask> for Subtask {
+    fn from(subtask: &todo::Subtask) -> Self {
+        Subtask {
+            id: subtask.id.to_string(),
+            description: subtask.description.to_string(),
+            done: subtask.done,
+        }
+    }
+}
+
+/// Metadata of a file attached to a todo handed to Swift and Kotlin
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attachment {
+    pub content_hash: String,
+    pub name: String,
+    pub media_type: String,
+    pub size: u64,
+}
+
+impl From<&todo::Attachment> for Attachment {
+    fn from(attachment: &todo::Attachment) -> Self {
+        Attachment {
+            content_hash: attachment.content_hash.to_string(),
+            name: attachment.name.to_string(),
+            media_type: attachment.media_type.to_string(),
+            size: attachment.size,
+        }
+    }
+}
+
+/// Domain event handed to Swift and Kotlin
+#[derive(Debug, Clone, PartialEq)]
+pub enum TodoEvent {
+    TodoCreated {
+        id: String,
+        description: String,
+        created_at: SystemTime,
+    },
+    TodoStateChanged {
+        id: String,
+        from_state: TodoState,
+        to_state: TodoState,
+        changed_at: SystemTime,
+    },
+    TodoArchived {
+        id: String,
+        archived_at: SystemTime,
+    },
+    TodoStale {
+        id: String,
+        rule: String,
+        state: TodoState,
+        stale_at: SystemTime,
+    },
+    TodoDueDateChanged {
+        id: String,
+        due_at: Option<SystemTime>,
+        changed_at: SystemTime,
+    },
+    TodoPriorityChanged {
+        id: String,
+        from_priority: Priority,
+        to_priority: Priority,
+        changed_at: SystemTime,
+    },
+    TodoTagAdded {
+        id: String,
+        tag: String,
+        added_at: SystemTime,
+    },
+    TodoTagRemoved {
+        id: String,
+        tag: String,
+        removed_at: SystemTime,
+    },
+    TodoSubtaskAdded {
+        id: String,
+        subtask_id: String,
+        description: String,
+        added_at: SystemTime,
+    },
+    TodoSubtaskToggled {
+        id: String,
+        subtask_id: String,
+        done: bool,
+        toggled_at: SystemTime,
+    },
+    TodoSubtaskRemoved {
+        id: String,
+        subtask_id: String,
+        removed_at: SystemTime,
+    },
+    TodoDescriptionUpdated {
+        id: String,
+        old: String,
+        new: String,
+        updated_at: SystemTime,
+    },
+    TodoBlocked {
+        id: String,
+        blocker_id: String,
+        blocked_at: SystemTime,
+    },
+    TodoUnblocked {
+        id: String,
+        blocker_id: String,
+        unblocked_at: SystemTime,
+    },
+    TodoDeleted {
+        id: String,
+        deleted_at: SystemTime,
+    },
+    TodoRestored {
+        id: String,
+        restored_at: SystemTime,
+    },
+    TodoAttachmentAdded {
+        id: String,
+        content_hash: String,
+        name: String,
+        media_type: String,
+        size: u64,
+        added_at: SystemTime,
+    },
+    TodoAttachmentRemoved {
+        id: String,
+        content_hash: String,
+        removed_at: SystemTime,
+    },
+}
+
+impl From<todo::TodoEvent> for TodoEvent {
+    fn from(event: todo::TodoEvent) -> Self {
+        match event {
+            todo::TodoEvent::TodoCreated {
+                id,
+                description,
+                created_at,
+            } => TodoEvent::TodoCreated {
+                id: id.to_string(),
+                description: description.to_string(),
+                created_at: created_at.into(),
+            },
+            todo::TodoEvent::TodoStateChanged {
+                id,
+                from_state,
+                to_state,
+                changed_at,
+            } => TodoEvent::TodoStateChanged {
+                id: id.to_string(),
+                from_state,
+                to_state,
+                changed_at: changed_at.into(),
+            },
+            todo::TodoEvent::TodoArchived { id, archived_at } => TodoEvent::TodoArchived {
+                id: id.to_string(),
+                archived_at: archived_at.into(),
+            },
+            todo::TodoEvent::TodoStale {
+                id,
+                rule,
+                state,
+                stale_at,
+            } => TodoEvent::TodoStale {
+                id: id.to_string(),
+                rule: rule.to_string(),
+                state,
+                stale_at: stale_at.into(),
+            },
+            todo::TodoEvent::TodoDueDateChanged {
+                id,
+                due_at,
+                changed_at,
+            } => TodoEvent::TodoDueDateChanged {
+                id: id.to_string(),
+                due_at: due_at.map(Into::into),
+                changed_at: changed_at.into(),
+            },
+            todo::TodoEvent::TodoPriorityChanged {
+                id,
+                from_priority,
+                to_priority,
+                changed_at,
+            } => TodoEvent::TodoPriorityChanged {
+                id: id.to_string(),
+                from_priority,
+                to_priority,
+                changed_at: changed_at.into(),
+            },
+            todo::TodoEvent::TodoTagAdded { id, tag, added_at } => TodoEvent::TodoTagAdded {
+                id: id.to_string(),
+                tag: tag.to_string(),
+                added_at: added_at.into(),
+            },
+            todo::TodoEvent::TodoTagRemoved {
+                id,
+                tag,
+                removed_at,
+            } => TodoEvent::TodoTagRemoved {
+                id: id.to_string(),
+                tag: tag.to_string(),
+                removed_at: removed_at.into(),
+            },
+            todo::TodoEvent::TodoSubtaskAdded {
+                id,
+                subtask_id,
+                description,
+                added_at,
+            } => TodoEvent::TodoSubtaskAdded {
+                id: id.to_string(),
+                subtask_id: subtask_id.to_string(),
+                description: description.to_string(),
+                added_at: added_at.into(),
+            },
+            todo::TodoEvent::TodoSubtaskToggled {
+                id,
+                subtask_id,
+                done,
+                toggled_at,
+            } => TodoEvent::TodoSubtaskToggled {
+                id: id.to_string(),
+                subtask_id: subtask_id.to_string(),
+                done,
+                toggled_at: toggled_at.into(),
+            },
+            todo::TodoEvent::TodoSubtaskRemoved {
+                id,
+                subtask_id,
+                removed_at,
+            } => TodoEvent::TodoSubtaskRemoved {
+                id: id.to_string(),
+                subtask_id: subtask_id.to_string(),
+                removed_at: removed_at.into(),
+            },
+            todo::TodoEvent::TodoDescriptionUpdated {
+                id,
+                old,
+                new,
+                updated_at,
+            } => TodoEvent::TodoDescriptionUpdated {
+                id: id.to_string(),
+                old: old.to_string(),
+                new: new.to_string(),
+                updated_at: updated_at.into(),
+            },
+            todo::TodoEvent::TodoBlocked {
+                id,
+                blocker_id,
+                blocked_at,
+            } => TodoEvent::TodoBlocked {
+                id: id.to_string(),
+                blocker_id: blocker_id.to_string(),
+                blocked_at: blocked_at.into(),
+            },
+            todo::TodoEvent::TodoUnblocked {
+                id,
+                blocker_id,
+                unblocked_at,
+            } => TodoEvent::TodoUnblocked {
+                id: id.to_string(),
+                blocker_id: blocker_id.to_string(),
+                unblocked_at: unblocked_at.into(),
+            },
+            todo::TodoEvent::TodoDeleted { id, deleted_at } => TodoEvent::TodoDeleted {
+                id: id.to_string(),
+                deleted_at: deleted_at.into(),
+            },
+            todo::TodoEvent::TodoRestored { id, restored_at } => TodoEvent::TodoRestored {
+                id: id.to_string(),
+                restored_at: restored_at.into(),
+            },
+            todo::TodoEvent::TodoAttachmentAdded {
+                id,
+                content_hash,
+                name,
+                media_type,
+                size,
+                added_at,
+            } => TodoEvent::TodoAttachmentAdded {
+                id: id.to_string(),
+                content_hash: content_hash.to_string(),
+                name: name.to_string(),
+                media_type: media_type.to_string(),
+                size,
+                added_at: added_at.into(),
+            },
+            todo::TodoEvent::TodoAttachmentRemoved {
+                id,
+                content_hash,
+                removed_at,
+            } => TodoEvent::TodoAttachmentRemoved {
+                id: id.to_string(),
+                content_hash: content_hash.to_string(),
+                removed_at: removed_at.into(),
+            },
+        }
+    }
+}
+
+/// A todo as saved by a change, with the events the change emitted, so apps can show it
+/// without reading it back
+#[derive(Debug, Clone, PartialEq)]
+pub struct TodoChange {
+    pub todo: Todo,
+    pub events: Vec<TodoEvent>,
+}
+
+impl From<(todo::Todo, Vec<todo::TodoEvent>)> for TodoChange {
+    fn from((todo, events): (todo::Todo, Vec<todo::TodoEvent>)) -> Self {
+        TodoChange {
+            todo: Todo::from(&todo),
+            events: events.into_iter().map(TodoEvent::from).collect(),
+        }
+    }
+}
+
+/// Application handlers sharing one in-memory repository
+pub struct TodoService {
+    add_todo_handler: AddTodoHandler<Arc<dyn TodoRepository>>,
+    get_todos_handler: GetTodosHandler<Arc<dyn TodoRepository>>,
+    change_todo_state_handler: ChangeTodoStateHandler<Arc<dyn TodoRepository>>,
+    delete_todo_handler: DeleteTodoHandler<Arc<dyn TodoRepository>>,
+}
+
+impl Default for TodoService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TodoService {
+    pub fn new() -> Self {
+        Self::from_repository(Arc::new(InMemoryTodoRepository::new()))
+    }
+
+    pub fn from_repository(repository: Arc<dyn TodoRepository>) -> Self {
+        Self {
+            add_todo_handler: AddTodoHandler::new(repository.clone()),
+            get_todos_handler: GetTodosHandler::new(repository.clone()),
+            change_todo_state_handler: ChangeTodoStateHandler::new(repository.clone()),
+            delete_todo_handler: DeleteTodoHandler::new(repository),
+        }
+    }
+
+    pub fn add_todo(&self, description: String) -> Result<TodoChange, TodoError> {
+        let change = block_on(self.add_todo_handler.new_todo(description))?;
+        Ok(TodoChange::from(change))
+    }
+
+    pub fn get_todos(&self) -> Result<Vec<Todo>, TodoError> {
+        let todos = block_on(self.get_todos_handler.get_todos())?;
+        Ok(todos.iter().map(Todo::from).collect())
+    }
+
+    pub fn change_state(&self, id: String, new_state: TodoState) -> Result<TodoChange, TodoError> {
+        let change = block_on(self.change_todo_state_handler.change_state(id, new_state))?;
+        Ok(TodoChange::from(change))
+    }
+
+    pub fn delete_todo(&self, id: String) -> Result<(), TodoError> {
+        block_on(self.delete_todo_handler.delete_todo(id))?;
+        Ok(())
+    }
+}
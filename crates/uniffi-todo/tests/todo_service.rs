@@ -0,0 +1,60 @@
+use todo::{TodoError, TodoState};
+use uniffi_todo::{TodoEvent, TodoService};
+
+#[test]
+fn test_add_change_and_delete_todo() {
+    // Arrange
+    let service = TodoService::new();
+
+    // Act
+    let created = service.add_todo("Test todo".to_string()).unwrap();
+    let TodoEvent::TodoCreated { id, .. } = &created.events[0] else {
+        panic!("Expected TodoCreated event");
+    };
+    let changed = service
+        .change_state(id.clone(), TodoState::InProgress)
+        .unwrap();
+    let todos = service.get_todos().unwrap();
+    service.delete_todo(id.clone()).unwrap();
+
+    // Assert
+    assert_eq!(&created.todo.id, id);
+    assert_eq!(created.todo.state, TodoState::Todo);
+    assert_eq!(changed.todo.state, TodoState::InProgress);
+    assert!(matches!(
+        &changed.events[0],
+        TodoEvent::TodoStateChanged {
+            from_state: TodoState::Todo,
+            to_state: TodoState::InProgress,
+            ..
+        }
+    ));
+    assert_eq!(todos.len(), 1);
+    assert_eq!(todos[0].description, "Test todo");
+    assert_eq!(todos[0].state, TodoState::InProgress);
+    assert!(service.get_todos().unwrap().is_empty());
+}
+
+#[test]
+fn test_errors_are_returned_to_the_caller() {
+    // Arrange
+    let service = TodoService::new();
+
+    // Act & Assert
+    assert_eq!(
+        service.add_todo("".to_string()).unwrap_err(),
+        TodoError::EmptyDescription
+    );
+    assert_eq!(
+        service
+            .change_state("non-existent-id".to_string(), TodoState::Done)
+            .unwrap_err(),
+        TodoError::TodoNotFound
+    );
+    assert_eq!(
+        service
+            .delete_todo("non-existent-id".to_string())
+            .unwrap_err(),
+        TodoError::TodoNotFound
+    );
+}